@@ -6,7 +6,7 @@
 
 use devtools::hex::random_hex;
 use lightcryptotools::bigint::BigInt;
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::ecdsa;
 use lightcryptotools::crypto::ecdsa::SigningOptions;
 use lightcryptotools::crypto::secp256k1;
@@ -22,7 +22,7 @@ fn secp256k1_signing_and_verifying(bench: &mut Bencher) {
     let public_key = private_key.public_key();
 
     let hash_bytes_len = 32;
-    let hash_bytes = hex_to_bytes(random_hex(hash_bytes_len * 2)).unwrap();
+    let hash_bytes = hex::decode(random_hex(hash_bytes_len * 2)).unwrap();
 
     bench.iter(|| {
         let (signature, _) = ecdsa::sign_with_options(
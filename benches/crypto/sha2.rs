@@ -5,7 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use devtools::hex::random_hex;
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::hash::{Sha256, Sha384, Sha512, UnkeyedHash};
 use test::Bencher;
 
@@ -13,7 +13,7 @@ const HASH_BYTE_LEN: usize = 4096;
 
 #[bench]
 fn sha256(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha256::new().digest(&bytes);
@@ -22,7 +22,7 @@ fn sha256(bench: &mut Bencher) {
 
 #[bench]
 fn sha384(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha384::new().digest(&bytes);
@@ -31,7 +31,7 @@ fn sha384(bench: &mut Bencher) {
 
 #[bench]
 fn sha512(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha512::new().digest(&bytes);
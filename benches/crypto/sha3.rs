@@ -5,7 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use devtools::hex::random_hex;
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::hash::{Sha3_256, Sha3_384, Sha3_512, UnkeyedHash};
 use test::Bencher;
 
@@ -13,7 +13,7 @@ const HASH_BYTE_LEN: usize = 4096;
 
 #[bench]
 fn sha3_256(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha3_256::new().digest(&bytes);
@@ -22,7 +22,7 @@ fn sha3_256(bench: &mut Bencher) {
 
 #[bench]
 fn sha3_384(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha3_384::new().digest(&bytes);
@@ -31,7 +31,7 @@ fn sha3_384(bench: &mut Bencher) {
 
 #[bench]
 fn sha3_512(bench: &mut Bencher) {
-    let bytes = hex_to_bytes(random_hex(HASH_BYTE_LEN * 2)).unwrap();
+    let bytes = hex::decode(random_hex(HASH_BYTE_LEN * 2)).unwrap();
 
     bench.iter(|| {
         Sha3_512::new().digest(&bytes);
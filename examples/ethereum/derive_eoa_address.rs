@@ -0,0 +1,53 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Derives EOA public addresses from fixed private keys and a freshly generated one.
+//!
+//! # Examples
+//!
+//! cargo run --example derive_eoa_address
+
+use lightcryptotools::blockchain::ethereum::account::EoaPrivateKey;
+
+// Test vectors from "ethereum/tests":
+// https://github.com/ethereum/tests/blob/develop/BasicTests/keyaddrtest.json
+const KEYS_AND_ADDRESSES: [(&str, &str); 2] = [
+    (
+        "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+        "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+    ),
+    (
+        "c87f65ff3f271bf5dc8643484f66b200109caffe4bf98c4cb393dc35740b28c0",
+        "0x13978aee95f38490e9769C39B2773Ed763d9cd5F",
+    ),
+];
+
+fn main() {
+    for (key_hex, _) in KEYS_AND_ADDRESSES {
+        println!("{key_hex} -> {}", derive_address(key_hex));
+    }
+
+    let private_key = EoaPrivateKey::generate().expect("failed to generate private key");
+    let key_hex = private_key.to_padded_hex();
+    println!("{key_hex} -> {}", private_key.public_key().address());
+}
+
+fn derive_address(key_hex: &str) -> String {
+    let private_key = EoaPrivateKey::from_any_hex(key_hex).expect("invalid private key");
+    private_key.public_key().address().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_address() {
+        for (key_hex, address) in KEYS_AND_ADDRESSES {
+            assert_eq!(derive_address(key_hex), address);
+        }
+    }
+}
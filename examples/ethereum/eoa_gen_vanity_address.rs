@@ -14,7 +14,7 @@
 use lightcryptotools::blockchain::ethereum::account::{
     EoaPrivateKey, EoaPrivateKeyData, EOA_PRIVATE_KEY_DATA_BYTE_LENGTH,
 };
-use lightcryptotools::crypto::codecs::bytes_to_lower_hex;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::random::generator::get_os_random_bytes;
 use std::time::Instant;
 
@@ -65,7 +65,7 @@ fn main() {
         println!("Vanity Address Found!");
         println!("=====================");
         println!("public address: 0x{checksummed_hex}");
-        println!("private key: 0x{}", bytes_to_lower_hex(&key_data));
+        println!("private key: 0x{}", hex::encode_lower(&key_data));
         break;
     }
 }
@@ -11,20 +11,24 @@
 //! cargo run --example eoa_key_to_address -- c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4
 //! cargo run --example eoa_key_to_address -- 0xc85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4
 
-use lightcryptotools::blockchain::ethereum::account::{EoaPrivateKey, EoaPrivateKeyData};
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::blockchain::ethereum::account::EoaPrivateKey;
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     let key_hex = std::env::args()
         .nth(1)
         .expect("Error: the parameter is missing");
-    let key_hex = key_hex.strip_prefix("0x").unwrap_or(&key_hex);
 
-    let key_bytes = hex_to_bytes(key_hex).expect("invalid key hex");
-    let key_data: EoaPrivateKeyData = key_bytes.try_into().expect("invalid key data");
-    let eoa_private_key = EoaPrivateKey::new(key_data).expect("invalid private key");
+    let eoa_private_key = match EoaPrivateKey::from_any_hex(&key_hex) {
+        Ok(private_key) => private_key,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
     let eoa_public_key = eoa_private_key.public_key();
     let address = eoa_public_key.address();
 
     println!("{address}");
+    ExitCode::SUCCESS
 }
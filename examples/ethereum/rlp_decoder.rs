@@ -26,8 +26,8 @@
 use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use lightcryptotools::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use lightcryptotools::blockchain::ethereum::rlp::RlpItemType;
-use lightcryptotools::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
-use lightcryptotools::tools::codable::{decode, Decodable};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::tools::codable::DecodingItem;
 use serde_json::{to_string_pretty, Value};
 
 fn main() {
@@ -36,40 +36,38 @@ fn main() {
         .expect("Error: the parameter is missing");
     let rlp_hex = rlp_hex.strip_prefix("0x").unwrap_or(&rlp_hex);
 
-    let rlp_data = match hex_to_bytes(rlp_hex) {
+    let rlp_data = match hex::decode(rlp_hex) {
         Ok(data) => data,
         Err(err) => {
             println!("invalid hex input: {err}");
             return;
         }
     };
-    let json_value_wrapper: JsonValueWrapper = match decode(&rlp_data) {
-        Ok(value) => value,
-        Err(err) => {
-            println!("Decoding failed: {err}");
-            return;
-        }
-    };
-    println!("{}", to_string_pretty(&json_value_wrapper.0).unwrap());
-}
-
-struct JsonValueWrapper(Value);
-
-impl<'a> Decodable<'a, RlpDecodingItem<'a>> for JsonValueWrapper {
-    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
-        return match decoding_item.item_type {
-            RlpItemType::SingleValue => {
-                let bytes = decoding_item.decode_as_bytes()?;
-                Ok(JsonValueWrapper(Value::String(
-                    "0x".to_owned() + &bytes_to_lower_hex(bytes),
-                )))
-            }
-            RlpItemType::List => {
-                let values = Vec::<Self>::decode_from(decoding_item)?;
-                Ok(Self(Value::Array(
-                    values.into_iter().map(|t| t.0).collect(),
-                )))
+    let json_value =
+        match RlpDecodingItem::new_from_data(&rlp_data).and_then(|item| to_json(&item)) {
+            Ok(value) => value,
+            Err(err) => {
+                println!("Decoding failed: {err}");
+                return;
             }
         };
+    println!("{}", to_string_pretty(&json_value).unwrap());
+}
+
+// Walks the items directly: `Decodable` is sealed, so the example can't implement it.
+fn to_json(decoding_item: &RlpDecodingItem) -> Result<Value, RlpDataDecodingError> {
+    match decoding_item.item_type {
+        RlpItemType::SingleValue => {
+            let bytes = decoding_item.decode_as_bytes()?;
+            Ok(Value::String("0x".to_owned() + &hex::encode_lower(bytes)))
+        }
+        RlpItemType::List => {
+            let values = decoding_item
+                .decode_as_items()?
+                .iter()
+                .map(to_json)
+                .collect::<Result<_, _>>()?;
+            Ok(Value::Array(values))
+        }
     }
 }
@@ -0,0 +1,138 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Builds and signs an EIP-1559 transaction, then prints the raw transaction hex and
+//! the sender recovered from the signature.
+//!
+//! Signs with a fixed private key first, and then with a freshly generated one.
+//!
+//! # Examples
+//!
+//! cargo run --example sign_eip1559_transaction
+
+use lightcryptotools::blockchain::ethereum::account::{EoaPrivateKey, EoaPrivateKeyData};
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip1559,
+};
+use lightcryptotools::blockchain::ethereum::types::{AccessList, AccessListItem};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::SigningOptions;
+
+const PRIVATE_KEY_HEX: &str =
+    "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d";
+
+fn main() {
+    let private_key = fixed_private_key();
+    let transaction = sign_transaction(&private_key, false);
+    println!("address: {}", private_key.public_key().address());
+    println!(
+        "raw transaction: 0x{}",
+        hex::encode_lower(&transaction.encode())
+    );
+    println!(
+        "recovered sender: {}",
+        transaction.recover_sender().unwrap()
+    );
+
+    println!();
+
+    let private_key = EoaPrivateKey::generate().expect("failed to generate private key");
+    let transaction = sign_transaction(&private_key, true);
+    println!("generated address: {}", private_key.public_key().address());
+    println!(
+        "raw transaction: 0x{}",
+        hex::encode_lower(&transaction.encode())
+    );
+    println!(
+        "recovered sender: {}",
+        transaction.recover_sender().unwrap()
+    );
+}
+
+fn fixed_private_key() -> EoaPrivateKey<'static> {
+    let key_data: EoaPrivateKeyData = hex::decode(PRIVATE_KEY_HEX).unwrap().try_into().unwrap();
+    EoaPrivateKey::new(key_data).unwrap()
+}
+
+/// Signs a sample transaction with `private_key`.
+///
+/// The signing is deterministic (RFC 6979) if `employ_extra_random_data` is `false`.
+fn sign_transaction(
+    private_key: &EoaPrivateKey,
+    employ_extra_random_data: bool,
+) -> TransactionEip1559 {
+    TransactionBuilder::new()
+        .with_chain_id(123.into())
+        .with_nonce(42.try_into().unwrap())
+        .with_max_priority_fee_per_gas("0x42".try_into().unwrap())
+        .with_max_fee_per_gas("0x0143".try_into().unwrap())
+        .with_gas_limit(0x5208.into())
+        .with_destination(
+            "0x123456789a123456789a123456789a123456789a"
+                .try_into()
+                .unwrap(),
+        )
+        .with_amount("0x0123".try_into().unwrap())
+        .with_access_list(AccessList(vec![AccessListItem {
+            address: "0x123456789a123456789a123456789a123456789a"
+                .try_into()
+                .unwrap(),
+            storage_keys: vec![
+                "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+                    .try_into()
+                    .unwrap(),
+            ],
+        }]))
+        .take_and_build_payload_eip_1559()
+        .unwrap()
+        .take_and_sign_with_options(
+            &private_key.0,
+            &SigningOptions {
+                employ_extra_random_data,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_with_fixed_key() {
+        let private_key = fixed_private_key();
+        let transaction = sign_transaction(&private_key, false);
+        assert_eq!(
+            hex::encode_lower(&transaction.encode()),
+            concat!(
+                "02f89f7b2a4282014382520894123456789a",
+                "123456789a123456789a123456789a820123",
+                "80f838f794123456789a123456789a123456",
+                "789a123456789ae1a00123456789abcdef01",
+                "23456789abcdef0123456789abcdef012345",
+                "6789abcdef80a02cd518c375dfd2231b9352",
+                "e600a559cd1c7dd38ed46f4e470bde6723aa",
+                "85ab90a0432f760d25c8aa48ee9933b81821",
+                "b37a7408b45a253f0639a7875fe64f49f0b6"
+            )
+        );
+        assert_eq!(
+            transaction.recover_sender().unwrap(),
+            private_key.public_key().address()
+        );
+    }
+
+    #[test]
+    fn test_sign_with_generated_key() {
+        let private_key = EoaPrivateKey::generate().unwrap();
+        let transaction = sign_transaction(&private_key, true);
+        assert_eq!(
+            transaction.recover_sender().unwrap(),
+            private_key.public_key().address()
+        );
+    }
+}
@@ -61,7 +61,7 @@ use lightcryptotools::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use lightcryptotools::blockchain::ethereum::transaction::{
     TransactionEip155, TransactionEip1559, TransactionEip2930, TransactionLegacy,
 };
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::tools::codable::decode;
 
 fn main() {
@@ -70,7 +70,7 @@ fn main() {
         .expect("Error: the parameter is missing");
     let tx_hex = tx_hex.strip_prefix("0x").unwrap_or(&tx_hex);
 
-    let tx_data = match hex_to_bytes(tx_hex) {
+    let tx_data = match hex::decode(tx_hex) {
         Ok(data) => data,
         Err(err) => {
             panic!("Invalid hex input: {err}");
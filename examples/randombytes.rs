@@ -10,7 +10,7 @@
 //!
 //! cargo run --example randombytes -- 32
 
-use lightcryptotools::crypto::codecs::bytes_to_lower_hex;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::random::generator::get_os_random_bytes;
 
 fn main() {
@@ -21,7 +21,7 @@ fn main() {
 
     match get_os_random_bytes(bytes_len) {
         Ok(bytes) => {
-            let hex = bytes_to_lower_hex(&bytes);
+            let hex = hex::encode_lower(&bytes);
             println!("{hex}");
         }
         Err(err) => {
@@ -0,0 +1,126 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Verifies ECDSA (secp256k1, SHA-256) signatures in the style of Project Wycheproof:
+//! each case has a message, an IEEE P1363 encoded signature and an expected result.
+//!
+//! # Examples
+//!
+//! cargo run --example verify_wycheproof_style
+
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::{
+    verify_with_options, PublicKey, Signature, VerifyingOptions,
+};
+use lightcryptotools::crypto::hash::{Sha256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+
+// From "ecdsa_secp256k1_sha256_p1363_test.json" of Project Wycheproof.
+const PUBLIC_KEY_HEX: &str = concat!(
+    "04b838ff44e5bc177bf21189d0766082fc9d843226887fc9760371100b7ee20a6f",
+    "f0c9d75bfba7b31a6bca1974496eeb56de357071955d83c4b1badaa0b21832e9"
+);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Expected {
+    Valid,
+    Invalid,
+}
+
+/// (comment, message hex, signature hex, enforce_low_s, expected result)
+const CASES: [(&str, &str, &str, bool, Expected); 5] = [
+    (
+        "signature malleability (tcId 1)",
+        "313233343030",
+        concat!(
+            "813ef79ccefa9a56f7ba805f0e478584fe5f0dd5f567bc09b5123ccbc9832365",
+            "900e75ad233fcc908509dbff5922647db37c21f4afd3203ae8dc4ae7794b0f87"
+        ),
+        false,
+        Expected::Valid,
+    ),
+    (
+        "high s rejected when low s is enforced",
+        "313233343030",
+        concat!(
+            "813ef79ccefa9a56f7ba805f0e478584fe5f0dd5f567bc09b5123ccbc9832365",
+            "900e75ad233fcc908509dbff5922647db37c21f4afd3203ae8dc4ae7794b0f87"
+        ),
+        true,
+        Expected::Invalid,
+    ),
+    (
+        "modified message",
+        "313233343031",
+        concat!(
+            "813ef79ccefa9a56f7ba805f0e478584fe5f0dd5f567bc09b5123ccbc9832365",
+            "900e75ad233fcc908509dbff5922647db37c21f4afd3203ae8dc4ae7794b0f87"
+        ),
+        false,
+        Expected::Invalid,
+    ),
+    (
+        "modified s (tcId 8)",
+        "313233343030",
+        concat!(
+            "813ef79ccefa9a56f7ba805f0e478584fe5f0dd5f567bc09b5123ccbc9832365",
+            "900e75ad233fcc908509dbff5922647ef8cd450e008a7fff2909ec5aa914ce46"
+        ),
+        false,
+        Expected::Invalid,
+    ),
+    (
+        "r and s are zero (tcId 9)",
+        "313233343030",
+        concat!(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000"
+        ),
+        false,
+        Expected::Invalid,
+    ),
+];
+
+fn main() {
+    for (comment, message_hex, signature_hex, enforce_low_s, expected) in CASES {
+        let result = verify_case(message_hex, signature_hex, enforce_low_s);
+        println!("{comment}: {result:?} (expected: {expected:?})");
+    }
+}
+
+fn verify_case(message_hex: &str, signature_hex: &str, enforce_low_s: bool) -> Expected {
+    let curve = secp256k1();
+    let public_key = PublicKey::from_sec1_hex(PUBLIC_KEY_HEX, curve).unwrap();
+    let signature = match Signature::from_p1363_hex(signature_hex, curve) {
+        Ok(signature) => signature,
+        Err(_) => return Expected::Invalid,
+    };
+
+    let hash = Sha256::new().digest(hex::decode(message_hex).unwrap());
+    let options = VerifyingOptions {
+        enforce_low_s,
+        strict_hash_byte_length: true,
+    };
+    match verify_with_options(&hash, &signature, &public_key, &options) {
+        Ok(true) => Expected::Valid,
+        _ => Expected::Invalid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_cases() {
+        for (_, message_hex, signature_hex, enforce_low_s, expected) in CASES {
+            assert_eq!(
+                verify_case(message_hex, signature_hex, enforce_low_s),
+                expected
+            );
+        }
+    }
+}
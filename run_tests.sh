@@ -6,6 +6,9 @@ cargo nextest run
 # Runs doctests explicitly, for nextest currently doesn't support doctests
 cargo test --doc
 
+# Runs the tests of the examples, which assert the outputs for fixed keys
+cargo test --examples
+
 # Tests time-consuming cases
 cargo test --release -- --ignored
 
@@ -16,6 +19,19 @@ cargo test --release -- --ignored
 
 # cargo bench
 
+# Benchmarks on the stable toolchain, see tests/bench/main.rs
+# cargo test --release --test bench -- --ignored --test-threads=1 --nocapture bench_
+# cargo test --release --test bench_arena -- --ignored --test-threads=1 --nocapture bench_
+
+# Builds the no_std subset
+cargo build --lib --no-default-features --features alloc --target wasm32-unknown-unknown
+
+# Tests the conversions of the optional feature num-bigint
+cargo nextest run --features num-bigint num_bigint
+
+# Tests the implementations of the codable traits outside the crate
+cargo nextest run --features unstable-codable
+
 # Tests bigint for u8_digit
 RUSTFLAGS="--cfg u8_digit" cargo nextest run bigint
 
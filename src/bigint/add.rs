@@ -6,16 +6,19 @@
 
 //! Implements addition operations.
 
-use super::bigint_core::BigInt;
+use super::bigint_core::{BigInt, Sign};
 use super::bigint_slice::{is_valid_biguint_slice, BigUintSlice};
 use super::bigint_vec::{digitvec_with_len, DigitVec};
 use super::cmp::cmp_digits;
 use super::digit::Digit;
 use super::helper_methods::carrying_add;
-use super::sub::{digitvec_subtracting_output, sub_digits};
-use std::cmp;
-use std::cmp::Ordering;
-use std::ops::Add;
+use super::len::len_digits;
+use super::sub::{
+    digitvec_subtracting_output, reverse_sub_digits_in_place, sub_digits, sub_digits_in_place,
+};
+use core::cmp;
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign};
 
 /// Adds `a` with `b`, and fills the output to `result`,
 /// returning the length of the output digits.
@@ -74,6 +77,59 @@ pub(crate) fn add_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
     result_digits_len
 }
 
+/// Adds `b` to `a` in place: a += b, returning the length of the output digits.
+///
+/// - `a` holds `a_len` digits, followed by zeros.
+/// - `a` must have a length no less than the return value of [`adding_output_max_len`].
+#[inline]
+pub(crate) fn add_digits_in_place(a: &mut [Digit], a_len: usize, b: &BigUintSlice) -> usize {
+    debug_assert!(is_valid_biguint_slice(b));
+    debug_assert!(a.len() >= adding_output_max_len(a_len, b.len()));
+    debug_assert!(a[a_len..].iter().all(|&digit| digit == 0));
+
+    let mut carry = false;
+    for (a_digit, &b_digit) in a.iter_mut().zip(b) {
+        (*a_digit, carry) = carrying_add(*a_digit, b_digit, carry);
+    }
+
+    // Propagates the resulting carry upwards, the digits of `a` beyond `a_len` are zeros.
+    for a_digit in &mut a[b.len()..] {
+        if !carry {
+            break;
+        }
+        (*a_digit, carry) = carrying_add(*a_digit, 0, carry);
+    }
+
+    len_digits(&a[..adding_output_max_len(a_len, b.len())])
+}
+
+impl BigInt {
+    /// Adds the signed `b` to `self` in place, reusing `digits_storage` if the output fits.
+    pub(super) fn add_assign_signed(&mut self, b: &BigUintSlice, b_sign: Sign) {
+        let a_len = self.digits_len;
+        if self.sign == b_sign {
+            self.grow_digits_storage(adding_output_max_len(a_len, b.len()));
+            let output_len = add_digits_in_place(self.digits_storage.to_mut(), a_len, b);
+            self.set_digits_len(output_len, b_sign);
+        } else {
+            match cmp_digits(self.as_digits(), b) {
+                Ordering::Less => {
+                    self.grow_digits_storage(b.len());
+                    let output_len =
+                        reverse_sub_digits_in_place(self.digits_storage.to_mut(), a_len, b);
+                    self.set_digits_len(output_len, b_sign);
+                }
+                Ordering::Equal => self.assign_digits(&[0], Sign::Positive),
+                Ordering::Greater => {
+                    let output_len =
+                        sub_digits_in_place(&mut self.digits_storage.to_mut()[..a_len], b);
+                    self.set_digits_len(output_len, self.sign);
+                }
+            }
+        }
+    }
+}
+
 impl<'a, 'b> Add<&'b BigInt> for &'a BigInt {
     type Output = BigInt;
 
@@ -127,6 +183,18 @@ impl Add for BigInt {
     }
 }
 
+impl AddAssign<&BigInt> for BigInt {
+    fn add_assign(&mut self, rhs: &BigInt) {
+        self.add_assign_signed(rhs.as_digits(), rhs.sign);
+    }
+}
+
+impl AddAssign for BigInt {
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_assign(&rhs);
+    }
+}
+
 /// Returns the length of the largest possible output of an addition operation: a + b.
 ///
 /// `a_len` and `b_len` are the length of the operands.
@@ -161,6 +229,8 @@ mod tests {
     use super::*;
     use crate::bigint::bigint_vec::digits_be;
     use crate::bigint::digit::Digit;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_add_digits() {
@@ -225,4 +295,73 @@ mod tests {
             assert_eq!(a + b, c)
         }
     }
+
+    #[test]
+    fn test_add_digits_in_place() {
+        // `data`: [(a, b, result)]
+        let data = [
+            (
+                digits_be!(1, 2, 3),
+                digits_be!(4, 5, 6),
+                digits_be!(5, 7, 9),
+            ),
+            (digits_be!(3), digits_be!(4, 5, 6), digits_be!(4, 5, 9)),
+            (
+                digits_be!(1, Digit::MAX, Digit::MAX),
+                digits_be!(1),
+                digits_be!(2, 0, 0),
+            ),
+            (
+                digits_be!(Digit::MAX, Digit::MAX),
+                digits_be!(1),
+                digits_be!(1, 0, 0),
+            ),
+        ];
+        for (a, b, result) in data {
+            let a_len = a.len();
+            let mut output = a.clone();
+            output.resize(adding_output_max_len(a_len, b.len()), 0);
+            let output_len = add_digits_in_place(&mut output, a_len, &b);
+            assert_eq!(result, output[..output_len]);
+            assert_eq!(vec!(0; output.len() - output_len), output[output_len..]);
+        }
+    }
+
+    #[test]
+    fn test_add_assign_signs() {
+        let data = [
+            (5, 7),
+            (5, -7),
+            (-5, 7),
+            (-5, -7),
+            (7, -5),
+            (5, -5),
+            (-5, 5),
+            (0, -1),
+        ];
+        for (a, b) in data {
+            let mut c = BigInt::from(a);
+            c += BigInt::from(b);
+            assert_eq!(c, BigInt::from(a + b));
+            assert_eq!(c.is_sign_negative(), a + b < 0);
+        }
+    }
+
+    #[test]
+    fn test_add_assign_reuses_storage() {
+        let mut a = BigInt::from_hex("0100000000000000000000000000000000").unwrap();
+        let storage_ptr = a.digits_storage.as_ptr();
+        a += BigInt::from_hex("-ffffffffffffffffffffffffffffffff").unwrap();
+        assert_eq!(a, BigInt::one());
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+    }
+
+    #[quickcheck]
+    fn add_assign_matches_add(a_hex: BigIntHexString, b_hex: BigIntHexString) -> bool {
+        let a = BigInt::from_hex(&a_hex.0).unwrap();
+        let b = BigInt::from_hex(&b_hex.0).unwrap();
+        let mut c = a.clone();
+        c += &b;
+        c == &a + &b && !(c.is_zero() && c.is_sign_negative())
+    }
 }
@@ -0,0 +1,476 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the optional arena serving the allocations of batch operations, e.g.,
+//! signing thousands of messages, from a pre-allocated buffer.
+//!
+//! The operations of `BigInt` allocate many small, short-lived `DigitVec`s. Within
+//! [`with_arena`], the allocations of the current thread are bump-allocated from the
+//! buffer of the arena, freeing them costs a counter decrement, and the buffer is reused
+//! from the start whenever every allocation of it has been freed. Once the buffer is
+//! exhausted, allocations fall back to the system allocator.
+//!
+//! `Vec` allocates with the global allocator, so the arena works through
+//! [`BigIntArenaAllocator`], installed by the application:
+//!
+//! ```text
+//! #[global_allocator]
+//! static GLOBAL: BigIntArenaAllocator = BigIntArenaAllocator;
+//! ```
+//!
+//! Without it installed, the arenas are never used and nothing changes: the library itself
+//! allocates as it always does.
+//!
+//! Allocations outliving the arena, e.g., the output of the closure, stay valid: the buffer
+//! is released once the last of them is freed, on whichever thread.
+//!
+//! The allocator can't tell the allocations of `DigitVec`s from the others, and serves every
+//! allocation of the thread within the arena. It also costs every deallocation of the
+//! process, within an arena or not: while any buffer exists, freeing a pointer searches the
+//! buffers it may belong to, up to 64 of them. For this reason, the arenas are behind the
+//! opt-in feature `bigint-arena`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::ptr;
+use std::sync::atomic::{fence, AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+/// The maximum number of buffers existing at the same time, including the ones of the
+/// arenas ended but with allocations alive. An arena is opened without a buffer, and
+/// doesn't serve any allocation, if all are taken.
+const MAX_REGIONS: usize = 64;
+const REGION_ALIGN: usize = 64;
+const NO_REGION: usize = usize::MAX;
+
+// The states of a region (buffer).
+const FREE: u8 = 0;
+/// Being set up by an arena.
+const RESERVED: u8 = 1;
+/// Owned by an arena.
+const OPEN: u8 = 2;
+/// The arena has ended, allocations of the region are alive.
+const CLOSED: u8 = 3;
+/// Being released.
+const RELEASING: u8 = 4;
+
+struct Region {
+    state: AtomicU8,
+    // Odd while `start` and `end` are being changed, see `set_range`.
+    version: AtomicUsize,
+    start: AtomicUsize,
+    end: AtomicUsize,
+    // Only the thread of the arena allocates from the region, and moves `next`.
+    next: AtomicUsize,
+    live_allocations: AtomicUsize,
+    fallbacks: AtomicUsize,
+}
+
+impl Region {
+    const fn new() -> Self {
+        Region {
+            state: AtomicU8::new(FREE),
+            version: AtomicUsize::new(0),
+            start: AtomicUsize::new(0),
+            end: AtomicUsize::new(0),
+            next: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+            fallbacks: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns true if `address` is in the buffer of the region.
+    ///
+    /// Reads `start` and `end` as a seqlock: a range changed during the read may mix the
+    /// bounds of two buffers, and is discarded. A region whose range changes, being set up
+    /// or released, has no allocation alive, so `address` of an allocation alive isn't in
+    /// it either way.
+    fn contains(&self, address: usize) -> bool {
+        let version = self.version.load(Ordering::Acquire);
+        if !version.is_multiple_of(2) {
+            return false;
+        }
+        let start = self.start.load(Ordering::Relaxed);
+        let end = self.end.load(Ordering::Relaxed);
+        fence(Ordering::Acquire);
+
+        self.version.load(Ordering::Relaxed) == version && start <= address && address < end
+    }
+
+    /// Sets the range of the buffer, returning the previous one. Only called by the thread
+    /// setting up or releasing the region.
+    fn set_range(&self, start: usize, end: usize) -> (usize, usize) {
+        self.version.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        let previous = (
+            self.start.swap(start, Ordering::Relaxed),
+            self.end.swap(end, Ordering::Relaxed),
+        );
+        self.version.fetch_add(1, Ordering::Release);
+        previous
+    }
+
+    /// Releases the buffer if the region is closed, and no other thread releases it.
+    fn release(&self) {
+        if self
+            .state
+            .compare_exchange(CLOSED, RELEASING, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            return;
+        }
+
+        // Empties the range before releasing the memory, so that a system allocation
+        // reusing the memory is never taken for an allocation of the region.
+        let (start, end) = self.set_range(0, 0);
+        // SAFETY: `start` was allocated with this layout in `open_region`, and no
+        // allocation of the region is alive.
+        unsafe {
+            System.dealloc(
+                start as *mut u8,
+                Layout::from_size_align_unchecked(end - start, REGION_ALIGN),
+            );
+        }
+        OPEN_REGIONS.fetch_sub(1, Ordering::SeqCst);
+        self.state.store(FREE, Ordering::SeqCst);
+    }
+}
+
+static REGIONS: [Region; MAX_REGIONS] = [const { Region::new() }; MAX_REGIONS];
+/// The number of regions not free, for `dealloc` to skip the search if zero.
+static OPEN_REGIONS: AtomicUsize = AtomicUsize::new(0);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Accessed by the allocator: const initialized and without destructor, so accessing
+    // it never allocates.
+    static CURRENT_REGION: Cell<usize> = const { Cell::new(NO_REGION) };
+    static CACHED_REGION: RefCell<Option<CachedRegion>> = const { RefCell::new(None) };
+}
+
+/// Claims a region and allocates its buffer of `size` bytes.
+fn open_region(size: usize) -> Option<usize> {
+    if size == 0 || !INSTALLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let layout = Layout::from_size_align(size, REGION_ALIGN).ok()?;
+
+    let index = REGIONS.iter().position(|region| {
+        region
+            .state
+            .compare_exchange(FREE, RESERVED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    })?;
+    let region = &REGIONS[index];
+
+    // SAFETY: `layout` has a non-zero size.
+    let start = unsafe { System.alloc(layout) } as usize;
+    if start == 0 {
+        region.state.store(FREE, Ordering::SeqCst);
+        return None;
+    }
+    region.set_range(start, start + size);
+    region.next.store(start, Ordering::SeqCst);
+    region.live_allocations.store(0, Ordering::SeqCst);
+    region.fallbacks.store(0, Ordering::SeqCst);
+    OPEN_REGIONS.fetch_add(1, Ordering::SeqCst);
+    region.state.store(OPEN, Ordering::SeqCst);
+
+    Some(index)
+}
+
+/// Ends the ownership of the region by an arena, the buffer is released now or once the
+/// last allocation of it is freed.
+fn close_region(index: usize) {
+    let region = &REGIONS[index];
+    region.state.store(CLOSED, Ordering::SeqCst);
+    if region.live_allocations.load(Ordering::SeqCst) == 0 {
+        region.release();
+    }
+}
+
+/// Bump-allocates from the region of the arena of the current thread, if any.
+fn arena_alloc(layout: Layout) -> Option<*mut u8> {
+    let index = CURRENT_REGION.try_with(Cell::get).unwrap_or(NO_REGION);
+    if index == NO_REGION {
+        return None;
+    }
+
+    let region = &REGIONS[index];
+    let start = region.start.load(Ordering::Relaxed);
+    // Restarts from the beginning if every allocation has been freed. No other thread
+    // allocates from the region, so the count can't increase meanwhile.
+    let next = if region.live_allocations.load(Ordering::SeqCst) == 0 {
+        start
+    } else {
+        region.next.load(Ordering::Relaxed)
+    };
+
+    let address = next.checked_add(layout.align() - 1)? & !(layout.align() - 1);
+    let new_next = address.checked_add(layout.size())?;
+    if new_next > region.end.load(Ordering::Relaxed) {
+        region.fallbacks.fetch_add(1, Ordering::Relaxed);
+        return None;
+    }
+    region.next.store(new_next, Ordering::Relaxed);
+    region.live_allocations.fetch_add(1, Ordering::SeqCst);
+
+    Some(address as *mut u8)
+}
+
+/// Frees `ptr` if it is allocated from a region, returns false otherwise.
+fn arena_dealloc(ptr: *mut u8) -> bool {
+    if OPEN_REGIONS.load(Ordering::SeqCst) == 0 {
+        return false;
+    }
+
+    let address = ptr as usize;
+    let Some(region) = REGIONS.iter().find(|region| region.contains(address)) else {
+        return false;
+    };
+    if region.live_allocations.fetch_sub(1, Ordering::SeqCst) == 1
+        && region.state.load(Ordering::SeqCst) == CLOSED
+    {
+        region.release();
+    }
+    true
+}
+
+/// The global allocator serving the allocations within [`with_arena`] and
+/// [`with_thread_local_arena`] from the buffers of the arenas, and the others from the
+/// system allocator.
+///
+/// The arenas are only used if it is installed with `#[global_allocator]`.
+pub struct BigIntArenaAllocator;
+
+unsafe impl GlobalAlloc for BigIntArenaAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            INSTALLED.store(true, Ordering::Relaxed);
+        }
+
+        match arena_alloc(layout) {
+            Some(ptr) => ptr,
+            None => System.alloc(layout),
+        }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match arena_alloc(layout) {
+            Some(ptr) => {
+                ptr::write_bytes(ptr, 0, layout.size());
+                ptr
+            }
+            None => System.alloc_zeroed(layout),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if !arena_dealloc(ptr) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let in_arena = CURRENT_REGION.try_with(Cell::get).unwrap_or(NO_REGION) != NO_REGION;
+        if !in_arena && !REGIONS.iter().any(|region| region.contains(ptr as usize)) {
+            return System.realloc(ptr, layout, new_size);
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+/// An arena, see [`with_arena`].
+pub struct BigIntArena {
+    region: Option<usize>,
+    // Cached regions outlive the arena.
+    cached: bool,
+    // Stays on the thread of the arena.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl BigIntArena {
+    /// Returns true if [`BigIntArenaAllocator`] is the global allocator, without which the
+    /// arenas don't serve any allocation.
+    pub fn is_installed() -> bool {
+        INSTALLED.load(Ordering::Relaxed)
+    }
+
+    /// Returns the size of the buffer in bytes, 0 if the arena has no buffer.
+    pub fn capacity(&self) -> usize {
+        self.region.map_or(0, |index| {
+            let region = &REGIONS[index];
+            region.end.load(Ordering::SeqCst) - region.start.load(Ordering::SeqCst)
+        })
+    }
+
+    /// Returns the bytes bump-allocated since the buffer was last reused from the start.
+    pub fn used(&self) -> usize {
+        self.region.map_or(0, |index| {
+            let region = &REGIONS[index];
+            region.next.load(Ordering::Relaxed) - region.start.load(Ordering::SeqCst)
+        })
+    }
+
+    /// Returns the number of allocations which didn't fit in the buffer, and were served
+    /// by the system allocator.
+    pub fn fallback_count(&self) -> usize {
+        self.region
+            .map_or(0, |index| REGIONS[index].fallbacks.load(Ordering::Relaxed))
+    }
+}
+
+impl Drop for BigIntArena {
+    fn drop(&mut self) {
+        if let (Some(index), false) = (self.region, self.cached) {
+            close_region(index);
+        }
+    }
+}
+
+/// Routes the allocations of the current thread to `region`, restoring the previous one
+/// on drop, even if the closure panics.
+struct ArenaScope {
+    previous: usize,
+}
+
+impl ArenaScope {
+    fn enter(region: Option<usize>) -> ArenaScope {
+        let previous = CURRENT_REGION.get();
+        CURRENT_REGION.set(region.unwrap_or(NO_REGION));
+        ArenaScope { previous }
+    }
+}
+
+impl Drop for ArenaScope {
+    fn drop(&mut self) {
+        CURRENT_REGION.set(self.previous);
+    }
+}
+
+/// The region kept by a thread for [`with_thread_local_arena`], closed when the thread
+/// exits.
+struct CachedRegion {
+    index: usize,
+    size: usize,
+}
+
+impl Drop for CachedRegion {
+    fn drop(&mut self) {
+        close_region(self.index);
+    }
+}
+
+/// Runs `f` with the allocations of the current thread served from a buffer of
+/// `arena_size` bytes, allocated for the call and released afterwards.
+///
+/// Requires [`BigIntArenaAllocator`] to be the global allocator, otherwise `f` runs with
+/// the allocations as usual.
+pub fn with_arena<F, R>(arena_size: usize, f: F) -> R
+where
+    F: FnOnce(&BigIntArena) -> R,
+{
+    let arena = BigIntArena {
+        region: open_region(arena_size),
+        cached: false,
+        _not_send: PhantomData,
+    };
+    let scope = ArenaScope::enter(arena.region);
+    let output = f(&arena);
+    drop(scope);
+
+    output
+}
+
+/// Like [`with_arena`], with the buffer kept by the current thread for the following
+/// calls, and released when the thread exits.
+///
+/// A kept buffer smaller than `arena_size` is replaced. Nested calls use buffers of their
+/// own.
+pub fn with_thread_local_arena<F, R>(arena_size: usize, f: F) -> R
+where
+    F: FnOnce(&BigIntArena) -> R,
+{
+    let cached = CACHED_REGION
+        .with_borrow_mut(Option::take)
+        .filter(|cached| cached.size >= arena_size)
+        .or_else(|| {
+            open_region(arena_size).map(|index| CachedRegion {
+                index,
+                size: arena_size,
+            })
+        });
+
+    let arena = BigIntArena {
+        region: cached.as_ref().map(|cached| cached.index),
+        cached: true,
+        _not_send: PhantomData,
+    };
+    let scope = ArenaScope::enter(arena.region);
+    let output = f(&arena);
+    drop(scope);
+
+    // Keeps the buffer unless a nested call has kept one meanwhile.
+    CACHED_REGION.with_borrow_mut(|slot| {
+        if slot.is_none() {
+            *slot = cached;
+        }
+    });
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The allocator isn't installed for the unit tests, see "tests/bigint_arena.rs" for
+    // the tests with it installed.
+    #[test]
+    fn test_without_allocator_installed() {
+        assert!(!BigIntArena::is_installed());
+
+        let output = with_arena(1 << 20, |arena| {
+            assert_eq!(arena.capacity(), 0);
+            vec![1_u64; 100]
+        });
+        assert_eq!(output, vec![1; 100]);
+
+        let output = with_thread_local_arena(1 << 20, |arena| {
+            assert_eq!(arena.capacity(), 0);
+            assert_eq!(arena.used(), 0);
+            assert_eq!(arena.fallback_count(), 0);
+            42
+        });
+        assert_eq!(output, 42);
+        assert_eq!(OPEN_REGIONS.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_region_contains() {
+        let region = Region::new();
+        assert!(!region.contains(0));
+
+        assert_eq!(region.set_range(0x1000, 0x2000), (0, 0));
+        assert!(region.contains(0x1000));
+        assert!(region.contains(0x1fff));
+        assert!(!region.contains(0x2000));
+
+        // The range being changed is never read.
+        region.version.fetch_add(1, Ordering::SeqCst);
+        assert!(!region.contains(0x1000));
+        region.version.fetch_add(1, Ordering::SeqCst);
+
+        assert_eq!(region.set_range(0, 0), (0x1000, 0x2000));
+        assert!(!region.contains(0x1000));
+    }
+}
@@ -6,15 +6,20 @@
 
 //! Defines `BigInt`.
 
+use super::bigint_slice::BigUintSlice;
 use super::digit::Digit;
+use alloc::borrow::Cow;
 
 /// A big integer.
 ///
 /// Digits are stored in little-endian order,
 /// e.g., the "least significant digit" is stored at position 0.
+///
+/// `digits_storage` can borrow static digits, see [`BigInt::from_static_digits`]. The
+/// digits are copied to an owned `Vec` by the first operation that writes to the storage.
 #[derive(Clone, Debug)]
 pub struct BigInt {
-    pub(crate) digits_storage: Vec<Digit>,
+    pub(crate) digits_storage: Cow<'static, [Digit]>,
     pub(crate) digits_len: usize, // The length of digits stored in `digits_storage`
     pub(crate) sign: Sign,
 }
@@ -23,13 +28,42 @@ impl BigInt {
     pub(crate) fn is_sign_negative(&self) -> bool {
         self.sign == Sign::Negative
     }
+
+    /// Extends `digits_storage` with zeros to a length no less than `len`.
+    ///
+    /// The storage never shrinks, so that the allocation can be reused by the in-place
+    /// operations.
+    pub(crate) fn grow_digits_storage(&mut self, len: usize) {
+        if self.digits_storage.len() < len {
+            self.digits_storage.to_mut().resize(len, 0);
+        }
+    }
+
+    /// Replaces the digits and the sign of `self`, reusing `digits_storage`.
+    ///
+    /// Zero is always stored as positive.
+    pub(crate) fn assign_digits(&mut self, digits: &BigUintSlice, sign: Sign) {
+        self.grow_digits_storage(digits.len());
+        let storage = self.digits_storage.to_mut();
+        storage[..digits.len()].copy_from_slice(digits);
+        storage[digits.len()..].fill(0);
+        self.set_digits_len(digits.len(), sign);
+    }
+
+    /// Sets the length of the digits stored in `digits_storage`, and the sign.
+    ///
+    /// Zero is always stored as positive.
+    pub(crate) fn set_digits_len(&mut self, digits_len: usize, sign: Sign) {
+        self.digits_len = digits_len;
+        self.sign = if self.is_zero() { Sign::Positive } else { sign };
+    }
 }
 
 /// Denotes the sign of a big integer.
 ///
 /// A big integer, including 0, can be denoted as either positive or negative.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub(crate) enum Sign {
+pub enum Sign {
     Positive,
     Negative,
 }
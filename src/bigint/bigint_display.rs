@@ -5,8 +5,28 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::bigint_core::BigInt;
-use std::fmt;
-use std::fmt::Display;
+use alloc::string::String;
+use core::fmt;
+use core::fmt::{Binary, Display, UpperHex};
+
+impl BigInt {
+    /// Returns the hexadecimal representation of `self` in uppercase.
+    ///
+    /// Apart from the case, the representation is the same as the one of `to_lower_hex`.
+    pub fn to_upper_hex(&self) -> String {
+        self.to_lower_hex().to_ascii_uppercase()
+    }
+
+    /// Returns the hexadecimal representation of `self`,
+    /// in uppercase if `upper` is true and in lowercase otherwise.
+    pub fn to_hex_with_case(&self, upper: bool) -> String {
+        if upper {
+            self.to_upper_hex()
+        } else {
+            self.to_lower_hex()
+        }
+    }
+}
 
 impl Display for BigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -14,3 +34,91 @@ impl Display for BigInt {
         write!(f, "0x{hex}")
     }
 }
+
+/// Formats `self` as uppercase hexadecimal, e.g., `format!("{:X}", n)`.
+///
+/// The sign, the alternate flag (`#` for the "0x" prefix) and the width options
+/// are respected.
+impl UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self.to_upper_hex();
+        let (is_nonnegative, digits) = match hex.strip_prefix('-') {
+            Some(digits) => (false, digits),
+            None => (true, hex.as_str()),
+        };
+        f.pad_integral(is_nonnegative, "0x", digits)
+    }
+}
+
+/// Formats `self` as binary, e.g., `format!("{:b}", n)`.
+///
+/// The sign, the alternate flag (`#` for the "0b" prefix) and the width options
+/// are respected.
+impl Binary for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let binary = self.to_binary_string();
+        let (is_nonnegative, digits) = match binary.strip_prefix('-') {
+            Some(digits) => (false, digits),
+            None => (true, binary.as_str()),
+        };
+        f.pad_integral(is_nonnegative, "0b", digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn test_to_upper_hex() {
+        let data = [
+            (BigInt::from(0), "00"),
+            (BigInt::from(0xab), "AB"),
+            (BigInt::from(-0xabcd), "-ABCD"),
+            (BigInt::from(u128::MAX), "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"),
+        ];
+        for (n, hex) in data {
+            assert_eq!(n.to_upper_hex(), hex);
+            assert_eq!(n.to_hex_with_case(true), hex);
+            assert_eq!(n.to_hex_with_case(false), hex.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_upper_hex_format() {
+        let data = [
+            (format!("{:X}", BigInt::from(0xab)), "AB"),
+            (format!("{:#X}", BigInt::from(0xab)), "0xAB"),
+            (format!("{:X}", BigInt::from(-0xab)), "-AB"),
+            (format!("{:#X}", BigInt::from(-0xab)), "-0xAB"),
+            (format!("{:06X}", BigInt::from(0xab)), "0000AB"),
+            (format!("{:+X}", BigInt::from(0xab)), "+AB"),
+        ];
+        for (output, expected) in data {
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn test_binary_format() {
+        let data = [
+            (format!("{:b}", BigInt::from(0)), "0"),
+            (format!("{:b}", BigInt::from(0b101)), "101"),
+            (format!("{:#b}", BigInt::from(0b101)), "0b101"),
+            (format!("{:b}", BigInt::from(-0b101)), "-101"),
+            (format!("{:#b}", BigInt::from(-0b101)), "-0b101"),
+            (format!("{:08b}", BigInt::from(0b101)), "00000101"),
+        ];
+        for (output, expected) in data {
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[quickcheck]
+    fn upper_hex_matches_lower_hex(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(hex.0).unwrap();
+        n.to_upper_hex().to_lowercase() == n.to_lower_hex()
+    }
+}
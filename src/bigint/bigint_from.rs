@@ -67,4 +67,26 @@ mod tests {
     test_from_int!(i32, test_from_i32);
     test_from_int!(i16, test_from_i16);
     test_from_int!(i8, test_from_i8);
+
+    #[test]
+    fn test_from_wide_int_boundaries() {
+        let data = [
+            (BigInt::from(0_u128), "00"),
+            (BigInt::from(0_i128), "00"),
+            (BigInt::from(u128::MAX), "ffffffffffffffffffffffffffffffff"),
+            (BigInt::from(i128::MAX), "7fffffffffffffffffffffffffffffff"),
+            (BigInt::from(i128::MIN), "-80000000000000000000000000000000"),
+            (BigInt::from(u64::MAX), "ffffffffffffffff"),
+            (BigInt::from(i64::MIN), "-8000000000000000"),
+        ];
+        for (n, hex) in data {
+            assert_eq!(n.to_lower_hex(), hex);
+        }
+
+        assert!(BigInt::from(0_i128).is_zero());
+        assert_eq!(
+            BigInt::from(i128::MIN),
+            -BigInt::from(i128::MAX) - BigInt::from(1)
+        );
+    }
 }
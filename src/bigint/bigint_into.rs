@@ -5,8 +5,16 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::bigint_core::{BigInt, Sign};
-use super::bytes::be_digits_to_be_bytes;
-use crate::crypto::codecs::bytes_to_lower_hex;
+use super::bigint_new::DECIMAL_CHUNK_LEN;
+use super::bytes::{be_digits_to_be_bytes, negate_twos_complement_be_bytes};
+use super::divrem::div_rem;
+use crate::crypto::codecs::hex;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
 
 impl BigInt {
     /// Returns the lowercase hexadecimal representation.
@@ -21,7 +29,7 @@ impl BigInt {
         }
 
         let bytes = self.to_be_bytes();
-        let mut hex = bytes_to_lower_hex(&bytes);
+        let mut hex = hex::encode_lower(&bytes);
 
         match self.sign {
             Sign::Positive => hex,
@@ -32,18 +40,188 @@ impl BigInt {
         }
     }
 
-    /// Return the memory representation of this big integer as a byte array in big-endian byte order.
+    /// Returns the lowercase hexadecimal representation of exactly `hex_len` characters,
+    /// leading zero padded, e.g., 0xabc is "0abc" with `hex_len` 4.
+    ///
+    /// Returns `BigIntError::TooLarge` if `self` needs more than `hex_len` characters,
+    /// and `BigIntError::Negative` if `self` is negative.
+    pub fn to_fixed_hex(&self, hex_len: usize) -> Result<String, BigIntError> {
+        if self.is_sign_negative() && !self.is_zero() {
+            return Err(BigIntError::Negative);
+        }
+
+        let hex = hex::encode_lower(&self.to_be_bytes());
+        let hex = hex.trim_start_matches('0');
+        if hex.len() > hex_len {
+            return Err(BigIntError::TooLarge);
+        }
+
+        Ok(format!("{hex:0>hex_len$}"))
+    }
+
+    /// Returns the big-endian bytes of exactly `byte_len` bytes, leading zero padded.
+    ///
+    /// Returns `BigIntError::TooLarge` if `self` needs more than `byte_len` bytes,
+    /// and `BigIntError::Negative` if `self` is negative.
+    pub fn to_fixed_bytes(&self, byte_len: usize) -> Result<Vec<u8>, BigIntError> {
+        if self.is_sign_negative() && !self.is_zero() {
+            return Err(BigIntError::Negative);
+        }
+
+        let bytes = self.to_be_bytes();
+        let bytes = &bytes[bytes.iter().take_while(|&&byte| byte == 0).count()..];
+        if bytes.len() > byte_len {
+            return Err(BigIntError::TooLarge);
+        }
+
+        let mut data = vec![0; byte_len - bytes.len()];
+        data.extend(bytes);
+        Ok(data)
+    }
+
+    /// Returns the binary representation, with the most significant bit first.
+    ///
+    /// The representation has no leading zeros; the binary representation of value zero
+    /// is "0". If `self` is negative, the representation is prefixed by '-'.
+    pub fn to_binary_string(&self) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut binary = String::with_capacity(self.bit_len() + 1);
+        if self.sign == Sign::Negative {
+            binary.push('-');
+        }
+        binary.extend(
+            self.le_bits()
+                .into_iter()
+                .rev()
+                .map(|bit| if bit { '1' } else { '0' }),
+        );
+        binary
+    }
+
+    /// Returns the decimal ASCII digits, e.g., `b"255"`, without leading zeros; the
+    /// representation of value zero is `b"0"`. If `self` is negative, the digits are
+    /// prefixed by '-'.
+    ///
+    /// See [`BigInt::from_decimal_bytes`] for the reverse.
+    pub fn to_decimal_bytes(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return vec![b'0'];
+        }
+
+        // Divides the magnitude by 10^19 until zero, collecting the remainders as chunks
+        // of 19 digits, least significant first.
+        let base = BigInt::from(10_u64.pow(DECIMAL_CHUNK_LEN as u32));
+        let mut n = self.clone();
+        n.sign = Sign::Positive;
+        let mut decimal = Vec::with_capacity(self.bit_len() * 3 / 10 + 2);
+        while !n.is_zero() {
+            let (quotient, remainder) = div_rem(&n, &base);
+            let mut chunk = remainder
+                .to_be_bytes()
+                .iter()
+                .fold(0_u64, |chunk, &byte| chunk << 8 | u64::from(byte));
+            for _ in 0..DECIMAL_CHUNK_LEN {
+                decimal.push(b'0' + (chunk % 10) as u8);
+                chunk /= 10;
+            }
+            n = quotient;
+        }
+
+        while decimal.last() == Some(&b'0') {
+            decimal.pop();
+        }
+        if self.sign == Sign::Negative {
+            decimal.push(b'-');
+        }
+        decimal.reverse();
+        decimal
+    }
+
+    /// Returns the sign and the magnitude as 32-bit digits, least significant first.
+    ///
+    /// The representation is independent of the internal digit size, for exchanging
+    /// values with other big integer libraries. The digits have no trailing zeros; value
+    /// zero is `(Sign::Positive, vec![])`.
+    ///
+    /// See [`BigInt::from_u32_digits`] for the reverse.
+    pub fn to_u32_digits(&self) -> (Sign, Vec<u32>) {
+        if self.is_zero() {
+            return (Sign::Positive, vec![]);
+        }
+
+        let bytes = self.to_be_bytes();
+        let digits = bytes
+            .rchunks(4)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |digit, &byte| digit << 8 | byte as u32)
+            })
+            .collect();
+        (self.sign, digits)
+    }
+
     pub(crate) fn to_be_bytes(&self) -> Vec<u8> {
         let mut digits = self.as_digits().to_vec();
         digits.reverse();
 
         be_digits_to_be_bytes(&digits)
     }
+
+    /// Returns the two's complement representation in big-endian byte order,
+    /// with the fewest bytes that keep the sign, i.e., the most significant bit.
+    ///
+    /// For instance, 128 is `[0x00, 0x80]`, -128 is `[0x80]` and -255 is `[0xff, 0x01]`.
+    /// The representation of value zero is `[0x00]`.
+    ///
+    /// See [`BigInt::from_signed_be_bytes`] for the reverse.
+    pub fn to_signed_be_bytes(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return vec![0];
+        }
+
+        let mut bytes = self.to_be_bytes();
+        if self.is_sign_negative() {
+            negate_twos_complement_be_bytes(&mut bytes);
+            if bytes[0] & 0x80 == 0 {
+                bytes.insert(0, 0xff);
+            }
+        } else if bytes[0] & 0x80 != 0 {
+            bytes.insert(0, 0);
+        }
+        bytes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BigIntError {
+    /// The value doesn't fit in the requested length.
+    TooLarge,
+    /// The value is negative, and the representation has no sign.
+    Negative,
+}
+
+impl Display for BigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BigIntError::TooLarge => write!(f, "Too large for the requested length"),
+            BigIntError::Negative => write!(f, "Negative value"),
+        }
+    }
 }
 
+impl core::error::Error for BigIntError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_to_hex() {
@@ -61,4 +239,204 @@ mod tests {
             assert_eq!(a.to_lower_hex(), output);
         }
     }
+
+    #[test]
+    fn test_to_fixed_hex() {
+        let data = [
+            (BigInt::from(0), 0, ""),
+            (BigInt::from(0), 4, "0000"),
+            (BigInt::from_hex("-00").unwrap(), 2, "00"),
+            (BigInt::from(0xf), 1, "f"),
+            (BigInt::from(0xabc), 3, "abc"),
+            (BigInt::from(0xabc), 4, "0abc"),
+            (BigInt::one() << 255, 64, &format!("8{}", "0".repeat(63))),
+        ];
+        for (n, hex_len, hex) in data {
+            assert_eq!(n.to_fixed_hex(hex_len).unwrap(), hex);
+        }
+
+        assert_eq!(BigInt::from(1).to_fixed_hex(0), Err(BigIntError::TooLarge));
+        assert_eq!(
+            BigInt::from(0xabc).to_fixed_hex(2),
+            Err(BigIntError::TooLarge)
+        );
+        assert_eq!(
+            (BigInt::one() << 256).to_fixed_hex(64),
+            Err(BigIntError::TooLarge)
+        );
+        assert_eq!(BigInt::from(-1).to_fixed_hex(4), Err(BigIntError::Negative));
+    }
+
+    #[test]
+    fn test_to_fixed_bytes() {
+        let data = [
+            (BigInt::from(0), 0, vec![]),
+            (BigInt::from(0), 2, vec![0, 0]),
+            (BigInt::from(0xff), 1, vec![0xff]),
+            (BigInt::from(0xabc), 4, vec![0, 0, 0x0a, 0xbc]),
+        ];
+        for (n, byte_len, bytes) in data {
+            assert_eq!(n.to_fixed_bytes(byte_len).unwrap(), bytes);
+        }
+
+        assert_eq!(
+            BigInt::from(0x100).to_fixed_bytes(1),
+            Err(BigIntError::TooLarge)
+        );
+        assert_eq!(
+            BigInt::from(-1).to_fixed_bytes(4),
+            Err(BigIntError::Negative)
+        );
+    }
+
+    #[quickcheck]
+    fn fixed_hex_matches_fixed_bytes(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(&hex.0).unwrap().abs();
+        let byte_len = n.to_be_bytes().len() + 2;
+        let fixed_hex = n.to_fixed_hex(byte_len * 2).unwrap();
+        fixed_hex == hex::encode_lower(&n.to_fixed_bytes(byte_len).unwrap())
+            && BigInt::from_hex(&fixed_hex).unwrap() == n
+    }
+
+    #[test]
+    fn test_to_binary_string() {
+        let data = [
+            (BigInt::from(0), "0"),
+            (BigInt::from(1), "1"),
+            (BigInt::from(-1), "-1"),
+            (BigInt::from(0b1001010), "1001010"),
+            (BigInt::from(i8::MIN), "-10000000"),
+            (BigInt::from_hex("-00").unwrap(), "0"),
+        ];
+        for (a, output) in data {
+            assert_eq!(a.to_binary_string(), output);
+        }
+
+        for i in 0..300 {
+            let binary = (BigInt::one() << i).to_binary_string();
+            assert_eq!(binary.len(), i + 1);
+            assert_eq!(binary.matches('1').count(), 1);
+            assert!(binary.starts_with('1'));
+        }
+    }
+
+    #[test]
+    fn test_to_signed_be_bytes() {
+        let data = [
+            (BigInt::from(0), "00"),
+            (BigInt::from_hex("-00").unwrap(), "00"),
+            (BigInt::from(1), "01"),
+            (BigInt::from(127), "7f"),
+            (BigInt::from(128), "0080"),
+            (BigInt::from(255), "00ff"),
+            (BigInt::from(256), "0100"),
+            (BigInt::from(-1), "ff"),
+            (BigInt::from(-128), "80"),
+            (BigInt::from(-129), "ff7f"),
+            (BigInt::from(-255), "ff01"),
+            (BigInt::from(-256), "ff00"),
+            (BigInt::from(i64::MIN), "8000000000000000"),
+        ];
+        for (n, hex) in data {
+            let bytes = n.to_signed_be_bytes();
+            assert_eq!(hex::encode_lower(&bytes), hex);
+            assert_eq!(BigInt::from_signed_be_bytes(&bytes), n);
+        }
+
+        assert_eq!(BigInt::from_signed_be_bytes(&[]), BigInt::zero());
+        // sign extension bytes
+        assert_eq!(BigInt::from_signed_be_bytes(&[0, 0, 1]), BigInt::one());
+        assert_eq!(
+            BigInt::from_signed_be_bytes(&[0xff, 0xff]),
+            BigInt::from(-1)
+        );
+
+        // The magnitude representation is unaffected.
+        assert_eq!(BigInt::from(-255).to_be_bytes(), [0xff]);
+        assert_eq!(BigInt::from(255).to_be_bytes(), [0xff]);
+    }
+
+    #[test]
+    fn test_u32_digits() {
+        // (n, sign, digits)
+        let data = [
+            (BigInt::from(0), Sign::Positive, vec![]),
+            (BigInt::from_hex("-00").unwrap(), Sign::Positive, vec![]),
+            (BigInt::from(1), Sign::Positive, vec![1]),
+            (BigInt::from(-1), Sign::Negative, vec![1]),
+            (BigInt::from(u32::MAX), Sign::Positive, vec![u32::MAX]),
+            (BigInt::from(1_u64 << 32), Sign::Positive, vec![0, 1]),
+            (
+                BigInt::from_hex("-0102030405060708090a").unwrap(),
+                Sign::Negative,
+                vec![0x0708090a, 0x03040506, 0x0102],
+            ),
+            (
+                BigInt::one() << 200,
+                Sign::Positive,
+                vec![0, 0, 0, 0, 0, 0, 0x100],
+            ),
+        ];
+        for (n, sign, digits) in data {
+            assert_eq!(n.to_u32_digits(), (sign, digits.clone()));
+            assert_eq!(BigInt::from_u32_digits(sign, &digits), n);
+        }
+
+        // Trailing zero digits are ignored, and zero is never negative.
+        assert_eq!(
+            BigInt::from_u32_digits(Sign::Negative, &[5, 0, 0]),
+            BigInt::from(-5)
+        );
+        let zero = BigInt::from_u32_digits(Sign::Negative, &[0, 0]);
+        assert!(zero.is_zero());
+        assert_eq!(zero.to_u32_digits(), (Sign::Positive, vec![]));
+        assert!(!zero.is_sign_negative());
+    }
+
+    #[test]
+    fn test_to_decimal_bytes() {
+        let data = [
+            (BigInt::from(0), &b"0"[..]),
+            (BigInt::from_hex("-00").unwrap(), b"0"),
+            (BigInt::from(255), b"255"),
+            (BigInt::from(-255), b"-255"),
+            (BigInt::from(u64::MAX), b"18446744073709551615"),
+            (
+                BigInt::from(10_000_000_000_000_000_000_u64),
+                b"10000000000000000000",
+            ),
+            (
+                BigInt::from(u128::MAX),
+                b"340282366920938463463374607431768211455",
+            ),
+        ];
+        for (n, decimal) in data {
+            assert_eq!(n.to_decimal_bytes(), decimal);
+        }
+    }
+
+    #[quickcheck]
+    fn decimal_bytes_round_trip(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(&hex.0).unwrap();
+        let decimal = n.to_decimal_bytes();
+        BigInt::from_decimal_bytes(&decimal).unwrap() == n
+            && BigInt::from_str_radix(&decimal, 10).unwrap() == n
+    }
+
+    #[quickcheck]
+    fn u32_digits_round_trip(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(&hex.0).unwrap();
+        let (sign, digits) = n.to_u32_digits();
+        BigInt::from_u32_digits(sign, &digits) == n && digits.last() != Some(&0)
+    }
+
+    #[quickcheck]
+    fn signed_be_bytes_round_trip(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(&hex.0).unwrap();
+        let bytes = n.to_signed_be_bytes();
+        let is_positive_with_magnitude_bytes = n.is_sign_negative()
+            || n.is_zero()
+            || bytes.strip_prefix(&[0]).unwrap_or(&bytes) == n.to_be_bytes();
+        BigInt::from_signed_be_bytes(&bytes) == n && is_positive_with_magnitude_bytes
+    }
 }
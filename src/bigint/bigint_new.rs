@@ -8,11 +8,14 @@
 
 use super::bigint_core::{BigInt, Sign};
 use super::bigint_vec::DigitVec;
-use super::bytes::be_bytes_to_le_digits;
+use super::bytes::{be_bytes_to_le_digits, negate_twos_complement_be_bytes};
 use crate::bigint::len::len_digits;
-use crate::crypto::codecs::{hex_to_bytes, CodecsError};
-use std::fmt;
-use std::fmt::Display;
+use crate::crypto::codecs::hex::{self, HexDecodingError};
+use crate::crypto::codecs::CodecsError;
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
 
 impl BigInt {
     /// Creates and initializes a `BigInt`.
@@ -20,7 +23,7 @@ impl BigInt {
     /// This is the designated constructor that all other constructors should call.
     pub(crate) fn new(digits: DigitVec, digits_len: usize, sign: Sign) -> BigInt {
         BigInt {
-            digits_storage: digits,
+            digits_storage: Cow::Owned(digits),
             digits_len,
             sign,
         }
@@ -34,30 +37,143 @@ impl BigInt {
         Self::new(digits, digits_len, sign)
     }
 
+    /// Creates a `BigInt` from `sign` and the magnitude as 32-bit digits, least significant
+    /// first, e.g., the output of [`BigInt::to_u32_digits`].
+    ///
+    /// Trailing zero digits are allowed. Empty `digits` represent zero, which is always
+    /// positive regardless of `sign`.
+    pub fn from_u32_digits(sign: Sign, digits: &[u32]) -> BigInt {
+        let bytes: Vec<u8> = digits
+            .iter()
+            .rev()
+            .flat_map(|digit| digit.to_be_bytes())
+            .skip_while(|&byte| byte == 0)
+            .collect();
+        if bytes.is_empty() {
+            return Self::zero();
+        }
+
+        Self::from_be_bytes(&bytes, sign)
+    }
+
+    /// Creates a `BigInt` from its two's complement representation `bytes`
+    /// in big-endian byte order, e.g., the output of [`BigInt::to_signed_be_bytes`].
+    ///
+    /// Leading sign extension bytes are allowed. Empty `bytes` represent zero.
+    pub fn from_signed_be_bytes(bytes: &[u8]) -> BigInt {
+        match bytes.first() {
+            Some(first) if first & 0x80 != 0 => {
+                let mut magnitude = bytes.to_vec();
+                negate_twos_complement_be_bytes(&mut magnitude);
+                Self::from_be_bytes(&magnitude, Sign::Negative)
+            }
+            Some(_) => Self::from_be_bytes(bytes, Sign::Positive),
+            None => Self::zero(),
+        }
+    }
+
+    /// Creates a `BigInt` from exactly `len` bytes in big-endian order, e.g., a fixed-size
+    /// field of an encoding. Leading zero bytes are part of the length.
+    ///
+    /// # Errors
+    ///
+    /// Will return `ParseIntError::InvalidLength` if `bytes` isn't `len` bytes long.
+    pub fn from_be_bytes_exact(
+        bytes: &[u8],
+        len: usize,
+        sign: Sign,
+    ) -> Result<BigInt, ParseIntError> {
+        if bytes.len() != len {
+            return Err(ParseIntError::InvalidLength {
+                expected: len,
+                found: bytes.len(),
+            });
+        }
+        if bytes.is_empty() {
+            return Ok(Self::zero());
+        }
+
+        Ok(Self::from_be_bytes(bytes, sign))
+    }
+
     /// Creates a `BigInt` from hexadecimal representation `hex`.
     /// `hex` must be 1-byte aligned -- having an even number of digits.
-    /// `hex` is expected to have an optional sign prefix '+' or '-'.
+    /// `hex` is expected to have an optional sign prefix '+' or '-', followed by an optional
+    /// prefix "0x" or "0X". The digits are case-insensitive.
+    ///
+    /// The position of an invalid character is reported as an index of `hex`, including
+    /// the prefixes.
     pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<BigInt, ParseIntError> {
-        let hex = hex.as_ref();
-        if hex.is_empty() {
+        let input = hex.as_ref();
+        if input.is_empty() {
             return Ok(BigInt::from(0));
         }
 
-        let (sign, hex) = match *hex.first().unwrap() as char {
-            '-' => (Sign::Negative, &hex[1..]),
-            '+' => (Sign::Positive, &hex[1..]),
-            _ => (Sign::Positive, hex),
+        let (sign, hex) = match *input.first().unwrap() as char {
+            '-' => (Sign::Negative, &input[1..]),
+            '+' => (Sign::Positive, &input[1..]),
+            _ => (Sign::Positive, input),
         };
+        let hex = strip_hex_prefix(hex);
 
-        // "-" and "+" alone are both invalid hex input.
+        // "-" and "+" alone are both invalid hex input, so are the prefixes alone.
         if hex.is_empty() {
             return Err(ParseIntError::InvalidInput);
         }
 
-        match hex_to_bytes(hex) {
-            Ok(bytes) => Ok(Self::from_be_bytes(&bytes, sign)),
-            Err(err) => Err(ParseIntError::CodecsError(err)),
+        let offset = input.len() - hex.len();
+        if let Some(index) = hex.iter().position(|c| !c.is_ascii_hexdigit()) {
+            return Err(ParseIntError::HexDecodingError(
+                HexDecodingError::InvalidCharacter {
+                    position: offset + index,
+                    found: hex[index],
+                },
+            ));
         }
+
+        // The characters are checked above, an odd length is the only error left.
+        hex::decode(hex)
+            .map(|bytes| Self::from_be_bytes(&bytes, sign))
+            .map_err(ParseIntError::HexDecodingError)
+    }
+
+    /// Creates a `BigInt` from binary representation `binary`,
+    /// with the most significant bit first, e.g., "1001010".
+    /// `binary` is expected to have an optional sign prefix '+' or '-'.
+    pub fn from_binary_string<T: AsRef<[u8]>>(binary: T) -> Result<BigInt, ParseIntError> {
+        let binary = binary.as_ref();
+        let (sign, binary) = match binary.first() {
+            Some(b'-') => (Sign::Negative, &binary[1..]),
+            Some(b'+') => (Sign::Positive, &binary[1..]),
+            _ => (Sign::Positive, binary),
+        };
+
+        if binary.is_empty() {
+            return Err(ParseIntError::InvalidInput);
+        }
+
+        // Packs the bits into big-endian bytes,
+        // the first byte holds the leading `binary.len() % 8` bits.
+        let mut bytes = Vec::with_capacity(binary.len().div_ceil(8));
+        let first_len = match binary.len() % 8 {
+            0 => 8,
+            len => len,
+        };
+        let (first, rest) = binary.split_at(first_len);
+        for chunk in core::iter::once(first).chain(rest.chunks(8)) {
+            let mut byte = 0_u8;
+            for &c in chunk {
+                let bit = match c {
+                    b'0' => 0,
+                    b'1' => 1,
+                    _ => return Err(ParseIntError::InvalidInput),
+                };
+                byte = (byte << 1) | bit;
+            }
+            bytes.push(byte);
+        }
+
+        Ok(Self::from_be_bytes(&bytes, sign))
     }
 
     /// Creates a `BigInt` from integer representation in a given base.
@@ -98,13 +214,52 @@ impl BigInt {
                 return Err(ParseIntError::InvalidInput);
             }
 
-            result = result * &radix_bigint + BigInt::from(n);
+            result *= &radix_bigint;
+            result += BigInt::from(n);
         }
 
         result.sign = sign;
         Ok(result)
     }
 
+    /// Creates a `BigInt` from the decimal ASCII digits `bytes`, e.g., `b"255"`, as some
+    /// wire protocols encode integers. `bytes` is expected to have an optional sign prefix
+    /// '+' or '-', followed by the digits `b'0'` to `b'9'` only.
+    ///
+    /// See [`BigInt::to_decimal_bytes`] for the reverse.
+    pub fn from_decimal_bytes(bytes: &[u8]) -> Result<BigInt, ParseIntError> {
+        let (sign, digits) = match bytes.first() {
+            Some(b'-') => (Sign::Negative, &bytes[1..]),
+            Some(b'+') => (Sign::Positive, &bytes[1..]),
+            _ => (Sign::Positive, bytes),
+        };
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(ParseIntError::InvalidInput);
+        }
+
+        // Horner's method in base 10^19, the leading chunk takes the remaining
+        // `digits.len() % 19` digits.
+        let first_len = match digits.len() % DECIMAL_CHUNK_LEN {
+            0 => DECIMAL_CHUNK_LEN,
+            len => len,
+        };
+        let (first, rest) = digits.split_at(first_len);
+        let base = BigInt::from(10_u64.pow(DECIMAL_CHUNK_LEN as u32));
+        let mut result = BigInt::zero();
+        for chunk in core::iter::once(first).chain(rest.chunks(DECIMAL_CHUNK_LEN)) {
+            let n = chunk
+                .iter()
+                .fold(0_u64, |n, &digit| n * 10 + u64::from(digit - b'0'));
+            result *= &base;
+            result += BigInt::from(n);
+        }
+
+        if !result.is_zero() {
+            result.sign = sign;
+        }
+        Ok(result)
+    }
+
     /// Creates a `BigInt` from `u128`.
     pub(crate) fn from_u128(n: u128, sign: Sign) -> BigInt {
         let bytes = n.to_be_bytes();
@@ -147,29 +302,71 @@ impl BigInt {
     }
 }
 
+/// The number of decimal digits converted at a time, 10^19 being the largest power of 10
+/// fitting in `u64`.
+pub(crate) const DECIMAL_CHUNK_LEN: usize = 19;
+
+/// Returns `hex` without the prefix "0x" or "0X", if any.
+pub(crate) fn strip_hex_prefix(hex: &[u8]) -> &[u8] {
+    hex.strip_prefix(b"0x")
+        .or_else(|| hex.strip_prefix(b"0X"))
+        .unwrap_or(hex)
+}
+
+/// Creates a `BigInt` from hex or decimal string.
+///
+/// The prefix "0x" or "0X" must present for hex, after the optional sign prefix '+' or '-'.
+///
+/// ```text
+/// let n1: BigInt = "-0x12EF".try_into().unwrap();
+/// let n2: BigInt = "-4847".try_into().unwrap();
+/// assert_eq!(n1, n2);
+/// ```
+impl TryFrom<&str> for BigInt {
+    type Error = ParseIntError;
+
+    fn try_from(s: &str) -> Result<BigInt, ParseIntError> {
+        let unsigned = s.strip_prefix(['+', '-']).unwrap_or(s);
+        if strip_hex_prefix(unsigned.as_bytes()).len() < unsigned.len() {
+            BigInt::from_hex(s)
+        } else {
+            BigInt::from_str_radix(s, 10)
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ParseIntError {
     CodecsError(CodecsError),
+    HexDecodingError(HexDecodingError),
     InvalidInput,
+    /// The input isn't of the fixed length, in bytes.
+    InvalidLength {
+        expected: usize,
+        found: usize,
+    },
 }
 
 impl Display for ParseIntError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ParseIntError::CodecsError(err) => write!(f, "Codecs error: {err}"),
+            ParseIntError::HexDecodingError(err) => write!(f, "Hex decoding error: {err}"),
             ParseIntError::InvalidInput => write!(f, "Invalid input"),
+            ParseIntError::InvalidLength { expected, found } => {
+                write!(f, "Invalid length: {found} bytes, expected {expected}")
+            }
         }
     }
 }
 
-impl std::error::Error for ParseIntError {}
+impl core::error::Error for ParseIntError {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bigint::bigint_new::ParseIntError;
-    use crate::crypto::codecs::CodecsError;
     use crate::testing_tools::quickcheck::{BigIntHexString, HexString};
     use ::quickcheck_macros::quickcheck;
 
@@ -197,6 +394,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_be_bytes_exact() {
+        let data = [
+            // (bytes, len, sign, result)
+            (&[][..], 0, Sign::Positive, Ok(BigInt::zero())),
+            (&[0, 0, 1], 3, Sign::Positive, Ok(BigInt::one())),
+            (&[0x12, 0x34], 2, Sign::Negative, Ok(BigInt::from(-0x1234))),
+            (
+                &[0xff; 32],
+                32,
+                Sign::Positive,
+                Ok(BigInt::from_hex("ff".repeat(32)).unwrap()),
+            ),
+            // too short
+            (
+                &[0xff; 31],
+                32,
+                Sign::Positive,
+                Err(ParseIntError::InvalidLength {
+                    expected: 32,
+                    found: 31,
+                }),
+            ),
+            (
+                &[],
+                1,
+                Sign::Positive,
+                Err(ParseIntError::InvalidLength {
+                    expected: 1,
+                    found: 0,
+                }),
+            ),
+            // too long, even with leading zeros
+            (
+                &[0; 33],
+                32,
+                Sign::Positive,
+                Err(ParseIntError::InvalidLength {
+                    expected: 32,
+                    found: 33,
+                }),
+            ),
+        ];
+        for (bytes, len, sign, result) in data {
+            assert_eq!(BigInt::from_be_bytes_exact(bytes, len, sign), result);
+        }
+    }
+
+    #[test]
+    fn test_from_hex_with_prefix_and_mixed_case() {
+        let n = BigInt::from(0xdead);
+        for hex in ["0xDEAD", "0xdead", "DEAD", "dead", "0XdEaD", "+0xDeAd"] {
+            assert_eq!(BigInt::from_hex(hex).unwrap(), n, "{hex}");
+        }
+        assert_eq!(BigInt::from_hex("-0XDEAD").unwrap(), -n);
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let data = [
+            ("0x12ef", 4847),
+            ("0X12EF", 4847),
+            ("-0x12Ef", -4847),
+            ("4847", 4847),
+            ("+4847", 4847),
+            ("-4847", -4847),
+        ];
+        for (s, n) in data {
+            let a: BigInt = s.try_into().unwrap();
+            assert_eq!(a, BigInt::from(n), "{s}");
+        }
+
+        assert!(BigInt::try_from("0x12eg").is_err());
+        assert!(BigInt::try_from("12ef").is_err());
+    }
+
     #[quickcheck]
     fn from_hex_and_to_hex_double_conversion(hex: BigIntHexString) -> bool {
         let n1 = BigInt::from_hex(hex.0).unwrap();
@@ -208,20 +481,46 @@ mod tests {
     #[test]
     fn test_from_hex_with_errors() {
         let data = [
-            ("0", ParseIntError::CodecsError(CodecsError::NotByteAligned)),
+            (
+                "0",
+                ParseIntError::HexDecodingError(HexDecodingError::OddLength),
+            ),
             (
                 "79be661",
-                ParseIntError::CodecsError(CodecsError::NotByteAligned),
+                ParseIntError::HexDecodingError(HexDecodingError::OddLength),
             ),
             (
                 "-0",
-                ParseIntError::CodecsError(CodecsError::NotByteAligned),
+                ParseIntError::HexDecodingError(HexDecodingError::OddLength),
+            ),
+            (
+                "-0x79be6",
+                ParseIntError::HexDecodingError(HexDecodingError::OddLength),
+            ),
+            (
+                "0x79bG66",
+                ParseIntError::HexDecodingError(HexDecodingError::InvalidCharacter {
+                    position: 5,
+                    found: b'G',
+                }),
             ),
             (
-                "-0x79be66",
-                ParseIntError::CodecsError(CodecsError::InvalidCharFound),
+                "-0x0x79be66",
+                ParseIntError::HexDecodingError(HexDecodingError::InvalidCharacter {
+                    position: 4,
+                    found: b'x',
+                }),
+            ),
+            (
+                "+79 e66",
+                ParseIntError::HexDecodingError(HexDecodingError::InvalidCharacter {
+                    position: 3,
+                    found: b' ',
+                }),
             ),
             ("-", ParseIntError::InvalidInput),
+            ("0x", ParseIntError::InvalidInput),
+            ("-0X", ParseIntError::InvalidInput),
         ];
         for (a_hex, err) in data {
             assert_eq!(BigInt::from_hex(a_hex).unwrap_err(), err);
@@ -252,6 +551,66 @@ mod tests {
         let _ = BigInt::from_str_radix("123456", 37);
     }
 
+    #[test]
+    fn test_from_decimal_bytes() {
+        // (input, hex)
+        let data = [
+            (&b"0"[..], "00"),
+            (b"-0", "00"),
+            (b"255", "ff"),
+            (b"+000255", "ff"),
+            (b"-255", "-ff"),
+            (b"18446744073709551615", "ffffffffffffffff"),
+            (b"18446744073709551616", "010000000000000000"),
+            (
+                b"340282366920938463463374607431768211455",
+                "ffffffffffffffffffffffffffffffff",
+            ),
+        ];
+        for (input, hex) in data {
+            assert_eq!(
+                BigInt::from_decimal_bytes(input).unwrap().to_lower_hex(),
+                hex
+            );
+        }
+
+        for input in [&b""[..], b"-", b"+", b"12a", b" 1", b"1 ", b"0x10", b"--1"] {
+            assert_eq!(
+                BigInt::from_decimal_bytes(input).unwrap_err(),
+                ParseIntError::InvalidInput
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_binary_string() {
+        let data = [
+            ("0", "00"),
+            ("-0", "00"),
+            ("000001", "01"),
+            ("+1001010", "4a"),
+            ("-10000000", "-80"),
+            ("111111111", "01ff"),
+        ];
+        for (binary, output) in data {
+            let a = BigInt::from_binary_string(binary).unwrap();
+            assert_eq!(a.to_lower_hex(), output);
+        }
+
+        for binary in ["", "-", "+", "102", "0b101", " 1", "1-"] {
+            assert_eq!(
+                BigInt::from_binary_string(binary).unwrap_err(),
+                ParseIntError::InvalidInput
+            );
+        }
+    }
+
+    #[quickcheck]
+    fn from_binary_string_and_to_binary_string_double_conversion(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(hex.0).unwrap();
+        BigInt::from_binary_string(n.to_binary_string()).unwrap() == n
+    }
+
     #[quickcheck]
     fn from_str_radix_16_eq_from_hex(hex: HexString) -> bool {
         let a = BigInt::from_hex(&hex.0).unwrap();
@@ -0,0 +1,85 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the conversions between `BigInt` and `num_bigint::BigInt`, through the
+//! big-endian bytes of the magnitude.
+
+use super::bigint_core::{BigInt, Sign};
+
+impl From<&num_bigint::BigInt> for BigInt {
+    fn from(n: &num_bigint::BigInt) -> Self {
+        let (sign, bytes) = n.to_bytes_be();
+        let sign = match sign {
+            num_bigint::Sign::Minus => Sign::Negative,
+            num_bigint::Sign::NoSign | num_bigint::Sign::Plus => Sign::Positive,
+        };
+        BigInt::from_be_bytes(&bytes, sign)
+    }
+}
+
+impl From<num_bigint::BigInt> for BigInt {
+    fn from(n: num_bigint::BigInt) -> Self {
+        BigInt::from(&n)
+    }
+}
+
+impl From<&BigInt> for num_bigint::BigInt {
+    fn from(n: &BigInt) -> Self {
+        // `num_bigint` takes the sign of zero as `NoSign` regardless.
+        let sign = match n.sign {
+            Sign::Negative => num_bigint::Sign::Minus,
+            Sign::Positive => num_bigint::Sign::Plus,
+        };
+        num_bigint::BigInt::from_bytes_be(sign, &n.to_be_bytes())
+    }
+}
+
+impl From<BigInt> for num_bigint::BigInt {
+    fn from(n: BigInt) -> Self {
+        num_bigint::BigInt::from(&n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn test_conversions() {
+        // (hex, decimal)
+        let data = [
+            ("00", "0"),
+            ("-00", "0"),
+            ("01", "1"),
+            ("-01", "-1"),
+            ("ff", "255"),
+            ("-0100", "-256"),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+            ),
+            (
+                "-0123456789abcdef0123456789abcdef0123456789abcdef",
+                "-27898229935051914142968983831921934135401027036219428335",
+            ),
+        ];
+        for (hex, decimal) in data {
+            let n = BigInt::from_hex(hex).unwrap();
+            let num = num_bigint::BigInt::from(&n);
+            assert_eq!(num.to_string(), decimal);
+            assert_eq!(BigInt::from(num), n);
+        }
+    }
+
+    #[quickcheck]
+    fn num_bigint_round_trip(hex: BigIntHexString) -> bool {
+        let n = BigInt::from_hex(&hex.0).unwrap();
+        let num = num_bigint::BigInt::parse_bytes(hex.0.as_bytes(), 16).unwrap();
+        num_bigint::BigInt::from(&n) == num && BigInt::from(num) == n
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the construction of `BigInt` from static digits, e.g., in the initializers of
+//! statics, without parsing or allocating at runtime.
+
+use super::bigint_core::{BigInt, Sign};
+use super::digit::Digit;
+use alloc::borrow::Cow;
+
+impl BigInt {
+    /// Creates a `BigInt` borrowing `digits`, least significant first, without allocating.
+    ///
+    /// Trailing zero digits are allowed. Zero is always positive regardless of `sign`.
+    /// The digits are copied to an owned storage by the first operation writing to it,
+    /// e.g., an in-place addition.
+    ///
+    /// Will panic if `digits` is empty.
+    pub const fn from_static_digits(digits: &'static [Digit], sign: Sign) -> BigInt {
+        assert!(!digits.is_empty());
+
+        let mut digits_len = digits.len();
+        while digits_len > 1 && digits[digits_len - 1] == 0 {
+            digits_len -= 1;
+        }
+        let sign = if digits_len == 1 && digits[0] == 0 {
+            Sign::Positive
+        } else {
+            sign
+        };
+
+        BigInt {
+            digits_storage: Cow::Borrowed(digits),
+            digits_len,
+            sign,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+const HEX_CHARS_PER_DIGIT: usize = Digit::BITS as usize / 4;
+
+/// Returns the number of digits to store the hexadecimal `hex`.
+#[cfg(feature = "std")]
+pub(crate) const fn hex_digits_len(hex: &str) -> usize {
+    hex.len().div_ceil(HEX_CHARS_PER_DIGIT)
+}
+
+/// Converts the hexadecimal `hex`, without the prefix "0x", to `N` digits, least
+/// significant first.
+///
+/// Will panic if `hex` is empty or invalid, or doesn't fit in `N` digits. Evaluated in a
+/// const context, the panic is a compile error.
+#[cfg(feature = "std")]
+pub(crate) const fn hex_to_digits<const N: usize>(hex: &str) -> [Digit; N] {
+    let hex = hex.as_bytes();
+    assert!(!hex.is_empty(), "empty hex");
+    assert!(hex.len() <= N * HEX_CHARS_PER_DIGIT, "hex too long");
+
+    let mut digits = [0; N];
+    let mut i = 0;
+    // From the least significant char, at the end of `hex`.
+    while i < hex.len() {
+        let char = hex[hex.len() - 1 - i];
+        let nibble = match char {
+            b'0'..=b'9' => char - b'0',
+            b'a'..=b'f' => char - b'a' + 10,
+            b'A'..=b'F' => char - b'A' + 10,
+            _ => panic!("invalid hex char"),
+        };
+        digits[i / HEX_CHARS_PER_DIGIT] |= (nibble as Digit) << (i % HEX_CHARS_PER_DIGIT * 4);
+        i += 1;
+    }
+
+    digits
+}
+
+/// Creates a positive `BigInt` from a hexadecimal string literal, without the prefix "0x".
+///
+/// The digits are computed at compile time and stored in a static, the `BigInt` borrows
+/// them. The macro can be used in the initializers of statics.
+#[cfg(feature = "std")]
+macro_rules! bigint_const {
+    ($hex:expr) => {{
+        const HEX: &str = $hex;
+        static DIGITS: [crate::bigint::Digit;
+            crate::bigint::bigint_static::hex_digits_len(HEX)] =
+            crate::bigint::bigint_static::hex_to_digits(HEX);
+        crate::bigint::BigInt::from_static_digits(&DIGITS, crate::bigint::Sign::Positive)
+    }};
+}
+
+#[cfg(feature = "std")]
+pub(crate) use bigint_const;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_static_digits() {
+        static DIGITS: [Digit; 3] = [3, 2, 0];
+        let n = BigInt::from_static_digits(&DIGITS, Sign::Negative);
+        assert_eq!(n.as_digits(), &[3, 2]);
+        assert_eq!(
+            n,
+            -(BigInt::from(2) << Digit::BITS as usize) - BigInt::from(3)
+        );
+        assert!(matches!(n.digits_storage, Cow::Borrowed(_)));
+
+        // zero is always positive
+        static ZERO: [Digit; 2] = [0, 0];
+        let n = BigInt::from_static_digits(&ZERO, Sign::Negative);
+        assert!(n.is_zero());
+        assert_eq!(n.sign, Sign::Positive);
+    }
+
+    #[test]
+    fn test_bigint_const() {
+        let hex = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+        let n =
+            bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        assert_eq!(n, BigInt::from_hex(hex).unwrap());
+        assert_eq!(n.as_digits().len(), hex_digits_len(hex));
+
+        // odd lengths, leading zeros and uppercase chars
+        assert_eq!(bigint_const!("7"), BigInt::from(7));
+        assert_eq!(bigint_const!("0"), BigInt::from(0));
+        assert_eq!(bigint_const!("0001"), BigInt::from(1));
+        assert_eq!(
+            bigint_const!("1FFFFFFFFFFFFFFFFF"),
+            BigInt::from_hex("1ffffffffffffffffF").unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid hex char")]
+    fn test_hex_to_digits_invalid_char() {
+        let _ = hex_to_digits::<4>("0x01");
+    }
+
+    #[test]
+    #[should_panic(expected = "hex too long")]
+    fn test_hex_to_digits_too_long() {
+        let _ = hex_to_digits::<1>(&"f".repeat(HEX_CHARS_PER_DIGIT + 1));
+    }
+
+    #[test]
+    fn test_growing_switches_to_owned_storage() {
+        const HEX: &str = "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        static DIGITS: [Digit; hex_digits_len(HEX)] = hex_to_digits(HEX);
+        let max = BigInt::from_hex(HEX).unwrap();
+
+        // in-place addition with a carry out of the static digits
+        let mut n = BigInt::from_static_digits(&DIGITS, Sign::Positive);
+        n += &BigInt::from(1);
+        assert!(matches!(n.digits_storage, Cow::Owned(_)));
+        assert_eq!(n, &max + &BigInt::from(1));
+
+        // in-place subtraction within the static digits
+        let mut n = BigInt::from_static_digits(&DIGITS, Sign::Positive);
+        n -= &BigInt::from(1);
+        assert_eq!(n, &max - &BigInt::from(1));
+
+        // in-place multiplication
+        let mut n = BigInt::from_static_digits(&DIGITS, Sign::Positive);
+        n *= &max;
+        assert_eq!(n, &max * &max);
+
+        // shifts, on a clone which borrows the static digits as well
+        let n = BigInt::from_static_digits(&DIGITS, Sign::Positive);
+        assert!(matches!(n.clone().digits_storage, Cow::Borrowed(_)));
+        assert_eq!(&n << 100, &max << 100);
+        assert_eq!(&n >> 100, &max >> 100);
+
+        // the static digits are intact
+        let n = BigInt::from_static_digits(&DIGITS, Sign::Positive);
+        assert_eq!(n, max);
+        assert!(DIGITS.iter().all(|&digit| digit == Digit::MAX));
+    }
+}
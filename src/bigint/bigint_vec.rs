@@ -5,6 +5,8 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::digit::Digit;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// A vector to store digits representing a big unsigned integer.
 pub(crate) type DigitVec = Vec<Digit>;
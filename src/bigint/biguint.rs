@@ -5,12 +5,14 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::bigint_core::{BigInt, Sign};
-use crate::bigint::bigint_new::ParseIntError;
-use std::fmt;
-use std::fmt::Display;
-use std::ops::{Add, Mul, Shr, Sub};
-
-#[derive(Debug, PartialEq, Eq, PartialOrd)]
+use crate::bigint::bigint_new::{strip_hex_prefix, ParseIntError};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
+use core::ops::{Add, Mul, Shr, Sub};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd)]
 pub struct BigUint(BigInt);
 
 macro_rules! impl_biguint_from_unsigned_int {
@@ -35,6 +37,7 @@ impl BigUint {
         BigUint(BigInt::from_be_bytes(bytes, Sign::Positive))
     }
 
+    #[cfg(feature = "std")]
     pub(crate) fn from_bigint(n: BigInt) -> Option<BigUint> {
         if n.is_sign_negative() {
             None
@@ -43,6 +46,11 @@ impl BigUint {
         }
     }
 
+    #[cfg(feature = "std")]
+    pub(crate) fn as_bigint(&self) -> &BigInt {
+        &self.0
+    }
+
     pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<BigUint, ParseIntError> {
         let n = BigInt::from_hex(hex)?;
         if n.is_sign_negative() {
@@ -67,11 +75,11 @@ impl BigUint {
 
 /// Creates a `BigUint` from hex or decimal string.
 ///
-/// The prefix "0x" must present for hex.
+/// The prefix "0x" or "0X" must present for hex.
 /// Sign prefixes '+' and '-' are not allowed.
 ///
 /// ```text
-/// let n1: BigUint = "0x12ef".try_into().unwrap();
+/// let n1: BigUint = "0x12EF".try_into().unwrap();
 /// let n2: BigUint = "4847".try_into().unwrap();
 /// assert_eq!(n1, n2);
 /// ```
@@ -79,8 +87,9 @@ impl TryFrom<&str> for BigUint {
     type Error = ParseIntError;
 
     fn try_from(s: &str) -> Result<BigUint, ParseIntError> {
-        if let Some(s) = s.strip_prefix("0x") {
-            BigUint::from_hex(s)
+        let hex = strip_hex_prefix(s.as_bytes());
+        if hex.len() < s.len() {
+            BigUint::from_hex(hex)
         } else {
             BigUint::from_str_radix(s, 10)
         }
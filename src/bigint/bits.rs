@@ -8,6 +8,8 @@ use super::bigint_core::BigInt;
 use crate::bigint::bigint_slice::{is_valid_biguint_slice, BigUintSlice};
 use crate::bigint::digit::Digit;
 use crate::bigint::zero::is_zero_digits;
+use alloc::vec;
+use alloc::vec::Vec;
 
 pub(crate) fn bit_len_digits(a: &BigUintSlice) -> usize {
     debug_assert!(is_valid_biguint_slice(a));
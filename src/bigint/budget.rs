@@ -0,0 +1,417 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the optional budget bounding the work of long-running operations.
+//!
+//! The inner loops of multiplication, division and GCD charge the budget of the current
+//! thread with the digit operations of each outer iteration. Once the budget is exceeded,
+//! the loops stop early and the result is discarded: the fallible entry points, e.g.,
+//! [`BigInt::try_div_rem`], return [`BudgetExceeded`], the others panic.
+//!
+//! Without a budget installed, nothing is charged and nothing changes.
+//!
+//! The budgets are thread-local, and require the feature "std". Without it, no budget can
+//! be installed, and `BudgetExceeded` is never returned.
+
+#[cfg(feature = "std")]
+use super::bigint_core::{BigInt, Sign};
+#[cfg(feature = "std")]
+use super::divrem::div_rem;
+#[cfg(feature = "std")]
+use super::gcd::gcd;
+#[cfg(feature = "std")]
+use crate::math::modular::{invert, modulo};
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::fmt;
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use core::marker::PhantomData;
+
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+struct Budget {
+    remaining: u64,
+    exceeded: bool,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static BUDGET: Cell<Option<Budget>> = const { Cell::new(None) };
+    // The number of fallible entry points being run, which report an exceeded budget
+    // instead of panicking.
+    static FALLIBLE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The guard of a budget of digit operations, installed for the current thread.
+///
+/// The budget is removed when the guard is dropped, restoring the budget installed
+/// before, if any. A nested budget is capped by the remaining of the outer one, and its
+/// consumption is charged to the outer one on drop. Guards must be dropped in the reverse
+/// order of entering.
+#[cfg(feature = "std")]
+#[must_use]
+pub struct BigIntBudget {
+    previous: Option<Budget>,
+    limit: u64,
+    // Stays on the thread of the budget.
+    _not_send: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "std")]
+impl BigIntBudget {
+    /// Installs a budget of `limit` digit operations for the current thread.
+    ///
+    /// E.g., `let _budget = BigIntBudget::enter(1_000_000);`
+    pub fn enter(limit: u64) -> BigIntBudget {
+        let previous = BUDGET.get();
+        let limit = match previous {
+            Some(previous) => limit.min(previous.remaining),
+            None => limit,
+        };
+        BUDGET.set(Some(Budget {
+            remaining: limit,
+            exceeded: false,
+        }));
+
+        BigIntBudget {
+            previous,
+            limit,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the remaining digit operations of the budget of the current thread, or
+    /// `None` if no budget is installed.
+    pub fn remaining() -> Option<u64> {
+        BUDGET.get().map(|budget| budget.remaining)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for BigIntBudget {
+    fn drop(&mut self) {
+        let current = BUDGET.get().unwrap();
+        let previous = self.previous.map(|previous| {
+            let remaining = previous
+                .remaining
+                .saturating_sub(self.limit - current.remaining);
+            Budget {
+                remaining,
+                exceeded: previous.exceeded || (current.exceeded && remaining == 0),
+            }
+        });
+        BUDGET.set(previous);
+    }
+}
+
+/// Charges `cost` digit operations to the budget of the current thread.
+///
+/// Returns false if the budget is exceeded, now or before, in which case the caller should
+/// stop as soon as possible.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn charge(cost: usize) -> bool {
+    BUDGET.with(|cell| match cell.get() {
+        None => true,
+        Some(Budget { exceeded: true, .. }) => false,
+        Some(Budget { remaining, .. }) => {
+            let cost = cost as u64;
+            let within = remaining >= cost;
+            cell.set(Some(Budget {
+                remaining: if within { remaining - cost } else { 0 },
+                exceeded: !within,
+            }));
+            within
+        }
+    })
+}
+
+/// Returns true if the budget of the current thread is exceeded.
+///
+/// Checked by the operations between their steps, for a step can't go on with the
+/// incomplete results of the previous ones.
+#[cfg(feature = "std")]
+pub(crate) fn is_exceeded() -> bool {
+    BUDGET.get().is_some_and(|budget| budget.exceeded)
+}
+
+/// Panics if the budget of the current thread is exceeded, unless a fallible entry point
+/// is being run.
+///
+/// Called by the operations after charging, for their results are then incomplete.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn assert_within_budget() {
+    if FALLIBLE_DEPTH.get() == 0 && is_exceeded() {
+        panic!("{}", BudgetExceeded);
+    }
+}
+
+/// Runs `f` as a fallible entry point, returning an error if the budget is exceeded
+/// before or during running.
+#[cfg(feature = "std")]
+fn run_within_budget<T>(f: impl FnOnce() -> T) -> Result<T, BudgetExceeded> {
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
+
+    // Restores the depth on drop, even if `f` panics.
+    struct FallibleScope;
+    impl Drop for FallibleScope {
+        fn drop(&mut self) {
+            FALLIBLE_DEPTH.set(FALLIBLE_DEPTH.get() - 1);
+        }
+    }
+
+    FALLIBLE_DEPTH.set(FALLIBLE_DEPTH.get() + 1);
+    let scope = FallibleScope;
+    let output = f();
+    drop(scope);
+
+    if is_exceeded() {
+        Err(BudgetExceeded)
+    } else {
+        Ok(output)
+    }
+}
+
+#[cfg(feature = "std")]
+impl BigInt {
+    /// Returns the quotient and the remainder of `self / divisor`, as `/` and `%` do, or
+    /// an error if the budget of the current thread is exceeded.
+    ///
+    /// Will panic if `divisor` is 0.
+    pub fn try_div_rem(&self, divisor: &BigInt) -> Result<(BigInt, BigInt), BudgetExceeded> {
+        run_within_budget(|| div_rem(self, divisor))
+    }
+
+    /// Returns the non-negative greatest common divisor of `self` and `other`, or an error
+    /// if the budget of the current thread is exceeded.
+    pub fn try_gcd(&self, other: &BigInt) -> Result<BigInt, BudgetExceeded> {
+        run_within_budget(|| {
+            let mut a = self.clone();
+            a.sign = Sign::Positive;
+            let mut b = other.clone();
+            b.sign = Sign::Positive;
+            if a < b {
+                core::mem::swap(&mut a, &mut b);
+            }
+
+            if b.is_zero() || a == b {
+                a
+            } else {
+                let (_, _, v) = gcd(&a, &b);
+                v
+            }
+        })
+    }
+
+    /// Returns the modulo multiplicative inverse of `self` under modulo `n`, `None` if
+    /// `self` is not invertible, or an error if the budget of the current thread is
+    /// exceeded.
+    ///
+    /// `n` must be greater than 1.
+    pub fn try_invert(&self, n: &BigInt) -> Result<Option<BigInt>, BudgetExceeded> {
+        assert!(*n > 1);
+
+        run_within_budget(|| {
+            let a = modulo(self, n);
+            if a.is_zero() {
+                None
+            } else {
+                invert(&a, n)
+            }
+        })
+    }
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn charge(_cost: usize) -> bool {
+    true
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn assert_within_budget() {}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn is_exceeded() -> bool {
+    false
+}
+
+/// The error of an operation aborted for exceeding the budget installed with
+/// [`BigIntBudget::enter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded;
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BigInt operation budget exceeded")
+    }
+}
+
+impl core::error::Error for BudgetExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn huge(digits: usize) -> BigInt {
+        // All the bits set
+        (BigInt::one() << (digits * 64)) - BigInt::one()
+    }
+
+    #[test]
+    fn test_without_budget() {
+        assert_eq!(BigIntBudget::remaining(), None);
+
+        let a = BigInt::from(1001);
+        assert_eq!(
+            a.try_div_rem(&BigInt::from(-10)),
+            Ok((BigInt::from(-100), BigInt::from(1)))
+        );
+        assert_eq!(a.try_gcd(&BigInt::from(-35)), Ok(BigInt::from(7)));
+        assert_eq!(
+            BigInt::from(-12).try_gcd(&BigInt::zero()),
+            Ok(BigInt::from(12))
+        );
+        assert_eq!(
+            BigInt::from(3).try_invert(&BigInt::from(7)),
+            Ok(Some(BigInt::from(5)))
+        );
+        assert_eq!(BigInt::from(14).try_invert(&BigInt::from(7)), Ok(None));
+        assert_eq!(BigInt::from(6).try_invert(&BigInt::from(9)), Ok(None));
+    }
+
+    #[test]
+    fn test_within_budget() {
+        let _budget = BigIntBudget::enter(1_000_000);
+        let a = huge(8);
+        let b = huge(3);
+        let (quotient, remainder) = a.try_div_rem(&b).unwrap();
+        assert_eq!(&quotient * &b + &remainder, a);
+        assert_eq!(a.try_gcd(&b), Ok(huge(1)));
+
+        let remaining = BigIntBudget::remaining().unwrap();
+        assert!(remaining < 1_000_000);
+        assert!(remaining > 0);
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let a = huge(10_000);
+        let b = huge(5_000) + BigInt::from(2);
+
+        let _budget = BigIntBudget::enter(1000);
+        assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+        assert_eq!(BigIntBudget::remaining(), Some(0));
+
+        // Exceeded once, every operation charging the budget fails.
+        let small = BigInt::from(1000);
+        assert_eq!(small.try_div_rem(&BigInt::from(7)), Err(BudgetExceeded));
+        assert_eq!(a.try_gcd(&b), Err(BudgetExceeded));
+        assert_eq!(small.try_invert(&BigInt::from(7)), Err(BudgetExceeded));
+    }
+
+    #[test]
+    fn test_budget_exceeded_partway() {
+        let a = huge(40);
+        let b = huge(17) + BigInt::from(12345);
+
+        // The digit operations of each call, with a budget large enough.
+        let limit = 1_000_000;
+        let gcd_cost = {
+            let _budget = BigIntBudget::enter(limit);
+            assert!(a.try_gcd(&b).is_ok());
+            limit - BigIntBudget::remaining().unwrap()
+        };
+        let invert_cost = {
+            let _budget = BigIntBudget::enter(limit);
+            assert!(b.try_invert(&a).is_ok());
+            limit - BigIntBudget::remaining().unwrap()
+        };
+
+        // Every smaller budget runs out at a different step, about 1000 of them sampled.
+        for limit in (0..gcd_cost).step_by((gcd_cost as usize / 1000).max(1)) {
+            let _budget = BigIntBudget::enter(limit);
+            assert_eq!(a.try_gcd(&b), Err(BudgetExceeded), "{limit}");
+        }
+        for limit in (0..invert_cost).step_by((invert_cost as usize / 1000).max(1)) {
+            let _budget = BigIntBudget::enter(limit);
+            assert_eq!(b.try_invert(&a), Err(BudgetExceeded), "{limit}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "BigInt operation budget exceeded")]
+    fn test_budget_exceeded_by_mul_operator() {
+        // Millions of digit operations even with the Karatsuba algorithm.
+        let a = huge(200_000);
+
+        let _budget = BigIntBudget::enter(100);
+        let _ = &a * &a;
+    }
+
+    #[test]
+    fn test_nested_budgets() {
+        let a = huge(8);
+        let b = huge(3);
+
+        let outer = BigIntBudget::enter(1_000_000);
+        {
+            let _inner = BigIntBudget::enter(10);
+            assert_eq!(BigIntBudget::remaining(), Some(10));
+            assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+        }
+        // The outer budget is charged with the inner one, and not exceeded.
+        assert_eq!(BigIntBudget::remaining(), Some(1_000_000 - 10));
+        assert!(a.try_div_rem(&b).is_ok());
+
+        {
+            // capped by the outer budget
+            let _inner = BigIntBudget::enter(u64::MAX);
+            assert!(BigIntBudget::remaining().unwrap() < 1_000_000);
+        }
+        drop(outer);
+        assert_eq!(BigIntBudget::remaining(), None);
+
+        // An inner budget exhausting the outer one exceeds both.
+        let _outer = BigIntBudget::enter(10);
+        {
+            let _inner = BigIntBudget::enter(100);
+            assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+        }
+        assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+    }
+
+    #[test]
+    fn test_budgets_of_threads() {
+        let a = huge(1_000);
+        let b = huge(300) + BigInt::from(2);
+
+        let _budget = BigIntBudget::enter(10);
+        let handle = thread::spawn({
+            let (a, b) = (a.clone(), b.clone());
+            move || {
+                // no budget
+                assert_eq!(BigIntBudget::remaining(), None);
+                let (quotient, remainder) = a.try_div_rem(&b).unwrap();
+                assert_eq!(&quotient * &b + &remainder, a);
+
+                let _budget = BigIntBudget::enter(20);
+                assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+            }
+        });
+        handle.join().unwrap();
+
+        assert_eq!(BigIntBudget::remaining(), Some(10));
+        assert_eq!(a.try_div_rem(&b), Err(BudgetExceeded));
+    }
+}
@@ -8,9 +8,12 @@
 
 use crate::bigint::bigint_core::BigInt;
 use crate::bigint::digit::{Digit, DIGIT_BYTES};
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
 
 impl BigInt {
+    #[cfg(feature = "std")]
     pub(crate) fn byte_len(&self) -> usize {
         if self.is_zero() {
             return 0;
@@ -22,23 +25,21 @@ impl BigInt {
 }
 
 /// Returns the memory representation of `digits` as a byte vector.
+///
+/// The leading zero bytes are stripped, including those of leading zero digits,
+/// so the representation of a value is the same whatever the size of `Digit` is.
 pub(crate) fn be_digits_to_be_bytes(digits: &[Digit]) -> Vec<u8> {
-    if let Some((first, elements)) = digits.split_first() {
-        let digits_len = digits.len();
-        let mut bytes = Vec::with_capacity(digits_len * DIGIT_BYTES as usize);
-
-        // Strips the leading zero bytes at the most significant digit.
-        let leading_zero_bytes_len = first.leading_zeros() / 8;
-        let first_bytes = first.to_be_bytes();
-        bytes.extend(&first_bytes[leading_zero_bytes_len as usize..]);
-
-        for digit in elements {
+    let mut bytes = Vec::with_capacity(digits.len() * DIGIT_BYTES as usize);
+    for digit in digits {
+        if bytes.is_empty() {
+            // Strips the leading zero bytes at the most significant non-zero digit.
+            let leading_zero_bytes_len = digit.leading_zeros() / 8;
+            bytes.extend(&digit.to_be_bytes()[leading_zero_bytes_len as usize..]);
+        } else {
             bytes.extend(digit.to_be_bytes());
         }
-        bytes
-    } else {
-        Vec::new()
     }
+    bytes
 }
 
 /// Creates a digit vector from its byte array representation `bytes`.
@@ -48,7 +49,8 @@ pub(crate) fn be_bytes_to_be_digits(bytes: &[u8]) -> Vec<Digit> {
         return Vec::new();
     }
 
-    let extend_n = DIGIT_BYTES as usize - bytes.len() % DIGIT_BYTES as usize;
+    let extend_n =
+        (DIGIT_BYTES as usize - bytes.len() % DIGIT_BYTES as usize) % DIGIT_BYTES as usize;
     let bytes: Cow<[u8]> = if extend_n > 0 {
         // Inserts padding for digit alignment.
         let mut bytes = bytes.to_vec();
@@ -68,6 +70,14 @@ pub(crate) fn be_bytes_to_be_digits(bytes: &[u8]) -> Vec<Digit> {
     digits
 }
 
+/// Negates the big-endian `bytes` in two's complement, in place.
+pub(crate) fn negate_twos_complement_be_bytes(bytes: &mut [u8]) {
+    let mut carry = true;
+    for byte in bytes.iter_mut().rev() {
+        (*byte, carry) = (!*byte).overflowing_add(carry as u8);
+    }
+}
+
 pub(crate) fn be_bytes_to_le_digits(bytes: &[u8]) -> Vec<Digit> {
     let mut digits = be_bytes_to_be_digits(bytes);
     digits.reverse();
@@ -78,6 +88,7 @@ pub(crate) fn be_bytes_to_le_digits(bytes: &[u8]) -> Vec<Digit> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bigint::bigint_core::Sign;
     use crate::bigint::digit::{Digit, DoubleDigit};
     use crate::testing_tools::quickcheck::BigIntHexString;
     use ::quickcheck_macros::quickcheck;
@@ -97,6 +108,87 @@ mod tests {
         }
     }
 
+    /// Returns the big-endian bytes of the non-negative `a`, extracted byte by byte with
+    /// arithmetic only, independently of the size of `Digit`.
+    fn portable_be_bytes(a: &BigInt) -> Vec<u8> {
+        let byte = BigInt::from(0x100);
+        let mut a = a.clone();
+        let mut bytes = vec![];
+        while !a.is_zero() {
+            let r = &a % &byte;
+            bytes.push(r.as_digits()[0] as u8);
+            a = a >> 8;
+        }
+        bytes.reverse();
+        bytes
+    }
+
+    #[test]
+    fn test_to_be_bytes_across_digit_sizes() {
+        // Values around the digit boundaries of u8, u32 and u64.
+        // (hex, bytes)
+        let data: [(&str, &[u8]); 10] = [
+            ("00", &[]),
+            ("01", &[0x01]),
+            ("ff", &[0xff]),
+            ("0100", &[0x01, 0x00]),
+            ("ffffffff", &[0xff; 4]),
+            ("0100000000", &[0x01, 0x00, 0x00, 0x00, 0x00]),
+            ("ffffffffffffffff", &[0xff; 8]),
+            (
+                "010000000000000000",
+                &[0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            ),
+            (
+                "0102030405060708090a0b0c0d0e0f10",
+                &[
+                    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                    0x0d, 0x0e, 0x0f, 0x10,
+                ],
+            ),
+            ("000000000000000000ab", &[0xab]),
+        ];
+        for (hex, bytes) in data {
+            let a = BigInt::from_hex(hex).unwrap();
+            assert_eq!(a.to_be_bytes(), bytes);
+            assert_eq!(portable_be_bytes(&a), bytes);
+            if !a.is_zero() {
+                assert_eq!(BigInt::from_be_bytes(bytes, Sign::Positive), a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_be_digits_to_be_bytes_leading_zero_digits() {
+        assert_eq!(be_digits_to_be_bytes(&[]), Vec::<u8>::new());
+        assert_eq!(be_digits_to_be_bytes(&[0, 0]), Vec::<u8>::new());
+        assert_eq!(be_digits_to_be_bytes(&[0, 1]), [1]);
+        let mut bytes = vec![1];
+        bytes.extend(vec![0; DIGIT_BYTES as usize]);
+        assert_eq!(be_digits_to_be_bytes(&[0, 1, 0]), bytes);
+    }
+
+    #[test]
+    fn test_be_bytes_to_be_digits_alignment() {
+        // No leading zero digit for digit-aligned bytes
+        let bytes = vec![0xff; DIGIT_BYTES as usize * 2];
+        assert_eq!(be_bytes_to_be_digits(&bytes), [Digit::MAX, Digit::MAX]);
+
+        let mut bytes = vec![0x01];
+        bytes.extend(vec![0; DIGIT_BYTES as usize]);
+        assert_eq!(be_bytes_to_be_digits(&bytes), [1, 0]);
+    }
+
+    #[quickcheck]
+    fn to_be_bytes_matches_portable(hex: BigIntHexString) -> bool {
+        let a = BigInt::from_hex(hex.0).unwrap().abs();
+        let bytes = a.to_be_bytes();
+        if a.is_zero() {
+            return bytes.is_empty();
+        }
+        bytes == portable_be_bytes(&a) && BigInt::from_be_bytes(&bytes, Sign::Positive) == a
+    }
+
     #[quickcheck]
     fn byte_len_compare_as_bytes(hex: BigIntHexString) -> bool {
         let a = BigInt::from_hex(hex.0).unwrap();
@@ -8,7 +8,9 @@
 
 use super::bigint_core::{BigInt, Sign};
 use super::bigint_slice::{is_valid_biguint_slice, BigUintSlice};
-use std::cmp::Ordering;
+use super::digit::Digit;
+use core::cmp::Ordering;
+use core::hint::black_box;
 
 /// Returns true if `a` and `b` are equal.
 fn eq_digits(a: &BigUintSlice, b: &BigUintSlice) -> bool {
@@ -50,6 +52,29 @@ impl PartialEq<Self> for BigInt {
 
 impl Eq for BigInt {}
 
+impl BigInt {
+    /// Returns true if `self` and `other` are equal.
+    ///
+    /// Unlike `==`, every digit is visited regardless of where the first difference is,
+    /// so the time taken only depends on the digit lengths of the operands.
+    pub fn ct_eq(&self, other: &BigInt) -> bool {
+        let a = self.as_digits();
+        let b = other.as_digits();
+
+        let mut diff: Digit = 0;
+        let mut magnitude: Digit = 0;
+        for i in 0..a.len().max(b.len()) {
+            let a_digit = a.get(i).copied().unwrap_or(0);
+            let b_digit = b.get(i).copied().unwrap_or(0);
+            diff |= black_box(a_digit ^ b_digit);
+            magnitude |= a_digit;
+        }
+
+        // Zero is equal to zero regardless of the sign.
+        (diff == 0) & ((magnitude == 0) | (self.sign == other.sign))
+    }
+}
+
 impl PartialOrd<Self> for BigInt {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -73,11 +98,123 @@ impl Ord for BigInt {
     }
 }
 
+impl BigInt {
+    /// Returns an `Ordering` between `self` and `other`, without allocating a `BigInt` for
+    /// `other`.
+    pub fn cmp_u64(&self, other: u64) -> Ordering {
+        if self.is_sign_negative() && !self.is_zero() {
+            return Ordering::Less;
+        }
+
+        match self.magnitude_u64() {
+            Some(magnitude) => magnitude.cmp(&other),
+            None => Ordering::Greater,
+        }
+    }
+
+    /// Returns an `Ordering` between `self` and `other`, without allocating a `BigInt` for
+    /// `other`.
+    pub fn cmp_i64(&self, other: i64) -> Ordering {
+        if other >= 0 {
+            return self.cmp_u64(other as u64);
+        }
+        if !self.is_sign_negative() || self.is_zero() {
+            return Ordering::Greater;
+        }
+
+        // Both negative, the greater magnitude is the smaller number.
+        match self.magnitude_u64() {
+            Some(magnitude) => other.unsigned_abs().cmp(&magnitude),
+            None => Ordering::Less,
+        }
+    }
+
+    /// Returns the magnitude of `self` if it fits in `u64`.
+    fn magnitude_u64(&self) -> Option<u64> {
+        let digits = self.as_digits();
+        // The most significant digit isn't zero, unless `self` is.
+        if digits.len() * Digit::BITS as usize > u64::BITS as usize {
+            return None;
+        }
+
+        let magnitude = digits
+            .iter()
+            .rev()
+            .fold(0_u128, |acc, &digit| (acc << Digit::BITS) | digit as u128);
+        Some(magnitude as u64)
+    }
+}
+
+/// Implements `PartialEq` and `PartialOrd` between `BigInt` and the primitive integer
+/// types, both ways, comparing with the digits directly.
+macro_rules! impl_cmp_with_primitive {
+    ($($t:ty => $cmp:ident),*) => {$(
+        impl PartialEq<$t> for BigInt {
+            fn eq(&self, other: &$t) -> bool {
+                self.$cmp(*other as _) == Ordering::Equal
+            }
+        }
+
+        impl PartialEq<BigInt> for $t {
+            fn eq(&self, other: &BigInt) -> bool {
+                other.$cmp(*self as _) == Ordering::Equal
+            }
+        }
+
+        impl PartialOrd<$t> for BigInt {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                Some(self.$cmp(*other as _))
+            }
+        }
+
+        impl PartialOrd<BigInt> for $t {
+            fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+                Some(other.$cmp(*self as _).reverse())
+            }
+        }
+    )*};
+}
+
+// `i32` makes the unsuffixed literals work, e.g., `n > 0`.
+impl_cmp_with_primitive!(
+    u64 => cmp_u64,
+    u32 => cmp_u64,
+    usize => cmp_u64,
+    i64 => cmp_i64,
+    i32 => cmp_i64
+);
+
+impl BigInt {
+    /// Returns the smaller of `self` and `other`, `self` if they are equal.
+    ///
+    /// The same as `Ord::min`, provided for discoverability.
+    pub fn min(self, other: BigInt) -> BigInt {
+        Ord::min(self, other)
+    }
+
+    /// Returns the greater of `self` and `other`, `other` if they are equal.
+    ///
+    /// The same as `Ord::max`, provided for discoverability.
+    pub fn max(self, other: BigInt) -> BigInt {
+        Ord::max(self, other)
+    }
+
+    /// Returns `low` if `self` is less than `low`, `high` if `self` is greater than `high`,
+    /// and `self` otherwise.
+    ///
+    /// Will panic if `low` is greater than `high`.
+    pub fn clamp(self, low: BigInt, high: BigInt) -> BigInt {
+        Ord::clamp(self, low, high)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bigint::bigint_core::Sign;
     use crate::bigint::bigint_vec::{digits_be, digitvec_with_len};
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_eq() {
@@ -99,6 +236,30 @@ mod tests {
         assert_eq!(cmp_digits(&a, &b), Ordering::Equal);
     }
 
+    #[test]
+    fn test_ct_eq() {
+        let data = [
+            (0, 0),
+            (0, 1),
+            (1, 1),
+            (-1, 1),
+            (-1, -1),
+            (u64::MAX as i128, u64::MAX as i128),
+            (u64::MAX as i128, u64::MAX as i128 + 1),
+            (i128::MAX, i128::MAX),
+            (i128::MAX, i128::MIN + 1),
+        ];
+        for (a, b) in data {
+            let a = BigInt::from(a);
+            let b = BigInt::from(b);
+            assert_eq!(a.ct_eq(&b), a == b);
+            assert_eq!(b.ct_eq(&a), a == b);
+        }
+
+        // zero with the negative sign
+        assert!(BigInt::zero().ct_eq(&-BigInt::zero()));
+    }
+
     #[test]
     fn test_not_eq() {
         let a = digits_be!(0);
@@ -144,6 +305,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_min_max() {
+        // (a, b, min, max)
+        let data = [
+            (0, 0, 0, 0),
+            (1, 2, 1, 2),
+            (2, 1, 1, 2),
+            (-1, 1, -1, 1),
+            (-3, -2, -3, -2),
+            (-2, -3, -3, -2),
+            (-5, -5, -5, -5),
+            (i128::MIN, i128::MAX, i128::MIN, i128::MAX),
+        ];
+        for (a, b, min, max) in data {
+            let a = BigInt::from(a);
+            let b = BigInt::from(b);
+            assert_eq!(a.clone().min(b.clone()), BigInt::from(min));
+            assert_eq!(a.max(b), BigInt::from(max));
+        }
+
+        // Equal operands are told apart by the sign of zero.
+        let positive_zero = BigInt::new(digitvec_with_len(1), 1, Sign::Positive);
+        let negative_zero = BigInt::new(digitvec_with_len(1), 1, Sign::Negative);
+        let min = positive_zero.clone().min(negative_zero.clone());
+        assert_eq!(min.sign, Sign::Positive);
+        let max = positive_zero.max(negative_zero);
+        assert_eq!(max.sign, Sign::Negative);
+    }
+
+    #[test]
+    fn test_clamp() {
+        // (n, low, high, output)
+        let data = [
+            (5, 1, 10, 5),
+            (0, 1, 10, 1),
+            (11, 1, 10, 10),
+            (1, 1, 10, 1),
+            (10, 1, 10, 10),
+            (-7, -5, -2, -5),
+            (-1, -5, -2, -2),
+            (-3, -5, 5, -3),
+            (3, 3, 3, 3),
+        ];
+        for (n, low, high, output) in data {
+            let clamped = BigInt::from(n).clamp(BigInt::from(low), BigInt::from(high));
+            assert_eq!(clamped, BigInt::from(output));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_clamp_low_greater_than_high() {
+        let _ = BigInt::from(0).clamp(BigInt::from(1), BigInt::from(-1));
+    }
+
     #[test]
     fn test_zero_partial_eq_and_ord() {
         let a = BigInt::new(digitvec_with_len(1), 1, Sign::Positive);
@@ -153,6 +369,107 @@ mod tests {
         assert_eq!(a < b, false);
     }
 
+    #[test]
+    fn test_cmp_u64() {
+        let data = [
+            // (a, b, a cmp b)
+            (0, 0, Ordering::Equal),
+            (0, 1, Ordering::Less),
+            (1, 0, Ordering::Greater),
+            (-1, 0, Ordering::Less),
+            (-1, u64::MAX, Ordering::Less),
+            (u64::MAX as i128, u64::MAX, Ordering::Equal),
+            (u64::MAX as i128 - 1, u64::MAX, Ordering::Less),
+            (u64::MAX as i128 + 1, u64::MAX, Ordering::Greater),
+            (u64::MAX as i128 + 1, 0, Ordering::Greater),
+            (-(u64::MAX as i128) - 1, u64::MAX, Ordering::Less),
+            (i128::MAX, u64::MAX, Ordering::Greater),
+            (i128::MIN + 1, 0, Ordering::Less),
+        ];
+        for (a, b, ordering) in data {
+            let a = BigInt::from(a);
+            assert_eq!(a.cmp_u64(b), ordering, "{a:?} {b}");
+            assert_eq!(a.partial_cmp(&b), Some(ordering));
+            assert_eq!(b.partial_cmp(&a), Some(ordering.reverse()));
+            assert_eq!(a == b, ordering == Ordering::Equal);
+            assert_eq!(b == a, ordering == Ordering::Equal);
+        }
+
+        // Zero with the negative sign
+        let negative_zero = BigInt::new(digitvec_with_len(1), 1, Sign::Negative);
+        assert_eq!(negative_zero.cmp_u64(0), Ordering::Equal);
+        assert_eq!(negative_zero.cmp_i64(0), Ordering::Equal);
+        assert_eq!(negative_zero.cmp_i64(-1), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_cmp_i64() {
+        let data = [
+            // (a, b, a cmp b)
+            (0, 0, Ordering::Equal),
+            (0, -1, Ordering::Greater),
+            (-1, -1, Ordering::Equal),
+            (-2, -1, Ordering::Less),
+            (-1, -2, Ordering::Greater),
+            (1, -1, Ordering::Greater),
+            (i64::MIN as i128, i64::MIN, Ordering::Equal),
+            (i64::MIN as i128 - 1, i64::MIN, Ordering::Less),
+            (i64::MIN as i128 + 1, i64::MIN, Ordering::Greater),
+            (i64::MAX as i128 + 1, i64::MAX, Ordering::Greater),
+            (-(u64::MAX as i128) - 1, i64::MIN, Ordering::Less),
+            (u64::MAX as i128, i64::MAX, Ordering::Greater),
+        ];
+        for (a, b, ordering) in data {
+            let a = BigInt::from(a);
+            assert_eq!(a.cmp_i64(b), ordering, "{a:?} {b}");
+            assert_eq!(a.partial_cmp(&b), Some(ordering));
+            assert_eq!(b.partial_cmp(&a), Some(ordering.reverse()));
+        }
+    }
+
+    #[test]
+    fn test_cmp_with_primitive_forms() {
+        let n = BigInt::from(5);
+        assert!(n == 5_u64);
+        assert!(5_u64 == n);
+        assert!(n == 5_u32);
+        assert!(5_u32 == n);
+        assert!(n == 5_usize);
+        assert!(5_usize == n);
+        assert!(n == 5_i64);
+        assert!(5_i64 == n);
+        assert!(n == 5);
+        assert!(5 == n);
+        assert!(n > 4_u64);
+        assert!(4_u64 < n);
+        assert!(n < 6_u32);
+        assert!(6_u32 > n);
+        assert!(n > -5_i64);
+        assert!(-5 < n);
+        assert!(n != 6);
+        // References, through the `PartialOrd<&B> for &A` of the standard library
+        let (n_ref, five_ref) = (&n, &5_u64);
+        assert!(n_ref >= five_ref);
+        assert!(five_ref <= n_ref);
+
+        assert!(BigInt::one().is_one());
+        assert!(!BigInt::zero().is_one());
+        assert!(!BigInt::from(-1).is_one());
+        assert!(!(BigInt::one() << 64).is_one());
+    }
+
+    #[quickcheck]
+    fn cmp_with_primitive_matches_cmp(a_hex: BigIntHexString, b: u64, c: i64) -> bool {
+        let a = BigInt::from_hex(&a_hex.0).unwrap();
+        // Also the values close to `b` and `c`, for the long `a` rarely are.
+        let a_near_b = BigInt::from(b) - &a % BigInt::from(3);
+        let a_near_c = BigInt::from(c) + &a % BigInt::from(3);
+
+        [a, a_near_b, a_near_c].iter().all(|n| {
+            n.cmp_u64(b) == n.cmp(&BigInt::from(b)) && n.cmp_i64(c) == n.cmp(&BigInt::from(c))
+        })
+    }
+
     #[test]
     fn test_less() {
         let a = digits_be!(3, 2, 1);
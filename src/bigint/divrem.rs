@@ -9,14 +9,17 @@
 use super::bigint_core::{BigInt, Sign};
 use super::bigint_slice::{is_valid_biguint_slice, BigUintSlice};
 use super::bigint_vec::{digitvec_with_len, DigitVec};
+use super::budget::{assert_within_budget, charge};
 use super::cmp::cmp_digits;
 use super::digit::{Digit, DoubleDigit};
 use super::helper_methods::{borrowing_sub, carrying_add};
 use super::len::len_digits;
+use super::shift::shift_right_digits;
 use super::zero::is_zero_digits;
 use crate::tools::slice::rwindows_mut_each;
-use std::cmp::Ordering;
-use std::ops::{Div, Rem};
+use alloc::vec;
+use core::cmp::Ordering;
+use core::ops::{Div, DivAssign, Rem, RemAssign};
 
 /// Divides `dividend` by `divisor`, and fills the outputs to `quotient` and `remainder`.
 ///
@@ -26,6 +29,9 @@ use std::ops::{Div, Rem};
 /// - `remainder` must have a length no less than the return value of [`div_rem_remainder_max_len`].
 /// - `quotient` and `remainder` will be filled with 0 first, and then the output digits.
 /// - Will panic if `divisor` represents 0.
+///
+/// A power of two divisor `2^k` takes a fast path: the quotient is `dividend >> k` and the
+/// remainder is the lowest `k` bits of `dividend`.
 #[inline]
 pub(crate) fn div_rem_digits(
     dividend: &BigUintSlice,
@@ -38,6 +44,64 @@ pub(crate) fn div_rem_digits(
     debug_assert!(quotient.len() >= div_rem_quotient_max_len(dividend.len()));
     debug_assert!(remainder.len() >= div_rem_remainder_max_len(divisor.len()));
 
+    if let Some(k) = power_of_two_exponent_digits(divisor) {
+        return div_rem_pow2_digits(dividend, k, quotient, remainder);
+    }
+
+    div_rem_general_digits(dividend, divisor, quotient, remainder)
+}
+
+/// Returns `k` if `divisor` represents `2^k`, otherwise `None`.
+#[inline]
+pub(crate) fn power_of_two_exponent_digits(divisor: &BigUintSlice) -> Option<usize> {
+    let (most_significant_digit, lower_digits) = divisor.split_last().unwrap();
+    if most_significant_digit.is_power_of_two() && lower_digits.iter().all(|&x| x == 0) {
+        Some(
+            lower_digits.len() * Digit::BITS as usize
+                + most_significant_digit.trailing_zeros() as usize,
+        )
+    } else {
+        None
+    }
+}
+
+/// Divides `dividend` by `2^k` with a shift and a mask, the same as [`div_rem_digits`].
+///
+/// `remainder` must have a length no less than the digits length of `2^k`.
+fn div_rem_pow2_digits(
+    dividend: &BigUintSlice,
+    k: usize,
+    quotient: &mut [Digit],
+    remainder: &mut [Digit],
+) -> (usize, usize) {
+    quotient.fill(0);
+    remainder.fill(0);
+
+    // `quotient = dividend >> k`
+    let dividend_digits_len = dividend.len();
+    quotient[..dividend_digits_len].copy_from_slice(dividend);
+    let quotient_len =
+        shift_right_digits(&mut quotient[..dividend_digits_len], dividend_digits_len, k);
+
+    // `remainder = dividend & (2^k - 1)`
+    let masked_digits_len = k / Digit::BITS as usize;
+    let masked_bits_len = k % Digit::BITS as usize;
+    let copied_digits_len = dividend_digits_len.min(masked_digits_len + 1);
+    remainder[..copied_digits_len].copy_from_slice(&dividend[..copied_digits_len]);
+    if copied_digits_len > masked_digits_len {
+        remainder[masked_digits_len] &= ((1 as Digit) << masked_bits_len) - 1;
+    }
+
+    (quotient_len, len_digits(remainder))
+}
+
+/// Divides `dividend` by `divisor` for [`div_rem_digits`], with Knuth's Algorithm D.
+fn div_rem_general_digits(
+    dividend: &BigUintSlice,
+    divisor: &BigUintSlice,
+    quotient: &mut [Digit],
+    remainder: &mut [Digit],
+) -> (usize, usize) {
     quotient.fill(0);
     remainder.fill(0);
 
@@ -195,6 +259,11 @@ pub(crate) fn div_rem_digits(
         &mut dividend_digits_storage,
         dividend_window_len,
         |dividend_window| {
+            // Skips the rest steps once the budget is exceeded.
+            if !charge(divisor_digits_len) {
+                return;
+            }
+
             let mut dividend_window_iter = dividend_window.iter().rev();
             dividend_window_iter.next(); // skips the digit for the borrow checking
 
@@ -231,7 +300,7 @@ pub(crate) fn div_rem_digits(
             // * `q_hat`:
             //   1. From the algorithm D:
             //       ```
-            //       q_hat = std::cmp::min(
+            //       q_hat = core::cmp::min(
             //           (dividend0_normalized * b + dividend1_normalized) / divisor0_normalized,
             //           Digit::MAX,
             //       );
@@ -369,6 +438,7 @@ pub(crate) fn div_rem(dividend: &BigInt, divisor: &BigInt) -> (BigInt, BigInt) {
     let mut quotient = digitvec_div_rem_quotient(a.len());
     let mut remainder = digitvec_div_rem_remainder(b.len());
     let (quotient_len, remainder_len) = div_rem_digits(a, b, &mut quotient, &mut remainder);
+    assert_within_budget();
 
     let quotient_sign = if dividend.sign == divisor.sign {
         Sign::Positive
@@ -381,6 +451,29 @@ pub(crate) fn div_rem(dividend: &BigInt, divisor: &BigInt) -> (BigInt, BigInt) {
     )
 }
 
+/// Divides `dividend` by `divisor` in place, keeping either the quotient or the remainder.
+///
+/// Both fit in `digits_storage` of the dividend, which is always reused.
+fn div_rem_assign(dividend: &mut BigInt, divisor: &BigInt, keeps_quotient: bool) {
+    let a = dividend.as_digits();
+    let b = divisor.as_digits();
+    let mut quotient = digitvec_div_rem_quotient(a.len());
+    let mut remainder = digitvec_div_rem_remainder(b.len());
+    let (quotient_len, remainder_len) = div_rem_digits(a, b, &mut quotient, &mut remainder);
+    assert_within_budget();
+
+    if keeps_quotient {
+        let sign = if dividend.sign == divisor.sign {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        };
+        dividend.assign_digits(&quotient[..quotient_len], sign);
+    } else {
+        dividend.assign_digits(&remainder[..remainder_len], dividend.sign);
+    }
+}
+
 impl<'a, 'b> Div<&'b BigInt> for &'a BigInt {
     type Output = BigInt;
 
@@ -414,6 +507,18 @@ impl Div for BigInt {
     }
 }
 
+impl DivAssign<&BigInt> for BigInt {
+    fn div_assign(&mut self, rhs: &BigInt) {
+        div_rem_assign(self, rhs, true);
+    }
+}
+
+impl DivAssign for BigInt {
+    fn div_assign(&mut self, rhs: Self) {
+        self.div_assign(&rhs);
+    }
+}
+
 impl<'a, 'b> Rem<&'b BigInt> for &'a BigInt {
     type Output = BigInt;
 
@@ -447,6 +552,18 @@ impl Rem for BigInt {
     }
 }
 
+impl RemAssign<&BigInt> for BigInt {
+    fn rem_assign(&mut self, rhs: &BigInt) {
+        div_rem_assign(self, rhs, false);
+    }
+}
+
+impl RemAssign for BigInt {
+    fn rem_assign(&mut self, rhs: Self) {
+        self.rem_assign(&rhs);
+    }
+}
+
 /// a -= b * c
 #[inline]
 fn sub_mul_digits(a: &mut [Digit], b: &BigUintSlice, c: Digit) {
@@ -535,6 +652,7 @@ mod tests {
     use super::*;
     use crate::testing_tools::quickcheck::BigIntHexString;
     use quickcheck::{Gen, QuickCheck};
+    use quickcheck_macros::quickcheck;
 
     #[test]
     #[should_panic(expected = "attempt to divide by zero")]
@@ -635,4 +753,150 @@ mod tests {
             assert_eq!(&a % &b, d);
         }
     }
+
+    #[test]
+    fn test_signed_div_rem_assign() {
+        let data = [
+            (7, 2),
+            (-7, -2),
+            (7, -2),
+            (-7, 2),
+            (-1, 2),
+            (-4, 2),
+            (2, -7),
+        ];
+        for (a, b) in data {
+            let mut c = BigInt::from(a);
+            c /= BigInt::from(b);
+            assert_eq!(c, BigInt::from(a / b));
+            assert_eq!(c.is_sign_negative(), a / b < 0);
+
+            let mut d = BigInt::from(a);
+            d %= BigInt::from(b);
+            assert_eq!(d, BigInt::from(a % b));
+            assert_eq!(d.is_sign_negative(), a % b < 0);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_assign_reuses_storage() {
+        let dividend =
+            BigInt::from_hex("4e432fBecBAF6B2EfE401dC31caC3C74b46cB1ACc826").unwrap();
+        let divisor = BigInt::from_hex("D0F8C7ae").unwrap();
+
+        let mut a = dividend.clone();
+        let storage_ptr = a.digits_storage.as_ptr();
+        a /= &divisor;
+        assert_eq!(a, &dividend / &divisor);
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+
+        let mut a = dividend.clone();
+        let storage_ptr = a.digits_storage.as_ptr();
+        a %= &divisor;
+        assert_eq!(a, &dividend % &divisor);
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+    }
+
+    /// Returns `dividend / 2^k` and `dividend % 2^k` without the fast path.
+    fn div_rem_pow2_with_general_path(dividend: &BigInt, k: usize) -> (BigInt, BigInt) {
+        let divisor = BigInt::one() << k;
+        let a = dividend.as_digits();
+        let b = divisor.as_digits();
+        let mut quotient = digitvec_div_rem_quotient(a.len());
+        let mut remainder = digitvec_div_rem_remainder(b.len());
+        let (quotient_len, remainder_len) =
+            div_rem_general_digits(a, b, &mut quotient, &mut remainder);
+        (
+            BigInt::new(quotient, quotient_len, dividend.sign),
+            BigInt::new(remainder, remainder_len, dividend.sign),
+        )
+    }
+
+    #[test]
+    fn test_power_of_two_exponent_digits() {
+        for k in [0, 1, 7, 8, 63, 64, 65, 300] {
+            let divisor = BigInt::one() << k;
+            assert_eq!(power_of_two_exponent_digits(divisor.as_digits()), Some(k));
+            let divisor = &divisor * BigInt::from(3);
+            assert_eq!(power_of_two_exponent_digits(divisor.as_digits()), None);
+        }
+        assert_eq!(
+            power_of_two_exponent_digits(BigInt::zero().as_digits()),
+            None
+        );
+        assert_eq!(
+            power_of_two_exponent_digits(BigInt::from(6).as_digits()),
+            None
+        );
+    }
+
+    #[quickcheck]
+    fn div_rem_pow2_matches_general_path(dividend_hex: BigIntHexString) -> bool {
+        let dividend = BigInt::from_hex(&dividend_hex.0).unwrap();
+        [dividend.clone(), -dividend].iter().all(|dividend| {
+            [1, 7, 8, 63, 64, 65, 300].into_iter().all(|k| {
+                let divisor = BigInt::one() << k;
+                let (quotient, remainder) = div_rem_pow2_with_general_path(dividend, k);
+                let mut quotient_assigned = dividend.clone();
+                quotient_assigned /= &divisor;
+                let mut remainder_assigned = dividend.clone();
+                remainder_assigned %= &divisor;
+
+                dividend / &divisor == quotient
+                    && dividend % &divisor == remainder
+                    && quotient_assigned == quotient
+                    && remainder_assigned == remainder
+                    && (dividend / -&divisor) == -&quotient
+                    && (dividend % -&divisor) == remainder
+            })
+        })
+    }
+
+    #[test]
+    fn test_div_rem_pow2_at_digit_boundaries() {
+        let dividend = BigInt::from_hex(
+            "DF9D8de0aDBCcC5effc99f39b8Cfe2Db8F4294dDf77B849ce548546d2fc4D3fEb6FdCe40ebBe2B8eAFcC01",
+        )
+        .unwrap();
+        for digits_len in 1..=8 {
+            let k = digits_len * Digit::BITS as usize;
+            let divisor = BigInt::one() << k;
+            for dividend in [
+                dividend.clone(),
+                -&dividend,
+                &divisor - BigInt::one(),
+                divisor.clone(),
+                &divisor + BigInt::one(),
+                -&divisor,
+            ] {
+                let (quotient, remainder) = div_rem_pow2_with_general_path(&dividend, k);
+                assert_eq!(&dividend / &divisor, quotient);
+                assert_eq!(&dividend % &divisor, remainder);
+            }
+        }
+
+        // 2^0
+        assert_eq!(&dividend / BigInt::one(), dividend);
+        assert!((&dividend % BigInt::one()).is_zero());
+    }
+
+    #[quickcheck]
+    fn div_rem_assign_matches_div_rem(
+        dividend_hex: BigIntHexString,
+        divisor_hex: BigIntHexString,
+    ) -> bool {
+        let dividend = BigInt::from_hex(&dividend_hex.0).unwrap();
+        let divisor = BigInt::from_hex(&divisor_hex.0).unwrap();
+        if divisor.is_zero() {
+            return true; // just ignore
+        }
+
+        let mut quotient = dividend.clone();
+        quotient /= &divisor;
+        let mut remainder = dividend.clone();
+        remainder %= &divisor;
+        quotient == &dividend / &divisor
+            && remainder == &dividend % &divisor
+            && !(remainder.is_zero() && remainder.is_sign_negative())
+    }
 }
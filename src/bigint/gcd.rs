@@ -8,13 +8,14 @@ use crate::bigint::add::add_digits;
 use crate::bigint::bigint_core::{BigInt, Sign};
 use crate::bigint::bigint_slice::BigUintSlice;
 use crate::bigint::bigint_vec::{digitvec_with_len, DigitVec};
+use crate::bigint::budget::{assert_within_budget, charge, is_exceeded, BudgetExceeded};
 use crate::bigint::cmp::cmp_digits;
 use crate::bigint::digit::Digit;
 use crate::bigint::divrem::div_rem_digits;
 use crate::bigint::mul::mul_digits;
 use crate::bigint::sub::sub_digits;
 use crate::bigint::zero::is_zero_digits;
-use std::cmp::Ordering;
+use core::cmp::Ordering;
 
 /// Computes the greatest common divisor(GCD) of `a` and `b`.
 /// Returns (x, y, v) such that xa + yb = v, where v = gcd(a, b).
@@ -22,10 +23,14 @@ use std::cmp::Ordering;
 /// `a` must be greater than `b` (a > b), and `b` must be greater than 0 (b > 0).
 pub(crate) fn gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
     assert!(a > b);
-    assert!(b > &BigInt::zero());
-
-    let ((y_digits, y_len, y_sign), (v_digits, v_len)) =
-        gcd_digits(a.as_digits(), b.as_digits());
+    assert!(*b > 0);
+
+    let digits = gcd_digits(a.as_digits(), b.as_digits());
+    assert_within_budget();
+    // Only a fallible entry point gets here with the budget exceeded, discarding the results.
+    let Ok(((y_digits, y_len, y_sign), (v_digits, v_len))) = digits else {
+        return (BigInt::zero(), BigInt::zero(), BigInt::zero());
+    };
     let y = BigInt::new(y_digits, y_len, y_sign);
     let v = BigInt::new(v_digits, v_len, Sign::Positive);
     // xa + yb = v
@@ -35,13 +40,15 @@ pub(crate) fn gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
     (x, y, v)
 }
 
-/// Returns (y, v) such that (x?)a + yb = v, where v = gcd(a, b)
+/// Returns (y, v) such that (x?)a + yb = v, where v = gcd(a, b), or an error if the budget
+/// is exceeded.
 ///
 /// a > b
+#[allow(clippy::type_complexity)]
 fn gcd_digits(
     a: &BigUintSlice,
     b: &BigUintSlice,
-) -> ((DigitVec, usize, Sign), (DigitVec, usize)) {
+) -> Result<((DigitVec, usize, Sign), (DigitVec, usize)), BudgetExceeded> {
     // Employs extended Euclidean algorithm to compute the greatest common divisor(GCD).
     // Also employs Lehmer's "digit partial cosequeuce calculation"(DPCC) for performance.
     //
@@ -120,6 +127,10 @@ fn gcd_digits(
     let mut v2: Digit;
 
     while !is_zero_digits(&b[..b_len]) {
+        if !charge(a_len) {
+            return Err(BudgetExceeded);
+        }
+
         // When the digit length of `b` is greater than 1,
         // do the "digit partial cosequeuce calculation"(DPCC).
         let dpcc = b_len > 1;
@@ -216,7 +227,7 @@ fn gcd_digits(
                     &mut remainder_digits,
                     &mut q_mul_ua_digits,
                     &mut q_mul_ua_t_digits,
-                );
+                )?;
             } else {
                 #[allow(clippy::collapsible_else_if)]
                 if dpcc_iteration_odd {
@@ -233,7 +244,7 @@ fn gcd_digits(
                         &mut y_mul_digits2,
                         &mut digits_t1,
                         &mut digits_t2,
-                    );
+                    )?;
                     iterate_dpcc_ua_and_ub_odd(
                         &mut ua,
                         &mut ua_len,
@@ -249,7 +260,7 @@ fn gcd_digits(
                         &mut y_mul_digits2,
                         &mut digits_t1,
                         &mut digits_t2,
-                    );
+                    )?;
                 } else {
                     iterate_dpcc_a_and_b_even(
                         &mut a,
@@ -264,7 +275,7 @@ fn gcd_digits(
                         &mut y_mul_digits2,
                         &mut digits_t1,
                         &mut digits_t2,
-                    );
+                    )?;
                     iterate_dpcc_ua_and_ub_even(
                         &mut ua,
                         &mut ua_len,
@@ -280,7 +291,7 @@ fn gcd_digits(
                         &mut y_mul_digits2,
                         &mut digits_t1,
                         &mut digits_t2,
-                    );
+                    )?;
                 } // dpcc_iteration_odd
             } // v0 == 0
         } else {
@@ -300,13 +311,13 @@ fn gcd_digits(
                 &mut remainder_digits,
                 &mut q_mul_ua_digits,
                 &mut q_mul_ua_t_digits,
-            );
+            )?;
         } // dpcc
     } // b != 0
 
     // y = ua
     // z = a
-    ((ua, ua_len, ua_sign), (a, a_len))
+    Ok(((ua, ua_len, ua_sign), (a, a_len)))
 }
 
 #[inline(always)]
@@ -327,7 +338,7 @@ fn iterate_dpcc_a_and_b_odd(
     y_mul_digits2: &mut [Digit],
     digits_t1: &mut [Digit],
     digits_t2: &mut [Digit],
-) {
+) -> Result<(), BudgetExceeded> {
     // a = (-u0)*a + v0*b = v0*b - u0*a
     let digits_t1_len = x_mul_digits1_sub_y_mul_digits2_unsigned(
         v0,
@@ -337,7 +348,7 @@ fn iterate_dpcc_a_and_b_odd(
         x_mul_digits1,
         y_mul_digits2,
         digits_t1,
-    );
+    )?;
     // b = u1*a + (-v1)*b = u1*a - v1*b
     let digits_t2_len = x_mul_digits1_sub_y_mul_digits2_unsigned(
         u1,
@@ -347,7 +358,7 @@ fn iterate_dpcc_a_and_b_odd(
         x_mul_digits1,
         y_mul_digits2,
         digits_t2,
-    );
+    )?;
 
     a.fill(0);
     a[..digits_t1_len].copy_from_slice(&digits_t1[..digits_t1_len]);
@@ -356,6 +367,8 @@ fn iterate_dpcc_a_and_b_odd(
     b.fill(0);
     b[..digits_t2_len].copy_from_slice(&digits_t2[..digits_t2_len]);
     *b_len = digits_t2_len;
+
+    Ok(())
 }
 
 #[inline(always)]
@@ -376,7 +389,7 @@ fn iterate_dpcc_a_and_b_even(
     y_mul_digits2: &mut [Digit],
     digits_t1: &mut [Digit],
     digits_t2: &mut [Digit],
-) {
+) -> Result<(), BudgetExceeded> {
     // a = u0*a + (-v0)*b = u0*a - v0*b
     let digits_t1_len = x_mul_digits1_sub_y_mul_digits2_unsigned(
         u0,
@@ -386,7 +399,7 @@ fn iterate_dpcc_a_and_b_even(
         x_mul_digits1,
         y_mul_digits2,
         digits_t1,
-    );
+    )?;
     // b = (-u1)*a + v1*b = v1*b - u1*a
     let digits_t2_len = x_mul_digits1_sub_y_mul_digits2_unsigned(
         v1,
@@ -396,7 +409,7 @@ fn iterate_dpcc_a_and_b_even(
         x_mul_digits1,
         y_mul_digits2,
         digits_t2,
-    );
+    )?;
 
     a.fill(0);
     a[..digits_t1_len].copy_from_slice(&digits_t1[..digits_t1_len]);
@@ -405,6 +418,8 @@ fn iterate_dpcc_a_and_b_even(
     b.fill(0);
     b[..digits_t2_len].copy_from_slice(&digits_t2[..digits_t2_len]);
     *b_len = digits_t2_len;
+
+    Ok(())
 }
 
 #[inline(always)]
@@ -427,7 +442,7 @@ fn iterate_dpcc_ua_and_ub_odd(
     y_mul_digits2: &mut [Digit],
     digits_t1: &mut [Digit],
     digits_t2: &mut [Digit],
-) {
+) -> Result<(), BudgetExceeded> {
     // ua = (-u0)*ua + v0*ub = v0*ub - u0*ua
     let (digits_t1_len, digits_t1_sign) = x_mul_digits1_sub_y_mul_digits2_signed(
         v0,
@@ -439,7 +454,7 @@ fn iterate_dpcc_ua_and_ub_odd(
         x_mul_digits1,
         y_mul_digits2,
         digits_t1,
-    );
+    )?;
     // ub = u1*ua + (-v1)*ub = u1*ua - v1*ub
     let (digits_t2_len, digits_t2_sign) = x_mul_digits1_sub_y_mul_digits2_signed(
         u1,
@@ -451,7 +466,7 @@ fn iterate_dpcc_ua_and_ub_odd(
         x_mul_digits1,
         y_mul_digits2,
         digits_t2,
-    );
+    )?;
     ua.fill(0);
     ua[..digits_t1_len].copy_from_slice(&digits_t1[..digits_t1_len]);
     *ua_len = digits_t1_len;
@@ -461,6 +476,8 @@ fn iterate_dpcc_ua_and_ub_odd(
     ub[..digits_t2_len].copy_from_slice(&digits_t2[..digits_t2_len]);
     *ub_len = digits_t2_len;
     *ub_sign = digits_t2_sign;
+
+    Ok(())
 }
 
 #[inline(always)]
@@ -483,7 +500,7 @@ fn iterate_dpcc_ua_and_ub_even(
     y_mul_digits2: &mut [Digit],
     digits_t1: &mut [Digit],
     digits_t2: &mut [Digit],
-) {
+) -> Result<(), BudgetExceeded> {
     // ua = u0*ua + (-v0)*ub = u0*ua - v0*ub
     let (digits_t1_len, digits_t1_sign) = x_mul_digits1_sub_y_mul_digits2_signed(
         u0,
@@ -495,7 +512,7 @@ fn iterate_dpcc_ua_and_ub_even(
         x_mul_digits1,
         y_mul_digits2,
         digits_t1,
-    );
+    )?;
     // ub = (-u1)*ua + v1*ub = v1*ub - u1*ua
     let (digits_t2_len, digits_t2_sign) = x_mul_digits1_sub_y_mul_digits2_signed(
         v1,
@@ -507,7 +524,7 @@ fn iterate_dpcc_ua_and_ub_even(
         x_mul_digits1,
         y_mul_digits2,
         digits_t2,
-    );
+    )?;
 
     ua.fill(0);
     ua[..digits_t1_len].copy_from_slice(&digits_t1[..digits_t1_len]);
@@ -518,6 +535,8 @@ fn iterate_dpcc_ua_and_ub_even(
     ub[..digits_t2_len].copy_from_slice(&digits_t2[..digits_t2_len]);
     *ub_len = digits_t2_len;
     *ub_sign = digits_t2_sign;
+
+    Ok(())
 }
 
 #[inline(always)]
@@ -540,7 +559,7 @@ fn iterate_euclid_extended(
     remainder_digits: &mut [Digit],
     q_mul_ua_digits: &mut [Digit],
     q_mul_ua_t_digits: &mut [Digit],
-) {
+) -> Result<(), BudgetExceeded> {
     // q = a / b
     let (q_digits_len, _) = div_rem_digits(
         &a[..*a_len],
@@ -548,6 +567,9 @@ fn iterate_euclid_extended(
         q_digits,
         &mut remainder_digits[..*b_len],
     );
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
     // t = a - q * b
     let q_mul_ua_t_digits_len = a_sub_q_mul_b(
         &a[..*a_len],
@@ -555,7 +577,7 @@ fn iterate_euclid_extended(
         &b[..*b_len],
         q_mul_ua_digits,
         q_mul_ua_t_digits,
-    );
+    )?;
     // a = b
     a.fill(0);
     a[..*b_len].copy_from_slice(&b[..*b_len]);
@@ -574,7 +596,7 @@ fn iterate_euclid_extended(
         *ub_sign,
         q_mul_ua_digits,
         q_mul_ua_t_digits,
-    );
+    )?;
     // ua = ub
     ua.fill(0);
     ua[..*ub_len].copy_from_slice(&ub[..*ub_len]);
@@ -585,9 +607,13 @@ fn iterate_euclid_extended(
     ub[..q_mul_ua_t_digits_len].copy_from_slice(&q_mul_ua_t_digits[..q_mul_ua_t_digits_len]);
     *ub_len = q_mul_ua_t_digits_len;
     *ub_sign = q_mul_ua_t_digits_sign;
+
+    Ok(())
 }
 
 /// result = x * digits1 - y * digits2 > 0
+///
+/// Returns an error if the budget is exceeded by the multiplications.
 #[inline(always)]
 fn x_mul_digits1_sub_y_mul_digits2_unsigned(
     x: Digit,
@@ -597,21 +623,25 @@ fn x_mul_digits1_sub_y_mul_digits2_unsigned(
     x_mul_digits1: &mut [Digit],
     y_mul_digits2: &mut [Digit],
     result: &mut [Digit],
-) -> usize {
+) -> Result<usize, BudgetExceeded> {
     let x_mul_digits1_len = mul_digits(&[x], digits1, x_mul_digits1);
     let y_mul_digits2_len = mul_digits(&[y], digits2, y_mul_digits2);
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
 
-    sub_digits(
+    Ok(sub_digits(
         &x_mul_digits1[..x_mul_digits1_len],
         &y_mul_digits2[..y_mul_digits2_len],
         result,
-    )
+    ))
 }
 
 /// result = x * digits1 - y * digits2
 /// `digits1`, `digits2`, and `result` can be negative.
 ///
-/// Returns the digit length and the sign of `result`.
+/// Returns the digit length and the sign of `result`, or an error if the budget is exceeded
+/// by the multiplications.
 #[inline(always)]
 #[allow(clippy::too_many_arguments)]
 fn x_mul_digits1_sub_y_mul_digits2_signed(
@@ -624,37 +654,41 @@ fn x_mul_digits1_sub_y_mul_digits2_signed(
     x_mul_digits1: &mut [Digit],
     y_mul_digits2: &mut [Digit],
     result: &mut [Digit],
-) -> (usize, Sign) {
+) -> Result<(usize, Sign), BudgetExceeded> {
     let x_mul_digits1_len = mul_digits(&[x], digits1, x_mul_digits1);
     let y_mul_digits2_len = mul_digits(&[y], digits2, y_mul_digits2);
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
     let x_mul_digits1_slice = &x_mul_digits1[..x_mul_digits1_len];
     let y_mul_digits2_slice = &y_mul_digits2[..y_mul_digits2_len];
 
     if digits1_sign != digits2_sign {
-        (
+        Ok((
             add_digits(x_mul_digits1_slice, y_mul_digits2_slice, result),
             digits1_sign,
-        )
+        ))
     } else {
         match cmp_digits(x_mul_digits1_slice, y_mul_digits2_slice) {
-            Ordering::Less => (
+            Ordering::Less => Ok((
                 sub_digits(y_mul_digits2_slice, x_mul_digits1_slice, result),
                 -digits1_sign,
-            ),
+            )),
             Ordering::Equal => {
                 result.fill(0);
-                (1, Sign::Positive)
+                Ok((1, Sign::Positive))
             }
-            Ordering::Greater => (
+            Ordering::Greater => Ok((
                 sub_digits(x_mul_digits1_slice, y_mul_digits2_slice, result),
                 digits1_sign,
-            ),
+            )),
         }
     }
 }
 
 /// result = a - q * b > 0
-/// Returns the digit length of `result`.
+/// Returns the digit length of `result`, or an error if the budget is exceeded by the
+/// multiplication.
 #[inline(always)]
 fn a_sub_q_mul_b(
     a: &BigUintSlice,
@@ -662,13 +696,17 @@ fn a_sub_q_mul_b(
     b: &BigUintSlice,
     q_mul_b_digits: &mut [Digit],
     result: &mut [Digit],
-) -> usize {
+) -> Result<usize, BudgetExceeded> {
     let q_mul_b_digits_len = mul_digits(q, b, q_mul_b_digits);
-    sub_digits(a, &q_mul_b_digits[..q_mul_b_digits_len], result)
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
+    Ok(sub_digits(a, &q_mul_b_digits[..q_mul_b_digits_len], result))
 }
 
 /// result = ua - q * ub
-/// Returns the digit length and the sign of `result`.
+/// Returns the digit length and the sign of `result`, or an error if the budget is exceeded
+/// by the multiplication.
 #[inline(always)]
 fn ua_sub_q_mul_ub(
     ua: &BigUintSlice,
@@ -678,28 +716,31 @@ fn ua_sub_q_mul_ub(
     ub_sign: Sign,
     q_mul_ub_digits: &mut [Digit],
     result: &mut [Digit],
-) -> (usize, Sign) {
+) -> Result<(usize, Sign), BudgetExceeded> {
     let q_mul_ub_digits_len = mul_digits(q, ub, q_mul_ub_digits);
+    if is_exceeded() {
+        return Err(BudgetExceeded);
+    }
 
     if ua_sign != ub_sign {
-        (
+        Ok((
             add_digits(ua, &q_mul_ub_digits[..q_mul_ub_digits_len], result),
             ua_sign,
-        )
+        ))
     } else {
         match cmp_digits(ua, &q_mul_ub_digits[..q_mul_ub_digits_len]) {
-            Ordering::Less => (
+            Ordering::Less => Ok((
                 sub_digits(&q_mul_ub_digits[..q_mul_ub_digits_len], ua, result),
                 -ua_sign,
-            ),
+            )),
             Ordering::Equal => {
                 result.fill(0);
-                (1, Sign::Positive)
+                Ok((1, Sign::Positive))
             }
-            Ordering::Greater => (
+            Ordering::Greater => Ok((
                 sub_digits(ua, &q_mul_ub_digits[..q_mul_ub_digits_len], result),
                 ua_sign,
-            ),
+            )),
         }
     }
 }
@@ -708,8 +749,8 @@ fn ua_sub_q_mul_ub(
 mod tests {
     use super::*;
     use crate::testing_tools::quickcheck::HexString;
+    use core::mem;
     use quickcheck::{Gen, QuickCheck};
-    use std::mem;
 
     #[test]
     fn test_gcd() {
@@ -773,19 +814,19 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_gcd_digits_a_less_than_b() {
-        gcd_digits(&[7], &[17]);
+        let _ = gcd_digits(&[7], &[17]);
     }
 
     #[test]
     #[should_panic]
     fn test_gcd_digits_a_equals_b() {
-        gcd_digits(&[7], &[7]);
+        let _ = gcd_digits(&[7], &[7]);
     }
 
     #[test]
     #[should_panic]
     fn test_gcd_digits_b_equals_to_zero() {
-        gcd_digits(&[7], &[0]);
+        let _ = gcd_digits(&[7], &[0]);
     }
 
     #[test]
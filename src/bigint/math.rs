@@ -4,16 +4,68 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::bigint::bigint_core::BigInt;
+use crate::bigint::bigint_core::{BigInt, Sign};
 use crate::bigint::digit::Digit;
+use crate::bigint::divrem::power_of_two_exponent_digits;
 
 impl BigInt {
-    pub(crate) fn is_even(&self) -> bool {
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> BigInt {
+        let mut n = self.clone();
+        n.sign = Sign::Positive;
+        n
+    }
+
+    /// Returns a number representing the sign of `self`:
+    /// - `0` if `self` is zero
+    /// - `1` if `self` is positive
+    /// - `-1` if `self` is negative
+    pub fn signum(&self) -> i32 {
+        if self.is_zero() {
+            0
+        } else if self.is_sign_negative() {
+            -1
+        } else {
+            1
+        }
+    }
+
+    /// Returns true if `self` is `2^k` for some `k`, zero and negative values are not.
+    pub fn is_power_of_two(&self) -> bool {
+        if self.is_sign_negative() {
+            return false;
+        }
+
+        power_of_two_exponent_digits(self.as_digits()).is_some()
+    }
+
+    /// Returns the smallest power of two greater than or equal to `self`.
+    ///
+    /// Returns 1 if `self` is less than or equal to 1.
+    pub fn next_power_of_two(&self) -> BigInt {
+        if self.is_sign_negative() || self.bit_len() <= 1 {
+            return BigInt::one();
+        }
+        if self.is_power_of_two() {
+            return self.clone();
+        }
+
+        BigInt::one() << self.bit_len()
+    }
+
+    /// Returns true if `self` is one.
+    pub fn is_one(&self) -> bool {
+        !self.is_sign_negative() && self.as_digits() == [1]
+    }
+
+    /// Returns true if `self` is even, zero included.
+    pub fn is_even(&self) -> bool {
         let digit = self.digits_storage.first().unwrap();
         *digit & 1 == 0
     }
 
-    pub(crate) fn is_odd(&self) -> bool {
+    /// Returns true if `self` is odd.
+    pub fn is_odd(&self) -> bool {
         !self.is_even()
     }
 
@@ -34,6 +86,47 @@ impl BigInt {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_abs_signum() {
+        let data = [(-17, 17, -1), (0, 0, 0), (17, 17, 1)];
+        for (n, abs, signum) in data {
+            let n = BigInt::from(n);
+            assert_eq!(n.abs(), BigInt::from(abs));
+            assert_eq!(n.signum(), signum);
+        }
+
+        // zero with the negative sign
+        let negative_zero = -BigInt::zero();
+        assert_eq!(negative_zero.signum(), 0);
+        assert!(!negative_zero.abs().is_sign_negative());
+    }
+
+    #[test]
+    fn test_power_of_two() {
+        // (n, is_power_of_two, next_power_of_two)
+        let data = [
+            (BigInt::from(0), false, BigInt::from(1)),
+            (BigInt::from(1), true, BigInt::from(1)),
+            (BigInt::from(2), true, BigInt::from(2)),
+            (BigInt::from(3), false, BigInt::from(4)),
+            (BigInt::from(1024), true, BigInt::from(1024)),
+            (BigInt::from(1025), false, BigInt::from(2048)),
+            (BigInt::from(-4), false, BigInt::from(1)),
+            (BigInt::one() << 200, true, BigInt::one() << 200),
+            (
+                (BigInt::one() << 200) + BigInt::one(),
+                false,
+                BigInt::one() << 201,
+            ),
+            (BigInt::from(u64::MAX), false, BigInt::one() << 64),
+        ];
+        for (n, is_power_of_two, next_power_of_two) in data {
+            assert_eq!(n.is_power_of_two(), is_power_of_two, "{n}");
+            assert_eq!(n.next_power_of_two(), next_power_of_two, "{n}");
+        }
+        assert!(!(-BigInt::zero()).is_power_of_two());
+    }
+
     #[test]
     fn test_is_even_odd() {
         let data = [(1, false), (2, true), (3, false), (4, true), (17, false)];
@@ -5,15 +5,21 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 mod add;
+#[cfg(feature = "bigint-arena")]
+mod arena;
 pub(crate) mod bigint_core;
 mod bigint_display;
 mod bigint_from;
 mod bigint_into;
 pub(crate) mod bigint_new;
+#[cfg(feature = "num-bigint")]
+mod bigint_num_bigint;
 mod bigint_slice;
+pub(crate) mod bigint_static;
 mod bigint_vec;
 mod biguint;
 mod bits;
+mod budget;
 mod bytes;
 mod cmp;
 pub(crate) mod digit;
@@ -24,10 +30,21 @@ mod len;
 pub(crate) mod math;
 mod mul;
 mod neg;
+mod prime;
 pub(crate) mod shift;
 mod sub;
 mod zero;
 
+#[cfg(feature = "bigint-arena")]
+pub use arena::{with_arena, with_thread_local_arena, BigIntArena, BigIntArenaAllocator};
 pub use bigint_core::BigInt;
-pub(crate) use bigint_core::Sign;
+pub use bigint_core::Sign;
+pub use bigint_into::BigIntError;
+pub use bigint_new::ParseIntError;
+#[cfg(feature = "std")]
+pub(crate) use bigint_static::bigint_const;
 pub use biguint::BigUint;
+#[cfg(feature = "std")]
+pub use budget::BigIntBudget;
+pub use budget::BudgetExceeded;
+pub use digit::Digit;
@@ -9,12 +9,28 @@
 use super::bigint_core::{BigInt, Sign};
 use super::bigint_slice::{is_valid_biguint_slice, BigUintSlice};
 use super::bigint_vec::{digitvec_with_len, DigitVec};
-use super::cmp::cmp_digits;
+use super::budget::{assert_within_budget, charge};
 use super::digit::{Digit, DoubleDigit};
+use super::helper_methods::{borrowing_sub, carrying_add};
 use super::len::len_digits;
 use super::zero::is_zero_digits;
-use std::cmp::Ordering;
-use std::ops::Mul;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Mul, MulAssign};
+
+/// Operands with fewer digits than this are multiplied with the "long multiplication"
+/// algorithm, larger ones are split with the Karatsuba algorithm first.
+///
+/// Below the threshold, the overhead of splitting outweighs the saved digit
+/// multiplications.
+pub(crate) const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Operands with no fewer digits than this are multiplied with the number-theoretic
+/// transform, see [`mul_digits_ntt`].
+///
+/// With 16-bit limbs, the transforms only outperform the Karatsuba algorithm at around
+/// 7000 64-bit digits, e.g., 2000-digit operands are multiplied about 1.5 times slower.
+pub(crate) const NTT_THRESHOLD: usize = 8000;
 
 /// Multiplies `a` with `b`, and fills the output to `result`,
 /// returning the length of the output digits.
@@ -23,9 +39,35 @@ use std::ops::Mul;
 /// - `result` will be filled with 0 first, and then the output digits.
 #[inline]
 pub(crate) fn mul_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit]) -> usize {
+    if a.len().min(b.len()) >= NTT_THRESHOLD {
+        debug_assert!(is_valid_biguint_slice(a));
+        debug_assert!(is_valid_biguint_slice(b));
+        debug_assert!(result.len() >= multiplying_output_max_len(a.len(), b.len()));
+
+        result.fill(0);
+        let product = mul_digits_ntt(a, b);
+        result[..product.len()].copy_from_slice(&product);
+        return len_digits(result);
+    }
+
+    mul_digits_with_karatsuba_threshold(a, b, result, KARATSUBA_THRESHOLD)
+}
+
+/// Same as [`mul_digits`], except that the Karatsuba algorithm is employed when both
+/// operands have at least `karatsuba_threshold` digits.
+///
+/// `karatsuba_threshold` must be no less than 4, for the sub-products to be smaller
+/// than the product being split.
+fn mul_digits_with_karatsuba_threshold(
+    a: &BigUintSlice,
+    b: &BigUintSlice,
+    result: &mut [Digit],
+    karatsuba_threshold: usize,
+) -> usize {
     debug_assert!(is_valid_biguint_slice(a));
     debug_assert!(is_valid_biguint_slice(b));
     debug_assert!(result.len() >= multiplying_output_max_len(a.len(), b.len()));
+    assert!(karatsuba_threshold >= 4);
 
     result.fill(0);
 
@@ -34,6 +76,90 @@ pub(crate) fn mul_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
         return 1;
     }
 
+    let output_max_len = multiplying_output_max_len(a.len(), b.len());
+    karatsuba_mul(a, b, &mut result[..output_max_len], karatsuba_threshold);
+    len_digits(result)
+}
+
+/// Multiplies `a` with `b`, splitting the operands with the [Karatsuba algorithm][1]
+/// until one of them has fewer than `karatsuba_threshold` digits.
+///
+/// - `a` and `b` may have zero padding.
+/// - `result` must be filled with 0, and have the length `a.len() + b.len()`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Karatsuba_algorithm
+fn karatsuba_mul(a: &[Digit], b: &[Digit], result: &mut [Digit], karatsuba_threshold: usize) {
+    debug_assert_eq!(result.len(), a.len() + b.len());
+
+    // Stops once the budget is exceeded, leaving `result` incomplete.
+    if !charge(0) {
+        return;
+    }
+
+    let (greater, smaller) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    if smaller.len() < karatsuba_threshold {
+        long_mul(greater, smaller, result);
+        return;
+    }
+
+    #[cfg(test)]
+    tests::KARATSUBA_SPLIT_COUNT.with(|count| count.set(count.get() + 1));
+
+    // greater = g1 * b^m + g0
+    let m = greater.len() / 2;
+    let (g0, g1) = greater.split_at(m);
+
+    if smaller.len() <= m {
+        // Too unbalanced to split `smaller` at `m`:
+        // greater * smaller = g1 * smaller * b^m + g0 * smaller
+        karatsuba_mul(
+            g0,
+            smaller,
+            &mut result[..m + smaller.len()],
+            karatsuba_threshold,
+        );
+        let mut t = vec![0; g1.len() + smaller.len()];
+        karatsuba_mul(g1, smaller, &mut t, karatsuba_threshold);
+        if !charge(0) {
+            return;
+        }
+        add_to(&mut result[m..], &t);
+        return;
+    }
+
+    // smaller = s1 * b^m + s0
+    // greater * smaller = z2 * b^(2 * m) + z1 * b^m + z0, where
+    // - z2 = g1 * s1
+    // - z0 = g0 * s0
+    // - z1 = g1 * s0 + g0 * s1 = (g0 + g1) * (s0 + s1) - z2 - z0
+    let (s0, s1) = smaller.split_at(m);
+    let (z0, z2) = result.split_at_mut(2 * m);
+    karatsuba_mul(g0, s0, z0, karatsuba_threshold);
+    karatsuba_mul(g1, s1, z2, karatsuba_threshold);
+
+    let g_sum = sum(g0, g1);
+    let s_sum = sum(s0, s1);
+    let mut z1 = vec![0; g_sum.len() + s_sum.len()];
+    karatsuba_mul(&g_sum, &s_sum, &mut z1, karatsuba_threshold);
+    if !charge(0) {
+        return;
+    }
+    sub_from(&mut z1, z0);
+    sub_from(&mut z1, z2);
+
+    // The zero padding of `z1` may not fit in the rest of `result`.
+    let z1_len = len_digits(&z1);
+    add_to(&mut result[m..], &z1[..z1_len]);
+}
+
+/// Multiplies `greater` with `smaller`, with the "long multiplication" algorithm.
+///
+/// - `greater` must be no shorter than `smaller`, both may have zero padding.
+/// - `result` must be filled with 0, and have a length no less than
+///   `greater.len() + smaller.len()`.
+fn long_mul(greater: &[Digit], smaller: &[Digit], result: &mut [Digit]) {
+    debug_assert!(greater.len() >= smaller.len());
+
     // Employs the ["long multiplication"][1] algorithm:
     // multiplying each digit of the multiplier with the multiplicand,
     // and then add up all the properly shifted results.
@@ -43,12 +169,6 @@ pub(crate) fn mul_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
     //
     // [1]: https://en.wikipedia.org/wiki/Multiplication_algorithm#Long_multiplication
 
-    let (greater, smaller) = if cmp_digits(a, b) == Ordering::Less {
-        (b, a)
-    } else {
-        (a, b)
-    };
-
     // +1 for the possible carry at the most significant digit of `greater`.
     let output_window_len = greater.len() + 1;
 
@@ -58,6 +178,10 @@ pub(crate) fn mul_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
     //
     // `output_window_offset` is in [0, smaller.len() - 1].
     for (output_window_offset, &smaller_digit) in smaller.iter().enumerate() {
+        if !charge(greater.len()) {
+            return;
+        }
+
         let mut carry: DoubleDigit = 0;
 
         let output_window =
@@ -85,8 +209,280 @@ pub(crate) fn mul_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
             *output_window.last_mut().unwrap() = carry as Digit;
         }
     }
+}
 
-    len_digits(result)
+// Multiplication with the number-theoretic transform (NTT).
+//
+// The digits of a product are the convolution of the digits of the operands, carried:
+// for `a = Σ a_i * B^i` and `b = Σ b_j * B^j`, `a * b = Σ c_k * B^k` with
+// `c_k = Σ_{i + j = k} a_i * b_j`. The NTT computes the convolution in O(n log n):
+//
+// 1. Both operands are split into 16-bit limbs (`B = 2^16`), zero padded to a power of two
+//    `n` no less than the number of limbs of the product.
+// 2. The limbs are transformed: evaluated at the `n`-th roots of unity of a prime field,
+//    with the Cooley–Tukey butterflies. The convolution becomes pointwise products.
+// 3. The pointwise products are transformed back with the inverse roots, and divided by `n`.
+// 4. The coefficients `c_k`, exact as long as they are less than the prime, are carried
+//    into the digits of the product.
+//
+// The prime `p = 2^64 - 2^32 + 1` is chosen for:
+// - `p - 1 = 2^32 * (2^32 - 1)`: `n`-th roots of unity exist for all `n` up to `2^32`,
+//   i.e., `g^((p - 1) / n)` for a generator `g` of the multiplicative group, 7.
+// - `c_k < min(a_len, b_len) * (2^16 - 1)^2 < 2^31 * 2^32 < p`: the coefficients never
+//   overflow, with up to `2^32` limbs, so one prime is enough and no Chinese remainder
+//   reconstruction is needed.
+// - `2^64 ≡ 2^32 - 1 (mod p)`: 128-bit products reduce with a few additions, see
+//   `ntt_reduce`.
+
+/// The prime of the field of the number-theoretic transform, `2^64 - 2^32 + 1`.
+const NTT_PRIME: u64 = 0xffff_ffff_0000_0001;
+
+/// A generator of the multiplicative group of the field.
+const NTT_GENERATOR: u64 = 7;
+
+/// `2^64 mod NTT_PRIME`.
+const NTT_EPSILON: u64 = 0xffff_ffff;
+
+/// The bits of a limb, small enough for the coefficients to be less than `NTT_PRIME`.
+const NTT_LIMB_BITS: usize = 16;
+
+/// Multiplies `a` with `b` with the number-theoretic transform, returning the product
+/// digits with the length `a.len() + b.len()`.
+///
+/// `a` and `b` may have zero padding.
+pub(crate) fn mul_digits_ntt(a: &[Digit], b: &[Digit]) -> Vec<Digit> {
+    let a_limbs = digits_to_ntt_limbs(a);
+    let b_limbs = digits_to_ntt_limbs(b);
+    let n = (a_limbs.len() + b_limbs.len()).next_power_of_two().max(2);
+    assert!(n as u64 <= 1 << 32, "operands too large");
+
+    let mut product_digits = vec![0; a.len() + b.len()];
+    // Charges the butterflies of the three transforms.
+    // Stops if the budget is exceeded, returning zeros.
+    if !charge(3 * n / 2 * n.trailing_zeros() as usize) {
+        return product_digits;
+    }
+
+    let mut a_values = a_limbs;
+    a_values.resize(n, 0);
+    let mut b_values = b_limbs;
+    b_values.resize(n, 0);
+    ntt(&mut a_values, false);
+    ntt(&mut b_values, false);
+    for (x, y) in a_values.iter_mut().zip(&b_values) {
+        *x = ntt_mul(*x, *y);
+    }
+    ntt(&mut a_values, true);
+
+    // Carries the coefficients into bytes.
+    let mut product_bytes =
+        Vec::with_capacity(product_digits.len() * (Digit::BITS / 8) as usize);
+    let mut carry: u128 = 0;
+    for coefficient in a_values {
+        carry += coefficient as u128;
+        product_bytes.extend_from_slice(&(carry as u16).to_le_bytes());
+        carry >>= NTT_LIMB_BITS;
+    }
+    debug_assert_eq!(carry, 0);
+
+    for (digit, bytes) in product_digits
+        .iter_mut()
+        .zip(product_bytes.chunks_exact((Digit::BITS / 8) as usize))
+    {
+        *digit = Digit::from_le_bytes(bytes.try_into().unwrap());
+    }
+    product_digits
+}
+
+/// Splits `digits` into limbs of `NTT_LIMB_BITS` bits, in little-endian order.
+fn digits_to_ntt_limbs(digits: &[Digit]) -> Vec<u64> {
+    let bytes: Vec<u8> = digits
+        .iter()
+        .flat_map(|digit| digit.to_le_bytes())
+        .collect();
+    bytes
+        .chunks(NTT_LIMB_BITS / 8)
+        .map(|limb_bytes| {
+            limb_bytes
+                .iter()
+                .rev()
+                .fold(0, |limb, &byte| limb << 8 | byte as u64)
+        })
+        .collect()
+}
+
+/// Transforms `values` in place, or transforms back if `inverse` is true.
+///
+/// The length of `values` must be a power of two, no less than 2.
+fn ntt(values: &mut [u64], inverse: bool) {
+    let n = values.len();
+    debug_assert!(n.is_power_of_two() && n >= 2);
+
+    // Reorders the values by the bit-reversed indices, for the butterflies to work
+    // in place.
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    // `twiddles[k] = w^k` for a root of unity `w` of order `n`, `k` in [0, n/2).
+    let mut root = ntt_pow(NTT_GENERATOR, (NTT_PRIME - 1) / n as u64);
+    if inverse {
+        root = ntt_pow(root, NTT_PRIME - 2);
+    }
+    let mut twiddles = Vec::with_capacity(n / 2);
+    let mut twiddle = 1;
+    for _ in 0..n / 2 {
+        twiddles.push(twiddle);
+        twiddle = ntt_mul(twiddle, root);
+    }
+
+    // Combines the transforms of the halves into the transforms of length `len`:
+    // for a root of unity `w_len` of order `len`, `w_len = w^(n / len)`,
+    // `X_k = E_k + w_len^k * O_k` and `X_(k + len/2) = E_k - w_len^k * O_k`.
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for chunk in values.chunks_exact_mut(len) {
+            let (even, odd) = chunk.split_at_mut(len / 2);
+            for ((x, y), &w) in even
+                .iter_mut()
+                .zip(odd.iter_mut())
+                .zip(twiddles.iter().step_by(stride))
+            {
+                let u = *x;
+                let v = ntt_mul(*y, w);
+                *x = ntt_add(u, v);
+                *y = ntt_sub(u, v);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let n_inverse = ntt_pow(n as u64, NTT_PRIME - 2);
+        for value in values.iter_mut() {
+            *value = ntt_mul(*value, n_inverse);
+        }
+    }
+}
+
+/// Returns `(a + b) mod NTT_PRIME`, for `a` and `b` less than `NTT_PRIME`.
+#[inline]
+fn ntt_add(a: u64, b: u64) -> u64 {
+    let (sum, overflowed) = a.overflowing_add(b);
+    // `2^64 ≡ NTT_EPSILON`, and `sum + NTT_EPSILON` won't overflow again.
+    let sum = if overflowed { sum + NTT_EPSILON } else { sum };
+    if sum >= NTT_PRIME {
+        sum - NTT_PRIME
+    } else {
+        sum
+    }
+}
+
+/// Returns `(a - b) mod NTT_PRIME`, for `a` and `b` less than `NTT_PRIME`.
+#[inline]
+fn ntt_sub(a: u64, b: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        a + (NTT_PRIME - b)
+    }
+}
+
+/// Returns `(a * b) mod NTT_PRIME`.
+#[inline]
+fn ntt_mul(a: u64, b: u64) -> u64 {
+    ntt_reduce(a as u128 * b as u128)
+}
+
+/// Returns `a^e mod NTT_PRIME`.
+fn ntt_pow(a: u64, e: u64) -> u64 {
+    let mut result = 1;
+    let mut base = a;
+    let mut e = e;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = ntt_mul(result, base);
+        }
+        base = ntt_mul(base, base);
+        e >>= 1;
+    }
+    result
+}
+
+/// Returns `x mod NTT_PRIME`.
+///
+/// With `x = x_lo + x_hi_lo * 2^64 + x_hi_hi * 2^96`, and for `2^64 ≡ 2^32 - 1` and
+/// `2^96 ≡ -1`, `x ≡ x_lo - x_hi_hi + x_hi_lo * (2^32 - 1)`.
+#[inline]
+fn ntt_reduce(x: u128) -> u64 {
+    let x_lo = x as u64;
+    let x_hi = (x >> 64) as u64;
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & NTT_EPSILON;
+
+    // `x_lo - x_hi_hi`, adding `NTT_PRIME` on borrowing: the wrapped difference already
+    // has `2^64` added, subtracting `NTT_EPSILON` leaves `NTT_PRIME`. It won't borrow
+    // again, for `x_hi_hi < 2^32`.
+    let (mut t0, borrowed) = x_lo.overflowing_sub(x_hi_hi);
+    if borrowed {
+        t0 -= NTT_EPSILON;
+    }
+    // `x_hi_lo * (2^32 - 1)` fits in 64 bits.
+    let t1 = x_hi_lo * NTT_EPSILON;
+    let (sum, overflowed) = t0.overflowing_add(t1);
+    let sum = if overflowed { sum + NTT_EPSILON } else { sum };
+    if sum >= NTT_PRIME {
+        sum - NTT_PRIME
+    } else {
+        sum
+    }
+}
+
+/// Returns `x + y`, one digit longer than the longer one of `x` and `y`.
+fn sum(x: &[Digit], y: &[Digit]) -> Vec<Digit> {
+    let mut output = vec![0; x.len().max(y.len()) + 1];
+    output[..x.len()].copy_from_slice(x);
+    add_to(&mut output, y);
+    output
+}
+
+/// Adds `x` to `acc` in place.
+///
+/// The sum must fit in `acc`.
+fn add_to(acc: &mut [Digit], x: &[Digit]) {
+    debug_assert!(acc.len() >= x.len());
+
+    let mut carry = false;
+    for (i, acc_digit) in acc.iter_mut().enumerate() {
+        let x_digit = x.get(i).copied().unwrap_or(0);
+        if i >= x.len() && !carry {
+            break;
+        }
+        (*acc_digit, carry) = carrying_add(*acc_digit, x_digit, carry);
+    }
+    debug_assert!(!carry);
+}
+
+/// Subtracts `x` from `acc` in place.
+///
+/// The difference must not be negative.
+fn sub_from(acc: &mut [Digit], x: &[Digit]) {
+    debug_assert!(acc.len() >= x.len());
+
+    let mut borrow = false;
+    for (i, acc_digit) in acc.iter_mut().enumerate() {
+        let x_digit = x.get(i).copied().unwrap_or(0);
+        if i >= x.len() && !borrow {
+            break;
+        }
+        (*acc_digit, borrow) = borrowing_sub(*acc_digit, x_digit, borrow);
+    }
+    debug_assert!(!borrow);
 }
 
 impl<'a, 'b> Mul<&'b BigInt> for &'a BigInt {
@@ -97,6 +493,7 @@ impl<'a, 'b> Mul<&'b BigInt> for &'a BigInt {
         let b = rhs.as_digits();
         let mut output = digitvec_multiplying_output(a.len(), b.len());
         let output_len = mul_digits(a, b, &mut output);
+        assert_within_budget();
 
         let sign = if self.sign == rhs.sign {
             Sign::Positive
@@ -131,6 +528,34 @@ impl Mul for BigInt {
     }
 }
 
+impl MulAssign<&BigInt> for BigInt {
+    /// Multiplies in place, reusing `digits_storage` if the product fits.
+    ///
+    /// The digits of `self` are copied out as an operand, the product is written to
+    /// `digits_storage` directly.
+    fn mul_assign(&mut self, rhs: &BigInt) {
+        let a = self.as_digits().to_vec();
+        let b = rhs.as_digits();
+        let output_max_len = multiplying_output_max_len(a.len(), b.len());
+        self.grow_digits_storage(output_max_len);
+        let output_len = mul_digits(&a, b, &mut self.digits_storage.to_mut()[..output_max_len]);
+        assert_within_budget();
+
+        let sign = if self.sign == rhs.sign {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        };
+        self.set_digits_len(output_len, sign);
+    }
+}
+
+impl MulAssign for BigInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.mul_assign(&rhs);
+    }
+}
+
 /// Returns the length of the largest possible output of an multiplication operation: a * b.
 ///
 /// `a_len` and `b_len` are the length of the operands.
@@ -165,6 +590,50 @@ mod tests {
     use super::*;
     use crate::bigint::bigint_vec::digits_be;
     use crate::bigint::digit::Digit;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use core::cell::Cell;
+    use quickcheck::TestResult;
+    use quickcheck_macros::quickcheck;
+
+    thread_local! {
+        // The number of times `karatsuba_mul` splits the operands.
+        pub(super) static KARATSUBA_SPLIT_COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Returns `a * b` and the number of Karatsuba splits.
+    fn mul_with_threshold(
+        a: &[Digit],
+        b: &[Digit],
+        karatsuba_threshold: usize,
+    ) -> (Vec<Digit>, usize) {
+        KARATSUBA_SPLIT_COUNT.with(|count| count.set(0));
+        let mut output = digitvec_multiplying_output_filled_1(a.len(), b.len());
+        let output_len =
+            mul_digits_with_karatsuba_threshold(a, b, &mut output, karatsuba_threshold);
+        assert_eq!(vec!(0; output.len() - output_len), output[output_len..]);
+        output.truncate(output_len);
+        (output, KARATSUBA_SPLIT_COUNT.with(|count| count.get()))
+    }
+
+    fn long_mul_digits(a: &[Digit], b: &[Digit]) -> Vec<Digit> {
+        let (output, split_count) = mul_with_threshold(a, b, usize::MAX);
+        assert_eq!(split_count, 0);
+        output
+    }
+
+    /// Makes a valid `BigUintSlice` of `digits`.
+    fn normalized(mut digits: Vec<Digit>) -> Vec<Digit> {
+        let len = if digits.is_empty() {
+            0
+        } else {
+            len_digits(&digits)
+        };
+        digits.truncate(len);
+        if digits.is_empty() {
+            digits.push(0);
+        }
+        digits
+    }
 
     #[test]
     fn test_mul_digits() {
@@ -233,4 +702,194 @@ mod tests {
             assert_eq!(a * b, c)
         }
     }
+
+    #[test]
+    fn test_karatsuba_mul() {
+        let max = Digit::MAX;
+        // `data`: [(a_len, b_len)]
+        let data = [
+            (4, 4),
+            (5, 4),
+            (7, 7),
+            (8, 5),
+            (16, 16),
+            (33, 17),
+            (64, 4),
+            (100, 31),
+        ];
+        for (a_len, b_len) in data {
+            for (a, b) in [
+                (vec![max; a_len], vec![max; b_len]),
+                (
+                    (1..=a_len as Digit).collect(),
+                    (1..=b_len as Digit).rev().collect(),
+                ),
+            ] {
+                let expected = long_mul_digits(&a, &b);
+                for karatsuba_threshold in [4, 5, 8] {
+                    let (output, split_count) = mul_with_threshold(&a, &b, karatsuba_threshold);
+                    assert_eq!(output, expected);
+                    assert_eq!(split_count > 0, b_len >= karatsuba_threshold);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_karatsuba_threshold() {
+        let a = vec![Digit::MAX; KARATSUBA_THRESHOLD];
+        let b = vec![Digit::MAX; KARATSUBA_THRESHOLD - 1];
+
+        KARATSUBA_SPLIT_COUNT.with(|count| count.set(0));
+        let mut output = digitvec_multiplying_output(a.len(), b.len());
+        mul_digits(&a, &b, &mut output);
+        assert_eq!(KARATSUBA_SPLIT_COUNT.with(|count| count.get()), 0);
+
+        let mut output = digitvec_multiplying_output(a.len(), a.len());
+        mul_digits(&a, &a, &mut output);
+        assert_eq!(KARATSUBA_SPLIT_COUNT.with(|count| count.get()), 1);
+    }
+
+    #[quickcheck]
+    fn karatsuba_mul_matches_long_mul(a: Vec<Digit>, b: Vec<Digit>) -> TestResult {
+        let a = normalized(a);
+        let b = normalized(b);
+        if a.len() < 4 || b.len() < 4 {
+            return TestResult::discard();
+        }
+
+        let expected = long_mul_digits(&a, &b);
+        TestResult::from_bool([4, 5, 8, 13].into_iter().all(|karatsuba_threshold| {
+            mul_with_threshold(&a, &b, karatsuba_threshold).0 == expected
+        }))
+    }
+
+    #[test]
+    fn test_ntt_reduce() {
+        let p = NTT_PRIME as u128;
+        for x in [
+            0,
+            1,
+            p - 1,
+            p,
+            p + 1,
+            u64::MAX as u128,
+            (p - 1) * (p - 1),
+            1 << 96,
+            (1 << 96) - 1,
+            u128::MAX,
+        ] {
+            assert_eq!(ntt_reduce(x) as u128, x % p);
+        }
+        assert_eq!(ntt_add(NTT_PRIME - 1, NTT_PRIME - 1), NTT_PRIME - 2);
+        assert_eq!(ntt_sub(0, NTT_PRIME - 1), 1);
+    }
+
+    #[quickcheck]
+    fn ntt_arithmetic_matches_u128(a: u64, b: u64) -> bool {
+        let p = NTT_PRIME as u128;
+        let (a, b) = (a % NTT_PRIME, b % NTT_PRIME);
+        ntt_mul(a, b) as u128 == a as u128 * b as u128 % p
+            && ntt_add(a, b) as u128 == (a as u128 + b as u128) % p
+            && ntt_sub(a, b) as u128 == (a as u128 + p - b as u128) % p
+    }
+
+    #[test]
+    fn test_ntt_roots_of_unity() {
+        // `7^((p - 1) / 2^32)` is a root of unity of order exactly `2^32`.
+        let root = ntt_pow(NTT_GENERATOR, (NTT_PRIME - 1) >> 32);
+        assert_eq!(ntt_pow(root, 1 << 32), 1);
+        assert_ne!(ntt_pow(root, 1 << 31), 1);
+
+        let values: Vec<u64> = (0..16).map(|i| i * 0x1234_5678_9abc).collect();
+        let mut transformed = values.clone();
+        ntt(&mut transformed, false);
+        assert_ne!(transformed, values);
+        ntt(&mut transformed, true);
+        assert_eq!(transformed, values);
+    }
+
+    #[test]
+    fn test_mul_digits_ntt() {
+        let max = Digit::MAX;
+        // `data`: [(a_len, b_len)]
+        let data = [(1, 1), (1, 2), (3, 5), (16, 16), (17, 64), (100, 31)];
+        for (a_len, b_len) in data {
+            for (a, b) in [
+                (vec![max; a_len], vec![max; b_len]),
+                (
+                    (1..=a_len as Digit).collect(),
+                    (1..=b_len as Digit).rev().collect(),
+                ),
+            ] {
+                let product = mul_digits_ntt(&a, &b);
+                assert_eq!(product.len(), a_len + b_len);
+                assert_eq!(normalized(product), long_mul_digits(&a, &b));
+            }
+        }
+
+        // zero padding
+        assert_eq!(
+            normalized(mul_digits_ntt(&[3, 0, 0], &[5, 0])),
+            digits_be!(15)
+        );
+    }
+
+    #[quickcheck]
+    fn ntt_mul_matches_karatsuba_mul(a: Vec<Digit>, b: Vec<Digit>) -> bool {
+        let a = normalized(a);
+        let b = normalized(b);
+        normalized(mul_digits_ntt(&a, &b)) == mul_with_threshold(&a, &b, 4).0
+    }
+
+    #[test]
+    fn test_ntt_threshold() {
+        for (a_len, b_len) in [
+            (NTT_THRESHOLD, NTT_THRESHOLD),
+            (NTT_THRESHOLD + 3, NTT_THRESHOLD + 1),
+        ] {
+            let a: Vec<Digit> = (0..a_len).map(|i| (i * 7 + 3) as Digit).collect();
+            let b = vec![Digit::MAX; b_len];
+            let mut output = digitvec_multiplying_output_filled_1(a.len(), b.len());
+            let output_len = mul_digits(&a, &b, &mut output);
+            assert_eq!(
+                output[..output_len],
+                mul_with_threshold(&a, &b, KARATSUBA_THRESHOLD).0
+            );
+            assert_eq!(vec!(0; output.len() - output_len), output[output_len..]);
+        }
+    }
+
+    #[test]
+    fn test_mul_assign_signs() {
+        let data = [(5, 7), (5, -7), (-5, 7), (-5, -7), (0, -7), (-5, 0)];
+        for (a, b) in data {
+            let mut c = BigInt::from(a);
+            c *= BigInt::from(b);
+            assert_eq!(c, BigInt::from(a * b));
+            assert_eq!(c.is_sign_negative(), a * b < 0);
+        }
+    }
+
+    #[test]
+    fn test_mul_assign_reuses_storage() {
+        let mut a = BigInt::from_hex("0100000000000000000000000000000000").unwrap();
+        a -= BigInt::from_hex("01000000000000000000000000000000").unwrap();
+        let storage_ptr = a.digits_storage.as_ptr();
+        a *= BigInt::from(0xff);
+        assert_eq!(
+            a,
+            BigInt::from_hex("fe01000000000000000000000000000000").unwrap()
+        );
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+    }
+
+    #[quickcheck]
+    fn mul_assign_matches_mul(a_hex: BigIntHexString, b_hex: BigIntHexString) -> bool {
+        let a = BigInt::from_hex(&a_hex.0).unwrap();
+        let b = BigInt::from_hex(&b_hex.0).unwrap();
+        let mut c = a.clone();
+        c *= &b;
+        c == &a * &b && !(c.is_zero() && c.is_sign_negative())
+    }
 }
@@ -5,7 +5,7 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::bigint_core::{BigInt, Sign};
-use std::ops::Neg;
+use core::ops::Neg;
 
 impl Neg for Sign {
     type Output = Self;
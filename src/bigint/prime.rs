@@ -0,0 +1,320 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements primality tests.
+
+use super::bigint_core::BigInt;
+use crate::math::barrett::BarrettContext;
+use crate::math::modular::{modulo, pow};
+
+/// The bases of the Miller-Rabin rounds, also used for trial division.
+const SMALL_PRIMES: [u32; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+impl BigInt {
+    /// Returns true if `self` is a strong probable prime to each of the first `rounds`
+    /// prime bases (2, 3, 5, ...).
+    ///
+    /// The bases are fixed rather than random, and at most 16 rounds are performed.
+    /// A `false` is always correct, a `true` is correct with high probability.
+    pub fn is_probably_prime(&self, rounds: usize) -> bool {
+        if let Some(is_prime) = self.small_prime_check() {
+            return is_prime;
+        }
+
+        let context = BarrettContext::new(self);
+        SMALL_PRIMES
+            .iter()
+            .take(rounds)
+            .all(|&base| is_strong_probable_prime(self, &BigInt::from(base), &context))
+    }
+
+    /// Returns true if `self` passes the Baillie-PSW primality test.
+    ///
+    /// Combines a Miller-Rabin round of base 2 and a strong Lucas probable prime test with
+    /// the parameters chosen by Selfridge's method A. No composite passing the test is known.
+    pub fn is_baillie_psw_prime(&self) -> bool {
+        if let Some(is_prime) = self.small_prime_check() {
+            return is_prime;
+        }
+
+        self.is_probably_prime(1) && is_strong_lucas_probable_prime(self)
+    }
+
+    /// Decides the primality of `self` by trial division with `SMALL_PRIMES`.
+    ///
+    /// Returns `None` if the division is inconclusive, in which case `self` is odd and
+    /// greater than the largest of `SMALL_PRIMES`.
+    fn small_prime_check(&self) -> Option<bool> {
+        if *self <= 1 {
+            return Some(false);
+        }
+
+        for p in SMALL_PRIMES {
+            let p = BigInt::from(p);
+            if self == &p {
+                return Some(true);
+            }
+            if (self % &p).is_zero() {
+                return Some(false);
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns true if the odd `n` is a strong probable prime to `base`.
+fn is_strong_probable_prime(n: &BigInt, base: &BigInt, context: &BarrettContext) -> bool {
+    let one = BigInt::one();
+    let n_minus_1 = n - &one;
+
+    // n - 1 = d * 2 ^ s, with d odd
+    let s = n_minus_1.trailing_zeros();
+    let d = &n_minus_1 >> s;
+
+    let mut x = pow(base, &d, n, Some(context));
+    if x == one || x == n_minus_1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = context.mul(&x, &x);
+        if x == n_minus_1 {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Returns true if the odd `n` is a strong Lucas probable prime, with the parameters
+/// chosen by Selfridge's method A.
+fn is_strong_lucas_probable_prime(n: &BigInt) -> bool {
+    // Finds the first D in the sequence 5, -7, 9, -11, ... with Jacobi(D / n) = -1.
+    // No such D exists for a perfect square, which is checked once a few candidates failed.
+    let mut d: i64 = 5;
+    loop {
+        match jacobi(&BigInt::from(d), n) {
+            -1 => break,
+            0 if &BigInt::from(d.abs()) != n => {
+                // `n` shares a factor with D.
+                return false;
+            }
+            _ => {}
+        }
+        if d == 13 && is_perfect_square(n) {
+            return false;
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+
+    // P = 1, Q = (1 - D) / 4
+    let q = modulo(&BigInt::from((1 - d) / 4), n);
+    let d = modulo(&BigInt::from(d), n);
+    let context = BarrettContext::new(n);
+    let half = |a: BigInt| {
+        let a = if a.is_odd() { a + n } else { a };
+        modulo(&(a >> 1), n)
+    };
+
+    // n + 1 = k * 2 ^ s, with k odd
+    let n_plus_1 = n + &BigInt::one();
+    let s = n_plus_1.trailing_zeros();
+    let k = &n_plus_1 >> s;
+
+    // Computes U(k), V(k) and Q ^ k from the most significant bit of `k`, with
+    // U(2m) = U(m) * V(m), V(2m) = V(m) ^ 2 - 2 * Q ^ m,
+    // U(m + 1) = (P * U(m) + V(m)) / 2, V(m + 1) = (D * U(m) + P * V(m)) / 2.
+    let mut u = BigInt::one();
+    let mut v = BigInt::one();
+    let mut q_k = q.clone();
+    for bit in k.le_bits().into_iter().rev().skip(1) {
+        u = context.mul(&u, &v);
+        v = modulo(&(context.mul(&v, &v) - &q_k - &q_k), n);
+        q_k = context.mul(&q_k, &q_k);
+        if bit {
+            let next_u = half(&u + &v);
+            let next_v = half(context.mul(&d, &u) + &v);
+            u = next_u;
+            v = next_v;
+            q_k = context.mul(&q_k, &q);
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    // V(k * 2 ^ r) for 0 < r < s
+    for _ in 1..s {
+        v = modulo(&(context.mul(&v, &v) - &q_k - &q_k), n);
+        if v.is_zero() {
+            return true;
+        }
+        q_k = context.mul(&q_k, &q_k);
+    }
+
+    false
+}
+
+/// Returns the Jacobi symbol (a / n), for an odd positive `n`.
+pub(crate) fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    debug_assert!(n.is_odd());
+    debug_assert!(*n > 0);
+
+    let mut a = modulo(a, n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        let zeros = a.trailing_zeros();
+        a = a >> zeros;
+        // (2 / n) = -1 if n = 3 or 5 (mod 8)
+        let n_mod_8 = n.as_digits()[0] & 7;
+        if zeros % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            result = -result;
+        }
+        // Quadratic reciprocity: flips the sign if both are 3 (mod 4).
+        if a.as_digits()[0] & 3 == 3 && n_mod_8 & 3 == 3 {
+            result = -result;
+        }
+        core::mem::swap(&mut a, &mut n);
+        a %= &n;
+    }
+
+    if n.is_one() {
+        result
+    } else {
+        0
+    }
+}
+
+/// Returns true if `n` is the square of an integer.
+fn is_perfect_square(n: &BigInt) -> bool {
+    // Newton's method, starting from an over-estimate.
+    let mut x = BigInt::one() << n.bit_len().div_ceil(2);
+    loop {
+        let y = (&x + &(n / &x)) >> 1;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+
+    &x * &x == *n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{secp256k1, secp256r1};
+
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut is_prime = vec![true; limit];
+        is_prime[0] = false;
+        is_prime[1] = false;
+        for i in 2..limit {
+            if is_prime[i] {
+                for j in (i * i..limit).step_by(i) {
+                    is_prime[j] = false;
+                }
+            }
+        }
+        is_prime
+    }
+
+    #[test]
+    fn test_small_numbers() {
+        for (n, is_prime) in sieve(3000).into_iter().enumerate() {
+            let a = BigInt::from(n);
+            assert_eq!(a.is_baillie_psw_prime(), is_prime, "{n}");
+            assert_eq!(a.is_probably_prime(16), is_prime, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_jacobi() {
+        // https://en.wikipedia.org/wiki/Jacobi_symbol#Table_of_values
+        let data = [
+            (1, 1, 1),
+            (2, 3, -1),
+            (5, 9, 1),
+            (6, 9, 0),
+            (2, 15, 1),
+            (7, 15, -1),
+            (-1, 7, -1),
+            (-7, 11, 1),
+            (30, 59, -1),
+        ];
+        for (a, n, symbol) in data {
+            assert_eq!(
+                jacobi(&BigInt::from(a), &BigInt::from(n)),
+                symbol,
+                "{a} / {n}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // Strong pseudoprimes to base 2 (OEIS A001262), without factors in `SMALL_PRIMES`.
+        for n in [42799u64, 49141, 65281, 88357, 3215031751] {
+            let n = BigInt::from(n);
+            assert!(n.is_probably_prime(1));
+            assert!(!n.is_baillie_psw_prime());
+        }
+
+        // Strong Lucas pseudoprimes (OEIS A217255).
+        for n in [5459u64, 5777, 10877, 16109, 18971] {
+            let n = BigInt::from(n);
+            assert!(is_strong_lucas_probable_prime(&n));
+            assert!(!n.is_baillie_psw_prime());
+        }
+
+        // Carmichael numbers
+        for n in [561u64, 1105, 1729, 2465, 2821, 6601] {
+            assert!(!BigInt::from(n).is_baillie_psw_prime());
+        }
+    }
+
+    #[test]
+    fn test_perfect_squares() {
+        for n in [59u64 * 59, 65537 * 65537, 4294967291 * 4294967291] {
+            let n = BigInt::from(n);
+            assert!(is_perfect_square(&n));
+            assert!(!n.is_baillie_psw_prime());
+            assert!(!is_perfect_square(&(n + BigInt::from(2))));
+        }
+    }
+
+    #[test]
+    fn test_large_primes() {
+        let secp256k1 = secp256k1();
+        let p256 = secp256r1();
+        let mersenne_127 = (BigInt::one() << 127) - BigInt::one();
+        let mersenne_521 = (BigInt::one() << 521) - BigInt::one();
+        let primes = [
+            BigInt::from(4294967291u32),
+            BigInt::from(18446744073709551557u64),
+            mersenne_127,
+            mersenne_521,
+            secp256k1.curve.p.clone(),
+            secp256k1.base_point_order.clone(),
+            p256.curve.p.clone(),
+            p256.base_point_order.clone(),
+        ];
+        for p in primes {
+            assert!(p.is_baillie_psw_prime(), "{p}");
+            assert!(p.is_probably_prime(16), "{p}");
+
+            let composite = &p * &BigInt::from(4294967291u32);
+            assert!(!composite.is_baillie_psw_prime(), "{composite}");
+            // The neighbors of large primes are even.
+            assert!(!(&p + &BigInt::one()).is_baillie_psw_prime());
+        }
+
+        // 2 ^ 128 + 1 = 59649589127497217 * 5704689200685129054721
+        let fermat_7 = (BigInt::one() << 128) + BigInt::one();
+        assert!(!fermat_7.is_baillie_psw_prime());
+    }
+}
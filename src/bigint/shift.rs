@@ -8,7 +8,9 @@ use super::bigint_core::BigInt;
 use super::len::len_digits;
 use crate::bigint::bits::bit_len_digits;
 use crate::bigint::digit::Digit;
-use std::ops::{Shl, Shr};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Shl, Shr};
 
 /// Returns the effective digits length of the shifted.
 pub(crate) fn shift_right_digits(digits: &mut [Digit], digits_len: usize, n: usize) -> usize {
@@ -48,7 +50,7 @@ pub(crate) fn shift_right_digits(digits: &mut [Digit], digits_len: usize, n: usi
 impl BigInt {
     fn shift_right(&mut self, n: usize) {
         self.digits_len = shift_right_digits(
-            &mut self.digits_storage[..self.digits_len],
+            &mut self.digits_storage.to_mut()[..self.digits_len],
             self.digits_len,
             n,
         );
@@ -113,7 +115,7 @@ pub(crate) fn shift_left_digits(digits: &mut Vec<Digit>, digits_len: usize, n: u
 
 impl BigInt {
     fn shift_left(&mut self, n: usize) {
-        self.digits_len = shift_left_digits(&mut self.digits_storage, self.digits_len, n);
+        self.digits_len = shift_left_digits(self.digits_storage.to_mut(), self.digits_len, n);
     }
 }
 
@@ -14,9 +14,9 @@ use super::cmp::cmp_digits;
 use super::digit::Digit;
 use super::helper_methods::borrowing_sub;
 use super::len::len_digits;
-use std::cmp;
-use std::cmp::Ordering;
-use std::ops::Sub;
+use core::cmp;
+use core::cmp::Ordering;
+use core::ops::{Sub, SubAssign};
 
 /// Subtracts `b` from `a`, and fills the output to `result`,
 /// returning the length of the output digits.
@@ -79,6 +79,58 @@ pub(crate) fn sub_digits(a: &BigUintSlice, b: &BigUintSlice, result: &mut [Digit
     len_digits(result)
 }
 
+/// Subtracts `b` from `a` in place: a -= b, returning the length of the output digits.
+///
+/// `a` must be no less than `b` (a >= b), and may have zero padding.
+///
+/// # Panics:
+///
+/// Panics if `a` is less than `b`.
+#[inline]
+pub(crate) fn sub_digits_in_place(a: &mut [Digit], b: &BigUintSlice) -> usize {
+    debug_assert!(is_valid_biguint_slice(b));
+    assert!(a.len() >= b.len(), "attempt to subtract with overflow");
+
+    let mut borrow = false;
+    for (a_digit, &b_digit) in a.iter_mut().zip(b) {
+        (*a_digit, borrow) = borrowing_sub(*a_digit, b_digit, borrow);
+    }
+    for a_digit in &mut a[b.len()..] {
+        if !borrow {
+            break;
+        }
+        (*a_digit, borrow) = borrowing_sub(*a_digit, 0, borrow);
+    }
+    assert!(!borrow, "attempt to subtract with overflow");
+
+    len_digits(a)
+}
+
+/// Subtracts `a` from `b`, and stores the output in `a`: a = b - a,
+/// returning the length of the output digits.
+///
+/// - `b` must be greater than `a` (b > a).
+/// - `a` holds `a_len` digits, followed by zeros, and must have a length no less than
+///   `b.len()`.
+#[inline]
+pub(crate) fn reverse_sub_digits_in_place(
+    a: &mut [Digit],
+    a_len: usize,
+    b: &BigUintSlice,
+) -> usize {
+    debug_assert!(is_valid_biguint_slice(b));
+    debug_assert!(a.len() >= b.len() && b.len() >= a_len);
+    debug_assert!(cmp_digits(&a[..a_len], b) == Ordering::Less);
+
+    let mut borrow = false;
+    for (a_digit, &b_digit) in a.iter_mut().zip(b) {
+        (*a_digit, borrow) = borrowing_sub(b_digit, *a_digit, borrow);
+    }
+    debug_assert!(!borrow);
+
+    len_digits(&a[..b.len()])
+}
+
 impl<'a, 'b> Sub<&'b BigInt> for &'a BigInt {
     type Output = BigInt;
 
@@ -133,6 +185,18 @@ impl Sub for BigInt {
     }
 }
 
+impl SubAssign<&BigInt> for BigInt {
+    fn sub_assign(&mut self, rhs: &BigInt) {
+        self.add_assign_signed(rhs.as_digits(), -rhs.sign);
+    }
+}
+
+impl SubAssign for BigInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.sub_assign(&rhs);
+    }
+}
+
 /// Returns the length of the largest possible output of an subtraction operation: a - b.
 ///
 /// `a_len` and `b_len` are the length of the operands.
@@ -167,6 +231,8 @@ mod tests {
     use super::*;
     use crate::bigint::bigint_vec::digits_be;
     use crate::bigint::digit::Digit;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_sub_digits() {
@@ -233,4 +299,84 @@ mod tests {
             assert_eq!(a - b, c)
         }
     }
+
+    #[test]
+    fn test_sub_digits_in_place() {
+        // `data`: [(a, b, result)]
+        let data = [
+            (
+                digits_be!(4, 5, 6),
+                digits_be!(1, 2, 3),
+                digits_be!(3, 3, 3),
+            ),
+            (
+                digits_be!(1, 0, 0),
+                digits_be!(1),
+                digits_be!(Digit::MAX, Digit::MAX),
+            ),
+            (digits_be!(4, 5, 6), digits_be!(4, 5, 6), digits_be!(0)),
+        ];
+        for (a, b, result) in data {
+            let mut output = a.clone();
+            let output_len = sub_digits_in_place(&mut output, &b);
+            assert_eq!(result, output[..output_len]);
+
+            // b - (b - a) = a
+            let mut output = result.clone();
+            output.resize(a.len(), 0);
+            if result != a {
+                let output_len = reverse_sub_digits_in_place(&mut output, result.len(), &a);
+                assert_eq!(b, output[..output_len]);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_sub_digits_in_place_overflow() {
+        let mut a = digits_be!(0, 1, 2);
+        sub_digits_in_place(&mut a, &digits_be!(1, 2, 3));
+    }
+
+    #[test]
+    fn test_sub_assign_signs() {
+        let data = [
+            (5, 7),
+            (7, 5),
+            (5, -7),
+            (-5, 7),
+            (-5, -7),
+            (5, 5),
+            (-5, -5),
+            (0, 1),
+        ];
+        for (a, b) in data {
+            let mut c = BigInt::from(a);
+            c -= BigInt::from(b);
+            assert_eq!(c, BigInt::from(a - b));
+            assert_eq!(c.is_sign_negative(), a - b < 0);
+        }
+    }
+
+    #[test]
+    fn test_sub_assign_reuses_storage() {
+        let mut a = BigInt::from_hex("0100000000000000000000000000000000").unwrap();
+        let storage_ptr = a.digits_storage.as_ptr();
+        a -= BigInt::from_hex("ffffffffffffffffffffffffffffffff").unwrap();
+        assert_eq!(a, BigInt::one());
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+
+        // the output grows within the storage
+        a -= BigInt::from_hex("-01000000000000000000000000000000").unwrap();
+        assert_eq!(a.digits_storage.as_ptr(), storage_ptr);
+    }
+
+    #[quickcheck]
+    fn sub_assign_matches_sub(a_hex: BigIntHexString, b_hex: BigIntHexString) -> bool {
+        let a = BigInt::from_hex(&a_hex.0).unwrap();
+        let b = BigInt::from_hex(&b_hex.0).unwrap();
+        let mut c = a.clone();
+        c -= &b;
+        c == &a - &b && !(c.is_zero() && c.is_sign_negative())
+    }
 }
@@ -0,0 +1,594 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::fmt;
+use std::fmt::Display;
+
+/// Represents a Contract ABI type, the counterpart of `AbiValue`.
+///
+/// The bit lengths of `Uint` and `Int` are multiples of 8 in [8, 256],
+/// and the byte length of `FixedBytes` is in [1, 32].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbiType {
+    /// `address`
+    Address,
+    /// `bool`
+    Bool,
+    /// `uint<M>`
+    Uint(usize),
+    /// `int<M>`
+    Int(usize),
+    /// `bytes<M>`
+    FixedBytes(usize),
+    /// `bytes`
+    Bytes,
+    /// `string`
+    String,
+    /// `T[k]`
+    FixedArray(Box<AbiType>, usize),
+    /// `T[]`
+    Array(Box<AbiType>),
+    /// `(T1,T2,...,Tn)`
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Returns `true` if `self` is "dynamic".
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(t, _) => t.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(|t| t.is_dynamic()),
+            _ => false,
+        }
+    }
+}
+
+/// Parses the Solidity type name `s`, e.g., `uint256`, `bytes32[5]` or `(uint256,address)[]`.
+///
+/// The aliases `uint` and `int` are parsed as `uint256` and `int256`. The names are the
+/// canonical ones of signatures, without whitespace or parameter names.
+///
+/// # Errors
+///
+/// Will return `AbiTypeParseError::UnsupportedType` for `fixed<M>x<N>`, `ufixed<M>x<N>` and
+/// `function`, which `AbiType` doesn't represent, and `AbiTypeParseError::TooDeeplyNested`
+/// if the tuples and arrays are nested more than 64 levels.
+pub fn parse_abi_type(s: &str) -> Result<AbiType, AbiTypeParseError> {
+    let mut parser = AbiTypeParser {
+        s: s.as_bytes(),
+        position: 0,
+    };
+    let (abi_type, _) = parser.parse_type(0)?;
+    if parser.position != s.len() {
+        return Err(AbiTypeParseError::InvalidSyntax {
+            position: parser.position,
+        });
+    }
+    Ok(abi_type)
+}
+
+// Tuples and arrays nested deeper are rejected, for the parser recurses into the tuples,
+// and `Display`, `is_dynamic` and dropping a type recurse into both.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// A recursive descent parser of the grammar
+///
+/// ```text
+/// type       = base_type { "[" [ length ] "]" }
+/// base_type  = "(" [ type { "," type } ] ")" | name
+/// ```
+///
+/// The parsing methods return the type along with its nesting height: 0 for an
+/// elementary type, 1 plus the height of the element for an array, and 1 plus the
+/// greatest height of the components for a tuple.
+struct AbiTypeParser<'a> {
+    s: &'a [u8],
+    position: usize,
+}
+
+impl<'a> AbiTypeParser<'a> {
+    fn parse_type(&mut self, depth: usize) -> Result<(AbiType, usize), AbiTypeParseError> {
+        let (mut abi_type, mut height) = if self.peek() == Some(b'(') {
+            self.parse_tuple(depth)?
+        } else {
+            (self.parse_name()?, 0)
+        };
+
+        while self.peek() == Some(b'[') {
+            // Fails before nesting the type any deeper.
+            if height == MAX_NESTING_DEPTH {
+                return Err(AbiTypeParseError::TooDeeplyNested);
+            }
+            height += 1;
+            self.position += 1;
+            let digits = self.take_while(|c| c.is_ascii_digit());
+            self.expect(b']')?;
+            abi_type = if digits.is_empty() {
+                AbiType::Array(Box::new(abi_type))
+            } else {
+                // Rejects zero-length arrays and the leading zeros.
+                if digits[0] == b'0' {
+                    return Err(AbiTypeParseError::InvalidArrayLength);
+                }
+                // Only ASCII digits are taken
+                let len = core::str::from_utf8(digits)
+                    .unwrap()
+                    .parse()
+                    .map_err(|_| AbiTypeParseError::InvalidArrayLength)?;
+                AbiType::FixedArray(Box::new(abi_type), len)
+            };
+        }
+        Ok((abi_type, height))
+    }
+
+    fn parse_tuple(&mut self, depth: usize) -> Result<(AbiType, usize), AbiTypeParseError> {
+        if depth == MAX_NESTING_DEPTH {
+            return Err(AbiTypeParseError::TooDeeplyNested);
+        }
+
+        self.expect(b'(')?;
+        let mut types = vec![];
+        if self.peek() == Some(b')') {
+            self.position += 1;
+            return Ok((AbiType::Tuple(types), 1));
+        }
+        let mut height = 0;
+        loop {
+            let (abi_type, component_height) = self.parse_type(depth + 1)?;
+            if component_height == MAX_NESTING_DEPTH {
+                return Err(AbiTypeParseError::TooDeeplyNested);
+            }
+            types.push(abi_type);
+            height = height.max(component_height + 1);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b')') => {
+                    self.position += 1;
+                    return Ok((AbiType::Tuple(types), height));
+                }
+                _ => {
+                    return Err(AbiTypeParseError::InvalidSyntax {
+                        position: self.position,
+                    })
+                }
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<AbiType, AbiTypeParseError> {
+        let start = self.position;
+        let name = self.take_while(|c| c.is_ascii_alphanumeric());
+        if name.is_empty() {
+            return Err(AbiTypeParseError::InvalidSyntax { position: start });
+        }
+        // Only ASCII alphanumerics are taken
+        let name = core::str::from_utf8(name).unwrap();
+
+        let abi_type = match name {
+            "address" => AbiType::Address,
+            "bool" => AbiType::Bool,
+            "bytes" => AbiType::Bytes,
+            "string" => AbiType::String,
+            "uint" => AbiType::Uint(256),
+            "int" => AbiType::Int(256),
+            "function" => return Err(AbiTypeParseError::UnsupportedType),
+            _ if name.starts_with("fixed") || name.starts_with("ufixed") => {
+                return Err(AbiTypeParseError::UnsupportedType);
+            }
+            _ => {
+                if let Some(bits) = parse_suffix(name, "uint") {
+                    check_bit_len(bits).map(AbiType::Uint)?
+                } else if let Some(bits) = parse_suffix(name, "int") {
+                    check_bit_len(bits).map(AbiType::Int)?
+                } else if let Some(len) = parse_suffix(name, "bytes") {
+                    if !(1..=32).contains(&len) {
+                        return Err(AbiTypeParseError::UnknownType);
+                    }
+                    AbiType::FixedBytes(len)
+                } else {
+                    return Err(AbiTypeParseError::UnknownType);
+                }
+            }
+        };
+        Ok(abi_type)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.position).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), AbiTypeParseError> {
+        if self.peek() != Some(c) {
+            return Err(AbiTypeParseError::InvalidSyntax {
+                position: self.position,
+            });
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a [u8] {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+        &self.s[start..self.position]
+    }
+}
+
+/// Returns the decimal number following `prefix` in `name`, without leading zeros.
+fn parse_suffix(name: &str, prefix: &str) -> Option<usize> {
+    let digits = name.strip_prefix(prefix)?;
+    if digits.is_empty()
+        || digits.starts_with('0')
+        || !digits.bytes().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+fn check_bit_len(bits: usize) -> Result<usize, AbiTypeParseError> {
+    if bits.is_multiple_of(8) && (8..=256).contains(&bits) {
+        Ok(bits)
+    } else {
+        Err(AbiTypeParseError::UnknownType)
+    }
+}
+
+/// An error returned by [`parse_abi_type`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbiTypeParseError {
+    /// A character at `position` (in bytes) doesn't fit the grammar, or the input ends early.
+    InvalidSyntax { position: usize },
+    /// An elementary type name isn't known, e.g., "uint7", "bytes33" or "uint256 ".
+    UnknownType,
+    /// A type of the specification `AbiType` doesn't represent, e.g., "fixed128x18".
+    UnsupportedType,
+    /// The length of a fixed-size array is zero, has leading zeros or doesn't fit in
+    /// `usize`.
+    InvalidArrayLength,
+    /// The tuples and arrays are nested too deeply.
+    TooDeeplyNested,
+}
+
+impl Display for AbiTypeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiTypeParseError::InvalidSyntax { position } => {
+                write!(f, "invalid ABI type syntax at position {position}")
+            }
+            AbiTypeParseError::UnknownType => write!(f, "unknown ABI type"),
+            AbiTypeParseError::UnsupportedType => write!(f, "unsupported ABI type"),
+            AbiTypeParseError::InvalidArrayLength => write!(f, "invalid ABI array length"),
+            AbiTypeParseError::TooDeeplyNested => write!(f, "ABI types nested too deeply"),
+        }
+    }
+}
+
+impl std::error::Error for AbiTypeParseError {}
+
+/// Formats the canonical type name used in function and event signatures,
+/// e.g., "uint256" and "(address,bytes32)[]".
+impl Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiType::Address => write!(f, "address"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Uint(bits) => write!(f, "uint{bits}"),
+            AbiType::Int(bits) => write!(f, "int{bits}"),
+            AbiType::FixedBytes(len) => write!(f, "bytes{len}"),
+            AbiType::Bytes => write!(f, "bytes"),
+            AbiType::String => write!(f, "string"),
+            AbiType::FixedArray(t, len) => write!(f, "{t}[{len}]"),
+            AbiType::Array(t) => write!(f, "{t}[]"),
+            AbiType::Tuple(types) => {
+                let names: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+                write!(f, "({})", names.join(","))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use devtools::path::integration_testing_data_path;
+    use serde_json::Value;
+    use std::fs::File;
+
+    #[test]
+    fn test_display() {
+        let data = [
+            (AbiType::Address, "address"),
+            (AbiType::Uint(256), "uint256"),
+            (AbiType::Int(8), "int8"),
+            (AbiType::FixedBytes(32), "bytes32"),
+            (AbiType::FixedArray(Box::new(AbiType::Bool), 2), "bool[2]"),
+            (
+                AbiType::Array(Box::new(AbiType::Tuple(vec![
+                    AbiType::Address,
+                    AbiType::String,
+                ]))),
+                "(address,string)[]",
+            ),
+            (
+                AbiType::Array(Box::new(AbiType::FixedArray(Box::new(AbiType::Bytes), 3))),
+                "bytes[3][]",
+            ),
+            (AbiType::Tuple(vec![]), "()"),
+        ];
+        for (abi_type, name) in data {
+            assert_eq!(abi_type.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_is_dynamic() {
+        let data = [
+            (AbiType::Uint(256), false),
+            (AbiType::String, true),
+            (AbiType::FixedArray(Box::new(AbiType::Uint(8)), 2), false),
+            (AbiType::FixedArray(Box::new(AbiType::Bytes), 2), true),
+            (AbiType::Array(Box::new(AbiType::Bool)), true),
+            (AbiType::Tuple(vec![AbiType::Bool, AbiType::Address]), false),
+            (AbiType::Tuple(vec![AbiType::Bool, AbiType::Bytes]), true),
+        ];
+        for (abi_type, is_dynamic) in data {
+            assert_eq!(abi_type.is_dynamic(), is_dynamic);
+        }
+        let data = [
+            // (name, is_dynamic)
+            ("bytes32[5]", false),
+            ("string", true),
+            ("uint8[]", true),
+            ("string[2]", true),
+            ("(uint256,address)", false),
+            ("(bool,(address,bytes))", true),
+            ("(bool,(address,bytes32)[2])", false),
+            ("(bool,(address,bytes32)[])", true),
+        ];
+        for (name, is_dynamic) in data {
+            assert_eq!(
+                parse_abi_type(name).unwrap().is_dynamic(),
+                is_dynamic,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_abi_type_elementary_types() {
+        for bits in (8..=256).step_by(8) {
+            assert_eq!(
+                parse_abi_type(&format!("uint{bits}")),
+                Ok(AbiType::Uint(bits))
+            );
+            assert_eq!(
+                parse_abi_type(&format!("int{bits}")),
+                Ok(AbiType::Int(bits))
+            );
+        }
+        for len in 1..=32 {
+            assert_eq!(
+                parse_abi_type(&format!("bytes{len}")),
+                Ok(AbiType::FixedBytes(len))
+            );
+        }
+
+        let data = [
+            // (name, type)
+            ("uint", AbiType::Uint(256)),
+            ("int", AbiType::Int(256)),
+            ("address", AbiType::Address),
+            ("bool", AbiType::Bool),
+            ("bytes", AbiType::Bytes),
+            ("string", AbiType::String),
+        ];
+        for (name, abi_type) in data {
+            assert_eq!(parse_abi_type(name), Ok(abi_type));
+        }
+
+        for name in ["function", "fixed", "ufixed", "fixed128x18", "ufixed8x1"] {
+            assert_eq!(
+                parse_abi_type(name),
+                Err(AbiTypeParseError::UnsupportedType),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_abi_type_composite_types() {
+        let data = [
+            // (name, type)
+            (
+                "bytes32[5]",
+                AbiType::FixedArray(Box::new(AbiType::FixedBytes(32)), 5),
+            ),
+            ("address[]", AbiType::Array(Box::new(AbiType::Address))),
+            (
+                "uint256[2][]",
+                AbiType::Array(Box::new(AbiType::FixedArray(
+                    Box::new(AbiType::Uint(256)),
+                    2,
+                ))),
+            ),
+            (
+                "string[][3]",
+                AbiType::FixedArray(Box::new(AbiType::Array(Box::new(AbiType::String))), 3),
+            ),
+            (
+                "(uint256,address)",
+                AbiType::Tuple(vec![AbiType::Uint(256), AbiType::Address]),
+            ),
+            ("()", AbiType::Tuple(vec![])),
+            ("(())", AbiType::Tuple(vec![AbiType::Tuple(vec![])])),
+            (
+                "(uint,(bytes,int8[])[2])[]",
+                AbiType::Array(Box::new(AbiType::Tuple(vec![
+                    AbiType::Uint(256),
+                    AbiType::FixedArray(
+                        Box::new(AbiType::Tuple(vec![
+                            AbiType::Bytes,
+                            AbiType::Array(Box::new(AbiType::Int(8))),
+                        ])),
+                        2,
+                    ),
+                ]))),
+            ),
+        ];
+        for (name, abi_type) in data {
+            assert_eq!(parse_abi_type(name).as_ref(), Ok(&abi_type), "{name}");
+            // The canonical names parse to the same type
+            assert_eq!(parse_abi_type(&abi_type.to_string()), Ok(abi_type));
+        }
+    }
+
+    #[test]
+    fn test_parse_abi_type_error_cases() {
+        let data = [
+            // (name, error)
+            ("", AbiTypeParseError::InvalidSyntax { position: 0 }),
+            ("uint256 ", AbiTypeParseError::InvalidSyntax { position: 7 }),
+            (" uint256", AbiTypeParseError::InvalidSyntax { position: 0 }),
+            ("uint256[", AbiTypeParseError::InvalidSyntax { position: 8 }),
+            (
+                "uint256[2",
+                AbiTypeParseError::InvalidSyntax { position: 9 },
+            ),
+            (
+                "uint256[a]",
+                AbiTypeParseError::InvalidSyntax { position: 8 },
+            ),
+            ("uint256]", AbiTypeParseError::InvalidSyntax { position: 7 }),
+            ("(uint256", AbiTypeParseError::InvalidSyntax { position: 8 }),
+            (
+                "(uint256,)",
+                AbiTypeParseError::InvalidSyntax { position: 9 },
+            ),
+            (
+                "(,uint256)",
+                AbiTypeParseError::InvalidSyntax { position: 1 },
+            ),
+            (
+                "(uint256 address)",
+                AbiTypeParseError::InvalidSyntax { position: 8 },
+            ),
+            (
+                "(uint256))",
+                AbiTypeParseError::InvalidSyntax { position: 9 },
+            ),
+            (
+                "uint256 a",
+                AbiTypeParseError::InvalidSyntax { position: 7 },
+            ),
+            ("uint7", AbiTypeParseError::UnknownType),
+            ("uint264", AbiTypeParseError::UnknownType),
+            ("uint0", AbiTypeParseError::UnknownType),
+            ("uint08", AbiTypeParseError::UnknownType),
+            ("int1024", AbiTypeParseError::UnknownType),
+            ("bytes0", AbiTypeParseError::UnknownType),
+            ("bytes33", AbiTypeParseError::UnknownType),
+            ("Address", AbiTypeParseError::UnknownType),
+            ("uint256x", AbiTypeParseError::UnknownType),
+            ("tuple", AbiTypeParseError::UnknownType),
+            ("bool[0]", AbiTypeParseError::InvalidArrayLength),
+            ("bool[01]", AbiTypeParseError::InvalidArrayLength),
+            ("bool[00]", AbiTypeParseError::InvalidArrayLength),
+            (
+                "bool[99999999999999999999999]",
+                AbiTypeParseError::InvalidArrayLength,
+            ),
+        ];
+        for (name, err) in data {
+            assert_eq!(parse_abi_type(name), Err(err), "{name}");
+        }
+
+        let nested = format!("{}{}", "(".repeat(64), ")".repeat(64));
+        assert!(parse_abi_type(&nested).is_ok());
+        let nested = format!("{}{}", "(".repeat(65), ")".repeat(65));
+        assert_eq!(
+            parse_abi_type(&nested),
+            Err(AbiTypeParseError::TooDeeplyNested)
+        );
+        // Fails without recursing into all the parentheses
+        assert_eq!(
+            parse_abi_type(&"(".repeat(1_000_000)),
+            Err(AbiTypeParseError::TooDeeplyNested)
+        );
+
+        // Array suffixes count toward the nesting depth.
+        assert!(parse_abi_type(&format!("uint{}", "[]".repeat(64))).is_ok());
+        for name in [
+            format!("uint{}", "[]".repeat(65)),
+            format!("uint{}", "[2]".repeat(65)),
+            format!("(uint{})", "[]".repeat(64)),
+            format!(
+                "{}uint{}[]",
+                "(".repeat(32),
+                ")".repeat(32).replace(')', ")[]")
+            ),
+        ] {
+            assert_eq!(
+                parse_abi_type(&name),
+                Err(AbiTypeParseError::TooDeeplyNested),
+                "{name}"
+            );
+        }
+        // Fails without nesting all the arrays, which would overflow the stack when
+        // dropped.
+        assert_eq!(
+            parse_abi_type(&format!("uint{}", "[]".repeat(1_000_000))),
+            Err(AbiTypeParseError::TooDeeplyNested)
+        );
+    }
+
+    #[test]
+    fn test_parse_abi_type_erc20() {
+        let path = integration_testing_data_path("blockchain/ethereum/erc20.abi.json");
+        let file = File::open(path).unwrap();
+        let entries: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+        let mut signatures = vec![];
+        for entry in &entries {
+            let types: Vec<AbiType> = entry["inputs"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|input| parse_abi_type(input["type"].as_str().unwrap()).unwrap())
+                .collect();
+            if let Some(outputs) = entry["outputs"].as_array() {
+                for output in outputs {
+                    parse_abi_type(output["type"].as_str().unwrap()).unwrap();
+                }
+            }
+            signatures.push(format!(
+                "{}{}",
+                entry["name"].as_str().unwrap(),
+                AbiType::Tuple(types)
+            ));
+        }
+        assert_eq!(
+            signatures,
+            [
+                "name()",
+                "symbol()",
+                "decimals()",
+                "totalSupply()",
+                "balanceOf(address)",
+                "transfer(address,uint256)",
+                "allowance(address,address)",
+                "approve(address,uint256)",
+                "transferFrom(address,address,uint256)",
+                "Transfer(address,address,uint256)",
+                "Approval(address,address,uint256)",
+            ]
+        );
+    }
+}
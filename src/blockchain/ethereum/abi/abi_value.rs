@@ -0,0 +1,159 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::encoding::{encode_in_place, encode_value_word, AbiEncodingError};
+use crate::bigint::{BigInt, BigUint};
+use crate::blockchain::ethereum::types::Address;
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+
+/// Represents a value of a Contract ABI type.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AbiValue {
+    /// `address`
+    Address(Address),
+    /// `bool`
+    Bool(bool),
+    /// `uint<M>`, encoded as a 256-bit word regardless of `M`.
+    Uint(BigUint),
+    /// `int<M>`, encoded as a 256-bit two's complement word regardless of `M`.
+    Int(BigInt),
+    /// `bytes<M>`, `0 < M <= 32`.
+    FixedBytes(Vec<u8>),
+    /// `bytes`
+    Bytes(Vec<u8>),
+    /// `string`
+    String(String),
+    /// `T[k]`
+    FixedArray(Vec<AbiValue>),
+    /// `T[]`
+    Array(Vec<AbiValue>),
+    /// `(T1,T2,...,Tn)`
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// Returns `true` if the type of `self` is "dynamic".
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiValue::Bytes(_) | AbiValue::String(_) | AbiValue::Array(_) => true,
+            AbiValue::FixedArray(values) | AbiValue::Tuple(values) => {
+                values.iter().any(|v| v.is_dynamic())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `self` encoded as an event topic, the way an indexed event parameter
+    /// is stored in a log entry.
+    ///
+    /// - A value of a "value type" is ABI encoded as a word.
+    /// - `bytes` and `string` are hashed with Keccak-256, without padding.
+    /// - Arrays and tuples are encoded "in place" and then hashed with Keccak-256.
+    ///
+    /// See: https://docs.soliditylang.org/en/latest/abi-spec.html#encoding-of-indexed-event-parameters
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if a value doesn't fit in a word, e.g., a `Uint` longer than
+    /// 32 bytes.
+    pub fn encode_as_topic(&self) -> Result<[u8; 32], AbiEncodingError> {
+        let topic = match self {
+            AbiValue::Bytes(bytes) => Keccak256::new().digest(bytes).try_into().unwrap(),
+            AbiValue::String(s) => Keccak256::new().digest(s).try_into().unwrap(),
+            AbiValue::FixedArray(_) | AbiValue::Array(_) | AbiValue::Tuple(_) => {
+                Keccak256::new()
+                    .digest(encode_in_place(self)?)
+                    .try_into()
+                    .unwrap()
+            }
+            _ => encode_value_word(self)?,
+        };
+        Ok(topic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_is_dynamic() {
+        let data = [
+            (AbiValue::Bool(true), false),
+            (AbiValue::FixedBytes(vec![1, 2]), false),
+            (AbiValue::Bytes(vec![]), true),
+            (AbiValue::String("".to_string()), true),
+            (AbiValue::Array(vec![]), true),
+            (AbiValue::FixedArray(vec![AbiValue::Bool(true)]), false),
+            (AbiValue::FixedArray(vec![AbiValue::Bytes(vec![])]), true),
+            (
+                AbiValue::Tuple(vec![
+                    AbiValue::Bool(true),
+                    AbiValue::Uint(BigUint::from(1_u8)),
+                ]),
+                false,
+            ),
+            (
+                AbiValue::Tuple(vec![AbiValue::Bool(true), AbiValue::String("".to_string())]),
+                true,
+            ),
+        ];
+        for (value, is_dynamic) in data {
+            assert_eq!(value.is_dynamic(), is_dynamic);
+        }
+    }
+
+    #[test]
+    fn test_encode_as_topic() {
+        let data = [
+            (
+                AbiValue::Address(
+                    "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+                        .try_into()
+                        .unwrap(),
+                ),
+                "0000000000000000000000006b175474e89094c44da98b954eedeac495271d0f",
+            ),
+            (
+                AbiValue::Bool(true),
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            (
+                AbiValue::Int(BigInt::from(-1)),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            (
+                AbiValue::FixedBytes(vec![0xde, 0xad]),
+                "dead000000000000000000000000000000000000000000000000000000000000",
+            ),
+            // keccak256("")
+            (
+                AbiValue::Bytes(vec![]),
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            // keccak256("hello")
+            (
+                AbiValue::String("hello".to_string()),
+                "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8",
+            ),
+            // keccak256(uint256(1) ++ uint256(2))
+            (
+                AbiValue::Array(vec![
+                    AbiValue::Uint(BigUint::from(1_u8)),
+                    AbiValue::Uint(BigUint::from(2_u8)),
+                ]),
+                "e90b7bceb6e7df5418fb78d8ee546e97c83a08bbccc01a0644d599ccd2a7c2e0",
+            ),
+        ];
+        for (value, topic_hex) in data {
+            assert_eq!(
+                hex::encode_lower(&value.encode_as_topic().unwrap()),
+                topic_hex
+            );
+        }
+    }
+}
@@ -0,0 +1,505 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the Contract ABI decoding.
+//!
+//! The helpers return `None` instead of panicking for malformed data,
+//! e.g., out-of-bounds offsets.
+
+use super::abi_type::AbiType;
+use super::abi_value::AbiValue;
+use super::encoding::{twos_complement, ABI_WORD_BYTE_LENGTH};
+use crate::bigint::{BigInt, BigUint, Sign};
+use crate::blockchain::ethereum::types::{Address, ADDRESS_DATA_BYTE_LENGTH};
+use std::fmt;
+use std::fmt::Display;
+use std::iter;
+
+/// Decodes `data` as the ABI encoding of values of `types`, the inverse of `encode`.
+///
+/// Values are validated against their types, e.g., the padding bytes of a `uint8` must
+/// be zero, and a `string` must be valid UTF-8. Data following the encoding is ignored.
+///
+/// The decoding reads at most `data.len()` bytes in total. A valid encoding reads each
+/// byte at most once, but the offsets of forged data can point many elements, e.g., of
+/// nested `uint256[][]`, at the same "tail", which would make the decoding exponential.
+///
+/// # Errors
+///
+/// Will return `AbiDecodingError::InvalidData` if `data` isn't a valid encoding, reads
+/// more than `data.len()` bytes, or a type is invalid, e.g., `uint7`.
+///
+/// # Examples
+///
+/// ```
+/// use lightcryptotools::bigint::BigUint;
+/// use lightcryptotools::blockchain::ethereum::abi::{decode, encode, AbiType, AbiValue};
+///
+/// let data = encode(&[AbiValue::Uint(BigUint::from(1_u8)), AbiValue::Bool(true)]).unwrap();
+/// let values = decode(&[AbiType::Uint(256), AbiType::Bool], &data).unwrap();
+/// assert_eq!(values, [AbiValue::Uint(BigUint::from(1_u8)), AbiValue::Bool(true)]);
+/// ```
+pub fn decode(types: &[AbiType], data: &[u8]) -> Result<Vec<AbiValue>, AbiDecodingError> {
+    let mut budget = data.len();
+    decode_sequence(types, data, 0, &mut budget).ok_or(AbiDecodingError::InvalidData)
+}
+
+/// Decodes the elements of a tuple or an array, whose encoding starts at `base` of `data`.
+///
+/// `budget` is the count of bytes left to read, see `decode`.
+fn decode_sequence<'a>(
+    types: impl IntoIterator<Item = &'a AbiType>,
+    data: &[u8],
+    base: usize,
+    budget: &mut usize,
+) -> Option<Vec<AbiValue>> {
+    let mut position = base;
+    let mut values = vec![];
+    for abi_type in types {
+        if abi_type.is_dynamic() {
+            consume(budget, ABI_WORD_BYTE_LENGTH)?;
+            let start = base.checked_add(decode_usize(data, position)?)?;
+            values.push(decode_value_within(abi_type, data, start, budget)?);
+            position += ABI_WORD_BYTE_LENGTH;
+        } else {
+            values.push(decode_value_within(abi_type, data, position, budget)?);
+            position = position.checked_add(static_byte_len(abi_type)?)?;
+        }
+    }
+    Some(values)
+}
+
+/// Returns the byte length of the encoding of the static `abi_type`.
+fn static_byte_len(abi_type: &AbiType) -> Option<usize> {
+    match abi_type {
+        AbiType::FixedArray(t, len) => static_byte_len(t)?.checked_mul(*len),
+        AbiType::Tuple(types) => types
+            .iter()
+            .try_fold(0_usize, |sum, t| sum.checked_add(static_byte_len(t)?)),
+        _ => Some(ABI_WORD_BYTE_LENGTH),
+    }
+}
+
+/// Decodes a value of `abi_type`, whose encoding starts at `start` of `data`.
+///
+/// Returns `None` if the bit length of `Uint` or `Int`, or the byte length of
+/// `FixedBytes`, is invalid.
+pub(crate) fn decode_value(abi_type: &AbiType, data: &[u8], start: usize) -> Option<AbiValue> {
+    let mut budget = data.len();
+    decode_value_within(abi_type, data, start, &mut budget)
+}
+
+/// Decodes a value of `abi_type` like `decode_value`, reading at most `budget` bytes.
+fn decode_value_within(
+    abi_type: &AbiType,
+    data: &[u8],
+    start: usize,
+    budget: &mut usize,
+) -> Option<AbiValue> {
+    if matches!(
+        abi_type,
+        AbiType::Address
+            | AbiType::Bool
+            | AbiType::Uint(_)
+            | AbiType::Int(_)
+            | AbiType::FixedBytes(_)
+    ) {
+        consume(budget, ABI_WORD_BYTE_LENGTH)?;
+    }
+    let value = match abi_type {
+        AbiType::Address => AbiValue::Address(decode_address(data, start)?),
+        AbiType::Bool => match decode_usize(data, start)? {
+            0 => AbiValue::Bool(false),
+            1 => AbiValue::Bool(true),
+            _ => return None,
+        },
+        AbiType::Uint(bits) => {
+            let word = decode_word(data, start)?;
+            let (padding, _) = word.split_at(padding_byte_len(*bits)?);
+            if padding.iter().any(|&b| b != 0) {
+                return None;
+            }
+            AbiValue::Uint(BigUint::from_be_bytes(word))
+        }
+        AbiType::Int(bits) => {
+            let mut word = *decode_word(data, start)?;
+            let padding_len = padding_byte_len(*bits)?;
+            let is_negative = word[padding_len] >= 0x80;
+            let padding_byte = if is_negative { 0xff } else { 0 };
+            if word[..padding_len].iter().any(|&b| b != padding_byte) {
+                return None;
+            }
+            if is_negative {
+                twos_complement(&mut word);
+                AbiValue::Int(BigInt::from_be_bytes(&word, Sign::Negative))
+            } else {
+                AbiValue::Int(BigInt::from_be_bytes(&word, Sign::Positive))
+            }
+        }
+        AbiType::FixedBytes(len) => {
+            if *len == 0 || *len > ABI_WORD_BYTE_LENGTH {
+                return None;
+            }
+            let word = decode_word(data, start)?;
+            let (bytes, padding) = word.split_at(*len);
+            if padding.iter().any(|&b| b != 0) {
+                return None;
+            }
+            AbiValue::FixedBytes(bytes.to_vec())
+        }
+        AbiType::Bytes => AbiValue::Bytes(decode_bytes_within(data, start, budget)?),
+        AbiType::String => {
+            AbiValue::String(String::from_utf8(decode_bytes_within(data, start, budget)?).ok()?)
+        }
+        AbiType::FixedArray(t, len) => AbiValue::FixedArray(decode_sequence(
+            iter::repeat_n(t.as_ref(), *len),
+            data,
+            start,
+            budget,
+        )?),
+        AbiType::Array(t) => {
+            consume(budget, ABI_WORD_BYTE_LENGTH)?;
+            let len = decode_usize(data, start)?;
+            // Each element takes at least a word, rejects a forged length before
+            // decoding the elements.
+            if len > data.len() / ABI_WORD_BYTE_LENGTH {
+                return None;
+            }
+            let elements_start = start + ABI_WORD_BYTE_LENGTH;
+            AbiValue::Array(decode_sequence(
+                iter::repeat_n(t.as_ref(), len),
+                data,
+                elements_start,
+                budget,
+            )?)
+        }
+        AbiType::Tuple(types) => AbiValue::Tuple(decode_sequence(types, data, start, budget)?),
+    };
+    Some(value)
+}
+
+/// Subtracts `len` from `budget`, returns `None` if `budget` is less than `len`.
+fn consume(budget: &mut usize, len: usize) -> Option<()> {
+    *budget = budget.checked_sub(len)?;
+    Some(())
+}
+
+/// Returns the byte length of the padding of an `uint<bits>` or `int<bits>` word, or
+/// `None` if `bits` is invalid.
+fn padding_byte_len(bits: usize) -> Option<usize> {
+    if bits == 0 || bits > 256 || !bits.is_multiple_of(8) {
+        return None;
+    }
+    Some(ABI_WORD_BYTE_LENGTH - bits / 8)
+}
+
+/// Returns the word starting at `position` of `data`.
+pub(crate) fn decode_word(data: &[u8], position: usize) -> Option<&[u8; ABI_WORD_BYTE_LENGTH]> {
+    let end = position.checked_add(ABI_WORD_BYTE_LENGTH)?;
+    data.get(position..end)?.try_into().ok()
+}
+
+/// Decodes the word starting at `position` of `data` as an `usize`.
+///
+/// Returns `None` if the value doesn't fit in an `usize`.
+pub(crate) fn decode_usize(data: &[u8], position: usize) -> Option<usize> {
+    const USIZE_BYTE_LENGTH: usize = usize::BITS as usize / 8;
+
+    let word = decode_word(data, position)?;
+    let (padding, value) = word.split_at(ABI_WORD_BYTE_LENGTH - USIZE_BYTE_LENGTH);
+    if padding.iter().any(|&b| b != 0) {
+        return None;
+    }
+    Some(usize::from_be_bytes(value.try_into().unwrap()))
+}
+
+/// Decodes the word starting at `position` of `data` as an `address`.
+///
+/// Returns `None` if the 12 padding bytes aren't all zero.
+pub(crate) fn decode_address(data: &[u8], position: usize) -> Option<Address> {
+    let word = decode_word(data, position)?;
+    let (padding, value) = word.split_at(ABI_WORD_BYTE_LENGTH - ADDRESS_DATA_BYTE_LENGTH);
+    if padding.iter().any(|&b| b != 0) {
+        return None;
+    }
+    Address::from_bytes(value)
+}
+
+/// Decodes a `bytes` in the "tail" of a sequence starting at `base` of `data`, whose
+/// offset (relative to `base`) is stored in the "head" word starting at `position`.
+pub(crate) fn decode_bytes(data: &[u8], base: usize, position: usize) -> Option<Vec<u8>> {
+    let start = base.checked_add(decode_usize(data, position)?)?;
+    decode_bytes_at(data, start)
+}
+
+/// Decodes a `bytes`, whose length word starts at `start` of `data`.
+fn decode_bytes_at(data: &[u8], start: usize) -> Option<Vec<u8>> {
+    let mut budget = data.len();
+    decode_bytes_within(data, start, &mut budget)
+}
+
+/// Decodes a `bytes` like `decode_bytes_at`, reading at most `budget` bytes.
+fn decode_bytes_within(data: &[u8], start: usize, budget: &mut usize) -> Option<Vec<u8>> {
+    consume(budget, ABI_WORD_BYTE_LENGTH)?;
+    let len = decode_usize(data, start)?;
+    consume(budget, len)?;
+    let content_start = start + ABI_WORD_BYTE_LENGTH;
+    let content_end = content_start.checked_add(len)?;
+    Some(data.get(content_start..content_end)?.to_vec())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbiDecodingError {
+    InvalidData,
+    TopicCountMismatch,
+    EventSignatureMismatch,
+}
+
+impl Display for AbiDecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiDecodingError::InvalidData => write!(f, "invalid ABI encoded data"),
+            AbiDecodingError::TopicCountMismatch => write!(f, "topic count mismatch"),
+            AbiDecodingError::EventSignatureMismatch => {
+                write!(f, "event signature mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AbiDecodingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::abi::encode;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_decode_address_and_bytes() {
+        let address: Address = "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+            .try_into()
+            .unwrap();
+        let data = encode(&[
+            AbiValue::Address(address.clone()),
+            AbiValue::Bytes(vec![1, 2, 3]),
+            AbiValue::Bytes(vec![]),
+        ])
+        .unwrap();
+
+        assert_eq!(decode_address(&data, 0), Some(address));
+        assert_eq!(decode_bytes(&data, 0, 32), Some(vec![1, 2, 3]));
+        assert_eq!(decode_bytes(&data, 0, 64), Some(vec![]));
+        // The head word of the first `bytes` is an offset, not an address.
+        assert_eq!(decode_usize(&data, 32), Some(96));
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        let mut word = [0_u8; 32];
+        assert_eq!(decode_word(&word[1..], 0), None);
+        assert_eq!(decode_word(&word, usize::MAX), None);
+        // offset 0 points to the offset word itself, whose value is treated as the length
+        assert_eq!(decode_bytes(&word, 0, 0), Some(vec![]));
+
+        word[0] = 1;
+        assert_eq!(decode_usize(&word, 0), None);
+        assert_eq!(decode_address(&word, 0), None);
+
+        // offset out of bounds
+        let mut word = [0_u8; 32];
+        word[31] = 64;
+        assert_eq!(decode_bytes(&word, 0, 0), None);
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let address: Address = "0x6B175474E89094C44Da98b954EedeAC495271d0F"
+            .try_into()
+            .unwrap();
+        let types = [
+            AbiType::Address,
+            AbiType::Bool,
+            AbiType::Uint(8),
+            AbiType::Int(256),
+            AbiType::Int(16),
+            AbiType::FixedBytes(2),
+            AbiType::Bytes,
+            AbiType::String,
+            AbiType::FixedArray(Box::new(AbiType::Uint(256)), 2),
+            AbiType::FixedArray(Box::new(AbiType::String), 2),
+            AbiType::Array(Box::new(AbiType::Bool)),
+            AbiType::Array(Box::new(AbiType::Bytes)),
+            AbiType::Tuple(vec![AbiType::Address, AbiType::Bytes]),
+            AbiType::Array(Box::new(AbiType::Tuple(vec![
+                AbiType::Uint(32),
+                AbiType::FixedArray(Box::new(AbiType::Bool), 2),
+            ]))),
+        ];
+        let values = || {
+            vec![
+                AbiValue::Address(address.clone()),
+                AbiValue::Bool(true),
+                AbiValue::Uint(BigUint::from(0xff_u8)),
+                AbiValue::Int(BigInt::from(-1)),
+                AbiValue::Int(BigInt::from(-32768)),
+                AbiValue::FixedBytes(vec![0xde, 0xad]),
+                AbiValue::Bytes(vec![1; 33]),
+                AbiValue::String("hello".to_string()),
+                AbiValue::FixedArray(vec![
+                    AbiValue::Uint(BigUint::from(1_u8)),
+                    AbiValue::Uint(BigUint::from(2_u8)),
+                ]),
+                AbiValue::FixedArray(vec![
+                    AbiValue::String("".to_string()),
+                    AbiValue::String("a".to_string()),
+                ]),
+                AbiValue::Array(vec![]),
+                AbiValue::Array(vec![AbiValue::Bytes(vec![]), AbiValue::Bytes(vec![2])]),
+                AbiValue::Tuple(vec![
+                    AbiValue::Address(address.clone()),
+                    AbiValue::Bytes(vec![3]),
+                ]),
+                AbiValue::Array(vec![AbiValue::Tuple(vec![
+                    AbiValue::Uint(BigUint::from(7_u8)),
+                    AbiValue::FixedArray(vec![AbiValue::Bool(false), AbiValue::Bool(true)]),
+                ])]),
+            ]
+        };
+
+        let data = encode(&values()).unwrap();
+        assert_eq!(decode(&types, &data), Ok(values()));
+        for (abi_type, value) in types.iter().zip(values()) {
+            let data = encode(std::slice::from_ref(&value)).unwrap();
+            assert_eq!(
+                decode(std::slice::from_ref(abi_type), &data),
+                Ok(vec![value])
+            );
+        }
+
+        assert_eq!(decode(&[], &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let word = |hex: &str| hex::decode(hex).unwrap();
+        let data = [
+            // truncated
+            (AbiType::Uint(256), word("00")),
+            (
+                AbiType::Bool,
+                word("0000000000000000000000000000000000000000000000000000000000000002"),
+            ),
+            (
+                AbiType::Uint(8),
+                word("0000000000000000000000000000000000000000000000000000000000000100"),
+            ),
+            // positive `int8` 0x80 isn't sign extended
+            (
+                AbiType::Int(8),
+                word("0000000000000000000000000000000000000000000000000000000000000080"),
+            ),
+            (
+                AbiType::Int(8),
+                word("ff0000000000000000000000000000000000000000000000000000000000007f"),
+            ),
+            (
+                AbiType::FixedBytes(1),
+                word("0101000000000000000000000000000000000000000000000000000000000000"),
+            ),
+            (
+                AbiType::Address,
+                word("0100000000000000000000006b175474e89094c44da98b954eedeac495271d0f"),
+            ),
+            // offset out of bounds
+            (
+                AbiType::Bytes,
+                word("0000000000000000000000000000000000000000000000000000000000000040"),
+            ),
+            // forged array length
+            (
+                AbiType::Array(Box::new(AbiType::Bool)),
+                word(concat!(
+                    "0000000000000000000000000000000000000000000000000000000000000020",
+                    "00000000000000000000000000000000000000000000000000000000ffffffff"
+                )),
+            ),
+            // invalid UTF-8
+            (
+                AbiType::String,
+                word(concat!(
+                    "0000000000000000000000000000000000000000000000000000000000000020",
+                    "0000000000000000000000000000000000000000000000000000000000000001",
+                    "ff00000000000000000000000000000000000000000000000000000000000000"
+                )),
+            ),
+            // invalid types
+            (AbiType::Uint(264), word(&"00".repeat(32))),
+            (AbiType::Int(7), word(&"00".repeat(32))),
+            (AbiType::FixedBytes(0), word(&"00".repeat(32))),
+            (AbiType::FixedBytes(33), word(&"00".repeat(32))),
+        ];
+        for (abi_type, data) in data {
+            assert_eq!(
+                decode(&[abi_type], &data),
+                Err(AbiDecodingError::InvalidData)
+            );
+        }
+
+        // The boundaries of `int8`
+        for (hex, n) in [
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80",
+                -128,
+            ),
+            (
+                "000000000000000000000000000000000000000000000000000000000000007f",
+                127,
+            ),
+        ] {
+            assert_eq!(
+                decode(&[AbiType::Int(8)], &word(hex)),
+                Ok(vec![AbiValue::Int(BigInt::from(n))])
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_shared_tails() {
+        // `uint256[]...[]` nested `DEPTH + 1` times: each of the `DEPTH` outer arrays has
+        // 2 elements, whose offsets both point to the same next array, the innermost is
+        // empty. Decoding the shared tails over and over would take 2^DEPTH steps.
+        const DEPTH: usize = 64;
+        let mut abi_type = AbiType::Uint(256);
+        for _ in 0..=DEPTH {
+            abi_type = AbiType::Array(Box::new(abi_type));
+        }
+        let word = |n: u8| {
+            let mut word = [0; ABI_WORD_BYTE_LENGTH];
+            word[31] = n;
+            word
+        };
+        let mut data = word(0x20).to_vec();
+        for _ in 0..DEPTH {
+            data.extend(word(2));
+            data.extend(word(0x40));
+            data.extend(word(0x40));
+        }
+        data.extend(word(0));
+        assert_eq!(
+            decode(&[abi_type], &data),
+            Err(AbiDecodingError::InvalidData)
+        );
+
+        // Two `string`s sharing a tail read more bytes than `data` has.
+        let data = [word(0x40), word(0x40), word(1), word(0)].concat();
+        assert_eq!(
+            decode(&[AbiType::String, AbiType::String], &data),
+            Err(AbiDecodingError::InvalidData)
+        );
+    }
+}
@@ -0,0 +1,676 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the Contract ABI encoding.
+
+use super::abi_type::AbiType;
+use super::abi_value::AbiValue;
+use crate::bigint::BigInt;
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use std::fmt;
+use std::fmt::Display;
+
+/// Byte length of an ABI "word".
+pub(crate) const ABI_WORD_BYTE_LENGTH: usize = 32;
+
+/// Returns the ABI encoding of `values`, the same as `abi.encode(values...)` in Solidity.
+///
+/// The values are encoded as `uint256` and `int256` regardless of their bit lengths, use
+/// `encode_with_types` to check them against narrower types.
+///
+/// # Errors
+///
+/// Will return `AbiEncodingError::ValueOutOfRange` if a `Uint` or `Int` doesn't fit in a
+/// 256-bit word, and `AbiEncodingError::InvalidType` if a `FixedBytes` is empty or longer
+/// than 32 bytes.
+///
+/// # Examples
+///
+/// ```
+/// use lightcryptotools::bigint::BigUint;
+/// use lightcryptotools::blockchain::ethereum::abi::{encode, AbiValue};
+///
+/// let data = encode(&[AbiValue::Uint(BigUint::from(1_u8)), AbiValue::Bool(true)]).unwrap();
+/// assert_eq!(data.len(), 64);
+/// ```
+pub fn encode(values: &[AbiValue]) -> Result<Vec<u8>, AbiEncodingError> {
+    encode_sequence(values)
+}
+
+/// Returns the ABI encoding of `values` of `types`, the inverse of `decode`.
+///
+/// Each value is checked against its type first: a `uint<M>` must be less than `2^M`,
+/// an `int<M>` must be in `[-2^(M-1), 2^(M-1))`, a `bytes<M>` must be `M` bytes long, and
+/// a `T[k]` must have `k` elements.
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - A value isn't of its type, or `values` and `types` differ in length.
+/// - A `Uint` or `Int` doesn't fit in the bit length of its type.
+/// - A type is invalid, e.g., `AbiType::Uint(7)`.
+pub fn encode_with_types(
+    types: &[AbiType],
+    values: &[AbiValue],
+) -> Result<Vec<u8>, AbiEncodingError> {
+    check_values(types, values)?;
+    encode(values)
+}
+
+/// Returns the Keccak-256 hash of the ABI encoding of `value`, the same as
+/// `keccak256(abi.encode(value))` in Solidity.
+///
+/// For a static array or tuple, e.g., `(uint256,bool)`, the hash is also the topic of an
+/// indexed event parameter of the value. The topics of the other types differ, see
+/// [`AbiValue::encode_as_topic`]: a value type, e.g., `bytes32`, is stored as is, and a
+/// dynamic value, e.g., `string`, is hashed without the offsets and lengths.
+///
+/// # Errors
+///
+/// Will return an error if `value` can't be encoded, see `encode`.
+pub fn keccak256_abi_encode(value: &AbiValue) -> Result<[u8; 32], AbiEncodingError> {
+    Ok(Keccak256::new()
+        .digest(encode(std::slice::from_ref(value))?)
+        .try_into()
+        .unwrap())
+}
+
+/// Checks `values` against `types`, see `encode_with_types`.
+fn check_values(types: &[AbiType], values: &[AbiValue]) -> Result<(), AbiEncodingError> {
+    if types.len() != values.len() {
+        return Err(AbiEncodingError::TypeMismatch);
+    }
+    types
+        .iter()
+        .zip(values)
+        .try_for_each(|(abi_type, value)| check_value(abi_type, value))
+}
+
+/// Checks that `value` is of `abi_type`, and fits in its bit or byte length.
+pub(crate) fn check_value(
+    abi_type: &AbiType,
+    value: &AbiValue,
+) -> Result<(), AbiEncodingError> {
+    match (abi_type, value) {
+        (AbiType::Address, AbiValue::Address(_))
+        | (AbiType::Bool, AbiValue::Bool(_))
+        | (AbiType::Bytes, AbiValue::Bytes(_))
+        | (AbiType::String, AbiValue::String(_)) => Ok(()),
+        (AbiType::Uint(bits), AbiValue::Uint(n)) => {
+            check_bit_len(*bits)?;
+            if n.as_bigint().bit_len() > *bits {
+                return Err(AbiEncodingError::ValueOutOfRange);
+            }
+            Ok(())
+        }
+        (AbiType::Int(bits), AbiValue::Int(i)) => check_int_range(i, *bits),
+        (AbiType::FixedBytes(len), AbiValue::FixedBytes(bytes)) => {
+            if !(1..=ABI_WORD_BYTE_LENGTH).contains(len) {
+                return Err(AbiEncodingError::InvalidType);
+            }
+            if bytes.len() != *len {
+                return Err(AbiEncodingError::TypeMismatch);
+            }
+            Ok(())
+        }
+        (AbiType::FixedArray(t, len), AbiValue::FixedArray(values)) => {
+            if values.len() != *len {
+                return Err(AbiEncodingError::TypeMismatch);
+            }
+            values.iter().try_for_each(|value| check_value(t, value))
+        }
+        (AbiType::Array(t), AbiValue::Array(values)) => {
+            values.iter().try_for_each(|value| check_value(t, value))
+        }
+        (AbiType::Tuple(types), AbiValue::Tuple(values)) => check_values(types, values),
+        _ => Err(AbiEncodingError::TypeMismatch),
+    }
+}
+
+fn check_bit_len(bits: usize) -> Result<(), AbiEncodingError> {
+    if bits.is_multiple_of(8) && (8..=256).contains(&bits) {
+        Ok(())
+    } else {
+        Err(AbiEncodingError::InvalidType)
+    }
+}
+
+/// Checks that `i` is in `[-2^(bits-1), 2^(bits-1))`, the range of `int<bits>`.
+fn check_int_range(i: &BigInt, bits: usize) -> Result<(), AbiEncodingError> {
+    check_bit_len(bits)?;
+    let bound = BigInt::one() << (bits - 1);
+    if *i < -&bound || *i >= bound {
+        return Err(AbiEncodingError::ValueOutOfRange);
+    }
+    Ok(())
+}
+
+/// Encodes `values` as the elements of a tuple or an array: the "heads" followed by
+/// the "tails" of the dynamic elements.
+fn encode_sequence(values: &[AbiValue]) -> Result<Vec<u8>, AbiEncodingError> {
+    let heads_byte_len: usize = values.iter().map(head_byte_len).sum();
+    let mut heads = Vec::with_capacity(heads_byte_len);
+    let mut tails = vec![];
+    for value in values {
+        if value.is_dynamic() {
+            let offset = heads_byte_len + tails.len();
+            heads.extend(encode_uint_word(&offset.to_be_bytes()));
+            tails.extend(encode_value(value)?);
+        } else {
+            heads.extend(encode_value(value)?);
+        }
+    }
+
+    heads.extend(tails);
+    Ok(heads)
+}
+
+/// Returns the byte length of `value` in the "heads" of its enclosing sequence.
+fn head_byte_len(value: &AbiValue) -> usize {
+    if value.is_dynamic() {
+        return ABI_WORD_BYTE_LENGTH;
+    }
+    match value {
+        AbiValue::FixedArray(values) | AbiValue::Tuple(values) => {
+            values.iter().map(head_byte_len).sum()
+        }
+        _ => ABI_WORD_BYTE_LENGTH,
+    }
+}
+
+fn encode_value(value: &AbiValue) -> Result<Vec<u8>, AbiEncodingError> {
+    let data = match value {
+        AbiValue::Bytes(bytes) => encode_bytes(bytes),
+        AbiValue::String(s) => encode_bytes(s.as_bytes()),
+        AbiValue::FixedArray(values) | AbiValue::Tuple(values) => encode_sequence(values)?,
+        AbiValue::Array(values) => {
+            let mut data = encode_uint_word(&values.len().to_be_bytes()).to_vec();
+            data.extend(encode_sequence(values)?);
+            data
+        }
+        _ => encode_value_word(value)?.to_vec(),
+    };
+    Ok(data)
+}
+
+/// Returns the word of a "value type" `value`, encoding a `Uint` as `uint256` and an
+/// `Int` as `int256`.
+///
+/// Returns `AbiEncodingError::TypeMismatch` if `value` isn't of a "value type".
+pub(crate) fn encode_value_word(
+    value: &AbiValue,
+) -> Result<[u8; ABI_WORD_BYTE_LENGTH], AbiEncodingError> {
+    let word = match value {
+        AbiValue::Address(address) => encode_uint_word(&address.0),
+        AbiValue::Bool(b) => encode_uint_word(&[*b as u8]),
+        AbiValue::Uint(n) => {
+            if n.as_bigint().bit_len() > ABI_WORD_BYTE_LENGTH * 8 {
+                return Err(AbiEncodingError::ValueOutOfRange);
+            }
+            encode_uint_word(&n.to_be_bytes())
+        }
+        AbiValue::Int(i) => {
+            check_int_range(i, ABI_WORD_BYTE_LENGTH * 8)?;
+            // The magnitude of `int256` fits in a word, the minimum `-2^255` included.
+            let mut word = encode_uint_word(&i.to_be_bytes());
+            if i.is_sign_negative() {
+                twos_complement(&mut word);
+            }
+            word
+        }
+        AbiValue::FixedBytes(bytes) => {
+            if bytes.is_empty() || bytes.len() > ABI_WORD_BYTE_LENGTH {
+                return Err(AbiEncodingError::InvalidType);
+            }
+            let mut word = [0; ABI_WORD_BYTE_LENGTH];
+            word[..bytes.len()].copy_from_slice(bytes);
+            word
+        }
+        _ => return Err(AbiEncodingError::TypeMismatch),
+    };
+    Ok(word)
+}
+
+/// Returns the "in place" encoding of `value`, used to derive the topic of an indexed
+/// event parameter: the elements of arrays and tuples are concatenated without offsets or
+/// lengths, and `bytes` and `string` are padded without their lengths.
+pub(crate) fn encode_in_place(value: &AbiValue) -> Result<Vec<u8>, AbiEncodingError> {
+    let data = match value {
+        AbiValue::Bytes(bytes) => pad_right(bytes),
+        AbiValue::String(s) => pad_right(s.as_bytes()),
+        AbiValue::FixedArray(values) | AbiValue::Array(values) | AbiValue::Tuple(values) => {
+            let mut data = vec![];
+            for value in values {
+                data.extend(encode_in_place(value)?);
+            }
+            data
+        }
+        _ => encode_value_word(value)?.to_vec(),
+    };
+    Ok(data)
+}
+
+/// Returns the big-endian `bytes` left-padded to a word.
+///
+/// # Panics
+///
+/// Panics if `bytes` doesn't fit in a word, after the leading zeros are stripped.
+pub(crate) fn encode_uint_word(bytes: &[u8]) -> [u8; ABI_WORD_BYTE_LENGTH] {
+    let first_non_zero = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let bytes = &bytes[first_non_zero..];
+    assert!(
+        bytes.len() <= ABI_WORD_BYTE_LENGTH,
+        "value too large for uint256"
+    );
+
+    let mut word = [0; ABI_WORD_BYTE_LENGTH];
+    word[(ABI_WORD_BYTE_LENGTH - bytes.len())..].copy_from_slice(bytes);
+    word
+}
+
+/// Returns the ABI encoding of type `bytes`: the length word followed by `bytes`
+/// right-padded to a multiple of the word length.
+pub(crate) fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut data = encode_uint_word(&bytes.len().to_be_bytes()).to_vec();
+    data.extend(pad_right(bytes));
+    data
+}
+
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let padded_len = bytes.len().div_ceil(ABI_WORD_BYTE_LENGTH) * ABI_WORD_BYTE_LENGTH;
+    let mut data = Vec::with_capacity(padded_len);
+    data.extend(bytes);
+    data.resize(padded_len, 0);
+    data
+}
+
+/// Negates the big-endian `word` in two's complement, in place.
+pub(crate) fn twos_complement(word: &mut [u8; ABI_WORD_BYTE_LENGTH]) {
+    let mut carry = true;
+    for b in word.iter_mut().rev() {
+        let (n, overflow) = (!*b).overflowing_add(carry as u8);
+        *b = n;
+        carry = overflow;
+    }
+}
+
+/// An error returned by [`encode`] and [`encode_with_types`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AbiEncodingError {
+    /// A value isn't of its type, e.g., an `AbiValue::Bool` for `uint256`, or a `bytes4`
+    /// of 3 bytes.
+    TypeMismatch,
+    /// A `Uint` or `Int` doesn't fit in the bit length of its type.
+    ValueOutOfRange,
+    /// A type is invalid, e.g., `uint7` or `bytes33`.
+    InvalidType,
+    /// An event signature isn't a name followed by parenthesized types.
+    InvalidEventSignature,
+    /// An event signature has no parameter at the position, or an indexed parameter
+    /// position is past the 3 indexed parameters an event can have.
+    ParameterPositionOutOfRange,
+    /// A parameter type of an event signature can't be parsed, e.g., `uint7`.
+    InvalidParameterType,
+}
+
+impl Display for AbiEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiEncodingError::TypeMismatch => write!(f, "ABI value mismatches its type"),
+            AbiEncodingError::ValueOutOfRange => {
+                write!(f, "ABI value out of the range of its type")
+            }
+            AbiEncodingError::InvalidType => write!(f, "invalid ABI type"),
+            AbiEncodingError::InvalidEventSignature => write!(f, "invalid event signature"),
+            AbiEncodingError::ParameterPositionOutOfRange => {
+                write!(f, "no event parameter at the position")
+            }
+            AbiEncodingError::InvalidParameterType => {
+                write!(f, "invalid event parameter type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AbiEncodingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::{BigInt, BigUint};
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_encode_int() {
+        let data = [
+            (
+                BigInt::from(0),
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                BigInt::from(-1),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+            (
+                BigInt::from(-256),
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00",
+            ),
+            (
+                BigInt::from(i128::MIN),
+                "ffffffffffffffffffffffffffffffff80000000000000000000000000000000",
+            ),
+            // int256 min
+            (
+                -(BigInt::one() << 255),
+                "8000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            // int256 max
+            (
+                (BigInt::one() << 255) - BigInt::one(),
+                "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+        ];
+        for (i, hex) in data {
+            assert_eq!(
+                hex::encode_lower(&encode(&[AbiValue::Int(i)]).unwrap()),
+                hex
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_err_cases() {
+        let data = || {
+            [
+                (
+                    AbiValue::Uint(BigUint::from_hex("01".repeat(33)).unwrap()),
+                    AbiEncodingError::ValueOutOfRange,
+                ),
+                (
+                    AbiValue::Int(BigInt::one() << 255),
+                    AbiEncodingError::ValueOutOfRange,
+                ),
+                (
+                    AbiValue::Int(-(BigInt::one() << 255) - BigInt::one()),
+                    AbiEncodingError::ValueOutOfRange,
+                ),
+                (AbiValue::FixedBytes(vec![]), AbiEncodingError::InvalidType),
+                (
+                    AbiValue::FixedBytes(vec![0; 33]),
+                    AbiEncodingError::InvalidType,
+                ),
+            ]
+        };
+        for (value, err) in data() {
+            assert_eq!(encode(&[value]), Err(err));
+        }
+        // nested
+        for (value, err) in data() {
+            assert_eq!(encode(&[AbiValue::Array(vec![value])]), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_encode_with_types() {
+        let uint = |n: u16| AbiValue::Uint(BigUint::from(n));
+        let int = |i: i16| AbiValue::Int(BigInt::from(i));
+
+        let types = [AbiType::Uint(8), AbiType::Int(8), AbiType::FixedBytes(4)];
+        let values = [uint(255), int(-128), AbiValue::FixedBytes(vec![1, 2, 3, 4])];
+        assert_eq!(
+            encode_with_types(&types, &values).unwrap(),
+            encode(&values).unwrap()
+        );
+
+        let data = [
+            (
+                AbiType::Uint(8),
+                uint(256),
+                AbiEncodingError::ValueOutOfRange,
+            ),
+            (AbiType::Int(8), int(128), AbiEncodingError::ValueOutOfRange),
+            (
+                AbiType::Int(8),
+                int(-129),
+                AbiEncodingError::ValueOutOfRange,
+            ),
+            (
+                AbiType::Int(8),
+                int(i16::MIN),
+                AbiEncodingError::ValueOutOfRange,
+            ),
+            (AbiType::Uint(7), uint(1), AbiEncodingError::InvalidType),
+            (AbiType::Int(264), int(1), AbiEncodingError::InvalidType),
+            (
+                AbiType::FixedBytes(33),
+                AbiValue::FixedBytes(vec![0; 33]),
+                AbiEncodingError::InvalidType,
+            ),
+            (
+                AbiType::FixedBytes(4),
+                AbiValue::FixedBytes(vec![1, 2, 3]),
+                AbiEncodingError::TypeMismatch,
+            ),
+            (AbiType::Uint(256), int(1), AbiEncodingError::TypeMismatch),
+            (
+                AbiType::Bool,
+                AbiValue::Bytes(vec![]),
+                AbiEncodingError::TypeMismatch,
+            ),
+            (
+                AbiType::FixedArray(Box::new(AbiType::Bool), 2),
+                AbiValue::FixedArray(vec![AbiValue::Bool(true)]),
+                AbiEncodingError::TypeMismatch,
+            ),
+            (
+                AbiType::Array(Box::new(AbiType::Uint(8))),
+                AbiValue::Array(vec![uint(1), uint(300)]),
+                AbiEncodingError::ValueOutOfRange,
+            ),
+            (
+                AbiType::Tuple(vec![AbiType::Bool, AbiType::Bool]),
+                AbiValue::Tuple(vec![AbiValue::Bool(true)]),
+                AbiEncodingError::TypeMismatch,
+            ),
+        ];
+        for (abi_type, value, err) in data {
+            assert_eq!(encode_with_types(&[abi_type], &[value]), Err(err));
+        }
+
+        assert_eq!(
+            encode_with_types(&[AbiType::Bool], &[]),
+            Err(AbiEncodingError::TypeMismatch)
+        );
+    }
+
+    #[test]
+    fn test_encode_bytes() {
+        let data = [
+            (
+                "",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "01",
+                "0000000000000000000000000000000000000000000000000000000000000001\
+                 0100000000000000000000000000000000000000000000000000000000000000",
+            ),
+        ];
+        for (bytes_hex, encoded_hex) in data {
+            let bytes = hex::decode(bytes_hex).unwrap();
+            assert_eq!(hex::encode_lower(&encode_bytes(&bytes)), encoded_hex);
+        }
+    }
+
+    #[test]
+    fn test_encode_spec_examples() {
+        // Examples from the Solidity ABI specification.
+        //
+        // sam(bytes,bool,uint256[]) with ("dave", true, [1, 2, 3])
+        let values = [
+            AbiValue::Bytes(b"dave".to_vec()),
+            AbiValue::Bool(true),
+            AbiValue::Array(vec![
+                AbiValue::Uint(BigUint::from(1_u8)),
+                AbiValue::Uint(BigUint::from(2_u8)),
+                AbiValue::Uint(BigUint::from(3_u8)),
+            ]),
+        ];
+        assert_eq!(
+            hex::encode_lower(&encode(&values).unwrap()),
+            "0000000000000000000000000000000000000000000000000000000000000060\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             0000000000000000000000000000000000000000000000000000000000000004\
+             6461766500000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000003"
+        );
+
+        // f(uint256,uint32[],bytes10,bytes)
+        // with (0x123, [0x456, 0x789], "1234567890", "Hello, world!")
+        let values = [
+            AbiValue::Uint(BigUint::from(0x123_u16)),
+            AbiValue::Array(vec![
+                AbiValue::Uint(BigUint::from(0x456_u16)),
+                AbiValue::Uint(BigUint::from(0x789_u16)),
+            ]),
+            AbiValue::FixedBytes(b"1234567890".to_vec()),
+            AbiValue::Bytes(b"Hello, world!".to_vec()),
+        ];
+        assert_eq!(
+            hex::encode_lower(&encode(&values).unwrap()),
+            "0000000000000000000000000000000000000000000000000000000000000123\
+             0000000000000000000000000000000000000000000000000000000000000080\
+             3132333435363738393000000000000000000000000000000000000000000000\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000456\
+             0000000000000000000000000000000000000000000000000000000000000789\
+             000000000000000000000000000000000000000000000000000000000000000d\
+             48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+        );
+
+        // g(uint256[][],string[])
+        // with ([[1, 2], [3]], ["one", "two", "three"])
+        let values = [
+            AbiValue::Array(vec![
+                AbiValue::Array(vec![
+                    AbiValue::Uint(BigUint::from(1_u8)),
+                    AbiValue::Uint(BigUint::from(2_u8)),
+                ]),
+                AbiValue::Array(vec![AbiValue::Uint(BigUint::from(3_u8))]),
+            ]),
+            AbiValue::Array(vec![
+                AbiValue::String("one".to_string()),
+                AbiValue::String("two".to_string()),
+                AbiValue::String("three".to_string()),
+            ]),
+        ];
+        assert_eq!(
+            hex::encode_lower(&encode(&values).unwrap()),
+            "0000000000000000000000000000000000000000000000000000000000000040\
+             0000000000000000000000000000000000000000000000000000000000000140\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000040\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000060\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             6f6e650000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             74776f0000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000005\
+             7468726565000000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_keccak256_abi_encode() {
+        // Digests of `keccak256(abi.encode(value))` in Solidity, cross-checked with
+        // alloy-dyn-abi.
+        let uint = |n: u8| AbiValue::Uint(BigUint::from(n));
+        let word =
+            hex::decode("deadbeef00000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+        let data = [
+            (
+                uint(1),
+                "b10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6",
+            ),
+            (
+                AbiValue::FixedBytes(word.clone()),
+                "10d1d7cbb06a29b1ee7e1f7f37e7ea0b1f460d29077feacf2d1f5886e2ba233d",
+            ),
+            // type(int256).min
+            (
+                AbiValue::Int(-(BigInt::one() << 255)),
+                "de3995408d4211c18871603faad2abbdd832ff9dfb97528065798ee20dc635bf",
+            ),
+            (
+                AbiValue::Tuple(vec![uint(1), AbiValue::Bool(true)]),
+                "cc69885fda6bcc1a4ace058b4a62bf5e179ea78fd58a1ccd71c22cc9b688792f",
+            ),
+            (
+                AbiValue::String("hello".to_string()),
+                "984002fcc0ca639f96622add24c2edd2fe72c65e71ca3faa243e091e0bc7cdab",
+            ),
+            // struct { uint256[] a; string b; }
+            (
+                AbiValue::Tuple(vec![
+                    AbiValue::Array(vec![uint(1), uint(2)]),
+                    AbiValue::String("hello".to_string()),
+                ]),
+                "4f116f8d792092636e3d0566d669af9e0486c20fc6901015b77e303f126270f5",
+            ),
+        ];
+        for (value, hash_hex) in data {
+            assert_eq!(
+                hex::encode_lower(&keccak256_abi_encode(&value).unwrap()),
+                hash_hex
+            );
+        }
+
+        // The topic of a value type isn't hashed.
+        let bytes32 = AbiValue::FixedBytes(word.clone());
+        assert_eq!(bytes32.encode_as_topic().unwrap().to_vec(), word);
+        // The topic of a static tuple is the hash.
+        let tuple = AbiValue::Tuple(vec![uint(1), AbiValue::Bool(true)]);
+        assert_eq!(keccak256_abi_encode(&tuple), tuple.encode_as_topic());
+        // The topic of a dynamic value is hashed without the offset and the length.
+        let hello = AbiValue::String("hello".to_string());
+        assert_ne!(keccak256_abi_encode(&hello), hello.encode_as_topic());
+    }
+
+    #[test]
+    fn test_encode_static_tuple_in_place() {
+        // (uint256,bool)[2] is static, so it's encoded in place without offsets.
+        let tuple = || {
+            AbiValue::Tuple(vec![
+                AbiValue::Uint(BigUint::from(1_u8)),
+                AbiValue::Bool(false),
+            ])
+        };
+        let values = [AbiValue::FixedArray(vec![tuple(), tuple()])];
+        assert_eq!(
+            hex::encode_lower(&encode(&values).unwrap()),
+            "0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000000"
+        );
+    }
+}
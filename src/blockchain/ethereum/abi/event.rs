@@ -0,0 +1,523 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements decoding of log entries emitted by events.
+//!
+//! See: https://docs.soliditylang.org/en/latest/abi-spec.html#events
+
+use super::abi_type::{parse_abi_type, AbiType};
+use super::abi_value::AbiValue;
+use super::decoding::{decode_value, AbiDecodingError};
+use super::encoding::{check_value, AbiEncodingError};
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use std::collections::HashMap;
+
+/// Describes an event, e.g., `event Transfer(address indexed from, address indexed to,
+/// uint256 value)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventAbi<'a> {
+    pub name: &'a str,
+    pub inputs: Vec<EventInput<'a>>,
+}
+
+/// Describes a parameter of an event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventInput<'a> {
+    pub name: &'a str,
+    pub type_: AbiType,
+    /// If `true`, the parameter is stored in a topic, otherwise in the data of the log
+    /// entry.
+    pub indexed: bool,
+}
+
+impl EventAbi<'_> {
+    /// Returns the signature of the event, e.g., "Transfer(address,address,uint256)".
+    pub fn signature(&self) -> String {
+        let types: Vec<String> = self.inputs.iter().map(|i| i.type_.to_string()).collect();
+        format!("{}({})", self.name, types.join(","))
+    }
+
+    /// Returns the first topic of the log entries emitted by the event:
+    /// the Keccak-256 hash of the signature.
+    pub fn topic(&self) -> [u8; 32] {
+        Keccak256::new()
+            .digest(self.signature())
+            .try_into()
+            .unwrap()
+    }
+}
+
+/// Decodes the parameters of the log entry consisting of `topics` and `data`, emitted by
+/// the event `event_abi`, keyed by the parameter names.
+///
+/// Indexed parameters are decoded from `topics[1..]`, in order. An indexed parameter of
+/// a "value type" is decoded as is. For the other types, e.g., `string`, only the
+/// Keccak-256 hash of the value is stored in the topic, and is returned as a 32-byte
+/// `AbiValue::FixedBytes`.
+///
+/// The non-indexed parameters are decoded from `data`, ABI encoded as a sequence.
+///
+/// Anonymous events, which don't have the signature topic, aren't supported.
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - The count of `topics` isn't 1 plus the count of the indexed parameters
+///   (`AbiDecodingError::TopicCountMismatch`).
+/// - `topics[0]` isn't the Keccak-256 hash of the signature of `event_abi`
+///   (`AbiDecodingError::EventSignatureMismatch`).
+/// - A topic or `data` can't be decoded as the parameter types, or a parameter type is
+///   invalid, e.g., `uint7` (`AbiDecodingError::InvalidData`).
+pub fn decode_log_event(
+    topics: &[[u8; 32]],
+    data: &[u8],
+    event_abi: &EventAbi,
+) -> Result<HashMap<String, AbiValue>, AbiDecodingError> {
+    let (indexed, non_indexed): (Vec<&EventInput>, Vec<&EventInput>) =
+        event_abi.inputs.iter().partition(|input| input.indexed);
+    if topics.len() != indexed.len() + 1 {
+        return Err(AbiDecodingError::TopicCountMismatch);
+    }
+    if topics[0] != event_abi.topic() {
+        return Err(AbiDecodingError::EventSignatureMismatch);
+    }
+
+    let mut values = HashMap::with_capacity(event_abi.inputs.len());
+    for (input, topic) in indexed.into_iter().zip(&topics[1..]) {
+        let value = match input.type_ {
+            AbiType::Address
+            | AbiType::Bool
+            | AbiType::Uint(_)
+            | AbiType::Int(_)
+            | AbiType::FixedBytes(_) => {
+                decode_value(&input.type_, topic, 0).ok_or(AbiDecodingError::InvalidData)?
+            }
+            _ => AbiValue::FixedBytes(topic.to_vec()),
+        };
+        values.insert(input.name.to_string(), value);
+    }
+
+    let types: Vec<AbiType> = non_indexed.iter().map(|i| i.type_.clone()).collect();
+    let decoded = super::decode(&types, data)?;
+    for (input, value) in non_indexed.into_iter().zip(decoded) {
+        values.insert(input.name.to_string(), value);
+    }
+
+    Ok(values)
+}
+
+/// Returns the topic of the log entries emitted by the event with the signature
+/// `event_sig`, e.g., "Transfer(address,address,uint256)", for the indexed parameter at
+/// `indexed_pos` of the signature with the value `value`, e.g., to build an `EventFilter`.
+///
+/// The topic is `value.encode_as_topic()`: the word of a value type, or the Keccak-256
+/// hash of a reference type, e.g., `string` or a tuple.
+///
+/// # Errors
+///
+/// Will return an error if `value` isn't of the parameter type, or doesn't fit in it, e.g.,
+/// 256 for `uint8`, see `encode_with_types`.
+///
+/// Will return `AbiEncodingError::InvalidEventSignature` if `event_sig` isn't a signature,
+/// `AbiEncodingError::ParameterPositionOutOfRange` if it has no parameter at `indexed_pos`,
+/// and `AbiEncodingError::InvalidParameterType` if the parameter type can't be parsed.
+pub fn event_topic_value(
+    event_sig: &str,
+    indexed_pos: usize,
+    value: &AbiValue,
+) -> Result<[u8; 32], AbiEncodingError> {
+    let types = signature_parameter_types(event_sig)?;
+    let type_ = types
+        .get(indexed_pos)
+        .ok_or(AbiEncodingError::ParameterPositionOutOfRange)?;
+    let abi_type = parse_abi_type(type_).map_err(|_| AbiEncodingError::InvalidParameterType)?;
+    check_value(&abi_type, value)?;
+
+    value.encode_as_topic()
+}
+
+/// Returns the parameter types of the signature `sig`, e.g., `["uint256", "(bool,bytes)"]`
+/// for "Name(uint256,(bool,bytes))".
+///
+/// # Errors
+///
+/// Will return `AbiEncodingError::InvalidEventSignature` if `sig` isn't a name followed by
+/// parenthesized types.
+fn signature_parameter_types(sig: &str) -> Result<Vec<&str>, AbiEncodingError> {
+    let parameters = sig
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .ok_or(AbiEncodingError::InvalidEventSignature)?;
+    if parameters.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Splits at the commas outside tuples.
+    let mut types = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in parameters.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                types.push(&parameters[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    types.push(&parameters[start..]);
+    Ok(types)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::{BigInt, BigUint};
+    use crate::blockchain::ethereum::abi::{encode, keccak256_abi_encode};
+    use crate::blockchain::ethereum::types::Address;
+    use crate::crypto::codecs::hex;
+
+    fn topic(hex: &str) -> [u8; 32] {
+        hex::decode_to_array(hex).unwrap()
+    }
+
+    fn address(hex: &str) -> AbiValue {
+        AbiValue::Address(Address::try_from(hex).unwrap())
+    }
+
+    fn input(name: &str, type_: AbiType, indexed: bool) -> EventInput {
+        EventInput {
+            name,
+            type_,
+            indexed,
+        }
+    }
+
+    fn erc20_event(name: &str) -> EventAbi {
+        // event Transfer(address indexed from, address indexed to, uint256 value)
+        // event Approval(address indexed owner, address indexed spender, uint256 value)
+        let (first, second) = match name {
+            "Transfer" => ("from", "to"),
+            _ => ("owner", "spender"),
+        };
+        EventAbi {
+            name,
+            inputs: vec![
+                input(first, AbiType::Address, true),
+                input(second, AbiType::Address, true),
+                input("value", AbiType::Uint(256), false),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_decode_erc20_transfer() {
+        let event_abi = erc20_event("Transfer");
+        assert_eq!(event_abi.signature(), "Transfer(address,address,uint256)");
+
+        let topics = [
+            topic("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+            topic("00000000000000000000000028c6c06298d514db089934071355e5743bf21d60"),
+            topic("000000000000000000000000dac17f958d2ee523a2206206994597c13d831ec7"),
+        ];
+        let data =
+            hex::decode("00000000000000000000000000000000000000000000000000000000ee6b2800")
+                .unwrap();
+        let values = decode_log_event(&topics, &data, &event_abi).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values["from"],
+            address("0x28C6c06298d514Db089934071355E5743bf21d60")
+        );
+        assert_eq!(
+            values["to"],
+            address("0xdAC17F958D2ee523a2206206994597C13D831ec7")
+        );
+        assert_eq!(
+            values["value"],
+            AbiValue::Uint(BigUint::from(4_000_000_000_u32))
+        );
+    }
+
+    #[test]
+    fn test_decode_erc20_approval() {
+        let event_abi = erc20_event("Approval");
+        assert_eq!(
+            hex::encode_lower(&event_abi.topic()),
+            "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"
+        );
+
+        let owner = "0x6B175474E89094C44Da98b954EedeAC495271d0F";
+        let spender = "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D";
+        let topics = [
+            event_abi.topic(),
+            address(owner).encode_as_topic().unwrap(),
+            address(spender).encode_as_topic().unwrap(),
+        ];
+        // unlimited allowance
+        let data = [0xff; 32];
+        let values = decode_log_event(&topics, &data, &event_abi).unwrap();
+        assert_eq!(values["owner"], address(owner));
+        assert_eq!(values["spender"], address(spender));
+        assert_eq!(
+            values["value"],
+            AbiValue::Uint(BigUint::from_hex("ff".repeat(32)).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_decode_erc1155_transfer_single() {
+        // event TransferSingle(address indexed operator, address indexed from,
+        //     address indexed to, uint256 id, uint256 value)
+        let event_abi = EventAbi {
+            name: "TransferSingle",
+            inputs: vec![
+                input("operator", AbiType::Address, true),
+                input("from", AbiType::Address, true),
+                input("to", AbiType::Address, true),
+                input("id", AbiType::Uint(256), false),
+                input("value", AbiType::Uint(256), false),
+            ],
+        };
+        assert_eq!(
+            hex::encode_lower(&event_abi.topic()),
+            "c3d58168c5ae7397731d063d5bbf3d657854427343f4c083240f7aacaa2d0f62"
+        );
+
+        let operator = "0x28C6c06298d514Db089934071355E5743bf21d60";
+        let to = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        // minting: `from` is the zero address
+        let topics = [
+            event_abi.topic(),
+            address(operator).encode_as_topic().unwrap(),
+            [0; 32],
+            address(to).encode_as_topic().unwrap(),
+        ];
+        let data = encode(&[
+            AbiValue::Uint(BigUint::from(42_u8)),
+            AbiValue::Uint(BigUint::from(1000_u16)),
+        ])
+        .unwrap();
+        let values = decode_log_event(&topics, &data, &event_abi).unwrap();
+        assert_eq!(values["operator"], address(operator));
+        assert_eq!(values["from"], AbiValue::Address(Address::null()));
+        assert_eq!(values["to"], address(to));
+        assert_eq!(values["id"], AbiValue::Uint(BigUint::from(42_u8)));
+        assert_eq!(values["value"], AbiValue::Uint(BigUint::from(1000_u16)));
+    }
+
+    #[test]
+    fn test_decode_indexed_dynamic_types() {
+        // event Message(string indexed text, bytes32[2] indexed pair, string note)
+        let event_abi = EventAbi {
+            name: "Message",
+            inputs: vec![
+                input("text", AbiType::String, true),
+                input(
+                    "pair",
+                    AbiType::FixedArray(Box::new(AbiType::FixedBytes(32)), 2),
+                    true,
+                ),
+                input("note", AbiType::String, false),
+            ],
+        };
+        let text_topic = AbiValue::String("hello".to_string())
+            .encode_as_topic()
+            .unwrap();
+        let pair_topic = AbiValue::FixedArray(vec![
+            AbiValue::FixedBytes(vec![1; 32]),
+            AbiValue::FixedBytes(vec![2; 32]),
+        ])
+        .encode_as_topic()
+        .unwrap();
+        let topics = [event_abi.topic(), text_topic, pair_topic];
+        let data = encode(&[AbiValue::String("world".to_string())]).unwrap();
+
+        let values = decode_log_event(&topics, &data, &event_abi).unwrap();
+        assert_eq!(values["text"], AbiValue::FixedBytes(text_topic.to_vec()));
+        assert_eq!(values["pair"], AbiValue::FixedBytes(pair_topic.to_vec()));
+        assert_eq!(values["note"], AbiValue::String("world".to_string()));
+    }
+
+    #[test]
+    fn test_event_topic_value() {
+        let from = address("0x28C6c06298d514Db089934071355E5743bf21d60");
+        assert_eq!(
+            event_topic_value("Transfer(address,address,uint256)", 0, &from).unwrap(),
+            topic("00000000000000000000000028c6c06298d514db089934071355e5743bf21d60")
+        );
+
+        // event Message(string indexed text, (uint256,bool) indexed pair, bytes32 indexed id)
+        let sig = "Message(string,(uint256,bool),bytes32)";
+        let text = AbiValue::String("hello".to_string());
+        assert_eq!(
+            event_topic_value(sig, 0, &text).unwrap(),
+            topic("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8")
+        );
+        let pair = AbiValue::Tuple(vec![
+            AbiValue::Uint(BigUint::from(1_u8)),
+            AbiValue::Bool(true),
+        ]);
+        assert_eq!(
+            event_topic_value(sig, 1, &pair).unwrap(),
+            keccak256_abi_encode(&pair).unwrap()
+        );
+        let id =
+            hex::decode("deadbeef00000000000000000000000000000000000000000000000000000000")
+                .unwrap();
+        assert_eq!(
+            event_topic_value(sig, 2, &AbiValue::FixedBytes(id.clone())).unwrap(),
+            topic("deadbeef00000000000000000000000000000000000000000000000000000000")
+        );
+    }
+
+    #[test]
+    fn test_signature_parameter_types() {
+        let data = [
+            ("Ping()", vec![]),
+            (
+                "Transfer(address,address,uint256)",
+                vec!["address", "address", "uint256"],
+            ),
+            (
+                "Message(string,(uint256,(bool,bytes))[],bytes32[2])",
+                vec!["string", "(uint256,(bool,bytes))[]", "bytes32[2]"],
+            ),
+        ];
+        for (sig, types) in data {
+            assert_eq!(signature_parameter_types(sig).unwrap(), types);
+        }
+    }
+
+    #[test]
+    fn test_event_topic_value_err_cases() {
+        let data = [
+            ("Message(string)", AbiValue::FixedBytes(vec![0; 32])),
+            ("Message(bytes32)", AbiValue::String("".to_string())),
+            ("Message(bytes32)", AbiValue::FixedBytes(vec![0xde, 0xad])),
+            ("Message(uint256[2])", AbiValue::FixedArray(vec![])),
+        ];
+        for (sig, value) in data {
+            assert_eq!(
+                event_topic_value(sig, 0, &value),
+                Err(AbiEncodingError::TypeMismatch)
+            );
+        }
+
+        let data = [
+            ("Message(uint8)", AbiValue::Uint(BigUint::from(256_u16))),
+            ("Message(int8)", AbiValue::Int(BigInt::from(128))),
+            ("Message(int8)", AbiValue::Int(BigInt::from(-129))),
+        ];
+        for (sig, value) in data {
+            assert_eq!(
+                event_topic_value(sig, 0, &value),
+                Err(AbiEncodingError::ValueOutOfRange)
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_topic_value_invalid_signature() {
+        let value = AbiValue::Bool(true);
+        for sig in ["Transfer", "Transfer(bool", "Transferbool)", ""] {
+            assert_eq!(
+                event_topic_value(sig, 0, &value),
+                Err(AbiEncodingError::InvalidEventSignature)
+            );
+        }
+
+        assert_eq!(
+            event_topic_value("Transfer(address,address,uint256)", 3, &value),
+            Err(AbiEncodingError::ParameterPositionOutOfRange)
+        );
+        assert_eq!(
+            event_topic_value("Ping()", 0, &value),
+            Err(AbiEncodingError::ParameterPositionOutOfRange)
+        );
+
+        for sig in ["Message(uint7)", "Message(boolean)", "Message((bool)"] {
+            assert_eq!(
+                event_topic_value(sig, 0, &value),
+                Err(AbiEncodingError::InvalidParameterType)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_log_event_err_cases() {
+        let transfer = erc20_event("Transfer");
+        let from = address("0x28C6c06298d514Db089934071355E5743bf21d60")
+            .encode_as_topic()
+            .unwrap();
+        let data = [0; 32];
+
+        // topic count
+        for topics in [
+            vec![],
+            vec![transfer.topic()],
+            vec![transfer.topic(), from, from, from],
+        ] {
+            assert_eq!(
+                decode_log_event(&topics, &data, &transfer),
+                Err(AbiDecodingError::TopicCountMismatch)
+            );
+        }
+
+        // An ERC-721 `Transfer` has the same signature, but indexes `tokenId` as well.
+        let mut erc721_transfer = erc20_event("Transfer");
+        erc721_transfer.inputs[2].indexed = true;
+        let topics = [transfer.topic(), from, from];
+        assert_eq!(
+            decode_log_event(&topics, &data, &erc721_transfer),
+            Err(AbiDecodingError::TopicCountMismatch)
+        );
+        let topics = [transfer.topic(), from, from, [0; 32]];
+        assert_eq!(
+            decode_log_event(&topics, &[], &erc721_transfer).unwrap()["value"],
+            AbiValue::Uint(BigUint::from(0_u8))
+        );
+
+        let topics = [erc20_event("Approval").topic(), from, from];
+        assert_eq!(
+            decode_log_event(&topics, &data, &transfer),
+            Err(AbiDecodingError::EventSignatureMismatch)
+        );
+
+        // invalid address topic
+        let topics = [transfer.topic(), [0xff; 32], from];
+        assert_eq!(
+            decode_log_event(&topics, &data, &transfer),
+            Err(AbiDecodingError::InvalidData)
+        );
+
+        // truncated data
+        let topics = [transfer.topic(), from, from];
+        assert_eq!(
+            decode_log_event(&topics, &data[1..], &transfer),
+            Err(AbiDecodingError::InvalidData)
+        );
+
+        // invalid parameter type, indexed or not
+        for indexed in [true, false] {
+            let event_abi = EventAbi {
+                name: "Message",
+                inputs: vec![input("value", AbiType::Uint(7), indexed)],
+            };
+            let topics = if indexed {
+                vec![event_abi.topic(), [0; 32]]
+            } else {
+                vec![event_abi.topic()]
+            };
+            assert_eq!(
+                decode_log_event(&topics, &data, &event_abi),
+                Err(AbiDecodingError::InvalidData)
+            );
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::abi_value::AbiValue;
+use super::encoding::AbiEncodingError;
+use crate::crypto::codecs::hex;
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+
+/// Maximum number of topics of a log entry.
+const MAX_TOPIC_COUNT: usize = 4;
+
+/// Represents the topic constraints of a log filter, e.g., the `topics` parameter of
+/// JSON-RPC method `eth_getLogs`.
+///
+/// A `None` topic matches any value.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EventFilter {
+    topics: [Option<[u8; 32]>; MAX_TOPIC_COUNT],
+}
+
+impl EventFilter {
+    /// Creates an `EventFilter` which matches any log entry.
+    pub fn new() -> EventFilter {
+        EventFilter {
+            topics: [None; MAX_TOPIC_COUNT],
+        }
+    }
+
+    /// Sets the first topic to the Keccak-256 hash of the event `signature`,
+    /// e.g., "Transfer(address,address,uint256)".
+    pub fn with_event(mut self, signature: &str) -> EventFilter {
+        self.topics[0] = Some(Keccak256::new().digest(signature).try_into().unwrap());
+        self
+    }
+
+    /// Sets the topic of the indexed event parameter at `position` to `value`.
+    ///
+    /// `position` is the zero-based position of the parameter among the indexed
+    /// parameters of the event, which is stored in topic `position + 1`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - `position` is greater than 2, for an event has at most 3 indexed parameters
+    ///   (`AbiEncodingError::ParameterPositionOutOfRange`).
+    /// - `value` can't be encoded as a topic, see `AbiValue::encode_as_topic`.
+    pub fn with_indexed(
+        mut self,
+        position: usize,
+        value: AbiValue,
+    ) -> Result<EventFilter, AbiEncodingError> {
+        if position >= MAX_TOPIC_COUNT - 1 {
+            return Err(AbiEncodingError::ParameterPositionOutOfRange);
+        }
+        self.topics[position + 1] = Some(value.encode_as_topic()?);
+        Ok(self)
+    }
+
+    /// Returns the topics in the format of the `topics` parameter of `eth_getLogs`:
+    /// "0x" prefixed hex for a constrained topic and `None` (JSON `null`) otherwise.
+    ///
+    /// The trailing `None`s are omitted.
+    pub fn to_eth_rpc_topics(&self) -> Vec<Option<String>> {
+        let len = self
+            .topics
+            .iter()
+            .rposition(|topic| topic.is_some())
+            .map_or(0, |i| i + 1);
+        self.topics[..len]
+            .iter()
+            .map(|topic| topic.map(|topic| format!("0x{}", hex::encode_lower(&topic))))
+            .collect()
+    }
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigUint;
+
+    #[test]
+    fn test_transfer_event_filter() {
+        // Topics as they appear in the ERC-20 `Transfer` log entries on Etherscan:
+        // topic 0 is keccak256("Transfer(address,address,uint256)"),
+        // topic 1 and 2 are the left-padded `from` and `to` addresses.
+        let transfer_topic = Some(
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".to_string(),
+        );
+        let from_topic = Some(
+            "0x00000000000000000000000028c6c06298d514db089934071355e5743bf21d60".to_string(),
+        );
+        let to_topic = Some(
+            "0x000000000000000000000000dac17f958d2ee523a2206206994597c13d831ec7".to_string(),
+        );
+        let from = AbiValue::Address(
+            "0x28C6c06298d514Db089934071355E5743bf21d60"
+                .try_into()
+                .unwrap(),
+        );
+        let to = || {
+            AbiValue::Address(
+                "0xdAC17F958D2ee523a2206206994597C13D831ec7"
+                    .try_into()
+                    .unwrap(),
+            )
+        };
+
+        let filter = EventFilter::new()
+            .with_event("Transfer(address,address,uint256)")
+            .with_indexed(0, from)
+            .unwrap();
+        assert_eq!(
+            filter.to_eth_rpc_topics(),
+            vec![transfer_topic.clone(), from_topic]
+        );
+
+        let filter = EventFilter::new()
+            .with_event("Transfer(address,address,uint256)")
+            .with_indexed(1, to())
+            .unwrap();
+        assert_eq!(
+            filter.to_eth_rpc_topics(),
+            vec![transfer_topic, None, to_topic.clone()]
+        );
+
+        let filter = EventFilter::new().with_indexed(1, to()).unwrap();
+        assert_eq!(filter.to_eth_rpc_topics(), vec![None, None, to_topic]);
+    }
+
+    #[test]
+    fn test_empty_event_filter() {
+        assert!(EventFilter::new().to_eth_rpc_topics().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_indexed_position() {
+        assert!(EventFilter::new()
+            .with_indexed(2, AbiValue::Bool(true))
+            .is_ok());
+        for position in [3, 4, usize::MAX] {
+            assert_eq!(
+                EventFilter::new().with_indexed(position, AbiValue::Bool(true)),
+                Err(AbiEncodingError::ParameterPositionOutOfRange)
+            );
+        }
+    }
+
+    #[test]
+    fn test_indexed_value_out_of_range() {
+        let value = AbiValue::Uint(BigUint::from_hex("01".repeat(33)).unwrap());
+        assert_eq!(
+            EventFilter::new().with_indexed(0, value),
+            Err(AbiEncodingError::ValueOutOfRange)
+        );
+    }
+}
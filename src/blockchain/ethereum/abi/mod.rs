@@ -0,0 +1,27 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements a subset of the Contract ABI (Application Binary Interface).
+//!
+//! See: https://docs.soliditylang.org/en/latest/abi-spec.html
+
+mod abi_type;
+mod abi_value;
+mod decoding;
+mod encoding;
+mod event;
+mod event_filter;
+
+pub use abi_type::{parse_abi_type, AbiType, AbiTypeParseError};
+pub use abi_value::AbiValue;
+pub use decoding::{decode, AbiDecodingError};
+pub(crate) use decoding::{decode_address, decode_bytes};
+pub(crate) use encoding::{
+    check_value, encode_bytes, encode_uint_word, encode_value_word, ABI_WORD_BYTE_LENGTH,
+};
+pub use encoding::{encode, encode_with_types, keccak256_abi_encode, AbiEncodingError};
+pub use event::{decode_log_event, event_topic_value, EventAbi, EventInput};
+pub use event_filter::EventFilter;
@@ -8,10 +8,19 @@
 
 use crate::bigint;
 use crate::bigint::BigInt;
+use crate::blockchain::ethereum::transaction::{
+    PayloadEip155, PayloadEip1559, PayloadEip2930, PayloadLegacy, TransactionBuildingError,
+    TransactionEip155, TransactionEip1559, TransactionEip2930, TransactionLegacy,
+};
 use crate::blockchain::ethereum::types::Address;
-use crate::crypto::ecdsa::{PrivateKey, PublicKey};
+use crate::crypto::codecs::hex;
+use crate::crypto::ecdsa::{PrivateKey, PublicKey, SigningOptions};
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
 use crate::crypto::secp256k1;
+use crate::random::generator::get_os_random_bytes;
+use crate::random::GetOsRandomBytesError;
+use std::fmt;
+use std::fmt::Display;
 
 pub const EOA_PRIVATE_KEY_DATA_BYTE_LENGTH: usize = 32;
 pub type EoaPrivateKeyData = [u8; EOA_PRIVATE_KEY_DATA_BYTE_LENGTH];
@@ -25,15 +34,143 @@ impl EoaPrivateKey<'_> {
         PrivateKey::new(d, secp256k1()).map(EoaPrivateKey)
     }
 
+    /// Generates a private key from the random bytes provided by the operating system.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the operating system fails to provide random bytes.
+    pub fn generate() -> Result<EoaPrivateKey<'static>, GetOsRandomBytesError> {
+        loop {
+            let bytes = get_os_random_bytes(EOA_PRIVATE_KEY_DATA_BYTE_LENGTH as u32)?;
+            let data: EoaPrivateKeyData = bytes.try_into().unwrap();
+            // Retries in the (unlikely) event that `data` is zero or not less than the order.
+            if let Some(private_key) = EoaPrivateKey::new(data) {
+                return Ok(private_key);
+            }
+        }
+    }
+
+    /// Parses a private key from the hexadecimal string `s`, in the forms commonly found
+    /// in the wild: with or without the prefix "0x", and surrounded by ASCII whitespace,
+    /// e.g., a trailing newline of a key file.
+    ///
+    /// After the whitespace and the prefix are removed, exactly 64 hexadecimal digits
+    /// must remain.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The len of the hexadecimal digits isn't 64.
+    /// - `s` contains non-hexadecimal digits, including whitespace between the digits.
+    /// - The key is zero or not less than the order of secp256k1.
+    pub fn from_any_hex(s: &str) -> Result<EoaPrivateKey<'static>, EoaKeyParsingError> {
+        let hex = s.trim_ascii();
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        if hex.len() != EOA_PRIVATE_KEY_DATA_BYTE_LENGTH * 2 {
+            return Err(EoaKeyParsingError::InvalidLength);
+        }
+
+        let data: EoaPrivateKeyData =
+            hex::decode_to_array(hex).map_err(|_| EoaKeyParsingError::InvalidHex)?;
+        EoaPrivateKey::new(data).ok_or(EoaKeyParsingError::OutOfRange)
+    }
+
+    /// Returns the key as 32 big-endian bytes, leading zero padded.
+    pub fn to_bytes(&self) -> EoaPrivateKeyData {
+        let bytes = self.0.data.to_be_bytes();
+        let mut data = [0; EOA_PRIVATE_KEY_DATA_BYTE_LENGTH];
+        data[EOA_PRIVATE_KEY_DATA_BYTE_LENGTH - bytes.len()..].copy_from_slice(&bytes);
+        data
+    }
+
+    /// Returns the key as 64 lowercase hexadecimal digits, leading zero padded,
+    /// without the prefix "0x".
+    pub fn to_padded_hex(&self) -> String {
+        hex::encode_lower(&self.to_bytes())
+    }
+
     pub fn public_key(&self) -> EoaPublicKey {
         EoaPublicKey(self.0.public_key())
     }
+
+    /// Signs a legacy transaction with [`SigningOptions::ethereum`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `TransactionBuildingError::SigningError` if signing fails.
+    pub fn sign_legacy(
+        &self,
+        payload: PayloadLegacy,
+    ) -> Result<TransactionLegacy, TransactionBuildingError> {
+        payload.take_and_sign_with_options(&self.0, &SigningOptions::ethereum())
+    }
+
+    /// Signs an EIP-155 transaction with [`SigningOptions::ethereum`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `TransactionBuildingError::SigningError` if signing fails.
+    pub fn sign_eip155(
+        &self,
+        payload: PayloadEip155,
+    ) -> Result<TransactionEip155, TransactionBuildingError> {
+        payload.take_and_sign_with_options(&self.0, &SigningOptions::ethereum())
+    }
+
+    /// Signs an EIP-1559 transaction with [`SigningOptions::ethereum`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `TransactionBuildingError::SigningError` if signing fails.
+    pub fn sign_eip1559(
+        &self,
+        payload: PayloadEip1559,
+    ) -> Result<TransactionEip1559, TransactionBuildingError> {
+        payload.take_and_sign_with_options(&self.0, &SigningOptions::ethereum())
+    }
+
+    /// Signs an EIP-2930 transaction with [`SigningOptions::ethereum`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `TransactionBuildingError::SigningError` if signing fails.
+    pub fn sign_eip2930(
+        &self,
+        payload: PayloadEip2930,
+    ) -> Result<TransactionEip2930, TransactionBuildingError> {
+        payload.take_and_sign_with_options(&self.0, &SigningOptions::ethereum())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EoaKeyParsingError {
+    InvalidLength,
+    InvalidHex,
+    /// The key is zero or not less than the order of secp256k1.
+    OutOfRange,
 }
 
+impl Display for EoaKeyParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EoaKeyParsingError::InvalidLength => write!(f, "invalid private key length"),
+            EoaKeyParsingError::InvalidHex => write!(f, "invalid private key hex"),
+            EoaKeyParsingError::OutOfRange => write!(f, "private key out of range"),
+        }
+    }
+}
+
+impl std::error::Error for EoaKeyParsingError {}
+
 // Public key of an externally-owned account.
 pub struct EoaPublicKey<'a>(pub PublicKey<'a>);
 
 impl EoaPublicKey<'_> {
+    /// Returns the address of the account, which is the same on every chain.
+    ///
+    /// For the chain-specific checksum of EIP-1191, format the address with
+    /// [`Address::to_checksum_string_with_chain_id`].
     pub fn address(&self) -> Address {
         // Takes the last 20 bytes of the Keccak-256 hash of the public key
         let bytes = self.0.curve_params.point_to_bytes(&self.0.data);
@@ -43,6 +180,9 @@ impl EoaPublicKey<'_> {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::transaction::TransactionBuilder;
+    use crate::blockchain::ethereum::types::Chain;
     use crate::testing_tools::ethereum::private_key_hex_to_address;
 
     #[test]
@@ -77,4 +217,137 @@ mod tests {
         let address = "0x2F015C60E0be116B1f0CD534704Db9c92118FB6A";
         assert_eq!(private_key_hex_to_address(key_hex), address);
     }
+
+    #[test]
+    fn test_from_any_hex() {
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let address = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826";
+        let data = [
+            key_hex.to_owned(),
+            format!("0x{key_hex}"),
+            key_hex.to_uppercase(),
+            format!("0x{}", key_hex.to_uppercase()),
+            format!("{key_hex}\n"),
+            format!("0x{key_hex}\r\n"),
+            format!(" \t{key_hex} \n"),
+        ];
+        for s in data {
+            let private_key = EoaPrivateKey::from_any_hex(&s).unwrap();
+            assert_eq!(private_key.public_key().address().to_string(), address);
+            assert_eq!(private_key.to_padded_hex(), key_hex);
+        }
+    }
+
+    #[test]
+    fn test_from_any_hex_err_cases() {
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let data = [
+            ("", EoaKeyParsingError::InvalidLength),
+            ("0x", EoaKeyParsingError::InvalidLength),
+            (&key_hex[1..], EoaKeyParsingError::InvalidLength),
+            (&format!("{key_hex}00"), EoaKeyParsingError::InvalidLength),
+            (&format!("00{key_hex}"), EoaKeyParsingError::InvalidLength),
+            (&format!("0x0x{key_hex}"), EoaKeyParsingError::InvalidLength),
+            // internal whitespace
+            (
+                &format!("{} {}", &key_hex[..31], &key_hex[32..]),
+                EoaKeyParsingError::InvalidHex,
+            ),
+            (
+                &format!("{}\n{}", &key_hex[..32], &key_hex[33..]),
+                EoaKeyParsingError::InvalidHex,
+            ),
+            (
+                &format!("0x {}", &key_hex[1..]),
+                EoaKeyParsingError::InvalidHex,
+            ),
+            (&key_hex.replace('c', "g"), EoaKeyParsingError::InvalidHex),
+            // zero
+            (&"0".repeat(64), EoaKeyParsingError::OutOfRange),
+            // n
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                EoaKeyParsingError::OutOfRange,
+            ),
+            // n + 1
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364142",
+                EoaKeyParsingError::OutOfRange,
+            ),
+            (&"f".repeat(64), EoaKeyParsingError::OutOfRange),
+        ];
+        for (s, err) in data {
+            assert_eq!(EoaPrivateKey::from_any_hex(s).err(), Some(err));
+        }
+
+        // n - 1
+        assert!(EoaPrivateKey::from_any_hex(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let data = [
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "00000000000000000000000000000000000000000000000000000000000000ff",
+            "000000000000000000000000000000000000000000000000ab00000000000000",
+            "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        ];
+        for key_hex in data {
+            let private_key = EoaPrivateKey::from_any_hex(key_hex).unwrap();
+            let bytes = private_key.to_bytes();
+            assert_eq!(hex::encode_lower(&bytes), key_hex);
+            assert_eq!(private_key.to_padded_hex(), key_hex);
+
+            let imported = EoaPrivateKey::from_any_hex(&private_key.to_padded_hex()).unwrap();
+            assert_eq!(imported.to_bytes(), bytes);
+            assert_eq!(EoaPrivateKey::new(bytes).unwrap().to_bytes(), bytes);
+        }
+    }
+
+    // The test data is from EIP-155
+    #[test]
+    fn test_sign_eip155() {
+        let private_key = EoaPrivateKey::from_any_hex(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let builder = TransactionBuilder::new()
+            .with_chain_id(Chain::EthereumMainnet.id())
+            .with_nonce(9.try_into().unwrap())
+            .with_gas_price("20000000000".try_into().unwrap())
+            .with_gas_limit(21000.into())
+            .with_destination(
+                "0x3535353535353535353535353535353535353535"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount("1000000000000000000".try_into().unwrap());
+        let expected = concat!(
+            "f86c098504a817c800825208943535353535",
+            "353535353535353535353535353535880de0",
+            "b6b3a76400008025a028ef61340bd939bc21",
+            "95fe537567866003e1a15d3c71ff63e15906",
+            "20aa636276a067cbe9d8997f761aecb70330",
+            "4b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        );
+
+        let payload = builder.clone().take_and_build_payload_eip_155().unwrap();
+        let transaction = private_key.sign_eip155(payload).unwrap();
+        assert_eq!(hex::encode_lower(&transaction.encode()), expected);
+
+        let encoded = builder
+            .clone()
+            .take_and_sign_typed(0x0, &private_key)
+            .unwrap();
+        assert_eq!(hex::encode_lower(&encoded), expected);
+
+        assert_eq!(
+            builder.clone().take_and_sign_typed(0x3, &private_key),
+            Err(TransactionBuildingError::UnsupportedTransactionType(0x3))
+        );
+    }
 }
@@ -5,7 +5,10 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 pub(crate) mod eoa;
+pub(crate) mod nonce_manager;
 
 pub use eoa::{
-    EoaPrivateKey, EoaPrivateKeyData, EoaPublicKey, EOA_PRIVATE_KEY_DATA_BYTE_LENGTH,
+    EoaKeyParsingError, EoaPrivateKey, EoaPrivateKeyData, EoaPublicKey,
+    EOA_PRIVATE_KEY_DATA_BYTE_LENGTH,
 };
+pub use nonce_manager::NonceManager;
@@ -0,0 +1,173 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the nonce sequence of an account sending transactions one after another,
+//! e.g., deploying multiple contracts.
+
+use super::eoa::EoaPrivateKey;
+use crate::blockchain::ethereum::types::{Address, EoaNonce, EoaNonceError};
+
+/// Hands out the nonces of an account in sequence, starting from the nonce of the next
+/// transaction, e.g., as returned by `eth_getTransactionCount` with the "pending" tag.
+///
+/// The manager doesn't know of the transactions sent elsewhere, use one manager per
+/// account and don't share the account with other senders.
+#[derive(Clone)]
+pub struct NonceManager {
+    current: EoaNonce,
+    /// `true` after the last nonce, 2^64-2, is handed out.
+    exhausted: bool,
+    address: Option<Address>,
+}
+
+impl NonceManager {
+    pub fn new(initial: EoaNonce) -> Self {
+        NonceManager {
+            current: initial,
+            exhausted: false,
+            address: None,
+        }
+    }
+
+    /// Creates a manager for the account of `key`, starting from `rpc_nonce`, the on-chain
+    /// nonce of the account.
+    ///
+    /// The address of `key` is kept to tell the account of the sequence, see
+    /// [`NonceManager::address`].
+    pub fn from_private_key(key: &EoaPrivateKey, rpc_nonce: EoaNonce) -> Self {
+        NonceManager {
+            current: rpc_nonce,
+            exhausted: false,
+            address: Some(key.public_key().address()),
+        }
+    }
+
+    /// Returns the address of the account, if the manager is created with
+    /// [`NonceManager::from_private_key`].
+    pub fn address(&self) -> Option<&Address> {
+        self.address.as_ref()
+    }
+
+    /// Returns the nonce of the next transaction, and advances to the one after it.
+    ///
+    /// # Errors
+    ///
+    /// Will return `EoaNonceError::Overflow` if the last nonce, 2^64-2, is handed out
+    /// already.
+    // Not `Iterator::next`, the overflow is an error rather than the end of the sequence.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<EoaNonce, EoaNonceError> {
+        if self.exhausted {
+            return Err(EoaNonceError::Overflow);
+        }
+        let current = self.current.clone();
+        match current.checked_increment() {
+            Ok(next) => self.current = next,
+            Err(_) => self.exhausted = true,
+        }
+        Ok(current)
+    }
+
+    /// Returns the nonce of the next transaction, without advancing.
+    ///
+    /// Once the last nonce, 2^64-2, is handed out, keeps returning it while
+    /// [`NonceManager::next`] fails.
+    pub fn peek(&self) -> EoaNonce {
+        self.current.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::account::EoaPrivateKey;
+    use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+    use crate::blockchain::ethereum::transaction::{TransactionBuilder, TransactionEip1559};
+    use crate::blockchain::ethereum::types::Chain;
+    use crate::tools::codable::{decode, DecodingItem};
+
+    #[test]
+    fn test_common() {
+        let mut manager = NonceManager::new(EoaNonce::from_u64(7).unwrap());
+        assert_eq!(manager.peek().value(), 7);
+        assert_eq!(manager.next().unwrap().value(), 7);
+        assert_eq!(manager.next().unwrap().value(), 8);
+        assert_eq!(manager.peek().value(), 9);
+        assert_eq!(manager.peek().value(), 9);
+        assert!(manager.address().is_none());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut manager = NonceManager::new(EoaNonce::from_u64(u64::MAX - 2).unwrap());
+        assert_eq!(manager.next().unwrap().value(), u64::MAX - 2);
+        // The last nonce, 2^64-2
+        assert_eq!(manager.peek().value(), u64::MAX - 1);
+        assert_eq!(manager.next().unwrap().value(), u64::MAX - 1);
+        assert_eq!(manager.peek().value(), u64::MAX - 1);
+        assert_eq!(manager.next().err(), Some(EoaNonceError::Overflow));
+        assert_eq!(manager.next().err(), Some(EoaNonceError::Overflow));
+    }
+
+    #[test]
+    fn test_from_private_key() {
+        let private_key = EoaPrivateKey::from_any_hex(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let mut manager =
+            NonceManager::from_private_key(&private_key, EoaNonce::from_u64(5).unwrap());
+        assert_eq!(manager.next().unwrap().value(), 5);
+        assert_eq!(manager.peek().value(), 6);
+        assert_eq!(manager.address(), Some(&private_key.public_key().address()));
+    }
+
+    #[test]
+    fn test_sequential_eip1559_transactions() {
+        let private_key = EoaPrivateKey::from_any_hex(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let address = private_key.public_key().address();
+        let rpc_nonce = 0x2a;
+        let mut manager = NonceManager::new(EoaNonce::from_u64(rpc_nonce).unwrap());
+
+        for i in 0..10 {
+            let payload = TransactionBuilder::new()
+                .with_chain_id(Chain::EthereumMainnet.id())
+                .with_nonce(manager.next().unwrap())
+                .with_max_priority_fee_per_gas("1000000000".try_into().unwrap())
+                .with_max_fee_per_gas("20000000000".try_into().unwrap())
+                .with_gas_limit(21000.into())
+                .with_destination(
+                    "0x3535353535353535353535353535353535353535"
+                        .try_into()
+                        .unwrap(),
+                )
+                .with_amount("1000000000000000000".try_into().unwrap())
+                .take_and_build_payload_eip_1559()
+                .unwrap();
+            let encoded = private_key.sign_eip1559(payload).unwrap().encode();
+
+            // 0x02 || rlp([chain_id, nonce, ...])
+            assert_eq!(encoded[0], 0x02);
+            let fields = RlpDecodingItem::new_from_data(&encoded[1..])
+                .unwrap()
+                .decode_as_items()
+                .unwrap();
+            assert_eq!(
+                fields[1].decode_as_bytes().unwrap(),
+                [(rpc_nonce + i) as u8]
+            );
+
+            let decoded = decode::<TransactionEip1559, _>(&encoded[1..]).unwrap();
+            assert_eq!(decoded.payload().nonce.value(), rpc_nonce + i);
+            assert_eq!(decoded.recover_sender(), Some(address.clone()));
+            assert_eq!(decoded.encode(), encoded);
+        }
+        assert_eq!(manager.peek().value(), rpc_nonce + 10);
+    }
+}
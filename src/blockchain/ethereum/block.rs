@@ -0,0 +1,227 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements block headers, their hashes and the ommers (uncles) hash.
+//!
+//! See: the section "4.3. The Block" of the Ethereum Yellow Paper.
+
+use crate::bigint::BigUint;
+use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use crate::blockchain::ethereum::types::{Address, Wei};
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::tools::codable::{encode, seal_codable, Encodable, EncodingItem};
+
+pub const BLOCK_HASH_BYTE_LENGTH: usize = 32;
+pub type BlockHash = [u8; BLOCK_HASH_BYTE_LENGTH];
+
+pub const LOGS_BLOOM_BYTE_LENGTH: usize = 256;
+
+/// The ommers hash of a block without ommers, `keccak256(rlp([]))`.
+pub const EMPTY_OMMERS_HASH: BlockHash = [
+    0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4,
+    0x1a, 0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4,
+    0x93, 0x47,
+];
+
+/// The header of a block, with the fields of the London hard fork.
+///
+/// `base_fee_per_gas` is absent in the headers before London.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub parent_hash: BlockHash,
+    pub ommers_hash: BlockHash,
+    pub beneficiary: Address,
+    pub state_root: [u8; 32],
+    pub transactions_root: [u8; 32],
+    pub receipts_root: [u8; 32],
+    pub logs_bloom: [u8; LOGS_BLOOM_BYTE_LENGTH],
+    pub difficulty: BigUint,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub mix_hash: [u8; 32],
+    pub nonce: [u8; 8],
+    pub base_fee_per_gas: Option<Wei>,
+}
+
+seal_codable!(RlpEncodingItem => BlockHeader);
+
+impl BlockHeader {
+    /// Returns the hash of the block, `keccak256(rlp(header))`.
+    pub fn hash(&self) -> BlockHash {
+        Keccak256::new().digest(encode(self)).try_into().unwrap()
+    }
+}
+
+impl Encodable<RlpEncodingItem> for BlockHeader {
+    fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
+        let mut fields_encoding_item = RlpEncodingItem::new();
+        fields_encoding_item.encode_bytes(&self.parent_hash);
+        fields_encoding_item.encode_bytes(&self.ommers_hash);
+        self.beneficiary.encode_to(&mut fields_encoding_item);
+        fields_encoding_item.encode_bytes(&self.state_root);
+        fields_encoding_item.encode_bytes(&self.transactions_root);
+        fields_encoding_item.encode_bytes(&self.receipts_root);
+        fields_encoding_item.encode_bytes(&self.logs_bloom);
+        self.difficulty.encode_to(&mut fields_encoding_item);
+        self.number.encode_to(&mut fields_encoding_item);
+        self.gas_limit.encode_to(&mut fields_encoding_item);
+        self.gas_used.encode_to(&mut fields_encoding_item);
+        self.timestamp.encode_to(&mut fields_encoding_item);
+        fields_encoding_item.encode_bytes(&self.extra_data);
+        fields_encoding_item.encode_bytes(&self.mix_hash);
+        fields_encoding_item.encode_bytes(&self.nonce);
+        if let Some(base_fee_per_gas) = &self.base_fee_per_gas {
+            base_fee_per_gas.encode_to(&mut fields_encoding_item);
+        }
+
+        encoding_item.encode_list_payload(&mut fields_encoding_item);
+    }
+}
+
+/// Returns the ommers hash of a block with the ommers `uncle_headers`,
+/// `keccak256(rlp(uncle_headers))`.
+pub fn compute_ommers_hash(uncle_headers: &[BlockHeader]) -> BlockHash {
+    let mut headers_encoding_item = RlpEncodingItem::new();
+    for header in uncle_headers {
+        header.encode_to(&mut headers_encoding_item);
+    }
+    let mut encoding_item = RlpEncodingItem::new();
+    encoding_item.encode_list_payload(&mut headers_encoding_item);
+
+    Keccak256::new()
+        .digest(encoding_item.take_data())
+        .try_into()
+        .unwrap()
+}
+
+/// Returns true if `hash` is the ommers hash of a block without ommers.
+pub fn ommers_hash_is_empty(hash: &BlockHash) -> bool {
+    hash == &EMPTY_OMMERS_HASH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    fn hash_from_hex(hex: &str) -> [u8; 32] {
+        hex::decode_to_array(hex).unwrap()
+    }
+
+    fn empty_trie_root() -> [u8; 32] {
+        hash_from_hex("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+    }
+
+    // The headers of the mainnet blocks 0 and 1.
+    fn genesis_header() -> BlockHeader {
+        BlockHeader {
+            parent_hash: [0; 32],
+            ommers_hash: EMPTY_OMMERS_HASH,
+            beneficiary: "0x0000000000000000000000000000000000000000"
+                .try_into()
+                .unwrap(),
+            state_root: hash_from_hex(
+                "d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            ),
+            transactions_root: empty_trie_root(),
+            receipts_root: empty_trie_root(),
+            logs_bloom: [0; LOGS_BLOOM_BYTE_LENGTH],
+            difficulty: BigUint::from_hex("0400000000").unwrap(),
+            number: 0,
+            gas_limit: 5000,
+            gas_used: 0,
+            timestamp: 0,
+            extra_data: hex::decode(
+                "11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            )
+            .unwrap(),
+            mix_hash: [0; 32],
+            nonce: [0, 0, 0, 0, 0, 0, 0, 0x42],
+            base_fee_per_gas: None,
+        }
+    }
+
+    fn block_1_header() -> BlockHeader {
+        BlockHeader {
+            parent_hash: genesis_header().hash(),
+            ommers_hash: EMPTY_OMMERS_HASH,
+            beneficiary: "0x05a56e2d52c817161883f50c441c3228cfe54d9f"
+                .try_into()
+                .unwrap(),
+            state_root: hash_from_hex(
+                "d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3",
+            ),
+            transactions_root: empty_trie_root(),
+            receipts_root: empty_trie_root(),
+            logs_bloom: [0; LOGS_BLOOM_BYTE_LENGTH],
+            difficulty: BigUint::from_hex("03ff800000").unwrap(),
+            number: 1,
+            gas_limit: 5000,
+            gas_used: 0,
+            timestamp: 0x55ba4224,
+            extra_data: b"Geth/v1.0.0/linux/go1.4.2".to_vec(),
+            mix_hash: hash_from_hex(
+                "969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59",
+            ),
+            nonce: hex::decode_to_array("539bd4979fef1ec4").unwrap(),
+            base_fee_per_gas: None,
+        }
+    }
+
+    #[test]
+    fn test_block_hash() {
+        assert_eq!(
+            hex::encode_lower(&genesis_header().hash()),
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        );
+        assert_eq!(
+            hex::encode_lower(&block_1_header().hash()),
+            "88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6"
+        );
+    }
+
+    #[test]
+    fn test_empty_ommers_hash() {
+        let hash = compute_ommers_hash(&[]);
+        assert_eq!(hash, EMPTY_OMMERS_HASH);
+        assert!(ommers_hash_is_empty(&hash));
+        assert_eq!(
+            hex::encode_lower(&hash),
+            "1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+        );
+        assert!(!ommers_hash_is_empty(&[0; 32]));
+    }
+
+    #[test]
+    fn test_ommers_hash() {
+        // The expected hashes are computed outside the crate, with an RLP encoder checked
+        // against the hashes of the mainnet blocks 0 and 1, and OpenSSL's Keccak-256.
+        //
+        // TODO: add a mainnet block with uncles, and assert its "sha3Uncles".
+        let data = [
+            (
+                vec![genesis_header()],
+                "02c985d3d9deabe7ba72c6ad2e71fc8cb46b93a09dec3b51ed55114cbc96de44",
+            ),
+            (
+                vec![block_1_header()],
+                "d289b12b229fb6a39ba8d9255c488064217f31764eba2b6858ef0629c054884a",
+            ),
+            (
+                vec![genesis_header(), block_1_header()],
+                "14d5ba398e28177db4d8d4c8ea972af75184d7239f66b25e9fca96adb91aada7",
+            ),
+        ];
+        for (uncle_headers, hash_hex) in data {
+            let hash = compute_ommers_hash(&uncle_headers);
+            assert_eq!(hex::encode_lower(&hash), hash_hex);
+            assert!(!ommers_hash_is_empty(&hash));
+        }
+    }
+}
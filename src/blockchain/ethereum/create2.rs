@@ -0,0 +1,186 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the contract addresses of `CREATE2` and salt searching for vanity addresses.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-1014
+
+use crate::blockchain::ethereum::types::{Address, ADDRESS_DATA_BYTE_LENGTH};
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+pub const CREATE2_SALT_BYTE_LENGTH: usize = 32;
+pub type Create2Salt = [u8; CREATE2_SALT_BYTE_LENGTH];
+
+/// Returns the address of the contract deployed by `deployer` with `CREATE2`:
+/// `keccak256(0xff ‖ deployer ‖ salt ‖ keccak256(init_code))[12..]`.
+pub fn create2_address(deployer: &Address, salt: &Create2Salt, init_code: &[u8]) -> Address {
+    let init_code_hash = Keccak256::new().digest(init_code);
+    create2_address_with_init_code_hash(deployer, salt, &init_code_hash)
+}
+
+fn create2_address_with_init_code_hash(
+    deployer: &Address,
+    salt: &Create2Salt,
+    init_code_hash: &[u8],
+) -> Address {
+    let mut data = Vec::with_capacity(1 + ADDRESS_DATA_BYTE_LENGTH + 2 * 32);
+    data.push(0xff);
+    data.extend(deployer.0);
+    data.extend(salt);
+    data.extend(init_code_hash);
+
+    let hash = Keccak256::new().digest(data);
+    Address::from_bytes(&hash[hash.len() - ADDRESS_DATA_BYTE_LENGTH..]).unwrap()
+}
+
+/// Returns the salt whose last 8 bytes are `counter` in big-endian, and the rest zero.
+fn salt_from_counter(counter: u64) -> Create2Salt {
+    let mut salt = [0; CREATE2_SALT_BYTE_LENGTH];
+    salt[CREATE2_SALT_BYTE_LENGTH - 8..].copy_from_slice(&counter.to_be_bytes());
+    salt
+}
+
+/// Seeks a salt with which `deployer` deploys `init_code` to an address starting
+/// with `prefix_bytes`.
+///
+/// The counter embedded in the last 8 bytes of the salt is incremented from zero,
+/// until a match is found or `max_attempts` salts have been tried.
+/// Each byte of `prefix_bytes` multiplies the expected attempts by 256.
+///
+/// Returns None if no match is found.
+pub fn find_create2_salt_for_prefix(
+    deployer: &Address,
+    init_code: &[u8],
+    prefix_bytes: &[u8],
+    max_attempts: u64,
+) -> Option<Create2Salt> {
+    let init_code_hash = Keccak256::new().digest(init_code);
+    (0..max_attempts).map(salt_from_counter).find(|salt| {
+        create2_address_with_init_code_hash(deployer, salt, &init_code_hash)
+            .0
+            .starts_with(prefix_bytes)
+    })
+}
+
+/// The same as `find_create2_salt_for_prefix`, except that the counters are searched in
+/// parallel on the rayon thread pool.
+///
+/// If several salts match, any of them may be returned.
+#[cfg(feature = "rayon")]
+pub fn find_create2_salt_for_prefix_parallel(
+    deployer: &Address,
+    init_code: &[u8],
+    prefix_bytes: &[u8],
+    max_attempts: u64,
+) -> Option<Create2Salt> {
+    let init_code_hash = Keccak256::new().digest(init_code);
+    (0..max_attempts)
+        .into_par_iter()
+        .map(salt_from_counter)
+        .find_any(|salt| {
+            create2_address_with_init_code_hash(deployer, salt, &init_code_hash)
+                .0
+                .starts_with(prefix_bytes)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    fn address(hex: &str) -> Address {
+        hex.try_into().unwrap()
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // Examples from EIP-1014
+        // (deployer, salt, init_code, address)
+        let data = [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ];
+        for (deployer, salt, init_code, expected) in data {
+            let salt: Create2Salt = hex::decode_to_array(salt).unwrap();
+            assert_eq!(
+                create2_address(&address(deployer), &salt, &hex::decode(init_code).unwrap()),
+                address(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_create2_salt_for_prefix() {
+        let deployer = address("0x00000000000000000000000000000000deadbeef");
+        let init_code = hex::decode("deadbeef").unwrap();
+
+        // The first match of the prefix 0xbeef is at counter 7466.
+        let salt =
+            find_create2_salt_for_prefix(&deployer, &init_code, &[0xbe, 0xef], 10_000).unwrap();
+        assert_eq!(salt, salt_from_counter(7466));
+        assert_eq!(
+            create2_address(&deployer, &salt, &init_code),
+            address("0xbeef6a7e5bd2d71a5d16a8b5945b3086f35c6b74")
+        );
+
+        assert_eq!(
+            find_create2_salt_for_prefix(&deployer, &init_code, &[0xbe, 0xef], 7466),
+            None
+        );
+        assert_eq!(
+            find_create2_salt_for_prefix(&deployer, &init_code, &[], 1),
+            Some(salt_from_counter(0))
+        );
+        assert_eq!(
+            find_create2_salt_for_prefix(&deployer, &init_code, &[0xbe, 0xef], 0),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_find_create2_salt_for_prefix_parallel() {
+        let deployer = address("0x00000000000000000000000000000000deadbeef");
+        let init_code = hex::decode("deadbeef").unwrap();
+
+        let salt =
+            find_create2_salt_for_prefix_parallel(&deployer, &init_code, &[0xbe, 0xef], 10_000)
+                .unwrap();
+        assert!(create2_address(&deployer, &salt, &init_code)
+            .0
+            .starts_with(&[0xbe, 0xef]));
+
+        assert_eq!(
+            find_create2_salt_for_prefix_parallel(&deployer, &init_code, &[0xbe, 0xef], 7466),
+            None
+        );
+    }
+}
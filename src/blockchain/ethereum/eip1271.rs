@@ -0,0 +1,125 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the helpers of EIP-1271 signatures of contract wallets, validated by
+//! calling `isValidSignature(bytes32 hash, bytes signature)` of the wallet.
+//!
+//! Only the calldata and the response are handled, the on-chain call itself, e.g., with
+//! `eth_call`, is out of scope.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-1271
+
+use crate::blockchain::ethereum::abi::{encode, AbiValue, ABI_WORD_BYTE_LENGTH};
+
+/// The value `isValidSignature` returns for a valid signature, which is also the function
+/// selector: `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`.
+pub const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Returns `EIP1271_MAGIC_VALUE`, `0x1626ba7e`.
+pub fn eip1271_magic_value() -> [u8; 4] {
+    EIP1271_MAGIC_VALUE
+}
+
+/// Returns the calldata of calling `isValidSignature(hash, signature)`.
+pub fn encode_is_valid_signature_call(hash: &[u8; 32], signature: &[u8]) -> Vec<u8> {
+    let arguments = encode(&[
+        AbiValue::FixedBytes(hash.to_vec()),
+        AbiValue::Bytes(signature.to_vec()),
+    ])
+    // A 32-byte `bytes32` and a `bytes` always encode.
+    .unwrap();
+
+    let mut data = Vec::with_capacity(EIP1271_MAGIC_VALUE.len() + arguments.len());
+    data.extend(EIP1271_MAGIC_VALUE);
+    data.extend(arguments);
+    data
+}
+
+/// Returns true if `response`, the data returned by `isValidSignature`, is the magic value
+/// `0x1626ba7e`, ABI encoded as a `bytes4` word.
+///
+/// Like OpenZeppelin's `SignatureChecker`, the whole first word is compared and trailing
+/// data is ignored. Any other response, including an empty one from a non-contract
+/// account, means the signature is invalid.
+pub fn decode_is_valid_signature_response(response: &[u8]) -> bool {
+    match response.get(..ABI_WORD_BYTE_LENGTH) {
+        Some(word) => {
+            let (value, padding) = word.split_at(EIP1271_MAGIC_VALUE.len());
+            value == EIP1271_MAGIC_VALUE && padding.iter().all(|&byte| byte == 0)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::hash::{Keccak256, UnkeyedHash};
+
+    #[test]
+    fn test_magic_value() {
+        let hash = Keccak256::new().digest("isValidSignature(bytes32,bytes)");
+        assert_eq!(hash[..4], eip1271_magic_value());
+        assert_eq!(hex::encode_lower(&eip1271_magic_value()), "1626ba7e");
+    }
+
+    #[test]
+    fn test_encode_is_valid_signature_call() {
+        let hash: [u8; 32] = hex::decode_to_array(
+            "7d2fc56ef03c6e6d5bb7b4fa2dc1d6b3cd58e3e0a24e8f72d1e73cb0c0a0e61c",
+        )
+        .unwrap();
+        let data = encode_is_valid_signature_call(&hash, &[0xab; 65]);
+        assert_eq!(
+            hex::encode_lower(&data),
+            [
+                "1626ba7e",
+                "7d2fc56ef03c6e6d5bb7b4fa2dc1d6b3cd58e3e0a24e8f72d1e73cb0c0a0e61c",
+                // offset of `signature`
+                "0000000000000000000000000000000000000000000000000000000000000040",
+                // length of `signature`
+                "0000000000000000000000000000000000000000000000000000000000000041",
+                &"ab".repeat(65),
+                &"00".repeat(31),
+            ]
+            .concat()
+        );
+
+        // empty signature
+        let data = encode_is_valid_signature_call(&hash, &[]);
+        assert_eq!(data.len(), 4 + 3 * 32);
+    }
+
+    #[test]
+    fn test_decode_is_valid_signature_response() {
+        let mut response = [0; 32];
+        response[..4].copy_from_slice(&EIP1271_MAGIC_VALUE);
+        assert!(decode_is_valid_signature_response(&response));
+        // trailing data
+        assert!(decode_is_valid_signature_response(
+            &[&response[..], &[0xff; 32]].concat()
+        ));
+
+        // too short
+        assert!(!decode_is_valid_signature_response(&[]));
+        assert!(!decode_is_valid_signature_response(&EIP1271_MAGIC_VALUE));
+        assert!(!decode_is_valid_signature_response(&response[..31]));
+        // a different value
+        assert!(!decode_is_valid_signature_response(&[0; 32]));
+        let mut invalid = response;
+        invalid[0] = 0xff;
+        assert!(!decode_is_valid_signature_response(&invalid));
+        // right-aligned, the way a `uint32` is encoded
+        let mut invalid = [0; 32];
+        invalid[28..].copy_from_slice(&EIP1271_MAGIC_VALUE);
+        assert!(!decode_is_valid_signature_response(&invalid));
+        // dirty padding
+        let mut invalid = response;
+        invalid[31] = 1;
+        assert!(!decode_is_valid_signature_response(&invalid));
+    }
+}
@@ -0,0 +1,548 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements EIP-191 signed data of the versions:
+//! - `0x00`: data with an intended validator.
+//! - `0x01`: EIP-712 structured data.
+//! - `0x45`: "personal_sign" messages.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-191
+
+use crate::bigint::{BigInt, Sign};
+use crate::blockchain::ethereum::account::{EoaPrivateKey, EoaPublicKey};
+use crate::blockchain::ethereum::types::{Address, ADDRESS_DATA_BYTE_LENGTH};
+use crate::crypto::ecdsa::{
+    recover_public_keys_from_signature, sign_with_options, Signature, SignatureRecoveryId,
+    SigningError, SigningOptions,
+};
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::crypto::secp256k1;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// Byte length of a signature in the `r ‖ s ‖ v` format.
+pub const PERSONAL_SIGNATURE_BYTE_LENGTH: usize = 65;
+
+const PERSONAL_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// Version byte of data with an intended validator.
+pub const EIP191_VERSION_INTENDED_VALIDATOR: u8 = 0x00;
+/// Version byte of EIP-712 structured data.
+pub const EIP191_VERSION_STRUCTURED_DATA: u8 = 0x01;
+/// Version byte of "personal_sign" messages, the `E` of "Ethereum Signed Message".
+pub const EIP191_VERSION_PERSONAL_SIGN: u8 = 0x45;
+
+/// Returns the hash to sign for the "personal_sign" `message`, which is
+/// `keccak256("\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message)`,
+/// where `len(message)` is the byte length of `message` in decimal.
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let len = message.len().to_string();
+    let mut data =
+        Vec::with_capacity(PERSONAL_MESSAGE_PREFIX.len() + len.len() + message.len());
+    data.extend(PERSONAL_MESSAGE_PREFIX);
+    data.extend(len.as_bytes());
+    data.extend(message);
+    Keccak256::new().digest(data).try_into().unwrap()
+}
+
+/// Returns the hash to sign for the EIP-191 signed data
+/// `keccak256(0x19 ‖ version ‖ version_specific_data ‖ message)`.
+///
+/// `version_specific_data` depends on `version`:
+/// - `0x00`: the 20-byte address of the intended validator.
+/// - `0x01`: the 32-byte EIP-712 domain separator, `message` must be the 32-byte
+///   `hashStruct` of the structured data.
+/// - `0x45`: must be empty, the header "thereum Signed Message:\n" ‖ len(message) is
+///   derived from `message`, see [`personal_message_hash`].
+///
+/// # Errors
+///
+/// Will return an error if `version` isn't one of the above, or if
+/// `version_specific_data` or `message` is invalid for `version`.
+pub fn eip191_hash(
+    version: u8,
+    version_specific_data: &[u8],
+    message: &[u8],
+) -> Result<[u8; 32], Eip191DataError> {
+    let is_valid = match version {
+        EIP191_VERSION_INTENDED_VALIDATOR => {
+            version_specific_data.len() == ADDRESS_DATA_BYTE_LENGTH
+        }
+        EIP191_VERSION_STRUCTURED_DATA => {
+            version_specific_data.len() == 32 && message.len() == 32
+        }
+        EIP191_VERSION_PERSONAL_SIGN => {
+            if !version_specific_data.is_empty() {
+                return Err(Eip191DataError::InvalidVersionSpecificData);
+            }
+            return Ok(personal_message_hash(message));
+        }
+        _ => return Err(Eip191DataError::UnsupportedVersion),
+    };
+    if !is_valid {
+        return Err(Eip191DataError::InvalidVersionSpecificData);
+    }
+
+    let mut data = Vec::with_capacity(2 + version_specific_data.len() + message.len());
+    data.extend([0x19, version]);
+    data.extend(version_specific_data);
+    data.extend(message);
+    Ok(Keccak256::new().digest(data).try_into().unwrap())
+}
+
+/// Signs the EIP-191 signed data, see [`eip191_hash`], returning the signature in the
+/// `r ‖ s ‖ v` format, where `v` is 27/28.
+///
+/// # Errors
+///
+/// Will return an error if the signed data is invalid, or if signing fails.
+pub fn eip191_sign(
+    version: u8,
+    version_specific_data: &[u8],
+    message: &[u8],
+    private_key: &EoaPrivateKey,
+) -> Result<[u8; PERSONAL_SIGNATURE_BYTE_LENGTH], Eip191SigningError> {
+    eip191_sign_with_options(
+        version,
+        version_specific_data,
+        message,
+        private_key,
+        &SigningOptions::default(),
+    )
+}
+
+/// Same as [`eip191_sign`], except that the signing options can be specified.
+pub fn eip191_sign_with_options(
+    version: u8,
+    version_specific_data: &[u8],
+    message: &[u8],
+    private_key: &EoaPrivateKey,
+    options: &SigningOptions,
+) -> Result<[u8; PERSONAL_SIGNATURE_BYTE_LENGTH], Eip191SigningError> {
+    let hash = eip191_hash(version, version_specific_data, message)
+        .map_err(Eip191SigningError::InvalidData)?;
+    let (signature, recovery_id) = sign_with_options(&hash, &private_key.0, options)
+        .map_err(Eip191SigningError::SigningFailed)?;
+
+    let mut bytes = [0; PERSONAL_SIGNATURE_BYTE_LENGTH];
+    for (element, output) in [&signature.r, &signature.s]
+        .into_iter()
+        .zip(bytes.chunks_mut(32))
+    {
+        let element_bytes = element.to_be_bytes();
+        output[32 - element_bytes.len()..].copy_from_slice(&element_bytes);
+    }
+    bytes[64] = 27 + recovery_id as u8;
+    Ok(bytes)
+}
+
+/// Recovers the address of the account which signed the EIP-191 signed data,
+/// see [`eip191_hash`].
+///
+/// `signature` is in the `r ‖ s ‖ v` format, where `v` is either 27/28 or 0/1.
+///
+/// # Errors
+///
+/// Will return an error if the signed data is invalid, if `signature` is malformed, or
+/// if no public key can be recovered.
+pub fn eip191_recover(
+    version: u8,
+    version_specific_data: &[u8],
+    message: &[u8],
+    signature: &[u8; PERSONAL_SIGNATURE_BYTE_LENGTH],
+) -> Result<Address, SignatureValidationError> {
+    let hash = eip191_hash(version, version_specific_data, message)
+        .map_err(SignatureValidationError::InvalidEip191Data)?;
+    recover_address(&hash, signature)
+}
+
+/// Recovers the address of the account which signed the "personal_sign" `message`.
+///
+/// `signature` is in the `r ‖ s ‖ v` format of `eth_sign`, where `v` is either 27/28
+/// or 0/1.
+///
+/// # Errors
+///
+/// Will return an error if `signature` is malformed or no public key can be recovered.
+pub fn personal_sign_recover(
+    message: &[u8],
+    signature: &[u8],
+) -> Result<Address, SignatureValidationError> {
+    let signature = signature
+        .try_into()
+        .map_err(|_| SignatureValidationError::InvalidSignatureLength)?;
+    recover_address(&personal_message_hash(message), signature)
+}
+
+/// Recovers the address of the account which signed `hash`, with `signature` in the
+/// `r ‖ s ‖ v` format.
+fn recover_address(
+    hash: &[u8],
+    signature: &[u8; PERSONAL_SIGNATURE_BYTE_LENGTH],
+) -> Result<Address, SignatureValidationError> {
+    let curve_params = secp256k1();
+    let r = BigInt::from_be_bytes(&signature[..32], Sign::Positive);
+    let s = BigInt::from_be_bytes(&signature[32..64], Sign::Positive);
+    let signature_rs =
+        Signature::new(r, s, curve_params).ok_or(SignatureValidationError::InvalidSignature)?;
+
+    let y_parity = match signature[64] {
+        v @ (0 | 1) => v,
+        v @ (27 | 28) => v - 27,
+        _ => return Err(SignatureValidationError::InvalidRecoveryId),
+    };
+    // "High x" is so unlikely on secp256k1 that `v` doesn't encode it.
+    let recovery_id = SignatureRecoveryId::from_u8(y_parity).unwrap();
+
+    let public_keys =
+        recover_public_keys_from_signature(&signature_rs, hash, Some(recovery_id))
+            .map_err(|_| SignatureValidationError::InvalidSignature)?;
+    public_keys
+        .into_iter()
+        .next()
+        .map(|public_key| EoaPublicKey(public_key).address())
+        .ok_or(SignatureValidationError::InvalidSignature)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureValidationError {
+    InvalidSignatureLength,
+    InvalidRecoveryId,
+    InvalidSignature,
+    InvalidEip6492Wrapper,
+    OnChainValidationRequired,
+    InvalidEip191Data(Eip191DataError),
+}
+
+impl Display for SignatureValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureValidationError::InvalidSignatureLength => {
+                write!(f, "invalid signature length")
+            }
+            SignatureValidationError::InvalidRecoveryId => write!(f, "invalid recovery id"),
+            SignatureValidationError::InvalidSignature => write!(f, "invalid signature"),
+            SignatureValidationError::InvalidEip6492Wrapper => {
+                write!(f, "invalid EIP-6492 wrapper")
+            }
+            SignatureValidationError::OnChainValidationRequired => {
+                write!(f, "on-chain validation required")
+            }
+            SignatureValidationError::InvalidEip191Data(err) => {
+                write!(f, "invalid EIP-191 data: {err}")
+            }
+        }
+    }
+}
+
+impl Error for SignatureValidationError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Eip191DataError {
+    UnsupportedVersion,
+    InvalidVersionSpecificData,
+}
+
+impl Display for Eip191DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eip191DataError::UnsupportedVersion => write!(f, "unsupported version"),
+            Eip191DataError::InvalidVersionSpecificData => {
+                write!(f, "invalid version specific data")
+            }
+        }
+    }
+}
+
+impl Error for Eip191DataError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Eip191SigningError {
+    InvalidData(Eip191DataError),
+    SigningFailed(SigningError),
+}
+
+impl Display for Eip191SigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eip191SigningError::InvalidData(err) => write!(f, "invalid EIP-191 data: {err}"),
+            Eip191SigningError::SigningFailed(err) => write!(f, "signing failed: {err}"),
+        }
+    }
+}
+
+impl Error for Eip191SigningError {}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::account::EoaPrivateKey;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::ecdsa::{sign_with_options, SigningOptions};
+
+    /// Signs `message` deterministically in the `r ‖ s ‖ v` format.
+    pub(crate) fn personal_sign(message: &[u8], private_key_hex: &str) -> Vec<u8> {
+        let key_data = hex::decode_to_array(private_key_hex).unwrap();
+        let private_key = EoaPrivateKey::new(key_data).unwrap();
+        let (signature, recovery_id) = sign_with_options(
+            &personal_message_hash(message),
+            &private_key.0,
+            &SigningOptions {
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut bytes = hex::decode(signature.to_p1363_hex().unwrap()).unwrap();
+        bytes.push(27 + recovery_id as u8);
+        bytes
+    }
+
+    #[test]
+    fn test_personal_message_hash() {
+        let data = [
+            (
+                "",
+                "5f35dce98ba4fba25530a026ed80b2cecdaa31091ba4958b99b52ea1d068adad",
+            ),
+            (
+                "hello",
+                "50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750",
+            ),
+        ];
+        for (message, hash_hex) in data {
+            assert_eq!(
+                hex::encode_lower(&personal_message_hash(message.as_bytes())),
+                hash_hex
+            );
+        }
+    }
+
+    #[test]
+    fn test_personal_sign_recover() {
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let address: Address = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            .try_into()
+            .unwrap();
+
+        let mut signature = personal_sign(b"hello", key_hex);
+        assert_eq!(
+            personal_sign_recover(b"hello", &signature),
+            Ok(address.clone())
+        );
+        assert_ne!(
+            personal_sign_recover(b"hallo", &signature),
+            Ok(address.clone())
+        );
+
+        // `v` in 0/1
+        signature[64] -= 27;
+        assert_eq!(personal_sign_recover(b"hello", &signature), Ok(address));
+    }
+
+    #[test]
+    fn test_personal_sign_recover_malformed() {
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let signature = personal_sign(b"hello", key_hex);
+
+        assert_eq!(
+            personal_sign_recover(b"hello", &signature[..64]),
+            Err(SignatureValidationError::InvalidSignatureLength)
+        );
+
+        let mut invalid_v = signature.clone();
+        invalid_v[64] = 29;
+        assert_eq!(
+            personal_sign_recover(b"hello", &invalid_v),
+            Err(SignatureValidationError::InvalidRecoveryId)
+        );
+
+        let mut zero_r = signature;
+        zero_r[..32].fill(0);
+        assert_eq!(
+            personal_sign_recover(b"hello", &zero_r),
+            Err(SignatureValidationError::InvalidSignature)
+        );
+    }
+
+    fn deterministic_options() -> SigningOptions {
+        SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_eip191_hash() {
+        let validator = hex::decode("d9145CCE52D386f254917e481eB44e9943F39138").unwrap();
+        // EIP-712 "Mail" example: domain separator and `hashStruct(message)`
+        let domain_separator =
+            hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap();
+        let struct_hash =
+            hex::decode("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+                .unwrap();
+
+        // (version, version_specific_data, message, hash)
+        let data = [
+            (
+                EIP191_VERSION_INTENDED_VALIDATOR,
+                validator.as_slice(),
+                b"hello".as_slice(),
+                "1d0b3cc4cd78b75c28bc1dcfc443341ab6ad9fb9e5bcd441f373325bd1f9f460",
+            ),
+            (
+                EIP191_VERSION_STRUCTURED_DATA,
+                &domain_separator,
+                &struct_hash,
+                "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            ),
+            (
+                EIP191_VERSION_PERSONAL_SIGN,
+                &[],
+                b"hello",
+                "50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750",
+            ),
+        ];
+        for (version, version_specific_data, message, hash_hex) in data {
+            assert_eq!(
+                hex::encode_lower(
+                    &eip191_hash(version, version_specific_data, message).unwrap()
+                ),
+                hash_hex
+            );
+        }
+    }
+
+    #[test]
+    fn test_eip191_hash_err_cases() {
+        let data: [(u8, &[u8], &[u8], Eip191DataError); 6] = [
+            (0x02, &[], b"hello", Eip191DataError::UnsupportedVersion),
+            (0x19, &[], b"hello", Eip191DataError::UnsupportedVersion),
+            (
+                EIP191_VERSION_INTENDED_VALIDATOR,
+                &[0; 19],
+                b"hello",
+                Eip191DataError::InvalidVersionSpecificData,
+            ),
+            (
+                EIP191_VERSION_STRUCTURED_DATA,
+                &[0; 32],
+                &[0; 31],
+                Eip191DataError::InvalidVersionSpecificData,
+            ),
+            (
+                EIP191_VERSION_STRUCTURED_DATA,
+                &[0; 33],
+                &[0; 32],
+                Eip191DataError::InvalidVersionSpecificData,
+            ),
+            (
+                EIP191_VERSION_PERSONAL_SIGN,
+                b"thereum Signed Message:\n5",
+                b"hello",
+                Eip191DataError::InvalidVersionSpecificData,
+            ),
+        ];
+        for (version, version_specific_data, message, err) in data {
+            assert_eq!(
+                eip191_hash(version, version_specific_data, message),
+                Err(err.clone())
+            );
+            assert_eq!(
+                eip191_recover(version, version_specific_data, message, &[0; 65]),
+                Err(SignatureValidationError::InvalidEip191Data(err))
+            );
+        }
+    }
+
+    #[test]
+    fn test_eip191_sign_and_recover() {
+        // keccak256("cow")
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let private_key = EoaPrivateKey::from_any_hex(key_hex).unwrap();
+        let address: Address = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            .try_into()
+            .unwrap();
+        let validator = hex::decode("d9145CCE52D386f254917e481eB44e9943F39138").unwrap();
+        let domain_separator =
+            hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap();
+        let struct_hash =
+            hex::decode("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+                .unwrap();
+
+        let data = [
+            (
+                EIP191_VERSION_INTENDED_VALIDATOR,
+                validator.as_slice(),
+                b"hello".as_slice(),
+            ),
+            (
+                EIP191_VERSION_STRUCTURED_DATA,
+                &domain_separator,
+                &struct_hash,
+            ),
+            (EIP191_VERSION_PERSONAL_SIGN, &[], b"hello"),
+        ];
+        for (version, version_specific_data, message) in data {
+            let signature = eip191_sign_with_options(
+                version,
+                version_specific_data,
+                message,
+                &private_key,
+                &deterministic_options(),
+            )
+            .unwrap();
+            assert_eq!(
+                eip191_recover(version, version_specific_data, message, &signature),
+                Ok(address.clone())
+            );
+
+            // A different message doesn't recover the address.
+            let mut other_message = message.to_vec();
+            other_message[0] ^= 1;
+            assert_ne!(
+                eip191_recover(version, version_specific_data, &other_message, &signature),
+                Ok(address.clone())
+            );
+        }
+
+        // The signature of the EIP-712 "Mail" example
+        let signature = eip191_sign_with_options(
+            EIP191_VERSION_STRUCTURED_DATA,
+            &domain_separator,
+            &struct_hash,
+            &private_key,
+            &deterministic_options(),
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode_lower(&signature),
+            concat!(
+                "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+                "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+                "1c"
+            )
+        );
+
+        // "personal_sign" is consistent with `personal_sign_recover`.
+        let signature = eip191_sign_with_options(
+            EIP191_VERSION_PERSONAL_SIGN,
+            &[],
+            b"hello",
+            &private_key,
+            &deterministic_options(),
+        )
+        .unwrap();
+        assert_eq!(signature.to_vec(), personal_sign(b"hello", key_hex));
+        assert_eq!(personal_sign_recover(b"hello", &signature), Ok(address));
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements EIP-2098 compact signatures: the 64 bytes `r ‖ yParityAndS`, where the
+//! y parity is folded into the top bit of `s`, which is always 0 for a "low s" signature.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-2098
+
+use crate::bigint::{BigInt, Sign};
+use crate::blockchain::ethereum::eip191::PERSONAL_SIGNATURE_BYTE_LENGTH;
+use crate::crypto::ecdsa::{Signature, SignatureCanonicality, SignatureRecoveryId};
+use crate::crypto::EllipticCurveParams;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// Byte length of a signature in the EIP-2098 format.
+pub const EIP2098_SIGNATURE_BYTE_LENGTH: usize = 64;
+
+impl<'a> Signature<'a> {
+    /// Returns the EIP-2098 compact representation `r ‖ yParityAndS` of `self`, with the
+    /// y parity of `recovery_id`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The elements of the curve aren't 32 bytes long.
+    /// - `recovery_id` is of a "high x", which can't be represented.
+    /// - `r` or `s` isn't in `(0, n)`, where n is the order of the base point.
+    /// - `s` isn't "low s", the format is only defined for "low s" signatures.
+    pub fn to_eip2098_bytes(
+        &self,
+        recovery_id: SignatureRecoveryId,
+    ) -> Result<[u8; EIP2098_SIGNATURE_BYTE_LENGTH], Eip2098EncodingError> {
+        if self.curve_params.base_point_order.byte_len() != 32 {
+            return Err(Eip2098EncodingError::UnsupportedCurve);
+        }
+        let y_parity = match recovery_id {
+            SignatureRecoveryId::LowXEvenY => 0,
+            SignatureRecoveryId::LowXOddY => 1,
+            _ => return Err(Eip2098EncodingError::UnsupportedRecoveryId),
+        };
+        match self.is_canonical() {
+            SignatureCanonicality::Canonical => {}
+            SignatureCanonicality::HighS => return Err(Eip2098EncodingError::HighS),
+            SignatureCanonicality::OutOfRange => return Err(Eip2098EncodingError::OutOfRange),
+        }
+
+        let mut bytes = [0; EIP2098_SIGNATURE_BYTE_LENGTH];
+        for (element, output) in [&self.r, &self.s].into_iter().zip(bytes.chunks_mut(32)) {
+            let element_bytes = element.to_be_bytes();
+            output[32 - element_bytes.len()..].copy_from_slice(&element_bytes);
+        }
+        bytes[32] |= y_parity << 7;
+        Ok(bytes)
+    }
+
+    /// Restores a signature and its recovery id from the EIP-2098 compact representation
+    /// `r ‖ yParityAndS`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The elements of the curve aren't 32 bytes long.
+    /// - `r` or `s` isn't in `(0, n)`, where n is the order of the base point.
+    /// - `s` isn't "low s", the format is only defined for "low s" signatures.
+    pub fn from_eip2098_bytes(
+        bytes: &[u8; EIP2098_SIGNATURE_BYTE_LENGTH],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<(Signature<'a>, SignatureRecoveryId), Eip2098DecodingError> {
+        if curve_params.base_point_order.byte_len() != 32 {
+            return Err(Eip2098DecodingError::UnsupportedCurve);
+        }
+
+        let (r_bytes, s_bytes) = bytes.split_at(32);
+        let mut s_bytes = s_bytes.to_vec();
+        let recovery_id = if s_bytes[0] & 0x80 == 0 {
+            SignatureRecoveryId::LowXEvenY
+        } else {
+            SignatureRecoveryId::LowXOddY
+        };
+        s_bytes[0] &= 0x7f;
+
+        let r = BigInt::from_be_bytes(r_bytes, Sign::Positive);
+        let s = BigInt::from_be_bytes(&s_bytes, Sign::Positive);
+        let signature =
+            Signature::new(r, s, curve_params).ok_or(Eip2098DecodingError::InvalidSignature)?;
+        if signature.is_canonical() == SignatureCanonicality::HighS {
+            return Err(Eip2098DecodingError::HighS);
+        }
+        Ok((signature, recovery_id))
+    }
+}
+
+/// Converts a signature in the `r ‖ s ‖ v` format, where `v` is either 27/28 or 0/1, to
+/// the EIP-2098 compact representation.
+///
+/// # Errors
+///
+/// Will return an error if `v` is invalid or if `s` isn't "low s".
+pub fn rsv_to_eip2098_bytes(
+    signature: &[u8; PERSONAL_SIGNATURE_BYTE_LENGTH],
+) -> Result<[u8; EIP2098_SIGNATURE_BYTE_LENGTH], Eip2098EncodingError> {
+    let y_parity = match signature[64] {
+        v @ (0 | 1) => v,
+        v @ (27 | 28) => v - 27,
+        _ => return Err(Eip2098EncodingError::UnsupportedRecoveryId),
+    };
+    // The top bit must be free for the y parity.
+    if signature[32] & 0x80 != 0 {
+        return Err(Eip2098EncodingError::HighS);
+    }
+
+    let mut bytes: [u8; EIP2098_SIGNATURE_BYTE_LENGTH] = signature[..64].try_into().unwrap();
+    bytes[32] |= y_parity << 7;
+    Ok(bytes)
+}
+
+/// Converts a signature in the EIP-2098 compact representation to the `r ‖ s ‖ v` format,
+/// where `v` is 27/28.
+pub fn eip2098_to_rsv_bytes(
+    signature: &[u8; EIP2098_SIGNATURE_BYTE_LENGTH],
+) -> [u8; PERSONAL_SIGNATURE_BYTE_LENGTH] {
+    let mut bytes = [0; PERSONAL_SIGNATURE_BYTE_LENGTH];
+    bytes[..64].copy_from_slice(signature);
+    bytes[32] &= 0x7f;
+    bytes[64] = 27 + (signature[32] >> 7);
+    bytes
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Eip2098EncodingError {
+    UnsupportedCurve,
+    UnsupportedRecoveryId,
+    HighS,
+    OutOfRange,
+}
+
+impl Display for Eip2098EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eip2098EncodingError::UnsupportedCurve => write!(f, "unsupported curve"),
+            Eip2098EncodingError::UnsupportedRecoveryId => {
+                write!(f, "unsupported recovery id")
+            }
+            Eip2098EncodingError::HighS => write!(f, "s is not low"),
+            Eip2098EncodingError::OutOfRange => write!(f, "r or s is out of range"),
+        }
+    }
+}
+
+impl Error for Eip2098EncodingError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Eip2098DecodingError {
+    UnsupportedCurve,
+    InvalidSignature,
+    HighS,
+}
+
+impl Display for Eip2098DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Eip2098DecodingError::UnsupportedCurve => write!(f, "unsupported curve"),
+            Eip2098DecodingError::InvalidSignature => write!(f, "invalid signature"),
+            Eip2098DecodingError::HighS => write!(f, "s is not low"),
+        }
+    }
+}
+
+impl Error for Eip2098DecodingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::account::EoaPrivateKey;
+    use crate::blockchain::ethereum::eip191::tests::personal_sign;
+    use crate::blockchain::ethereum::eip191::{personal_message_hash, personal_sign_recover};
+    use crate::crypto::codecs::hex;
+    use crate::crypto::ecdsa::{sign_with_options, verify, SigningOptions};
+    use crate::crypto::{secp256k1, secp384r1};
+
+    #[test]
+    fn test_eip_examples() {
+        // The examples of EIP-2098, messages signed with "personal_sign".
+        let key_hex = "1234567890123456789012345678901234567890123456789012345678901234";
+        // (message, r ‖ s ‖ v, r ‖ yParityAndS)
+        let data = [
+            (
+                "Hello World",
+                concat!(
+                    "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+                    "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+                    "1b"
+                ),
+                concat!(
+                    "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+                    "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064"
+                ),
+            ),
+            (
+                "It's a small(er) world",
+                concat!(
+                    "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76",
+                    "139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+                    "1c"
+                ),
+                concat!(
+                    "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76",
+                    "939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"
+                ),
+            ),
+        ];
+        for (message, rsv_hex, compact_hex) in data {
+            let rsv = personal_sign(message.as_bytes(), key_hex);
+            assert_eq!(hex::encode_lower(&rsv), rsv_hex);
+            let rsv: [u8; PERSONAL_SIGNATURE_BYTE_LENGTH] = rsv.try_into().unwrap();
+
+            let compact = rsv_to_eip2098_bytes(&rsv).unwrap();
+            assert_eq!(hex::encode_lower(&compact), compact_hex);
+            assert_eq!(eip2098_to_rsv_bytes(&compact), rsv);
+
+            let (signature, recovery_id) =
+                Signature::from_eip2098_bytes(&compact, secp256k1()).unwrap();
+            assert_eq!(27 + recovery_id as u8, rsv[64]);
+            assert_eq!(signature.to_eip2098_bytes(recovery_id).unwrap(), compact);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let private_key = EoaPrivateKey::from_any_hex(
+            "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+        )
+        .unwrap();
+        let public_key = private_key.public_key();
+        let options = SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+
+        let mut parities = [false; 2];
+        for i in 0..16 {
+            let message = format!("message {i}");
+            let hash = personal_message_hash(message.as_bytes());
+            let (signature, recovery_id) =
+                sign_with_options(&hash, &private_key.0, &options).unwrap();
+            parities[recovery_id as usize] = true;
+
+            let compact = signature.to_eip2098_bytes(recovery_id).unwrap();
+            let (decoded, decoded_recovery_id) =
+                Signature::from_eip2098_bytes(&compact, secp256k1()).unwrap();
+            assert_eq!((&decoded.r, &decoded.s), (&signature.r, &signature.s));
+            assert_eq!(decoded_recovery_id, recovery_id);
+            assert!(verify(&hash, &decoded, &public_key.0).unwrap());
+
+            // through the `r ‖ s ‖ v` format
+            let rsv = eip2098_to_rsv_bytes(&compact);
+            assert_eq!(
+                personal_sign_recover(message.as_bytes(), &rsv),
+                Ok(public_key.address())
+            );
+            assert_eq!(rsv_to_eip2098_bytes(&rsv), Ok(compact));
+        }
+        // Both parities are covered.
+        assert_eq!(parities, [true, true]);
+    }
+
+    #[test]
+    fn test_encoding_err_cases() {
+        let curve_params = secp256k1();
+        let n = &curve_params.base_point_order;
+        let low_s = Signature::new(BigInt::from(7), BigInt::from(11), curve_params).unwrap();
+        let high_s =
+            Signature::new(BigInt::from(7), n - &BigInt::from(11), curve_params).unwrap();
+
+        assert_eq!(
+            high_s.to_eip2098_bytes(SignatureRecoveryId::LowXEvenY),
+            Err(Eip2098EncodingError::HighS)
+        );
+        assert_eq!(
+            low_s.to_eip2098_bytes(SignatureRecoveryId::HighXOddY),
+            Err(Eip2098EncodingError::UnsupportedRecoveryId)
+        );
+
+        // `r ‖ s ‖ v`
+        let rsv = |signature: &Signature, v: u8| {
+            let mut bytes = eip2098_to_rsv_bytes(&[0; EIP2098_SIGNATURE_BYTE_LENGTH]);
+            let s = signature.s.to_be_bytes();
+            bytes[64 - s.len()..64].copy_from_slice(&s);
+            bytes[31] = 7;
+            bytes[64] = v;
+            bytes
+        };
+        assert_eq!(
+            rsv_to_eip2098_bytes(&rsv(&high_s, 27)),
+            Err(Eip2098EncodingError::HighS)
+        );
+        assert!(rsv_to_eip2098_bytes(&rsv(&low_s, 0)).is_ok());
+        assert_eq!(
+            rsv_to_eip2098_bytes(&rsv(&low_s, 29)),
+            Err(Eip2098EncodingError::UnsupportedRecoveryId)
+        );
+
+        // Created with `Signature::new_unchecked`, e.g., from unbounded RLP fields.
+        for (r, s) in [
+            (BigInt::one() << 300, BigInt::from(11)),
+            (-BigInt::one(), BigInt::from(11)),
+            (BigInt::from(7), BigInt::zero()),
+            (BigInt::from(7), n.clone()),
+        ] {
+            let signature = Signature::new_unchecked(r, s, curve_params);
+            assert_eq!(
+                signature.to_eip2098_bytes(SignatureRecoveryId::LowXEvenY),
+                Err(Eip2098EncodingError::OutOfRange)
+            );
+        }
+
+        // The elements of P-384 are 48 bytes long.
+        let signature = Signature::new(BigInt::from(7), BigInt::from(11), secp384r1()).unwrap();
+        assert_eq!(
+            signature.to_eip2098_bytes(SignatureRecoveryId::LowXEvenY),
+            Err(Eip2098EncodingError::UnsupportedCurve)
+        );
+    }
+
+    #[test]
+    fn test_decoding_err_cases() {
+        let curve_params = secp256k1();
+        let n_hex = curve_params.base_point_order.to_lower_hex();
+        let one_hex = format!("{:0>64}", "01");
+        let zero_hex = "00".repeat(32);
+        // r ‖ yParityAndS
+        let data = [
+            format!("{zero_hex}{one_hex}"),
+            format!("{one_hex}{zero_hex}"),
+            format!("{n_hex}{one_hex}"),
+            // s = 0 with the y parity set
+            format!("{one_hex}80{}", "00".repeat(31)),
+        ];
+        for hex in data {
+            let bytes = hex::decode_to_array(&hex).unwrap();
+            assert_eq!(
+                Signature::from_eip2098_bytes(&bytes, curve_params).err(),
+                Some(Eip2098DecodingError::InvalidSignature),
+                "{hex}"
+            );
+        }
+
+        // s = n / 2 + 1 fits in the 255 bits, but isn't "low s".
+        let half_n = &curve_params.base_point_order >> 1;
+        for s in [
+            half_n.clone() + BigInt::one(),
+            (BigInt::one() << 255) - BigInt::one(),
+        ] {
+            let hex = format!("{one_hex}{:0>64}", s.to_lower_hex());
+            let bytes = hex::decode_to_array(&hex).unwrap();
+            assert_eq!(
+                Signature::from_eip2098_bytes(&bytes, curve_params).err(),
+                Some(Eip2098DecodingError::HighS),
+                "{hex}"
+            );
+        }
+        let hex = format!("{one_hex}{:0>64}", half_n.to_lower_hex());
+        assert!(Signature::from_eip2098_bytes(
+            &hex::decode_to_array(&hex).unwrap(),
+            curve_params
+        )
+        .is_ok());
+    }
+}
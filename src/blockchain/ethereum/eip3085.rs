@@ -0,0 +1,218 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the parameter of the RPC method `wallet_addEthereumChain`, which asks a
+//! wallet, e.g., an EIP-1193 provider, to add a chain.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-3085
+
+use crate::blockchain::ethereum::types::{Chain, ChainId};
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+/// The native currency of a chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NativeCurrency {
+    pub name: String,
+    /// 2-6 characters long.
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// The parameter of `wallet_addEthereumChain`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddEthereumChainParams {
+    pub chain_id: ChainId,
+    pub chain_name: String,
+    pub rpc_urls: Vec<String>,
+    pub native_currency: NativeCurrency,
+    /// Omitted from the serialized object if empty.
+    pub block_explorer_urls: Vec<String>,
+}
+
+impl Chain {
+    /// Returns the `wallet_addEthereumChain` parameter of the chain, or `None` for the
+    /// chains without known public endpoints, e.g., the deprecated testnets.
+    pub fn add_ethereum_chain_params(&self) -> Option<AddEthereumChainParams> {
+        let ether = || NativeCurrency {
+            name: "Ether".to_string(),
+            symbol: "ETH".to_string(),
+            decimals: 18,
+        };
+
+        let (chain_name, rpc_urls, native_currency, block_explorer_urls) = match self {
+            Chain::EthereumMainnet => (
+                "Ethereum Mainnet",
+                vec!["https://cloudflare-eth.com"],
+                ether(),
+                vec!["https://etherscan.io"],
+            ),
+            Chain::Polygon => (
+                "Polygon Mainnet",
+                vec!["https://polygon-rpc.com/"],
+                NativeCurrency {
+                    name: "MATIC".to_string(),
+                    symbol: "MATIC".to_string(),
+                    decimals: 18,
+                },
+                vec!["https://polygonscan.com/"],
+            ),
+            Chain::GethPrivateChains => (
+                "Geth Private Chain",
+                vec!["http://127.0.0.1:8545"],
+                ether(),
+                vec![],
+            ),
+            Chain::ExpanseMainnet
+            | Chain::Ropsten
+            | Chain::Rinkeby
+            | Chain::Goerli
+            | Chain::Kovan => return None,
+        };
+
+        Some(AddEthereumChainParams {
+            chain_id: self.id(),
+            chain_name: chain_name.to_string(),
+            rpc_urls: rpc_urls.into_iter().map(String::from).collect(),
+            native_currency,
+            block_explorer_urls: block_explorer_urls.into_iter().map(String::from).collect(),
+        })
+    }
+}
+
+/// Serializes the parameter as the JSON object EIP-3085 specifies, e.g.,
+///
+/// ```text
+/// {"chainId":"0x89","chainName":"Polygon Mainnet","nativeCurrency":{"name":"MATIC",
+/// "symbol":"MATIC","decimals":18},"rpcUrls":["https://polygon-rpc.com/"],
+/// "blockExplorerUrls":["https://polygonscan.com/"]}
+/// ```
+///
+/// `chainId` is hexadecimal without leading zeros, as EIP-3085 requires.
+#[cfg(feature = "serde")]
+impl Serialize for AddEthereumChainParams {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let field_count = if self.block_explorer_urls.is_empty() {
+            4
+        } else {
+            5
+        };
+        let mut state = serializer.serialize_struct("AddEthereumChainParams", field_count)?;
+        state.serialize_field("chainId", &chain_id_to_hex(&self.chain_id))?;
+        state.serialize_field("chainName", &self.chain_name)?;
+        state.serialize_field("nativeCurrency", &self.native_currency)?;
+        state.serialize_field("rpcUrls", &self.rpc_urls)?;
+        if self.block_explorer_urls.is_empty() {
+            state.skip_field("blockExplorerUrls")?;
+        } else {
+            state.serialize_field("blockExplorerUrls", &self.block_explorer_urls)?;
+        }
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for NativeCurrency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("NativeCurrency", 3)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("symbol", &self.symbol)?;
+        state.serialize_field("decimals", &self.decimals)?;
+        state.end()
+    }
+}
+
+/// Returns "0x" followed by the lowercase hexadecimal of `chain_id` without leading zeros.
+#[cfg(feature = "serde")]
+fn chain_id_to_hex(chain_id: &ChainId) -> String {
+    let hex = chain_id.0.to_lower_hex();
+    match hex.trim_start_matches('0') {
+        "" => "0x0".to_string(),
+        hex => format!("0x{hex}"),
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygon() {
+        let params = Chain::Polygon.add_ethereum_chain_params().unwrap();
+        let json = serde_json::to_string(&params).unwrap();
+
+        // The parameter MetaMask documents for adding Polygon.
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"chainId":"0x89","chainName":"Polygon Mainnet","#,
+                r#""nativeCurrency":{"name":"MATIC","symbol":"MATIC","decimals":18},"#,
+                r#""rpcUrls":["https://polygon-rpc.com/"],"#,
+                r#""blockExplorerUrls":["https://polygonscan.com/"]}"#
+            )
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "chainId": "0x89",
+                "chainName": "Polygon Mainnet",
+                "nativeCurrency": {"name": "MATIC", "symbol": "MATIC", "decimals": 18},
+                "rpcUrls": ["https://polygon-rpc.com/"],
+                "blockExplorerUrls": ["https://polygonscan.com/"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_prebuilt_chains() {
+        // (chain, chain_id)
+        let data = [
+            (Chain::EthereumMainnet, "0x1"),
+            (Chain::Polygon, "0x89"),
+            (Chain::GethPrivateChains, "0x539"),
+        ];
+        for (chain, chain_id) in data {
+            let value =
+                serde_json::to_value(chain.add_ethereum_chain_params().unwrap()).unwrap();
+            assert_eq!(value["chainId"], chain_id);
+        }
+
+        // no block explorer
+        let json = serde_json::to_string(
+            &Chain::GethPrivateChains
+                .add_ethereum_chain_params()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(!json.contains("blockExplorerUrls"));
+
+        assert!(Chain::Ropsten.add_ethereum_chain_params().is_none());
+    }
+
+    #[test]
+    fn test_json_escaping() {
+        let params = AddEthereumChainParams {
+            chain_id: ChainId::from(0x100),
+            chain_name: "A \"quoted\" \\ name\n\u{1}".to_string(),
+            rpc_urls: vec![],
+            native_currency: NativeCurrency {
+                name: "Ünicode".to_string(),
+                symbol: "UNI".to_string(),
+                decimals: 0,
+            },
+            block_explorer_urls: vec![],
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["chainId"], "0x100");
+        assert_eq!(value["chainName"], "A \"quoted\" \\ name\n\u{1}");
+        assert_eq!(value["nativeCurrency"]["name"], "Ünicode");
+        assert_eq!(value["rpcUrls"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements EIP-4337 (account abstraction) helpers.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-4337
+
+mod user_operation;
+
+pub use user_operation::UserOperation;
@@ -0,0 +1,269 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::bigint::BigUint;
+use crate::blockchain::ethereum::abi::{
+    encode_bytes, encode_uint_word, AbiEncodingError, ABI_WORD_BYTE_LENGTH,
+};
+use crate::blockchain::ethereum::types::{Address, ChainId, Wei};
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+
+/// Number of fields of `UserOperation`, which is also the number of words in the "head" of
+/// its ABI encoding.
+const USER_OPERATION_FIELD_COUNT: usize = 11;
+
+/// Represents the `UserOperation` struct of the EntryPoint contract (v0.6).
+///
+/// See: https://eips.ethereum.org/EIPS/eip-4337
+#[derive(Debug, PartialEq, Eq)]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: BigUint,
+    pub init_code: Vec<u8>,
+    pub call_data: Vec<u8>,
+    pub call_gas_limit: BigUint,
+    pub verification_gas_limit: BigUint,
+    pub pre_verification_gas: BigUint,
+    pub max_fee_per_gas: Wei,
+    pub max_priority_fee_per_gas: Wei,
+    pub paymaster_and_data: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
+impl UserOperation {
+    /// Returns the ABI encoding of `self` as a tuple, the way the EntryPoint contract
+    /// receives a `UserOperation`.
+    ///
+    /// The "head" consists of one word per field, with the dynamic fields (`init_code`,
+    /// `call_data`, `paymaster_and_data` and `signature`) replaced by their offsets.
+    /// The "tail" follows with the length-prefixed and padded dynamic fields.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AbiEncodingError::ValueOutOfRange` if a numeric field doesn't fit in
+    /// `uint256`.
+    pub fn abi_encode(&self) -> Result<Vec<u8>, AbiEncodingError> {
+        let mut head = Vec::with_capacity(USER_OPERATION_FIELD_COUNT * ABI_WORD_BYTE_LENGTH);
+        let mut tail = vec![];
+        let head_byte_len = USER_OPERATION_FIELD_COUNT * ABI_WORD_BYTE_LENGTH;
+
+        let mut encode_dynamic = |head: &mut Vec<u8>, bytes: &[u8]| {
+            head.extend(encode_uint_word(
+                &(head_byte_len + tail.len()).to_be_bytes(),
+            ));
+            tail.extend(encode_bytes(bytes));
+        };
+
+        head.extend(encode_address_word(&self.sender));
+        head.extend(encode_uint256(&self.nonce.to_be_bytes())?);
+        encode_dynamic(&mut head, &self.init_code);
+        encode_dynamic(&mut head, &self.call_data);
+        head.extend(encode_uint256(&self.call_gas_limit.to_be_bytes())?);
+        head.extend(encode_uint256(&self.verification_gas_limit.to_be_bytes())?);
+        head.extend(encode_uint256(&self.pre_verification_gas.to_be_bytes())?);
+        head.extend(encode_uint256(&self.max_fee_per_gas.to_be_bytes())?);
+        head.extend(encode_uint256(
+            &self.max_priority_fee_per_gas.to_be_bytes(),
+        )?);
+        encode_dynamic(&mut head, &self.paymaster_and_data);
+        encode_dynamic(&mut head, &self.signature);
+
+        head.extend(tail);
+        Ok(head)
+    }
+
+    /// Returns the "packed" `self`, following `UserOperationLib.pack` of the EntryPoint
+    /// contract: all the fields except `signature` ABI encoded, with the dynamic fields
+    /// replaced by their Keccak-256 hashes.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AbiEncodingError::ValueOutOfRange` if a numeric field doesn't fit in
+    /// `uint256`.
+    pub fn pack(&self) -> Result<Vec<u8>, AbiEncodingError> {
+        let mut data =
+            Vec::with_capacity((USER_OPERATION_FIELD_COUNT - 1) * ABI_WORD_BYTE_LENGTH);
+        data.extend(encode_address_word(&self.sender));
+        data.extend(encode_uint256(&self.nonce.to_be_bytes())?);
+        data.extend(Keccak256::new().digest(&self.init_code));
+        data.extend(Keccak256::new().digest(&self.call_data));
+        data.extend(encode_uint256(&self.call_gas_limit.to_be_bytes())?);
+        data.extend(encode_uint256(&self.verification_gas_limit.to_be_bytes())?);
+        data.extend(encode_uint256(&self.pre_verification_gas.to_be_bytes())?);
+        data.extend(encode_uint256(&self.max_fee_per_gas.to_be_bytes())?);
+        data.extend(encode_uint256(
+            &self.max_priority_fee_per_gas.to_be_bytes(),
+        )?);
+        data.extend(Keccak256::new().digest(&self.paymaster_and_data));
+        Ok(data)
+    }
+
+    /// Returns the hash to sign, which is
+    /// `keccak256(abi.encode(keccak256(pack(self)), entry_point, chain_id))`.
+    ///
+    /// The hash is the same as what `EntryPoint.getUserOpHash` returns.
+    ///
+    /// # Errors
+    ///
+    /// Will return `AbiEncodingError::ValueOutOfRange` if a numeric field doesn't fit in
+    /// `uint256`.
+    pub fn hash(
+        &self,
+        entry_point: &Address,
+        chain_id: ChainId,
+    ) -> Result<[u8; 32], AbiEncodingError> {
+        let mut data = Vec::with_capacity(3 * ABI_WORD_BYTE_LENGTH);
+        data.extend(Keccak256::new().digest(self.pack()?));
+        data.extend(encode_address_word(entry_point));
+        data.extend(encode_uint_word(&chain_id.0.to_be_bytes()));
+        Ok(Keccak256::new().digest(data).try_into().unwrap())
+    }
+}
+
+fn encode_address_word(address: &Address) -> [u8; ABI_WORD_BYTE_LENGTH] {
+    encode_uint_word(&address.0)
+}
+
+/// Encodes the big-endian `bytes` as `uint256`, `encode_uint_word` panics on a value
+/// longer than a word.
+fn encode_uint256(bytes: &[u8]) -> Result<[u8; ABI_WORD_BYTE_LENGTH], AbiEncodingError> {
+    let first_non_zero = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    if bytes.len() - first_non_zero > ABI_WORD_BYTE_LENGTH {
+        return Err(AbiEncodingError::ValueOutOfRange);
+    }
+    Ok(encode_uint_word(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    fn user_operation() -> UserOperation {
+        UserOperation {
+            sender: "0x9c5754De1443984659E1b3a8d1931D83475ba29C"
+                .try_into()
+                .unwrap(),
+            nonce: BigUint::from(1_u64),
+            init_code: vec![],
+            call_data: hex::decode(
+                "b61d27f6\
+                 000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266\
+                 00000000000000000000000000000000000000000000000000038d7ea4c68000\
+                 0000000000000000000000000000000000000000000000000000000000000060\
+                 0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .unwrap(),
+            call_gas_limit: BigUint::from(33100_u64),
+            verification_gas_limit: BigUint::from(60624_u64),
+            pre_verification_gas: BigUint::from(44056_u64),
+            max_fee_per_gas: Wei::from_decimal("1695000030").unwrap(),
+            max_priority_fee_per_gas: Wei::from_decimal("1695000000").unwrap(),
+            paymaster_and_data: vec![],
+            signature: hex::decode(
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+                 bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb1c",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_hash() {
+        // TODO: replace with a userOpHash published for EntryPoint v0.6, the hashes below
+        // are computed with the formula of `getUserOpHash`, not taken from a chain.
+        let entry_point = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789"
+            .try_into()
+            .unwrap();
+        let data = [
+            (
+                1,
+                "b7594e055c95344e9cc21a4337f66840c09ad5773300c4bf97fbc96ad1039a8b",
+            ),
+            (
+                11155111,
+                "6a5af26e347d8e2e92e2386ccb2b677448cd6bd5fc915ab206efd75f0d9db865",
+            ),
+        ];
+        for (chain_id, hash_hex) in data {
+            let hash = user_operation()
+                .hash(&entry_point, ChainId::from(chain_id))
+                .unwrap();
+            assert_eq!(hex::encode_lower(&hash), hash_hex);
+        }
+    }
+
+    #[test]
+    fn test_pack() {
+        assert_eq!(
+            hex::encode_lower(&user_operation().pack().unwrap()),
+            "0000000000000000000000009c5754de1443984659e1b3a8d1931d83475ba29c\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470\
+             fbedf21c6ebded01e4e5124a255cca7d7306ea32035b909e01329b2d73717e21\
+             000000000000000000000000000000000000000000000000000000000000814c\
+             000000000000000000000000000000000000000000000000000000000000ecd0\
+             000000000000000000000000000000000000000000000000000000000000ac18\
+             000000000000000000000000000000000000000000000000000000006507a5de\
+             000000000000000000000000000000000000000000000000000000006507a5c0\
+             c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn test_abi_encode() {
+        assert_eq!(
+            hex::encode_lower(&user_operation().abi_encode().unwrap()),
+            "0000000000000000000000009c5754de1443984659e1b3a8d1931d83475ba29c\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000160\
+             0000000000000000000000000000000000000000000000000000000000000180\
+             000000000000000000000000000000000000000000000000000000000000814c\
+             000000000000000000000000000000000000000000000000000000000000ecd0\
+             000000000000000000000000000000000000000000000000000000000000ac18\
+             000000000000000000000000000000000000000000000000000000006507a5de\
+             000000000000000000000000000000000000000000000000000000006507a5c0\
+             0000000000000000000000000000000000000000000000000000000000000240\
+             0000000000000000000000000000000000000000000000000000000000000260\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000084\
+             b61d27f6000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cf\
+             ffb9226600000000000000000000000000000000000000000000000000038d7e\
+             a4c6800000000000000000000000000000000000000000000000000000000000\
+             0000006000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000041\
+             aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\
+             bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb\
+             1c00000000000000000000000000000000000000000000000000000000000000"
+        );
+    }
+    #[test]
+    fn test_value_out_of_range() {
+        let mut user_operation = user_operation();
+        user_operation.call_gas_limit =
+            BigUint::from_hex(format!("01{}", "00".repeat(32))).unwrap();
+        assert_eq!(
+            user_operation.abi_encode(),
+            Err(AbiEncodingError::ValueOutOfRange)
+        );
+        assert_eq!(
+            user_operation.pack(),
+            Err(AbiEncodingError::ValueOutOfRange)
+        );
+        let entry_point = Address([0; 20]);
+        assert_eq!(
+            user_operation.hash(&entry_point, ChainId::from(1)),
+            Err(AbiEncodingError::ValueOutOfRange)
+        );
+
+        // `uint256` max fits.
+        let mut user_operation = self::user_operation();
+        user_operation.max_fee_per_gas = Wei::from_hex("f".repeat(64)).unwrap();
+        assert!(user_operation.abi_encode().is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements EIP-6492 signatures of pre-deployed (counterfactual) contract wallets.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-6492
+
+use crate::blockchain::ethereum::abi::{
+    decode_address, decode_bytes, encode, AbiValue, ABI_WORD_BYTE_LENGTH,
+};
+use crate::blockchain::ethereum::eip191::{personal_sign_recover, SignatureValidationError};
+use crate::blockchain::ethereum::types::Address;
+
+/// The magic suffix of an EIP-6492 signature.
+pub const EIP6492_MAGIC_SUFFIX: [u8; 32] = [
+    0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64,
+    0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92, 0x64, 0x92,
+    0x64, 0x92,
+];
+
+/// Represents an EIP-6492 wrapped signature:
+/// `abi.encode(factory, factory_calldata, signature) ‖ EIP6492_MAGIC_SUFFIX`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Eip6492Signature {
+    /// Address of the factory which deploys the contract wallet.
+    pub factory: Address,
+    /// Calldata to deploy the contract wallet with `factory`.
+    pub factory_calldata: Vec<u8>,
+    /// The signature to validate with EIP-1271 once the contract wallet is deployed.
+    pub signature: Vec<u8>,
+}
+
+impl Eip6492Signature {
+    /// Decodes `signature` if it ends with `EIP6492_MAGIC_SUFFIX`.
+    ///
+    /// Returns `Ok(None)` if `signature` isn't wrapped.
+    ///
+    /// # Errors
+    ///
+    /// Will return `SignatureValidationError::InvalidEip6492Wrapper` if `signature` ends
+    /// with the magic suffix, but the payload isn't a valid `(address, bytes, bytes)`
+    /// ABI encoding.
+    pub fn decode(
+        signature: &[u8],
+    ) -> Result<Option<Eip6492Signature>, SignatureValidationError> {
+        let payload = match signature.strip_suffix(&EIP6492_MAGIC_SUFFIX) {
+            None => return Ok(None),
+            Some(payload) => payload,
+        };
+
+        let decode_payload = || {
+            Some(Eip6492Signature {
+                factory: decode_address(payload, 0)?,
+                factory_calldata: decode_bytes(payload, 0, ABI_WORD_BYTE_LENGTH)?,
+                signature: decode_bytes(payload, 0, 2 * ABI_WORD_BYTE_LENGTH)?,
+            })
+        };
+        decode_payload()
+            .map(Some)
+            .ok_or(SignatureValidationError::InvalidEip6492Wrapper)
+    }
+
+    /// Returns the wrapped signature, ending with `EIP6492_MAGIC_SUFFIX`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = encode(&[
+            AbiValue::Address(self.factory.clone()),
+            AbiValue::Bytes(self.factory_calldata.clone()),
+            AbiValue::Bytes(self.signature.clone()),
+        ])
+        // An address and two `bytes` always encode.
+        .unwrap();
+        data.extend(EIP6492_MAGIC_SUFFIX);
+        data
+    }
+}
+
+/// Validates that `signature` of the "personal_sign" `message` is from `expected_signer`.
+///
+/// A `signature` not wrapped by EIP-6492 is validated off-chain with
+/// `personal_sign_recover`, the way an EOA signature is.
+///
+/// A wrapped signature belongs to a contract wallet which hasn't been deployed yet.
+/// Validating it requires calling the factory to deploy the wallet, and then
+/// `isValidSignature` (EIP-1271) of the wallet, all on-chain, which is out of scope.
+/// For a well-formed wrapped signature, `SignatureValidationError::OnChainValidationRequired`
+/// is returned; use `Eip6492Signature::decode` to obtain the factory, the deployment
+/// calldata and the actual signature.
+///
+/// # Errors
+///
+/// Will return an error if `signature` is malformed or requires on-chain validation.
+pub fn validate_eip6492_signature(
+    message: &[u8],
+    signature: &[u8],
+    expected_signer: &Address,
+) -> Result<bool, SignatureValidationError> {
+    if Eip6492Signature::decode(signature)?.is_some() {
+        return Err(SignatureValidationError::OnChainValidationRequired);
+    }
+
+    let signer = personal_sign_recover(message, signature)?;
+    Ok(&signer == expected_signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::eip191::tests::personal_sign;
+    use crate::crypto::codecs::hex;
+
+    fn wrapped_signature() -> Eip6492Signature {
+        Eip6492Signature {
+            factory: "0x9406Cc6185a346906296840746125a0E44976454"
+                .try_into()
+                .unwrap(),
+            factory_calldata: hex::decode(
+                "5fbfb9cf0000000000000000000000000000000000000000000000000000000000000000",
+            )
+            .unwrap(),
+            signature: vec![0xab; 65],
+        }
+    }
+
+    #[test]
+    fn test_eip6492_signature_decode() {
+        let wrapped = wrapped_signature();
+        let data = wrapped.encode();
+        assert!(data.ends_with(&EIP6492_MAGIC_SUFFIX));
+        assert_eq!(Eip6492Signature::decode(&data), Ok(Some(wrapped)));
+
+        assert_eq!(Eip6492Signature::decode(&[0xab; 65]), Ok(None));
+
+        // truncated payload
+        let mut truncated = data[..data.len() - 64].to_vec();
+        truncated.extend(EIP6492_MAGIC_SUFFIX);
+        assert_eq!(
+            Eip6492Signature::decode(&truncated),
+            Err(SignatureValidationError::InvalidEip6492Wrapper)
+        );
+        assert_eq!(
+            Eip6492Signature::decode(&EIP6492_MAGIC_SUFFIX),
+            Err(SignatureValidationError::InvalidEip6492Wrapper)
+        );
+    }
+
+    #[test]
+    fn test_validate_eip6492_signature() {
+        let key_hex = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+        let signer: Address = "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            .try_into()
+            .unwrap();
+        let signature = personal_sign(b"hello", key_hex);
+
+        assert_eq!(
+            validate_eip6492_signature(b"hello", &signature, &signer),
+            Ok(true)
+        );
+        assert_eq!(
+            validate_eip6492_signature(b"hello", &signature, &Address::null()),
+            Ok(false)
+        );
+        assert_eq!(
+            validate_eip6492_signature(b"hello", &wrapped_signature().encode(), &signer),
+            Err(SignatureValidationError::OnChainValidationRequired)
+        );
+    }
+}
@@ -0,0 +1,454 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements offline suggestions of EIP-1559 fees from a recent base fee history.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-1559
+
+use crate::bigint::{BigInt, BigUint};
+use crate::blockchain::ethereum::types::Wei;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Display;
+
+/// The bound divisor of the base fee change between two blocks, limiting the change
+/// to 12.5%.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// The ratio of the gas limit to the gas target of a block.
+const ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// The default floor of the suggested priority fee: 1 gwei.
+pub const DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI: u64 = 1_000_000_000;
+
+/// Returns the base fee of the block following the block with `parent_base_fee`,
+/// `parent_gas_used` and `parent_gas_limit`, with the rule of EIP-1559.
+///
+/// This function will panic if `parent_gas_limit` is less than 2.
+pub fn next_base_fee(
+    parent_base_fee: &Wei,
+    parent_gas_used: u64,
+    parent_gas_limit: u64,
+) -> Wei {
+    let base_fee = parent_base_fee.0.as_bigint();
+    let gas_target = parent_gas_limit / ELASTICITY_MULTIPLIER;
+    assert!(gas_target > 0);
+
+    let change = |gas_delta: u64| {
+        base_fee * BigInt::from(gas_delta)
+            / BigInt::from(gas_target)
+            / BigInt::from(BASE_FEE_MAX_CHANGE_DENOMINATOR)
+    };
+    let next = match parent_gas_used.cmp(&gas_target) {
+        Ordering::Equal => base_fee.clone(),
+        Ordering::Greater => {
+            // increases by at least 1
+            let delta = change(parent_gas_used - gas_target).max(BigInt::one());
+            base_fee + delta
+        }
+        Ordering::Less => base_fee - change(gas_target - parent_gas_used),
+    };
+    Wei(BigUint::from_bigint(next).unwrap())
+}
+
+/// Suggested fees of an EIP-1559 transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: Wei,
+    pub max_priority_fee_per_gas: Wei,
+}
+
+impl FeeSuggestion {
+    /// Suggests the fees for a transaction to stay includable for `blocks_ahead` blocks
+    /// after the next one, with [`DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI`] as the floor of
+    /// the priority fee.
+    ///
+    /// See [`FeeSuggestion::suggest_with_priority_fee_floor`].
+    pub fn suggest(
+        base_fee_history: &[Wei],
+        gas_used_ratios: &[(u64, u64)],
+        priority_percentile_hint: Option<Wei>,
+        blocks_ahead: u8,
+    ) -> Result<FeeSuggestion, FeeSuggestionError> {
+        Self::suggest_with_priority_fee_floor(
+            base_fee_history,
+            gas_used_ratios,
+            priority_percentile_hint,
+            blocks_ahead,
+            &Wei(BigUint::from(DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI)),
+        )
+    }
+
+    /// Suggests the fees for a transaction to stay includable for `blocks_ahead` blocks
+    /// after the next one.
+    ///
+    /// - `base_fee_history` and `gas_used_ratios` describe the recent blocks, oldest
+    ///   first. A gas used ratio is `(gas_used, gas_limit)`.
+    /// - `priority_percentile_hint` is the priority fee observed for the recent blocks,
+    ///   e.g., a percentile of `eth_feeHistory`.
+    ///
+    /// The base fee of the next block is determined by the latest block. From it, the
+    /// worst case is projected: every one of the following `blocks_ahead` blocks is
+    /// full, raising the base fee by 12.5%.
+    ///
+    /// The priority fee is `priority_percentile_hint`, but no less than
+    /// `priority_fee_floor`. The max fee is the projected base fee plus the priority fee,
+    /// saturating at the max value of uint256.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The history is empty.
+    /// - `base_fee_history` and `gas_used_ratios` have different lengths.
+    /// - A gas used ratio has a gas limit less than 2, or more gas used than the limit.
+    pub fn suggest_with_priority_fee_floor(
+        base_fee_history: &[Wei],
+        gas_used_ratios: &[(u64, u64)],
+        priority_percentile_hint: Option<Wei>,
+        blocks_ahead: u8,
+        priority_fee_floor: &Wei,
+    ) -> Result<FeeSuggestion, FeeSuggestionError> {
+        if base_fee_history.is_empty() {
+            return Err(FeeSuggestionError::EmptyHistory);
+        }
+        if base_fee_history.len() != gas_used_ratios.len() {
+            return Err(FeeSuggestionError::HistoryLengthMismatch);
+        }
+        if gas_used_ratios.iter().any(|&(gas_used, gas_limit)| {
+            gas_limit < ELASTICITY_MULTIPLIER || gas_used > gas_limit
+        }) {
+            return Err(FeeSuggestionError::InvalidGasUsedRatio);
+        }
+
+        let (gas_used, gas_limit) = *gas_used_ratios.last().unwrap();
+        let mut base_fee = next_base_fee(base_fee_history.last().unwrap(), gas_used, gas_limit);
+        for _ in 0..blocks_ahead {
+            base_fee = next_base_fee(&base_fee, gas_limit, gas_limit);
+        }
+
+        let max_priority_fee_per_gas = match priority_percentile_hint {
+            Some(hint) if hint.0 >= priority_fee_floor.0 => hint,
+            _ => Wei(BigUint::from_bigint(priority_fee_floor.0.as_bigint().clone()).unwrap()),
+        };
+
+        let uint256_max = (BigInt::one() << 256) - BigInt::one();
+        let max_fee =
+            (base_fee.0.as_bigint() + max_priority_fee_per_gas.0.as_bigint()).min(uint256_max);
+        Ok(FeeSuggestion {
+            max_fee_per_gas: Wei(BigUint::from_bigint(max_fee).unwrap()),
+            max_priority_fee_per_gas,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeeSuggestionError {
+    EmptyHistory,
+    HistoryLengthMismatch,
+    InvalidGasUsedRatio,
+}
+
+impl Display for FeeSuggestionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeeSuggestionError::EmptyHistory => write!(f, "empty history"),
+            FeeSuggestionError::HistoryLengthMismatch => write!(f, "history length mismatch"),
+            FeeSuggestionError::InvalidGasUsedRatio => write!(f, "invalid gas used ratio"),
+        }
+    }
+}
+
+impl std::error::Error for FeeSuggestionError {}
+
+/// A rough estimate of EIP-1559 fees from the median of the recent base fees.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The median of the recent base fees.
+    pub base_fee: Wei,
+    pub suggested_priority_fee: Wei,
+    pub suggested_max_fee: Wei,
+}
+
+/// Estimates the fees for a transaction to be included within `target_confirmation_blocks`
+/// blocks.
+///
+/// Unlike [`FeeSuggestion`], which projects the base fee from the gas usage, the estimate
+/// scales the median of `recent_base_fees` by a fixed multiplier: 2x for the next block
+/// (a `target_confirmation_blocks` of 0 or 1), 1.5x for up to 3 blocks and 1.25x beyond.
+/// The priority fee is 2 gwei, 1.5 gwei and 1 gwei respectively. The max fee is the scaled
+/// base fee plus the priority fee.
+///
+/// # Errors
+///
+/// Will return `FeeSuggestionError::EmptyHistory` if `recent_base_fees` is empty.
+pub fn estimate_eip1559_fees(
+    recent_base_fees: &[Wei],
+    target_confirmation_blocks: u32,
+) -> Result<FeeEstimate, FeeSuggestionError> {
+    if recent_base_fees.is_empty() {
+        return Err(FeeSuggestionError::EmptyHistory);
+    }
+
+    let mut base_fees: Vec<&BigInt> = recent_base_fees
+        .iter()
+        .map(|fee| fee.0.as_bigint())
+        .collect();
+    base_fees.sort();
+    let middle = base_fees.len() / 2;
+    let median = if base_fees.len() % 2 == 1 {
+        base_fees[middle].clone()
+    } else {
+        (base_fees[middle - 1] + base_fees[middle]) >> 1
+    };
+
+    // (multiplier in percent, priority fee in wei)
+    let (multiplier, priority_fee) = match target_confirmation_blocks {
+        0 | 1 => (200_u64, 2 * DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI),
+        2 | 3 => (150, DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI * 3 / 2),
+        _ => (125, DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI),
+    };
+    let priority_fee = BigInt::from(priority_fee);
+    let max_fee = &median * &BigInt::from(multiplier) / BigInt::from(100) + &priority_fee;
+
+    Ok(FeeEstimate {
+        base_fee: Wei(BigUint::from_bigint(median).unwrap()),
+        suggested_priority_fee: Wei(BigUint::from_bigint(priority_fee).unwrap()),
+        suggested_max_fee: Wei(BigUint::from_bigint(max_fee).unwrap()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::account::EoaPrivateKey;
+    use crate::blockchain::ethereum::transaction::TransactionBuilder;
+    use crate::crypto::ecdsa::SigningOptions;
+
+    fn wei(n: u64) -> Wei {
+        Wei(BigUint::from(n))
+    }
+
+    const GWEI: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_next_base_fee() {
+        // (parent_base_fee, parent_gas_used, parent_gas_limit, next_base_fee)
+        let data = [
+            (100 * GWEI, 15_000_000, 30_000_000, 100 * GWEI),
+            (100 * GWEI, 30_000_000, 30_000_000, 112_500_000_000),
+            (100 * GWEI, 20_000_000, 30_000_000, 104_166_666_666),
+            (100 * GWEI, 5_000_000, 30_000_000, 91_666_666_667),
+            (100 * GWEI, 0, 30_000_000, 87_500_000_000),
+            // increases by at least 1
+            (7, 30_000_000, 30_000_000, 8),
+            (7, 15_000_001, 30_000_000, 8),
+            // Mainnet blocks 14402566 and 14402712
+            (36_803_013_756, 2_023_848, 30_087_887, 32_821_521_542),
+            (24_870_031_149, 29_999_374, 30_000_000, 27_978_655_303),
+        ];
+        for (base_fee, gas_used, gas_limit, expected) in data {
+            assert_eq!(
+                next_base_fee(&wei(base_fee), gas_used, gas_limit),
+                wei(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_suggest_projection() {
+        // The latest block is at the gas target: the next base fee is unchanged.
+        let history = [wei(90 * GWEI), wei(100 * GWEI)];
+        let ratios = [(30_000_000, 30_000_000), (15_000_000, 30_000_000)];
+
+        // 100 gwei compounded by 12.5% for `blocks_ahead` blocks
+        let data = [
+            (0, 100_000_000_000),
+            (1, 112_500_000_000),
+            (2, 126_562_500_000),
+            (3, 142_382_812_500),
+            (4, 160_180_664_062),
+            (5, 180_203_247_069),
+        ];
+        for (blocks_ahead, projected_base_fee) in data {
+            let suggestion =
+                FeeSuggestion::suggest(&history, &ratios, Some(wei(2 * GWEI)), blocks_ahead)
+                    .unwrap();
+            assert_eq!(
+                suggestion,
+                FeeSuggestion {
+                    max_fee_per_gas: wei(projected_base_fee + 2 * GWEI),
+                    max_priority_fee_per_gas: wei(2 * GWEI),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_suggest_priority_fee_floor() {
+        let history = [wei(100 * GWEI)];
+        let ratios = [(15_000_000, 30_000_000)];
+
+        // (hint, floor, priority fee)
+        let data = [
+            (None, GWEI, GWEI),
+            (Some(wei(GWEI / 2)), GWEI, GWEI),
+            (Some(wei(3 * GWEI)), GWEI, 3 * GWEI),
+            (Some(wei(0)), 0, 0),
+        ];
+        for (hint, floor, priority_fee) in data {
+            let suggestion = FeeSuggestion::suggest_with_priority_fee_floor(
+                &history,
+                &ratios,
+                hint,
+                0,
+                &wei(floor),
+            )
+            .unwrap();
+            assert_eq!(suggestion.max_priority_fee_per_gas, wei(priority_fee));
+            assert_eq!(suggestion.max_fee_per_gas, wei(100 * GWEI + priority_fee));
+        }
+    }
+
+    #[test]
+    fn test_suggest_saturating() {
+        let ratios = [(30_000_000, 30_000_000)];
+
+        // Compounding for 255 blocks raises the base fee by about 10^13 times.
+        let suggestion =
+            FeeSuggestion::suggest(&[wei(100 * GWEI)], &ratios, None, u8::MAX).unwrap();
+        let max_fee = suggestion.max_fee_per_gas.0.as_bigint();
+        assert!(max_fee > &BigInt::from(100 * GWEI as u128 * 10_u128.pow(13)));
+
+        // saturates at the max value of uint256
+        let huge_base_fee = Wei::from_hex("ff".repeat(30)).unwrap();
+        let suggestion =
+            FeeSuggestion::suggest(&[huge_base_fee], &ratios, None, u8::MAX).unwrap();
+        assert_eq!(
+            suggestion.max_fee_per_gas,
+            Wei::from_hex("ff".repeat(32)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_suggest_err_cases() {
+        let data = [
+            (vec![], vec![], FeeSuggestionError::EmptyHistory),
+            (
+                vec![wei(GWEI)],
+                vec![],
+                FeeSuggestionError::HistoryLengthMismatch,
+            ),
+            (
+                vec![wei(GWEI), wei(GWEI)],
+                vec![(0, 30_000_000)],
+                FeeSuggestionError::HistoryLengthMismatch,
+            ),
+            (
+                vec![wei(GWEI)],
+                vec![(30_000_001, 30_000_000)],
+                FeeSuggestionError::InvalidGasUsedRatio,
+            ),
+            (
+                vec![wei(GWEI)],
+                vec![(0, 1)],
+                FeeSuggestionError::InvalidGasUsedRatio,
+            ),
+        ];
+        for (history, ratios, err) in data {
+            assert_eq!(FeeSuggestion::suggest(&history, &ratios, None, 1), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_estimate_eip1559_fees() {
+        // The `baseFeePerGas` of a recorded `eth_feeHistory` response for mainnet blocks
+        // 13442553 (0xcd1df9) to 13442557, followed by the base fee of the next block.
+        // Taken from the tests of rust-web3 0.19.0 (src/api/eth.rs).
+        let history = [
+            "0x15f794d04b",
+            "0x1730fe199f",
+            "0x176212b802",
+            "0x165bce08cb",
+            "0x16c6235c9d",
+            "0x1539ff7ccd",
+        ]
+        .map(|fee| Wei::from_hex(fee).unwrap());
+
+        // The median of the 6 base fees is (96029509835 + 97813486749) / 2, rounded down.
+        // (target_confirmation_blocks, priority fee, max fee)
+        let data = [
+            (0, 2 * GWEI, 193_842_996_584 + 2 * GWEI),
+            (1, 2 * GWEI, 193_842_996_584 + 2 * GWEI),
+            (2, 1_500_000_000, 145_382_247_438 + 1_500_000_000),
+            (3, 1_500_000_000, 145_382_247_438 + 1_500_000_000),
+            (4, GWEI, 121_151_872_865 + GWEI),
+            (100, GWEI, 121_151_872_865 + GWEI),
+        ];
+        for (target_confirmation_blocks, priority_fee, max_fee) in data {
+            assert_eq!(
+                estimate_eip1559_fees(&history, target_confirmation_blocks),
+                Ok(FeeEstimate {
+                    base_fee: wei(96_921_498_292),
+                    suggested_priority_fee: wei(priority_fee),
+                    suggested_max_fee: wei(max_fee),
+                })
+            );
+        }
+
+        // The median of an odd number of base fees: the 5 blocks of the response only.
+        assert_eq!(
+            estimate_eip1559_fees(&history[..5], 1).unwrap().base_fee,
+            wei(97_813_486_749)
+        );
+
+        assert_eq!(
+            estimate_eip1559_fees(&[], 1),
+            Err(FeeSuggestionError::EmptyHistory)
+        );
+    }
+
+    #[test]
+    fn test_suggestion_to_transaction() {
+        let history = [wei(100 * GWEI), wei(110 * GWEI)];
+        let ratios = [(29_000_000, 30_000_000), (12_000_000, 30_000_000)];
+        let FeeSuggestion {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        } = FeeSuggestion::suggest(&history, &ratios, Some(wei(2 * GWEI)), 3).unwrap();
+
+        let private_key = EoaPrivateKey::from_any_hex(
+            "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+        )
+        .unwrap();
+        let transaction = TransactionBuilder::new()
+            .with_chain_id(1.into())
+            .with_nonce(0.try_into().unwrap())
+            .with_max_priority_fee_per_gas(max_priority_fee_per_gas)
+            .with_max_fee_per_gas(max_fee_per_gas)
+            .with_gas_limit(21_000.into())
+            .with_destination(
+                "0x123456789a123456789a123456789a123456789a"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount(wei(1))
+            .take_and_build_payload_eip_1559()
+            .unwrap()
+            .take_and_sign_with_options(
+                &private_key.0,
+                &SigningOptions {
+                    employ_extra_random_data: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            transaction.recover_sender(),
+            Some(private_key.public_key().address())
+        );
+    }
+}
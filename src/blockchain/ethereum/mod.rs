@@ -4,8 +4,38 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+#[cfg(feature = "std")]
+pub mod abi;
+#[cfg(feature = "std")]
 pub mod account;
+#[cfg(feature = "std")]
+pub mod block;
+#[cfg(feature = "std")]
+pub mod create2;
+#[cfg(feature = "std")]
+pub mod eip1271;
+#[cfg(feature = "std")]
+pub mod eip191;
+#[cfg(feature = "std")]
+pub mod eip2098;
+#[cfg(feature = "std")]
+pub mod eip3085;
+#[cfg(feature = "std")]
+pub mod eip4337;
+#[cfg(feature = "std")]
+pub mod eip6492;
+#[cfg(feature = "std")]
+pub mod eip712;
+#[cfg(feature = "std")]
+pub mod fees;
 pub mod rlp;
+#[cfg(feature = "std")]
+pub mod siwe;
+#[cfg(feature = "std")]
 pub mod ssz;
+#[cfg(feature = "std")]
 pub mod transaction;
+#[cfg(feature = "std")]
 pub mod types;
+#[cfg(feature = "std")]
+pub mod unsigned_bundle;
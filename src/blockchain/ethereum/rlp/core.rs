@@ -6,6 +6,9 @@
 
 //! Common definitions for RLP.
 
+use core::fmt;
+use core::fmt::Display;
+
 /// Represents RLP item types.
 ///
 /// "...
@@ -21,6 +24,15 @@ pub enum RlpItemType {
     List,
 }
 
+impl Display for RlpItemType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RlpItemType::SingleValue => write!(f, "a single value"),
+            RlpItemType::List => write!(f, "a list"),
+        }
+    }
+}
+
 // For encoding data with size more than 55 bytes long,
 // "...The range of the first byte is thus [0xb8, 0xbf]..." for single value, and
 // "...The range of the first byte is thus [0xf8, 0xff]..." for list.
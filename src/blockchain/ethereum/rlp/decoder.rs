@@ -7,9 +7,13 @@
 //! Provides `DecodingItem` implementation for RLP.
 
 use super::core::RlpItemType;
-use super::decoding::{decode_data, decode_list_payload, RlpDataDecodingError};
+use super::decoding::{
+    check_item_type, check_uint_payload, decode_data, decode_list_payload, RlpDataDecodingError,
+};
 use crate::bigint::BigUint;
-use crate::tools::codable::{Decodable, DecodingItem};
+use crate::tools::codable::{seal_codable, Decodable, DecodingItem};
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// The RLP decoding type which implements `DecodingItem`.
 pub struct RlpDecodingItem<'a> {
@@ -18,17 +22,14 @@ pub struct RlpDecodingItem<'a> {
 }
 
 impl<'a> RlpDecodingItem<'a> {
-    pub fn decode_as_bytes(&self) -> Result<&[u8], RlpDataDecodingError> {
-        if self.item_type != RlpItemType::SingleValue {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+    /// Returns the payload of a single value, borrowed from the data being decoded.
+    pub fn decode_as_bytes(&self) -> Result<&'a [u8], RlpDataDecodingError> {
+        check_item_type(RlpItemType::SingleValue, self.item_type)?;
         Ok(self.payload)
     }
 
     pub fn decode_as_items(&self) -> Result<Vec<Self>, RlpDataDecodingError> {
-        if self.item_type != RlpItemType::List {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+        check_item_type(RlpItemType::List, self.item_type)?;
 
         let items = decode_list_payload(self.payload)?;
         let mut decoding_items = Vec::with_capacity(items.len());
@@ -40,6 +41,9 @@ impl<'a> RlpDecodingItem<'a> {
     }
 }
 
+#[cfg(not(feature = "unstable-codable"))]
+impl crate::tools::codable::SealedItem for RlpDecodingItem<'_> {}
+
 impl<'a> DecodingItem<'a> for RlpDecodingItem<'a> {
     type Error = RlpDataDecodingError;
 
@@ -49,18 +53,26 @@ impl<'a> DecodingItem<'a> for RlpDecodingItem<'a> {
     }
 }
 
+seal_codable!(<'a> RlpDecodingItem<'a> => u64, String, BigUint, &'a [u8]);
+
+// Covers `Vec<u8>` as well.
+#[cfg(not(feature = "unstable-codable"))]
+impl<'a, T> crate::tools::codable::Sealed<RlpDecodingItem<'a>> for Vec<T> {}
+
+#[cfg(not(feature = "unstable-codable"))]
+impl<'a, const N: usize> crate::tools::codable::Sealed<RlpDecodingItem<'a>> for &'a [u8; N] {}
+
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for u64 {
     fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
-        if decoding_item.item_type != RlpItemType::SingleValue {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+        check_item_type(RlpItemType::SingleValue, decoding_item.item_type)?;
 
-        if decoding_item.payload.len() > std::mem::size_of::<u64>() {
+        if decoding_item.payload.len() > core::mem::size_of::<u64>() {
             return Err(RlpDataDecodingError::InvalidFormat);
         }
+        check_uint_payload(decoding_item.payload)?;
 
-        let mut n_bytes = [0; std::mem::size_of::<u64>()];
-        n_bytes[(std::mem::size_of::<u64>() - decoding_item.payload.len())..]
+        let mut n_bytes = [0; core::mem::size_of::<u64>()];
+        n_bytes[(core::mem::size_of::<u64>() - decoding_item.payload.len())..]
             .copy_from_slice(decoding_item.payload);
         let n = u64::from_be_bytes(n_bytes);
 
@@ -70,9 +82,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for u64 {
 
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for String {
     fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
-        if decoding_item.item_type != RlpItemType::SingleValue {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+        check_item_type(RlpItemType::SingleValue, decoding_item.item_type)?;
 
         match String::from_utf8(decoding_item.payload.to_vec()) {
             Ok(str) => Ok(str),
@@ -83,19 +93,34 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for String {
 
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for Vec<u8> {
     fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
-        if decoding_item.item_type != RlpItemType::SingleValue {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+        check_item_type(RlpItemType::SingleValue, decoding_item.item_type)?;
 
         Ok(decoding_item.payload.to_vec())
     }
 }
 
+/// Decodes a single value without copying, borrowing from the data being decoded.
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for &'a [u8] {
+    fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
+        decoding_item.decode_as_bytes()
+    }
+}
+
+/// Decodes a single value of exactly `N` bytes without copying, borrowing from the data
+/// being decoded.
+impl<'a, const N: usize> Decodable<'a, RlpDecodingItem<'a>> for &'a [u8; N] {
+    fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
+        decoding_item
+            .decode_as_bytes()?
+            .try_into()
+            .map_err(|_| RlpDataDecodingError::InvalidFormat)
+    }
+}
+
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for BigUint {
     fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
-        if decoding_item.item_type != RlpItemType::SingleValue {
-            return Err(RlpDataDecodingError::InvalidFormat);
-        }
+        check_item_type(RlpItemType::SingleValue, decoding_item.item_type)?;
+        check_uint_payload(decoding_item.payload)?;
 
         Ok(if decoding_item.payload.is_empty() {
             // BigInt represents 0 as [0_u8] -- empty is not allowed.
@@ -112,8 +137,7 @@ where
     T: Decodable<'a, RlpDecodingItem<'a>>,
 {
     fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
-        debug_assert!(decoding_item.item_type == RlpItemType::List);
-
+        // `decode_as_items` rejects a single value.
         let items = decoding_item.decode_as_items()?;
         let mut values = Self::with_capacity(items.len());
         for item in items {
@@ -127,7 +151,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::hex_to_bytes;
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{decode, Decodable};
     use devtools::path::integration_testing_data_path;
     use serde_json::{Number, Value};
@@ -151,6 +175,64 @@ mod tests {
             payload: &[1, 2, 3, 4, 5, 6, 7, 8, 9],
         };
         assert!(u64::decode_from(&decoding_item).is_err());
+
+        // u64 and BigUint, leading zeros
+        for payload in [&[0][..], &[0, 1]] {
+            let decoding_item = RlpDecodingItem {
+                item_type: RlpItemType::SingleValue,
+                payload,
+            };
+            assert!(matches!(
+                u64::decode_from(&decoding_item),
+                Err(RlpDataDecodingError::NonCanonicalInteger)
+            ));
+            assert!(matches!(
+                BigUint::decode_from(&decoding_item),
+                Err(RlpDataDecodingError::NonCanonicalInteger)
+            ));
+        }
+
+        // Vec<T>, single value
+        let decoding_item = RlpDecodingItem {
+            item_type: RlpItemType::SingleValue,
+            payload: &[1],
+        };
+        assert!(matches!(
+            Vec::<u64>::decode_from(&decoding_item),
+            Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue
+            })
+        ));
+
+        // u64, list
+        let decoding_item = RlpDecodingItem {
+            item_type: RlpItemType::List,
+            payload: &[],
+        };
+        assert!(matches!(
+            u64::decode_from(&decoding_item),
+            Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::SingleValue,
+                found: RlpItemType::List
+            })
+        ));
+    }
+
+    #[test]
+    fn test_decoding_borrowed_bytes() {
+        // [ "cat", "dog" ]
+        let data = hex::decode("c88363617483646f67").unwrap();
+        let values: Vec<&[u8]> = decode(&data).unwrap();
+        assert_eq!(values, [b"cat", b"dog"]);
+        // borrowed from `data`
+        assert_eq!(values[0].as_ptr(), data[2..].as_ptr());
+
+        let values: Vec<&[u8; 3]> = decode(&data).unwrap();
+        assert_eq!(values, [b"cat", b"dog"]);
+        assert!(decode::<Vec<&[u8; 4]>, RlpDecodingItem>(&data).is_err());
+        // a list for a single value
+        assert!(decode::<&[u8], RlpDecodingItem>(&data).is_err());
     }
 
     #[test]
@@ -162,7 +244,7 @@ mod tests {
         for value in value_vec {
             let input = &value["in"];
             let output_hex = value["out"].as_str().unwrap();
-            let output = hex_to_bytes(output_hex).unwrap();
+            let output = hex::decode(output_hex).unwrap();
 
             // Decodes a `serde_json::Value` from `output`.
             // See `Decodable` implementations for `JsonValueSingleValueU64` and `JsonValueSingleValueString`.
@@ -201,6 +283,8 @@ mod tests {
 
     struct JsonValueSingleValueU64(Value);
 
+    seal_codable!(<'a> RlpDecodingItem<'a> => JsonValueSingleValueU64, JsonValueSingleValueString);
+
     impl<'a> Decodable<'a, RlpDecodingItem<'a>> for JsonValueSingleValueU64 {
         fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
             return match decoding_item.item_type {
@@ -9,9 +9,11 @@
 
 use super::core::RlpItemType;
 use super::core::{UintByteLengthOfPayloadByteLength, UintPayloadByteLength};
-use std::error::Error;
-use std::fmt;
-use std::fmt::Display;
+use crate::bigint::{BigInt, Sign};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::fmt::Display;
 
 /// Decodes a RLP item from `data`.
 /// Returns the type and the payload data of the item if the decoding is successful.
@@ -24,6 +26,59 @@ pub(crate) fn decode_data(data: &[u8]) -> Result<(RlpItemType, &[u8]), RlpDataDe
     Ok((item_type, &data[header_byte_length as usize..]))
 }
 
+/// Decodes a RLP list item from `data`, returning the types and the payloads of its items.
+///
+/// Returns `RlpDataDecodingError::UnexpectedItemType` if `data` is a single value.
+pub fn decode_list(data: &[u8]) -> Result<Vec<(RlpItemType, &[u8])>, RlpDataDecodingError> {
+    let (item_type, payload) = decode_data(data)?;
+    check_item_type(RlpItemType::List, item_type)?;
+    decode_list_payload(payload)
+}
+
+/// Decodes a RLP single value item from `data`, returning its payload.
+///
+/// Returns `RlpDataDecodingError::UnexpectedItemType` if `data` is a list.
+pub fn decode_value(data: &[u8]) -> Result<&[u8], RlpDataDecodingError> {
+    let (item_type, payload) = decode_data(data)?;
+    check_item_type(RlpItemType::SingleValue, item_type)?;
+    Ok(payload)
+}
+
+/// Returns `RlpDataDecodingError::UnexpectedItemType` if `found` isn't `expected`.
+pub(crate) fn check_item_type(
+    expected: RlpItemType,
+    found: RlpItemType,
+) -> Result<(), RlpDataDecodingError> {
+    if expected != found {
+        return Err(RlpDataDecodingError::UnexpectedItemType { expected, found });
+    }
+    Ok(())
+}
+
+/// Decodes an unsigned integer from the single value item `data`.
+///
+/// Returns `RlpDataDecodingError::NonCanonicalInteger` if the payload has leading zeros,
+/// including the single byte `[0x00]` -- zero is encoded as the empty string.
+/// See [`super::encoding::encode_uint_bigint`] for the reverse.
+pub fn decode_uint_bigint(data: &[u8]) -> Result<BigInt, RlpDataDecodingError> {
+    let payload = decode_value(data)?;
+    check_uint_payload(payload)?;
+
+    Ok(if payload.is_empty() {
+        BigInt::zero()
+    } else {
+        BigInt::from_be_bytes(payload, Sign::Positive)
+    })
+}
+
+/// Returns an error if the integer `payload` of a single value item has leading zeros.
+pub(crate) fn check_uint_payload(payload: &[u8]) -> Result<(), RlpDataDecodingError> {
+    if payload.first() == Some(&0) {
+        return Err(RlpDataDecodingError::NonCanonicalInteger);
+    }
+    Ok(())
+}
+
 /// Decodes an array of RLP items from `list_payload`.
 pub(crate) fn decode_list_payload(
     list_payload: &[u8],
@@ -51,6 +106,74 @@ pub(crate) fn decode_list_payload(
     Ok(item_list)
 }
 
+/// Validates that `data` is a single RLP item in the canonical form, recursing into lists.
+///
+/// Returns `RlpDataDecodingError::NonCanonicalEncoding` for
+/// - a single byte within [0x00, 0x7f] encoded as a string of length 1, e.g., `[0x81, 0x00]`
+/// - a long-form header for a payload shorter than 56 bytes
+/// - a long-form header whose length has leading zeros
+///
+/// Whether a single value is an integer depends on the application, hence the leading zeros
+/// of integers are checked by [`decode_uint_bigint`] instead.
+pub fn validate_canonical_rlp(data: &[u8]) -> Result<(), RlpDataDecodingError> {
+    let (item_type, header_byte_length, payload_byte_length) = decode_canonical_header(data)?;
+    if data.len() != header_byte_length + payload_byte_length {
+        return Err(RlpDataDecodingError::InvalidFormat);
+    }
+
+    // Keeps the list payloads to validate in a stack instead of recursing, for the depth of
+    // untrusted data is unbounded.
+    let mut list_payloads = Vec::new();
+    if item_type == RlpItemType::List {
+        list_payloads.push(&data[header_byte_length..]);
+    }
+    while let Some(mut remaining_list_payload) = list_payloads.pop() {
+        while !remaining_list_payload.is_empty() {
+            let (item_type, header_byte_length, payload_byte_length) =
+                decode_canonical_header(remaining_list_payload)?;
+            let item_byte_length = header_byte_length + payload_byte_length;
+            if remaining_list_payload.len() < item_byte_length {
+                return Err(RlpDataDecodingError::InvalidFormat);
+            }
+            if item_type == RlpItemType::List {
+                list_payloads
+                    .push(&remaining_list_payload[header_byte_length..item_byte_length]);
+            }
+            remaining_list_payload = &remaining_list_payload[item_byte_length..];
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes a RLP header from `data` like `decode_data_header`, returning an error if the
+/// header isn't in the canonical form.
+fn decode_canonical_header(
+    data: &[u8],
+) -> Result<(RlpItemType, usize, usize), RlpDataDecodingError> {
+    let (item_type, header_byte_length, payload_byte_length) = decode_data_header(data)?;
+    let (header_byte_length, payload_byte_length) =
+        (header_byte_length as usize, payload_byte_length as usize);
+    let is_canonical = match header_byte_length {
+        0 => true,
+        // "For a single byte whose value is in the [0x00, 0x7f] range, that byte is its own
+        // RLP encoding."
+        1 => {
+            item_type == RlpItemType::List
+                || payload_byte_length != 1
+                || !matches!(data.get(1), Some(0x00..=0x7f))
+        }
+        // The long form is for payloads of more than 55 bytes, with the length in the
+        // fewest bytes.
+        _ => payload_byte_length >= 56 && data[1] != 0,
+    };
+    if !is_canonical {
+        return Err(RlpDataDecodingError::NonCanonicalEncoding);
+    }
+
+    Ok((item_type, header_byte_length, payload_byte_length))
+}
+
 /// Decodes a RLP header from `data`.
 ///
 /// On success, returns
@@ -94,26 +217,12 @@ fn decode_data_header(
         // The range of the first byte is thus [0xb8, 0xbf]..."
         0xb8..=0xbf => {
             let byte_length_of_payload_byte_length = first - 0xb7;
-            if data.len() < (1 + byte_length_of_payload_byte_length as usize) {
-                return Err(RlpDataDecodingError::InvalidFormat);
-            }
-            let mut payload_byte_length_bytes =
-                [0; std::mem::size_of::<UintPayloadByteLength>()];
-            payload_byte_length_bytes[(std::mem::size_of::<UintPayloadByteLength>()
-                - byte_length_of_payload_byte_length as usize)..]
-                .copy_from_slice(&data[1..=byte_length_of_payload_byte_length as usize]);
             let payload_byte_length =
-                UintPayloadByteLength::from_be_bytes(payload_byte_length_bytes) as usize;
-
-            if data.len()
-                < (1 + byte_length_of_payload_byte_length as usize + payload_byte_length)
-            {
-                return Err(RlpDataDecodingError::InvalidFormat);
-            }
+                decode_long_payload_byte_length(data, byte_length_of_payload_byte_length)?;
             Ok((
                 RlpItemType::SingleValue,
                 1 + byte_length_of_payload_byte_length,
-                UintPayloadByteLength::try_from(payload_byte_length).unwrap(),
+                payload_byte_length,
             ))
         }
 
@@ -134,36 +243,62 @@ fn decode_data_header(
         // The range of the first byte is thus [0xf8, 0xff].
         0xf8..=0xff => {
             let byte_length_of_payload_byte_length = first - 0xf7;
-            if data.len() < (1 + byte_length_of_payload_byte_length as usize) {
-                return Err(RlpDataDecodingError::InvalidFormat);
-            }
-            let mut payload_byte_length_bytes =
-                [0; std::mem::size_of::<UintPayloadByteLength>()];
-            payload_byte_length_bytes[(std::mem::size_of::<UintPayloadByteLength>()
-                - byte_length_of_payload_byte_length as usize)..]
-                .copy_from_slice(&data[1..=byte_length_of_payload_byte_length as usize]);
             let payload_byte_length =
-                UintPayloadByteLength::from_be_bytes(payload_byte_length_bytes) as usize;
-
-            if data.len()
-                < (1 + byte_length_of_payload_byte_length as usize + payload_byte_length)
-            {
-                return Err(RlpDataDecodingError::InvalidFormat);
-            }
+                decode_long_payload_byte_length(data, byte_length_of_payload_byte_length)?;
             Ok((
                 RlpItemType::List,
                 1 + byte_length_of_payload_byte_length,
-                UintPayloadByteLength::try_from(payload_byte_length).unwrap(),
+                payload_byte_length,
             ))
         }
     }
 }
 
+/// Decodes the payload length of the long forms, the `byte_length_of_payload_byte_length`
+/// bytes following the first byte of `data`.
+///
+/// Returns an error if `data` is too short for the header and the payload.
+fn decode_long_payload_byte_length(
+    data: &[u8],
+    byte_length_of_payload_byte_length: UintByteLengthOfPayloadByteLength,
+) -> Result<UintPayloadByteLength, RlpDataDecodingError> {
+    const UINT_PAYLOAD_BYTE_LENGTH_SIZE: usize = core::mem::size_of::<UintPayloadByteLength>();
+
+    let byte_length_of_payload_byte_length = byte_length_of_payload_byte_length as usize;
+    // A length that doesn't fit in `UintPayloadByteLength` is unrepresentable anyway.
+    if byte_length_of_payload_byte_length > UINT_PAYLOAD_BYTE_LENGTH_SIZE {
+        return Err(RlpDataDecodingError::InvalidFormat);
+    }
+    let header_byte_length = 1 + byte_length_of_payload_byte_length;
+    if data.len() < header_byte_length {
+        return Err(RlpDataDecodingError::InvalidFormat);
+    }
+
+    let mut payload_byte_length_bytes = [0; UINT_PAYLOAD_BYTE_LENGTH_SIZE];
+    payload_byte_length_bytes
+        [(UINT_PAYLOAD_BYTE_LENGTH_SIZE - byte_length_of_payload_byte_length)..]
+        .copy_from_slice(&data[1..header_byte_length]);
+    let payload_byte_length = UintPayloadByteLength::from_be_bytes(payload_byte_length_bytes);
+
+    // Compares with the remaining length, for `header_byte_length + payload_byte_length`
+    // may overflow.
+    if payload_byte_length > (data.len() - header_byte_length) as UintPayloadByteLength {
+        return Err(RlpDataDecodingError::InvalidFormat);
+    }
+    Ok(payload_byte_length)
+}
+
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum RlpDataDecodingError {
     InvalidFormat,
     TransactionTypeMismatch,
+    NonCanonicalInteger,
+    NonCanonicalEncoding,
+    UnexpectedItemType {
+        expected: RlpItemType,
+        found: RlpItemType,
+    },
 }
 
 impl Display for RlpDataDecodingError {
@@ -175,6 +310,15 @@ impl Display for RlpDataDecodingError {
             RlpDataDecodingError::TransactionTypeMismatch => {
                 write!(f, "Transaction interpreted with the wrong type")
             }
+            RlpDataDecodingError::NonCanonicalInteger => {
+                write!(f, "Integer encoded with leading zeros")
+            }
+            RlpDataDecodingError::NonCanonicalEncoding => {
+                write!(f, "Non-canonical encoding")
+            }
+            RlpDataDecodingError::UnexpectedItemType { expected, found } => {
+                write!(f, "Expected {expected}, found {found}")
+            }
         }
     }
 }
@@ -184,6 +328,8 @@ impl Error for RlpDataDecodingError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::testing_tools::quickcheck::{RlpEncodedData, RlpLongHeaderData};
     use ::quickcheck_macros::quickcheck;
 
     #[test]
@@ -294,11 +440,161 @@ mod tests {
         assert!(decode_list_payload(&payload2).is_err());
     }
 
+    #[test]
+    fn test_decoding_long_form_error_cases() {
+        for first in (0xb8..=0xbf).chain(0xf8..=0xff) {
+            let byte_length_of_payload_byte_length = (first & 0x3f) as usize - 0x37;
+
+            // header truncated
+            for len in 1..=byte_length_of_payload_byte_length {
+                let mut data = vec![first];
+                data.extend(vec![0x01; len - 1]);
+                assert!(decode_data(&data).is_err());
+                assert!(decode_list_payload(&data).is_err());
+            }
+
+            // payload truncated
+            let mut data = vec![first];
+            data.extend(vec![0; byte_length_of_payload_byte_length - 1]);
+            data.push(0x40);
+            data.extend([0; 0x3f]);
+            assert!(decode_data(&data).is_err());
+            assert!(decode_list_payload(&data).is_err());
+            data.push(0);
+            assert!(decode_data(&data).is_ok());
+
+            // absurdly large declared length
+            let mut data = vec![first];
+            data.extend(vec![0xff; byte_length_of_payload_byte_length]);
+            data.extend([0; 0x40]);
+            assert!(decode_data(&data).is_err());
+            assert!(decode_list_payload(&data).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decoding_list_and_value() {
+        // [ "cat", "dog" ]
+        let data = hex::decode("c88363617483646f67").unwrap();
+        let items = decode_list(&data).unwrap();
+        assert_eq!(
+            items,
+            [
+                (RlpItemType::SingleValue, &b"cat"[..]),
+                (RlpItemType::SingleValue, &b"dog"[..])
+            ]
+        );
+        let err = decode_value(&data).unwrap_err();
+        assert!(matches!(
+            err,
+            RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::SingleValue,
+                found: RlpItemType::List
+            }
+        ));
+        assert_eq!(err.to_string(), "Expected a single value, found a list");
+
+        // "dog"
+        let data = hex::decode("83646f67").unwrap();
+        assert_eq!(decode_value(&data).unwrap(), b"dog");
+        let err = decode_list(&data).unwrap_err();
+        assert!(matches!(
+            err,
+            RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue
+            }
+        ));
+        assert_eq!(err.to_string(), "Expected a list, found a single value");
+
+        // Malformed data is still `InvalidFormat`
+        for hex in ["", "8364", "c2c0"] {
+            let data = hex::decode(hex).unwrap();
+            assert!(matches!(
+                decode_value(&data),
+                Err(RlpDataDecodingError::InvalidFormat)
+            ));
+            assert!(matches!(
+                decode_list(&data),
+                Err(RlpDataDecodingError::InvalidFormat)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validating_canonical_rlp() {
+        let long_string = hex::encode_lower(&[0x61; 56]);
+        let data = [
+            // (hex, is_canonical)
+            ("00", true),
+            ("7f", true),
+            ("80", true),
+            ("8180", true),
+            ("820001", true),
+            ("c0", true),
+            ("c7c0c1c0c3c0c1c0", true),
+            (&format!("b838{long_string}"), true),
+            (&format!("f83ab838{long_string}"), true),
+            // single bytes within [0x00, 0x7f] in the string form
+            ("8100", false),
+            ("817f", false),
+            ("c28100", false),
+            ("c3c28100", false),
+            // long forms for short payloads
+            ("b80161", false),
+            ("b800", false),
+            ("f801c0", false),
+            ("c3f801c0", false),
+            // long-form lengths with leading zeros
+            (&format!("b90038{long_string}"), false),
+            (&format!("f9003ab838{long_string}"), false),
+        ];
+        for (hex, is_canonical) in data {
+            let result = validate_canonical_rlp(&hex::decode(hex).unwrap());
+            if is_canonical {
+                assert!(result.is_ok(), "{hex}");
+            } else {
+                assert!(
+                    matches!(result, Err(RlpDataDecodingError::NonCanonicalEncoding)),
+                    "{hex}"
+                );
+            }
+        }
+
+        // Malformed data
+        for hex in ["", "8000", "81", "c181", "c28080c0", "c1c180"] {
+            assert!(
+                matches!(
+                    validate_canonical_rlp(&hex::decode(hex).unwrap()),
+                    Err(RlpDataDecodingError::InvalidFormat)
+                ),
+                "{hex}"
+            );
+        }
+    }
+
+    // Tests that the encoder outputs the canonical form.
+    #[quickcheck]
+    fn encoded_data_is_canonical(data: RlpEncodedData) -> bool {
+        validate_canonical_rlp(&data.0).is_ok()
+    }
+
+    // Tests that decoding won't panic for data with a long-form header.
+    #[quickcheck]
+    fn test_decoding_long_header_data_will_not_panic(data: RlpLongHeaderData) -> bool {
+        let _ = decode_data(&data.0);
+        let _ = decode_list_payload(&data.0);
+        let _ = validate_canonical_rlp(&data.0);
+
+        true
+    }
+
     // Tests that decoding won't panic for whatever data it's fed.
     #[quickcheck]
     fn test_decoding_random_data_will_not_panic(data: Vec<u8>) -> bool {
         let _ = decode_data(&data);
         let _ = decode_list_payload(&data);
+        let _ = validate_canonical_rlp(&data);
 
         true
     }
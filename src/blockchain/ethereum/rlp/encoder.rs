@@ -7,10 +7,11 @@
 //! Provides `EncodingItem` implementation for RLP.
 
 use super::core::RlpItemType;
-use super::encoding::{encode_payload_length, encode_single_value};
+use super::encoding::{encode_payload_length, encode_single_value, encode_uint_be_bytes};
 use crate::bigint::BigUint;
-use crate::tools::bytes::strip_leading_zeros;
-use crate::tools::codable::{Encodable, EncodingItem};
+use crate::tools::codable::{seal_codable, Encodable, EncodingItem};
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// The RLP encoding type which implements `EncodingItem`.
 pub struct RlpEncodingItem {
@@ -33,6 +34,9 @@ impl RlpEncodingItem {
     }
 }
 
+#[cfg(not(feature = "unstable-codable"))]
+impl crate::tools::codable::SealedItem for RlpEncodingItem {}
+
 impl EncodingItem for RlpEncodingItem {
     fn new() -> RlpEncodingItem {
         RlpEncodingItem {
@@ -41,23 +45,25 @@ impl EncodingItem for RlpEncodingItem {
     }
 
     fn take_data(&mut self) -> Vec<u8> {
-        std::mem::take(&mut self.encoded_data)
+        core::mem::take(&mut self.encoded_data)
     }
 }
 
+seal_codable!(RlpEncodingItem => u64, BigUint, &str);
+
+// Covers `Vec<u8>` as well.
+#[cfg(not(feature = "unstable-codable"))]
+impl<T> crate::tools::codable::Sealed<RlpEncodingItem> for Vec<T> {}
+
 impl Encodable<RlpEncodingItem> for u64 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
-        encoding_item.extend_encoded_data(&encode_single_value(strip_leading_zeros(
-            &self.to_be_bytes(),
-        )));
+        encoding_item.extend_encoded_data(&encode_uint_be_bytes(&self.to_be_bytes()));
     }
 }
 
 impl Encodable<RlpEncodingItem> for BigUint {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
-        encoding_item.extend_encoded_data(&encode_single_value(strip_leading_zeros(
-            &self.to_be_bytes(),
-        )));
+        encoding_item.extend_encoded_data(&encode_uint_be_bytes(&self.to_be_bytes()));
     }
 }
 
@@ -90,7 +96,7 @@ impl Encodable<RlpEncodingItem> for Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{encode, Encodable};
     use devtools::path::integration_testing_data_path;
     use serde_json::Value;
@@ -100,11 +106,11 @@ mod tests {
     fn test_take_data_emptying_internal_data() {
         let mut encoding_item = RlpEncodingItem::new();
         12_u64.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "0c");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "0c");
 
         // Reuses `encoding_item`
         19_u64.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "13");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "13");
     }
 
     #[test]
@@ -116,7 +122,7 @@ mod tests {
         for value in value_vec {
             let input = &value["in"];
             let output_hex = value["out"].as_str().unwrap();
-            let output = hex_to_bytes(output_hex).unwrap();
+            let output = hex::decode(output_hex).unwrap();
 
             // Encodes a `serde_json::Value`.
             // See the `Encodable` implementation for `serde_json::Value` below.
@@ -125,6 +131,8 @@ mod tests {
         }
     }
 
+    seal_codable!(RlpEncodingItem => Value);
+
     impl Encodable<RlpEncodingItem> for Value {
         fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
             match self {
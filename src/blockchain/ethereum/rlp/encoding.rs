@@ -8,13 +8,37 @@
 //! https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp
 
 use super::core::{RlpItemType, MAX_BYTE_LENGTH_OF_PAYLOAD_BYTE_LENGTH};
+use crate::bigint::BigInt;
 use crate::tools::bytes::strip_leading_zeros;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// Encodes `payload` as a single value item.
 pub(crate) fn encode_single_value(payload: &[u8]) -> Vec<u8> {
     encode_item(RlpItemType::SingleValue, payload)
 }
 
+/// Encodes the unsigned integer `n` as a single value item.
+///
+/// The payload is the big-endian representation of `n` without leading zeros, zero being
+/// the empty string, e.g., 0 is `[0x80]`, 0x7f is `[0x7f]` and 0x80 is `[0x81, 0x80]`.
+/// Integer fields must be encoded this way, while byte strings (e.g., the data of a
+/// transaction) keep their leading zeros and go through `encode_single_value` as they are.
+///
+/// # Panics
+///
+/// Panics if `n` is negative.
+pub fn encode_uint_bigint(n: &BigInt) -> Vec<u8> {
+    assert!(!n.is_sign_negative(), "RLP integers can't be negative");
+    encode_uint_be_bytes(&n.to_be_bytes())
+}
+
+/// Encodes the unsigned integer in big-endian `bytes` as a single value item,
+/// see [`encode_uint_bigint`].
+pub(crate) fn encode_uint_be_bytes(bytes: &[u8]) -> Vec<u8> {
+    encode_single_value(strip_leading_zeros(bytes))
+}
+
 /// Encodes `payload` as a single value item or a list item.
 /// The item type is specified by `item_type`.
 pub(crate) fn encode_item(item_type: RlpItemType, payload: &[u8]) -> Vec<u8> {
@@ -12,5 +12,6 @@ pub mod decoder;
 pub mod decoding;
 pub mod encoder;
 pub mod encoding;
+pub mod schema;
 
 pub use self::core::RlpItemType;
@@ -0,0 +1,177 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements typed RLP decoding with a schema describing the expected items.
+
+use super::core::RlpItemType;
+use super::decoding::{decode_data, decode_list_payload, RlpDataDecodingError};
+use crate::bigint::{BigInt, Sign};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Describes the shape of a RLP item.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RlpSchema {
+    /// A single value interpreted as a big-endian unsigned integer.
+    Uint,
+    /// A single value kept as bytes.
+    Bytes,
+    /// A list of exactly the items described.
+    List(Vec<RlpSchema>),
+}
+
+/// A RLP item decoded with a `RlpSchema`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RlpValue<'a> {
+    Uint(BigInt),
+    Bytes(&'a [u8]),
+    List(Vec<RlpValue<'a>>),
+}
+
+/// Decodes the RLP item `data` as described by `schema`.
+///
+/// Returns the items of the list if `schema` is a `RlpSchema::List`, and the single value
+/// alone otherwise.
+pub fn decode_schema<'a>(
+    data: &'a [u8],
+    schema: &RlpSchema,
+) -> Result<Vec<RlpValue<'a>>, RlpDataDecodingError> {
+    let (item_type, payload) = decode_data(data)?;
+    match decode_item(item_type, payload, schema)? {
+        RlpValue::List(values) => Ok(values),
+        value => Ok(vec![value]),
+    }
+}
+
+fn decode_item<'a>(
+    item_type: RlpItemType,
+    payload: &'a [u8],
+    schema: &RlpSchema,
+) -> Result<RlpValue<'a>, RlpDataDecodingError> {
+    match (schema, item_type) {
+        (RlpSchema::Uint, RlpItemType::SingleValue) => {
+            Ok(RlpValue::Uint(if payload.is_empty() {
+                BigInt::zero()
+            } else {
+                BigInt::from_be_bytes(payload, Sign::Positive)
+            }))
+        }
+        (RlpSchema::Bytes, RlpItemType::SingleValue) => Ok(RlpValue::Bytes(payload)),
+        (RlpSchema::List(schemas), RlpItemType::List) => {
+            let items = decode_list_payload(payload)?;
+            if items.len() != schemas.len() {
+                return Err(RlpDataDecodingError::InvalidFormat);
+            }
+
+            let mut values = Vec::with_capacity(items.len());
+            for ((item_type, payload), schema) in items.into_iter().zip(schemas) {
+                values.push(decode_item(item_type, payload, schema)?);
+            }
+            Ok(RlpValue::List(values))
+        }
+        (RlpSchema::List(_), found) => Err(RlpDataDecodingError::UnexpectedItemType {
+            expected: RlpItemType::List,
+            found,
+        }),
+        (_, found) => Err(RlpDataDecodingError::UnexpectedItemType {
+            expected: RlpItemType::SingleValue,
+            found,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_decode_schema() {
+        // [ "cat", 1024, [ [], "" ] ]
+        let data = hex::decode("ca83636174820400c2c080").unwrap();
+        let schema = RlpSchema::List(vec![
+            RlpSchema::Bytes,
+            RlpSchema::Uint,
+            RlpSchema::List(vec![RlpSchema::List(vec![]), RlpSchema::Uint]),
+        ]);
+        assert_eq!(
+            decode_schema(&data, &schema).unwrap(),
+            [
+                RlpValue::Bytes(b"cat"),
+                RlpValue::Uint(BigInt::from(1024)),
+                RlpValue::List(vec![RlpValue::List(vec![]), RlpValue::Uint(BigInt::zero())]),
+            ]
+        );
+
+        // single values
+        let data = hex::decode("820400").unwrap();
+        assert_eq!(
+            decode_schema(&data, &RlpSchema::Uint).unwrap(),
+            [RlpValue::Uint(BigInt::from(1024))]
+        );
+        assert_eq!(
+            decode_schema(&data, &RlpSchema::Bytes).unwrap(),
+            [RlpValue::Bytes(&[0x04, 0x00])]
+        );
+    }
+
+    #[test]
+    fn test_decode_schema_mismatch() {
+        // (hex, schema)
+        let data = [
+            // a list for a single value
+            ("c0", RlpSchema::Uint),
+            ("c0", RlpSchema::Bytes),
+            // a single value for a list
+            ("80", RlpSchema::List(vec![])),
+            // too few items
+            (
+                "c180",
+                RlpSchema::List(vec![RlpSchema::Uint, RlpSchema::Uint]),
+            ),
+            // too many items
+            ("c28080", RlpSchema::List(vec![RlpSchema::Uint])),
+            // mismatched nested item
+            (
+                "c280c0",
+                RlpSchema::List(vec![RlpSchema::Uint, RlpSchema::Bytes]),
+            ),
+            // malformed data
+            ("", RlpSchema::Bytes),
+            ("c3808080", RlpSchema::List(vec![RlpSchema::Uint])),
+        ];
+        for (hex, schema) in data {
+            let data = hex::decode(hex).unwrap();
+            assert!(decode_schema(&data, &schema).is_err(), "{hex}");
+        }
+
+        // (hex, schema, expected, found)
+        let data = [
+            (
+                "c0",
+                RlpSchema::Uint,
+                RlpItemType::SingleValue,
+                RlpItemType::List,
+            ),
+            (
+                "c180",
+                RlpSchema::List(vec![RlpSchema::List(vec![])]),
+                RlpItemType::List,
+                RlpItemType::SingleValue,
+            ),
+        ];
+        for (hex, schema, expected_item_type, found_item_type) in data {
+            let data = hex::decode(hex).unwrap();
+            match decode_schema(&data, &schema) {
+                Err(RlpDataDecodingError::UnexpectedItemType { expected, found }) => {
+                    assert_eq!(expected, expected_item_type);
+                    assert_eq!(found, found_item_type);
+                }
+                result => panic!("{hex}: {result:?}"),
+            }
+        }
+    }
+}
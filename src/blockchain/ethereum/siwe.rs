@@ -0,0 +1,683 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the messages of EIP-4361, Sign-In with Ethereum (SIWE), which are signed
+//! with "personal_sign", see [`personal_sign_recover`].
+//!
+//! A message looks like:
+//!
+//! ```text
+//! example.com wants you to sign in with your Ethereum account:
+//! 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+//!
+//! I accept the ExampleOrg Terms of Service: https://example.com/tos
+//!
+//! URI: https://example.com/login
+//! Version: 1
+//! Chain ID: 1
+//! Nonce: 32891756
+//! Issued At: 2021-09-30T16:25:24Z
+//! Resources:
+//! - ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+//! - https://example.com/my-web2-claim.json
+//! ```
+//!
+//! Only the syntax is validated. Checking the domain, the nonce and the time fields
+//! against the session is up to the relying party.
+//!
+//! See: https://eips.ethereum.org/EIPS/eip-4361
+
+use crate::bigint::BigUint;
+use crate::blockchain::ethereum::eip191::{
+    personal_sign_recover, SignatureValidationError, PERSONAL_SIGNATURE_BYTE_LENGTH,
+};
+use crate::blockchain::ethereum::types::{Address, ChainId};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+const RESOURCE_PREFIX: &str = "- ";
+
+/// The only version EIP-4361 defines.
+pub const SIWE_VERSION: u8 = 1;
+const MIN_NONCE_LENGTH: usize = 8;
+
+/// A Sign-In with Ethereum message.
+///
+/// `Display` formats the message to sign, which [`SiweMessage::parse`] parses back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// The RFC 3986 authority requesting the signing, e.g., "example.com".
+    pub domain: String,
+    /// The address signing in, formatted with the EIP-55 checksum.
+    pub address: Address,
+    /// A human-readable assertion, a single line.
+    pub statement: Option<String>,
+    /// The RFC 3986 URI referring to the resource that is the subject of the signing.
+    pub uri: String,
+    /// Must be 1.
+    pub version: u8,
+    pub chain_id: ChainId,
+    /// At least 8 alphanumeric characters, to prevent replay attacks.
+    pub nonce: String,
+    /// The RFC 3339 date-time of issuing, e.g., "2021-09-30T16:25:24Z".
+    pub issued_at: String,
+    /// The RFC 3339 date-time the signed message expires at.
+    pub expiration_time: Option<String>,
+    /// The RFC 3339 date-time the signed message becomes valid at.
+    pub not_before: Option<String>,
+    /// A system-specific identifier of the sign-in request.
+    pub request_id: Option<String>,
+    /// The RFC 3986 URIs the user wishes to have resolved as part of the authentication.
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Parses `message`, the lines of which are separated by "\n".
+    ///
+    /// The optional scheme before the domain, e.g., `https://example.com wants you to ...`,
+    /// isn't supported.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if `message` doesn't follow the ABNF of EIP-4361, or if any
+    /// field is invalid, e.g., the address isn't EIP-55 checksummed.
+    pub fn parse(message: &str) -> Result<SiweMessage, SiweParseError> {
+        let mut lines = Lines::new(message);
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER_SUFFIX))
+            .filter(|domain| is_authority(domain))
+            .ok_or(SiweParseError::InvalidHeader)?;
+
+        let address_line = lines.next().ok_or(SiweParseError::InvalidAddress)?;
+        let address = Address::try_from(address_line)
+            .ok()
+            .filter(|address| address.to_string() == address_line)
+            .ok_or(SiweParseError::InvalidAddress)?;
+
+        lines.expect_empty()?;
+        let statement = lines.next_if(|line| !line.is_empty()).map(str::to_string);
+        lines.expect_empty()?;
+
+        let uri = lines.field(URI_TAG)?;
+        if !is_uri(uri) {
+            return Err(SiweParseError::InvalidUri);
+        }
+        let version = lines.field(VERSION_TAG)?;
+        if version != SIWE_VERSION.to_string() {
+            return Err(SiweParseError::UnsupportedVersion);
+        }
+        let chain_id = lines.field(CHAIN_ID_TAG)?;
+        // Rejects the leading zeros: the message is signed as it is rendered, see
+        // `SiweMessage::verify_signature`.
+        if chain_id.is_empty()
+            || !chain_id.bytes().all(|c| c.is_ascii_digit())
+            || (chain_id.len() > 1 && chain_id.starts_with('0'))
+        {
+            return Err(SiweParseError::InvalidChainId);
+        }
+        let chain_id = BigUint::from_str_radix(chain_id, 10)
+            .map_err(|_| SiweParseError::InvalidChainId)?
+            .into();
+        let nonce = lines.field(NONCE_TAG)?;
+        if nonce.len() < MIN_NONCE_LENGTH || !nonce.bytes().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweParseError::InvalidNonce);
+        }
+        let issued_at = lines.field(ISSUED_AT_TAG)?;
+        let expiration_time = lines.optional_field(EXPIRATION_TIME_TAG);
+        let not_before = lines.optional_field(NOT_BEFORE_TAG);
+        for date_time in [Some(issued_at), expiration_time, not_before]
+            .into_iter()
+            .flatten()
+        {
+            if !is_rfc3339_date_time(date_time) {
+                return Err(SiweParseError::InvalidDateTime);
+            }
+        }
+        let request_id = lines.optional_field(REQUEST_ID_TAG);
+
+        let mut resources = vec![];
+        if lines.next_if(|line| line == RESOURCES_TAG).is_some() {
+            while let Some(line) = lines.next_if(|line| line.starts_with(RESOURCE_PREFIX)) {
+                let resource = &line[RESOURCE_PREFIX.len()..];
+                if !is_uri(resource) {
+                    return Err(SiweParseError::InvalidUri);
+                }
+                resources.push(resource.to_string());
+            }
+        }
+        lines.expect_end()?;
+
+        Ok(SiweMessage {
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri: uri.to_string(),
+            version: SIWE_VERSION,
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at: issued_at.to_string(),
+            expiration_time: expiration_time.map(str::to_string),
+            not_before: not_before.map(str::to_string),
+            request_id: request_id.map(str::to_string),
+            resources,
+        })
+    }
+
+    /// Recovers the address of the account which signed the message with "personal_sign".
+    ///
+    /// `signature` is in the `r ‖ s ‖ v` format, where `v` is either 27/28 or 0/1.
+    ///
+    /// The sign-in is valid only if the recovered address is `self.address`. A mismatch
+    /// isn't an error here: for a contract wallet, validate the signature with EIP-1271
+    /// instead, see [`crate::blockchain::ethereum::eip1271`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if `signature` is malformed or no public key can be recovered.
+    pub fn verify_signature(
+        &self,
+        signature: &[u8; PERSONAL_SIGNATURE_BYTE_LENGTH],
+    ) -> Result<Address, SignatureValidationError> {
+        personal_sign_recover(self.to_string().as_bytes(), signature)
+    }
+}
+
+/// Formats the message to sign, in the format of EIP-4361.
+impl Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{HEADER_SUFFIX}", self.domain)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{}", self.version)?;
+        writeln!(
+            f,
+            "{CHAIN_ID_TAG}{}",
+            String::from_utf8(self.chain_id.0.as_bigint().to_decimal_bytes()).unwrap()
+        )?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in &self.resources {
+                write!(f, "\n{RESOURCE_PREFIX}{resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The lines of a message, tracking the 1-based number of the current line for errors.
+struct Lines<'a> {
+    lines: std::iter::Peekable<std::str::Split<'a, char>>,
+    line_number: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(message: &'a str) -> Self {
+        Lines {
+            lines: message.split('\n').peekable(),
+            line_number: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.next_if(|_| true)
+    }
+
+    fn next_if(&mut self, predicate: impl FnOnce(&str) -> bool) -> Option<&'a str> {
+        let line = self.lines.next_if(|line| predicate(line))?;
+        self.line_number += 1;
+        Some(line)
+    }
+
+    fn unexpected_line(&self) -> SiweParseError {
+        SiweParseError::UnexpectedLine(self.line_number + 1)
+    }
+
+    fn expect_empty(&mut self) -> Result<(), SiweParseError> {
+        self.next_if(str::is_empty)
+            .map(|_| ())
+            .ok_or_else(|| self.unexpected_line())
+    }
+
+    fn expect_end(&mut self) -> Result<(), SiweParseError> {
+        match self.lines.peek() {
+            Some(_) => Err(self.unexpected_line()),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the value of the required field of `tag`.
+    fn field(&mut self, tag: &str) -> Result<&'a str, SiweParseError> {
+        self.optional_field(tag)
+            .ok_or_else(|| self.unexpected_line())
+    }
+
+    /// Returns the value of the field of `tag` if it's the next line.
+    fn optional_field(&mut self, tag: &str) -> Option<&'a str> {
+        self.next_if(|line| line.starts_with(tag))
+            .map(|line| &line[tag.len()..])
+    }
+}
+
+/// Tests if `s` is plausibly an RFC 3986 authority: "[userinfo@]host[:port]".
+fn is_authority(s: &str) -> bool {
+    !s.is_empty() && !s.contains("://") && !s.contains(|c: char| c.is_whitespace() || c == '/')
+}
+
+/// Tests if `s` is plausibly an RFC 3986 URI: a scheme, ":", and a non-empty remainder
+/// without whitespace.
+fn is_uri(s: &str) -> bool {
+    let Some((scheme, remainder)) = s.split_once(':') else {
+        return false;
+    };
+    let mut scheme_chars = scheme.chars();
+    scheme_chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme_chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        && !remainder.is_empty()
+        && !remainder.contains(char::is_whitespace)
+}
+
+/// Tests if `s` is an RFC 3339 "date-time", e.g., "2021-09-30T16:25:24.000+08:00".
+fn is_rfc3339_date_time(s: &str) -> bool {
+    let s = s.as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Option<u32> {
+        let digits = s.get(range)?;
+        digits.iter().try_fold(0, |n, &c| {
+            c.is_ascii_digit().then(|| n * 10 + (c - b'0') as u32)
+        })
+    };
+    let separators_at = |separators: &[(usize, &[u8])]| {
+        separators
+            .iter()
+            .all(|(i, expected)| s.get(*i).is_some_and(|c| expected.contains(c)))
+    };
+
+    // full-date "T" partial-time: "YYYY-MM-DDTHH:MM:SS"
+    let is_valid = number(0..4).is_some()
+        && number(5..7).is_some_and(|month| (1..=12).contains(&month))
+        && number(8..10).is_some_and(|day| (1..=31).contains(&day))
+        && number(11..13).is_some_and(|hour| hour <= 23)
+        && number(14..16).is_some_and(|minute| minute <= 59)
+        // 60 for leap seconds
+        && number(17..19).is_some_and(|second| second <= 60)
+        && separators_at(&[(4, b"-"), (7, b"-"), (10, b"Tt"), (13, b":"), (16, b":")]);
+    if !is_valid {
+        return false;
+    }
+
+    // ["." 1*DIGIT] time-offset
+    let mut offset = 19;
+    if s.get(offset) == Some(&b'.') {
+        let digits_len = s[offset + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits_len == 0 {
+            return false;
+        }
+        offset += 1 + digits_len;
+    }
+    match s.get(offset) {
+        Some(b'Z' | b'z') => s.len() == offset + 1,
+        Some(b'+' | b'-') => {
+            s.len() == offset + 6
+                && number(offset + 1..offset + 3).is_some_and(|hour| hour <= 23)
+                && number(offset + 4..offset + 6).is_some_and(|minute| minute <= 59)
+                && s[offset + 3] == b':'
+        }
+        _ => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SiweParseError {
+    /// The first line isn't "${domain} wants you to sign in with your Ethereum account:".
+    InvalidHeader,
+    /// The second line isn't an EIP-55 checksummed address.
+    InvalidAddress,
+    /// The 1-based line number of a line missing, out of order, or not expected.
+    UnexpectedLine(usize),
+    InvalidUri,
+    UnsupportedVersion,
+    InvalidChainId,
+    InvalidNonce,
+    InvalidDateTime,
+}
+
+impl Display for SiweParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SiweParseError::InvalidHeader => write!(f, "invalid header"),
+            SiweParseError::InvalidAddress => write!(f, "invalid address"),
+            SiweParseError::UnexpectedLine(line_number) => {
+                write!(f, "unexpected line {line_number}")
+            }
+            SiweParseError::InvalidUri => write!(f, "invalid URI"),
+            SiweParseError::UnsupportedVersion => write!(f, "unsupported version"),
+            SiweParseError::InvalidChainId => write!(f, "invalid chain ID"),
+            SiweParseError::InvalidNonce => write!(f, "invalid nonce"),
+            SiweParseError::InvalidDateTime => write!(f, "invalid date-time"),
+        }
+    }
+}
+
+impl Error for SiweParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    // The example of EIP-4361
+    const EXAMPLE_MESSAGE: &str = "\
+example.com wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ExampleOrg Terms of Service: https://example.com/tos
+
+URI: https://example.com/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn test_parse() {
+        let message = SiweMessage::parse(EXAMPLE_MESSAGE).unwrap();
+        assert_eq!(
+            message,
+            SiweMessage {
+                domain: "example.com".to_string(),
+                address: "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+                    .try_into()
+                    .unwrap(),
+                statement: Some(
+                    "I accept the ExampleOrg Terms of Service: https://example.com/tos"
+                        .to_string()
+                ),
+                uri: "https://example.com/login".to_string(),
+                version: 1,
+                chain_id: 1.into(),
+                nonce: "32891756".to_string(),
+                issued_at: "2021-09-30T16:25:24Z".to_string(),
+                expiration_time: None,
+                not_before: None,
+                request_id: None,
+                resources: vec![
+                    "ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/"
+                        .to_string(),
+                    "https://example.com/my-web2-claim.json".to_string(),
+                ],
+            }
+        );
+        assert_eq!(message.to_string(), EXAMPLE_MESSAGE);
+
+        // Renders the chain ID as it is parsed, which the signature signs.
+        for chain_id in ["0", "10", "11155111"] {
+            let s = EXAMPLE_MESSAGE.replace("Chain ID: 1", &format!("Chain ID: {chain_id}"));
+            assert_eq!(SiweMessage::parse(&s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_parse_optional_fields() {
+        // No statement, all the optional fields but the resources
+        let s = "\
+localhost:4361 wants you to sign in with your Ethereum account:
+0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826
+
+
+URI: http://localhost:4361
+Version: 1
+Chain ID: 11155111
+Nonce: ToTaLLyRanDOM
+Issued At: 2021-12-07T18:28:18.807+08:00
+Expiration Time: 2023-01-07T18:28:18.807-05:00
+Not Before: 2022-12-07T18:28:18.807Z
+Request ID: 200";
+        let message = SiweMessage::parse(s).unwrap();
+        assert_eq!(message.domain, "localhost:4361");
+        assert_eq!(message.statement, None);
+        assert_eq!(message.chain_id, 11155111.into());
+        assert_eq!(
+            message.expiration_time.as_deref(),
+            Some("2023-01-07T18:28:18.807-05:00")
+        );
+        assert_eq!(
+            message.not_before.as_deref(),
+            Some("2022-12-07T18:28:18.807Z")
+        );
+        assert_eq!(message.request_id.as_deref(), Some("200"));
+        assert!(message.resources.is_empty());
+        assert_eq!(message.to_string(), s);
+
+        // An empty request ID
+        let s = s.replace("Request ID: 200", "Request ID: ");
+        let message = SiweMessage::parse(&s).unwrap();
+        assert_eq!(message.request_id.as_deref(), Some(""));
+        assert_eq!(message.to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_err_cases() {
+        // (original line, replacement, error)
+        let data = [
+            (
+                "example.com wants",
+                "https://example.com wants",
+                SiweParseError::InvalidHeader,
+            ),
+            (
+                "example.com wants",
+                "example .com wants",
+                SiweParseError::InvalidHeader,
+            ),
+            (
+                "with your Ethereum account:",
+                "with your account:",
+                SiweParseError::InvalidHeader,
+            ),
+            (
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                SiweParseError::InvalidAddress,
+            ),
+            (
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc",
+                SiweParseError::InvalidAddress,
+            ),
+            (
+                "Cc2\n\nI accept",
+                "Cc2\nI accept",
+                SiweParseError::UnexpectedLine(3),
+            ),
+            ("tos\n\nURI", "tos\nURI", SiweParseError::UnexpectedLine(5)),
+            (
+                "tos\n\nURI",
+                "tos\n\n\nURI",
+                SiweParseError::UnexpectedLine(6),
+            ),
+            (
+                "URI: https://example.com/login",
+                "URI: example.com/login",
+                SiweParseError::InvalidUri,
+            ),
+            (
+                "Version: 1",
+                "Version: 2",
+                SiweParseError::UnsupportedVersion,
+            ),
+            (
+                "Chain ID: 1",
+                "Chain ID: 0x1",
+                SiweParseError::InvalidChainId,
+            ),
+            ("Chain ID: 1", "Chain ID: ", SiweParseError::InvalidChainId),
+            (
+                "Chain ID: 1",
+                "Chain ID: 01",
+                SiweParseError::InvalidChainId,
+            ),
+            (
+                "Nonce: 32891756",
+                "Nonce: 3289175",
+                SiweParseError::InvalidNonce,
+            ),
+            (
+                "Nonce: 32891756",
+                "Nonce: 3289175-6",
+                SiweParseError::InvalidNonce,
+            ),
+            (
+                "Issued At: 2021-09-30T16:25:24Z",
+                "Issued At: 2021-09-30 16:25:24Z",
+                SiweParseError::InvalidDateTime,
+            ),
+            (
+                "Issued At: 2021-09-30T16:25:24Z",
+                "Issued At: 2021-09-30T16:25:24Z\nExpiration Time: 2021-09-30",
+                SiweParseError::InvalidDateTime,
+            ),
+            // out of order
+            (
+                "Version: 1\nChain ID: 1",
+                "Chain ID: 1\nVersion: 1",
+                SiweParseError::UnexpectedLine(7),
+            ),
+            (
+                "Issued At: 2021-09-30T16:25:24Z",
+                "Request ID: 1\nIssued At: 2021-09-30T16:25:24Z",
+                SiweParseError::UnexpectedLine(10),
+            ),
+            (
+                "- https://example.com/my-web2-claim.json",
+                "- https://example.com/my-web2-claim.json\n",
+                SiweParseError::UnexpectedLine(14),
+            ),
+            (
+                "- https://example.com/my-web2-claim.json",
+                "- example.com/my-web2-claim.json",
+                SiweParseError::InvalidUri,
+            ),
+        ];
+        for (from, to, err) in data {
+            assert!(EXAMPLE_MESSAGE.contains(from));
+            let message = EXAMPLE_MESSAGE.replacen(from, to, 1);
+            assert_eq!(SiweMessage::parse(&message), Err(err), "{to}");
+        }
+
+        // missing fields
+        let message = &EXAMPLE_MESSAGE[..EXAMPLE_MESSAGE.find("\nNonce").unwrap()];
+        assert_eq!(
+            SiweMessage::parse(message),
+            Err(SiweParseError::UnexpectedLine(9))
+        );
+        assert_eq!(SiweMessage::parse(""), Err(SiweParseError::InvalidHeader));
+    }
+
+    #[test]
+    fn test_rfc3339_date_time() {
+        let valid = [
+            "2021-09-30T16:25:24Z",
+            "2021-09-30t16:25:24z",
+            "1985-04-12T23:20:50.52Z",
+            "1996-12-19T16:39:57-08:00",
+            "1990-12-31T23:59:60Z",
+            "1937-01-01T12:00:27.87+00:20",
+        ];
+        for s in valid {
+            assert!(is_rfc3339_date_time(s), "{s}");
+        }
+
+        let invalid = [
+            "",
+            "2021-09-30",
+            "2021-09-30T16:25:24",
+            "2021-13-30T16:25:24Z",
+            "2021-09-00T16:25:24Z",
+            "2021-09-30T24:25:24Z",
+            "2021-09-30T16:25:24.Z",
+            "2021-09-30T16:25:24+0800",
+            "2021-09-30T16:25:24Z ",
+            "2021/09/30T16:25:24Z",
+        ];
+        for s in invalid {
+            assert!(!is_rfc3339_date_time(s), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        // The example message signed by the account of the private key keccak256("cow"),
+        // c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4. The signature is
+        // checked outside the crate, with `openssl pkeyutl -verify` on the "personal_sign"
+        // hash.
+        // TODO: Replace with a published vector of spruceid/siwe once one is at hand.
+        let s = EXAMPLE_MESSAGE.replace(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+        );
+        let mut message = SiweMessage::parse(&s).unwrap();
+        let signature: [u8; 65] = hex::decode_to_array(concat!(
+            "72ce5ca55f509afb0fe1d7d701acf60de66e5d6b5c3561781908c01f2105c7ad",
+            "5ed216c06f664dfb7d988c930d451359845290b0e7e14f99b0639943e2e335ce",
+            "1b",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            message.verify_signature(&signature),
+            Ok(message.address.clone())
+        );
+
+        // A tampered message recovers another address.
+        message.nonce = "32891757".to_string();
+        assert_ne!(
+            message.verify_signature(&signature),
+            Ok(message.address.clone())
+        );
+
+        let mut invalid_v = signature;
+        invalid_v[64] = 29;
+        assert_eq!(
+            message.verify_signature(&invalid_v),
+            Err(SignatureValidationError::InvalidRecoveryId)
+        );
+    }
+}
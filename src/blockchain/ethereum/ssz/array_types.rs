@@ -9,8 +9,8 @@
 use super::core::SszType;
 use super::core::BYTES_PER_LENGTH_OFFSET;
 use super::decoder::{SszDataDecodingError, SszDecodingItem};
-use super::encoder::SszEncodingItem;
-use crate::tools::codable::{Decodable, DecodingItem, EncodingItem};
+use super::encoder::write_elements;
+use crate::tools::codable::{Decodable, DecodingItem};
 use std::fmt::Debug;
 
 // TODO: implements optimization for `[u8; N]`
@@ -27,19 +27,13 @@ impl<T: SszType + Debug, const N: usize> SszType for [T; N] {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut encoding_item = SszEncodingItem::new();
-
-        if T::size().is_none() {
-            for value in self {
-                encoding_item.encode_variable_size_data(&value.to_bytes());
-            }
-        } else {
-            for value in self {
-                encoding_item.encode_fixed_size_data(&value.to_bytes());
-            }
-        }
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
 
-        encoding_item.take_data()
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        write_elements(self.as_slice(), bytes);
     }
 
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -53,13 +47,17 @@ impl<T: SszType + Debug, const N: usize> SszType for [T; N] {
             }
             Some(size) => {
                 let n = u32::try_from(N).unwrap();
-                if bytes.len() != size.checked_mul(n).unwrap() as usize {
-                    return Err(SszDataDecodingError::InvalidFormat);
+                let expected_len = size.checked_mul(n).unwrap() as usize;
+                if bytes.len() != expected_len {
+                    return Err(SszDataDecodingError::from_length_mismatch(
+                        expected_len,
+                        bytes.len(),
+                    ));
                 }
                 let mut objects = Vec::with_capacity(N);
-                for chunk in bytes.chunks_exact(size as usize) {
+                for (i, chunk) in bytes.chunks_exact(size as usize).enumerate() {
                     let decoding_item = SszDecodingItem::new_from_data(chunk).unwrap();
-                    let object = T::decode_from(&decoding_item)?;
+                    let object = T::decode_from(&decoding_item).map_err(|e| e.nested(i))?;
                     objects.push(object);
                 }
                 Ok(objects.try_into().unwrap())
@@ -88,7 +86,10 @@ pub(crate) fn decode_variable_size_objects_from_bytes<T: SszType>(
     let bytes_len =
         u32::try_from(bytes.len()).map_err(|_| SszDataDecodingError::InvalidFormat)?;
     if bytes_len < headers_len {
-        return Err(SszDataDecodingError::InvalidFormat);
+        return Err(SszDataDecodingError::TruncatedFixedField {
+            expected: headers_len as usize,
+            available: bytes_len as usize,
+        });
     }
 
     // Creates an array of offsets,
@@ -102,9 +103,22 @@ pub(crate) fn decode_variable_size_objects_from_bytes<T: SszType>(
     {
         let decoding_item = SszDecodingItem::new_from_data(chunk).unwrap();
         let offset = u32::decode_from(&decoding_item)?;
-        if offset < previous_offset || offset > bytes_len {
+        if offset > bytes_len {
+            return Err(SszDataDecodingError::OffsetOutOfBounds {
+                offset,
+                limit: bytes_len,
+            });
+        }
+        // The first offset marks the end of the headers, implying the number of objects.
+        if offsets.is_empty() && offset != headers_len {
             return Err(SszDataDecodingError::InvalidFormat);
         }
+        if offset < previous_offset {
+            return Err(SszDataDecodingError::OffsetsNotMonotonic {
+                previous: previous_offset,
+                current: offset,
+            });
+        }
         offsets.push(offset);
         previous_offset = offset;
     }
@@ -116,14 +130,14 @@ pub(crate) fn decode_variable_size_objects_from_bytes<T: SszType>(
     // Iterates `offsets` over all contiguous and overlapping windows of length 2,
     // and creates the objects.
     let mut objects = Vec::with_capacity(objects_number as usize);
-    for w in offsets.windows(2) {
+    for (i, w) in offsets.windows(2).enumerate() {
         let offset1 = w[0];
         let offset2 = w[1];
         debug_assert!(offset2 >= offset1);
         let decoding_item =
             SszDecodingItem::new_from_data(&bytes[(offset1 as usize)..(offset2 as usize)])
                 .unwrap();
-        let object = T::decode_from(&decoding_item)?;
+        let object = T::decode_from(&decoding_item).map_err(|e| e.nested(i))?;
         objects.push(object);
     }
 
@@ -134,7 +148,7 @@ pub(crate) fn decode_variable_size_objects_from_bytes<T: SszType>(
 mod tests {
     use crate::blockchain::ethereum::ssz::decoder::{SszDataDecodingError, SszDecodingItem};
     use crate::blockchain::ethereum::ssz::encoder::SszEncodingItem;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
 
     /// Tests fixed size element `u8`
@@ -145,7 +159,7 @@ mod tests {
         let value = [0_u8, 1, 2, 3, 4, 5, 6];
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "00010203040506"
         );
     }
@@ -154,11 +168,20 @@ mod tests {
     fn test_byte_array_decoding() {
         let data = [
             ("00010203040506", Ok([0_u8, 1, 2, 3, 4, 5, 6])),
-            ("0000010203040506", Err(SszDataDecodingError::InvalidFormat)), // invalid length
-            ("00", Err(SszDataDecodingError::InvalidFormat)),
+            (
+                "0000010203040506",
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
+            (
+                "00",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 7,
+                    available: 1,
+                }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(<[u8; 7]>::decode_from(&decoding_item), result);
         }
@@ -180,7 +203,7 @@ mod tests {
         ];
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "000102030405060001020304050600010203040506"
         );
     }
@@ -196,15 +219,59 @@ mod tests {
                     [0_u8, 1, 2, 3, 4, 5, 6],
                 ]),
             ),
-            ("0001020304050600", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "0001020304050600",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 21,
+                    available: 8,
+                }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(<[[u8; 7]; 3]>::decode_from(&decoding_item), result);
         }
     }
 
+    #[test]
+    fn test_array_of_byte_array_decoding_wrong_element_count() {
+        let element = [0_u8, 1, 2, 3, 4, 5, 6];
+        // (elements, result)
+        let data = [
+            (
+                vec![element; 2],
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 21,
+                    available: 14,
+                }),
+            ),
+            (vec![element; 3], Ok([element; 3])),
+            (
+                vec![element; 4],
+                Err(SszDataDecodingError::TrailingBytes { count: 7 }),
+            ),
+        ];
+        for (elements, result) in data {
+            let data = elements.concat();
+            let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+            assert_eq!(<[[u8; 7]; 3]>::decode_from(&decoding_item), result);
+        }
+    }
+
+    #[test]
+    fn test_array_of_bool_decoding_nested_error() {
+        let data = hex::decode("000102").unwrap();
+        let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+        assert_eq!(
+            <[bool; 3]>::decode_from(&decoding_item),
+            Err(SszDataDecodingError::NestedError {
+                element_index: 2,
+                source: Box::new(SszDataDecodingError::InvalidFormat),
+            })
+        );
+    }
+
     /// Tests variable size element `Vec<u8>`
     #[test]
     fn test_array_of_bytes_encoding() {
@@ -221,7 +288,7 @@ mod tests {
         ];
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "0c000000130000001a000000000102030405060001020304050600010203040506"
         );
     }
@@ -237,12 +304,45 @@ mod tests {
                     vec![0_u8, 1, 2, 3, 4, 5, 6],
                 ]),
             ),
-            ("0c0000", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "0c0000",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 12,
+                    available: 3,
+                }),
+            ), // headers
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(<[Vec<u8>; 3]>::decode_from(&decoding_item), result);
         }
     }
+
+    #[test]
+    fn test_array_of_bytes_decoding_wrong_element_count() {
+        let element = vec![0_u8, 1, 2, 3, 4, 5, 6];
+        // The offsets of 2 and 4 elements imply the wrong number of elements.
+        for n in [2, 4] {
+            let mut encoding_item = SszEncodingItem::new();
+            vec![element.clone(); n].encode_to(&mut encoding_item);
+            let data = encoding_item.take_data();
+            let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+            assert_eq!(
+                <[Vec<u8>; 3]>::decode_from(&decoding_item),
+                Err(SszDataDecodingError::InvalidFormat),
+                "{n}"
+            );
+        }
+
+        // The first offset past the headers
+        let data =
+            hex::decode("10000000170000001e000000000102030405060001020304050600010203040506")
+                .unwrap();
+        let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+        assert_eq!(
+            <[Vec<u8>; 3]>::decode_from(&decoding_item),
+            Err(SszDataDecodingError::InvalidFormat)
+        );
+    }
 }
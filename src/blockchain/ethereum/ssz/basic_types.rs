@@ -21,8 +21,17 @@ macro_rules! impl_ssztype_for_unsigned_int {
                 self.to_le_bytes().into()
             }
 
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+
             fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
-                Ok(<$T>::from_le_bytes(bytes.try_into().map_err(|_| SszDataDecodingError::InvalidFormat)?))
+                Ok(<$T>::from_le_bytes(bytes.try_into().map_err(|_| {
+                    SszDataDecodingError::from_length_mismatch(
+                        std::mem::size_of::<$T>(),
+                        bytes.len(),
+                    )
+                })?))
             }
         }
     };
@@ -43,12 +52,12 @@ impl SszType for bool {
         (*self as u8).to_le_bytes().into()
     }
 
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
-        let n = u8::from_le_bytes(
-            bytes
-                .try_into()
-                .map_err(|_| SszDataDecodingError::InvalidFormat)?,
-        );
+        let n = u8::try_from_bytes(bytes)?;
         match n {
             0 => Ok(false),
             1 => Ok(true),
@@ -61,7 +70,7 @@ impl SszType for bool {
 mod tests {
     use crate::blockchain::ethereum::ssz::decoder::{SszDataDecodingError, SszDecodingItem};
     use crate::blockchain::ethereum::ssz::encoder::SszEncodingItem;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
 
     #[test]
@@ -72,7 +81,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -82,10 +91,13 @@ mod tests {
             ("00", Ok(false)),
             ("01", Ok(true)),
             ("02", Err(SszDataDecodingError::InvalidFormat)), // invalid value
-            ("0001", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "0001",
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(bool::decode_from(&decoding_item), result);
         }
@@ -100,7 +112,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -110,10 +122,13 @@ mod tests {
             ("00", Ok(0_u8)),
             ("01", Ok(1_u8)),
             ("ab", Ok(0xab_u8)),
-            ("0001", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "0001",
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(u8::decode_from(&decoding_item), result);
         }
@@ -127,7 +142,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -136,11 +151,20 @@ mod tests {
         let data = [
             ("0000", Ok(0_u16)),
             ("cdab", Ok(0xabcd_u16)),
-            ("01", Err(SszDataDecodingError::InvalidFormat)), // invalid length
-            ("000001", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "01",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 2,
+                    available: 1,
+                }),
+            ),
+            (
+                "000001",
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(u16::decode_from(&decoding_item), result);
         }
@@ -154,7 +178,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -163,11 +187,20 @@ mod tests {
         let data = [
             ("00000000", Ok(0_u32)),
             ("67452301", Ok(0x01234567_u32)),
-            ("01", Err(SszDataDecodingError::InvalidFormat)), // invalid length
-            ("0000000001", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "01",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 4,
+                    available: 1,
+                }),
+            ),
+            (
+                "0000000001",
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(u32::decode_from(&decoding_item), result);
         }
@@ -184,7 +217,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -193,14 +226,20 @@ mod tests {
         let data = [
             ("0000000000000000", Ok(0_u64)),
             ("efcdab8967452301", Ok(0x0123456789abcdef_u64)),
-            ("01", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "01",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 8,
+                    available: 1,
+                }),
+            ),
             (
                 "000000000000000001",
-                Err(SszDataDecodingError::InvalidFormat),
-            ), // invalid length
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(u64::decode_from(&decoding_item), result);
         }
@@ -220,7 +259,7 @@ mod tests {
         for (value, hex) in data {
             let mut encoding_item = SszEncodingItem::new();
             value.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -232,14 +271,20 @@ mod tests {
                 "efcdab89674523018877665544332211",
                 Ok(0x11223344556677880123456789abcdef_u128),
             ),
-            ("01", Err(SszDataDecodingError::InvalidFormat)), // invalid length
+            (
+                "01",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 16,
+                    available: 1,
+                }),
+            ),
             (
                 "0000000000000000000000000000000001",
-                Err(SszDataDecodingError::InvalidFormat),
-            ), // invalid length
+                Err(SszDataDecodingError::TrailingBytes { count: 1 }),
+            ),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(u128::decode_from(&decoding_item), result);
         }
@@ -12,13 +12,134 @@ use super::decoder::{SszDataDecodingError, SszDecodingItem};
 use super::encoder::SszEncodingItem;
 use crate::tools::codable::{Decodable, DecodingItem};
 
+/// Asserts at compile time that the fields of a struct are laid out in the order they
+/// are listed, e.g., `ssz_assert_field_order!(MyContainer, a, b, c)`.
+///
+/// Lists the fields in the order `to_bytes` and `try_from_bytes` serialize them to
+/// catch the struct being reordered without updating the serialization.
+///
+/// The check compares `core::mem::offset_of!` of the fields, and fires only for
+/// `#[repr(C)]` structs. The compiler is free to reorder the fields of a struct of the
+/// default representation, for which the assertion may fail spuriously.
+///
+/// ```
+/// use lightcryptotools::ssz_assert_field_order;
+///
+/// #[repr(C)]
+/// struct Checkpoint {
+///     epoch: u64,
+///     root: [u8; 32],
+/// }
+///
+/// ssz_assert_field_order!(Checkpoint, epoch, root);
+/// ```
+///
+/// Listing the fields out of order fails to compile:
+///
+/// ```compile_fail
+/// # use lightcryptotools::ssz_assert_field_order;
+/// #
+/// # #[repr(C)]
+/// # struct Checkpoint {
+/// #     epoch: u64,
+/// #     root: [u8; 32],
+/// # }
+/// #
+/// ssz_assert_field_order!(Checkpoint, root, epoch);
+/// ```
+#[macro_export]
+macro_rules! ssz_assert_field_order {
+    ( $type:ty, $( $field:ident ),+ $(,)? ) => {
+        const _: () = {
+            let offsets = [ $( ::core::mem::offset_of!($type, $field) ),+ ];
+            let mut i = 1;
+            while i < offsets.len() {
+                assert!(
+                    offsets[i - 1] < offsets[i],
+                    "fields are not in serialization order"
+                );
+                i += 1;
+            }
+        };
+    };
+}
+
 impl SszEncodingItem {
     /// Encodes `object` as a container element.
     pub fn encode_as_container_element<T: SszType>(&mut self, object: &T) {
         if T::size().is_none() {
-            self.encode_variable_size_data(&object.to_bytes());
+            self.encode_variable_size_object(object);
         } else {
-            self.encode_fixed_size_data(&object.to_bytes());
+            self.encode_fixed_size_object(object);
+        }
+    }
+}
+
+/// Writes the elements of a container straight into the buffer of the enclosing item.
+///
+/// `write_container` runs the given closure twice: the first run appends the fixed-size
+/// elements and reserves the offsets of the variable-size ones, the second appends the
+/// variable-size elements and patches their offsets. Nested containers and lists write
+/// into the same buffer, so no level of nesting has a buffer of its own.
+///
+/// ```
+/// use lightcryptotools::blockchain::ethereum::ssz::{SszContainerWriter, SszType};
+///
+/// // class Pair(Container):
+/// //     A: uint16
+/// //     B: List[uint8, 16]
+/// let (a, b) = (0xabcd_u16, vec![1_u8, 2]);
+/// let mut bytes = vec![];
+/// SszContainerWriter::write_container(&mut bytes, |writer| {
+///     writer.write_element(&a);
+///     writer.write_element(&b);
+/// });
+/// assert_eq!(bytes, [0xcd, 0xab, 6, 0, 0, 0, 1, 2]);
+/// ```
+pub struct SszContainerWriter<'a> {
+    bytes: &'a mut Vec<u8>,
+    // Where the container starts in `bytes`, which its offsets are relative to.
+    start: usize,
+    // `None` in the first run. In the second run, the position in `bytes` of the header
+    // of the next element.
+    header_cursor: Option<usize>,
+}
+
+impl SszContainerWriter<'_> {
+    /// Appends the container whose elements `write_elements` writes to `bytes`.
+    ///
+    /// `write_elements` must write the same elements in the same order on both runs.
+    ///
+    /// This function will panic if an offset doesn't fit in `u32`.
+    pub fn write_container<F>(bytes: &mut Vec<u8>, write_elements: F)
+    where
+        F: Fn(&mut SszContainerWriter<'_>),
+    {
+        let start = bytes.len();
+        let mut writer = SszContainerWriter {
+            bytes,
+            start,
+            header_cursor: None,
+        };
+        write_elements(&mut writer);
+        writer.header_cursor = Some(start);
+        write_elements(&mut writer);
+    }
+
+    /// Writes `element` as the next container element.
+    pub fn write_element<T: SszType>(&mut self, element: &T) {
+        let offset_byte_len = BYTES_PER_LENGTH_OFFSET as usize;
+        match (self.header_cursor, T::size()) {
+            (None, Some(_)) => element.write_bytes(self.bytes),
+            (None, None) => self.bytes.resize(self.bytes.len() + offset_byte_len, 0),
+            (Some(cursor), Some(size)) => self.header_cursor = Some(cursor + size as usize),
+            (Some(cursor), None) => {
+                let offset = u32::try_from(self.bytes.len() - self.start).unwrap();
+                self.bytes[cursor..cursor + offset_byte_len]
+                    .copy_from_slice(&offset.to_le_bytes());
+                element.write_bytes(self.bytes);
+                self.header_cursor = Some(cursor + offset_byte_len);
+            }
         }
     }
 }
@@ -35,22 +156,20 @@ impl<'a> SszDecodingItem<'a> {
         sizes: &[Option<u32>],
     ) -> Result<Vec<Self>, SszDataDecodingError> {
         // Calculates the "headers length" in bytes
-        let mut headers_len = 0;
+        let mut headers_len: usize = 0;
         for &size in sizes {
-            match size {
-                None => {
-                    headers_len += BYTES_PER_LENGTH_OFFSET;
-                }
-                Some(size) => {
-                    headers_len += size;
-                }
-            }
+            headers_len += size.unwrap_or(BYTES_PER_LENGTH_OFFSET) as usize;
         }
         let bytes_len =
             u32::try_from(self.data.len()).map_err(|_| SszDataDecodingError::InvalidFormat)?;
-        if bytes_len < headers_len {
-            return Err(SszDataDecodingError::InvalidFormat);
+        if (bytes_len as usize) < headers_len {
+            return Err(SszDataDecodingError::TruncatedFixedField {
+                expected: headers_len,
+                available: bytes_len as usize,
+            });
         }
+        // Not larger than `bytes_len`
+        let headers_len = headers_len as u32;
 
         let mut items = Vec::with_capacity(sizes.len());
 
@@ -68,6 +187,7 @@ impl<'a> SszDecodingItem<'a> {
         // From the elements' "natural order" perspective, this is the header starting point of
         // the "next" container item.
         let mut header_cursor_rev = headers_len;
+        let mut has_variable_size_element = false;
         for &size in sizes.iter().rev() {
             match size {
                 None => {
@@ -78,9 +198,22 @@ impl<'a> SszDecodingItem<'a> {
                     )
                     .unwrap();
                     let offset = u32::decode_from(&decoding_item)?;
+                    if offset > bytes_len {
+                        return Err(SszDataDecodingError::OffsetOutOfBounds {
+                            offset,
+                            limit: bytes_len,
+                        });
+                    }
                     if offset > previous_offset_rev {
-                        return Err(SszDataDecodingError::InvalidFormat);
+                        // Iterating backwards, `previous_offset_rev` is the offset after
+                        // `offset` in the serialization order.
+                        return Err(SszDataDecodingError::OffsetsNotMonotonic {
+                            previous: offset,
+                            current: previous_offset_rev,
+                        });
                     }
+                    // An element serialized to zero bytes has the same offset as the next
+                    // one, or `bytes_len` for the last element, and gets an empty slice.
                     let decoding_item = SszDecodingItem::new_from_data(
                         &self.data[(offset as usize)..(previous_offset_rev as usize)],
                     )
@@ -89,11 +222,15 @@ impl<'a> SszDecodingItem<'a> {
 
                     header_cursor_rev -= BYTES_PER_LENGTH_OFFSET;
                     previous_offset_rev = offset;
+                    has_variable_size_element = true;
                 }
                 Some(size) => {
                     // Fixed-size element.
                     if size > header_cursor_rev {
-                        return Err(SszDataDecodingError::InvalidFormat);
+                        return Err(SszDataDecodingError::TruncatedFixedField {
+                            expected: size as usize,
+                            available: header_cursor_rev as usize,
+                        });
                     }
                     let decoding_item = SszDecodingItem::new_from_data(
                         &self.data
@@ -106,6 +243,21 @@ impl<'a> SszDecodingItem<'a> {
             }
         }
 
+        // The first variable-size element starts right after the fixed-size part, otherwise
+        // its data would overlap the headers or leave a gap.
+        if has_variable_size_element && previous_offset_rev != headers_len {
+            return Err(SszDataDecodingError::FirstOffsetMismatch {
+                offset: previous_offset_rev,
+                expected: headers_len,
+            });
+        }
+        // Without a variable-size element, the container is the fixed-size part only.
+        if !has_variable_size_element && bytes_len != headers_len {
+            return Err(SszDataDecodingError::TrailingBytes {
+                count: (bytes_len - headers_len) as usize,
+            });
+        }
+
         items.reverse();
         Ok(items)
     }
@@ -113,10 +265,11 @@ impl<'a> SszDecodingItem<'a> {
 
 #[cfg(test)]
 mod tests {
+    use super::SszContainerWriter;
     use crate::blockchain::ethereum::ssz::core::SszType;
     use crate::blockchain::ethereum::ssz::decoder::{SszDataDecodingError, SszDecodingItem};
     use crate::blockchain::ethereum::ssz::encoder::SszEncodingItem;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
 
     #[test]
@@ -132,7 +285,7 @@ mod tests {
             b: 0x0123,
         };
         value.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "67452301");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "67452301");
     }
 
     #[test]
@@ -145,29 +298,37 @@ mod tests {
             }),
         )];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(SmallTestStruct::decode_from(&decoding_item), result);
         }
     }
 
     #[derive(Debug, PartialEq, Eq)]
+    #[repr(C)]
     struct SmallTestStruct {
         a: u16,
         b: u16,
     }
 
+    ssz_assert_field_order!(SmallTestStruct, a, b);
+
     impl SszType for SmallTestStruct {
         fn size() -> Option<u32> {
             Some(u16::size().unwrap() + u16::size().unwrap())
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.encode_as_container_element(&self.b);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -191,14 +352,14 @@ mod tests {
         let mut encoding_item = SszEncodingItem::new();
         let value = SingleFieldTestStruct { a: 0xab };
         value.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "ab");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "ab");
     }
 
     #[test]
     fn test_single_field_test_struct_decoding() {
         let data = [("ab", Ok(SingleFieldTestStruct { a: 0xab }))];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(SingleFieldTestStruct::decode_from(&decoding_item), result);
         }
@@ -215,10 +376,15 @@ mod tests {
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -248,47 +414,68 @@ mod tests {
         };
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "ab33221100ddccbbaa78563412"
         );
     }
 
     #[test]
     fn test_fixed_test_struct_decoding() {
-        let data = [(
-            "ab33221100ddccbbaa78563412",
-            Ok(FixedTestStruct {
-                a: 0xab,
-                b: 0xaabbccdd00112233,
-                c: 0x12345678,
-            }),
-        )];
+        let data = [
+            (
+                "ab33221100ddccbbaa78563412",
+                Ok(FixedTestStruct {
+                    a: 0xab,
+                    b: 0xaabbccdd00112233,
+                    c: 0x12345678,
+                }),
+            ),
+            (
+                "ab33221100ddccbbaa785634",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 13,
+                    available: 12,
+                }),
+            ),
+            (
+                "ab33221100ddccbbaa78563412ffff",
+                Err(SszDataDecodingError::TrailingBytes { count: 2 }),
+            ),
+        ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(FixedTestStruct::decode_from(&decoding_item), result);
         }
     }
 
     #[derive(Debug, PartialEq, Eq)]
+    #[repr(C)]
     struct FixedTestStruct {
         a: u8,
         b: u64,
         c: u32,
     }
 
+    ssz_assert_field_order!(FixedTestStruct, a, b, c);
+
     impl SszType for FixedTestStruct {
         fn size() -> Option<u32> {
             Some(u8::size().unwrap() + u64::size().unwrap() + u32::size().unwrap())
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.encode_as_container_element(&self.b);
-            encoding_item.encode_as_container_element(&self.c);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+                writer.write_element(&self.c);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -320,7 +507,7 @@ mod tests {
         };
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "cdab07000000ff"
         );
 
@@ -333,7 +520,7 @@ mod tests {
         };
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "cdab07000000ff010002000300"
         );
     }
@@ -349,7 +536,7 @@ mod tests {
             }),
         )];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(VarTestStruct::decode_from(&decoding_item), result);
         }
@@ -368,12 +555,17 @@ mod tests {
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.encode_as_container_element(&self.b);
-            encoding_item.encode_as_container_element(&self.c);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+                writer.write_element(&self.c);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -416,7 +608,53 @@ mod tests {
         //              VarTestStruct(A=0xbeef, B=List[uint16, 1024](4, 5, 6), C=0x22)),
         //      )
         let mut encoding_item = SszEncodingItem::new();
-        let value = ComplexTestStruct {
+        let value = complex_test_struct();
+        value.encode_to(&mut encoding_item);
+        assert_eq!(
+            hex::encode_lower(&encoding_item.take_data()),
+            concat!(
+                "bbaa",
+                "47000000", // offset of B, []uint16
+                "ff",
+                "4b000000", // offset of foobar
+                "51000000", // offset of E
+                "cc424242424242424237133713",
+                "dd3333333333333333cdabcdab",
+                "ee444444444444444433221100",
+                "ff555555555555555577665544",
+                "5e000000",                   // pointer to G
+                "22114433",                   // contents of B
+                "666f6f626172",               // foobar
+                "cdab07000000ff010002000300", // contents of E
+                "08000000",
+                "15000000", // [start G]: local offsets of [2]varTestStruct
+                "adde0700000011010002000300",
+                "efbe0700000022040005000600",
+            )
+        );
+    }
+
+    #[test]
+    fn test_complex_test_struct_encode_into() {
+        let value = complex_test_struct();
+        let mut encoding_item = SszEncodingItem::new();
+        value.encode_to(&mut encoding_item);
+
+        let mut data = vec![0xab];
+        encoding_item.encode_into(&mut data);
+        // Leaves `encoding_item` unchanged, encoding again.
+        encoding_item.encode_into(&mut data);
+        let expected = encoding_item.take_data();
+        assert_eq!(data, [&[0xab], &expected[..], &expected[..]].concat());
+
+        let mut data = vec![0xab];
+        value.write_bytes(&mut data);
+        assert_eq!(data[1..], expected);
+        assert_eq!(value.to_bytes(), expected);
+    }
+
+    fn complex_test_struct() -> ComplexTestStruct {
+        ComplexTestStruct {
             a: 0xaabb,
             b: vec![0x1122, 0x3344],
             c: 0xff,
@@ -460,30 +698,7 @@ mod tests {
                     c: 0x22,
                 },
             ],
-        };
-        value.encode_to(&mut encoding_item);
-        assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
-            concat!(
-                "bbaa",
-                "47000000", // offset of B, []uint16
-                "ff",
-                "4b000000", // offset of foobar
-                "51000000", // offset of E
-                "cc424242424242424237133713",
-                "dd3333333333333333cdabcdab",
-                "ee444444444444444433221100",
-                "ff555555555555555577665544",
-                "5e000000",                   // pointer to G
-                "22114433",                   // contents of B
-                "666f6f626172",               // foobar
-                "cdab07000000ff010002000300", // contents of E
-                "08000000",
-                "15000000", // [start G]: local offsets of [2]varTestStruct
-                "adde0700000011010002000300",
-                "efbe0700000022040005000600",
-            )
-        );
+        }
     }
 
     #[test]
@@ -555,13 +770,14 @@ mod tests {
             }),
         )];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(ComplexTestStruct::decode_from(&decoding_item), result);
         }
     }
 
     #[derive(Debug, PartialEq, Eq)]
+    #[repr(C)]
     struct ComplexTestStruct {
         a: u16,
         b: Vec<u16>,
@@ -572,22 +788,29 @@ mod tests {
         g: [VarTestStruct; 2],
     }
 
+    ssz_assert_field_order!(ComplexTestStruct, a, b, c, d, e, f, g);
+
     impl SszType for ComplexTestStruct {
         fn size() -> Option<u32> {
             None
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.encode_as_container_element(&self.b);
-            encoding_item.encode_as_container_element(&self.c);
-            encoding_item.encode_as_container_element(&self.d);
-            encoding_item.encode_as_container_element(&self.e);
-            encoding_item.encode_as_container_element(&self.f);
-            encoding_item.encode_as_container_element(&self.g);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+                writer.write_element(&self.c);
+                writer.write_element(&self.d);
+                writer.write_element(&self.e);
+                writer.write_element(&self.f);
+                writer.write_element(&self.g);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -652,7 +875,7 @@ mod tests {
         ];
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "0800000015000000cdab0b0000000b000000ffef00cdab0b0000000b000000ffef00"
         );
     }
@@ -671,19 +894,42 @@ mod tests {
             ),
             (
                 "cdab0b0000000b000000",
-                Err(SszDataDecodingError::InvalidFormat),
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 11,
+                    available: 10,
+                }),
             ), // container: bytes_len < headers_len
             (
                 "cdab0c0000000b000000ffef00",
-                Err(SszDataDecodingError::InvalidFormat),
+                Err(SszDataDecodingError::OffsetsNotMonotonic {
+                    previous: 12,
+                    current: 11,
+                }),
             ), // container: offset > previous_offset_rev
             (
                 "cdabff0000000b000000ffef00",
-                Err(SszDataDecodingError::InvalidFormat),
-            ), // container: offset > previous_offset_rev
+                Err(SszDataDecodingError::OffsetOutOfBounds {
+                    offset: 255,
+                    limit: 13,
+                }),
+            ), // container: offset > bytes_len
+            (
+                "cdab0a0000000b000000ffef00",
+                Err(SszDataDecodingError::FirstOffsetMismatch {
+                    offset: 10,
+                    expected: 11,
+                }),
+            ), // container: first offset inside the fixed-size part
+            (
+                "cdab0c0000000c000000ffef00",
+                Err(SszDataDecodingError::FirstOffsetMismatch {
+                    offset: 12,
+                    expected: 11,
+                }),
+            ), // container: first offset leaving a gap
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(VarTestStruct2::decode_from(&decoding_item), result);
         }
@@ -709,23 +955,144 @@ mod tests {
                     },
                 ]),
             ),
-            ("08000000150000", Err(SszDataDecodingError::InvalidFormat)), // array: bytes_len < headers_len
+            (
+                "08000000150000",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 8,
+                    available: 7,
+                }),
+            ), // array: bytes_len < headers_len
             (
                 "0800000006000000cdab0b0000000b000000ffef00cdab0b0000000b000000ffef00",
-                Err(SszDataDecodingError::InvalidFormat),
+                Err(SszDataDecodingError::OffsetsNotMonotonic {
+                    previous: 8,
+                    current: 6,
+                }),
             ), // array: offset < previous_offset
             (
                 "ff00000006000000cdab0b0000000b000000ffef00cdab0b0000000b000000ffef00",
-                Err(SszDataDecodingError::InvalidFormat),
+                Err(SszDataDecodingError::OffsetOutOfBounds {
+                    offset: 255,
+                    limit: 34,
+                }),
             ), // array: offset > bytes_len
+            (
+                "0800000015000000cdab0b0000000b000000ffef00cdab0c0000000b000000ffef00",
+                Err(SszDataDecodingError::NestedError {
+                    element_index: 1,
+                    source: Box::new(SszDataDecodingError::OffsetsNotMonotonic {
+                        previous: 12,
+                        current: 11,
+                    }),
+                }),
+            ), // element 1: offset > previous_offset_rev
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(<[VarTestStruct2; 2]>::decode_from(&decoding_item), result);
         }
     }
 
+    #[test]
+    fn test_empty_tail_test_struct_encoding() {
+        let mut encoding_item = SszEncodingItem::new();
+        let value = EmptyTailTestStruct {
+            a: 0xabcd,
+            b: vec![],
+        };
+        value.encode_to(&mut encoding_item);
+        // The offset of B equals the length of the data
+        assert_eq!(
+            hex::encode_lower(&encoding_item.take_data()),
+            "cdab06000000"
+        );
+    }
+
+    #[test]
+    fn test_empty_tail_test_struct_decoding() {
+        let data = hex::decode("cdab06000000").unwrap();
+        let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+        let items = decoding_item
+            .decode_as_items(&[u16::size(), Vec::<u8>::size()])
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].data, [0xcd, 0xab]);
+        assert!(items[1].data.is_empty());
+
+        let data = [
+            (
+                "cdab06000000",
+                Ok(EmptyTailTestStruct {
+                    a: 0xabcd,
+                    b: vec![],
+                }),
+            ),
+            (
+                "cdab06000000ff",
+                Ok(EmptyTailTestStruct {
+                    a: 0xabcd,
+                    b: vec![0xff],
+                }),
+            ),
+            (
+                "cdab07000000",
+                Err(SszDataDecodingError::OffsetOutOfBounds {
+                    offset: 7,
+                    limit: 6,
+                }),
+            ), // offset > bytes_len
+            (
+                "cdab05000000",
+                Err(SszDataDecodingError::FirstOffsetMismatch {
+                    offset: 5,
+                    expected: 6,
+                }),
+            ), // offset inside the fixed-size part
+        ];
+        for (hex, result) in data {
+            let data = hex::decode(hex).unwrap();
+            let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
+            assert_eq!(EmptyTailTestStruct::decode_from(&decoding_item), result);
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct EmptyTailTestStruct {
+        a: u16,
+        b: Vec<u8>,
+    }
+
+    impl SszType for EmptyTailTestStruct {
+        fn size() -> Option<u32> {
+            None
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
+
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+            });
+        }
+
+        fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
+            let decoding_item = SszDecodingItem::new_from_data(bytes).unwrap();
+            let sizes = [u16::size(), Vec::<u8>::size()];
+            let items = decoding_item.decode_as_items(&sizes)?;
+            let mut iter = items.iter();
+
+            let a = u16::decode_from(iter.next().unwrap())?;
+            let b = Vec::<u8>::decode_from(iter.next().unwrap())?;
+            Ok(EmptyTailTestStruct { a, b })
+        }
+    }
+
     #[derive(Debug, PartialEq, Eq)]
     struct VarTestStruct2 {
         a: u16,
@@ -740,13 +1107,18 @@ mod tests {
         }
 
         fn to_bytes(&self) -> Vec<u8> {
-            let mut encoding_item = SszEncodingItem::new();
+            let mut bytes = vec![];
+            self.write_bytes(&mut bytes);
+            bytes
+        }
 
-            encoding_item.encode_as_container_element(&self.a);
-            encoding_item.encode_as_container_element(&self.b);
-            encoding_item.encode_as_container_element(&self.c);
-            encoding_item.encode_as_container_element(&self.d);
-            encoding_item.take_data()
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            SszContainerWriter::write_container(bytes, |writer| {
+                writer.write_element(&self.a);
+                writer.write_element(&self.b);
+                writer.write_element(&self.c);
+                writer.write_element(&self.d);
+            });
         }
 
         fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -6,6 +6,8 @@
 
 use super::decoder::{SszDataDecodingError, SszDecodingItem};
 use super::encoder::SszEncodingItem;
+#[cfg(not(feature = "unstable-codable"))]
+use crate::tools::codable::Sealed;
 use crate::tools::codable::{Decodable, Encodable};
 
 pub trait SszType: Sized {
@@ -16,17 +18,32 @@ pub trait SszType: Sized {
     /// Return the memory representation of `self` as a byte array.
     fn to_bytes(&self) -> Vec<u8>;
 
+    /// Appends the memory representation of `self` to `bytes`.
+    ///
+    /// Appends `to_bytes` by default. Lists and vectors write their elements straight into
+    /// `bytes`, and so does a container implementing this with `SszContainerWriter`.
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        bytes.extend(self.to_bytes());
+    }
+
     /// Creates `Self` from `bytes`.
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError>;
 }
 
+// `SszType` is the open extension point: any of its implementors is SSZ codable.
+#[cfg(not(feature = "unstable-codable"))]
+impl<T: SszType> Sealed<SszEncodingItem> for T {}
+
+#[cfg(not(feature = "unstable-codable"))]
+impl<'a, T: SszType> Sealed<SszDecodingItem<'a>> for T {}
+
 impl<T: SszType> Encodable<SszEncodingItem> for T {
     fn encode_to(&self, encoding_item: &mut SszEncodingItem) {
         debug_assert!(
             encoding_item.is_empty(),
             "Use `encode_as_container_element` to encode a container element"
         );
-        encoding_item.encode_fixed_size_data(&self.to_bytes());
+        encoding_item.encode_fixed_size_object(self);
     }
 }
 
@@ -16,6 +16,9 @@ pub struct SszDecodingItem<'a> {
     pub data: &'a [u8],
 }
 
+#[cfg(not(feature = "unstable-codable"))]
+impl crate::tools::codable::SealedItem for SszDecodingItem<'_> {}
+
 impl<'a> DecodingItem<'a> for SszDecodingItem<'a> {
     type Error = SszDataDecodingError;
 
@@ -27,7 +30,54 @@ impl<'a> DecodingItem<'a> for SszDecodingItem<'a> {
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum SszDataDecodingError {
+    /// The data is malformed in a way not covered by the other variants,
+    /// e.g., a `boolean` byte which is neither 0 nor 1.
     InvalidFormat,
+    /// An `offset` points beyond the end of the data (`limit`).
+    OffsetOutOfBounds { offset: u32, limit: u32 },
+    /// An offset (`current`) is smaller than the offset before it (`previous`).
+    OffsetsNotMonotonic { previous: u32, current: u32 },
+    /// The first offset of a container (`offset`) doesn't point right after the fixed-size
+    /// part (`expected`).
+    FirstOffsetMismatch { offset: u32, expected: u32 },
+    /// The data is shorter (`available` bytes) than the fixed-size part requires
+    /// (`expected` bytes).
+    TruncatedFixedField { expected: usize, available: usize },
+    /// The data is longer than a fixed-size type, with `count` bytes left over.
+    TrailingBytes { count: usize },
+    /// The element at `element_index` of a vector or a list fails to decode.
+    NestedError {
+        element_index: usize,
+        source: Box<SszDataDecodingError>,
+    },
+}
+
+impl SszDataDecodingError {
+    /// Wraps `self` as the error of the element at `element_index`.
+    pub(crate) fn nested(self, element_index: usize) -> SszDataDecodingError {
+        SszDataDecodingError::NestedError {
+            element_index,
+            source: Box::new(self),
+        }
+    }
+
+    /// Returns the error for `available` bytes of data,
+    /// which is expected to be exactly `expected` bytes.
+    pub(crate) fn from_length_mismatch(
+        expected: usize,
+        available: usize,
+    ) -> SszDataDecodingError {
+        if available < expected {
+            SszDataDecodingError::TruncatedFixedField {
+                expected,
+                available,
+            }
+        } else {
+            SszDataDecodingError::TrailingBytes {
+                count: available - expected,
+            }
+        }
+    }
 }
 
 impl Display for SszDataDecodingError {
@@ -36,8 +86,45 @@ impl Display for SszDataDecodingError {
             SszDataDecodingError::InvalidFormat => {
                 write!(f, "Invalid format")
             }
+            SszDataDecodingError::OffsetOutOfBounds { offset, limit } => {
+                write!(f, "Offset {offset} out of bounds (limit {limit})")
+            }
+            SszDataDecodingError::OffsetsNotMonotonic { previous, current } => {
+                write!(
+                    f,
+                    "Offset {current} is smaller than the previous offset {previous}"
+                )
+            }
+            SszDataDecodingError::FirstOffsetMismatch { offset, expected } => {
+                write!(f, "First offset {offset} doesn't match {expected}")
+            }
+            SszDataDecodingError::TruncatedFixedField {
+                expected,
+                available,
+            } => {
+                write!(
+                    f,
+                    "Truncated data: expected {expected} bytes, {available} available"
+                )
+            }
+            SszDataDecodingError::TrailingBytes { count } => {
+                write!(f, "{count} trailing bytes")
+            }
+            SszDataDecodingError::NestedError {
+                element_index,
+                source,
+            } => {
+                write!(f, "Element {element_index}: {source}")
+            }
         }
     }
 }
 
-impl Error for SszDataDecodingError {}
+impl Error for SszDataDecodingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SszDataDecodingError::NestedError { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
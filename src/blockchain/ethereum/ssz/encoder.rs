@@ -6,98 +6,159 @@
 
 //! Provides `EncodingItem` implementation for SSZ.
 
-use super::core::BYTES_PER_LENGTH_OFFSET;
+use super::core::{SszType, BYTES_PER_LENGTH_OFFSET};
 use crate::tools::codable::EncodingItem;
-
-enum HeaderType {
-    FixedSizeObjectData(Vec<u8>),
-    VariableSizeObjectDataRelativeOffset(u32),
-}
+use std::mem;
 
 /// The SSZ encoding type which implements `EncodingItem`.
 pub struct SszEncodingItem {
-    headers: Vec<HeaderType>,
-    headers_byte_len: u32,
+    // The fixed-size data, and the offsets of the variable-size data relative to the
+    // start of `encoded_variable_size_objects`, in the order of encoding.
+    headers: Vec<u8>,
+    // The positions of the relative offsets in `headers`.
+    offset_positions: Vec<usize>,
     encoded_variable_size_objects: Vec<u8>,
 }
 
 impl SszEncodingItem {
     /// Returns `true` if no data is encoded.
     pub(crate) fn is_empty(&self) -> bool {
-        self.headers_byte_len == 0
+        self.headers.is_empty()
+    }
+
+    /// Encodes `object` as fixed-size data, written directly after the encoded headers.
+    pub(crate) fn encode_fixed_size_object<T: SszType>(&mut self, object: &T) {
+        object.write_bytes(&mut self.headers);
+        self.check_headers_byte_len();
     }
 
-    pub(crate) fn encode_fixed_size_data(&mut self, bytes: &[u8]) {
-        self.headers
-            .push(HeaderType::FixedSizeObjectData(bytes.to_vec()));
-        let bytes_len = u32::try_from(bytes.len()).unwrap();
-        self.headers_byte_len = self.headers_byte_len.checked_add(bytes_len).unwrap();
+    /// Encodes `object` as variable-size data, written directly after the encoded
+    /// variable-size objects.
+    pub(crate) fn encode_variable_size_object<T: SszType>(&mut self, object: &T) {
+        self.push_relative_offset();
+        object.write_bytes(&mut self.encoded_variable_size_objects);
     }
 
-    pub(crate) fn encode_variable_size_data(&mut self, bytes: &[u8]) {
+    /// Appends the encoded data to `data`, as `take_data` returns.
+    ///
+    /// Unlike `take_data`, `self` is left unchanged, and no buffer is allocated if `data`
+    /// has the room.
+    pub fn encode_into(&self, data: &mut Vec<u8>) {
+        let start = data.len();
+        data.reserve(self.headers.len() + self.encoded_variable_size_objects.len());
+        data.extend_from_slice(&self.headers);
+        self.resolve_offsets(&mut data[start..]);
+        data.extend_from_slice(&self.encoded_variable_size_objects);
+    }
+
+    fn push_relative_offset(&mut self) {
         let relative_offset = u32::try_from(self.encoded_variable_size_objects.len()).unwrap();
+        self.offset_positions.push(self.headers.len());
+        self.headers.extend(relative_offset.to_le_bytes());
+        self.check_headers_byte_len();
+    }
+
+    // Panics if the headers don't fit in the range of an offset.
+    fn check_headers_byte_len(&self) {
+        u32::try_from(self.headers.len()).unwrap();
+    }
+
+    /// Converts the relative offsets in `headers`, a copy of `self.headers`, into the
+    /// offsets from the start of the encoded data.
+    fn resolve_offsets(&self, headers: &mut [u8]) {
+        let headers_byte_len = headers.len() as u32;
+        for &position in &self.offset_positions {
+            let offset_bytes =
+                &mut headers[position..position + BYTES_PER_LENGTH_OFFSET as usize];
+            let relative_offset = u32::from_le_bytes(offset_bytes.try_into().unwrap());
+            let offset = relative_offset.checked_add(headers_byte_len).unwrap();
+            offset_bytes.copy_from_slice(&offset.to_le_bytes());
+        }
+    }
+}
+
+/// Appends the elements of a list or a vector to `bytes`, each written in place: the
+/// offsets of variable-size elements are reserved first, then filled in as the elements
+/// are written.
+///
+/// Panics if an offset doesn't fit in `u32`.
+pub(crate) fn write_elements<T: SszType>(elements: &[T], bytes: &mut Vec<u8>) {
+    if T::size().is_some() {
+        for element in elements {
+            element.write_bytes(bytes);
+        }
+        return;
+    }
 
-        self.headers
-            .push(HeaderType::VariableSizeObjectDataRelativeOffset(
-                relative_offset,
-            ));
-        self.headers_byte_len = self
-            .headers_byte_len
-            .checked_add(BYTES_PER_LENGTH_OFFSET)
-            .unwrap();
-        self.encoded_variable_size_objects.extend(bytes);
+    let offset_byte_len = BYTES_PER_LENGTH_OFFSET as usize;
+    let start = bytes.len();
+    bytes.resize(start + elements.len() * offset_byte_len, 0);
+    for (i, element) in elements.iter().enumerate() {
+        let offset = u32::try_from(bytes.len() - start).unwrap();
+        let position = start + i * offset_byte_len;
+        bytes[position..position + offset_byte_len].copy_from_slice(&offset.to_le_bytes());
+        element.write_bytes(bytes);
     }
 }
 
+#[cfg(not(feature = "unstable-codable"))]
+impl crate::tools::codable::SealedItem for SszEncodingItem {}
+
 impl EncodingItem for SszEncodingItem {
     fn new() -> SszEncodingItem {
         SszEncodingItem {
             headers: vec![],
-            headers_byte_len: 0,
+            offset_positions: vec![],
             encoded_variable_size_objects: vec![],
         }
     }
 
     fn take_data(&mut self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(
-            self.headers_byte_len as usize + self.encoded_variable_size_objects.len(),
-        );
-
-        // Builds headers.
-        for header in self.headers.drain(0..) {
-            match header {
-                HeaderType::FixedSizeObjectData(d) => {
-                    data.extend(d);
-                }
-                HeaderType::VariableSizeObjectDataRelativeOffset(relative_offset) => {
-                    let offset = relative_offset.checked_add(self.headers_byte_len).unwrap();
-                    data.extend(offset.to_le_bytes());
-                }
-            }
-        }
+        // Builds headers, reusing the buffer.
+        let mut data = mem::take(&mut self.headers);
+        self.resolve_offsets(&mut data);
+        self.offset_positions.clear();
 
         // Builds "data".
         data.append(&mut self.encoded_variable_size_objects);
-        self.headers_byte_len = 0;
-
         data
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::write_elements;
     use crate::blockchain::ethereum::ssz::SszEncodingItem;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{Encodable, EncodingItem};
 
     #[test]
     fn test_take_data_emptying_internal_data() {
         let mut encoding_item = SszEncodingItem::new();
         12_u8.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "0c");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "0c");
 
         // Reuses `encoding_item`
         19_u8.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "13");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "13");
+    }
+
+    #[test]
+    fn test_write_elements_in_place() {
+        let value: Vec<Vec<u16>> = vec![vec![1, 2], vec![], vec![3]];
+
+        // The offsets are relative to the start of the list, not of `bytes`.
+        let mut bytes = vec![0xab];
+        write_elements(&value, &mut bytes);
+        assert_eq!(
+            hex::encode_lower(&bytes),
+            "ab0c0000001000000010000000010002000300"
+        );
+
+        let mut encoding_item = SszEncodingItem::new();
+        for element in &value {
+            encoding_item.encode_variable_size_object(element);
+        }
+        assert_eq!(bytes[1..], encoding_item.take_data());
     }
 }
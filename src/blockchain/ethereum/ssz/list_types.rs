@@ -9,8 +9,8 @@
 use super::array_types::decode_variable_size_objects_from_bytes;
 use super::core::{SszType, BYTES_PER_LENGTH_OFFSET};
 use super::decoder::{SszDataDecodingError, SszDecodingItem};
-use super::encoder::SszEncodingItem;
-use crate::tools::codable::{Decodable, DecodingItem, EncodingItem};
+use super::encoder::write_elements;
+use crate::tools::codable::{Decodable, DecodingItem};
 
 // TODO: implements optimization for `Vec<u8>`
 
@@ -21,19 +21,13 @@ impl<T: SszType> SszType for Vec<T> {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut encoding_item = SszEncodingItem::new();
-
-        if T::size().is_none() {
-            for value in self {
-                encoding_item.encode_variable_size_data(&value.to_bytes());
-            }
-        } else {
-            for value in self {
-                encoding_item.encode_fixed_size_data(&value.to_bytes());
-            }
-        }
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
 
-        encoding_item.take_data()
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        write_elements(self.as_slice(), bytes);
     }
 
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -42,7 +36,16 @@ impl<T: SszType> SszType for Vec<T> {
                 // "...Using the first offset, we can compute the length of the list
                 // (divide by BYTES_PER_LENGTH_OFFSET), as it gives us the total number of bytes in
                 // the offset data..."
-                let decoding_item = SszDecodingItem::new_from_data(&bytes[0..4]).unwrap();
+                if bytes.is_empty() {
+                    return Ok(vec![]);
+                }
+                let first_offset_bytes = bytes
+                    .get(0..(BYTES_PER_LENGTH_OFFSET as usize))
+                    .ok_or(SszDataDecodingError::TruncatedFixedField {
+                        expected: BYTES_PER_LENGTH_OFFSET as usize,
+                        available: bytes.len(),
+                    })?;
+                let decoding_item = SszDecodingItem::new_from_data(first_offset_bytes).unwrap();
                 let payload_offset = u32::decode_from(&decoding_item)?;
                 if payload_offset < BYTES_PER_LENGTH_OFFSET
                     || payload_offset % BYTES_PER_LENGTH_OFFSET != 0
@@ -56,14 +59,19 @@ impl<T: SszType> SszType for Vec<T> {
                 Ok(objects)
             }
             Some(size) => {
-                if bytes.len() % (size as usize) != 0 {
-                    return Err(SszDataDecodingError::InvalidFormat);
+                let remainder = bytes.len() % (size as usize);
+                if remainder != 0 {
+                    // The last element is truncated.
+                    return Err(SszDataDecodingError::TruncatedFixedField {
+                        expected: size as usize,
+                        available: remainder,
+                    });
                 }
                 let n = bytes.len() / (size as usize);
                 let mut objects = Vec::with_capacity(n);
-                for chunk in bytes.chunks_exact(size as usize) {
+                for (i, chunk) in bytes.chunks_exact(size as usize).enumerate() {
                     let decoding_item = SszDecodingItem::new_from_data(chunk).unwrap();
-                    let object = T::decode_from(&decoding_item)?;
+                    let object = T::decode_from(&decoding_item).map_err(|e| e.nested(i))?;
                     objects.push(object);
                 }
                 Ok(objects)
@@ -74,9 +82,9 @@ impl<T: SszType> SszType for Vec<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::blockchain::ethereum::ssz::decoder::SszDecodingItem;
+    use crate::blockchain::ethereum::ssz::decoder::{SszDataDecodingError, SszDecodingItem};
     use crate::blockchain::ethereum::ssz::encoder::SszEncodingItem;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
 
     /// Tests fixed size element `u8`
@@ -92,9 +100,9 @@ mod tests {
         ];
         for hex in data {
             let mut encoding_item = SszEncodingItem::new();
-            let bytes = hex_to_bytes(hex).unwrap();
+            let bytes = hex::decode(hex).unwrap();
             bytes.encode_to(&mut encoding_item);
-            assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), hex);
+            assert_eq!(hex::encode_lower(&encoding_item.take_data()), hex);
         }
     }
 
@@ -107,7 +115,7 @@ mod tests {
             ("", Ok(vec![])),
         ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(Vec::<u8>::decode_from(&decoding_item), result);
         }
@@ -120,14 +128,23 @@ mod tests {
         let mut encoding_item = SszEncodingItem::new();
         let value = vec![0x4567_u16, 0x0123];
         value.encode_to(&mut encoding_item);
-        assert_eq!(bytes_to_lower_hex(&encoding_item.take_data()), "67452301");
+        assert_eq!(hex::encode_lower(&encoding_item.take_data()), "67452301");
     }
 
     #[test]
     fn test_list_of_u16_decoding() {
-        let data = [("67452301", Ok(vec![0x4567_u16, 0x0123]))];
+        let data = [
+            ("67452301", Ok(vec![0x4567_u16, 0x0123])),
+            (
+                "674523",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 2,
+                    available: 1,
+                }),
+            ), // the last element
+        ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(Vec::<u16>::decode_from(&decoding_item), result);
         }
@@ -149,7 +166,7 @@ mod tests {
         ];
         value.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "000102030405060001020304050600010203040506"
         );
     }
@@ -165,7 +182,7 @@ mod tests {
             ]),
         )];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(Vec::<[u8; 7]>::decode_from(&decoding_item), result);
         }
@@ -187,23 +204,47 @@ mod tests {
         ];
         array.encode_to(&mut encoding_item);
         assert_eq!(
-            bytes_to_lower_hex(&encoding_item.take_data()),
+            hex::encode_lower(&encoding_item.take_data()),
             "0c000000130000001a000000000102030405060001020304050600010203040506"
         );
     }
 
     #[test]
     fn test_array_of_bytes_decoding() {
-        let data = [(
-            "0c000000130000001a000000000102030405060001020304050600010203040506",
-            Ok(vec![
-                vec![0_u8, 1, 2, 3, 4, 5, 6],
-                vec![0_u8, 1, 2, 3, 4, 5, 6],
-                vec![0_u8, 1, 2, 3, 4, 5, 6],
-            ]),
-        )];
+        let data = [
+            (
+                "0c000000130000001a000000000102030405060001020304050600010203040506",
+                Ok(vec![
+                    vec![0_u8, 1, 2, 3, 4, 5, 6],
+                    vec![0_u8, 1, 2, 3, 4, 5, 6],
+                    vec![0_u8, 1, 2, 3, 4, 5, 6],
+                ]),
+            ),
+            ("", Ok(vec![])),
+            (
+                "0c00",
+                Err(SszDataDecodingError::TruncatedFixedField {
+                    expected: 4,
+                    available: 2,
+                }),
+            ), // the first offset
+            (
+                "0c0000001a00000013000000000102030405060001020304050600010203040506",
+                Err(SszDataDecodingError::OffsetsNotMonotonic {
+                    previous: 0x1a,
+                    current: 0x13,
+                }),
+            ),
+            (
+                "0c00000013000000ff000000000102030405060001020304050600010203040506",
+                Err(SszDataDecodingError::OffsetOutOfBounds {
+                    offset: 0xff,
+                    limit: 33,
+                }),
+            ),
+        ];
         for (hex, result) in data {
-            let data = hex_to_bytes(hex).unwrap();
+            let data = hex::decode(hex).unwrap();
             let decoding_item = SszDecodingItem::new_from_data(&data).unwrap();
             assert_eq!(Vec::<Vec<u8>>::decode_from(&decoding_item), result);
         }
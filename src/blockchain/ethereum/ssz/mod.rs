@@ -22,5 +22,6 @@ mod encoder;
 mod list_types;
 
 pub use self::core::SszType;
+pub use container_types::SszContainerWriter;
 pub use decoder::{SszDataDecodingError, SszDecodingItem};
 pub use encoder::SszEncodingItem;
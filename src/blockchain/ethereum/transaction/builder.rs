@@ -4,10 +4,13 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::blockchain::ethereum::account::EoaPrivateKey;
+use crate::blockchain::ethereum::transaction::{TransactionEip1559, TransactionEip2930};
 use crate::blockchain::ethereum::types::address::Address;
 use crate::blockchain::ethereum::types::currency_unit::Wei;
 use crate::blockchain::ethereum::types::eoa_nonce::EoaNonce;
-use crate::blockchain::ethereum::types::{AccessList, ChainId};
+use crate::blockchain::ethereum::types::gas::GasLimit;
+use crate::blockchain::ethereum::types::{AccessList, ChainId, TransactionType};
 use crate::crypto::ecdsa::SigningError;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
@@ -17,6 +20,7 @@ use std::fmt::{Display, Formatter};
 pub enum TransactionBuildingError {
     MissingFields,
     SigningError(SigningError),
+    UnsupportedTransactionType(TransactionType),
 }
 
 impl Display for TransactionBuildingError {
@@ -24,19 +28,23 @@ impl Display for TransactionBuildingError {
         match self {
             TransactionBuildingError::MissingFields => write!(f, "Some fields are missing"),
             TransactionBuildingError::SigningError(err) => write!(f, "Signing error: {err}"),
+            TransactionBuildingError::UnsupportedTransactionType(tx_type) => {
+                write!(f, "Unsupported transaction type: {tx_type:#04x}")
+            }
         }
     }
 }
 
 impl Error for TransactionBuildingError {}
 
+#[derive(Clone)]
 pub struct TransactionBuilder {
     pub(crate) chain_id: Option<ChainId>,
     pub(crate) nonce: Option<EoaNonce>,
     pub(crate) gas_price: Option<Wei>,
     pub(crate) max_priority_fee_per_gas: Option<Wei>,
     pub(crate) max_fee_per_gas: Option<Wei>,
-    pub(crate) gas_limit: Option<u64>,
+    pub(crate) gas_limit: Option<GasLimit>,
     pub(crate) destination: Option<Address>,
     pub(crate) amount: Option<Wei>,
     pub(crate) data: Option<Vec<u8>>,
@@ -87,7 +95,7 @@ impl TransactionBuilder {
         self
     }
 
-    pub fn with_gas_limit(mut self, gas_limit: u64) -> TransactionBuilder {
+    pub fn with_gas_limit(mut self, gas_limit: GasLimit) -> TransactionBuilder {
         self.gas_limit = Some(gas_limit);
         self
     }
@@ -111,6 +119,46 @@ impl TransactionBuilder {
         self.access_list = Some(access_list);
         self
     }
+
+    /// Builds the payload of `tx_type`, signs it with `private_key` and returns the
+    /// encoded transaction, ready to be sent with `eth_sendRawTransaction`.
+    ///
+    /// Type `0x0` is signed as an EIP-155 transaction if the chain ID is set, or as a
+    /// legacy one otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - `tx_type` isn't `0x0`, `0x1` (EIP-2930) or `0x2` (EIP-1559).
+    /// - The fields required by `tx_type` are missing.
+    /// - Signing fails.
+    pub fn take_and_sign_typed(
+        &mut self,
+        tx_type: TransactionType,
+        private_key: &EoaPrivateKey,
+    ) -> Result<Vec<u8>, TransactionBuildingError> {
+        match tx_type {
+            0x0 if self.chain_id.is_some() => {
+                let payload = self.take_and_build_payload_eip_155()?;
+                Ok(private_key.sign_eip155(payload)?.encode())
+            }
+            0x0 => {
+                let payload = self.take_and_build_payload_legacy()?;
+                Ok(private_key.sign_legacy(payload)?.encode())
+            }
+            _ if tx_type == TransactionEip2930::transaction_type() => {
+                let payload = self.take_and_build_payload_eip_2930()?;
+                Ok(private_key.sign_eip2930(payload)?.encode())
+            }
+            _ if tx_type == TransactionEip1559::transaction_type() => {
+                let payload = self.take_and_build_payload_eip_1559()?;
+                Ok(private_key.sign_eip1559(payload)?.encode())
+            }
+            _ => Err(TransactionBuildingError::UnsupportedTransactionType(
+                tx_type,
+            )),
+        }
+    }
 }
 
 impl Default for TransactionBuilder {
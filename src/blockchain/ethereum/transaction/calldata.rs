@@ -0,0 +1,138 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the utilities of the calldata (the `data` field) of transactions.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The gas cost of a zero byte of calldata.
+pub const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+
+/// The gas cost of a non-zero byte of calldata, reduced from 68 by EIP-2028.
+///
+/// See: https://eips.ethereum.org/EIPS/eip-2028
+pub const CALLDATA_NON_ZERO_BYTE_GAS: u64 = 16;
+
+/// The gas cost of a non-zero byte of calldata before EIP-2028 (Istanbul).
+pub const CALLDATA_NON_ZERO_BYTE_GAS_PRE_EIP2028: u64 = 68;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TransactionValidationError {
+    /// The calldata of `size` bytes exceeds `limit` bytes.
+    CalldataTooLarge { size: usize, limit: usize },
+}
+
+impl Display for TransactionValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionValidationError::CalldataTooLarge { size, limit } => {
+                write!(
+                    f,
+                    "Calldata of {size} bytes exceeds the limit of {limit} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl Error for TransactionValidationError {}
+
+/// Returns the gas cost of `data` as the calldata of a transaction: 4 gas per zero byte,
+/// and 16 gas per non-zero byte, or 68 gas before EIP-2028 if `eip2028` is false.
+///
+/// Only the part of the intrinsic gas paid for the calldata is returned, the base cost of
+/// a transaction (21000) is excluded.
+pub fn estimate_calldata_cost(data: &[u8], eip2028: bool) -> u64 {
+    let non_zero_byte_gas = if eip2028 {
+        CALLDATA_NON_ZERO_BYTE_GAS
+    } else {
+        CALLDATA_NON_ZERO_BYTE_GAS_PRE_EIP2028
+    };
+
+    data.iter()
+        .map(|&byte| {
+            if byte == 0 {
+                CALLDATA_ZERO_BYTE_GAS
+            } else {
+                non_zero_byte_gas
+            }
+        })
+        .sum()
+}
+
+/// Validates that `data`, the calldata of a transaction, is no larger than `limit` bytes.
+///
+/// EIP-4488 (not deployed) proposed to bound the calldata in a block along with its cost
+/// reduction, `limit` is the size allowed for the transaction.
+///
+/// See: https://eips.ethereum.org/EIPS/eip-4488
+pub fn validate_eip4488_calldata_size(
+    data: &[u8],
+    limit: usize,
+) -> Result<(), TransactionValidationError> {
+    if data.len() > limit {
+        return Err(TransactionValidationError::CalldataTooLarge {
+            size: data.len(),
+            limit,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_estimate_calldata_cost() {
+        assert_eq!(estimate_calldata_cost(&[], true), 0);
+        assert_eq!(estimate_calldata_cost(&[0], true), 4);
+        assert_eq!(estimate_calldata_cost(&[0], false), 4);
+        assert_eq!(estimate_calldata_cost(&[1], true), 16);
+        assert_eq!(estimate_calldata_cost(&[1], false), 68);
+
+        // transfer(0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed, 10^18)
+        let data = hex::decode(
+            [
+                "a9059cbb",
+                "0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+                "0000000000000000000000000000000000000000000000000de0b6b3a7640000",
+            ]
+            .concat(),
+        )
+        .unwrap();
+        assert_eq!(data.len(), 68);
+        // 30 non-zero bytes: 4 of the selector, 20 of the address and 6 of the amount;
+        // 38 zero bytes.
+        assert_eq!(estimate_calldata_cost(&data, true), 30 * 16 + 38 * 4);
+        assert_eq!(estimate_calldata_cost(&data, false), 30 * 68 + 38 * 4);
+    }
+
+    #[test]
+    fn test_validate_eip4488_calldata_size() {
+        assert_eq!(validate_eip4488_calldata_size(&[], 0), Ok(()));
+        assert_eq!(validate_eip4488_calldata_size(&[0; 300], 300), Ok(()));
+        assert_eq!(
+            validate_eip4488_calldata_size(&[0; 301], 300),
+            Err(TransactionValidationError::CalldataTooLarge {
+                size: 301,
+                limit: 300
+            })
+        );
+        assert_eq!(
+            TransactionValidationError::CalldataTooLarge {
+                size: 301,
+                limit: 300
+            }
+            .to_string(),
+            "Calldata of 301 bytes exceeds the limit of 300 bytes"
+        );
+    }
+}
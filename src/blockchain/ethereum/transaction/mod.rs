@@ -4,7 +4,73 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+//! Builds, signs and encodes transactions.
+//!
+//! Two Keccak-256 hashes are involved with a transaction, over different bytes, where
+//! `fields` are the fields of the payload, without the signature:
+//!
+//! | Type     | `signing_hash()`                              | `transaction_id()` of the signed transaction |
+//! |----------|-----------------------------------------------|----------------------------------------------|
+//! | Legacy   | `rlp([fields])`, or EIP-155's with a chain ID | `rlp([fields, v, r, s])`                     |
+//! | EIP-155  | `rlp([fields, chainId, 0, 0])`                | `rlp([fields, v, r, s])`, `chainId` in `v`   |
+//! | EIP-2930 | `0x01 \|\| rlp([fields])`                     | `0x01 \|\| rlp([fields, yParity, r, s])`     |
+//! | EIP-1559 | `0x02 \|\| rlp([fields])`                     | `0x02 \|\| rlp([fields, yParity, r, s])`     |
+//!
+//! A [`TransactionLegacy`] decoded from an EIP-155 transaction carries the chain ID in `v`,
+//! and its signature signs the EIP-155 hash rather than the `signing_hash()` of its
+//! payload. Use [`TransactionLegacy::signing_hash`], which takes the chain ID into account.
+//!
+//! The transaction ID covers the signature, and a signature can be altered without
+//! invalidating it, e.g., `s` replaced with `n - s`, which changes the ID. Don't take the
+//! ID of a transaction not yet included in a block as final.
+//!
+//! Except for `TransactionLegacy`, the signing hash is only available on the payload types,
+//! and the transaction ID only on the signed types, so the two can't be mixed up:
+//!
+//! ```
+//! use lightcryptotools::blockchain::ethereum::account::EoaPrivateKey;
+//! use lightcryptotools::blockchain::ethereum::transaction::TransactionBuilder;
+//! use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+//!
+//! let private_key = EoaPrivateKey::from_any_hex(
+//!     "4646464646464646464646464646464646464646464646464646464646464646",
+//! )
+//! .unwrap();
+//! let payload = TransactionBuilder::new()
+//!     .with_chain_id(1.into())
+//!     .with_nonce(0.try_into().unwrap())
+//!     .with_max_priority_fee_per_gas("0x01".try_into().unwrap())
+//!     .with_max_fee_per_gas("0x02".try_into().unwrap())
+//!     .with_gas_limit(21000.into())
+//!     .with_destination("0x3535353535353535353535353535353535353535".try_into().unwrap())
+//!     .with_amount("0x01".try_into().unwrap())
+//!     .take_and_build_payload_eip_1559()
+//!     .unwrap();
+//!
+//! let signing_hash = payload.signing_hash();
+//! let transaction = private_key.sign_eip1559(payload).unwrap();
+//! let transaction_id = transaction.transaction_id();
+//!
+//! assert_eq!(transaction_id.as_slice(), Keccak256::new().digest(transaction.encode()));
+//! assert_ne!(transaction_id, signing_hash);
+//! ```
+//!
+//! ```compile_fail
+//! # use lightcryptotools::blockchain::ethereum::transaction::PayloadEip1559;
+//! fn payload_has_no_transaction_id(payload: &PayloadEip1559) {
+//!     payload.transaction_id();
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! # use lightcryptotools::blockchain::ethereum::transaction::TransactionEip1559;
+//! fn transaction_has_no_signing_hash(transaction: &TransactionEip1559) {
+//!     transaction.signing_hash();
+//! }
+//! ```
+
 pub(crate) mod builder;
+pub(crate) mod calldata;
 pub(crate) mod payload;
 pub(crate) mod types;
 
@@ -14,7 +80,12 @@ pub use payload::eip_2930::PayloadEip2930;
 pub use payload::legacy::PayloadLegacy;
 pub use types::transaction_eip_155::TransactionEip155;
 pub use types::transaction_eip_1559::TransactionEip1559;
+pub use types::transaction_eip_1559_ref::TransactionEip1559Ref;
 pub use types::transaction_eip_2930::TransactionEip2930;
 pub use types::transaction_legacy::TransactionLegacy;
 
 pub use builder::{TransactionBuilder, TransactionBuildingError};
+pub use calldata::{
+    estimate_calldata_cost, validate_eip4488_calldata_size, TransactionValidationError,
+    CALLDATA_NON_ZERO_BYTE_GAS, CALLDATA_NON_ZERO_BYTE_GAS_PRE_EIP2028, CALLDATA_ZERO_BYTE_GAS,
+};
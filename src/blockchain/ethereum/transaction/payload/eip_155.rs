@@ -7,8 +7,9 @@
 use crate::blockchain::ethereum::transaction::{TransactionBuilder, TransactionBuildingError};
 use crate::blockchain::ethereum::types::address::Address;
 use crate::blockchain::ethereum::types::eoa_nonce::EoaNonce;
+use crate::blockchain::ethereum::types::gas::GasLimit;
 use crate::blockchain::ethereum::types::{ChainId, Wei};
-use crate::crypto::codecs::bytes_to_lower_hex;
+use crate::crypto::codecs::hex;
 use std::fmt;
 use std::fmt::Display;
 
@@ -16,13 +17,19 @@ pub struct PayloadEip155 {
     pub(crate) chain_id: ChainId,
     pub(crate) nonce: EoaNonce,
     pub(crate) gas_price: Wei,
-    pub(crate) gas_limit: u64,
+    pub(crate) gas_limit: GasLimit,
     pub(crate) destination: Address,
     pub(crate) amount: Wei,
     pub(crate) data: Vec<u8>,
 }
 
 impl TransactionBuilder {
+    /// Builds a `PayloadEip155` from a copy of the fields, leaving `self` intact to be reused
+    /// as a template.
+    pub fn build_payload_eip_155(&self) -> Result<PayloadEip155, TransactionBuildingError> {
+        self.clone().take_and_build_payload_eip_155()
+    }
+
     pub fn take_and_build_payload_eip_155(
         &mut self,
     ) -> Result<PayloadEip155, TransactionBuildingError> {
@@ -61,10 +68,10 @@ impl Display for PayloadEip155 {
         writeln!(f, "chain_id: {}", self.chain_id)?;
         writeln!(f, "nonce: {}", self.nonce)?;
         writeln!(f, "gas_price: {}", self.gas_price)?;
-        writeln!(f, "gas_limit: 0x{:x}", self.gas_limit)?;
+        writeln!(f, "gas_limit: {}", self.gas_limit)?;
         writeln!(f, "destination: {}", self.destination)?;
         writeln!(f, "amount: {}", self.amount)?;
-        writeln!(f, "data: 0x{}", bytes_to_lower_hex(&self.data))?;
+        writeln!(f, "data: 0x{}", hex::encode_lower(&self.data))?;
 
         Ok(())
     }
@@ -74,7 +81,7 @@ impl Display for PayloadEip155 {
 mod tests {
     use super::*;
     use crate::blockchain::ethereum::types::Chain;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::encode;
 
     // The test data is from EIP-155
@@ -84,7 +91,7 @@ mod tests {
             .with_chain_id(Chain::EthereumMainnet.id())
             .with_nonce(9.try_into().unwrap())
             .with_gas_price("20000000000".try_into().unwrap())
-            .with_gas_limit(21000)
+            .with_gas_limit(21000.into())
             .with_destination(
                 "0x3535353535353535353535353535353535353535"
                     .try_into()
@@ -96,7 +103,7 @@ mod tests {
 
         let rlp_data = encode(&payload);
         assert_eq!(
-            bytes_to_lower_hex(&rlp_data),
+            hex::encode_lower(&rlp_data),
             concat!(
                 "ec098504a817c80082520894353535353535",
                 "3535353535353535353535353535880de0b6",
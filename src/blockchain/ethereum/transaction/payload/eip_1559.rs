@@ -5,8 +5,10 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::blockchain::ethereum::transaction::{TransactionBuilder, TransactionBuildingError};
-use crate::blockchain::ethereum::types::{AccessList, Address, ChainId, EoaNonce, Wei};
-use crate::crypto::codecs::bytes_to_lower_hex;
+use crate::blockchain::ethereum::types::{
+    AccessList, Address, ChainId, EoaNonce, GasLimit, Wei,
+};
+use crate::crypto::codecs::hex;
 use std::fmt;
 use std::fmt::Display;
 
@@ -15,7 +17,7 @@ pub struct PayloadEip1559 {
     pub(crate) nonce: EoaNonce,
     pub(crate) max_priority_fee_per_gas: Wei,
     pub(crate) max_fee_per_gas: Wei,
-    pub(crate) gas_limit: u64,
+    pub(crate) gas_limit: GasLimit,
     pub(crate) destination: Address,
     pub(crate) amount: Wei,
     pub(crate) data: Vec<u8>,
@@ -23,6 +25,12 @@ pub struct PayloadEip1559 {
 }
 
 impl TransactionBuilder {
+    /// Builds a `PayloadEip1559` from a copy of the fields, leaving `self` intact to be reused
+    /// as a template.
+    pub fn build_payload_eip_1559(&self) -> Result<PayloadEip1559, TransactionBuildingError> {
+        self.clone().take_and_build_payload_eip_1559()
+    }
+
     pub fn take_and_build_payload_eip_1559(
         &mut self,
     ) -> Result<PayloadEip1559, TransactionBuildingError> {
@@ -61,6 +69,14 @@ impl TransactionBuilder {
     }
 }
 
+impl PayloadEip1559 {
+    /// Returns true if the max fee covers `base_fee`, the base fee of a block, thus the
+    /// transaction can be included in it.
+    pub fn would_be_included(&self, base_fee: &Wei) -> bool {
+        self.max_fee_per_gas.0 >= base_fee.0
+    }
+}
+
 impl Display for PayloadEip1559 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "chain_id: {}", self.chain_id)?;
@@ -71,10 +87,10 @@ impl Display for PayloadEip1559 {
             self.max_priority_fee_per_gas
         )?;
         writeln!(f, "max_fee_per_gas: {}", self.max_fee_per_gas)?;
-        writeln!(f, "gas_limit: 0x{:x}", self.gas_limit)?;
+        writeln!(f, "gas_limit: {}", self.gas_limit)?;
         writeln!(f, "destination: {}", self.destination)?;
         writeln!(f, "amount: {}", self.amount)?;
-        writeln!(f, "data: 0x{}", bytes_to_lower_hex(&self.data))?;
+        writeln!(f, "data: 0x{}", hex::encode_lower(&self.data))?;
         writeln!(f, "access_list: {}", &self.access_list)?;
 
         Ok(())
@@ -85,7 +101,7 @@ impl Display for PayloadEip1559 {
 mod tests {
     use super::*;
     use crate::blockchain::ethereum::types::AccessListItem;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::encode;
 
     #[test]
@@ -95,7 +111,7 @@ mod tests {
             .with_nonce(42.try_into().unwrap())
             .with_max_priority_fee_per_gas("0x42".try_into().unwrap())
             .with_max_fee_per_gas("0x0143".try_into().unwrap())
-            .with_gas_limit(0x5208)
+            .with_gas_limit(0x5208.into())
             .with_destination(
                 "0x123456789a123456789a123456789a123456789a"
                     .try_into()
@@ -115,9 +131,13 @@ mod tests {
             .take_and_build_payload_eip_1559()
             .unwrap();
 
+        assert!(payload.would_be_included(&"0x0142".try_into().unwrap()));
+        assert!(payload.would_be_included(&"0x0143".try_into().unwrap()));
+        assert!(!payload.would_be_included(&"0x0144".try_into().unwrap()));
+
         let rlp_data = encode(&payload);
         assert_eq!(
-            bytes_to_lower_hex(&rlp_data),
+            hex::encode_lower(&rlp_data),
             concat!(
                 "f85c7b2a4282014382520894123456789a12",
                 "3456789a123456789a123456789a82012380",
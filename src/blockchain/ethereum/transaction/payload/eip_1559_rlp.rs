@@ -5,11 +5,21 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::eip_1559::PayloadEip1559;
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
-use crate::tools::codable::{Encodable, EncodingItem};
+use crate::blockchain::ethereum::rlp::RlpItemType;
+use crate::blockchain::ethereum::transaction::TransactionBuilder;
+use crate::blockchain::ethereum::types::{
+    AccessList, Address, ChainId, EoaNonce, GasLimit, Wei,
+};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
 
 // [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, destination, amount, data, access_list]
 // See EIP-1559: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+seal_codable!(RlpEncodingItem => PayloadEip1559);
+seal_codable!(<'a> RlpDecodingItem<'a> => PayloadEip1559);
+
 impl Encodable<RlpEncodingItem> for PayloadEip1559 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
         let mut list_encoding_item = RlpEncodingItem::new();
@@ -28,3 +38,34 @@ impl Encodable<RlpEncodingItem> for PayloadEip1559 {
         encoding_item.encode_list_payload(&mut list_encoding_item);
     }
 }
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for PayloadEip1559 {
+    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+        match decoding_item.item_type {
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
+            RlpItemType::List => {
+                let items = decoding_item.decode_as_items()?;
+                if items.len() != 9 {
+                    return Err(RlpDataDecodingError::InvalidFormat);
+                }
+                let mut iter = items.iter();
+
+                TransactionBuilder::new()
+                    .with_chain_id(ChainId::decode_from(iter.next().unwrap())?)
+                    .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
+                    .with_max_priority_fee_per_gas(Wei::decode_from(iter.next().unwrap())?)
+                    .with_max_fee_per_gas(Wei::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
+                    .with_destination(Address::decode_from(iter.next().unwrap())?)
+                    .with_amount(Wei::decode_from(iter.next().unwrap())?)
+                    .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
+                    .with_access_list(AccessList::decode_from(iter.next().unwrap())?)
+                    .take_and_build_payload_eip_1559()
+                    .map_err(|_| RlpDataDecodingError::InvalidFormat)
+            }
+        }
+    }
+}
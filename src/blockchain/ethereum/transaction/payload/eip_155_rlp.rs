@@ -5,8 +5,13 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::eip_155::PayloadEip155;
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
-use crate::tools::codable::{Encodable, EncodingItem};
+use crate::blockchain::ethereum::rlp::RlpItemType;
+use crate::blockchain::ethereum::transaction::TransactionBuilder;
+use crate::blockchain::ethereum::types::{Address, ChainId, EoaNonce, GasLimit, Wei};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
 
 // ...when computing the hash of a transaction for the purposes of signing,
 // ...you SHOULD hash nine rlp encoded elements
@@ -14,6 +19,9 @@ use crate::tools::codable::{Encodable, EncodingItem};
 //
 // See EIP-155: Simple replay attack protection
 // https://eips.ethereum.org/EIPS/eip-155
+seal_codable!(RlpEncodingItem => PayloadEip155);
+seal_codable!(<'a> RlpDecodingItem<'a> => PayloadEip155);
+
 impl Encodable<RlpEncodingItem> for PayloadEip155 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
         let mut list_encoding_item = RlpEncodingItem::new();
@@ -30,3 +38,40 @@ impl Encodable<RlpEncodingItem> for PayloadEip155 {
         encoding_item.encode_list_payload(&mut list_encoding_item);
     }
 }
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for PayloadEip155 {
+    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+        match decoding_item.item_type {
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
+            RlpItemType::List => {
+                let items = decoding_item.decode_as_items()?;
+                if items.len() != 9 {
+                    return Err(RlpDataDecodingError::InvalidFormat);
+                }
+                let mut iter = items.iter();
+
+                let mut builder = TransactionBuilder::new()
+                    .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
+                    .with_gas_price(Wei::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
+                    .with_destination(Address::decode_from(iter.next().unwrap())?)
+                    .with_amount(Wei::decode_from(iter.next().unwrap())?)
+                    .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
+                    .with_chain_id(ChainId::decode_from(iter.next().unwrap())?);
+                // The two trailing zeros
+                for item in iter {
+                    if u64::decode_from(item)? != 0 {
+                        return Err(RlpDataDecodingError::InvalidFormat);
+                    }
+                }
+
+                builder
+                    .take_and_build_payload_eip_155()
+                    .map_err(|_| RlpDataDecodingError::InvalidFormat)
+            }
+        }
+    }
+}
@@ -5,8 +5,10 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::blockchain::ethereum::transaction::{TransactionBuilder, TransactionBuildingError};
-use crate::blockchain::ethereum::types::{AccessList, Address, ChainId, EoaNonce, Wei};
-use crate::crypto::codecs::bytes_to_lower_hex;
+use crate::blockchain::ethereum::types::{
+    AccessList, Address, ChainId, EoaNonce, GasLimit, Wei,
+};
+use crate::crypto::codecs::hex;
 use std::fmt;
 use std::fmt::Display;
 
@@ -14,7 +16,7 @@ pub struct PayloadEip2930 {
     pub(crate) chain_id: ChainId,
     pub(crate) nonce: EoaNonce,
     pub(crate) gas_price: Wei,
-    pub(crate) gas_limit: u64,
+    pub(crate) gas_limit: GasLimit,
     pub(crate) destination: Address,
     pub(crate) amount: Wei,
     pub(crate) data: Vec<u8>,
@@ -22,6 +24,12 @@ pub struct PayloadEip2930 {
 }
 
 impl TransactionBuilder {
+    /// Builds a `PayloadEip2930` from a copy of the fields, leaving `self` intact to be reused
+    /// as a template.
+    pub fn build_payload_eip_2930(&self) -> Result<PayloadEip2930, TransactionBuildingError> {
+        self.clone().take_and_build_payload_eip_2930()
+    }
+
     pub fn take_and_build_payload_eip_2930(
         &mut self,
     ) -> Result<PayloadEip2930, TransactionBuildingError> {
@@ -62,10 +70,10 @@ impl Display for PayloadEip2930 {
         writeln!(f, "chain_id: {}", self.chain_id)?;
         writeln!(f, "nonce: {}", self.nonce)?;
         writeln!(f, "gas_price: {}", self.gas_price)?;
-        writeln!(f, "gas_limit: 0x{:x}", self.gas_limit)?;
+        writeln!(f, "gas_limit: {}", self.gas_limit)?;
         writeln!(f, "destination: {}", self.destination)?;
         writeln!(f, "amount: {}", self.amount)?;
-        writeln!(f, "data: 0x{}", bytes_to_lower_hex(&self.data))?;
+        writeln!(f, "data: 0x{}", hex::encode_lower(&self.data))?;
         writeln!(f, "access_list: {}", &self.access_list)?;
 
         Ok(())
@@ -75,7 +83,7 @@ impl Display for PayloadEip2930 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::encode;
 
     #[test]
@@ -84,20 +92,20 @@ mod tests {
             .with_chain_id(144.into())
             .with_nonce(9.try_into().unwrap())
             .with_gas_price("0x765898be".try_into().unwrap())
-            .with_gas_limit(0xbb)
+            .with_gas_limit(0xbb.into())
             .with_destination(
                 "0xf933abf2475062e0f3e7bde89da3f6c9e6963b67"
                     .try_into()
                     .unwrap(),
             )
             .with_amount("0xd7".try_into().unwrap())
-            .with_data(hex_to_bytes("74a53d7a649760e78359").unwrap())
+            .with_data(hex::decode("74a53d7a649760e78359").unwrap())
             .take_and_build_payload_eip_2930()
             .unwrap();
 
         let rlp_data = encode(&payload);
         assert_eq!(
-            bytes_to_lower_hex(&rlp_data),
+            hex::encode_lower(&rlp_data),
             concat!(
                 "ed81900984765898be81bb94f933abf24750",
                 "62e0f3e7bde89da3f6c9e6963b6781d78a74",
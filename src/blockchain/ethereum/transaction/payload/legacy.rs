@@ -10,20 +10,27 @@ use crate::blockchain::ethereum::transaction::builder::{
 use crate::blockchain::ethereum::types::address::Address;
 use crate::blockchain::ethereum::types::currency_unit::Wei;
 use crate::blockchain::ethereum::types::eoa_nonce::EoaNonce;
-use crate::crypto::codecs::bytes_to_lower_hex;
+use crate::blockchain::ethereum::types::gas::GasLimit;
+use crate::crypto::codecs::hex;
 use std::fmt;
 use std::fmt::Display;
 
 pub struct PayloadLegacy {
     pub(crate) nonce: EoaNonce,
     pub(crate) gas_price: Wei,
-    pub(crate) gas_limit: u64,
+    pub(crate) gas_limit: GasLimit,
     pub(crate) destination: Address,
     pub(crate) amount: Wei,
     pub(crate) data: Vec<u8>,
 }
 
 impl TransactionBuilder {
+    /// Builds a `PayloadLegacy` from a copy of the fields, leaving `self` intact to be reused
+    /// as a template.
+    pub fn build_payload_legacy(&self) -> Result<PayloadLegacy, TransactionBuildingError> {
+        self.clone().take_and_build_payload_legacy()
+    }
+
     pub fn take_and_build_payload_legacy(
         &mut self,
     ) -> Result<PayloadLegacy, TransactionBuildingError> {
@@ -58,10 +65,10 @@ impl Display for PayloadLegacy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "nonce: {}", self.nonce)?;
         writeln!(f, "gas_price: {}", self.gas_price)?;
-        writeln!(f, "gas_limit: 0x{:x}", self.gas_limit)?;
+        writeln!(f, "gas_limit: {}", self.gas_limit)?;
         writeln!(f, "destination: {}", self.destination)?;
         writeln!(f, "amount: {}", self.amount)?;
-        writeln!(f, "data: 0x{}", bytes_to_lower_hex(&self.data))?;
+        writeln!(f, "data: 0x{}", hex::encode_lower(&self.data))?;
 
         Ok(())
     }
@@ -71,7 +78,7 @@ impl Display for PayloadLegacy {
 mod tests {
     use super::*;
     use crate::bigint::BigUint;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::tools::codable::encode;
 
     #[test]
@@ -79,7 +86,7 @@ mod tests {
         let payload = TransactionBuilder::new()
             .with_nonce(0.try_into().unwrap())
             .with_gas_price("0x09184e72a000".try_into().unwrap())
-            .with_gas_limit(0x2710)
+            .with_gas_limit(0x2710.into())
             .with_destination(
                 "0x0000000000000000000000000000000000000000"
                     .try_into()
@@ -87,7 +94,7 @@ mod tests {
             )
             .with_amount(BigUint::from(0_u8).into())
             .with_data(
-                hex_to_bytes(
+                hex::decode(
                     "7f7465737432000000000000000000000000000000000000000000000000000000600057",
                 )
                 .unwrap(),
@@ -97,7 +104,7 @@ mod tests {
 
         let rlp_data = encode(&payload);
         assert_eq!(
-            bytes_to_lower_hex(&rlp_data),
+            hex::encode_lower(&rlp_data),
             concat!(
                 "f846808609184e72a0008227109400000000",
                 "0000000000000000000000000000000080a4",
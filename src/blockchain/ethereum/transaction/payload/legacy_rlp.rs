@@ -5,8 +5,16 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::legacy::PayloadLegacy;
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
-use crate::tools::codable::{Encodable, EncodingItem};
+use crate::blockchain::ethereum::rlp::RlpItemType;
+use crate::blockchain::ethereum::transaction::TransactionBuilder;
+use crate::blockchain::ethereum::types::{Address, EoaNonce, GasLimit, Wei};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => PayloadLegacy);
+seal_codable!(<'a> RlpDecodingItem<'a> => PayloadLegacy);
 
 impl Encodable<RlpEncodingItem> for PayloadLegacy {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -21,3 +29,31 @@ impl Encodable<RlpEncodingItem> for PayloadLegacy {
         encoding_item.encode_list_payload(&mut list_encoding_item);
     }
 }
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for PayloadLegacy {
+    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+        match decoding_item.item_type {
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
+            RlpItemType::List => {
+                let items = decoding_item.decode_as_items()?;
+                if items.len() != 6 {
+                    return Err(RlpDataDecodingError::InvalidFormat);
+                }
+                let mut iter = items.iter();
+
+                TransactionBuilder::new()
+                    .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
+                    .with_gas_price(Wei::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
+                    .with_destination(Address::decode_from(iter.next().unwrap())?)
+                    .with_amount(Wei::decode_from(iter.next().unwrap())?)
+                    .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
+                    .take_and_build_payload_legacy()
+                    .map_err(|_| RlpDataDecodingError::InvalidFormat)
+            }
+        }
+    }
+}
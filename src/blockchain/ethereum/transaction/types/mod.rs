@@ -6,6 +6,7 @@
 
 pub(crate) mod transaction_eip_155;
 pub(crate) mod transaction_eip_1559;
+pub(crate) mod transaction_eip_1559_ref;
 pub(crate) mod transaction_eip_1559_rlp;
 pub(crate) mod transaction_eip_155_rlp;
 pub(crate) mod transaction_eip_2930;
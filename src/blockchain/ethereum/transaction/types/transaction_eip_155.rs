@@ -7,8 +7,9 @@
 use crate::bigint::BigUint;
 use crate::blockchain::ethereum::transaction::builder::TransactionBuildingError;
 use crate::blockchain::ethereum::transaction::payload::eip_155::PayloadEip155;
-use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, SigningOptions};
+use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, Signature, SigningOptions};
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::crypto::secp256k1;
 use crate::tools::codable::encode;
 use std::fmt;
 use std::fmt::Display;
@@ -24,16 +25,46 @@ impl TransactionEip155 {
     pub fn encode(&self) -> Vec<u8> {
         encode(self)
     }
+
+    /// Returns the transaction ID (the "transaction hash"):
+    /// `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s]))`, in which
+    /// the chain ID is folded into `v`.
+    ///
+    /// Not to confuse with [`PayloadEip155::signing_hash`], the hash the signature signs.
+    pub fn transaction_id(&self) -> [u8; 32] {
+        Keccak256::new().digest(self.encode()).try_into().unwrap()
+    }
+
+    /// Returns the payload the signature signs, see [`PayloadEip155::signing_hash`].
+    pub fn payload(&self) -> &PayloadEip155 {
+        &self.payload
+    }
+
+    /// Returns the signature as it is decoded, without checking the ranges of r and s.
+    ///
+    /// See [`Signature::is_canonical`] for the classification.
+    pub fn signature(&self) -> Signature<'static> {
+        Signature::new_unchecked(
+            self.r.as_bigint().clone(),
+            self.s.as_bigint().clone(),
+            secp256k1(),
+        )
+    }
 }
 
 impl PayloadEip155 {
+    /// Returns the hash to sign:
+    /// `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data, chainId, 0, 0]))`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Keccak256::new().digest(encode(self)).try_into().unwrap()
+    }
+
     pub fn take_and_sign_with_options(
         self,
         private_key: &PrivateKey,
         options: &SigningOptions,
     ) -> Result<TransactionEip155, TransactionBuildingError> {
-        let rlp_data = encode(&self);
-        let hash = Keccak256::new().digest(rlp_data);
+        let hash = self.signing_hash();
 
         let (signature, recovery_id) =
             ecdsa_signing::sign_with_options(&hash, private_key, options)
@@ -79,12 +110,15 @@ impl Display for TransactionEip155 {
 
 #[cfg(test)]
 mod tests {
-    use crate::bigint::{BigInt, BigUint};
+    use super::*;
+    use crate::bigint::BigInt;
+    use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
     use crate::blockchain::ethereum::transaction::builder::TransactionBuilder;
     use crate::blockchain::ethereum::types::Chain;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use crate::crypto::ecdsa::{PrivateKey, SigningOptions};
     use crate::crypto::secp256k1;
+    use crate::tools::codable::decode;
 
     // The test data is from EIP-155
     #[test]
@@ -100,7 +134,7 @@ mod tests {
             .with_chain_id(Chain::EthereumMainnet.id())
             .with_nonce(9.try_into().unwrap())
             .with_gas_price("20000000000".try_into().unwrap())
-            .with_gas_limit(21000)
+            .with_gas_limit(21000.into())
             .with_destination(
                 "0x3535353535353535353535353535353535353535"
                     .try_into()
@@ -137,7 +171,7 @@ mod tests {
         );
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             concat!(
                 "f86c098504a817c800825208943535353535",
                 "353535353535353535353535353535880de0",
@@ -147,5 +181,10 @@ mod tests {
                 "4b3800ccf555c9f3dc64214b297fb1966a3b6d83",
             )
         );
+
+        let decoded =
+            decode::<TransactionEip155, RlpDecodingItem>(&transaction.encode()).unwrap();
+        assert_eq!(decoded.payload.chain_id, Chain::EthereumMainnet.id());
+        assert_eq!(decoded.encode(), transaction.encode());
     }
 }
@@ -5,12 +5,17 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::bigint::BigUint;
+use crate::blockchain::ethereum::account::EoaPublicKey;
 use crate::blockchain::ethereum::transaction::payload::eip_1559::PayloadEip1559;
 use crate::blockchain::ethereum::transaction::TransactionBuildingError;
-use crate::blockchain::ethereum::types::TransactionType;
+use crate::blockchain::ethereum::types::{Address, TransactionType};
 use crate::crypto::ecdsa::ecdsa_core::YParity;
-use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, SigningOptions};
+use crate::crypto::ecdsa::{
+    ecdsa_signing, recover_public_keys_from_signature, PrivateKey, Signature,
+    SignatureCanonicality, SignatureRecoveryId, SigningOptions,
+};
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::crypto::secp256k1;
 use crate::tools::codable::encode;
 use std::fmt;
 use std::fmt::Display;
@@ -26,19 +31,40 @@ impl TransactionEip1559 {
     pub fn transaction_type() -> TransactionType {
         0x2
     }
+
+    /// Returns the payload the signature signs, see [`PayloadEip1559::signing_hash`].
+    pub fn payload(&self) -> &PayloadEip1559 {
+        &self.payload
+    }
+
+    /// Returns the signature as it is decoded, without checking the ranges of r and s.
+    ///
+    /// See [`Signature::is_canonical`] for the classification.
+    pub fn signature(&self) -> Signature<'static> {
+        Signature::new_unchecked(
+            self.r.as_bigint().clone(),
+            self.s.as_bigint().clone(),
+            secp256k1(),
+        )
+    }
 }
 
 impl PayloadEip1559 {
+    /// Returns the hash to sign: `keccak256(0x02 || rlp(payload))`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let payload_rlp_data = encode(self);
+        let mut message = Vec::with_capacity(payload_rlp_data.len() + 1);
+        message.push(TransactionEip1559::transaction_type());
+        message.extend(&payload_rlp_data);
+        Keccak256::new().digest(message).try_into().unwrap()
+    }
+
     pub fn take_and_sign_with_options(
         self,
         private_key: &PrivateKey,
         options: &SigningOptions,
     ) -> Result<TransactionEip1559, TransactionBuildingError> {
-        let payload_rlp_data = encode(&self);
-        let mut message = Vec::with_capacity(payload_rlp_data.len() + 1);
-        message.push(TransactionEip1559::transaction_type());
-        message.extend(&payload_rlp_data);
-        let hash = Keccak256::new().digest(message);
+        let hash = self.signing_hash();
 
         let (signature, recovery_id) =
             ecdsa_signing::sign_with_options(&hash, private_key, options)
@@ -57,6 +83,27 @@ impl PayloadEip1559 {
 }
 
 impl TransactionEip1559 {
+    /// Recovers the address of the sender from the signature.
+    ///
+    /// Returns `None` if the signature is invalid. A "high s" signature is accepted.
+    pub fn recover_sender(&self) -> Option<Address> {
+        let signature = self.signature();
+        if signature.is_canonical() == SignatureCanonicality::OutOfRange {
+            return None;
+        }
+        let recovery_id = SignatureRecoveryId::from_u8(self.y_parity as u8)?;
+        let public_keys = recover_public_keys_from_signature(
+            &signature,
+            &self.payload.signing_hash(),
+            Some(recovery_id),
+        )
+        .ok()?;
+        public_keys
+            .into_iter()
+            .next()
+            .map(|public_key| EoaPublicKey(public_key).address())
+    }
+
     pub fn encode(&self) -> Vec<u8> {
         let rlp_data = encode(self);
         let mut data = Vec::with_capacity(rlp_data.len() + 1);
@@ -66,6 +113,15 @@ impl TransactionEip1559 {
 
         data
     }
+
+    /// Returns the transaction ID (the "transaction hash"):
+    /// `keccak256(0x02 || rlp(transaction))`, the hash of
+    /// [`TransactionEip1559::encode`], which covers the signature.
+    ///
+    /// Not to confuse with [`PayloadEip1559::signing_hash`], the hash the signature signs.
+    pub fn transaction_id(&self) -> [u8; 32] {
+        Keccak256::new().digest(self.encode()).try_into().unwrap()
+    }
 }
 
 impl Display for TransactionEip1559 {
@@ -91,12 +147,18 @@ impl Display for TransactionEip1559 {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::bigint::BigInt;
-    use crate::blockchain::ethereum::transaction::TransactionBuilder;
-    use crate::blockchain::ethereum::types::{AccessList, AccessListItem};
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+    use crate::blockchain::ethereum::transaction::{
+        TransactionBuilder, TransactionBuildingError,
+    };
+    use crate::blockchain::ethereum::types::{AccessList, AccessListItem, EoaNonce, GasLimit};
+    use crate::crypto::codecs::hex;
     use crate::crypto::ecdsa::{PrivateKey, SigningOptions};
     use crate::crypto::secp256k1;
+    use crate::testing_tools::ethereum::private_key_hex_to_address;
+    use crate::tools::codable::decode;
 
     #[test]
     fn test_common() {
@@ -112,7 +174,7 @@ mod tests {
             .with_nonce(42.try_into().unwrap())
             .with_max_priority_fee_per_gas("0x42".try_into().unwrap())
             .with_max_fee_per_gas("0x0143".try_into().unwrap())
-            .with_gas_limit(0x5208)
+            .with_gas_limit(0x5208.into())
             .with_destination(
                 "0x123456789a123456789a123456789a123456789a"
                     .try_into()
@@ -141,7 +203,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             concat!(
                 "02f89f7b2a4282014382520894123456789a",
                 "123456789a123456789a123456789a820123",
@@ -154,5 +216,154 @@ mod tests {
                 "b37a7408b45a253f0639a7875fe64f49f0b6"
             )
         );
+        assert_eq!(
+            transaction.recover_sender().unwrap().to_string(),
+            private_key_hex_to_address(
+                "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d"
+            )
+        );
+
+        let data = transaction.encode();
+        let decoded = decode::<TransactionEip1559, RlpDecodingItem>(&data[1..]).unwrap();
+        assert_eq!(decoded.payload.gas_limit, GasLimit::from(21000));
+        assert_eq!(decoded.encode(), data);
+    }
+
+    #[test]
+    fn test_sign_from_template() {
+        let curve = secp256k1();
+        let d = BigInt::from_hex(
+            "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+        )
+        .unwrap();
+        let private_key = PrivateKey::new(d, curve).unwrap();
+        let sender = private_key_hex_to_address(
+            "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+        );
+        let options = SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+
+        let builder = |nonce: Option<u64>| {
+            let builder = TransactionBuilder::new()
+                .with_chain_id(123.into())
+                .with_max_priority_fee_per_gas("0x42".try_into().unwrap())
+                .with_max_fee_per_gas("0x0143".try_into().unwrap())
+                .with_gas_limit(0x5208.into())
+                .with_destination(
+                    "0x123456789a123456789a123456789a123456789a"
+                        .try_into()
+                        .unwrap(),
+                )
+                .with_amount("0x0123".try_into().unwrap());
+            match nonce {
+                None => builder,
+                Some(n) => builder.with_nonce(EoaNonce::from_u64(n).unwrap()),
+            }
+        };
+
+        // Everything but the nonce.
+        let template = builder(None);
+        assert_eq!(
+            template.build_payload_eip_1559().err(),
+            Some(TransactionBuildingError::MissingFields)
+        );
+
+        let mut encoded_transactions = Vec::with_capacity(100);
+        for n in 0..100 {
+            let payload = template
+                .clone()
+                .with_nonce(EoaNonce::from_u64(n).unwrap())
+                .build_payload_eip_1559()
+                .unwrap();
+            assert_eq!(payload.nonce.value(), n);
+            let expected_payload = builder(Some(n)).take_and_build_payload_eip_1559().unwrap();
+            assert_eq!(payload.signing_hash(), expected_payload.signing_hash());
+
+            let transaction = payload
+                .take_and_sign_with_options(&private_key, &options)
+                .unwrap();
+            // Recovering is slow, only a few are checked.
+            if n % 33 == 0 {
+                assert_eq!(transaction.recover_sender().unwrap().to_string(), sender);
+            }
+            encoded_transactions.push(transaction.encode());
+        }
+
+        encoded_transactions.sort();
+        encoded_transactions.dedup();
+        assert_eq!(encoded_transactions.len(), 100);
+    }
+
+    #[test]
+    fn test_high_s_malleability() {
+        let data = hex::decode(concat!(
+            "02f89f7b2a4282014382520894123456789a",
+            "123456789a123456789a123456789a820123",
+            "80f838f794123456789a123456789a123456",
+            "789a123456789ae1a00123456789abcdef01",
+            "23456789abcdef0123456789abcdef012345",
+            "6789abcdef80a02cd518c375dfd2231b9352",
+            "e600a559cd1c7dd38ed46f4e470bde6723aa",
+            "85ab90a0432f760d25c8aa48ee9933b81821",
+            "b37a7408b45a253f0639a7875fe64f49f0b6"
+        ))
+        .unwrap();
+        let sender = private_key_hex_to_address(
+            "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+        );
+        let transaction = decode::<TransactionEip1559, RlpDecodingItem>(&data[1..]).unwrap();
+        assert_eq!(transaction.encode(), data);
+        assert_eq!(
+            transaction.signature().is_canonical(),
+            SignatureCanonicality::Canonical
+        );
+
+        // s replaced with n - s: the signing hash stays, the ID changes.
+        let n = &secp256k1().base_point_order;
+        let high_s = BigUint::from_bigint(n - transaction.s.as_bigint()).unwrap();
+        let malleated = |y_parity| {
+            let mut malleated =
+                decode::<TransactionEip1559, RlpDecodingItem>(&data[1..]).unwrap();
+            malleated.y_parity = y_parity;
+            malleated.s = high_s.clone();
+            malleated
+        };
+        let flipped_y_parity = match transaction.y_parity {
+            YParity::Even => YParity::Odd,
+            YParity::Odd => YParity::Even,
+        };
+
+        // With the y parity flipped, the sender is the same, but the signature is "high s",
+        // which is rejected since EIP-2.
+        let malleated_transaction = malleated(flipped_y_parity);
+        assert_eq!(
+            malleated_transaction.payload.signing_hash(),
+            transaction.payload.signing_hash()
+        );
+        assert_ne!(
+            malleated_transaction.transaction_id(),
+            transaction.transaction_id()
+        );
+        assert_eq!(
+            malleated_transaction.signature().is_canonical(),
+            SignatureCanonicality::HighS
+        );
+        assert_eq!(
+            malleated_transaction.recover_sender().unwrap().to_string(),
+            sender
+        );
+
+        // With the y parity kept, another sender is recovered.
+        let malleated_transaction = malleated(transaction.y_parity);
+        assert_ne!(
+            malleated_transaction.transaction_id(),
+            transaction.transaction_id()
+        );
+        assert_ne!(
+            malleated_transaction.recover_sender().unwrap().to_string(),
+            sender
+        );
     }
 }
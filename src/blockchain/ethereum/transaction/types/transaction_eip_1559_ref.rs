@@ -0,0 +1,180 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::transaction_eip_1559::TransactionEip1559;
+use crate::bigint::BigUint;
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+use crate::blockchain::ethereum::transaction::PayloadEip1559;
+use crate::blockchain::ethereum::types::{
+    AccessList, AccessListItemRef, Address, AddressData, ChainId, EoaNonce, GasLimit, Wei,
+};
+use crate::crypto::ecdsa::ecdsa_core::YParity;
+use crate::tools::codable::decode;
+
+/// A `TransactionEip1559` decoded without copying the byte fields: the destination, the
+/// data and the access list borrow from the data it is decoded from.
+///
+/// Scanning transactions with large data this way doesn't allocate for the data.
+#[derive(Clone, Debug)]
+pub struct TransactionEip1559Ref<'a> {
+    pub(crate) chain_id: ChainId,
+    pub(crate) nonce: EoaNonce,
+    pub(crate) max_priority_fee_per_gas: Wei,
+    pub(crate) max_fee_per_gas: Wei,
+    pub(crate) gas_limit: GasLimit,
+    pub(crate) destination: &'a AddressData,
+    pub(crate) amount: Wei,
+    pub(crate) data: &'a [u8],
+    pub(crate) access_list: Vec<AccessListItemRef<'a>>,
+    pub(crate) y_parity: YParity,
+    pub(crate) r: BigUint,
+    pub(crate) s: BigUint,
+}
+
+impl<'a> TransactionEip1559Ref<'a> {
+    /// Decodes a transaction from `data`, the output of [`TransactionEip1559::encode`]:
+    /// `0x02 || rlp(transaction)`.
+    pub fn decode_borrowed(data: &'a [u8]) -> Result<Self, RlpDataDecodingError> {
+        match data.split_first() {
+            Some((&transaction_type, rlp_data))
+                if transaction_type == TransactionEip1559::transaction_type() =>
+            {
+                decode::<_, RlpDecodingItem>(rlp_data)
+            }
+            _ => Err(RlpDataDecodingError::InvalidFormat),
+        }
+    }
+
+    pub fn destination(&self) -> &'a AddressData {
+        self.destination
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn access_list(&self) -> &[AccessListItemRef<'a>] {
+        &self.access_list
+    }
+
+    /// Copies the borrowed fields into a `TransactionEip1559`.
+    pub fn to_transaction(&self) -> TransactionEip1559 {
+        let payload = PayloadEip1559 {
+            chain_id: self.chain_id.clone(),
+            nonce: self.nonce.clone(),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.clone(),
+            max_fee_per_gas: self.max_fee_per_gas.clone(),
+            gas_limit: self.gas_limit,
+            destination: Address(*self.destination),
+            amount: self.amount.clone(),
+            data: self.data.to_vec(),
+            access_list: AccessList(
+                self.access_list
+                    .iter()
+                    .map(AccessListItemRef::to_access_list_item)
+                    .collect(),
+            ),
+        };
+
+        TransactionEip1559 {
+            payload,
+            y_parity: self.y_parity,
+            r: self.r.clone(),
+            s: self.s.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+    use crate::blockchain::ethereum::transaction::TransactionBuilder;
+    use crate::blockchain::ethereum::types::{AccessListItem, StorageKey};
+    use crate::crypto::ecdsa::{PrivateKey, SigningOptions};
+    use crate::crypto::secp256k1;
+    use crate::tools::codable::encode;
+
+    fn signed_transaction(data: Vec<u8>) -> TransactionEip1559 {
+        let private_key = PrivateKey::new(
+            BigInt::from_hex(
+                "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d",
+            )
+            .unwrap(),
+            secp256k1(),
+        )
+        .unwrap();
+        let options = SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+
+        TransactionBuilder::new()
+            .with_chain_id(1.into())
+            .with_nonce(42.try_into().unwrap())
+            .with_max_priority_fee_per_gas("0x42".try_into().unwrap())
+            .with_max_fee_per_gas("0x0143".try_into().unwrap())
+            .with_gas_limit(0x5208.into())
+            .with_destination(
+                "0x123456789a123456789a123456789a123456789a"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount("0x0123".try_into().unwrap())
+            .with_data(data)
+            .with_access_list(AccessList(vec![AccessListItem {
+                address: "0xabcdef0123abcdef0123abcdef0123abcdef0123"
+                    .try_into()
+                    .unwrap(),
+                storage_keys: vec![StorageKey([0x11; 32]), StorageKey([0x22; 32])],
+            }]))
+            .take_and_build_payload_eip_1559()
+            .unwrap()
+            .take_and_sign_with_options(&private_key, &options)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_borrowed() {
+        let transaction = signed_transaction(vec![0xab; 1000]);
+        let data = transaction.encode();
+
+        let borrowed = TransactionEip1559Ref::decode_borrowed(&data).unwrap();
+        assert_eq!(borrowed.data(), &[0xab; 1000]);
+        assert_eq!(borrowed.destination(), &transaction.payload.destination.0);
+        assert_eq!(borrowed.access_list().len(), 1);
+        assert_eq!(borrowed.access_list()[0].storage_keys[1], &[0x22; 32]);
+
+        // The byte fields lie within `data`.
+        let range = data.as_ptr_range();
+        assert!(range.contains(&borrowed.data().as_ptr()));
+        assert!(range.contains(&borrowed.destination().as_ptr()));
+        assert!(range.contains(&borrowed.access_list()[0].address.as_ptr()));
+
+        let owned = borrowed.to_transaction();
+        assert_eq!(owned.encode(), data);
+        assert_eq!(encode(&owned.payload), encode(&transaction.payload));
+        assert_eq!(owned.y_parity, transaction.y_parity);
+        assert_eq!(owned.r, transaction.r);
+        assert_eq!(owned.s, transaction.s);
+    }
+
+    #[test]
+    fn test_decode_borrowed_err_cases() {
+        let data = signed_transaction(vec![]).encode();
+
+        // missing or wrong transaction type
+        assert!(TransactionEip1559Ref::decode_borrowed(&[]).is_err());
+        assert!(TransactionEip1559Ref::decode_borrowed(&data[1..]).is_err());
+        let mut wrong_type = data.clone();
+        wrong_type[0] = 0x01;
+        assert!(TransactionEip1559Ref::decode_borrowed(&wrong_type).is_err());
+
+        // truncated
+        assert!(TransactionEip1559Ref::decode_borrowed(&data[..data.len() - 1]).is_err());
+    }
+}
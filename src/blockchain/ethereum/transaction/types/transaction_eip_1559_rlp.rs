@@ -5,15 +5,21 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::transaction_eip_1559::TransactionEip1559;
+use super::transaction_eip_1559_ref::TransactionEip1559Ref;
 use crate::bigint::BigUint;
 use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::rlp::RlpItemType;
 use crate::blockchain::ethereum::transaction::TransactionBuilder;
-use crate::blockchain::ethereum::types::{AccessList, Address, ChainId, EoaNonce, Wei};
+use crate::blockchain::ethereum::types::{
+    AccessList, AccessListItemRef, Address, AddressData, ChainId, EoaNonce, GasLimit, Wei,
+};
 use crate::crypto::ecdsa::ecdsa_core::YParity;
-use crate::tools::codable::{Decodable, Encodable, EncodingItem};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => TransactionEip1559);
+seal_codable!(<'a> RlpDecodingItem<'a> => TransactionEip1559, TransactionEip1559Ref<'a>);
 
 impl Encodable<RlpEncodingItem> for TransactionEip1559 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -43,7 +49,10 @@ impl Encodable<RlpEncodingItem> for TransactionEip1559 {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip1559 {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         return match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let items = decoding_item.decode_as_items()?;
                 if items.len() != 12 {
@@ -56,7 +65,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip1559 {
                     .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
                     .with_max_priority_fee_per_gas(Wei::decode_from(iter.next().unwrap())?)
                     .with_max_fee_per_gas(Wei::decode_from(iter.next().unwrap())?)
-                    .with_gas_limit(u64::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
                     .with_destination(Address::decode_from(iter.next().unwrap())?)
                     .with_amount(Wei::decode_from(iter.next().unwrap())?)
                     .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
@@ -64,15 +73,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip1559 {
                     .take_and_build_payload_eip_1559()
                     .map_err(|_| RlpDataDecodingError::InvalidFormat)?;
 
-                let y_parity_u64 = u64::decode_from(iter.next().unwrap())?;
-                let y_parity_u8 = u8::try_from(y_parity_u64)
-                    .map_err(|_| RlpDataDecodingError::InvalidFormat)?;
-                let y_parity = match YParity::from_u8(y_parity_u8) {
-                    None => {
-                        return Err(RlpDataDecodingError::InvalidFormat);
-                    }
-                    Some(y_parity) => y_parity,
-                };
+                let y_parity = decode_y_parity(iter.next().unwrap())?;
                 let r = BigUint::decode_from(iter.next().unwrap())?;
                 let s = BigUint::decode_from(iter.next().unwrap())?;
                 Ok(TransactionEip1559 {
@@ -85,3 +86,35 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip1559 {
         };
     }
 }
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip1559Ref<'a> {
+    fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
+        let items = decoding_item.decode_as_items()?;
+        if items.len() != 12 {
+            return Err(RlpDataDecodingError::InvalidFormat);
+        }
+        let mut iter = items.iter();
+
+        Ok(TransactionEip1559Ref {
+            chain_id: ChainId::decode_from(iter.next().unwrap())?,
+            nonce: EoaNonce::decode_from(iter.next().unwrap())?,
+            max_priority_fee_per_gas: Wei::decode_from(iter.next().unwrap())?,
+            max_fee_per_gas: Wei::decode_from(iter.next().unwrap())?,
+            gas_limit: GasLimit::decode_from(iter.next().unwrap())?,
+            destination: <&AddressData>::decode_from(iter.next().unwrap())?,
+            amount: Wei::decode_from(iter.next().unwrap())?,
+            data: <&[u8]>::decode_from(iter.next().unwrap())?,
+            access_list: Vec::<AccessListItemRef>::decode_from(iter.next().unwrap())?,
+            y_parity: decode_y_parity(iter.next().unwrap())?,
+            r: BigUint::decode_from(iter.next().unwrap())?,
+            s: BigUint::decode_from(iter.next().unwrap())?,
+        })
+    }
+}
+
+fn decode_y_parity(decoding_item: &RlpDecodingItem) -> Result<YParity, RlpDataDecodingError> {
+    let y_parity_u64 = u64::decode_from(decoding_item)?;
+    let y_parity_u8 =
+        u8::try_from(y_parity_u64).map_err(|_| RlpDataDecodingError::InvalidFormat)?;
+    YParity::from_u8(y_parity_u8).ok_or(RlpDataDecodingError::InvalidFormat)
+}
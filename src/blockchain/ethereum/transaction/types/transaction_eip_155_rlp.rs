@@ -11,8 +11,11 @@ use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::rlp::RlpItemType;
 use crate::blockchain::ethereum::transaction::TransactionBuilder;
-use crate::blockchain::ethereum::types::{Address, EoaNonce, Wei};
-use crate::tools::codable::{Decodable, Encodable, EncodingItem};
+use crate::blockchain::ethereum::types::{Address, EoaNonce, GasLimit, Wei};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => TransactionEip155);
+seal_codable!(<'a> RlpDecodingItem<'a> => TransactionEip155);
 
 impl Encodable<RlpEncodingItem> for TransactionEip155 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -34,7 +37,10 @@ impl Encodable<RlpEncodingItem> for TransactionEip155 {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip155 {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         return match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let items = decoding_item.decode_as_items()?;
                 if items.len() != 9 {
@@ -44,7 +50,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip155 {
 
                 let nonce = EoaNonce::decode_from(iter.next().unwrap())?;
                 let gas_price = Wei::decode_from(iter.next().unwrap())?;
-                let gas_limit = u64::decode_from(iter.next().unwrap())?;
+                let gas_limit = GasLimit::decode_from(iter.next().unwrap())?;
                 let destination = Address::decode_from(iter.next().unwrap())?;
                 let amount = Wei::decode_from(iter.next().unwrap())?;
                 let data = Vec::<u8>::decode_from(iter.next().unwrap())?;
@@ -59,7 +65,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip155 {
                 if v < n_35 {
                     return Err(RlpDataDecodingError::TransactionTypeMismatch);
                 }
-                let chain_id_n = (&v - n_35) >> 2;
+                let chain_id_n = (&v - n_35) >> 1;
 
                 let payload = TransactionBuilder::new()
                     .with_chain_id(chain_id_n.into())
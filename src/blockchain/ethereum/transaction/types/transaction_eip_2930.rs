@@ -9,8 +9,9 @@ use crate::blockchain::ethereum::transaction::payload::eip_2930::PayloadEip2930;
 use crate::blockchain::ethereum::transaction::TransactionBuildingError;
 use crate::blockchain::ethereum::types::TransactionType;
 use crate::crypto::ecdsa::ecdsa_core::YParity;
-use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, SigningOptions};
+use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, Signature, SigningOptions};
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::crypto::secp256k1;
 use crate::tools::codable::encode;
 use std::fmt;
 use std::fmt::Display;
@@ -26,24 +27,45 @@ impl TransactionEip2930 {
     pub fn transaction_type() -> TransactionType {
         0x1
     }
+
+    /// Returns the payload the signature signs, see [`PayloadEip2930::signing_hash`].
+    pub fn payload(&self) -> &PayloadEip2930 {
+        &self.payload
+    }
+
+    /// Returns the signature as it is decoded, without checking the ranges of r and s.
+    ///
+    /// See [`Signature::is_canonical`] for the classification.
+    pub fn signature(&self) -> Signature<'static> {
+        Signature::new_unchecked(
+            self.r.as_bigint().clone(),
+            self.s.as_bigint().clone(),
+            secp256k1(),
+        )
+    }
 }
 
 impl PayloadEip2930 {
-    pub fn take_and_sign_with_options(
-        self,
-        private_key: &PrivateKey,
-        options: &SigningOptions,
-    ) -> Result<TransactionEip2930, TransactionBuildingError> {
+    /// Returns the hash to sign: `keccak256(0x01 || rlp(payload))`.
+    pub fn signing_hash(&self) -> [u8; 32] {
         // ...The signatureYParity, signatureR, signatureS elements of this transaction
         // represent a secp256k1 signature over
         // keccak256(0x01 || rlp([chainId, nonce, gasPrice, gasLimit, to, value, data, accessList]))...
         //
         // -- from EIP-2930
-        let payload_rlp_data = encode(&self);
+        let payload_rlp_data = encode(self);
         let mut message = Vec::with_capacity(payload_rlp_data.len() + 1);
         message.push(TransactionEip2930::transaction_type());
         message.extend(&payload_rlp_data);
-        let hash = Keccak256::new().digest(message);
+        Keccak256::new().digest(message).try_into().unwrap()
+    }
+
+    pub fn take_and_sign_with_options(
+        self,
+        private_key: &PrivateKey,
+        options: &SigningOptions,
+    ) -> Result<TransactionEip2930, TransactionBuildingError> {
+        let hash = self.signing_hash();
 
         let (signature, recovery_id) =
             ecdsa_signing::sign_with_options(&hash, private_key, options)
@@ -71,6 +93,15 @@ impl TransactionEip2930 {
 
         data
     }
+
+    /// Returns the transaction ID (the "transaction hash"):
+    /// `keccak256(0x01 || rlp(transaction))`, the hash of
+    /// [`TransactionEip2930::encode`], which covers the signature.
+    ///
+    /// Not to confuse with [`PayloadEip2930::signing_hash`], the hash the signature signs.
+    pub fn transaction_id(&self) -> [u8; 32] {
+        Keccak256::new().digest(self.encode()).try_into().unwrap()
+    }
 }
 
 impl Display for TransactionEip2930 {
@@ -98,7 +129,7 @@ impl Display for TransactionEip2930 {
 mod tests {
     use crate::bigint::BigInt;
     use crate::blockchain::ethereum::transaction::TransactionBuilder;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::crypto::ecdsa::{PrivateKey, SigningOptions};
     use crate::crypto::secp256k1;
 
@@ -115,14 +146,14 @@ mod tests {
             .with_chain_id(144.into())
             .with_nonce(9.try_into().unwrap())
             .with_gas_price("0x765898be".try_into().unwrap())
-            .with_gas_limit(0xbb)
+            .with_gas_limit(0xbb.into())
             .with_destination(
                 "0xf933abf2475062e0f3e7bde89da3f6c9e6963b67"
                     .try_into()
                     .unwrap(),
             )
             .with_amount("0xd7".try_into().unwrap())
-            .with_data(hex_to_bytes("74a53d7a649760e78359").unwrap())
+            .with_data(hex::decode("74a53d7a649760e78359").unwrap())
             .take_and_build_payload_eip_2930()
             .unwrap()
             .take_and_sign_with_options(
@@ -135,7 +166,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             concat!(
                 "01f87081900984765898be81bb94f933abf2",
                 "475062e0f3e7bde89da3f6c9e6963b6781d7",
@@ -11,9 +11,14 @@ use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::rlp::RlpItemType;
 use crate::blockchain::ethereum::transaction::TransactionBuilder;
-use crate::blockchain::ethereum::types::{AccessList, Address, ChainId, EoaNonce, Wei};
+use crate::blockchain::ethereum::types::{
+    AccessList, Address, ChainId, EoaNonce, GasLimit, Wei,
+};
 use crate::crypto::ecdsa::ecdsa_core::YParity;
-use crate::tools::codable::{Decodable, Encodable, EncodingItem};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => TransactionEip2930);
+seal_codable!(<'a> RlpDecodingItem<'a> => TransactionEip2930);
 
 impl Encodable<RlpEncodingItem> for TransactionEip2930 {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -38,7 +43,10 @@ impl Encodable<RlpEncodingItem> for TransactionEip2930 {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip2930 {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         return match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let items = decoding_item.decode_as_items()?;
                 if items.len() != 11 {
@@ -50,7 +58,7 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionEip2930 {
                     .with_chain_id(ChainId::decode_from(iter.next().unwrap())?)
                     .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
                     .with_gas_price(Wei::decode_from(iter.next().unwrap())?)
-                    .with_gas_limit(u64::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
                     .with_destination(Address::decode_from(iter.next().unwrap())?)
                     .with_amount(Wei::decode_from(iter.next().unwrap())?)
                     .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
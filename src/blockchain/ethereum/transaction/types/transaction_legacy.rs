@@ -5,35 +5,135 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::bigint::BigUint;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::transaction::builder::TransactionBuildingError;
 use crate::blockchain::ethereum::transaction::payload::legacy::PayloadLegacy;
-use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, SigningOptions};
+use crate::blockchain::ethereum::types::ChainId;
+use crate::crypto::ecdsa::{ecdsa_signing, PrivateKey, Signature, SigningOptions};
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
-use crate::tools::codable::encode;
+use crate::crypto::secp256k1;
+use crate::tools::codable::{encode, Encodable, EncodingItem};
 use std::fmt;
 use std::fmt::Display;
 
 pub struct TransactionLegacy {
     pub(crate) payload: PayloadLegacy,
-    pub(crate) v: u8,
+    pub(crate) v: BigUint,
     pub(crate) r: BigUint,
     pub(crate) s: BigUint,
+    /// The chain ID parsed from `v`, `None` if the transaction isn't replay-protected.
+    pub(crate) chain_id: Option<ChainId>,
 }
 
 impl TransactionLegacy {
     pub fn encode(&self) -> Vec<u8> {
         encode(self)
     }
+
+    /// Returns the transaction ID (the "transaction hash"): `keccak256(rlp(transaction))`,
+    /// which covers the signature.
+    ///
+    /// Not to confuse with [`TransactionLegacy::signing_hash`], the hash the signature signs.
+    pub fn transaction_id(&self) -> [u8; 32] {
+        Keccak256::new().digest(self.encode()).try_into().unwrap()
+    }
+
+    /// Returns the hash the signature signs, which depends on whether the transaction is
+    /// replay-protected:
+    ///
+    /// - With a chain ID (EIP-155):
+    ///   `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data, chainId, 0, 0]))`.
+    /// - Otherwise, [`PayloadLegacy::signing_hash`]:
+    ///   `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data]))`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        let chain_id = match &self.chain_id {
+            Some(chain_id) => chain_id,
+            None => return self.payload.signing_hash(),
+        };
+
+        // See `PayloadEip155`.
+        let mut list_encoding_item = RlpEncodingItem::new();
+        self.payload.nonce.encode_to(&mut list_encoding_item);
+        self.payload.gas_price.encode_to(&mut list_encoding_item);
+        self.payload.gas_limit.encode_to(&mut list_encoding_item);
+        self.payload.destination.encode_to(&mut list_encoding_item);
+        self.payload.amount.encode_to(&mut list_encoding_item);
+        self.payload.data.encode_to(&mut list_encoding_item);
+        chain_id.encode_to(&mut list_encoding_item);
+        0_u64.encode_to(&mut list_encoding_item);
+        0_u64.encode_to(&mut list_encoding_item);
+
+        let mut encoding_item = RlpEncodingItem::new();
+        encoding_item.encode_list_payload(&mut list_encoding_item);
+        Keccak256::new()
+            .digest(encoding_item.take_data())
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns the chain ID of an EIP-155 transaction decoded as a legacy one, or `None`
+    /// if the transaction is signed without a chain ID (`v` is 27 or 28).
+    pub fn chain_id(&self) -> Option<&ChainId> {
+        self.chain_id.as_ref()
+    }
+
+    /// Returns true if the signature commits to a chain ID (EIP-155), which prevents the
+    /// transaction from being replayed on other chains.
+    pub fn is_replay_protected(&self) -> bool {
+        self.chain_id.is_some()
+    }
+
+    /// Returns the payload fields, without the chain ID of a replay-protected transaction.
+    ///
+    /// [`PayloadLegacy::signing_hash`] of the payload is the hash the signature signs only
+    /// if the transaction isn't replay-protected, see [`TransactionLegacy::signing_hash`].
+    pub fn payload(&self) -> &PayloadLegacy {
+        &self.payload
+    }
+
+    /// Returns the signature as it is decoded, without checking the ranges of r and s,
+    /// e.g., the "high s" signature of a transaction before EIP-2 (Homestead).
+    ///
+    /// See [`Signature::is_canonical`] for the classification.
+    pub fn signature(&self) -> Signature<'static> {
+        Signature::new_unchecked(
+            self.r.as_bigint().clone(),
+            self.s.as_bigint().clone(),
+            secp256k1(),
+        )
+    }
+}
+
+/// Parses the chain ID from the `v` of a legacy transaction: `v` is
+/// `{0,1} + CHAIN_ID * 2 + 35` for EIP-155 transactions and 27 or 28 otherwise.
+///
+/// Returns `RlpDataDecodingError::InvalidFormat` for any other `v`.
+pub(crate) fn chain_id_from_v(v: &BigUint) -> Result<Option<ChainId>, RlpDataDecodingError> {
+    let n_35 = BigUint::from(35_u8);
+    if v >= &n_35 {
+        return Ok(Some(ChainId((v - n_35) >> 1)));
+    }
+    if *v == BigUint::from(27_u8) || *v == BigUint::from(28_u8) {
+        return Ok(None);
+    }
+
+    Err(RlpDataDecodingError::InvalidFormat)
 }
 
 impl PayloadLegacy {
+    /// Returns the hash to sign:
+    /// `keccak256(rlp([nonce, gasPrice, gasLimit, to, value, data]))`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        Keccak256::new().digest(encode(self)).try_into().unwrap()
+    }
+
     pub fn take_and_sign_with_options(
         self,
         private_key: &PrivateKey,
         options: &SigningOptions,
     ) -> Result<TransactionLegacy, TransactionBuildingError> {
-        let rlp_data = encode(&self);
-        let hash = Keccak256::new().digest(rlp_data);
+        let hash = self.signing_hash();
 
         let (signature, recovery_id) =
             ecdsa_signing::sign_with_options(&hash, private_key, options)
@@ -43,13 +143,14 @@ impl PayloadLegacy {
 
         // "...Tw = 27 + Ty..."
         // See Ethereum Yellow Paper, 4.2. The Transaction.
-        let v = 27 + recovery_id.y_parity() as u8;
+        let v = BigUint::from(27 + recovery_id.y_parity() as u8);
 
         Ok(TransactionLegacy {
             payload: self,
             v,
             r,
             s,
+            chain_id: None,
         })
     }
 }
@@ -68,7 +169,7 @@ impl Display for TransactionLegacy {
         writeln!(f, "---------")?;
         writeln!(f, "Signature")?;
         writeln!(f, "---------")?;
-        writeln!(f, "v: 0x{:x} ({})", self.v, self.v)?;
+        writeln!(f, "v: {}", self.v)?;
         writeln!(f, "r: {}", self.r)?;
         writeln!(f, "s: {}", self.s)?;
         Ok(())
@@ -77,11 +178,17 @@ impl Display for TransactionLegacy {
 
 #[cfg(test)]
 mod tests {
-    use crate::bigint::{BigInt, BigUint};
+    use super::*;
+    use crate::bigint::BigInt;
+    use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
     use crate::blockchain::ethereum::transaction::builder::TransactionBuilder;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
-    use crate::crypto::ecdsa::{PrivateKey, SigningOptions};
-    use crate::crypto::secp256k1;
+    use crate::blockchain::ethereum::types::Chain;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::ecdsa::{
+        verify, verify_with_options, PrivateKey, SignatureCanonicality, SigningOptions,
+        VerifyingError, VerifyingOptions,
+    };
+    use crate::tools::codable::decode;
 
     #[test]
     fn test_common() {
@@ -95,7 +202,7 @@ mod tests {
         let transaction = TransactionBuilder::new()
             .with_nonce(0.try_into().unwrap())
             .with_gas_price("0x09184e72a000".try_into().unwrap())
-            .with_gas_limit(0x2710)
+            .with_gas_limit(0x2710.into())
             .with_destination(
                 "0x0000000000000000000000000000000000000000"
                     .try_into()
@@ -103,7 +210,7 @@ mod tests {
             )
             .with_amount(BigUint::from(0_u8).into())
             .with_data(
-                hex_to_bytes(
+                hex::decode(
                     "7f7465737432000000000000000000000000000000000000000000000000000000600057",
                 )
                 .unwrap(),
@@ -120,7 +227,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             concat!(
                 "f889808609184e72a0008227109400000000",
                 "0000000000000000000000000000000080a4",
@@ -133,4 +240,179 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_decode_pre_eip_155() {
+        // The transaction of `test_common`
+        let data = hex::decode(concat!(
+            "f889808609184e72a0008227109400000000",
+            "0000000000000000000000000000000080a4",
+            "7f7465737432000000000000000000000000",
+            "000000000000000000000000000000600057",
+            "1ba070bad1a10475d4b24e8227978077233c",
+            "3367a7642701db223465793e68d368b3a07e",
+            "3d131ef92c04eca4e48f1f5c0d2ea971f280",
+            "2d0e61ec21c8354b605ad286c0"
+        ))
+        .unwrap();
+
+        let transaction = decode::<TransactionLegacy, RlpDecodingItem>(&data).unwrap();
+        assert_eq!(transaction.v, BigUint::from(27_u8));
+        assert_eq!(transaction.chain_id(), None);
+        assert!(!transaction.is_replay_protected());
+        assert_eq!(transaction.encode(), data);
+    }
+
+    #[test]
+    fn test_decode_high_s() {
+        // The transaction of `test_common`, with s negated (n - s) and y parity flipped
+        // (v 28): the same transaction signed by the same key, in the "high s" form
+        // accepted before EIP-2 (Homestead).
+        //
+        // TODO: replace with a "high s" transaction mined before Homestead (block 1150000).
+        let data = hex::decode(concat!(
+            "f889808609184e72a0008227109400000000",
+            "0000000000000000000000000000000080a4",
+            "7f7465737432000000000000000000000000",
+            "000000000000000000000000000000600057",
+            "1ba070bad1a10475d4b24e8227978077233c",
+            "3367a7642701db223465793e68d368b3a07e",
+            "3d131ef92c04eca4e48f1f5c0d2ea971f280",
+            "2d0e61ec21c8354b605ad286c0"
+        ))
+        .unwrap();
+        let low_s = decode::<TransactionLegacy, RlpDecodingItem>(&data).unwrap();
+        assert_eq!(
+            low_s.signature().is_canonical(),
+            SignatureCanonicality::Canonical
+        );
+
+        let n = BigUint::from_bigint(secp256k1().base_point_order.clone()).unwrap();
+        let high_s = TransactionLegacy {
+            v: BigUint::from(28_u8),
+            s: n - low_s.s,
+            ..low_s
+        };
+        let high_s_data = high_s.encode();
+
+        // decode, inspect and re-encode unchanged
+        let transaction = decode::<TransactionLegacy, RlpDecodingItem>(&high_s_data).unwrap();
+        let signature = transaction.signature();
+        assert_eq!(signature.is_canonical(), SignatureCanonicality::HighS);
+        assert_eq!(signature.components().1, transaction.s.as_bigint());
+        assert_eq!(transaction.encode(), high_s_data);
+
+        // Verifies unless "low s" is enforced.
+        let d = BigInt::from_hex(
+            "164122e5d39e9814ca723a749253663bafb07f6af91704d9754c361eb315f0c1",
+        )
+        .unwrap();
+        let private_key = PrivateKey::new(d, secp256k1()).unwrap();
+        let public_key = private_key.public_key();
+        let hash = Keccak256::new().digest(encode(&transaction.payload));
+        assert_eq!(verify(&hash, &signature, &public_key), Ok(true));
+        assert_eq!(
+            verify_with_options(
+                &hash,
+                &signature,
+                &public_key,
+                &VerifyingOptions {
+                    enforce_low_s: true,
+                    ..Default::default()
+                }
+            ),
+            Err(VerifyingError::StrictHighSFound)
+        );
+    }
+
+    #[test]
+    fn test_decode_eip_155() {
+        // The example of EIP-155, chain ID 1 and v 37
+        let data = hex::decode(concat!(
+            "f86c098504a817c800825208943535353535",
+            "353535353535353535353535353535880de0",
+            "b6b3a76400008025a028ef61340bd939bc21",
+            "95fe537567866003e1a15d3c71ff63e15906",
+            "20aa636276a067cbe9d8997f761aecb70330",
+            "4b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        ))
+        .unwrap();
+
+        let transaction = decode::<TransactionLegacy, RlpDecodingItem>(&data).unwrap();
+        assert_eq!(transaction.v, BigUint::from(37_u8));
+        assert_eq!(transaction.chain_id(), Some(&Chain::EthereumMainnet.id()));
+        assert!(transaction.is_replay_protected());
+        assert_eq!(transaction.encode(), data);
+
+        // "...signing hash: 0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        let signing_hash = transaction.signing_hash();
+        assert_eq!(
+            hex::encode_lower(&signing_hash),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_ne!(signing_hash, transaction.payload().signing_hash());
+
+        // The signature verifies against the chain-aware hash.
+        let d = BigInt::from_hex(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let private_key = PrivateKey::new(d, secp256k1()).unwrap();
+        let public_key = private_key.public_key();
+        assert_eq!(
+            verify(&signing_hash, &transaction.signature(), &public_key),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_signing_hash_pre_eip_155() {
+        // The transaction of `test_common`
+        let data = hex::decode(concat!(
+            "f889808609184e72a0008227109400000000",
+            "0000000000000000000000000000000080a4",
+            "7f7465737432000000000000000000000000",
+            "000000000000000000000000000000600057",
+            "1ba070bad1a10475d4b24e8227978077233c",
+            "3367a7642701db223465793e68d368b3a07e",
+            "3d131ef92c04eca4e48f1f5c0d2ea971f280",
+            "2d0e61ec21c8354b605ad286c0"
+        ))
+        .unwrap();
+
+        let transaction = decode::<TransactionLegacy, RlpDecodingItem>(&data).unwrap();
+        assert_eq!(
+            transaction.signing_hash(),
+            transaction.payload().signing_hash()
+        );
+    }
+
+    #[test]
+    fn test_chain_id_from_v() {
+        // (v, chain_id)
+        let data = [
+            (27, None),
+            (28, None),
+            (35, Some(0)),
+            (37, Some(1)),
+            (38, Some(1)),
+            // v doesn't fit in a byte
+            (2709, Some(1337)),
+            (2710, Some(1337)),
+        ];
+        for (v, chain_id) in data {
+            assert_eq!(
+                chain_id_from_v(&BigUint::from(v as u64)).unwrap(),
+                chain_id.map(ChainId::from)
+            );
+        }
+
+        // Neither 27 or 28, nor EIP-155
+        for v in [0, 1, 26, 29, 34] {
+            assert!(matches!(
+                chain_id_from_v(&BigUint::from(v as u64)),
+                Err(RlpDataDecodingError::InvalidFormat)
+            ));
+        }
+    }
 }
@@ -4,15 +4,37 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use super::transaction_legacy::TransactionLegacy;
+use super::transaction_legacy::{chain_id_from_v, TransactionLegacy};
 use crate::bigint::BigUint;
 use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use crate::blockchain::ethereum::rlp::schema::RlpSchema;
 use crate::blockchain::ethereum::rlp::RlpItemType;
 use crate::blockchain::ethereum::transaction::TransactionBuilder;
-use crate::blockchain::ethereum::types::{Address, EoaNonce, Wei};
-use crate::tools::codable::{Decodable, Encodable, EncodingItem};
+use crate::blockchain::ethereum::types::{Address, EoaNonce, GasLimit, Wei};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => TransactionLegacy);
+seal_codable!(<'a> RlpDecodingItem<'a> => TransactionLegacy);
+
+impl TransactionLegacy {
+    /// Returns the schema of the RLP encoding: nonce, gas price, gas limit, destination,
+    /// amount, data, v, r and s.
+    pub fn rlp_schema() -> RlpSchema {
+        RlpSchema::List(vec![
+            RlpSchema::Uint,
+            RlpSchema::Uint,
+            RlpSchema::Uint,
+            RlpSchema::Bytes,
+            RlpSchema::Uint,
+            RlpSchema::Bytes,
+            RlpSchema::Uint,
+            RlpSchema::Uint,
+            RlpSchema::Uint,
+        ])
+    }
+}
 
 impl Encodable<RlpEncodingItem> for TransactionLegacy {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -23,7 +45,7 @@ impl Encodable<RlpEncodingItem> for TransactionLegacy {
         self.payload.destination.encode_to(&mut list_encoding_item);
         self.payload.amount.encode_to(&mut list_encoding_item);
         self.payload.data.encode_to(&mut list_encoding_item);
-        (self.v as u64).encode_to(&mut list_encoding_item);
+        self.v.encode_to(&mut list_encoding_item);
         self.r.encode_to(&mut list_encoding_item);
         self.s.encode_to(&mut list_encoding_item);
 
@@ -34,7 +56,10 @@ impl Encodable<RlpEncodingItem> for TransactionLegacy {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionLegacy {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         return match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let items = decoding_item.decode_as_items()?;
                 if items.len() != 9 {
@@ -45,19 +70,25 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for TransactionLegacy {
                 let payload = TransactionBuilder::new()
                     .with_nonce(EoaNonce::decode_from(iter.next().unwrap())?)
                     .with_gas_price(Wei::decode_from(iter.next().unwrap())?)
-                    .with_gas_limit(u64::decode_from(iter.next().unwrap())?)
+                    .with_gas_limit(GasLimit::decode_from(iter.next().unwrap())?)
                     .with_destination(Address::decode_from(iter.next().unwrap())?)
                     .with_amount(Wei::decode_from(iter.next().unwrap())?)
                     .with_data(Vec::<u8>::decode_from(iter.next().unwrap())?)
                     .take_and_build_payload_legacy()
                     .map_err(|_| RlpDataDecodingError::InvalidFormat)?;
 
-                let v_u64 = u64::decode_from(iter.next().unwrap())?;
-                let v = u8::try_from(v_u64).map_err(|_| RlpDataDecodingError::InvalidFormat)?;
+                let v = BigUint::decode_from(iter.next().unwrap())?;
                 let r = BigUint::decode_from(iter.next().unwrap())?;
                 let s = BigUint::decode_from(iter.next().unwrap())?;
+                let chain_id = chain_id_from_v(&v)?;
 
-                Ok(TransactionLegacy { payload, v, r, s })
+                Ok(TransactionLegacy {
+                    payload,
+                    v,
+                    r,
+                    s,
+                    chain_id,
+                })
             }
         };
     }
@@ -4,10 +4,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::blockchain::ethereum::types::{Address, StorageKey};
+use crate::blockchain::ethereum::types::{Address, AddressData, StorageKey, StorageKeyData};
 use std::fmt;
 use std::fmt::Display;
 
+#[derive(Clone)]
 pub struct AccessListItem {
     pub address: Address,
     pub storage_keys: Vec<StorageKey>,
@@ -26,7 +27,29 @@ impl Display for AccessListItem {
     }
 }
 
-#[derive(Default)]
+/// An `AccessListItem` borrowing the address and the storage keys from the data it is
+/// decoded from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessListItemRef<'a> {
+    pub address: &'a AddressData,
+    pub storage_keys: Vec<&'a StorageKeyData>,
+}
+
+impl AccessListItemRef<'_> {
+    /// Copies the borrowed fields into an `AccessListItem`.
+    pub fn to_access_list_item(&self) -> AccessListItem {
+        AccessListItem {
+            address: Address(*self.address),
+            storage_keys: self
+                .storage_keys
+                .iter()
+                .map(|&storage_key| StorageKey(*storage_key))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct AccessList(pub Vec<AccessListItem>);
 
 impl Display for AccessList {
@@ -49,7 +72,7 @@ impl Display for AccessList {
 //         writeln!(f, "gas_limit: 0x{:x}", self.gas_limit)?;
 //         writeln!(f, "destination: {}", self.destination)?;
 //         writeln!(f, "amount: {}", self.amount)?;
-//         writeln!(f, "data: 0x{}", bytes_to_lower_hex(&self.data))?;
+//         writeln!(f, "data: 0x{}", hex::encode_lower(&self.data))?;
 //         writeln!(f, "access_list: {}", &self.access_list)?;
 //
 //         Ok(())
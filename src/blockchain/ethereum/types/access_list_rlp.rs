@@ -4,13 +4,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use super::access_list::{AccessList, AccessListItem};
+use super::access_list::{AccessList, AccessListItem, AccessListItemRef};
 use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::rlp::RlpItemType;
-use crate::blockchain::ethereum::types::{Address, StorageKey};
-use crate::tools::codable::{Decodable, Encodable, EncodingItem};
+use crate::blockchain::ethereum::types::{Address, AddressData, StorageKey, StorageKeyData};
+use crate::tools::codable::{seal_codable, Decodable, Encodable, EncodingItem};
+
+seal_codable!(RlpEncodingItem => AccessListItem, AccessList);
+seal_codable!(<'a> RlpDecodingItem<'a> => AccessListItem, AccessListItemRef<'a>, AccessList);
 
 impl Encodable<RlpEncodingItem> for AccessListItem {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -25,7 +28,10 @@ impl Encodable<RlpEncodingItem> for AccessListItem {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for AccessListItem {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         return match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let items = decoding_item.decode_as_items()?;
                 if items.len() != 2 {
@@ -44,6 +50,22 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for AccessListItem {
     }
 }
 
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for AccessListItemRef<'a> {
+    fn decode_from(decoding_item: &RlpDecodingItem<'a>) -> Result<Self, RlpDataDecodingError> {
+        let items = decoding_item.decode_as_items()?;
+        if items.len() != 2 {
+            return Err(RlpDataDecodingError::InvalidFormat);
+        }
+
+        let address = <&AddressData>::decode_from(&items[0])?;
+        let storage_keys = Vec::<&StorageKeyData>::decode_from(&items[1])?;
+        Ok(AccessListItemRef {
+            address,
+            storage_keys,
+        })
+    }
+}
+
 impl Encodable<RlpEncodingItem> for AccessList {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
         self.0.encode_to(encoding_item);
@@ -53,7 +75,10 @@ impl Encodable<RlpEncodingItem> for AccessList {
 impl<'a> Decodable<'a, RlpDecodingItem<'a>> for AccessList {
     fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
         match decoding_item.item_type {
-            RlpItemType::SingleValue => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::SingleValue => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::List,
+                found: RlpItemType::SingleValue,
+            }),
             RlpItemType::List => {
                 let access_list_items = Vec::<AccessListItem>::decode_from(decoding_item)?;
                 Ok(AccessList(access_list_items))
@@ -0,0 +1,225 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `Serialize` and `Deserialize` for `AccessList` in the format of the
+//! JSON-RPC API, e.g., `eth_call` and `eth_getTransactionByHash`:
+//!
+//! ```text
+//! [{"address":"0x...","storageKeys":["0x...","0x..."]}]
+//! ```
+
+use super::access_list::{AccessList, AccessListItem};
+use crate::blockchain::ethereum::types::{Address, StorageKey};
+use serde::de::{self, IgnoredAny, MapAccess, Unexpected, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+const ADDRESS_FIELD: &str = "address";
+const STORAGE_KEYS_FIELD: &str = "storageKeys";
+
+/// Serializes the address as lowercase hex, as the JSON-RPC API returns it.
+impl Serialize for AccessListItem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let storage_keys: Vec<String> = self
+            .storage_keys
+            .iter()
+            .map(|storage_key| storage_key.to_string())
+            .collect();
+
+        let mut state = serializer.serialize_struct("AccessListItem", 2)?;
+        state.serialize_field(ADDRESS_FIELD, &format!("{:#x}", self.address))?;
+        state.serialize_field(STORAGE_KEYS_FIELD, &storage_keys)?;
+        state.end()
+    }
+}
+
+impl Serialize for AccessList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.0)
+    }
+}
+
+/// Accepts the hex of the address in any case, the EIP-55 checksum isn't verified.
+/// Unknown fields are ignored.
+impl<'de> Deserialize<'de> for AccessListItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct(
+            "AccessListItem",
+            &[ADDRESS_FIELD, STORAGE_KEYS_FIELD],
+            AccessListItemVisitor,
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<AccessListItem>::deserialize(deserializer).map(AccessList)
+    }
+}
+
+struct AccessListItemVisitor;
+
+impl<'de> Visitor<'de> for AccessListItemVisitor {
+    type Value = AccessListItem;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an access list item with an address and storage keys")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut address = None;
+        let mut storage_keys = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                ADDRESS_FIELD => {
+                    if address.is_some() {
+                        return Err(de::Error::duplicate_field(ADDRESS_FIELD));
+                    }
+                    let hex: String = map.next_value()?;
+                    address = Some(parse_address(&hex)?);
+                }
+                STORAGE_KEYS_FIELD => {
+                    if storage_keys.is_some() {
+                        return Err(de::Error::duplicate_field(STORAGE_KEYS_FIELD));
+                    }
+                    let hex_list: Vec<String> = map.next_value()?;
+                    storage_keys = Some(
+                        hex_list
+                            .iter()
+                            .map(|hex| parse_storage_key(hex))
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(AccessListItem {
+            address: address.ok_or_else(|| de::Error::missing_field(ADDRESS_FIELD))?,
+            storage_keys: storage_keys
+                .ok_or_else(|| de::Error::missing_field(STORAGE_KEYS_FIELD))?,
+        })
+    }
+}
+
+fn parse_address<E: de::Error>(hex: &str) -> Result<Address, E> {
+    Address::try_from(hex)
+        .map_err(|_| E::invalid_value(Unexpected::Str(hex), &"a 0x-prefixed hex of 20 bytes"))
+}
+
+fn parse_storage_key<E: de::Error>(hex: &str) -> Result<StorageKey, E> {
+    StorageKey::try_from(hex)
+        .map_err(|_| E::invalid_value(Unexpected::Str(hex), &"a 0x-prefixed hex of 32 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+    use crate::tools::codable::encode;
+
+    const ACCESS_LIST_JSON: &str = concat!(
+        r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","storageKeys":["#,
+        r#""0x0000000000000000000000000000000000000000000000000000000000000003","#,
+        r#""0x0000000000000000000000000000000000000000000000000000000000000007"]},"#,
+        r#"{"address":"0xbb9bc244d798123fde783fcc1c72d3bb8c189413","storageKeys":[]}]"#
+    );
+
+    #[test]
+    fn test_serializing_access_list() {
+        let access_list = AccessList(vec![
+            AccessListItem {
+                address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"
+                    .try_into()
+                    .unwrap(),
+                storage_keys: vec![
+                    "0x0000000000000000000000000000000000000000000000000000000000000003"
+                        .try_into()
+                        .unwrap(),
+                    "0x0000000000000000000000000000000000000000000000000000000000000007"
+                        .try_into()
+                        .unwrap(),
+                ],
+            },
+            AccessListItem {
+                address: "0xbb9bc244d798123fde783fcc1c72d3bb8c189413"
+                    .try_into()
+                    .unwrap(),
+                storage_keys: vec![],
+            },
+        ]);
+        assert_eq!(
+            serde_json::to_string(&access_list).unwrap(),
+            ACCESS_LIST_JSON
+        );
+
+        let decoded: AccessList = serde_json::from_str(ACCESS_LIST_JSON).unwrap();
+        assert_eq!(
+            encode::<_, RlpEncodingItem>(&decoded),
+            encode::<_, RlpEncodingItem>(&access_list)
+        );
+
+        assert_eq!(serde_json::to_string(&AccessList::default()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_deserializing_access_list() {
+        // Checksummed, unknown fields, pretty printed
+        let json = r#"[
+            {
+                "storageKeys": [],
+                "address": "0xDe0B295669a9FD93d5F28D9Ec85E40f4cb697BAe",
+                "extra": {"nested": [1, 2]}
+            }
+        ]"#;
+        let access_list: AccessList = serde_json::from_str(json).unwrap();
+        assert_eq!(access_list.0.len(), 1);
+        assert_eq!(
+            format!("{:#x}", access_list.0[0].address),
+            "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"
+        );
+        assert!(access_list.0[0].storage_keys.is_empty());
+    }
+
+    #[test]
+    fn test_deserializing_access_list_error_cases() {
+        let data = [
+            // (json, error message)
+            (r#"[{"storageKeys":[]}]"#, "missing field `address`"),
+            (
+                r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"}]"#,
+                "missing field `storageKeys`",
+            ),
+            (
+                r#"[{"address":"de0b295669a9fd93d5f28d9ec85e40f4cb697bae","storageKeys":[]}]"#,
+                "expected a 0x-prefixed hex of 20 bytes",
+            ),
+            (
+                r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697b","storageKeys":[]}]"#,
+                "expected a 0x-prefixed hex of 20 bytes",
+            ),
+            (
+                r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","storageKeys":["0x03"]}]"#,
+                "expected a 0x-prefixed hex of 32 bytes",
+            ),
+            (
+                r#"[{"address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","address":"0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae","storageKeys":[]}]"#,
+                "duplicate field `address`",
+            ),
+            (
+                r#"{"address":"0x"}"#,
+                "invalid type: map, expected a sequence",
+            ),
+        ];
+        for (json, message) in data {
+            let err = serde_json::from_str::<AccessList>(json).err().unwrap();
+            assert!(err.to_string().contains(message), "{json}: {err}");
+        }
+    }
+}
@@ -7,7 +7,8 @@
 //! Implements EOA (externally-owned account) related functions.
 
 use crate::bigint::bigint_new::ParseIntError;
-use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+use crate::blockchain::ethereum::types::ChainId;
+use crate::crypto::codecs::hex;
 use crate::crypto::hash::{Keccak256, UnkeyedHash};
 use std::fmt;
 use std::fmt::Display;
@@ -17,6 +18,7 @@ pub const ADDRESS_DATA_BYTE_LENGTH: usize = 20;
 pub type AddressData = [u8; ADDRESS_DATA_BYTE_LENGTH];
 
 // Public address of an externally-owned account.
+#[derive(Clone, PartialEq, Eq)]
 pub struct Address(pub AddressData);
 
 impl Address {
@@ -34,7 +36,7 @@ impl Address {
     }
 
     pub(crate) fn from_hex<T: AsRef<[u8]>>(hex: T) -> Option<Address> {
-        if let Ok(bytes) = hex_to_bytes(hex) {
+        if let Ok(bytes) = hex::decode(hex) {
             Address::from_bytes(&bytes)
         } else {
             None
@@ -44,15 +46,30 @@ impl Address {
 
 impl Address {
     fn to_lower_hex(&self) -> String {
-        bytes_to_lower_hex(&self.0)
+        hex::encode_lower(&self.0)
     }
 
     pub fn to_checksummed_hex(&self) -> String {
         let hex = self.to_lower_hex();
         String::from_utf8(eip_55_checksum_encode(hex.as_bytes())).unwrap()
     }
+
+    /// Returns the address prefixed with "0x", checksummed with the chain-specific
+    /// checksum of EIP-1191, e.g., for RSK (chain ID 30).
+    ///
+    /// Most chains, including Ethereum, don't adopt EIP-1191: the checksum is only
+    /// recognized by the wallets of the chains adopting it. The address itself doesn't
+    /// depend on the chain.
+    ///
+    /// See: https://eips.ethereum.org/EIPS/eip-1191
+    pub fn to_checksum_string_with_chain_id(&self, chain_id: &ChainId) -> String {
+        let hex = self.to_lower_hex();
+        let checksummed_hex = eip_1191_checksum_encode(hex.as_bytes(), chain_id);
+        format!("0x{}", String::from_utf8(checksummed_hex).unwrap())
+    }
 }
 
+/// Formats the address prefixed with "0x", checksummed with EIP-55.
 impl Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let checksummed_hex = self.to_checksummed_hex();
@@ -60,6 +77,35 @@ impl Display for Address {
     }
 }
 
+/// Formats the address as `Address("0x...")`, checksummed as its `Display`.
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Address").field(&self.to_string()).finish()
+    }
+}
+
+/// Formats the address as 40 lowercase hexadecimal digits, prefixed with "0x" only for
+/// the alternate form `{:#x}`.
+impl fmt::LowerHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&self.to_lower_hex())
+    }
+}
+
+/// Formats the address as 40 uppercase hexadecimal digits, prefixed with "0x" only for
+/// the alternate form `{:#X}`.
+impl fmt::UpperHex for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        f.write_str(&hex::encode_upper(&self.0))
+    }
+}
+
 /// Creates a `EoaPublicAddress` from a 40-char hex.
 /// The hex must be prefixed with "0x".
 ///
@@ -92,8 +138,29 @@ impl TryFrom<&str> for Address {
 // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-55.md
 // https://github.com/ethereum/eips/issues/55
 fn eip_55_checksum_encode(address_lower_hex: &[u8]) -> Vec<u8> {
-    let hashed_address_lower_hex =
-        bytes_to_lower_hex(&Keccak256::new().digest(address_lower_hex));
+    let hash = Keccak256::new().digest(address_lower_hex);
+    checksum_encode(address_lower_hex, &hash)
+}
+
+// Returns `address_lower_hex` checksummed with the chain-specific checksum of EIP-1191,
+// which hashes the decimal chain ID followed by "0x" and the address.
+//
+// `address_lower_hex` has the same requirements as `eip_55_checksum_encode`.
+//
+// See EIP-1191 for details:
+// https://eips.ethereum.org/EIPS/eip-1191
+fn eip_1191_checksum_encode(address_lower_hex: &[u8], chain_id: &ChainId) -> Vec<u8> {
+    let mut message = chain_id.0.as_bigint().to_decimal_bytes();
+    message.extend(b"0x");
+    message.extend(address_lower_hex);
+    let hash = Keccak256::new().digest(message);
+    checksum_encode(address_lower_hex, &hash)
+}
+
+// Uppercases the letters of `address_lower_hex` whose corresponding hexadecimal digits of
+// `hash` are greater than 7.
+fn checksum_encode(address_lower_hex: &[u8], hash: &[u8]) -> Vec<u8> {
+    let hashed_address_lower_hex = hex::encode_lower(hash);
     let mut checksummed_address_hex = Vec::with_capacity(address_lower_hex.len());
     for (&c1, &c2) in zip(address_lower_hex, hashed_address_lower_hex.as_bytes()) {
         match c1 {
@@ -176,4 +243,83 @@ mod tests {
             assert_eq!(result, address.as_bytes()[2..]);
         }
     }
+
+    #[test]
+    fn test_formatting() {
+        // USDC
+        // https://etherscan.io/address/0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48
+        let address: Address = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+        );
+        assert_eq!(
+            format!("{address:?}"),
+            r#"Address("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")"#
+        );
+        assert_eq!(
+            format!("{address:x}"),
+            "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+        assert_eq!(
+            format!("{address:X}"),
+            "A0B86991C6218B36C1D19D4A2E9EB0CE3606EB48"
+        );
+        assert_eq!(
+            format!("{address:#x}"),
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+        );
+    }
+
+    #[test]
+    fn test_eip_1191_checksum_encoding() {
+        // The test cases of EIP-1191, RSK Mainnet and RSK Testnet.
+        let data = [
+            (
+                30,
+                [
+                    "0x27b1FdB04752BBc536007A920D24ACB045561c26",
+                    "0x3599689E6292B81B2D85451025146515070129Bb",
+                    "0x42712D45473476B98452f434E72461577d686318",
+                    "0x52908400098527886E0F7030069857D2E4169ee7",
+                    "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+                    "0x6549F4939460DE12611948B3F82B88C3C8975323",
+                    "0x66F9664f97f2B50F62d13EA064982F936de76657",
+                    "0x8617E340b3D01Fa5f11f306f4090fd50E238070D",
+                    "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+                    "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+                    "0xDe709F2102306220921060314715629080e2FB77",
+                    "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+                ],
+            ),
+            (
+                31,
+                [
+                    "0x27B1FdB04752BbC536007a920D24acB045561C26",
+                    "0x3599689e6292b81b2D85451025146515070129Bb",
+                    "0x42712D45473476B98452F434E72461577D686318",
+                    "0x52908400098527886E0F7030069857D2e4169EE7",
+                    "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+                    "0x6549f4939460dE12611948b3f82b88C3c8975323",
+                    "0x66f9664F97F2b50f62d13eA064982F936DE76657",
+                    "0x8617e340b3D01fa5F11f306F4090Fd50e238070d",
+                    "0xd1220a0CF47c7B9Be7A2E6Ba89f429762E7b9adB",
+                    "0xdbF03B407C01E7cd3cbEa99509D93f8dDDc8C6fB",
+                    "0xDE709F2102306220921060314715629080e2Fb77",
+                    "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
+                ],
+            ),
+        ];
+        for (chain_id, addresses) in data {
+            for checksummed in addresses {
+                let address: Address = checksummed.to_lowercase().as_str().try_into().unwrap();
+                assert_eq!(
+                    address.to_checksum_string_with_chain_id(&ChainId::from(chain_id)),
+                    checksummed
+                );
+            }
+        }
+    }
 }
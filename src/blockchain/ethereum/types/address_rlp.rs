@@ -9,7 +9,10 @@ use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::rlp::RlpItemType;
 use crate::blockchain::ethereum::types::address::Address;
-use crate::tools::codable::{Decodable, Encodable};
+use crate::tools::codable::{seal_codable, Decodable, Encodable};
+
+seal_codable!(RlpEncodingItem => Address);
+seal_codable!(<'a> RlpDecodingItem<'a> => Address);
 
 impl Encodable<RlpEncodingItem> for Address {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
@@ -28,7 +31,10 @@ impl<'a> Decodable<'a, RlpDecodingItem<'a>> for Address {
                     Err(RlpDataDecodingError::InvalidFormat)
                 }
             }
-            RlpItemType::List => Err(RlpDataDecodingError::InvalidFormat),
+            RlpItemType::List => Err(RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::SingleValue,
+                found: RlpItemType::List,
+            }),
         }
     }
 }
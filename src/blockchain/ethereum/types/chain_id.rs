@@ -9,7 +9,7 @@ use std::fmt;
 use std::fmt::Display;
 
 /// Represents Chain ID
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ChainId(pub(crate) BigUint);
 
 impl From<BigUint> for ChainId {
@@ -30,8 +30,10 @@ impl Display for ChainId {
     }
 }
 
+/// The well-known chains, more may be added in a minor release.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u64)]
+#[non_exhaustive]
 pub enum Chain {
     EthereumMainnet = 1,
     ExpanseMainnet = 2,
@@ -39,6 +41,7 @@ pub enum Chain {
     Rinkeby = 4,
     Goerli = 5,
     Kovan = 42,
+    Polygon = 137,
     GethPrivateChains = 1337,
 }
 
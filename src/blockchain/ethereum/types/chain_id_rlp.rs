@@ -9,7 +9,10 @@ use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use crate::blockchain::ethereum::types::ChainId;
-use crate::tools::codable::{Decodable, Encodable};
+use crate::tools::codable::{seal_codable, Decodable, Encodable};
+
+seal_codable!(RlpEncodingItem => ChainId);
+seal_codable!(<'a> RlpDecodingItem<'a> => ChainId);
 
 impl Encodable<RlpEncodingItem> for ChainId {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
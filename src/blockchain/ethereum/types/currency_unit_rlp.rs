@@ -9,7 +9,10 @@ use crate::bigint::BigUint;
 use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
-use crate::tools::codable::{Decodable, Encodable};
+use crate::tools::codable::{seal_codable, Decodable, Encodable};
+
+seal_codable!(RlpEncodingItem => Wei);
+seal_codable!(<'a> RlpDecodingItem<'a> => Wei);
 
 impl Encodable<RlpEncodingItem> for Wei {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
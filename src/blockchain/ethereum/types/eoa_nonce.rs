@@ -19,6 +19,7 @@ use std::fmt::Display;
 ///
 ///
 /// [1]: https://eips.ethereum.org/EIPS/eip-2681
+#[derive(Clone, Debug)]
 pub struct EoaNonce(u64);
 
 impl EoaNonce {
@@ -33,8 +34,37 @@ impl EoaNonce {
     pub fn value(&self) -> u64 {
         self.0
     }
+
+    /// Returns the nonce after `self`, e.g., of the next transaction of the account.
+    ///
+    /// # Errors
+    ///
+    /// Will return `EoaNonceError::Overflow` if `self` is the last nonce, 2^64-2.
+    pub fn checked_increment(&self) -> Result<EoaNonce, EoaNonceError> {
+        self.0
+            .checked_add(1)
+            .and_then(EoaNonce::from_u64)
+            .ok_or(EoaNonceError::Overflow)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EoaNonceError {
+    /// The nonce would exceed 2^64-2.
+    Overflow,
 }
 
+impl Display for EoaNonceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EoaNonceError::Overflow => write!(f, "nonce overflow"),
+        }
+    }
+}
+
+impl std::error::Error for EoaNonceError {}
+
 impl TryFrom<u64> for EoaNonce {
     type Error = &'static str;
 
@@ -71,4 +101,19 @@ mod tests {
         // 2^64-1
         assert!(EoaNonce::from_u64(18446744073709551615_u64).is_none());
     }
+
+    #[test]
+    fn test_checked_increment() {
+        let data = [
+            // (nonce, next nonce)
+            (0, Ok(1)),
+            (41, Ok(42)),
+            (u64::MAX - 2, Ok(u64::MAX - 1)),
+            (u64::MAX - 1, Err(EoaNonceError::Overflow)),
+        ];
+        for (n, next) in data {
+            let nonce = EoaNonce::from_u64(n).unwrap();
+            assert_eq!(nonce.checked_increment().map(|nonce| nonce.value()), next);
+        }
+    }
 }
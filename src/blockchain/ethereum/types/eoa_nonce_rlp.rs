@@ -8,7 +8,10 @@ use super::eoa_nonce::EoaNonce;
 use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
-use crate::tools::codable::{Decodable, Encodable};
+use crate::tools::codable::{seal_codable, Decodable, Encodable};
+
+seal_codable!(RlpEncodingItem => EoaNonce);
+seal_codable!(<'a> RlpDecodingItem<'a> => EoaNonce);
 
 impl Encodable<RlpEncodingItem> for EoaNonce {
     fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
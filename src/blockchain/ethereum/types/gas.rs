@@ -0,0 +1,170 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::currency_unit::Wei;
+use crate::bigint::{BigInt, BigUint};
+use std::fmt;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+/// The maximum bit length of an amount of Wei, the EVM stores amounts in 256-bit words.
+const WEI_MAX_BIT_LEN: usize = 256;
+
+/// Represents the gas limit of a transaction: the maximum amount of gas it can use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GasLimit(u64);
+
+/// Represents the amount of gas used, e.g., by a transaction or a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GasUsed(u64);
+
+impl GasLimit {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the cost of the gas at `price` per gas, `gas_limit * price`, or `None` if the
+    /// cost doesn't fit in 256 bits.
+    pub fn cost_at(&self, price: &Wei) -> Option<Wei> {
+        gas_cost_at(self.0, price)
+    }
+}
+
+impl GasUsed {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the cost of the gas at `price` per gas, `gas_used * price`, or `None` if the
+    /// cost doesn't fit in 256 bits.
+    pub fn cost_at(&self, price: &Wei) -> Option<Wei> {
+        gas_cost_at(self.0, price)
+    }
+}
+
+fn gas_cost_at(gas: u64, price: &Wei) -> Option<Wei> {
+    let cost = price.0.as_bigint() * BigInt::from(gas);
+    if cost.bit_len() > WEI_MAX_BIT_LEN {
+        return None;
+    }
+
+    BigUint::from_bigint(cost).map(Wei)
+}
+
+/// Parses `s` as a hexadecimal with the prefix "0x", e.g., "0x5208", or as a decimal
+/// otherwise.
+fn parse_gas(s: &str) -> Result<u64, ParseIntError> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        // `from_str_radix` accepts a leading "+", which isn't a hexadecimal digit.
+        Some(hex) if hex.starts_with('+') => u64::from_str_radix("+", 16),
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+}
+
+impl From<u64> for GasLimit {
+    fn from(n: u64) -> Self {
+        GasLimit(n)
+    }
+}
+
+impl From<u64> for GasUsed {
+    fn from(n: u64) -> Self {
+        GasUsed(n)
+    }
+}
+
+impl TryFrom<&str> for GasLimit {
+    type Error = ParseIntError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        parse_gas(s).map(GasLimit)
+    }
+}
+
+impl TryFrom<&str> for GasUsed {
+    type Error = ParseIntError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        parse_gas(s).map(GasUsed)
+    }
+}
+
+impl Display for GasLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.0;
+        write!(f, "{n:#x}")
+    }
+}
+
+impl Display for GasUsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.0;
+        write!(f, "{n:#x}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_str() {
+        let gas_limit: GasLimit = "0x5208".try_into().unwrap();
+        assert_eq!(gas_limit, GasLimit::from(21000));
+        assert_eq!(GasLimit::try_from("0X5208").unwrap().value(), 21000);
+        assert_eq!(GasLimit::try_from("21000").unwrap().value(), 21000);
+        assert_eq!(
+            GasUsed::try_from("0xffffffffffffffff").unwrap().value(),
+            u64::MAX
+        );
+
+        for s in [
+            "",
+            "0x",
+            "5208x",
+            "0x5208g",
+            "-1",
+            "0x10000000000000000",
+            "0x+5208",
+            "0x-5208",
+        ] {
+            assert!(GasLimit::try_from(s).is_err());
+            assert!(GasUsed::try_from(s).is_err());
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(GasLimit::from(21000).to_string(), "0x5208");
+        assert_eq!(GasUsed::from(0).to_string(), "0x0");
+    }
+
+    #[test]
+    fn test_cost_at() {
+        let price = Wei::from_decimal("20000000000").unwrap(); // 20 gwei
+        assert_eq!(
+            GasLimit::from(21000).cost_at(&price),
+            Some(Wei::from_decimal("420000000000000").unwrap())
+        );
+        assert_eq!(
+            GasUsed::from(0).cost_at(&price),
+            Some(Wei::from_decimal("0").unwrap())
+        );
+
+        // 2^192 * (2^64 - 1) fits in 256 bits
+        let price = Wei::from_hex(format!("01{}", "00".repeat(24))).unwrap();
+        assert_eq!(
+            GasLimit::from(u64::MAX).cost_at(&price),
+            Some(Wei::from_hex(format!("{}{}", "ff".repeat(8), "00".repeat(24))).unwrap())
+        );
+        // 2^200 * (2^64 - 1) doesn't
+        let price = Wei::from_hex(format!("01{}", "00".repeat(25))).unwrap();
+        assert_eq!(GasLimit::from(u64::MAX).cost_at(&price), None);
+        assert_eq!(GasUsed::from(1 << 56).cost_at(&price), None);
+        assert!(GasUsed::from((1 << 56) - 1).cost_at(&price).is_some());
+    }
+}
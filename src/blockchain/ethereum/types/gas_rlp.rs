@@ -0,0 +1,64 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::gas::{GasLimit, GasUsed};
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use crate::tools::codable::{seal_codable, Decodable, Encodable};
+
+seal_codable!(RlpEncodingItem => GasLimit, GasUsed);
+seal_codable!(<'a> RlpDecodingItem<'a> => GasLimit, GasUsed);
+
+impl Encodable<RlpEncodingItem> for GasLimit {
+    fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
+        self.value().encode_to(encoding_item);
+    }
+}
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for GasLimit {
+    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+        let n = u64::decode_from(decoding_item)?;
+        Ok(GasLimit::from(n))
+    }
+}
+
+impl Encodable<RlpEncodingItem> for GasUsed {
+    fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
+        self.value().encode_to(encoding_item);
+    }
+}
+
+impl<'a> Decodable<'a, RlpDecodingItem<'a>> for GasUsed {
+    fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+        let n = u64::decode_from(decoding_item)?;
+        Ok(GasUsed::from(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::codable::{decode, encode};
+
+    #[test]
+    fn test_same_encoding_as_u64() {
+        for n in [0, 1, 0x7f, 0x80, 0x5208, u64::MAX] {
+            let data = encode::<_, RlpEncodingItem>(&n);
+            assert_eq!(encode(&GasLimit::from(n)), data);
+            assert_eq!(encode(&GasUsed::from(n)), data);
+
+            assert_eq!(
+                decode::<GasLimit, RlpDecodingItem>(&data).unwrap(),
+                GasLimit::from(n)
+            );
+            assert_eq!(
+                decode::<GasUsed, RlpDecodingItem>(&data).unwrap(),
+                GasUsed::from(n)
+            );
+        }
+    }
+}
@@ -6,6 +6,8 @@
 
 pub(crate) mod access_list;
 pub(crate) mod access_list_rlp;
+#[cfg(feature = "serde")]
+mod access_list_serde;
 pub(crate) mod address;
 pub(crate) mod address_rlp;
 pub(crate) mod chain_id;
@@ -15,13 +17,16 @@ pub(crate) mod currency_unit;
 pub(crate) mod currency_unit_rlp;
 pub(crate) mod eoa_nonce;
 pub(crate) mod eoa_nonce_rlp;
+pub(crate) mod gas;
+pub(crate) mod gas_rlp;
 pub(crate) mod storage_key;
 pub(crate) mod storage_key_rlp;
 
-pub use access_list::{AccessList, AccessListItem};
+pub use access_list::{AccessList, AccessListItem, AccessListItemRef};
 pub use address::*;
 pub use chain_id::{Chain, ChainId};
 pub use common::*;
 pub use currency_unit::Wei;
-pub use eoa_nonce::EoaNonce;
-pub use storage_key::StorageKey;
+pub use eoa_nonce::{EoaNonce, EoaNonceError};
+pub use gas::{GasLimit, GasUsed};
+pub use storage_key::{StorageKey, StorageKeyData};
@@ -5,13 +5,14 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::bigint::bigint_new::ParseIntError;
-use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+use crate::crypto::codecs::hex;
 use std::fmt;
 use std::fmt::Display;
 
 pub const STORAGE_KEY_DATA_BYTE_LENGTH: usize = 32;
 pub type StorageKeyData = [u8; STORAGE_KEY_DATA_BYTE_LENGTH];
 
+#[derive(Clone)]
 pub struct StorageKey(pub(crate) StorageKeyData);
 
 impl StorageKey {
@@ -24,7 +25,7 @@ impl StorageKey {
     }
 
     pub(crate) fn from_hex<T: AsRef<[u8]>>(hex: T) -> Option<StorageKey> {
-        if let Ok(bytes) = hex_to_bytes(hex) {
+        if let Ok(bytes) = hex::decode(hex) {
             StorageKey::from_bytes(&bytes)
         } else {
             None
@@ -34,7 +35,7 @@ impl StorageKey {
 
 impl StorageKey {
     fn to_lower_hex(&self) -> String {
-        bytes_to_lower_hex(&self.0)
+        hex::encode_lower(&self.0)
     }
 }
 
@@ -0,0 +1,502 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::unsigned_payload::UnsignedPayload;
+use crate::blockchain::ethereum::account::EoaPrivateKey;
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use crate::blockchain::ethereum::transaction::TransactionBuildingError;
+use crate::blockchain::ethereum::types::Address;
+use crate::crypto::hash::{Keccak256, UnkeyedHash};
+use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// The version of the bundle encoding, see the module documentation.
+pub const UNSIGNED_BUNDLE_VERSION: u64 = 1;
+
+/// Signs transactions for a single account, e.g., `EoaPrivateKey` or a hardware wallet.
+pub trait TransactionSigner {
+    /// Returns the address of the account `sign_payload` signs for.
+    fn address(&self) -> Address;
+
+    /// Signs `payload`, returning the encoded signed transaction, ready to be broadcast.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if signing fails.
+    fn sign_payload(
+        &self,
+        payload: UnsignedPayload,
+    ) -> Result<Vec<u8>, TransactionBuildingError>;
+}
+
+impl TransactionSigner for EoaPrivateKey<'_> {
+    fn address(&self) -> Address {
+        self.public_key().address()
+    }
+
+    fn sign_payload(
+        &self,
+        payload: UnsignedPayload,
+    ) -> Result<Vec<u8>, TransactionBuildingError> {
+        Ok(match payload {
+            UnsignedPayload::Legacy(payload) => self.sign_legacy(payload)?.encode(),
+            UnsignedPayload::Eip155(payload) => self.sign_eip155(payload)?.encode(),
+            UnsignedPayload::Eip2930(payload) => self.sign_eip2930(payload)?.encode(),
+            UnsignedPayload::Eip1559(payload) => self.sign_eip1559(payload)?.encode(),
+        })
+    }
+}
+
+/// An unsigned transaction with the metadata the signer checks it against, see the module
+/// documentation.
+pub struct UnsignedTransactionBundle {
+    payload: Vec<u8>,
+    expected_sender: Address,
+    description: String,
+    integrity_hash: [u8; 32],
+}
+
+impl UnsignedTransactionBundle {
+    /// Creates a bundle of the encoded `payload`, `expected_sender` and `description`.
+    ///
+    /// The integrity hash is computed here, over the three fields, and checked by
+    /// [`UnsignedTransactionBundle::validate`] before signing.
+    pub fn new(
+        payload: UnsignedPayload,
+        expected_sender: Address,
+        description: String,
+    ) -> UnsignedTransactionBundle {
+        let payload = payload.encode();
+        let integrity_hash = integrity_hash(&payload, &expected_sender, &description);
+        UnsignedTransactionBundle {
+            payload,
+            expected_sender,
+            description,
+            integrity_hash,
+        }
+    }
+
+    /// Returns the payload, decoded from the bundled bytes.
+    ///
+    /// # Errors
+    ///
+    /// Will return `BundleValidationError::InvalidPayload` if the payload doesn't decode.
+    pub fn payload(&self) -> Result<UnsignedPayload, BundleValidationError> {
+        UnsignedPayload::decode(&self.payload).map_err(BundleValidationError::InvalidPayload)
+    }
+
+    pub fn expected_sender(&self) -> &Address {
+        &self.expected_sender
+    }
+
+    /// Returns the description provided by the constructor, not checked against the
+    /// payload.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Checks the integrity hash, then decodes the payload.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The integrity hash doesn't match the other fields.
+    /// - The payload doesn't decode.
+    pub fn validate(&self) -> Result<(), BundleValidationError> {
+        self.validated_payload().map(|_| ())
+    }
+
+    /// Validates `self` as `validate`, and checks the expected sender against the address
+    /// of `signer`.
+    ///
+    /// # Errors
+    ///
+    /// Will return the errors of `validate`, or `BundleValidationError::SenderMismatch` if
+    /// the addresses differ.
+    pub fn validate_for_signer(
+        &self,
+        signer: &dyn TransactionSigner,
+    ) -> Result<(), BundleValidationError> {
+        self.validate()?;
+        check_sender(&self.expected_sender, signer)
+    }
+
+    /// Validates `self` for `signer` as `validate_for_signer`, then signs the payload.
+    ///
+    /// # Errors
+    ///
+    /// Will return `BundleSigningError::InvalidBundle` if the validation fails, or
+    /// `BundleSigningError::SigningError` if signing fails.
+    pub fn sign(
+        self,
+        signer: &dyn TransactionSigner,
+    ) -> Result<SignedBundle, BundleSigningError> {
+        let payload = self.validated_payload()?;
+        check_sender(&self.expected_sender, signer)?;
+
+        let raw_transaction = signer
+            .sign_payload(payload)
+            .map_err(BundleSigningError::SigningError)?;
+        let transaction_id = Keccak256::new()
+            .digest(&raw_transaction)
+            .try_into()
+            .unwrap();
+        Ok(SignedBundle {
+            raw_transaction,
+            transaction_id,
+            description: self.description,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut list_encoding_item = RlpEncodingItem::new();
+        encode_hashed_fields(
+            &mut list_encoding_item,
+            &self.payload,
+            &self.expected_sender,
+            &self.description,
+        );
+        list_encoding_item.encode_bytes(&self.integrity_hash);
+
+        let mut encoding_item = RlpEncodingItem::new();
+        encoding_item.encode_list_payload(&mut list_encoding_item);
+        encoding_item.take_data()
+    }
+
+    /// Decodes a bundle returned by `encode`. The bundle isn't validated.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - `data` isn't a bundle.
+    /// - The version isn't `UNSIGNED_BUNDLE_VERSION`.
+    pub fn decode(data: &[u8]) -> Result<UnsignedTransactionBundle, BundleValidationError> {
+        let decode_items = || -> Result<_, RlpDataDecodingError> {
+            let items = RlpDecodingItem::new_from_data(data)?.decode_as_items()?;
+            if items.len() != 5 {
+                return Err(RlpDataDecodingError::InvalidFormat);
+            }
+            Ok((
+                u64::decode_from(&items[0])?,
+                Vec::<u8>::decode_from(&items[1])?,
+                Address::decode_from(&items[2])?,
+                String::decode_from(&items[3])?,
+                *<&[u8; 32]>::decode_from(&items[4])?,
+            ))
+        };
+        let (version, payload, expected_sender, description, integrity_hash) =
+            decode_items().map_err(BundleValidationError::InvalidFormat)?;
+        if version != UNSIGNED_BUNDLE_VERSION {
+            return Err(BundleValidationError::UnsupportedVersion(version));
+        }
+
+        Ok(UnsignedTransactionBundle {
+            payload,
+            expected_sender,
+            description,
+            integrity_hash,
+        })
+    }
+
+    fn validated_payload(&self) -> Result<UnsignedPayload, BundleValidationError> {
+        let hash = integrity_hash(&self.payload, &self.expected_sender, &self.description);
+        if hash != self.integrity_hash {
+            return Err(BundleValidationError::IntegrityMismatch);
+        }
+        self.payload()
+    }
+}
+
+impl Display for UnsignedTransactionBundle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "description: {}", self.description)?;
+        writeln!(f, "expected_sender: {}", self.expected_sender)?;
+        match self.payload() {
+            Ok(payload) => write!(f, "{payload}"),
+            Err(_) => writeln!(f, "payload: invalid"),
+        }
+    }
+}
+
+/// The result of `UnsignedTransactionBundle::sign`.
+pub struct SignedBundle {
+    /// The encoded signed transaction, e.g., for `eth_sendRawTransaction`.
+    pub raw_transaction: Vec<u8>,
+    pub transaction_id: [u8; 32],
+    pub description: String,
+}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum BundleValidationError {
+    InvalidFormat(RlpDataDecodingError),
+    UnsupportedVersion(u64),
+    /// The integrity hash doesn't match, the bundle was altered or corrupted.
+    IntegrityMismatch,
+    InvalidPayload(RlpDataDecodingError),
+    SenderMismatch {
+        expected: Address,
+        found: Address,
+    },
+}
+
+impl Display for BundleValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleValidationError::InvalidFormat(err) => write!(f, "invalid bundle: {err}"),
+            BundleValidationError::UnsupportedVersion(version) => {
+                write!(f, "unsupported bundle version {version}")
+            }
+            BundleValidationError::IntegrityMismatch => write!(f, "bundle integrity mismatch"),
+            BundleValidationError::InvalidPayload(err) => write!(f, "invalid payload: {err}"),
+            BundleValidationError::SenderMismatch { expected, found } => {
+                write!(f, "sender mismatch: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for BundleValidationError {}
+
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum BundleSigningError {
+    InvalidBundle(BundleValidationError),
+    SigningError(TransactionBuildingError),
+}
+
+impl Display for BundleSigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleSigningError::InvalidBundle(err) => write!(f, "{err}"),
+            BundleSigningError::SigningError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for BundleSigningError {}
+
+impl From<BundleValidationError> for BundleSigningError {
+    fn from(err: BundleValidationError) -> Self {
+        BundleSigningError::InvalidBundle(err)
+    }
+}
+
+fn check_sender(
+    expected_sender: &Address,
+    signer: &dyn TransactionSigner,
+) -> Result<(), BundleValidationError> {
+    let address = signer.address();
+    if &address != expected_sender {
+        return Err(BundleValidationError::SenderMismatch {
+            expected: expected_sender.clone(),
+            found: address,
+        });
+    }
+    Ok(())
+}
+
+// Encodes the fields covered by the integrity hash into `encoding_item`.
+fn encode_hashed_fields(
+    encoding_item: &mut RlpEncodingItem,
+    payload: &[u8],
+    expected_sender: &Address,
+    description: &str,
+) {
+    UNSIGNED_BUNDLE_VERSION.encode_to(encoding_item);
+    encoding_item.encode_bytes(payload);
+    expected_sender.encode_to(encoding_item);
+    description.encode_to(encoding_item);
+}
+
+// keccak256(rlp([version, payload, expected_sender, description]))
+fn integrity_hash(payload: &[u8], expected_sender: &Address, description: &str) -> [u8; 32] {
+    let mut list_encoding_item = RlpEncodingItem::new();
+    encode_hashed_fields(
+        &mut list_encoding_item,
+        payload,
+        expected_sender,
+        description,
+    );
+
+    let mut encoding_item = RlpEncodingItem::new();
+    encoding_item.encode_list_payload(&mut list_encoding_item);
+    Keccak256::new()
+        .digest(encoding_item.take_data())
+        .try_into()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::transaction::{TransactionBuilder, TransactionEip1559};
+    use crate::tools::codable::decode;
+
+    const KEY_HEX: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+
+    fn private_key() -> EoaPrivateKey<'static> {
+        EoaPrivateKey::from_any_hex(KEY_HEX).unwrap()
+    }
+
+    fn bundle() -> UnsignedTransactionBundle {
+        let payload = TransactionBuilder::new()
+            .with_chain_id(1.into())
+            .with_nonce(7.try_into().unwrap())
+            .with_max_priority_fee_per_gas("0x3b9aca00".try_into().unwrap())
+            .with_max_fee_per_gas("0x04a817c800".try_into().unwrap())
+            .with_gas_limit(21000.into())
+            .with_destination(
+                "0x3535353535353535353535353535353535353535"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount("0x0de0b6b3a7640000".try_into().unwrap())
+            .take_and_build_payload_eip_1559()
+            .unwrap();
+        UnsignedTransactionBundle::new(
+            payload.into(),
+            private_key().address(),
+            "Send 1 ETH".to_owned(),
+        )
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let bundle = bundle();
+        bundle.validate().unwrap();
+
+        let encoded = bundle.encode();
+        let decoded = UnsignedTransactionBundle::decode(&encoded).unwrap();
+        decoded.validate().unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(decoded.description(), "Send 1 ETH");
+        assert_eq!(decoded.expected_sender(), bundle.expected_sender());
+        assert_eq!(
+            decoded.payload().unwrap().signing_hash(),
+            bundle.payload().unwrap().signing_hash()
+        );
+        assert_eq!(decoded.to_string(), bundle.to_string());
+        assert!(decoded
+            .to_string()
+            .contains("max_fee_per_gas: 0x04a817c800"));
+    }
+
+    #[test]
+    fn test_tampering() {
+        let encoded = bundle().encode();
+
+        // The max fee per gas, 0x04a817c800 -> 0x14a817c800
+        let max_fee_rlp = [0x85, 0x04, 0xa8, 0x17, 0xc8, 0x00];
+        let index = encoded
+            .windows(max_fee_rlp.len())
+            .position(|window| window == max_fee_rlp)
+            .unwrap();
+        let mut tampered = encoded.clone();
+        tampered[index + 1] = 0x14;
+        let bundle = UnsignedTransactionBundle::decode(&tampered).unwrap();
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::IntegrityMismatch)
+        ));
+        assert!(matches!(
+            bundle.sign(&private_key()),
+            Err(BundleSigningError::InvalidBundle(
+                BundleValidationError::IntegrityMismatch
+            ))
+        ));
+
+        // The description
+        let mut bundle = UnsignedTransactionBundle::decode(&encoded).unwrap();
+        bundle.description = "Send 0.1 ETH".to_owned();
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::IntegrityMismatch)
+        ));
+
+        // A payload not decoding, with the integrity hash recomputed
+        let mut bundle = UnsignedTransactionBundle::decode(&encoded).unwrap();
+        bundle.payload = vec![0x02, 0xc0];
+        bundle.integrity_hash = integrity_hash(
+            &bundle.payload,
+            &bundle.expected_sender,
+            &bundle.description,
+        );
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_decoding_error_cases() {
+        let bundle = bundle();
+        let mut list_encoding_item = RlpEncodingItem::new();
+        2_u64.encode_to(&mut list_encoding_item);
+        list_encoding_item.encode_bytes(&bundle.payload);
+        bundle.expected_sender.encode_to(&mut list_encoding_item);
+        bundle
+            .description
+            .as_str()
+            .encode_to(&mut list_encoding_item);
+        list_encoding_item.encode_bytes(&bundle.integrity_hash);
+        let mut encoding_item = RlpEncodingItem::new();
+        encoding_item.encode_list_payload(&mut list_encoding_item);
+        assert!(matches!(
+            UnsignedTransactionBundle::decode(&encoding_item.take_data()),
+            Err(BundleValidationError::UnsupportedVersion(2))
+        ));
+
+        let encoded = bundle.encode();
+        for data in [&[][..], &[0xc0], &encoded[..encoded.len() - 1]] {
+            assert!(matches!(
+                UnsignedTransactionBundle::decode(data),
+                Err(BundleValidationError::InvalidFormat(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_signing() {
+        let private_key = private_key();
+        bundle().validate_for_signer(&private_key).unwrap();
+
+        let signed = bundle().sign(&private_key).unwrap();
+        assert_eq!(signed.description, "Send 1 ETH");
+        let transaction =
+            decode::<TransactionEip1559, _>(&signed.raw_transaction[1..]).unwrap();
+        assert_eq!(transaction.encode(), signed.raw_transaction);
+        assert_eq!(transaction.transaction_id(), signed.transaction_id);
+        assert_eq!(transaction.recover_sender(), Some(private_key.address()));
+    }
+
+    #[test]
+    fn test_signing_sender_mismatch() {
+        let other_key = EoaPrivateKey::from_any_hex(
+            "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+        )
+        .unwrap();
+        let expected = private_key().address();
+        let found = other_key.address();
+
+        let result = bundle().validate_for_signer(&other_key);
+        assert!(matches!(
+            result,
+            Err(BundleValidationError::SenderMismatch { expected: ref e, found: ref f })
+                if e == &expected && f == &found
+        ));
+        assert!(matches!(
+            bundle().sign(&other_key),
+            Err(BundleSigningError::InvalidBundle(
+                BundleValidationError::SenderMismatch { .. }
+            ))
+        ));
+    }
+}
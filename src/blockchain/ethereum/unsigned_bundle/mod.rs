@@ -0,0 +1,35 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements a container of an unsigned transaction, passed from an online constructor,
+//! which knows the nonces and the fees but holds no keys, to an offline signer.
+//!
+//! The bundle is the RLP encoding of a list:
+//!
+//! ```text
+//! [version, payload, expected_sender, description, integrity_hash]
+//! ```
+//!
+//! - `version`: 1.
+//! - `payload`: the bytes hashed for signing, `0x01 || rlp([fields])` for EIP-2930,
+//!   `0x02 || rlp([fields])` for EIP-1559, and `rlp([fields])` for the legacy and EIP-155
+//!   transactions, told apart by the field count.
+//! - `integrity_hash`: the Keccak-256 hash of `rlp([version, payload, expected_sender,
+//!   description])`.
+//!
+//! The integrity hash detects the bundles altered or corrupted after bundling, but anyone
+//! can recompute it, it doesn't authenticate the constructor. The signer still decodes the
+//! payload itself, e.g., to display it, rather than trusting `description`, and refuses to
+//! sign for a sender other than its own.
+
+mod bundle;
+mod unsigned_payload;
+
+pub use bundle::{
+    BundleSigningError, BundleValidationError, SignedBundle, TransactionSigner,
+    UnsignedTransactionBundle, UNSIGNED_BUNDLE_VERSION,
+};
+pub use unsigned_payload::UnsignedPayload;
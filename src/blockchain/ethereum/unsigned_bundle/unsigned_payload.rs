@@ -0,0 +1,207 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+use crate::blockchain::ethereum::transaction::{
+    PayloadEip155, PayloadEip1559, PayloadEip2930, PayloadLegacy, TransactionEip1559,
+    TransactionEip2930,
+};
+use crate::tools::codable::{decode, encode, DecodingItem};
+use std::fmt;
+use std::fmt::Display;
+
+/// The payload of any of the transaction types, before signing.
+pub enum UnsignedPayload {
+    Legacy(PayloadLegacy),
+    Eip155(PayloadEip155),
+    Eip2930(PayloadEip2930),
+    Eip1559(PayloadEip1559),
+}
+
+impl UnsignedPayload {
+    /// Returns the bytes hashed for signing: `rlp([fields])`, prefixed with the
+    /// transaction type for EIP-2930 and EIP-1559.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            UnsignedPayload::Legacy(payload) => encode(payload),
+            UnsignedPayload::Eip155(payload) => encode(payload),
+            UnsignedPayload::Eip2930(payload) => [
+                &[TransactionEip2930::transaction_type()][..],
+                &encode(payload),
+            ]
+            .concat(),
+            UnsignedPayload::Eip1559(payload) => [
+                &[TransactionEip1559::transaction_type()][..],
+                &encode(payload),
+            ]
+            .concat(),
+        }
+    }
+
+    /// Decodes the bytes returned by `encode`. The legacy and EIP-155 payloads are told
+    /// apart by the field count, 6 and 9.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if `data` isn't the encoding of a supported payload.
+    pub fn decode(data: &[u8]) -> Result<Self, RlpDataDecodingError> {
+        match data.first() {
+            None => Err(RlpDataDecodingError::InvalidFormat),
+            Some(&tx_type) if tx_type == TransactionEip2930::transaction_type() => {
+                Ok(UnsignedPayload::Eip2930(decode(&data[1..])?))
+            }
+            Some(&tx_type) if tx_type == TransactionEip1559::transaction_type() => {
+                Ok(UnsignedPayload::Eip1559(decode(&data[1..])?))
+            }
+            // The first byte of an RLP list
+            Some(0xc0..) => {
+                let field_count = RlpDecodingItem::new_from_data(data)?
+                    .decode_as_items()?
+                    .len();
+                if field_count == 9 {
+                    Ok(UnsignedPayload::Eip155(decode(data)?))
+                } else {
+                    Ok(UnsignedPayload::Legacy(decode(data)?))
+                }
+            }
+            Some(_) => Err(RlpDataDecodingError::TransactionTypeMismatch),
+        }
+    }
+
+    pub fn signing_hash(&self) -> [u8; 32] {
+        match self {
+            UnsignedPayload::Legacy(payload) => payload.signing_hash(),
+            UnsignedPayload::Eip155(payload) => payload.signing_hash(),
+            UnsignedPayload::Eip2930(payload) => payload.signing_hash(),
+            UnsignedPayload::Eip1559(payload) => payload.signing_hash(),
+        }
+    }
+}
+
+impl From<PayloadLegacy> for UnsignedPayload {
+    fn from(payload: PayloadLegacy) -> Self {
+        UnsignedPayload::Legacy(payload)
+    }
+}
+
+impl From<PayloadEip155> for UnsignedPayload {
+    fn from(payload: PayloadEip155) -> Self {
+        UnsignedPayload::Eip155(payload)
+    }
+}
+
+impl From<PayloadEip2930> for UnsignedPayload {
+    fn from(payload: PayloadEip2930) -> Self {
+        UnsignedPayload::Eip2930(payload)
+    }
+}
+
+impl From<PayloadEip1559> for UnsignedPayload {
+    fn from(payload: PayloadEip1559) -> Self {
+        UnsignedPayload::Eip1559(payload)
+    }
+}
+
+impl Display for UnsignedPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsignedPayload::Legacy(payload) => {
+                writeln!(f, "type: legacy")?;
+                write!(f, "{payload}")
+            }
+            UnsignedPayload::Eip155(payload) => {
+                writeln!(f, "type: EIP-155")?;
+                write!(f, "{payload}")
+            }
+            UnsignedPayload::Eip2930(payload) => {
+                writeln!(f, "type: EIP-2930")?;
+                write!(f, "{payload}")
+            }
+            UnsignedPayload::Eip1559(payload) => {
+                writeln!(f, "type: EIP-1559")?;
+                write!(f, "{payload}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::ethereum::transaction::TransactionBuilder;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_encoding() {
+        let builder = TransactionBuilder::new()
+            .with_chain_id(5.into())
+            .with_nonce(42.try_into().unwrap())
+            .with_gas_price("0x01".try_into().unwrap())
+            .with_max_priority_fee_per_gas("0x02".try_into().unwrap())
+            .with_max_fee_per_gas("0x03".try_into().unwrap())
+            .with_gas_limit(21000.into())
+            .with_destination(
+                "0x3535353535353535353535353535353535353535"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount("0x04".try_into().unwrap())
+            .with_data(vec![0x12, 0x34]);
+        let data: [(UnsignedPayload, &str); 4] = [
+            (
+                builder.build_payload_legacy().unwrap().into(),
+                "de2a0182520894353535353535353535353535353535353535353504821234",
+            ),
+            (
+                builder.build_payload_eip_155().unwrap().into(),
+                "e12a0182520894353535353535353535353535353535353535353504821234058080",
+            ),
+            (
+                builder.build_payload_eip_2930().unwrap().into(),
+                "01e0052a0182520894353535353535353535353535353535353535353504821234c0",
+            ),
+            (
+                builder.build_payload_eip_1559().unwrap().into(),
+                "02e1052a020382520894353535353535353535353535353535353535353504821234c0",
+            ),
+        ];
+        for (payload, encoded_hex) in data {
+            let encoded = payload.encode();
+            assert_eq!(hex::encode_lower(&encoded), encoded_hex);
+
+            let decoded = UnsignedPayload::decode(&encoded).unwrap();
+            assert_eq!(decoded.encode(), encoded);
+            assert_eq!(decoded.signing_hash(), payload.signing_hash());
+            assert_eq!(decoded.to_string(), payload.to_string());
+        }
+    }
+
+    #[test]
+    fn test_decoding_error_cases() {
+        let data = [
+            // (encoded, error)
+            ("", RlpDataDecodingError::InvalidFormat),
+            ("03c0", RlpDataDecodingError::TransactionTypeMismatch),
+            ("80", RlpDataDecodingError::TransactionTypeMismatch),
+            ("c0", RlpDataDecodingError::InvalidFormat),
+            ("02c0", RlpDataDecodingError::InvalidFormat),
+            // EIP-155, a trailing field isn't zero
+            (
+                "e12a0182520894353535353535353535353535353535353535353504821234050180",
+                RlpDataDecodingError::InvalidFormat,
+            ),
+        ];
+        for (encoded_hex, error) in data {
+            let result = UnsignedPayload::decode(&hex::decode(encoded_hex).unwrap());
+            assert_eq!(
+                format!("{:?}", result.err().unwrap()),
+                format!("{error:?}"),
+                "{encoded_hex}"
+            );
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Defines `Algorithm`, the pairings of ECDSA curves and hash functions.
+
+use super::ecdsa::{
+    sign_with_options_and_rfc6979_hmac_hasher, verify_with_options, PrivateKey, PublicKey,
+    Signature, SigningError, SigningOptions, VerifyingError, VerifyingOptions,
+};
+use super::elliptic_curve_params::EllipticCurveParams;
+use super::hash::{Keccak256, Sha256, Sha384, Sha512, UnkeyedHash};
+use super::nist_curves::{secp256r1, secp384r1, secp521r1};
+use super::secp256k1::secp256k1;
+
+/// An ECDSA curve combined with the hash function applied to the messages,
+/// so that a message is never hashed with a function the protocol doesn't expect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Secp256k1Sha256,
+    P256Sha256,
+    P384Sha384,
+    P521Sha512,
+    /// As used by Ethereum.
+    Secp256k1Keccak256,
+}
+
+impl Algorithm {
+    /// Returns the parameters of the curve of `self`.
+    pub fn curve_params(&self) -> &'static EllipticCurveParams {
+        match self {
+            Algorithm::Secp256k1Sha256 | Algorithm::Secp256k1Keccak256 => secp256k1(),
+            Algorithm::P256Sha256 => secp256r1(),
+            Algorithm::P384Sha384 => secp384r1(),
+            Algorithm::P521Sha512 => secp521r1(),
+        }
+    }
+
+    /// Returns the hash of `message` with the hash function of `self`.
+    pub fn hash(&self, message: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Secp256k1Sha256 | Algorithm::P256Sha256 => Sha256::new().digest(message),
+            Algorithm::P384Sha384 => Sha384::new().digest(message),
+            Algorithm::P521Sha512 => Sha512::new().digest(message),
+            Algorithm::Secp256k1Keccak256 => Keccak256::new().digest(message),
+        }
+    }
+
+    /// Hashes `message` and signs the hash with `private_key`.
+    pub fn hash_and_sign<'a>(
+        &self,
+        message: &[u8],
+        private_key: &'a PrivateKey,
+    ) -> Result<Signature<'a>, SigningError> {
+        self.hash_and_sign_with_options(message, private_key, &SigningOptions::default())
+    }
+
+    /// Hashes `message` and signs the hash with `private_key`.
+    ///
+    /// `options.strict_hash_byte_length` is ignored, for the hash function is determined
+    /// by `self`. For P-521, the 512-bit hash is shorter than the order of the base point.
+    ///
+    /// The HMAC of the deterministic nonce (RFC 6979) employs the hash function of `self`,
+    /// except for `Secp256k1Keccak256`, which employs SHA-256 as the other Ethereum
+    /// implementations do.
+    ///
+    /// # Errors
+    ///
+    /// Will return `SigningError::CurveMismatch` if `private_key` isn't on the curve of
+    /// `self`.
+    pub fn hash_and_sign_with_options<'a>(
+        &self,
+        message: &[u8],
+        private_key: &'a PrivateKey,
+        options: &SigningOptions,
+    ) -> Result<Signature<'a>, SigningError> {
+        if private_key.curve_params != self.curve_params() {
+            return Err(SigningError::CurveMismatch);
+        }
+
+        let hash = self.hash(message);
+        let options = SigningOptions {
+            strict_hash_byte_length: false,
+            ..options.clone()
+        };
+        let (signature, _) = match self {
+            Algorithm::Secp256k1Sha256
+            | Algorithm::P256Sha256
+            | Algorithm::Secp256k1Keccak256 => sign_with_options_and_rfc6979_hmac_hasher(
+                &hash,
+                private_key,
+                &options,
+                &mut Sha256::new(),
+            ),
+            Algorithm::P384Sha384 => sign_with_options_and_rfc6979_hmac_hasher(
+                &hash,
+                private_key,
+                &options,
+                &mut Sha384::new(),
+            ),
+            Algorithm::P521Sha512 => sign_with_options_and_rfc6979_hmac_hasher(
+                &hash,
+                private_key,
+                &options,
+                &mut Sha512::new(),
+            ),
+        }?;
+        Ok(signature)
+    }
+
+    /// Hashes `message` and verifies `signature` of the hash with `public_key`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `VerifyingError::InvalidSignature` if the verification fails, and
+    /// `VerifyingError::CurveMismatch` if `signature` or `public_key` isn't on the curve
+    /// of `self`.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+        public_key: &PublicKey,
+    ) -> Result<(), VerifyingError> {
+        let curve_params = self.curve_params();
+        if public_key.curve_params != curve_params || signature.curve_params != curve_params {
+            return Err(VerifyingError::CurveMismatch);
+        }
+
+        let hash = self.hash(message);
+        let options = VerifyingOptions {
+            strict_hash_byte_length: false,
+            ..Default::default()
+        };
+        if verify_with_options(&hash, signature, public_key, &options)? {
+            Ok(())
+        } else {
+            Err(VerifyingError::InvalidSignature)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+
+    const ALGORITHMS: [Algorithm; 5] = [
+        Algorithm::Secp256k1Sha256,
+        Algorithm::P256Sha256,
+        Algorithm::P384Sha384,
+        Algorithm::P521Sha512,
+        Algorithm::Secp256k1Keccak256,
+    ];
+
+    fn deterministic_options() -> SigningOptions {
+        SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hash_and_sign_verify() {
+        for algorithm in ALGORITHMS {
+            let private_key =
+                PrivateKey::new(BigInt::from(0xc0ffee), algorithm.curve_params()).unwrap();
+            let public_key = private_key.public_key();
+            let signature = algorithm
+                .hash_and_sign_with_options(b"sample", &private_key, &deterministic_options())
+                .unwrap();
+
+            assert_eq!(algorithm.verify(b"sample", &signature, &public_key), Ok(()));
+            assert_eq!(
+                algorithm.verify(b"test", &signature, &public_key),
+                Err(VerifyingError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rfc6979_sample() {
+        // RFC 6979, A.2.5. ECDSA, 256 Bits (Prime Field), With SHA-256, message = "sample"
+        let private_key = PrivateKey::new(
+            BigInt::from_hex(
+                "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+            )
+            .unwrap(),
+            secp256r1(),
+        )
+        .unwrap();
+        let signature = Algorithm::P256Sha256
+            .hash_and_sign_with_options(
+                b"sample",
+                &private_key,
+                &SigningOptions {
+                    enforce_low_s: false,
+                    ..deterministic_options()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            signature.to_p1363_hex().unwrap(),
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+             f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+        );
+    }
+
+    #[test]
+    fn test_curve_mismatch() {
+        let private_key = PrivateKey::new(BigInt::from(0xc0ffee), secp256k1()).unwrap();
+        let public_key = private_key.public_key();
+        let signature = Algorithm::Secp256k1Sha256
+            .hash_and_sign_with_options(b"sample", &private_key, &deterministic_options())
+            .unwrap();
+
+        assert_eq!(
+            Algorithm::P256Sha256
+                .hash_and_sign_with_options(b"sample", &private_key, &deterministic_options())
+                .unwrap_err(),
+            SigningError::CurveMismatch
+        );
+        assert_eq!(
+            Algorithm::P256Sha256.verify(b"sample", &signature, &public_key),
+            Err(VerifyingError::CurveMismatch)
+        );
+        // Same curve, different hash functions
+        assert_eq!(
+            Algorithm::Secp256k1Keccak256.verify(b"sample", &signature, &public_key),
+            Err(VerifyingError::InvalidSignature)
+        );
+    }
+}
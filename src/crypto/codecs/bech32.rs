@@ -0,0 +1,751 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements Bech32 (BIP-173) and Bech32m (BIP-350), and the segwit addresses on top of
+//! them.
+//!
+//! A Bech32 string is a human-readable part (HRP), the separator "1", and the data part:
+//! 5-bit values in the alphabet "qpzry9x8gf2tvdw0s3jn54khce6mua7l", followed by a 6-char
+//! checksum. The checksum is a BCH code over GF(32). Bech32 and Bech32m differ only in
+//! the constant the checksum is XORed with.
+//!
+//! - `encode`/`decode` convert between strings and the HRP and the 5-bit values.
+//! - `regroup_to_5bit`/`regroup_from_5bit` convert between bytes and 5-bit values.
+//! - `segwit_address`/`decode_segwit_address` tie them together for the witness
+//!   programs, e.g., P2WPKH and P2TR.
+//!
+//! Strings are at most 90 characters, the HRP characters are in the ASCII range
+//! `[33, 126]`, and a string is either all lowercase or all uppercase. The encoding outputs
+//! lowercase.
+//!
+//! # Examples
+//!
+//! ```
+//! use lightcryptotools::crypto::codecs::bech32::{self, Bech32Variant};
+//! use lightcryptotools::crypto::codecs::hex;
+//!
+//! let program = hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap();
+//! let address = bech32::segwit_address("bc", 0, &program).unwrap();
+//! assert_eq!(address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+//!
+//! let (hrp, data, variant) = bech32::decode(&address).unwrap();
+//! assert_eq!(hrp, "bc");
+//! assert_eq!(variant, Bech32Variant::Bech32);
+//! assert_eq!(bech32::regroup_from_5bit(&data[1..]).unwrap(), program);
+//! ```
+//!
+//! See: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki and
+//! https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
+use core::iter;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: u8 = b'1';
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+/// The HRP leaves room for the separator and the checksum.
+const MAX_HRP_LENGTH: usize = MAX_LENGTH - 1 - CHECKSUM_LENGTH;
+const BECH32M_CONSTANT: u32 = 0x2bc830a3;
+
+const MAX_WITNESS_VERSION: u8 = 16;
+const MIN_WITNESS_PROGRAM_LENGTH: usize = 2;
+const MAX_WITNESS_PROGRAM_LENGTH: usize = 40;
+
+/// The checksum variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bech32Variant {
+    /// BIP-173, for the segwit addresses of witness version 0.
+    Bech32,
+    /// BIP-350, for the segwit addresses of witness versions 1 through 16.
+    Bech32m,
+}
+
+impl Bech32Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => BECH32M_CONSTANT,
+        }
+    }
+}
+
+/// Returns the Bech32 string of `hrp` and the 5-bit values `data_5bit`, with the checksum
+/// of `variant`.
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - `hrp` is empty or has characters out of `[33, 126]`.
+/// - `hrp` mixes lowercase and uppercase letters. It's lowercased otherwise.
+/// - A value of `data_5bit` is greater than 31.
+/// - The string would be longer than 90 characters.
+pub fn encode(
+    hrp: &str,
+    data_5bit: &[u8],
+    variant: Bech32Variant,
+) -> Result<String, Bech32Error> {
+    let hrp = hrp.as_bytes();
+    if hrp.is_empty() || hrp.len() > MAX_HRP_LENGTH {
+        return Err(Bech32Error::InvalidHrp);
+    }
+    validate_characters(hrp)?;
+    if hrp.len() + 1 + data_5bit.len() + CHECKSUM_LENGTH > MAX_LENGTH {
+        return Err(Bech32Error::TooLong);
+    }
+    if data_5bit.iter().any(|&value| value > 31) {
+        return Err(Bech32Error::InvalidData);
+    }
+
+    let hrp = hrp.to_ascii_lowercase();
+    let checksum = create_checksum(&hrp, data_5bit, variant);
+
+    let mut s = String::with_capacity(hrp.len() + 1 + data_5bit.len() + CHECKSUM_LENGTH);
+    s.extend(hrp.iter().map(|&c| c as char));
+    s.push(SEPARATOR as char);
+    s.extend(
+        data_5bit
+            .iter()
+            .chain(&checksum)
+            .map(|&value| CHARSET[value as usize] as char),
+    );
+    Ok(s)
+}
+
+/// Returns the lowercase HRP, the 5-bit values without the checksum, and the checksum
+/// variant of the Bech32 string `s`.
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - `s` is longer than 90 characters.
+/// - `s` has characters out of `[33, 126]`, or the data part has characters out of the
+///   alphabet.
+/// - `s` mixes lowercase and uppercase letters.
+/// - The separator is missing, or the HRP is empty.
+/// - The data part is shorter than the checksum.
+/// - The checksum is neither a Bech32 nor a Bech32m one.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>, Bech32Variant), Bech32Error> {
+    let bytes = s.as_bytes();
+    if bytes.len() > MAX_LENGTH {
+        return Err(Bech32Error::TooLong);
+    }
+    validate_characters(bytes)?;
+
+    let bytes = bytes.to_ascii_lowercase();
+    let separator_position = bytes
+        .iter()
+        .rposition(|&c| c == SEPARATOR)
+        .ok_or(Bech32Error::MissingSeparator)?;
+    if separator_position == 0 {
+        return Err(Bech32Error::InvalidHrp);
+    }
+    let (hrp, data_part) = (
+        &bytes[..separator_position],
+        &bytes[separator_position + 1..],
+    );
+    if data_part.len() < CHECKSUM_LENGTH {
+        return Err(Bech32Error::TooShortChecksum);
+    }
+
+    let mut data = Vec::with_capacity(data_part.len());
+    for (i, &c) in data_part.iter().enumerate() {
+        let value = CHARSET.iter().position(|&d| d == c).ok_or_else(|| {
+            let position = separator_position + 1 + i;
+            Bech32Error::InvalidCharacter {
+                position,
+                found: s.as_bytes()[position],
+            }
+        })?;
+        data.push(value as u8);
+    }
+
+    let variant = match polymod(hrp_expand(hrp).chain(data.iter().copied())) {
+        1 => Bech32Variant::Bech32,
+        BECH32M_CONSTANT => Bech32Variant::Bech32m,
+        _ => return Err(Bech32Error::InvalidChecksum),
+    };
+    data.truncate(data.len() - CHECKSUM_LENGTH);
+
+    let hrp = hrp.iter().map(|&c| c as char).collect();
+    Ok((hrp, data, variant))
+}
+
+/// Regroups `bytes` into 5-bit values, most significant bits first. The bits of the last
+/// value are zero padded.
+pub fn regroup_to_5bit(bytes: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut accumulator = 0_u32;
+    let mut bits = 0;
+    for &byte in bytes {
+        accumulator = (accumulator << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data.push(((accumulator >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        data.push(((accumulator << (5 - bits)) & 31) as u8);
+    }
+    data
+}
+
+/// Regroups the 5-bit values `data` into bytes, the reverse of [`regroup_to_5bit`].
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - A value of `data` is greater than 31.
+/// - The padding bits are more than 4, or aren't all zero.
+pub fn regroup_from_5bit(data: &[u8]) -> Result<Vec<u8>, Bech32Error> {
+    let mut bytes = Vec::with_capacity(data.len() * 5 / 8);
+    let mut accumulator = 0_u32;
+    let mut bits = 0;
+    for &value in data {
+        if value > 31 {
+            return Err(Bech32Error::InvalidData);
+        }
+        accumulator = (accumulator << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((accumulator >> bits) as u8);
+        }
+    }
+    if bits >= 5 || accumulator & ((1 << bits) - 1) != 0 {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(bytes)
+}
+
+/// Returns the segwit address of the witness `program` and `witness_version`, e.g., with
+/// `hrp` "bc" for the Bitcoin mainnet and "tb" for the testnet.
+///
+/// Version 0 is encoded with Bech32, versions 1 through 16 with Bech32m, e.g., P2TR
+/// (version 1, a 32-byte program).
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - `witness_version` is greater than 16.
+/// - The program isn't 2 to 40 bytes, or, for version 0, isn't 20 (P2WPKH) or 32 (P2WSH)
+///   bytes.
+/// - `hrp` isn't valid, see [`encode`].
+pub fn segwit_address(
+    hrp: &str,
+    witness_version: u8,
+    program: &[u8],
+) -> Result<String, Bech32Error> {
+    validate_witness_program(witness_version, program)?;
+    let variant = if witness_version == 0 {
+        Bech32Variant::Bech32
+    } else {
+        Bech32Variant::Bech32m
+    };
+
+    let mut data = Vec::with_capacity(1 + (program.len() * 8).div_ceil(5));
+    data.push(witness_version);
+    data.extend(regroup_to_5bit(program));
+    encode(hrp, &data, variant)
+}
+
+/// Returns the witness version and the witness program of the segwit `address`, the
+/// reverse of [`segwit_address`].
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - `address` isn't a valid Bech32 string, see [`decode`].
+/// - The HRP of `address` isn't `hrp`, compared case-insensitively.
+/// - The witness version is missing or greater than 16.
+/// - The checksum variant doesn't match the witness version (an `InvalidChecksum`).
+/// - The program isn't valid, see [`segwit_address`].
+pub fn decode_segwit_address(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), Bech32Error> {
+    let (decoded_hrp, data, variant) = decode(address)?;
+    if !decoded_hrp.eq_ignore_ascii_case(hrp) {
+        return Err(Bech32Error::HrpMismatch);
+    }
+    let (&witness_version, data) = data
+        .split_first()
+        .ok_or(Bech32Error::InvalidWitnessVersion)?;
+    if witness_version > MAX_WITNESS_VERSION {
+        return Err(Bech32Error::InvalidWitnessVersion);
+    }
+    let expected_variant = if witness_version == 0 {
+        Bech32Variant::Bech32
+    } else {
+        Bech32Variant::Bech32m
+    };
+    if variant != expected_variant {
+        return Err(Bech32Error::InvalidChecksum);
+    }
+
+    let program = regroup_from_5bit(data)?;
+    validate_witness_program(witness_version, &program)?;
+    Ok((witness_version, program))
+}
+
+fn validate_witness_program(witness_version: u8, program: &[u8]) -> Result<(), Bech32Error> {
+    if witness_version > MAX_WITNESS_VERSION {
+        return Err(Bech32Error::InvalidWitnessVersion);
+    }
+    if !(MIN_WITNESS_PROGRAM_LENGTH..=MAX_WITNESS_PROGRAM_LENGTH).contains(&program.len())
+        || (witness_version == 0 && program.len() != 20 && program.len() != 32)
+    {
+        return Err(Bech32Error::InvalidWitnessProgramLength);
+    }
+    Ok(())
+}
+
+/// Checks the characters are in `[33, 126]` and not in mixed case.
+fn validate_characters(s: &[u8]) -> Result<(), Bech32Error> {
+    if let Some(position) = s.iter().position(|c| !(33..=126).contains(c)) {
+        return Err(Bech32Error::InvalidCharacter {
+            position,
+            found: s[position],
+        });
+    }
+    if s.iter().any(u8::is_ascii_lowercase) && s.iter().any(u8::is_ascii_uppercase) {
+        return Err(Bech32Error::MixedCase);
+    }
+    Ok(())
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATORS: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let mut checksum = 1_u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Expands the HRP into the high bits of its characters, a zero, and the low bits.
+fn hrp_expand(hrp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hrp.iter()
+        .map(|c| c >> 5)
+        .chain(iter::once(0))
+        .chain(hrp.iter().map(|c| c & 31))
+}
+
+fn create_checksum(hrp: &[u8], data: &[u8], variant: Bech32Variant) -> [u8; CHECKSUM_LENGTH] {
+    let values = hrp_expand(hrp)
+        .chain(data.iter().copied())
+        .chain([0; CHECKSUM_LENGTH]);
+    let checksum = polymod(values) ^ variant.constant();
+    core::array::from_fn(|i| ((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 31) as u8)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Bech32Error {
+    /// The string is longer than 90 characters.
+    TooLong,
+    /// `found` at `position` is out of `[33, 126]`, or out of the alphabet of the data part.
+    InvalidCharacter { position: usize, found: u8 },
+    /// The string mixes lowercase and uppercase letters.
+    MixedCase,
+    /// The separator "1" is missing.
+    MissingSeparator,
+    /// The HRP is empty, or too long to leave room for the checksum.
+    InvalidHrp,
+    /// The data part is shorter than the 6-char checksum.
+    TooShortChecksum,
+    /// The checksum doesn't verify, or its variant doesn't match the witness version.
+    InvalidChecksum,
+    /// A value to encode or regroup is greater than 31.
+    InvalidData,
+    /// Regrouping 5-bit values to bytes leaves more than 4 bits, or non-zero bits.
+    InvalidPadding,
+    /// The HRP of a segwit address isn't the expected one.
+    HrpMismatch,
+    /// The witness version is missing or greater than 16.
+    InvalidWitnessVersion,
+    /// The witness program isn't 2 to 40 bytes, or 20 or 32 bytes for version 0.
+    InvalidWitnessProgramLength,
+}
+
+impl Display for Bech32Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bech32Error::TooLong => write!(f, "bech32 string longer than 90 characters"),
+            Bech32Error::InvalidCharacter { position, found } => write!(
+                f,
+                "invalid bech32 character '{}' at position {position}",
+                (*found as char).escape_default()
+            ),
+            Bech32Error::MixedCase => write!(f, "mixed-case bech32 string"),
+            Bech32Error::MissingSeparator => write!(f, "missing bech32 separator"),
+            Bech32Error::InvalidHrp => write!(f, "invalid bech32 human-readable part"),
+            Bech32Error::TooShortChecksum => write!(f, "bech32 checksum too short"),
+            Bech32Error::InvalidChecksum => write!(f, "invalid bech32 checksum"),
+            Bech32Error::InvalidData => write!(f, "invalid 5-bit value"),
+            Bech32Error::InvalidPadding => write!(f, "invalid padding of 5-bit values"),
+            Bech32Error::HrpMismatch => write!(f, "unexpected human-readable part"),
+            Bech32Error::InvalidWitnessVersion => write!(f, "invalid witness version"),
+            Bech32Error::InvalidWitnessProgramLength => {
+                write!(f, "invalid witness program length")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Bech32Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use ::quickcheck_macros::quickcheck;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_decode_valid_strings() {
+        // BIP-173 and BIP-350
+        // (string, variant)
+        let data = [
+            ("A12UEL5L", Bech32Variant::Bech32),
+            ("a12uel5l", Bech32Variant::Bech32),
+            ("an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs", Bech32Variant::Bech32),
+            ("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Bech32Variant::Bech32),
+            ("11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j", Bech32Variant::Bech32),
+            ("split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w", Bech32Variant::Bech32),
+            ("?1ezyfcl", Bech32Variant::Bech32),
+            ("A1LQFN3A", Bech32Variant::Bech32m),
+            ("a1lqfn3a", Bech32Variant::Bech32m),
+            ("an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6", Bech32Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Bech32Variant::Bech32m),
+            ("11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8", Bech32Variant::Bech32m),
+            ("split1checkupstagehandshakeupstreamerranterredcaperredlc445v", Bech32Variant::Bech32m),
+            ("?1v759aa", Bech32Variant::Bech32m),
+        ];
+        for (s, variant) in data {
+            let (hrp, values, decoded_variant) = decode(s).unwrap();
+            assert_eq!(decoded_variant, variant);
+            assert_eq!(
+                encode(&hrp, &values, variant).unwrap(),
+                s.to_ascii_lowercase()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_strings() {
+        // BIP-173 and BIP-350
+        // (string, error)
+        let data = [
+            ("\u{20}1nwldj5", Bech32Error::InvalidCharacter { position: 0, found: 0x20 }),
+            ("\u{7f}1axkwrx", Bech32Error::InvalidCharacter { position: 0, found: 0x7f }),
+            ("\u{80}1eym55h", Bech32Error::InvalidCharacter { position: 0, found: 0xc2 }),
+            ("an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx", Bech32Error::TooLong),
+            ("pzry9x0s0muk", Bech32Error::MissingSeparator),
+            ("1pzry9x0s0muk", Bech32Error::InvalidHrp),
+            ("x1b4n0q5v", Bech32Error::InvalidCharacter { position: 2, found: b'b' }),
+            ("li1dgmt3", Bech32Error::TooShortChecksum),
+            ("de1lg7wt\u{ff}", Bech32Error::InvalidCharacter { position: 8, found: 0xc3 }),
+            // The checksum is calculated with the uppercase form of the HRP.
+            ("A1G7SGD8", Bech32Error::InvalidChecksum),
+            ("10a06t8", Bech32Error::InvalidHrp),
+            ("1qzzfhee", Bech32Error::InvalidHrp),
+            ("\u{20}1xj0phk", Bech32Error::InvalidCharacter { position: 0, found: 0x20 }),
+            ("\u{7f}1g6xzxy", Bech32Error::InvalidCharacter { position: 0, found: 0x7f }),
+            ("\u{80}1vctc34", Bech32Error::InvalidCharacter { position: 0, found: 0xc2 }),
+            ("an84characterslonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11d6pts4", Bech32Error::TooLong),
+            ("qyrz8wqd2c9m", Bech32Error::MissingSeparator),
+            ("1qyrz8wqd2c9m", Bech32Error::InvalidHrp),
+            ("y1b0jsk6g", Bech32Error::InvalidCharacter { position: 2, found: b'b' }),
+            ("lt1igcx5c0", Bech32Error::InvalidCharacter { position: 3, found: b'i' }),
+            ("in1muywd", Bech32Error::TooShortChecksum),
+            ("mm1crxm3i", Bech32Error::InvalidCharacter { position: 8, found: b'i' }),
+            ("au1s5cgom", Bech32Error::InvalidCharacter { position: 7, found: b'o' }),
+            ("M1VUXWEZ", Bech32Error::InvalidChecksum),
+            ("16plkw9", Bech32Error::InvalidHrp),
+            ("1p2gdwpf", Bech32Error::InvalidHrp),
+            ("a12UEL5L", Bech32Error::MixedCase),
+        ];
+        for (s, err) in data {
+            assert_eq!(decode(s), Err(err), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_encode_err_cases() {
+        // (hrp, data, error)
+        let data: [(&str, &[u8], Bech32Error); 5] = [
+            ("", &[], Bech32Error::InvalidHrp),
+            (
+                "a b",
+                &[],
+                Bech32Error::InvalidCharacter {
+                    position: 1,
+                    found: b' ',
+                },
+            ),
+            ("aB", &[], Bech32Error::MixedCase),
+            ("a", &[32], Bech32Error::InvalidData),
+            ("a", &[0; 83], Bech32Error::TooLong),
+        ];
+        for (hrp, data_5bit, err) in data {
+            assert_eq!(encode(hrp, data_5bit, Bech32Variant::Bech32), Err(err));
+        }
+
+        assert_eq!(encode("A", &[], Bech32Variant::Bech32).unwrap(), "a12uel5l");
+        assert_eq!(
+            encode("a", &[0; 82], Bech32Variant::Bech32).unwrap().len(),
+            90
+        );
+    }
+
+    #[test]
+    fn test_segwit_addresses() {
+        // BIP-350
+        // (address, witness version, witness program)
+        let data = [
+            ("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", 0, "751e76e8199196d454941c45d1b3a323f1433bd6"),
+            ("tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", 0, "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"),
+            ("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", 1, "751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6"),
+            ("BC1SW50QGDZ25J", 16, "751e"),
+            ("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", 2, "751e76e8199196d454941c45d1b3a323"),
+            ("tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy", 0, "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433"),
+            ("tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c", 1, "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433"),
+            ("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", 1, "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        ];
+        for (address, witness_version, program_hex) in data {
+            let hrp = &address[..2];
+            let program = hex::decode(program_hex).unwrap();
+            assert_eq!(
+                decode_segwit_address(hrp, address).unwrap(),
+                (witness_version, program.clone())
+            );
+            assert_eq!(
+                segwit_address(hrp, witness_version, &program).unwrap(),
+                address.to_ascii_lowercase()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_segwit_addresses() {
+        // BIP-173 and BIP-350. The BIP-173 addresses of witness versions 1 and later have
+        // Bech32 checksums, which fail before the checks they were meant for.
+        // (hrp, address, error)
+        let data = [
+            (
+                "bc",
+                "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty",
+                Bech32Error::HrpMismatch,
+            ),
+            (
+                "bc",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2",
+                Bech32Error::InvalidWitnessVersion,
+            ),
+            ("bc", "bc1rw5uspcuh", Bech32Error::InvalidChecksum),
+            (
+                "bc",
+                "bc10w508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kw5rljs90",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+                Bech32Error::InvalidWitnessProgramLength,
+            ),
+            (
+                "tb",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7",
+                Bech32Error::MixedCase,
+            ),
+            (
+                "bc",
+                "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "tb",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv",
+                Bech32Error::InvalidPadding,
+            ),
+            ("bc", "bc1gmk9yu", Bech32Error::InvalidWitnessVersion),
+            // Witness versions 1 and later with Bech32 checksums, valid before BIP-350
+            (
+                "bc",
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx",
+                Bech32Error::InvalidChecksum,
+            ),
+            ("bc", "BC1SW50QA3JX3S", Bech32Error::InvalidChecksum),
+            (
+                "bc",
+                "bc1zw508d6qejxtdg4y5r3zarvaryvg6kdaj",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+                Bech32Error::HrpMismatch,
+            ),
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "tb",
+                "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "tb",
+                "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+                Bech32Error::InvalidChecksum,
+            ),
+            (
+                "bc",
+                "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+                Bech32Error::InvalidCharacter {
+                    position: 59,
+                    found: b'o',
+                },
+            ),
+            (
+                "bc",
+                "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+                Bech32Error::InvalidWitnessVersion,
+            ),
+            (
+                "bc",
+                "bc1pw5dgrnzv",
+                Bech32Error::InvalidWitnessProgramLength,
+            ),
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+                Bech32Error::InvalidWitnessProgramLength,
+            ),
+            (
+                "tb",
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+                Bech32Error::MixedCase,
+            ),
+            (
+                "bc",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+                Bech32Error::InvalidPadding,
+            ),
+            (
+                "tb",
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+                Bech32Error::InvalidPadding,
+            ),
+        ];
+        for (hrp, address, err) in data {
+            assert_eq!(decode_segwit_address(hrp, address), Err(err), "{address}");
+        }
+    }
+
+    #[test]
+    fn test_segwit_address_err_cases() {
+        let program = [0; 20];
+        assert_eq!(
+            segwit_address("bc", 17, &program),
+            Err(Bech32Error::InvalidWitnessVersion)
+        );
+        assert_eq!(
+            segwit_address("bc", 0, &program[..19]),
+            Err(Bech32Error::InvalidWitnessProgramLength)
+        );
+        assert_eq!(
+            segwit_address("bc", 1, &[0; 41]),
+            Err(Bech32Error::InvalidWitnessProgramLength)
+        );
+        assert!(segwit_address("bc", 1, &program[..2]).is_ok());
+    }
+
+    #[test]
+    fn test_regroup() {
+        // (bytes, 5-bit values)
+        let data: [(&[u8], &[u8]); 4] = [
+            (&[], &[]),
+            (&[0xff], &[31, 28]),
+            (&[0x75, 0x1e], &[14, 20, 15, 0]),
+            (&[0xff; 5], &[31; 8]),
+        ];
+        for (bytes, values) in data {
+            assert_eq!(regroup_to_5bit(bytes), values);
+            assert_eq!(regroup_from_5bit(values).unwrap(), bytes);
+        }
+
+        // (5-bit values, error)
+        let data: [(&[u8], Bech32Error); 4] = [
+            (&[32], Bech32Error::InvalidData),
+            // 5 bits of padding
+            (&[0], Bech32Error::InvalidPadding),
+            // Non-zero padding
+            (&[31, 29], Bech32Error::InvalidPadding),
+            (&[14, 20, 15, 1], Bech32Error::InvalidPadding),
+        ];
+        for (values, err) in data {
+            assert_eq!(regroup_from_5bit(values), Err(err));
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = Bech32Error::InvalidCharacter {
+            position: 2,
+            found: b'b',
+        };
+        assert_eq!(
+            err.to_string(),
+            "invalid bech32 character 'b' at position 2"
+        );
+    }
+
+    #[quickcheck]
+    fn regroup_round_trip(bytes: Vec<u8>) -> bool {
+        regroup_from_5bit(&regroup_to_5bit(&bytes)).unwrap() == bytes
+    }
+
+    #[quickcheck]
+    fn encode_and_decode_round_trip(bytes: Vec<u8>, is_bech32m: bool) -> bool {
+        let variant = if is_bech32m {
+            Bech32Variant::Bech32m
+        } else {
+            Bech32Variant::Bech32
+        };
+        let data = regroup_to_5bit(&bytes[..bytes.len().min(40)]);
+        let s = encode("lct", &data, variant).unwrap();
+        decode(&s).unwrap() == ("lct".to_string(), data, variant)
+            && decode(&s.to_ascii_uppercase()).unwrap().1
+                == regroup_to_5bit(&bytes[..bytes.len().min(40)])
+    }
+}
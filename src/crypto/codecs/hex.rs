@@ -0,0 +1,596 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Constant-time implementation for hex/bytes conversion.
+//!
+//! From time to time, your colleagues pass down some [mysterious code snippets][1] that
+//! you can copy and paste into your project, and they just work.
+//! This implementation is one of them.
+//! The same code can be found in [libsodium][2] and [WireGuard][3].
+//! Original code by [CodesInChaos][4].
+//!
+//! - `encode_lower`/`encode_upper` return the hexadecimal of bytes, `encode_into` and
+//!   `encode_upper_into` append it to a `String`.
+//! - `decode` returns the bytes of a hexadecimal string, `decode_into` appends them to a
+//!   `Vec<u8>`, and `decode_in_place` decodes a buffer onto itself.
+//!
+//! The `*_into` and `*_in_place` variants don't allocate if the buffers have the room.
+//!
+//! The decoding accepts both lowercase and uppercase digits. A "0x" prefix is rejected
+//! as an invalid character, unless accepted with `DecodingOptions`. An odd number of
+//! digits is always an error, the digits aren't zero padded.
+//!
+//! # Examples
+//!
+//! ```
+//! use lightcryptotools::crypto::codecs::hex::{self, DecodingOptions, HexDecodingError};
+//!
+//! assert_eq!(hex::encode_lower(&[0x13, 0x7a, 0xcf]), "137acf");
+//! assert_eq!(hex::encode_upper(&[0x13, 0x7a, 0xcf]), "137ACF");
+//! assert_eq!(hex::decode("137aCF").unwrap(), [0x13, 0x7a, 0xcf]);
+//!
+//! let err = hex::decode("137gcf").unwrap_err();
+//! assert_eq!(err, HexDecodingError::InvalidCharacter { position: 3, found: b'g' });
+//!
+//! let options = DecodingOptions { allows_0x_prefix: true };
+//! assert_eq!(hex::decode_with_options("0x137acf", &options).unwrap(), [0x13, 0x7a, 0xcf]);
+//! ```
+//!
+//! [1]: https://www.reddit.com/r/HalfLife/comments/nwrtol/valve_still_uses_the_same_light_flicker_pattern/
+//! [2]: https://github.com/jedisct1/libsodium/blob/64129657a5c67f3bab84562aa8d57dacc685cc75/src/libsodium/sodium/codecs.c#L12-L101
+//! [3]: https://git.zx2c4.com/wireguard-tools/tree/src/encoding.c?id=d8230ea0dcb02d716125b2b3c076f2de40ebed99#n74
+//! [4]: https://stackoverflow.com/questions/311165/how-do-you-convert-a-byte-array-to-a-hexadecimal-string-and-vice-versa#answer-14333437
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
+
+/// 'a' - 10, the ASCII of the lowercase digit of a nibble in [0xa, 0xf] is the base + nibble.
+const LOWER_ALPHA_BASE: i8 = 87;
+/// 'A' - 10, the ASCII of the uppercase digit of a nibble in [0xa, 0xf] is the base + nibble.
+const UPPER_ALPHA_BASE: i8 = 55;
+
+/// The options of decoding.
+#[derive(Clone, Debug, Default)]
+pub struct DecodingOptions {
+    /// Accepts, and skips, a leading "0x" or "0X". False by default.
+    pub allows_0x_prefix: bool,
+}
+
+/// Returns the lowercase hexadecimal of `bytes`.
+///
+/// Each byte is converted into the corresponding 2-digit hex representation.
+pub fn encode_lower(bytes: &[u8]) -> String {
+    let mut hex = String::new();
+    encode_into(bytes, &mut hex);
+    hex
+}
+
+/// Returns the uppercase hexadecimal of `bytes`.
+pub fn encode_upper(bytes: &[u8]) -> String {
+    let mut hex = String::new();
+    encode_upper_into(bytes, &mut hex);
+    hex
+}
+
+/// Appends the lowercase hexadecimal of `bytes` to `hex`.
+///
+/// Doesn't allocate if `hex` has the room for `bytes.len() * 2` more characters.
+pub fn encode_into(bytes: &[u8], hex: &mut String) {
+    encode_with_alpha_base(bytes, LOWER_ALPHA_BASE, hex);
+}
+
+/// Appends the uppercase hexadecimal of `bytes` to `hex`.
+///
+/// Doesn't allocate if `hex` has the room for `bytes.len() * 2` more characters.
+pub fn encode_upper_into(bytes: &[u8], hex: &mut String) {
+    encode_with_alpha_base(bytes, UPPER_ALPHA_BASE, hex);
+}
+
+fn encode_with_alpha_base(bytes: &[u8], alpha_base: i8, hex: &mut String) {
+    // 1 byte expands to 2 corresponding hexadecimal digits.
+    hex.reserve(bytes.len() * 2);
+
+    // 48 is '0', the ASCII of the digit of a nibble in [0x0, 0x9] is 48 + nibble.
+    let digit_offset = 48 - alpha_base;
+    for byte in bytes {
+        let low_nibble = (byte & 0x0f) as i8;
+        let high_nibble = (byte >> 4) as i8;
+
+        // A nibble is 4 bits, representing [0x0, 0xf]
+        //
+        // 1. `nibble - 10`:
+        //
+        //     The result is < 0 for nibble in [0x0, 0x9] (char: 0-9),
+        //     and >= 0 for nibble in [0xa, 0xf] (char: a-f)
+        //
+        // 2. ``lhs_i8 >> 7``:
+        //
+        //     Using ``>> 7`` on lhs_i8 extracts the sign[^1].
+        //     The result is -1 for lhs_i8 < 0, and 0 for lhs_i8 >= 0.
+        //
+        // 3. Combining 1 and 2, `(nibble - 10) >> 7`:
+        //    The result is -1 for nibble in [0x0, 0x9], and 0 for nibble in [0xa, 0xf]
+        //
+        // 4. `-1 & digit_offset` and `0 & digit_offset`:
+        //
+        //     The result is `digit_offset` for `-1 & digit_offset`, 0 for `0 & digit_offset`
+        //
+        // 5. `alpha_base + nibble + digit_offset` or `alpha_base + nibble + 0`:
+        //
+        //     Combining 3 and 4,
+        //     the result is [48, 57] for [0x0, 0x9], and [alpha_base + 10, alpha_base + 15]
+        //     for [0xa, 0xf].
+        //     In ASCII, [48, 57] represents chars '0' to '9',
+        //     [97, 102] represents chars 'a' to 'f', and [65, 70] represents 'A' to 'F'.
+        //
+        // [^1]: `>>` performs arithmetic right shift on signed integer types.
+        //     https://doc.rust-lang.org/reference/expressions/operator-expr.html#arithmetic-and-logical-binary-operators
+        let high = alpha_base + high_nibble + (((high_nibble - 10) >> 7) & digit_offset);
+        let low = alpha_base + low_nibble + (((low_nibble - 10) >> 7) & digit_offset);
+        hex.push(high as u8 as char);
+        hex.push(low as u8 as char);
+    }
+}
+
+/// Returns the bytes represented by the hexadecimal string `input`.
+///
+/// `input` is a string composed of hexadecimal digits: [0-9a-fA-F].
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - The len of `input` isn't even.
+/// - `input` contains non-hexadecimal digits, with the position of the first one.
+pub fn decode<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, HexDecodingError> {
+    decode_with_options(input, &DecodingOptions::default())
+}
+
+/// Returns the bytes represented by the hexadecimal string `input`, decoded with
+/// `options`.
+///
+/// The error positions are the indices into `input`, including the prefix if any.
+pub fn decode_with_options<T: AsRef<[u8]>>(
+    input: T,
+    options: &DecodingOptions,
+) -> Result<Vec<u8>, HexDecodingError> {
+    let mut bytes = Vec::new();
+    decode_into_with_options(input, &mut bytes, options)?;
+    Ok(bytes)
+}
+
+/// Appends the bytes represented by the hexadecimal string `input` to `bytes`.
+///
+/// Doesn't allocate if `bytes` has the room for `input.len() / 2` more bytes.
+/// `bytes` is left unchanged on error.
+pub fn decode_into<T: AsRef<[u8]>>(
+    input: T,
+    bytes: &mut Vec<u8>,
+) -> Result<(), HexDecodingError> {
+    decode_into_with_options(input, bytes, &DecodingOptions::default())
+}
+
+/// Appends the bytes represented by the hexadecimal string `input` to `bytes`, decoded
+/// with `options`.
+pub fn decode_into_with_options<T: AsRef<[u8]>>(
+    input: T,
+    bytes: &mut Vec<u8>,
+    options: &DecodingOptions,
+) -> Result<(), HexDecodingError> {
+    let input = input.as_ref();
+    let offset = prefix_len(input, options);
+    let hex = &input[offset..];
+    if hex.len() & 1 != 0 {
+        return Err(HexDecodingError::OddLength);
+    }
+
+    let start = bytes.len();
+    bytes.resize(start + hex.len() / 2, 0);
+    if let Err(err) = decode_to_slice(hex, offset, &mut bytes[start..]) {
+        bytes.truncate(start);
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Decodes the hexadecimal string in `buffer` onto the start of itself, returns the
+/// number of the decoded bytes.
+///
+/// The content of `buffer` is unspecified on error.
+///
+/// # Examples
+///
+/// ```
+/// use lightcryptotools::crypto::codecs::hex;
+///
+/// let mut buffer = *b"137acf";
+/// let len = hex::decode_in_place(&mut buffer).unwrap();
+/// assert_eq!(&buffer[..len], [0x13, 0x7a, 0xcf]);
+/// ```
+pub fn decode_in_place(buffer: &mut [u8]) -> Result<usize, HexDecodingError> {
+    decode_in_place_with_options(buffer, &DecodingOptions::default())
+}
+
+/// Decodes the hexadecimal string in `buffer` onto the start of itself with `options`,
+/// returns the number of the decoded bytes.
+pub fn decode_in_place_with_options(
+    buffer: &mut [u8],
+    options: &DecodingOptions,
+) -> Result<usize, HexDecodingError> {
+    let offset = prefix_len(buffer, options);
+    let hex_len = buffer.len() - offset;
+    if hex_len & 1 != 0 {
+        return Err(HexDecodingError::OddLength);
+    }
+
+    // The byte `i` is decoded from the digits at `offset + 2 * i`, which is never before
+    // `i`, so the digits are always read before overwritten.
+    for i in 0..hex_len / 2 {
+        let position = offset + 2 * i;
+        buffer[i] = decode_digit_pair(buffer[position], buffer[position + 1], position)?;
+    }
+    Ok(hex_len / 2)
+}
+
+/// Returns the `N` bytes represented by the hexadecimal string `input`.
+///
+/// # Errors
+///
+/// Will return an error if:
+/// - The len of `input` isn't even.
+/// - `input` doesn't represent exactly `N` bytes.
+/// - `input` contains non-hexadecimal digits.
+pub fn decode_to_array<const N: usize, T: AsRef<[u8]>>(
+    input: T,
+) -> Result<[u8; N], HexDecodingError> {
+    let input = input.as_ref();
+    if input.len() & 1 != 0 {
+        return Err(HexDecodingError::OddLength);
+    }
+    if input.len() != N * 2 {
+        return Err(HexDecodingError::UnexpectedLength);
+    }
+
+    let mut bytes = [0; N];
+    decode_to_slice(input, 0, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Returns 2 if `input` starts with a prefix `options` accepts, 0 otherwise.
+fn prefix_len(input: &[u8], options: &DecodingOptions) -> usize {
+    if options.allows_0x_prefix && (input.starts_with(b"0x") || input.starts_with(b"0X")) {
+        2
+    } else {
+        0
+    }
+}
+
+/// Decodes `hex` into `bytes`, the len of which is half of `hex`.
+///
+/// `offset` is the position of `hex` in the input, for reporting the errors.
+fn decode_to_slice(
+    hex: &[u8],
+    offset: usize,
+    bytes: &mut [u8],
+) -> Result<(), HexDecodingError> {
+    debug_assert_eq!(hex.len(), bytes.len() * 2);
+
+    for (i, (chunk, byte)) in hex.chunks_exact(2).zip(bytes.iter_mut()).enumerate() {
+        *byte = decode_digit_pair(chunk[0], chunk[1], offset + 2 * i)?;
+    }
+    Ok(())
+}
+
+/// Returns the byte represented by the digits `high` and `low`, which are at `position`
+/// and `position + 1` of the input.
+#[inline(always)]
+fn decode_digit_pair(high: u8, low: u8, position: usize) -> Result<u8, HexDecodingError> {
+    let high = decode_digit(high).ok_or(HexDecodingError::InvalidCharacter {
+        position,
+        found: high,
+    })?;
+    let low = decode_digit(low).ok_or(HexDecodingError::InvalidCharacter {
+        position: position + 1,
+        found: low,
+    })?;
+    Ok((high << 4) | low)
+}
+
+/// Returns the value of the hexadecimal digit `c`, or `None` if `c` isn't one.
+#[inline(always)]
+fn decode_digit(c: u8) -> Option<u8> {
+    let c = c as u16;
+
+    // The result is [0, 9] for `c` in [48, 57],
+    // and > 9 for the rest.
+    let c_num = c ^ 48;
+
+    // The result is 0xff for `c_num` in [0, 9],
+    // and 0 for the rest.
+    let c_num0 = c_num.wrapping_sub(10) >> 8;
+
+    // The result is [10, 15] for `c` in both [65, 70] and [97, 102].
+    let c_alpha = (c & !32).wrapping_sub(55);
+
+    // 1. `c_alpha.wrapping_sub(10)`:
+    //
+    //     The result is [0, 5] for `c_alpha` in [10, 15].
+    //
+    // 2. `c_alpha.wrapping_sub(16)`:
+    //
+    //     The result is [0xfffa, 0xffff] for `c_alpha` in [10, 15].
+    //
+    // 3. `(c_alpha.wrapping_sub(10) ^ c_alpha.wrapping_sub(16)) >> 8`
+    //
+    //     The result is 0xff for `c_alpha` in both [65, 70] and [97, 102],
+    //     and 0 for the rest.
+    let c_alpha0 = (c_alpha.wrapping_sub(10) ^ c_alpha.wrapping_sub(16)) >> 8;
+
+    if (c_num0 | c_alpha0) == 0 {
+        return None;
+    }
+    Some(((c_num0 & c_num) | (c_alpha0 & c_alpha)) as u8)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HexDecodingError {
+    /// `found` at `position` isn't a hexadecimal digit.
+    InvalidCharacter { position: usize, found: u8 },
+    /// The number of the digits is odd.
+    OddLength,
+    /// The digits don't represent the expected number of bytes.
+    UnexpectedLength,
+}
+
+impl Display for HexDecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexDecodingError::InvalidCharacter { position, found } => write!(
+                f,
+                "invalid hex character '{}' at position {position}",
+                (*found as char).escape_default()
+            ),
+            HexDecodingError::OddLength => write!(f, "odd number of hex digits"),
+            HexDecodingError::UnexpectedLength => write!(f, "unexpected number of hex digits"),
+        }
+    }
+}
+
+impl core::error::Error for HexDecodingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_tools::quickcheck::HexString;
+    use ::quickcheck_macros::quickcheck;
+
+    const PREFIXED: DecodingOptions = DecodingOptions {
+        allows_0x_prefix: true,
+    };
+
+    #[test]
+    fn test_encode() {
+        // (bytes, lower, upper)
+        let data: [(&[u8], &str, &str); 4] = [
+            (&[], "", ""),
+            (&[0x00], "00", "00"),
+            (&[0x13, 0x7a, 0xcf], "137acf", "137ACF"),
+            (
+                &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef],
+                "0123456789abcdef",
+                "0123456789ABCDEF",
+            ),
+        ];
+        for (bytes, lower, upper) in data {
+            assert_eq!(encode_lower(bytes), lower);
+            assert_eq!(encode_upper(bytes), upper);
+        }
+
+        let mut hex = "0x".to_string();
+        encode_into(&[0xab], &mut hex);
+        encode_upper_into(&[0xcd], &mut hex);
+        assert_eq!(hex, "0xabCD");
+    }
+
+    #[test]
+    fn test_invalid_character_positions() {
+        // (input, position, found)
+        let data = [
+            ("g0123456", 0, b'g'),
+            ("0g123456", 1, b'g'),
+            ("012 3456", 3, b' '),
+            ("0123x456", 4, b'x'),
+            ("012345\n6", 6, b'\n'),
+            ("0123456G", 7, b'G'),
+        ];
+        for (input, position, found) in data {
+            let expected = HexDecodingError::InvalidCharacter { position, found };
+            assert_eq!(decode(input).unwrap_err(), expected, "{input}");
+
+            let mut buffer = input.as_bytes().to_vec();
+            assert_eq!(decode_in_place(&mut buffer).unwrap_err(), expected);
+
+            // Counted from the start of the input, prefix included.
+            let prefixed = format!("0x{input}");
+            let expected = HexDecodingError::InvalidCharacter {
+                position: position + 2,
+                found,
+            };
+            assert_eq!(
+                decode_with_options(&prefixed, &PREFIXED).unwrap_err(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_odd_length() {
+        for input in ["0", "d559b", "d559g", "0x123"] {
+            assert_eq!(decode(input).unwrap_err(), HexDecodingError::OddLength);
+        }
+        // The prefix isn't counted.
+        assert_eq!(
+            decode_with_options("0x1", &PREFIXED).unwrap_err(),
+            HexDecodingError::OddLength
+        );
+        assert_eq!(
+            decode_in_place(&mut b"abc".to_vec()).unwrap_err(),
+            HexDecodingError::OddLength
+        );
+    }
+
+    #[test]
+    fn test_0x_prefix() {
+        // (input, bytes)
+        let data: [(&str, &[u8]); 4] = [
+            ("0x", &[]),
+            ("0x00", &[0x00]),
+            ("0X137acf", &[0x13, 0x7a, 0xcf]),
+            ("137acf", &[0x13, 0x7a, 0xcf]),
+        ];
+        for (input, bytes) in data {
+            assert_eq!(decode_with_options(input, &PREFIXED).unwrap(), bytes);
+
+            let mut buffer = input.as_bytes().to_vec();
+            let len = decode_in_place_with_options(&mut buffer, &PREFIXED).unwrap();
+            assert_eq!(&buffer[..len], bytes);
+        }
+
+        // Rejected by default.
+        assert_eq!(
+            decode("0x00").unwrap_err(),
+            HexDecodingError::InvalidCharacter {
+                position: 1,
+                found: b'x'
+            }
+        );
+        // Only one prefix.
+        assert_eq!(
+            decode_with_options("0x0x00", &PREFIXED).unwrap_err(),
+            HexDecodingError::InvalidCharacter {
+                position: 3,
+                found: b'x'
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_into() {
+        let mut bytes = vec![0xff];
+        decode_into("137acf", &mut bytes).unwrap();
+        assert_eq!(bytes, [0xff, 0x13, 0x7a, 0xcf]);
+
+        // Unchanged on error.
+        assert!(decode_into("00112g", &mut bytes).is_err());
+        assert_eq!(bytes, [0xff, 0x13, 0x7a, 0xcf]);
+
+        decode_into_with_options("0x00", &mut bytes, &PREFIXED).unwrap();
+        assert_eq!(bytes, [0xff, 0x13, 0x7a, 0xcf, 0x00]);
+    }
+
+    #[test]
+    fn test_decode_to_array() {
+        let hex = "0123456789abcdef0123456789ABCDEF0123456789abcdef0123456789abcdef";
+        let bytes: [u8; 32] = decode_to_array(hex).unwrap();
+        assert_eq!(bytes.to_vec(), decode(hex).unwrap());
+        assert_eq!(decode_to_array::<0, _>("").unwrap(), [0_u8; 0]);
+
+        // too short
+        let err = decode_to_array::<32, _>(&hex[2..]).unwrap_err();
+        assert_eq!(err, HexDecodingError::UnexpectedLength);
+        // too long
+        let err = decode_to_array::<32, _>(format!("{hex}00")).unwrap_err();
+        assert_eq!(err, HexDecodingError::UnexpectedLength);
+
+        let err = decode_to_array::<32, _>(&hex[1..]).unwrap_err();
+        assert_eq!(err, HexDecodingError::OddLength);
+        let err = decode_to_array::<2, _>("d5G9").unwrap_err();
+        assert_eq!(
+            err,
+            HexDecodingError::InvalidCharacter {
+                position: 2,
+                found: b'G'
+            }
+        );
+    }
+
+    #[test]
+    fn test_large_input_round_trip() {
+        let bytes: Vec<u8> = (0..1 << 20)
+            .map(|i: u32| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect();
+
+        let lower = encode_lower(&bytes);
+        let upper = encode_upper(&bytes);
+        assert_eq!(lower.len(), bytes.len() * 2);
+        assert_eq!(upper, lower.to_uppercase());
+        assert_eq!(decode(&lower).unwrap(), bytes);
+        assert_eq!(decode(&upper).unwrap(), bytes);
+
+        let mut buffer = lower.into_bytes();
+        let len = decode_in_place(&mut buffer).unwrap();
+        assert_eq!(&buffer[..len], bytes);
+    }
+
+    #[test]
+    fn test_byte_values_double_conversion() {
+        // For each value in [0, 255] as one byte,
+        // converts the byte to hex and back again.
+        for i in u8::MIN..=u8::MAX {
+            assert_eq!(decode(encode_lower(&[i])).unwrap(), [i]);
+            assert_eq!(decode(encode_upper(&[i])).unwrap(), [i]);
+        }
+    }
+
+    #[test]
+    fn test_input_char_validation_check() {
+        // Goes through all the combinations for a two-character string,
+        // and feeds the strings to `decode`.
+        // Should return `InvalidCharacter` for any character not in [0-9a-fA-F].
+        for i in u8::MIN..=u8::MAX {
+            for j in u8::MIN..=u8::MAX {
+                let input = [i, j];
+                match (i.is_ascii_hexdigit(), j.is_ascii_hexdigit()) {
+                    (true, true) => assert_eq!(
+                        encode_lower(&decode(input).unwrap()),
+                        String::from_utf8_lossy(&input).to_lowercase()
+                    ),
+                    (false, _) => assert_eq!(
+                        decode(input).unwrap_err(),
+                        HexDecodingError::InvalidCharacter {
+                            position: 0,
+                            found: i
+                        }
+                    ),
+                    (true, false) => assert_eq!(
+                        decode(input).unwrap_err(),
+                        HexDecodingError::InvalidCharacter {
+                            position: 1,
+                            found: j
+                        }
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_display() {
+        let err = HexDecodingError::InvalidCharacter {
+            position: 3,
+            found: b'\n',
+        };
+        assert_eq!(err.to_string(), "invalid hex character '\\n' at position 3");
+    }
+
+    #[quickcheck]
+    fn decode_double_conversion(hex: HexString) -> bool {
+        let bytes = decode(&hex.0).unwrap();
+        encode_lower(&bytes) == hex.0.to_lowercase()
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the codecs of bytes.
+//!
+//! The functions at the top level are the names before `hex`, kept for compatibility.
+
+pub mod bech32;
+pub mod hex;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
+use hex::HexDecodingError;
+
+/// Returns lowercase hexadecimal representation of `bytes`.
+#[deprecated(note = "use `hex::encode_lower`")]
+pub fn bytes_to_lower_hex(bytes: &[u8]) -> String {
+    hex::encode_lower(bytes)
+}
+
+/// Returns bytes represented by the hexadecimal string `hex`.
+#[deprecated(note = "use `hex::decode`, which reports the error positions")]
+pub fn hex_to_bytes<T: AsRef<[u8]>>(hex: T) -> Result<Vec<u8>, CodecsError> {
+    Ok(hex::decode(hex)?)
+}
+
+/// Returns the `N` bytes represented by the hexadecimal string `hex`.
+#[deprecated(note = "use `hex::decode_to_array`, which reports the error positions")]
+pub fn hex_to_array<const N: usize, T: AsRef<[u8]>>(hex: T) -> Result<[u8; N], CodecsError> {
+    Ok(hex::decode_to_array(hex)?)
+}
+
+/// The error of the deprecated functions, see `HexDecodingError` for the current one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodecsError {
+    InvalidCharFound,
+    NotByteAligned,
+    UnexpectedLength,
+}
+
+impl From<HexDecodingError> for CodecsError {
+    fn from(err: HexDecodingError) -> Self {
+        match err {
+            HexDecodingError::InvalidCharacter { .. } => CodecsError::InvalidCharFound,
+            HexDecodingError::OddLength => CodecsError::NotByteAligned,
+            HexDecodingError::UnexpectedLength => CodecsError::UnexpectedLength,
+        }
+    }
+}
+
+impl Display for CodecsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecsError::InvalidCharFound => write!(f, "Invalid char found"),
+            CodecsError::NotByteAligned => write!(f, "Not 1-byte aligned"),
+            CodecsError::UnexpectedLength => write!(f, "Unexpected length"),
+        }
+    }
+}
+
+impl core::error::Error for CodecsError {}
+
+#[cfg(test)]
+mod tests {
+    #![allow(deprecated)]
+
+    use super::*;
+
+    #[test]
+    fn test_deprecated_functions() {
+        assert_eq!(bytes_to_lower_hex(&[0x13, 0x7a, 0xcf]), "137acf");
+        assert_eq!(hex_to_bytes("137ACF").unwrap(), [0x13, 0x7a, 0xcf]);
+        assert_eq!(hex_to_array::<3, _>("137acf").unwrap(), [0x13, 0x7a, 0xcf]);
+
+        // (input, error)
+        let data = [
+            ("d559b", CodecsError::NotByteAligned),
+            ("d55G9b", CodecsError::InvalidCharFound),
+        ];
+        for (input, err) in data {
+            assert_eq!(hex_to_bytes(input).unwrap_err(), err);
+        }
+        let err = hex_to_array::<2, _>("d559b4").unwrap_err();
+        assert_eq!(err, CodecsError::UnexpectedLength);
+    }
+}
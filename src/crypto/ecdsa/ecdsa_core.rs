@@ -5,10 +5,10 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::ecdsa_key::{PrivateKey, PublicKey};
-use crate::bigint::bigint_core::Sign;
 use crate::bigint::BigInt;
 use crate::crypto::elliptic_curve_params::EllipticCurveParams;
-use crate::math::modular::{invert, modulo};
+use crate::math::elliptic_curve::Point;
+use crate::math::modular::{invert, invert_prime_ct, modulo};
 use std::fmt;
 use std::fmt::Display;
 
@@ -25,6 +25,32 @@ impl<'a> Signature<'a> {
         signature.is_valid().then_some(signature)
     }
 
+    /// Creates a `Signature` without checking that r and s are in `(0, n)`, where n is the
+    /// order of the base point.
+    ///
+    /// For representing, inspecting and re-encoding signatures as they are, e.g., the
+    /// nonstandard ones in historical chain data. Use [`Signature::is_canonical`] to
+    /// classify the signature; verifying rejects the out-of-range ones.
+    pub fn new_unchecked(r: BigInt, s: BigInt, curve_params: &'a EllipticCurveParams) -> Self {
+        Signature { r, s, curve_params }
+    }
+
+    /// Returns `(r, s)`.
+    pub fn components(&self) -> (&BigInt, &BigInt) {
+        (&self.r, &self.s)
+    }
+
+    /// Classifies the signature by the ranges of r and s, see [`SignatureCanonicality`].
+    pub fn is_canonical(&self) -> SignatureCanonicality {
+        if !self.is_valid() {
+            SignatureCanonicality::OutOfRange
+        } else if !self.is_low_s_signature() {
+            SignatureCanonicality::HighS
+        } else {
+            SignatureCanonicality::Canonical
+        }
+    }
+
     fn is_valid(&self) -> bool {
         // Ensures that "0 < r < n and 0 < s < n":
         // https://neilmadden.blog/2022/04/19/psychic-signatures-in-java/
@@ -32,8 +58,8 @@ impl<'a> Signature<'a> {
         //
         // Tests are done in the integration test "test_invalid_verifying".
         // Search "Invalid r, s values (== 0)".
-        (self.r > BigInt::zero() && self.r < self.curve_params.base_point_order)
-            && (self.s > BigInt::zero() && self.s < self.curve_params.base_point_order)
+        (self.r > 0 && self.r < self.curve_params.base_point_order)
+            && (self.s > 0 && self.s < self.curve_params.base_point_order)
     }
 
     pub(crate) fn is_low_s_signature(&self) -> bool {
@@ -45,6 +71,20 @@ impl<'a> Signature<'a> {
     }
 }
 
+/// The classification of a signature by the ranges of r and s, n is the order of the base
+/// point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureCanonicality {
+    /// r and s are in `(0, n)`, and s is at most `n / 2` ("low s").
+    Canonical,
+    /// r and s are in `(0, n)`, and s is greater than `n / 2`. Such signatures are valid
+    /// ECDSA signatures, but some protocols reject them as malleable, e.g., Ethereum since
+    /// EIP-2 (Homestead).
+    HighS,
+    /// r or s isn't in `(0, n)`. Such signatures can never be verified.
+    OutOfRange,
+}
+
 impl PrivateKey<'_> {
     /// Generates a ECDSA signature and the recovery id of `hash` with the private key `self`.
     ///
@@ -58,11 +98,12 @@ impl PrivateKey<'_> {
         hash: &BigInt,
         k: &BigInt,
     ) -> Option<(Signature, SignatureRecoveryId)> {
-        assert!(hash.bit_len() <= self.curve_params.base_point_order.bit_len());
+        // The callers truncate the hash with `truncate_to_scalar`.
+        debug_assert!(hash.bit_len() <= self.curve_params.base_point_order.bit_len());
 
         // `k` in [1, n - 1]
         // n: the order of the base point
-        assert!(k > &BigInt::zero() && k < &self.curve_params.base_point_order);
+        assert!(*k > 0 && k < &self.curve_params.base_point_order);
 
         let curve_params = self.curve_params;
         let kg = curve_params.curve.mul_point(&curve_params.base_point, k);
@@ -72,9 +113,14 @@ impl PrivateKey<'_> {
             return None;
         }
 
-        // s = (h + rd) / k mod p
-        let s = (hash + &r * &self.data) * invert(k, &curve_params.base_point_order).unwrap();
-        let s = modulo(&s, &curve_params.base_point_order);
+        // s = (h + rd) / k mod n
+        //
+        // `k` is secret, so it is inverted with `invert_prime_ct`, whose operation count
+        // doesn't depend on `k`. The order of the base point is prime.
+        let mut s = &r * &self.data;
+        s += hash;
+        s *= invert_prime_ct(k, &curve_params.base_point_order);
+        s %= &curve_params.base_point_order;
         if s.is_zero() {
             return None;
         }
@@ -102,14 +148,40 @@ impl PublicKey<'_> {
     ///
     /// # Notes
     ///
-    /// This function allows `hash` to be zero. If `hash` is zero, for any public key Q(x, y),
-    /// a signature (x, x) will pass the verification. For an example,
-    /// see the testcase "test_verify_zero_hash" below.
+    /// Before the expensive scalar multiplications, this function rejects (returns `false`)
+    /// the degenerate cases which can never produce a meaningful verification:
     ///
-    /// With this in mind, the higher level verifying functions in this library (ecdsa_verifying.rs)
-    /// don't allow zero hash by default.
+    /// - `hash` is zero. If `hash` is zero, for any public key Q(x, y), a signature (x, x)
+    ///   would pass the verification. For an example, see the testcase
+    ///   "test_verify_zero_hash" below.
+    /// - The public key is the identity element (the point at infinity).
+    /// - `r` or `s` isn't in [1, n - 1]. `Signature::new` checks the range, but the fields
+    ///   are public and can be modified afterwards.
+    ///
+    /// The higher level verifying functions in this library (ecdsa_verifying.rs)
+    /// additionally report zero hash as an error.
     pub(crate) fn verify(&self, hash: &BigInt, signature: &Signature) -> bool {
-        assert!(hash.bit_len() <= self.curve_params.base_point_order.bit_len());
+        self.verify_with_multiplier(hash, signature, |v| {
+            self.curve_params.curve.mul_point(&self.data, v)
+        })
+    }
+
+    /// Like `verify`, with `mul_public_key` multiplying the public key with a scalar, e.g.,
+    /// with a precomputed table.
+    pub(crate) fn verify_with_multiplier(
+        &self,
+        hash: &BigInt,
+        signature: &Signature,
+        mul_public_key: impl FnOnce(&BigInt) -> Point,
+    ) -> bool {
+        // The callers truncate the hash with `truncate_to_scalar`,
+        // identically to signing.
+        debug_assert!(hash.bit_len() <= self.curve_params.base_point_order.bit_len());
+
+        // Fast rejections
+        if hash.is_zero() || self.data.is_identity_element() || !signature.is_valid() {
+            return false;
+        }
 
         let curve_params = self.curve_params;
 
@@ -127,7 +199,7 @@ impl PublicKey<'_> {
 
         // Q = uG + vP
         let ug = curve_params.curve.mul_point(&curve_params.base_point, &u);
-        let vp = curve_params.curve.mul_point(&self.data, &v);
+        let vp = mul_public_key(&v);
         let q = curve_params.curve.add_points(&ug, &vp);
         let qx = modulo(&q.x, &curve_params.base_point_order);
 
@@ -136,44 +208,16 @@ impl PublicKey<'_> {
 }
 
 /// Returns true if the hash length in bits equals the order of the base point in bits.
+///
+/// If the bit length of the order isn't 1-byte aligned (e.g., P-521),
+/// no hash length matches.
 pub(crate) fn hash_length_matches_base_point_order(
     hash_byte_length: usize,
     curve_params: &EllipticCurveParams,
 ) -> bool {
-    debug_assert_eq!(
-        curve_params.base_point_order.bit_len() % u8::BITS as usize,
-        0,
-        "The bit length of the order of the base point is not 1-byte aligned."
-    );
-
     hash_byte_length * u8::BITS as usize == curve_params.base_point_order.bit_len()
 }
 
-impl BigInt {
-    /// Converts up to `max_bits_len` leading bits of `bytes` to an integer.
-    pub(crate) fn from_be_bytes_with_max_bits_len(
-        bytes: &[u8],
-        max_bits_len: usize,
-        sign: Sign,
-    ) -> BigInt {
-        debug_assert!(max_bits_len > 0);
-
-        if bytes.len() * 8 <= max_bits_len {
-            return BigInt::from_be_bytes(bytes, sign);
-        }
-
-        let bytes_len = max_bits_len / 8;
-        let bits_remaining_len = max_bits_len % 8;
-        if bits_remaining_len == 0 {
-            BigInt::from_be_bytes(&bytes[0..bytes_len], sign)
-        } else {
-            let mut n = BigInt::from_be_bytes(&bytes[0..=bytes_len], sign);
-            n = n >> (8 - bits_remaining_len);
-            n
-        }
-    }
-}
-
 /// Bit flags determine the viable public keys that can be recovered from a signature.
 ///
 /// LowX: R.x < base_point_order
@@ -257,6 +301,8 @@ pub(crate) const EMPTY_HASH_NOT_ALLOWED_ERROR_DISPLAY: &str = "Empty hash is not
 pub(crate) const ZERO_HASH_NOT_ALLOWED_ERROR_DISPLAY: &str = "Zero hash is not allowed";
 pub(crate) const HASH_BIT_LENGTH_DOES_NOT_MATCH_BASE_POINT_ORDER_ERROR_DISPLAY: &str =
     "Hash length in bits doesn't equal to the order of the base point in bits";
+pub(crate) const CURVE_MISMATCH_ERROR_DISPLAY: &str =
+    "The key or the signature is on a different curve";
 
 #[cfg(test)]
 mod tests {
@@ -264,7 +310,7 @@ mod tests {
     use crate::crypto::elliptic_curve_params::EllipticCurveParams;
     use crate::crypto::secp256k1;
     use crate::math::elliptic_curve::{Curve, Point};
-    use crate::testing_tools::quickcheck::HexString;
+    use crate::testing_tools::quickcheck::{ArbitraryEcScalar, BigInt256, HexString};
     use quickcheck::{Gen, QuickCheck};
 
     #[test]
@@ -333,7 +379,7 @@ mod tests {
         let (signature, _) = private_key.sign(&hash_n, &k).unwrap();
 
         // sign and verify
-        let hex = signature.to_p1363_hex();
+        let hex = signature.to_p1363_hex().unwrap();
         assert_eq!(hex, signature_hex);
         assert!(public_key.verify(&hash_n, &signature));
 
@@ -387,8 +433,30 @@ mod tests {
             .quickcheck(prop as fn(HexString, HexString, HexString) -> bool)
     }
 
+    #[test]
+    fn sign_and_verify_with_full_size_numbers() {
+        const TEST_NUMBER: u64 = 10;
+
+        fn prop(hash_n: BigInt256, d: ArbitraryEcScalar, k: ArbitraryEcScalar) -> bool {
+            let private_key = PrivateKey::new(d.0, secp256k1()).unwrap();
+            let public_key = private_key.public_key();
+
+            let (signature, _) = private_key.sign(&hash_n.0, &k.0).unwrap();
+            let success = public_key.verify(&hash_n.0, &signature);
+            let failure = public_key.verify(&(&hash_n.0 >> 1), &signature);
+
+            success && !failure
+        }
+
+        QuickCheck::new()
+            .tests(TEST_NUMBER)
+            .quickcheck(prop as fn(BigInt256, ArbitraryEcScalar, ArbitraryEcScalar) -> bool)
+    }
+
     #[test]
     fn test_verify_zero_hash() {
+        // Without the fast rejection, for any public key Q(x, y),
+        // a signature (x, x) would pass the verification of the zero hash.
         let curve = secp256k1();
         let private_key1 = PrivateKey::new(BigInt::from(1), curve).unwrap();
         let public_key1 = private_key1.public_key();
@@ -402,7 +470,7 @@ mod tests {
             curve,
         )
         .unwrap();
-        assert!(public_key1.verify(&hash_n, &fake_signature));
+        assert!(!public_key1.verify(&hash_n, &fake_signature));
 
         let fake_signature = Signature::new(
             public_key2.data.x.clone(),
@@ -410,61 +478,33 @@ mod tests {
             curve,
         )
         .unwrap();
-        assert!(public_key2.verify(&hash_n, &fake_signature));
+        assert!(!public_key2.verify(&hash_n, &fake_signature));
     }
 
     #[test]
-    fn test_from_be_bytes_with_max_bits_len() {
-        #[rustfmt::skip]
-        let data = [
-            (&[u8::MAX][..], 17, BigInt::from(255)),
-            (&[u8::MAX][..], 16, BigInt::from(255)),
-            (&[u8::MAX][..], 15, BigInt::from(255)),
-            (&[u8::MAX][..], 8, BigInt::from(255)),
-            (&[u8::MAX][..], 7, BigInt::from(255 >> 1)),
-            (&[u8::MAX][..], 6, BigInt::from(255 >> 2)),
-            (&[u8::MAX][..], 5, BigInt::from(255 >> 3)),
-            (&[u8::MAX][..], 4, BigInt::from(255 >> 4)),
-            (&[u8::MAX][..], 3, BigInt::from(255 >> 5)),
-            (&[u8::MAX][..], 2, BigInt::from(255 >> 6)),
-            (&[u8::MAX][..], 1, BigInt::from(255 >> 7)),
-            (&[1_u8][..], 1, BigInt::from(0)),
-            (&[1_u8][..], 5, BigInt::from(0)),
-            (&[1_u8][..], 7, BigInt::from(0)),
-            (&[1_u8][..], 8, BigInt::from(1)),
-            (&[1_u8][..], 9, BigInt::from(1)),
-            (&[128_u8, 1][..], 1, BigInt::from(1)),
-            (&[128_u8, 1][..], 3, BigInt::from(1 << 2)),
-            (&[128_u8, 1][..], 5, BigInt::from(1 << 4)),
-            (&[128_u8, 1][..], 7, BigInt::from(1 << 6)),
-            (&[128_u8, 1][..], 8, BigInt::from(1 << 7)),
-            (&[128_u8, 1][..], 9, BigInt::from(256)),
-            (&[128_u8, 1][..], 10, BigInt::from(256 << 1)),
-            (&[128_u8, 1][..], 12, BigInt::from(256 << 3)),
-            (&[128_u8, 1][..], 14, BigInt::from(256 << 5)),
-            (&[128_u8, 1][..], 15, BigInt::from(256 << 6)),
-            (&[128_u8, 1][..], 16, BigInt::from((256 << 7) + 1)),
-            (&[128_u8, 1][..], 17, BigInt::from((256 << 7) + 1)),
-            (&[
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 1_u8,
-            ][..], 256, BigInt::from(1)),
-            (&[
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 0,
-                0, 0, 0, 0, 0, 0, 0, 1_u8, 66,
-            ][..], 256, BigInt::from(1)),
-        ];
+    fn test_verify_identity_public_key() {
+        let curve = secp256k1();
+        let public_key = PublicKey {
+            data: Point::identity_element(),
+            curve_params: curve,
+        };
+        let signature = Signature::new(BigInt::from(1), BigInt::from(1), curve).unwrap();
+        assert!(!public_key.verify(&BigInt::from(1), &signature));
+    }
 
-        for (bytes, max_bits_len, n) in data {
-            assert_eq!(
-                BigInt::from_be_bytes_with_max_bits_len(bytes, max_bits_len, Sign::Positive),
-                n
-            );
-        }
+    #[test]
+    fn test_verify_out_of_range_signature() {
+        let curve = secp256k1();
+        let private_key = PrivateKey::new(BigInt::from(1), curve).unwrap();
+        let public_key = private_key.public_key();
+        let hash_n = BigInt::from(1);
+        let (mut signature, _) = private_key.sign(&hash_n, &BigInt::from(2)).unwrap();
+        assert!(public_key.verify(&hash_n, &signature));
+
+        signature.s = BigInt::zero();
+        assert!(!public_key.verify(&hash_n, &signature));
+        signature.s = curve.base_point_order.clone();
+        assert!(!public_key.verify(&hash_n, &signature));
     }
 
     #[test]
@@ -494,4 +534,61 @@ mod tests {
             assert_eq!(signature.is_low_s_signature(), is_low_s);
         }
     }
+
+    #[test]
+    fn test_is_canonical() {
+        let curve = secp256k1();
+        let n = &curve.base_point_order;
+        let order_div_2 = n >> 1;
+
+        // (r, s, canonicality)
+        let data = [
+            (
+                BigInt::one(),
+                BigInt::one(),
+                SignatureCanonicality::Canonical,
+            ),
+            (
+                n - BigInt::one(),
+                order_div_2.clone(),
+                SignatureCanonicality::Canonical,
+            ),
+            (
+                BigInt::one(),
+                &order_div_2 + BigInt::one(),
+                SignatureCanonicality::HighS,
+            ),
+            (
+                BigInt::one(),
+                n - BigInt::one(),
+                SignatureCanonicality::HighS,
+            ),
+            (
+                BigInt::zero(),
+                BigInt::one(),
+                SignatureCanonicality::OutOfRange,
+            ),
+            (
+                BigInt::one(),
+                BigInt::zero(),
+                SignatureCanonicality::OutOfRange,
+            ),
+            (n.clone(), BigInt::one(), SignatureCanonicality::OutOfRange),
+            (BigInt::one(), n.clone(), SignatureCanonicality::OutOfRange),
+            (
+                BigInt::one(),
+                BigInt::from(-1),
+                SignatureCanonicality::OutOfRange,
+            ),
+        ];
+        for (r, s, canonicality) in data {
+            let signature = Signature::new_unchecked(r.clone(), s.clone(), curve);
+            assert_eq!(signature.is_canonical(), canonicality);
+            assert_eq!(signature.components(), (&r, &s));
+            assert_eq!(
+                Signature::new(r, s, curve).is_some(),
+                canonicality != SignatureCanonicality::OutOfRange
+            );
+        }
+    }
 }
@@ -4,9 +4,12 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::bigint::bigint_core::Sign;
+use crate::bigint::BigInt;
 use crate::crypto::ecdsa::{PublicKey, Signature};
 use crate::crypto::elliptic_curve_params::EllipticCurveParamsEncoding;
 use crate::crypto::p1363::P1363;
+use crate::crypto::raw_xy::RawXY;
 use crate::crypto::sec1::Sec1;
 use crate::crypto::{p1363, sec1, EllipticCurveParams};
 
@@ -16,7 +19,7 @@ pub(crate) trait SignatureEncoding {
         curve_params: &EllipticCurveParams,
     ) -> Result<Signature, Box<dyn std::error::Error>>;
 
-    fn encode(signature: &Signature) -> String;
+    fn encode(signature: &Signature) -> Result<String, Box<dyn std::error::Error>>;
 }
 
 impl<'a> Signature<'a> {
@@ -30,8 +33,27 @@ impl<'a> Signature<'a> {
     }
 
     /// Returns IEEE P1363 encoded signature.
-    pub fn to_p1363_hex(&self) -> String {
+    ///
+    /// Returns `SignatureEncodingError::OutOfRange` unless both r and s are in `(0, n)`,
+    /// where n is the order of the base point.
+    pub fn to_p1363_hex(&self) -> Result<String, p1363::SignatureEncodingError> {
         P1363::encode(self)
+            .map_err(|e| *e.downcast_ref::<p1363::SignatureEncodingError>().unwrap())
+    }
+
+    /// Restores a signature from the big-endian bytes of r and s.
+    ///
+    /// Returns `SignatureDecodingError::InvalidSignature` unless both r and s are in
+    /// `(0, n)`, where n is the order of the base point.
+    pub fn from_rs_bytes(
+        r: &[u8; 32],
+        s: &[u8; 32],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<Signature<'a>, p1363::SignatureDecodingError> {
+        let r = BigInt::from_be_bytes(r, Sign::Positive);
+        let s = BigInt::from_be_bytes(s, Sign::Positive);
+        Signature::new(r, s, curve_params)
+            .ok_or(p1363::SignatureDecodingError::InvalidSignature)
     }
 }
 
@@ -56,4 +78,118 @@ impl<'a> PublicKey<'a> {
     pub fn to_sec1_hex(&self, compressed: bool) -> String {
         Sec1::encode_point(&self.data, self.curve_params, compressed)
     }
+
+    /// Returns SEC1 encoded elliptic curve point,
+    /// in uppercase hexadecimal if `upper` is true and in lowercase otherwise.
+    pub fn to_sec1_hex_with_case(&self, compressed: bool, upper: bool) -> String {
+        Sec1::encode_point_with_case(&self.data, self.curve_params, compressed, upper)
+    }
+
+    /// Restores a `PublicKey` from the fixed-width `x ‖ y` bytes of an elliptic curve
+    /// point, without a prefix byte.
+    pub fn from_raw_xy_bytes(
+        bytes: &[u8],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<PublicKey<'a>, sec1::PointDecodingError> {
+        match RawXY::decode_point_from_bytes(bytes, curve_params) {
+            // Bypasses `PublicKey::new()`,
+            // for `point` has already been validated by `decode_point_from_bytes`.
+            Ok(point) => Ok(PublicKey {
+                data: point,
+                curve_params,
+            }),
+            Err(err) => Err(*err.downcast_ref::<sec1::PointDecodingError>().unwrap()),
+        }
+    }
+
+    /// Returns the fixed-width `x ‖ y` bytes of the elliptic curve point,
+    /// without a prefix byte.
+    pub fn to_raw_xy_bytes(&self) -> Vec<u8> {
+        RawXY::encode_point_to_bytes(&self.data, self.curve_params, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::secp256k1;
+
+    #[test]
+    fn test_signature_from_rs_bytes() {
+        let secp256k1 = secp256k1();
+        let r: [u8; 32] = hex::decode_to_array(
+            "fbe907aac2bd7cd0ce3711f644235486367bdca4b87f19f76a7935fa00c6d169",
+        )
+        .unwrap();
+        let s: [u8; 32] = hex::decode_to_array(
+            "7f16095dd8cb6a4da57da25e3a3178665513e12c7b4dc52f2c212d250eef6407",
+        )
+        .unwrap();
+
+        let signature = Signature::from_rs_bytes(&r, &s, secp256k1).unwrap();
+        assert_eq!(
+            signature.to_p1363_hex().unwrap(),
+            concat!(
+                "fbe907aac2bd7cd0ce3711f644235486367bdca4b87f19f76a7935fa00c6d169",
+                "7f16095dd8cb6a4da57da25e3a3178665513e12c7b4dc52f2c212d250eef6407"
+            )
+        );
+
+        let mut one = [0; 32];
+        one[31] = 1;
+        let signature = Signature::from_rs_bytes(&one, &one, secp256k1).unwrap();
+        assert_eq!(signature.r, BigInt::one());
+        assert_eq!(signature.s, BigInt::one());
+    }
+
+    #[test]
+    fn test_signature_from_rs_bytes_error() {
+        let secp256k1 = secp256k1();
+        let s: [u8; 32] = hex::decode_to_array(
+            "7f16095dd8cb6a4da57da25e3a3178665513e12c7b4dc52f2c212d250eef6407",
+        )
+        .unwrap();
+        // the base point order
+        let n: [u8; 32] = hex::decode_to_array(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        )
+        .unwrap();
+
+        for (r, s) in [
+            (&[0; 32], &s),
+            (&s, &[0; 32]),
+            (&n, &s),
+            (&s, &n),
+            (&[0xff; 32], &s),
+        ] {
+            assert_eq!(
+                Signature::from_rs_bytes(r, s, secp256k1).unwrap_err(),
+                p1363::SignatureDecodingError::InvalidSignature
+            );
+        }
+    }
+
+    #[test]
+    fn test_signature_to_p1363_hex_error() {
+        let secp256k1 = secp256k1();
+        let n = &secp256k1.base_point_order;
+        for (r, s) in [
+            (BigInt::one() << 300, BigInt::from(11)),
+            (-BigInt::one(), BigInt::from(11)),
+            (BigInt::from(7), BigInt::zero()),
+            (BigInt::from(7), n.clone()),
+        ] {
+            let signature = Signature::new_unchecked(r, s, secp256k1);
+            assert_eq!(
+                signature.to_p1363_hex().unwrap_err(),
+                p1363::SignatureEncodingError::OutOfRange
+            );
+        }
+
+        // "high s" signatures are still encodable.
+        let signature =
+            Signature::new(BigInt::from(7), n - &BigInt::from(11), secp256k1).unwrap();
+        assert!(signature.to_p1363_hex().is_ok());
+    }
 }
@@ -4,9 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::bigint::BigInt;
-use crate::crypto::elliptic_curve_params::EllipticCurveParams;
+use crate::bigint::{BigInt, Sign};
+use crate::crypto::elliptic_curve_params::{EllipticCurveParams, PointValidationError};
 use crate::math::elliptic_curve::Point;
+use std::fmt::{self, Display};
 
 pub struct PrivateKey<'a> {
     pub data: BigInt,
@@ -25,8 +26,44 @@ impl<'a> PrivateKey<'a> {
         private_key.is_valid().then_some(private_key)
     }
 
+    /// Creates a private key from `bytes`, a big-endian integer of exactly the byte length
+    /// of the order of `curve_params`, e.g., 32 bytes for secp256k1.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if:
+    /// - The len of `bytes` isn't the byte length of the order.
+    /// - The key is zero or not less than the order.
+    pub fn from_bytes(
+        bytes: &[u8],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<Self, PrivateKeyDecodingError> {
+        let order_byte_len = curve_params.base_point_order.byte_len();
+        let data = BigInt::from_be_bytes_exact(bytes, order_byte_len, Sign::Positive).map_err(
+            |_| PrivateKeyDecodingError::InvalidLength {
+                expected: order_byte_len,
+                found: bytes.len(),
+            },
+        )?;
+        PrivateKey::new(data, curve_params).ok_or(PrivateKeyDecodingError::OutOfRange)
+    }
+
+    /// Returns the key as big-endian bytes, leading zero padded to the byte length of the
+    /// order, the inverse of `from_bytes`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data`, a public field, has been set to a negative value, or to one longer
+    /// than the order of the base point.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // A key created with `new` or `from_bytes` is less than the order, it always fits.
+        self.data
+            .to_fixed_bytes(self.curve_params.base_point_order.byte_len())
+            .unwrap()
+    }
+
     fn is_valid(&self) -> bool {
-        self.data > BigInt::zero() && self.data < self.curve_params.base_point_order
+        self.data > 0 && self.data < self.curve_params.base_point_order
     }
 
     pub fn public_key(&self) -> PublicKey {
@@ -48,11 +85,64 @@ impl<'a> PublicKey<'a> {
     fn is_valid(&self) -> bool {
         self.curve_params.validate_point(&self.data)
     }
+
+    /// Validates each of `points` as a public key of `curve_params`, e.g., when importing
+    /// keys in bulk, returning the results in the order of `points`.
+    ///
+    /// The points are validated independently, `points` can be split into chunks validated
+    /// on multiple threads.
+    pub fn validate_batch(
+        points: &[Point],
+        curve_params: &EllipticCurveParams,
+    ) -> Vec<Result<(), PointValidationError>> {
+        points
+            .iter()
+            .map(|point| curve_params.validate_point_detailed(point))
+            .collect()
+    }
+
+    /// Returns true if `self` and `other` are equal, comparing the coordinates with
+    /// `BigInt::ct_eq`.
+    ///
+    /// Prefer this over `==` for keys derived from secrets.
+    pub fn ct_eq(&self, other: &PublicKey) -> bool {
+        let x_eq = self.data.x.ct_eq(&other.data.x);
+        let y_eq = self.data.y.ct_eq(&other.data.y);
+        (self.curve_params == other.curve_params) & x_eq & y_eq
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PrivateKeyDecodingError {
+    InvalidLength {
+        expected: usize,
+        found: usize,
+    },
+    /// The key is zero or not less than the order.
+    OutOfRange,
+}
+
+impl Display for PrivateKeyDecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrivateKeyDecodingError::InvalidLength { expected, found } => write!(
+                f,
+                "invalid private key length: {found} bytes, expected {expected}"
+            ),
+            PrivateKeyDecodingError::OutOfRange => write!(f, "private key out of range"),
+        }
+    }
+}
+
+impl std::error::Error for PrivateKeyDecodingError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::elliptic_curve_params::Coordinate;
+    use crate::crypto::{secp256k1, secp256r1, secp521r1};
 
     #[test]
     fn test_keys_partial_eq() {
@@ -94,4 +184,155 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_private_key_bytes() {
+        let data = [
+            // (curve, key)
+            (
+                secp256k1(),
+                "8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f",
+            ),
+            (
+                secp256k1(),
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            (
+                secp256k1(),
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            ),
+            (
+                secp256r1(),
+                "00c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f67",
+            ),
+            // 66 bytes
+            (
+                secp521r1(),
+                "0000000000000000000000000000000000000000000000000000000000000000\
+                 00000000000000000000000000000000000000000000000000000000000000000002",
+            ),
+        ];
+        for (curve, key_hex) in data {
+            let bytes = hex::decode(key_hex).unwrap();
+            let private_key = PrivateKey::from_bytes(&bytes, curve).unwrap();
+            assert_eq!(private_key.data, BigInt::from_hex(key_hex).unwrap());
+            assert_eq!(private_key.to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_private_key_bytes_error_cases() {
+        let curve = secp256k1();
+        let data = [
+            // (key, error)
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                PrivateKeyDecodingError::OutOfRange,
+            ),
+            // The order
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                PrivateKeyDecodingError::OutOfRange,
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                PrivateKeyDecodingError::OutOfRange,
+            ),
+            (
+                "01",
+                PrivateKeyDecodingError::InvalidLength {
+                    expected: 32,
+                    found: 1,
+                },
+            ),
+            (
+                "",
+                PrivateKeyDecodingError::InvalidLength {
+                    expected: 32,
+                    found: 0,
+                },
+            ),
+            (
+                "000000000000000000000000000000000000000000000000000000000000000001",
+                PrivateKeyDecodingError::InvalidLength {
+                    expected: 32,
+                    found: 33,
+                },
+            ),
+        ];
+        for (key_hex, error) in data {
+            let bytes = hex::decode(key_hex).unwrap();
+            assert_eq!(
+                PrivateKey::from_bytes(&bytes, curve).err(),
+                Some(error),
+                "{key_hex}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_private_key_to_bytes_panic_on_negative_data() {
+        let mut private_key = PrivateKey::new(BigInt::from(7), secp256k1()).unwrap();
+        private_key.data = BigInt::from(-7);
+        let _ = private_key.to_bytes();
+    }
+
+    #[test]
+    fn test_validate_batch() {
+        let curve = secp256k1();
+        let point = |d: u32| curve.curve.mul_point(&curve.base_point, &BigInt::from(d));
+        let mut not_on_curve = point(2);
+        not_on_curve.y += &BigInt::one();
+        let mut x_out_of_range = point(3);
+        x_out_of_range.x += &curve.curve.p;
+
+        let points = [
+            point(1),
+            not_on_curve,
+            point(2),
+            Point::identity_element(),
+            x_out_of_range,
+            point(4),
+        ];
+        assert_eq!(
+            PublicKey::validate_batch(&points, curve),
+            [
+                Ok(()),
+                Err(PointValidationError::NotOnCurve),
+                Ok(()),
+                Err(PointValidationError::IdentityElement),
+                Err(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::X
+                }),
+                Ok(()),
+            ]
+        );
+        assert!(PublicKey::validate_batch(&[], curve).is_empty());
+    }
+
+    #[test]
+    fn test_public_key_ct_eq() {
+        let curve = secp256k1();
+        let public_key = |d: u32| {
+            let data = curve.curve.mul_point(&curve.base_point, &BigInt::from(d));
+            PublicKey::new(data, curve).unwrap()
+        };
+
+        let keys = [1, 2, 3, 0xdeadbeef].map(public_key);
+        for a in &keys {
+            for b in &keys {
+                assert_eq!(a.ct_eq(b), a == b);
+            }
+        }
+        assert!(keys[1].ct_eq(&public_key(2)));
+
+        // Same coordinates, different curves
+        let other_curve = PublicKey {
+            data: keys[1].data.clone(),
+            curve_params: secp256r1(),
+        };
+        assert!(!keys[1].ct_eq(&other_curve));
+        assert_ne!(keys[1], other_curve);
+    }
 }
@@ -0,0 +1,409 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements public keys with the precomputed wNAF table of verifying, and its wire
+//! format, e.g., to cache the table on disk across processes.
+//!
+//! The wire format, integers in big-endian:
+//!
+//! ```text
+//! magic          4 bytes   "LCPK"
+//! version        1 byte    1
+//! curve id      32 bytes   see `curve_identifier`
+//! window width   1 byte
+//! point count    4 bytes   2^(w-2)
+//! points                   x ‖ y each, of the byte length of the field modulus
+//! ```
+//!
+//! The first point is the public key, followed by its odd multiples `3 * Q, 5 * Q, ...`.
+
+use super::ecdsa_core::Signature;
+use super::ecdsa_key::PublicKey;
+use super::ecdsa_verifying::{
+    verify_with_options_and_multiplier, VerifyingError, VerifyingOptions,
+};
+use crate::crypto::elliptic_curve_params::{
+    EllipticCurveParams, EllipticCurveParamsEncoding, PointValidationError,
+};
+use crate::crypto::hash::{Sha256, UnkeyedHash};
+use crate::crypto::raw_xy::RawXY;
+use crate::math::elliptic_curve::{Point, MUL_WINDOW_WIDTH_RANGE};
+use std::fmt;
+use std::fmt::Display;
+
+const MAGIC: &[u8; 4] = b"LCPK";
+const VERSION: u8 = 1;
+const CURVE_ID_BYTE_LENGTH: usize = 32;
+const HEADER_BYTE_LENGTH: usize = 4 + 1 + CURVE_ID_BYTE_LENGTH + 1 + 4;
+
+/// A public key with the odd multiples of it precomputed for the wNAF method, which
+/// speeds up verifying many signatures with the same key.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PrecomputedPublicKey<'a> {
+    public_key: PublicKey<'a>,
+    window_width: usize,
+    // `Q, 3 * Q, ..., (2^(w-1) - 1) * Q`
+    odd_multiples: Vec<Point>,
+}
+
+impl<'a> PrecomputedPublicKey<'a> {
+    /// Precomputes the table of `public_key` for the window width `window_width`.
+    ///
+    /// Will panic if the window width is not within `2..=6`.
+    pub fn new(public_key: PublicKey<'a>, window_width: usize) -> Self {
+        assert!(
+            MUL_WINDOW_WIDTH_RANGE.contains(&window_width),
+            "invalid window width"
+        );
+
+        let odd_multiples = public_key
+            .curve_params
+            .curve
+            .odd_multiples(&public_key.data, window_width);
+        PrecomputedPublicKey {
+            public_key,
+            window_width,
+            odd_multiples,
+        }
+    }
+
+    pub fn public_key(&self) -> &PublicKey<'a> {
+        &self.public_key
+    }
+
+    pub fn window_width(&self) -> usize {
+        self.window_width
+    }
+
+    /// Same as `ecdsa::verify`, with the precomputed table.
+    pub fn verify(&self, hash: &[u8], signature: &Signature) -> Result<bool, VerifyingError> {
+        self.verify_with_options(hash, signature, &VerifyingOptions::default())
+    }
+
+    /// Same as `ecdsa::verify_with_options`, with the precomputed table.
+    pub fn verify_with_options(
+        &self,
+        hash: &[u8],
+        signature: &Signature,
+        options: &VerifyingOptions,
+    ) -> Result<bool, VerifyingError> {
+        let curve = &self.public_key.curve_params.curve;
+        verify_with_options_and_multiplier(hash, signature, &self.public_key, options, |v| {
+            curve.mul_point_with_odd_multiples(&self.odd_multiples, v, self.window_width)
+        })
+    }
+
+    /// Serializes the table in the wire format described in the module documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let curve_params = self.public_key.curve_params;
+        let point_byte_len = RawXY::element_byte_len(curve_params) * 2;
+        let mut bytes =
+            Vec::with_capacity(HEADER_BYTE_LENGTH + self.odd_multiples.len() * point_byte_len);
+        bytes.extend(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend(curve_identifier(curve_params));
+        bytes.push(self.window_width as u8);
+        bytes.extend((self.odd_multiples.len() as u32).to_be_bytes());
+        for point in &self.odd_multiples {
+            bytes.extend(RawXY::encode_point_to_bytes(point, curve_params, false));
+        }
+
+        bytes
+    }
+
+    /// Deserializes a table from `bytes`, serialized by [`Self::to_bytes`] for
+    /// `curve_params`.
+    ///
+    /// The header must be consistent, and every point must be a valid point of the curve:
+    /// a corrupted table, or one of another curve, is rejected. The relations between the
+    /// points aren't checked, which would cost as much as the precomputation: a table with
+    /// valid points of the curve which aren't the multiples of the public key makes the
+    /// verifying results wrong. Store the table where only the trusted writers can modify
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if `bytes` isn't a table of `curve_params`.
+    pub fn from_bytes(
+        bytes: &[u8],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<Self, PrecomputedTableError> {
+        Self::from_bytes_with_validation(bytes, curve_params, true)
+    }
+
+    /// Like [`Self::from_bytes`], without validating the points.
+    ///
+    /// Only the header is checked: a corrupted point, including one which isn't on the
+    /// curve, is used as is and makes the verifying results wrong, or worse, exposes the
+    /// verifying to invalid-curve attacks. Only use it for tables whose integrity is
+    /// guaranteed otherwise, e.g., authenticated with a MAC.
+    pub fn from_trusted_bytes(
+        bytes: &[u8],
+        curve_params: &'a EllipticCurveParams,
+    ) -> Result<Self, PrecomputedTableError> {
+        Self::from_bytes_with_validation(bytes, curve_params, false)
+    }
+
+    fn from_bytes_with_validation(
+        bytes: &[u8],
+        curve_params: &'a EllipticCurveParams,
+        validates_points: bool,
+    ) -> Result<Self, PrecomputedTableError> {
+        if bytes.len() < HEADER_BYTE_LENGTH {
+            return Err(PrecomputedTableError::InvalidLength);
+        }
+        let (header, points_bytes) = bytes.split_at(HEADER_BYTE_LENGTH);
+        let (magic, header) = header.split_at(MAGIC.len());
+        if magic != MAGIC {
+            return Err(PrecomputedTableError::InvalidMagic);
+        }
+        if header[0] != VERSION {
+            return Err(PrecomputedTableError::UnsupportedVersion(header[0]));
+        }
+        let (curve_id, header) = header[1..].split_at(CURVE_ID_BYTE_LENGTH);
+        if curve_id != curve_identifier(curve_params) {
+            return Err(PrecomputedTableError::CurveMismatch);
+        }
+        let window_width = header[0] as usize;
+        if !MUL_WINDOW_WIDTH_RANGE.contains(&window_width) {
+            return Err(PrecomputedTableError::InvalidWindowWidth);
+        }
+        let point_count = u32::from_be_bytes(header[1..].try_into().unwrap()) as usize;
+        if point_count != 1 << (window_width - 2) {
+            return Err(PrecomputedTableError::PointCountMismatch);
+        }
+        let point_byte_len = RawXY::element_byte_len(curve_params) * 2;
+        if points_bytes.len() != point_count * point_byte_len {
+            return Err(PrecomputedTableError::InvalidLength);
+        }
+
+        let mut odd_multiples = Vec::with_capacity(point_count);
+        for (index, point_bytes) in points_bytes.chunks(point_byte_len).enumerate() {
+            let point =
+                RawXY::decode_unvalidated_point_from_bytes(point_bytes, curve_params).unwrap();
+            if validates_points {
+                curve_params
+                    .validate_point_detailed(&point)
+                    .map_err(|reason| PrecomputedTableError::InvalidPoint { index, reason })?;
+            }
+            odd_multiples.push(point);
+        }
+
+        Ok(PrecomputedPublicKey {
+            public_key: PublicKey {
+                data: odd_multiples[0].clone(),
+                curve_params,
+            },
+            window_width,
+            odd_multiples,
+        })
+    }
+}
+
+/// Returns the SHA-256 hash of the parameters of the curve, identifying the curve in the
+/// wire format: p, a, b, the base point x and y, and the base point order, each as its
+/// big-endian bytes prefixed with the byte length in 2 bytes, followed by the cofactor in
+/// 4 bytes.
+fn curve_identifier(curve_params: &EllipticCurveParams) -> [u8; CURVE_ID_BYTE_LENGTH] {
+    let mut bytes = vec![];
+    for n in [
+        &curve_params.curve.p,
+        &curve_params.curve.a,
+        &curve_params.curve.b,
+        &curve_params.base_point.x,
+        &curve_params.base_point.y,
+        &curve_params.base_point_order,
+    ] {
+        let n_bytes = n.to_be_bytes();
+        bytes.extend((n_bytes.len() as u16).to_be_bytes());
+        bytes.extend(n_bytes);
+    }
+    bytes.extend(curve_params.cofactor.to_be_bytes());
+
+    Sha256::new().digest(bytes).try_into().unwrap()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PrecomputedTableError {
+    /// Truncated, or with extra bytes.
+    InvalidLength,
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    /// The table is of another curve.
+    CurveMismatch,
+    InvalidWindowWidth,
+    /// The point count doesn't match the window width.
+    PointCountMismatch,
+    /// The point at `index` isn't a valid point of the curve.
+    InvalidPoint {
+        index: usize,
+        reason: PointValidationError,
+    },
+}
+
+impl Display for PrecomputedTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecomputedTableError::InvalidLength => write!(f, "invalid table length"),
+            PrecomputedTableError::InvalidMagic => write!(f, "invalid table magic"),
+            PrecomputedTableError::UnsupportedVersion(version) => {
+                write!(f, "unsupported table version {version}")
+            }
+            PrecomputedTableError::CurveMismatch => write!(f, "table of another curve"),
+            PrecomputedTableError::InvalidWindowWidth => write!(f, "invalid window width"),
+            PrecomputedTableError::PointCountMismatch => {
+                write!(f, "point count mismatches the window width")
+            }
+            PrecomputedTableError::InvalidPoint { index, reason } => {
+                write!(f, "invalid point at {index}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrecomputedTableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+    use crate::crypto::ecdsa::{sign_with_options, verify, PrivateKey, SigningOptions};
+    use crate::crypto::{secp256k1, secp256r1};
+
+    fn private_key(curve_params: &EllipticCurveParams) -> PrivateKey {
+        PrivateKey::new(BigInt::from_hex("c0ffee15c0ffee").unwrap(), curve_params).unwrap()
+    }
+
+    fn signatures<'a>(private_key: &'a PrivateKey, hashes: &[[u8; 32]]) -> Vec<Signature<'a>> {
+        let options = SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+        hashes
+            .iter()
+            .map(|hash| sign_with_options(hash, private_key, &options).unwrap().0)
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for curve_params in [secp256k1(), secp256r1()] {
+            let private_key = private_key(curve_params);
+            let hashes = [[1; 32], [0xfe; 32]];
+            let signatures = signatures(&private_key, &hashes);
+
+            for window_width in [2, 5] {
+                let precomputed =
+                    PrecomputedPublicKey::new(private_key.public_key(), window_width);
+                let bytes = precomputed.to_bytes();
+                let loaded = PrecomputedPublicKey::from_bytes(&bytes, curve_params).unwrap();
+                assert_eq!(loaded, precomputed);
+                assert_eq!(loaded.public_key(), &private_key.public_key());
+                assert_eq!(
+                    PrecomputedPublicKey::from_trusted_bytes(&bytes, curve_params).unwrap(),
+                    precomputed
+                );
+
+                // the same results as without the table, for every hash and signature
+                for hash in &hashes {
+                    for signature in &signatures {
+                        let expected = verify(hash, signature, &private_key.public_key());
+                        assert_eq!(precomputed.verify(hash, signature), expected);
+                        assert_eq!(loaded.verify(hash, signature), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let curve_params = secp256k1();
+        let private_key = private_key(curve_params);
+        let bytes = PrecomputedPublicKey::new(private_key.public_key(), 5).to_bytes();
+
+        assert_eq!(&bytes[..5], b"LCPK\x01");
+        assert_eq!(bytes[37], 5);
+        assert_eq!(bytes[38..42], [0, 0, 0, 8]);
+        assert_eq!(bytes.len(), 42 + 8 * 64);
+        assert_eq!(
+            bytes[42..106],
+            curve_params.point_to_bytes(&private_key.public_key().data)
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_err_cases() {
+        let curve_params = secp256k1();
+        let bytes =
+            PrecomputedPublicKey::new(private_key(curve_params).public_key(), 5).to_bytes();
+        let modified = |index: usize, byte: u8| {
+            let mut bytes = bytes.clone();
+            bytes[index] = byte;
+            bytes
+        };
+
+        // (bytes, error)
+        let data = [
+            (vec![], PrecomputedTableError::InvalidLength),
+            (bytes[..41].to_vec(), PrecomputedTableError::InvalidLength),
+            // truncated
+            (
+                bytes[..bytes.len() - 1].to_vec(),
+                PrecomputedTableError::InvalidLength,
+            ),
+            (
+                [&bytes[..], &[0]].concat(),
+                PrecomputedTableError::InvalidLength,
+            ),
+            (modified(0, b'X'), PrecomputedTableError::InvalidMagic),
+            (modified(4, 2), PrecomputedTableError::UnsupportedVersion(2)),
+            (
+                modified(5, bytes[5] ^ 1),
+                PrecomputedTableError::CurveMismatch,
+            ),
+            (modified(37, 7), PrecomputedTableError::InvalidWindowWidth),
+            (modified(37, 4), PrecomputedTableError::PointCountMismatch),
+            (modified(41, 4), PrecomputedTableError::PointCountMismatch),
+            // a flipped byte in the y of the third point
+            (
+                modified(42 + 2 * 64 + 63, bytes[42 + 2 * 64 + 63] ^ 1),
+                PrecomputedTableError::InvalidPoint {
+                    index: 2,
+                    reason: PointValidationError::NotOnCurve,
+                },
+            ),
+        ];
+        for (bytes, error) in data {
+            assert_eq!(
+                PrecomputedPublicKey::from_bytes(&bytes, curve_params),
+                Err(error)
+            );
+        }
+
+        // mismatched curve
+        assert_eq!(
+            PrecomputedPublicKey::from_bytes(&bytes, secp256r1()),
+            Err(PrecomputedTableError::CurveMismatch)
+        );
+
+        // The trusted loading only checks the header.
+        let corrupted = modified(42 + 2 * 64 + 63, bytes[42 + 2 * 64 + 63] ^ 1);
+        assert!(PrecomputedPublicKey::from_trusted_bytes(&corrupted, curve_params).is_ok());
+        assert_eq!(
+            PrecomputedPublicKey::from_trusted_bytes(&bytes[..100], curve_params),
+            Err(PrecomputedTableError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_curve_identifier() {
+        let identifiers = [secp256k1(), secp256r1()].map(curve_identifier);
+        assert_ne!(identifiers[0], identifiers[1]);
+        assert_eq!(identifiers[0], curve_identifier(secp256k1()));
+    }
+}
@@ -10,8 +10,9 @@ use super::ecdsa_core::{
     HASH_BIT_LENGTH_DOES_NOT_MATCH_BASE_POINT_ORDER_ERROR_DISPLAY,
     ZERO_HASH_NOT_ALLOWED_ERROR_DISPLAY,
 };
-use crate::bigint::{BigInt, Sign};
-use crate::crypto::ecdsa::{PublicKey, Signature};
+use crate::bigint::BigInt;
+use crate::crypto::ecdsa::{PublicKey, Signature, SignatureCanonicality};
+use crate::crypto::hash_to_scalar::truncate_to_scalar;
 use crate::math::{modular, Point};
 use std::fmt;
 use std::fmt::Display;
@@ -45,17 +46,18 @@ pub fn recover_public_keys_from_signature_with_options<'a>(
         return Err(RecoveryError::EmptyHashNotAllowed);
     }
 
+    // A signature created with `Signature::new_unchecked` may have r or s out of `(0, n)`.
+    if signature.is_canonical() == SignatureCanonicality::OutOfRange {
+        return Err(RecoveryError::InvalidSignature);
+    }
+
     if options.strict_hash_byte_length
         && !hash_length_matches_base_point_order(hash.len(), signature.curve_params)
     {
         return Err(RecoveryError::HashBitLengthDoesNotMatchBasePointOrder);
     }
 
-    let hash_n = BigInt::from_be_bytes_with_max_bits_len(
-        hash,
-        signature.curve_params.base_point_order.bit_len(),
-        Sign::Positive,
-    );
+    let hash_n = truncate_to_scalar(hash, &signature.curve_params.base_point_order);
     if hash_n.is_zero() {
         return Err(RecoveryError::ZeroHashNotAllowed);
     }
@@ -201,7 +203,7 @@ impl std::error::Error for RecoveryError {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::hex_to_bytes;
+    use crate::crypto::codecs::hex;
     use crate::crypto::ecdsa::{sign_with_options, PrivateKey, SigningOptions};
     use crate::crypto::secp256k1;
 
@@ -210,7 +212,7 @@ mod tests {
         let secp256k1 = secp256k1();
 
         let hash_hex = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
-        let hash = hex_to_bytes(hash_hex).unwrap();
+        let hash = hex::decode(hash_hex).unwrap();
         let d = BigInt::from(1);
 
         let private_key = PrivateKey::new(d, secp256k1).unwrap();
@@ -241,7 +243,7 @@ mod tests {
         let secp256k1 = secp256k1();
 
         let hash =
-            hex_to_bytes("6b8d2c81b11b2d699528dde488dbdf2f94293d0d33c32e347f255fa4a6c1f0a9")
+            hex::decode("6b8d2c81b11b2d699528dde488dbdf2f94293d0d33c32e347f255fa4a6c1f0a9")
                 .unwrap();
         let r = BigInt::from_hex(
             "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
@@ -288,4 +290,27 @@ mod tests {
             RecoveryError::ZeroHashNotAllowed
         );
     }
+
+    #[test]
+    fn test_recover_public_keys_out_of_range() {
+        let secp256k1 = secp256k1();
+        let hash =
+            hex::decode("6b8d2c81b11b2d699528dde488dbdf2f94293d0d33c32e347f255fa4a6c1f0a9")
+                .unwrap();
+        let n = &secp256k1.base_point_order;
+        let data = [
+            (BigInt::zero(), BigInt::one()),
+            (BigInt::one(), BigInt::zero()),
+            (n.clone(), BigInt::one()),
+            (BigInt::one(), n.clone()),
+            (n + BigInt::one(), BigInt::one()),
+        ];
+        for (r, s) in data {
+            let signature = Signature::new_unchecked(r, s, secp256k1);
+            assert_eq!(
+                recover_public_keys_from_signature(&signature, &hash, None),
+                Err(RecoveryError::InvalidSignature)
+            );
+        }
+    }
 }
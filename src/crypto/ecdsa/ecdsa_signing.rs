@@ -6,14 +6,13 @@
 
 use super::ecdsa_core::{
     hash_length_matches_base_point_order, Signature, SignatureRecoveryId,
-    EMPTY_HASH_NOT_ALLOWED_ERROR_DISPLAY,
+    CURVE_MISMATCH_ERROR_DISPLAY, EMPTY_HASH_NOT_ALLOWED_ERROR_DISPLAY,
     HASH_BIT_LENGTH_DOES_NOT_MATCH_BASE_POINT_ORDER_ERROR_DISPLAY,
     ZERO_HASH_NOT_ALLOWED_ERROR_DISPLAY,
 };
 use super::ecdsa_key::PrivateKey;
-use crate::bigint::bigint_core::Sign;
-use crate::bigint::BigInt;
 use crate::crypto::hash::{Sha256, UnkeyedHash};
+use crate::crypto::hash_to_scalar::truncate_to_scalar;
 use crate::crypto::rfc6979::{GenerateNonceError, Rfc6979};
 use std::fmt;
 use std::fmt::Display;
@@ -50,11 +49,7 @@ pub fn sign_with_options_and_rfc6979_hmac_hasher<'a, H: UnkeyedHash>(
     }
 
     // SEC1: truncates the hash to the bit length of the order of the base point.
-    let hash_n = BigInt::from_be_bytes_with_max_bits_len(
-        hash,
-        private_key.curve_params.base_point_order.bit_len(),
-        Sign::Positive,
-    );
+    let hash_n = truncate_to_scalar(hash, &private_key.curve_params.base_point_order);
 
     if !options.is_zero_hash_allowed && hash_n.is_zero() {
         return Err(SigningError::ZeroHashNotAllowed);
@@ -63,6 +58,7 @@ pub fn sign_with_options_and_rfc6979_hmac_hasher<'a, H: UnkeyedHash>(
     let rfc6979 = Rfc6979::new(
         private_key.curve_params.base_point_order.clone(),
         options.employ_extra_random_data,
+        options.additional_entropy.as_deref(),
     );
     loop {
         // TODO: Fix the Minerva vulnerability
@@ -105,11 +101,16 @@ pub fn sign_with_options_and_rfc6979_hmac_hasher<'a, H: UnkeyedHash>(
     }
 }
 
+#[derive(Clone)]
 pub struct SigningOptions {
     pub enforce_low_s: bool,
     pub strict_hash_byte_length: bool,
     pub employ_extra_random_data: bool,
     pub is_zero_hash_allowed: bool, // mostly for dev and testing
+    /// The extra data fed to `K` and `V` of RFC 6979 (section 3.6) in place of the OS
+    /// random bytes, e.g., to reproduce the signatures with extra entropy. Takes precedence
+    /// over `employ_extra_random_data`.
+    pub additional_entropy: Option<Vec<u8>>,
 }
 
 impl Default for SigningOptions {
@@ -119,9 +120,23 @@ impl Default for SigningOptions {
             strict_hash_byte_length: true,
             employ_extra_random_data: true,
             is_zero_hash_allowed: false,
+            additional_entropy: None,
         }
     }
 }
+
+impl SigningOptions {
+    /// Returns the options Ethereum clients sign transactions with: low s values as
+    /// required by EIP-2, and the deterministic nonces of RFC 6979 without extra random
+    /// data, so the signatures are reproducible, e.g., match those of ethers.js.
+    pub fn ethereum() -> Self {
+        Self {
+            employ_extra_random_data: false,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum SigningError {
@@ -129,6 +144,7 @@ pub enum SigningError {
     ZeroHashNotAllowed,
     HashBitLengthDoesNotMatchBasePointOrder,
     FailedToGenerateNonce(GenerateNonceError),
+    CurveMismatch,
 }
 
 impl Display for SigningError {
@@ -150,6 +166,9 @@ impl Display for SigningError {
             SigningError::FailedToGenerateNonce(err) => {
                 write!(f, "Failed to generate deterministic nonce: {err}")
             }
+            SigningError::CurveMismatch => {
+                write!(f, "{}", CURVE_MISMATCH_ERROR_DISPLAY)
+            }
         }
     }
 }
@@ -160,7 +179,7 @@ impl std::error::Error for SigningError {}
 mod tests {
     use super::*;
     use crate::bigint::BigInt;
-    use crate::crypto::codecs::hex_to_bytes;
+    use crate::crypto::codecs::hex;
     use crate::crypto::ecdsa::PrivateKey;
     use crate::crypto::{secp256k1, EllipticCurveParams};
     use crate::random::{generator, GetOsRandomBytesError};
@@ -223,20 +242,23 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_sign_with_curve_base_point_order_not_byte_aligned() {
         let curve = EllipticCurveParams {
             base_point_order: BigInt::from(12),
             ..Default::default()
         };
         let private_key = PrivateKey::new(BigInt::one(), &curve).unwrap();
-        let _ = sign_with_options(
-            &[1],
-            &private_key,
-            &SigningOptions {
-                employ_extra_random_data: false,
-                ..Default::default()
-            },
+        assert_eq!(
+            sign_with_options(
+                &[1],
+                &private_key,
+                &SigningOptions {
+                    employ_extra_random_data: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap_err(),
+            SigningError::HashBitLengthDoesNotMatchBasePointOrder
         );
     }
 
@@ -251,7 +273,7 @@ mod tests {
             "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", // extraEntropyN
             "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", // extraEntropyMax
         ];
-        let extra_data_vec = extra_data_hex_vec.map(|hex| hex_to_bytes(hex).unwrap());
+        let extra_data_vec = extra_data_hex_vec.map(|hex| hex::decode(hex).unwrap());
 
         let path = integration_testing_data_path("crypto/secp256k1/noble-secp256k1/ecdsa.json");
         let file = File::open(path).unwrap();
@@ -274,7 +296,7 @@ mod tests {
 
             // without extra data
             let (signature, _) = sign_with_options(
-                &hex_to_bytes(m_hex).unwrap(),
+                &hex::decode(m_hex).unwrap(),
                 &private_key,
                 &SigningOptions {
                     employ_extra_random_data: false,
@@ -282,7 +304,7 @@ mod tests {
                 },
             )
             .unwrap();
-            assert_eq!(signature.to_p1363_hex(), signature_hex);
+            assert_eq!(signature.to_p1363_hex().unwrap(), signature_hex);
 
             // with extra data
             for (extra_data, &signature_hex) in zip(
@@ -294,7 +316,7 @@ mod tests {
                     ctx.expect().return_once(|_| Ok(extra_data));
 
                     let (signature, _) = sign_with_options(
-                        &hex_to_bytes(m_hex).unwrap(),
+                        &hex::decode(m_hex).unwrap(),
                         &private_key,
                         &SigningOptions {
                             employ_extra_random_data: true,
@@ -302,7 +324,7 @@ mod tests {
                         },
                     )
                     .unwrap();
-                    assert_eq!(signature.to_p1363_hex(), signature_hex);
+                    assert_eq!(signature.to_p1363_hex().unwrap(), signature_hex);
                 }
             }
         }
@@ -331,4 +353,61 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_sign_with_additional_entropy() {
+        let secp256k1 = secp256k1();
+
+        let path = integration_testing_data_path("crypto/secp256k1/noble-secp256k1/ecdsa.json");
+        let file = File::open(path).unwrap();
+        let root: Value = serde_json::from_reader(file).unwrap();
+        let value_vec = root["extraEntropy"].as_array().unwrap();
+        for value in value_vec {
+            let d_hex = value["d"].as_str().unwrap();
+            let m_hex = value["m"].as_str().unwrap();
+            let private_key =
+                PrivateKey::new(BigInt::from_hex(d_hex).unwrap(), secp256k1).unwrap();
+
+            // (additional entropy, expected signature)
+            let data = [
+                (
+                    "0000000000000000000000000000000000000000000000000000000000000000",
+                    &value["extraEntropy0"],
+                ),
+                (
+                    "0000000000000000000000000000000000000000000000000000000000000001",
+                    &value["extraEntropy1"],
+                ),
+                (
+                    "6e723d3fd94ed5d2b6bdd4f123364b0f3ca52af829988a63f8afe91d29db1c33",
+                    &value["extraEntropyRand"],
+                ),
+                (
+                    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                    &value["extraEntropyN"],
+                ),
+                (
+                    "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                    &value["extraEntropyMax"],
+                ),
+            ];
+            for (entropy_hex, signature_hex) in data {
+                // `employ_extra_random_data` is ignored, the OS isn't involved.
+                let (signature, _) = sign_with_options(
+                    &hex::decode(m_hex).unwrap(),
+                    &private_key,
+                    &SigningOptions {
+                        employ_extra_random_data: true,
+                        additional_entropy: Some(hex::decode(entropy_hex).unwrap()),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert_eq!(
+                    signature.to_p1363_hex().unwrap(),
+                    signature_hex.as_str().unwrap()
+                );
+            }
+        }
+    }
 }
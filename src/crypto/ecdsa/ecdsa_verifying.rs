@@ -5,13 +5,15 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::ecdsa_core::{
-    hash_length_matches_base_point_order, Signature, EMPTY_HASH_NOT_ALLOWED_ERROR_DISPLAY,
+    hash_length_matches_base_point_order, Signature, SignatureCanonicality,
+    CURVE_MISMATCH_ERROR_DISPLAY, EMPTY_HASH_NOT_ALLOWED_ERROR_DISPLAY,
     HASH_BIT_LENGTH_DOES_NOT_MATCH_BASE_POINT_ORDER_ERROR_DISPLAY,
     ZERO_HASH_NOT_ALLOWED_ERROR_DISPLAY,
 };
 use super::ecdsa_key::PublicKey;
-use crate::bigint::bigint_core::Sign;
 use crate::bigint::BigInt;
+use crate::crypto::hash_to_scalar::truncate_to_scalar;
+use crate::math::elliptic_curve::Point;
 use std::fmt;
 use std::fmt::Display;
 
@@ -28,11 +30,31 @@ pub fn verify_with_options(
     signature: &Signature,
     public_key: &PublicKey,
     options: &VerifyingOptions,
+) -> Result<bool, VerifyingError> {
+    verify_with_options_and_multiplier(hash, signature, public_key, options, |v| {
+        public_key.curve_params.curve.mul_point(&public_key.data, v)
+    })
+}
+
+/// Like `verify_with_options`, with `mul_public_key` multiplying the public key with a
+/// scalar, e.g., with a precomputed table.
+pub(crate) fn verify_with_options_and_multiplier(
+    hash: &[u8],
+    signature: &Signature,
+    public_key: &PublicKey,
+    options: &VerifyingOptions,
+    mul_public_key: impl FnOnce(&BigInt) -> Point,
 ) -> Result<bool, VerifyingError> {
     if hash.is_empty() {
         return Err(VerifyingError::EmptyHashNotAllowed);
     }
 
+    // Rejects out-of-range signatures, e.g., created with `Signature::new_unchecked`.
+    // High s signatures are valid unless "low s" is enforced.
+    if signature.is_canonical() == SignatureCanonicality::OutOfRange {
+        return Err(VerifyingError::InvalidSignature);
+    }
+
     if options.enforce_low_s && !signature.is_low_s_signature() {
         return Err(VerifyingError::StrictHighSFound);
     }
@@ -43,21 +65,22 @@ pub fn verify_with_options(
         return Err(VerifyingError::HashBitLengthDoesNotMatchBasePointOrder);
     }
 
-    let hash_n = BigInt::from_be_bytes_with_max_bits_len(
-        hash,
-        public_key.curve_params.base_point_order.bit_len(),
-        Sign::Positive,
-    );
+    // SEC1: truncates the hash to the bit length of the order of the base point,
+    // identically to signing.
+    let hash_n = truncate_to_scalar(hash, &public_key.curve_params.base_point_order);
     if hash_n.is_zero() {
         return Err(VerifyingError::ZeroHashNotAllowed);
     }
 
-    let result = public_key.verify(&hash_n, signature);
+    let result = public_key.verify_with_multiplier(&hash_n, signature, mul_public_key);
     Ok(result)
 }
 
 pub struct VerifyingOptions {
     pub enforce_low_s: bool,
+    /// If true, the bit length of the hash must equal the one of the order of the base
+    /// point, mirroring `SigningOptions::strict_hash_byte_length`. Otherwise, a longer hash
+    /// is truncated as it is in signing.
     pub strict_hash_byte_length: bool,
 }
 
@@ -78,6 +101,8 @@ pub enum VerifyingError {
     ZeroHashNotAllowed,
     StrictHighSFound,
     HashBitLengthDoesNotMatchBasePointOrder,
+    InvalidSignature,
+    CurveMismatch,
 }
 
 impl Display for VerifyingError {
@@ -99,6 +124,12 @@ impl Display for VerifyingError {
                     HASH_BIT_LENGTH_DOES_NOT_MATCH_BASE_POINT_ORDER_ERROR_DISPLAY
                 )
             }
+            VerifyingError::InvalidSignature => {
+                write!(f, "Invalid signature")
+            }
+            VerifyingError::CurveMismatch => {
+                write!(f, "{}", CURVE_MISMATCH_ERROR_DISPLAY)
+            }
         }
     }
 }
@@ -108,8 +139,9 @@ impl std::error::Error for VerifyingError {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::ecdsa::{sign_with_options, PrivateKey, SigningOptions};
-    use crate::crypto::secp256k1;
+    use crate::crypto::ecdsa::{sign_with_options, PrivateKey, SigningError, SigningOptions};
+    use crate::crypto::hash::{Sha512, UnkeyedHash};
+    use crate::crypto::{secp256k1, secp521r1};
 
     #[test]
     fn test_verifying_err_cases() {
@@ -157,4 +189,134 @@ mod tests {
             VerifyingError::ZeroHashNotAllowed
         );
     }
+
+    #[test]
+    fn test_verifying_per_canonicality() {
+        let curve_params = secp256k1();
+        let private_key = PrivateKey::new(BigInt::from(0xc0ffee), curve_params).unwrap();
+        let public_key = private_key.public_key();
+        let hash = [0x5a; 32];
+        let (signature, _) = sign_with_options(
+            &hash,
+            &private_key,
+            &SigningOptions {
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let enforcing_low_s = VerifyingOptions {
+            enforce_low_s: true,
+            ..Default::default()
+        };
+
+        // canonical
+        assert_eq!(signature.is_canonical(), SignatureCanonicality::Canonical);
+        assert_eq!(verify(&hash, &signature, &public_key), Ok(true));
+        assert_eq!(
+            verify_with_options(&hash, &signature, &public_key, &enforcing_low_s),
+            Ok(true)
+        );
+
+        // high s, the negated s verifies as well
+        let n = &curve_params.base_point_order;
+        let high_s =
+            Signature::new_unchecked(signature.r.clone(), n - &signature.s, curve_params);
+        assert_eq!(high_s.is_canonical(), SignatureCanonicality::HighS);
+        assert_eq!(verify(&hash, &high_s, &public_key), Ok(true));
+        assert_eq!(
+            verify_with_options(&hash, &high_s, &public_key, &enforcing_low_s),
+            Err(VerifyingError::StrictHighSFound)
+        );
+
+        // out of range: s + n is congruent to s, but never verified
+        let out_of_range =
+            Signature::new_unchecked(signature.r.clone(), n + &signature.s, curve_params);
+        assert_eq!(
+            out_of_range.is_canonical(),
+            SignatureCanonicality::OutOfRange
+        );
+        for options in [VerifyingOptions::default(), enforcing_low_s] {
+            assert_eq!(
+                verify_with_options(&hash, &out_of_range, &public_key, &options),
+                Err(VerifyingError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn test_verifying_truncated_hash() {
+        let not_strict_signing = SigningOptions {
+            strict_hash_byte_length: false,
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+        let not_strict_verifying = VerifyingOptions {
+            strict_hash_byte_length: false,
+            ..Default::default()
+        };
+
+        let hash_512 = Sha512::new().digest(b"sample");
+        // (hash, curve_params)
+        let data = [
+            ((0..40).collect::<Vec<u8>>(), secp256k1()),
+            (hash_512.clone(), secp256k1()),
+            (hash_512, secp521r1()),
+        ];
+        for (hash, curve_params) in data {
+            let private_key = PrivateKey::new(BigInt::from(0xc0ffee), curve_params).unwrap();
+            let public_key = private_key.public_key();
+            let (signature, _) =
+                sign_with_options(&hash, &private_key, &not_strict_signing).unwrap();
+
+            assert_eq!(
+                verify_with_options(&hash, &signature, &public_key, &not_strict_verifying),
+                Ok(true)
+            );
+
+            // Only the leading bits matter.
+            if hash.len() * 8 > curve_params.base_point_order.bit_len() {
+                let mut hash_tail_modified = hash.clone();
+                *hash_tail_modified.last_mut().unwrap() ^= 1;
+                assert_eq!(
+                    verify_with_options(
+                        &hash_tail_modified,
+                        &signature,
+                        &public_key,
+                        &not_strict_verifying
+                    ),
+                    Ok(true)
+                );
+            }
+            let mut hash_head_modified = hash.clone();
+            hash_head_modified[0] ^= 1;
+            assert_eq!(
+                verify_with_options(
+                    &hash_head_modified,
+                    &signature,
+                    &public_key,
+                    &not_strict_verifying
+                ),
+                Ok(false)
+            );
+
+            // Strict
+            assert_eq!(
+                verify(&hash, &signature, &public_key),
+                Err(VerifyingError::HashBitLengthDoesNotMatchBasePointOrder)
+            );
+            assert_eq!(
+                sign_with_options(
+                    &hash,
+                    &private_key,
+                    &SigningOptions {
+                        employ_extra_random_data: false,
+                        ..Default::default()
+                    }
+                )
+                .unwrap_err(),
+                SigningError::HashBitLengthDoesNotMatchBasePointOrder
+            );
+        }
+    }
 }
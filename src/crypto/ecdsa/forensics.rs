@@ -0,0 +1,279 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Detects ECDSA signatures sharing a nonce, and recovers the private key from them.
+//!
+//! Two signatures of different hashes made with the same nonce `k` share `r`, and
+//! k = (h1 - h2) / (s1 - s2) mod n, from which d = (s * k - h) / r mod n.
+
+use super::ecdsa_core::{Signature, SignatureCanonicality};
+use super::ecdsa_key::PrivateKey;
+use crate::bigint::BigInt;
+use crate::math::modular::{invert, modulo};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+
+/// Returns the index pairs `(i, j)`, `i < j`, of the signatures in `signatures` of the same
+/// curve sharing `r`, thus most likely the nonce.
+///
+/// Each element of `signatures` is a signature and the hash it signs, truncated to the
+/// bit length of the base point order as in signing. The pairs are sorted.
+pub fn detect_shared_nonce(signatures: &[(Signature, BigInt)]) -> Vec<(usize, usize)> {
+    let mut groups: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+    for (i, (signature, _)) in signatures.iter().enumerate() {
+        groups.entry(signature.r.to_be_bytes()).or_default().push(i);
+    }
+
+    let mut pairs = vec![];
+    for indices in groups.values() {
+        for (position, &i) in indices.iter().enumerate() {
+            for &j in &indices[position + 1..] {
+                // The same r of different curves doesn't imply the same nonce.
+                if signatures[i].0.curve_params == signatures[j].0.curve_params {
+                    pairs.push((i, j));
+                }
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// Recovers the private key from two signatures made with the same nonce.
+///
+/// `hash1` and `hash2` are the hashes signed, truncated to the bit length of the base point
+/// order as in signing. One of the signatures may have its `s` negated, e.g., by the "low s"
+/// normalization.
+pub fn recover_private_key_from_nonce_reuse<'a>(
+    signature1: &Signature<'a>,
+    hash1: &BigInt,
+    signature2: &Signature,
+    hash2: &BigInt,
+) -> Result<PrivateKey<'a>, NonceReuseError> {
+    let curve_params = signature1.curve_params;
+    if curve_params != signature2.curve_params {
+        return Err(NonceReuseError::CurveMismatch);
+    }
+    // Out-of-range components, e.g., `s + n` of an unchecked signature, would make the
+    // comparisons below miss the degenerate cases.
+    if signature1.is_canonical() == SignatureCanonicality::OutOfRange
+        || signature2.is_canonical() == SignatureCanonicality::OutOfRange
+    {
+        return Err(NonceReuseError::OutOfRange);
+    }
+    if signature1.r != signature2.r {
+        return Err(NonceReuseError::DifferentR);
+    }
+
+    let n = &curve_params.base_point_order;
+    let s1 = &signature1.s;
+    let s2 = &signature2.s;
+    if s1 == s2 {
+        return Err(NonceReuseError::IdenticalS);
+    }
+    if modulo(&(s1 + s2), n).is_zero() {
+        return Err(NonceReuseError::NegatedS);
+    }
+
+    let hash_diff = modulo(&(hash1 - hash2), n);
+    if hash_diff.is_zero() {
+        return Err(NonceReuseError::InconsistentNonce);
+    }
+
+    // With the same k: s1 - s2 = (h1 - h2) / k.
+    // With k negated in one signature: s1 + s2 = (h1 - h2) / k.
+    for s_diff in [s1 - s2, s1 + s2] {
+        let s_diff_inverse = match invert(&s_diff, n) {
+            Some(inverse) => inverse,
+            None => continue,
+        };
+        let k = modulo(&(&hash_diff * s_diff_inverse), n);
+        let kg = curve_params.curve.mul_point(&curve_params.base_point, &k);
+        if modulo(&kg.x, n) != signature1.r {
+            continue;
+        }
+
+        // d = (s1 * k - h1) / r
+        let r_inverse = invert(&signature1.r, n).ok_or(NonceReuseError::InconsistentNonce)?;
+        let d = modulo(&((s1 * &k - hash1) * r_inverse), n);
+        return PrivateKey::new(d, curve_params).ok_or(NonceReuseError::InconsistentNonce);
+    }
+
+    Err(NonceReuseError::InconsistentNonce)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NonceReuseError {
+    CurveMismatch,
+    /// r or s of a signature isn't in `[1, n - 1]`, see [`Signature::is_canonical`].
+    OutOfRange,
+    DifferentR,
+    /// The signatures are the same, most likely of the same hash.
+    IdenticalS,
+    /// One signature is the other with `s` negated, most likely of the same hash.
+    NegatedS,
+    /// No nonce reproduces `r` for the hashes.
+    InconsistentNonce,
+}
+
+impl Display for NonceReuseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonceReuseError::CurveMismatch => write!(f, "signatures of different curves"),
+            NonceReuseError::OutOfRange => write!(f, "signature components out of range"),
+            NonceReuseError::DifferentR => write!(f, "signatures with different r"),
+            NonceReuseError::IdenticalS => write!(f, "signatures with the same s"),
+            NonceReuseError::NegatedS => write!(f, "signatures with s negated"),
+            NonceReuseError::InconsistentNonce => {
+                write!(f, "no shared nonce consistent with the signatures")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NonceReuseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::bigint_core::Sign;
+    use crate::crypto::ecdsa::{sign_with_options, SigningOptions};
+    use crate::crypto::hash::{Sha256, UnkeyedHash};
+    use crate::crypto::{secp256k1, secp256r1};
+
+    fn hash_n(message: &str) -> BigInt {
+        BigInt::from_be_bytes(&Sha256::new().digest(message), Sign::Positive)
+    }
+
+    #[test]
+    fn test_recover_from_nonce_reuse() {
+        for curve_params in [secp256k1(), secp256r1()] {
+            let private_key =
+                PrivateKey::new(BigInt::from(0x1234567890_u64), curve_params).unwrap();
+            let k = BigInt::from(0xdeadbeef_u64);
+            let h1 = hash_n("first");
+            let h2 = hash_n("second");
+            let h3 = hash_n("third");
+            let (signature1, _) = private_key.sign(&h1, &k).unwrap();
+            let (signature2, _) = private_key.sign(&h2, &k).unwrap();
+            let (signature3, _) = private_key.sign(&h3, &BigInt::from(7)).unwrap();
+
+            let signatures = [
+                (signature1.clone(), h1.clone()),
+                (signature3.clone(), h3.clone()),
+                (signature2.clone(), h2.clone()),
+            ];
+            assert_eq!(detect_shared_nonce(&signatures), [(0, 2)]);
+
+            let recovered =
+                recover_private_key_from_nonce_reuse(&signature1, &h1, &signature2, &h2)
+                    .unwrap();
+            assert_eq!(recovered.data, private_key.data);
+            assert_eq!(recovered.public_key(), private_key.public_key());
+
+            // "Low s" normalization of one of the signatures
+            let n = &curve_params.base_point_order;
+            let negated =
+                Signature::new(signature2.r.clone(), n - &signature2.s, curve_params).unwrap();
+            let recovered =
+                recover_private_key_from_nonce_reuse(&signature1, &h1, &negated, &h2).unwrap();
+            assert_eq!(recovered.data, private_key.data);
+
+            assert_eq!(
+                recover_private_key_from_nonce_reuse(&signature1, &h1, &signature3, &h3).err(),
+                Some(NonceReuseError::DifferentR)
+            );
+        }
+    }
+
+    #[test]
+    fn test_degenerate_cases() {
+        let curve_params = secp256k1();
+        let private_key = PrivateKey::new(BigInt::from(17), curve_params).unwrap();
+        let k = BigInt::from(99);
+        let h = hash_n("message");
+        let (signature, _) = private_key.sign(&h, &k).unwrap();
+
+        // The same message signed twice
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(&signature, &h, &signature, &h).err(),
+            Some(NonceReuseError::IdenticalS)
+        );
+
+        // The signature and its malleated copy
+        let n = &curve_params.base_point_order;
+        let negated =
+            Signature::new(signature.r.clone(), n - &signature.s, curve_params).unwrap();
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(&signature, &h, &negated, &h).err(),
+            Some(NonceReuseError::NegatedS)
+        );
+
+        // Different s of the same hash can't share a nonce.
+        let (other, _) = private_key.sign(&(&h + &BigInt::one()), &k).unwrap();
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(&signature, &h, &other, &h).err(),
+            Some(NonceReuseError::InconsistentNonce)
+        );
+
+        let other_curve = Signature {
+            r: signature.r.clone(),
+            s: signature.s.clone(),
+            curve_params: secp256r1(),
+        };
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(&signature, &h, &other_curve, &h).err(),
+            Some(NonceReuseError::CurveMismatch)
+        );
+
+        // s + n is s modulo n, but not the same s.
+        let unreduced =
+            Signature::new_unchecked(signature.r.clone(), &signature.s + n, curve_params);
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(&signature, &h, &unreduced, &h).err(),
+            Some(NonceReuseError::OutOfRange)
+        );
+        assert_eq!(
+            recover_private_key_from_nonce_reuse(
+                &unreduced,
+                &h,
+                &signature,
+                &(&h + &BigInt::one())
+            )
+            .err(),
+            Some(NonceReuseError::OutOfRange)
+        );
+
+        let signatures = [
+            (signature.clone(), h.clone()),
+            (other_curve, h.clone()),
+            (negated, h.clone()),
+        ];
+        assert_eq!(detect_shared_nonce(&signatures), [(0, 2)]);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rfc6979_signatures_never_share_nonce() {
+        let curve_params = secp256k1();
+        let private_key = PrivateKey::new(BigInt::from(0xc0ffee_u32), curve_params).unwrap();
+        let options = SigningOptions {
+            employ_extra_random_data: false,
+            ..Default::default()
+        };
+
+        let signatures: Vec<(Signature, BigInt)> = (0..10000)
+            .map(|i| {
+                let hash = Sha256::new().digest(format!("message {i}"));
+                let (signature, _) = sign_with_options(&hash, &private_key, &options).unwrap();
+                (signature, BigInt::from_be_bytes(&hash, Sign::Positive))
+            })
+            .collect();
+        assert!(detect_shared_nonce(&signatures).is_empty());
+    }
+}
@@ -5,7 +5,11 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use crate::bigint::BigInt;
+use crate::crypto::codecs::hex;
 use crate::math::elliptic_curve::{Curve, Point};
+use crate::math::modular::modulo;
+use std::fmt;
+use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct EllipticCurveParams {
@@ -40,23 +44,136 @@ pub(crate) trait EllipticCurveParamsEncoding {
         point: &Point,
         curve_params: &EllipticCurveParams,
         compressed: bool,
+    ) -> String {
+        Self::encode_point_with_case(point, curve_params, compressed, false)
+    }
+
+    /// Encodes `point` into hexadecimal,
+    /// in uppercase if `upper` is true and in lowercase otherwise.
+    fn encode_point_with_case(
+        point: &Point,
+        curve_params: &EllipticCurveParams,
+        compressed: bool,
+        upper: bool,
     ) -> String;
+
+    /// Decodes a point from its binary representation.
+    ///
+    /// The default implementation decodes the hexadecimal of `bytes` with `decode_point`.
+    fn decode_point_from_bytes(
+        bytes: &[u8],
+        curve_params: &EllipticCurveParams,
+    ) -> Result<Point, Box<dyn std::error::Error>> {
+        Self::decode_point(hex::encode_lower(bytes), curve_params)
+    }
+
+    /// Encodes `point` into its binary representation.
+    ///
+    /// The default implementation converts the hexadecimal from `encode_point` to bytes.
+    fn encode_point_to_bytes(
+        point: &Point,
+        curve_params: &EllipticCurveParams,
+        compressed: bool,
+    ) -> Vec<u8> {
+        hex::decode(Self::encode_point(point, curve_params, compressed)).unwrap()
+    }
 }
 
 impl EllipticCurveParams {
+    /// Creates the parameters, validating the base point and its order.
+    ///
+    /// Protects against invalid-curve attacks with hand-built parameters: the base point
+    /// must be a valid point of the curve, and [`Self::generator_order_product_check`]
+    /// must pass. The check is only done here, once; the bundled curves are built
+    /// directly.
+    pub fn new(
+        curve: Curve,
+        base_point: Point,
+        base_point_order: BigInt,
+        cofactor: u32,
+    ) -> Result<Self, CurveParamsError> {
+        let curve_params = EllipticCurveParams {
+            curve,
+            base_point,
+            base_point_order,
+            cofactor,
+        };
+
+        if curve_params.base_point_order <= 1 {
+            return Err(CurveParamsError::BasePointOrderMismatch);
+        }
+        if !curve_params.validate_point(&curve_params.base_point) {
+            return Err(CurveParamsError::InvalidBasePoint);
+        }
+        if !curve_params.generator_order_product_check() {
+            return Err(CurveParamsError::BasePointOrderMismatch);
+        }
+
+        Ok(curve_params)
+    }
+
+    /// Returns true if `n * G` is the point at infinity, where `G` is the base point and
+    /// `n` is its order.
+    ///
+    /// Multiples of the order also pass, [`Self::security_check`] checks that `n` is prime.
+    pub fn generator_order_product_check(&self) -> bool {
+        self.curve
+            .mul_point(&self.base_point, &self.base_point_order)
+            .is_identity_element()
+    }
+
+    /// Returns true if `point` is in the subgroup generated by the base point, that is,
+    /// `n * point` is the point at infinity, where `n` is the order of the base point.
+    ///
+    /// `point` must be a point of the curve. For curves with a cofactor of 1, e.g.,
+    /// secp256k1, every point of the curve is in the subgroup. For curves with a cofactor
+    /// h > 1, e.g., Ed25519 (h = 8), a point received from a peer may lie in a small
+    /// subgroup and leak bits of a secret scalar multiplied with it (small-subgroup
+    /// attacks): reject the point if it isn't in the subgroup, or map it into the
+    /// subgroup with [`Self::clear_cofactor`].
+    pub fn is_in_subgroup(&self, point: &Point) -> bool {
+        self.curve
+            .mul_point(point, &self.base_point_order)
+            .is_identity_element()
+    }
+
+    /// Returns `h * point`, where `h` is the cofactor, which is in the subgroup generated
+    /// by the base point for any point of the curve.
+    ///
+    /// Returns a copy of `point` for curves with a cofactor of 1, e.g., secp256k1.
+    pub fn clear_cofactor(&self, point: &Point) -> Point {
+        if self.cofactor == 1 {
+            return point.clone();
+        }
+
+        self.curve.mul_point(point, &BigInt::from(self.cofactor))
+    }
+
     /// Validates that `point` is legitimate in the curve.
     pub(crate) fn validate_point(&self, point: &Point) -> bool {
+        self.validate_point_detailed(point).is_ok()
+    }
+
+    /// Validates that `point` is legitimate in the curve, e.g., as a public key received
+    /// from a peer, returning the reason if it isn't.
+    ///
+    /// The checks are done in the order of the variants of [`PointValidationError`], only
+    /// the first failing one is reported.
+    pub fn validate_point_detailed(&self, point: &Point) -> Result<(), PointValidationError> {
         // For details see "An Illustrated Guide to Elliptic Curve Cryptography Validation"
         // https://research.nccgroup.com/2021/11/18/an-illustrated-guide-to-elliptic-curve-cryptography-validation/
 
+        // Checks that the point coordinates are in [0, p), the field modulus.
         let zero = BigInt::zero();
-        if point.x < zero || point.y < zero {
-            return false;
+        for (coordinate, which) in [(&point.x, Coordinate::X), (&point.y, Coordinate::Y)] {
+            if coordinate < &zero || coordinate >= &self.curve.p {
+                return Err(PointValidationError::CoordinateOutOfRange { which });
+            }
         }
 
-        // Checks that the point coordinates are lower than the field modulus.
-        if point.x >= self.curve.p || point.y >= self.curve.p {
-            return false;
+        // Checks that the point is not the point at infinity.
+        if point.is_identity_element() {
+            return Err(PointValidationError::IdentityElement);
         }
 
         // Checks that the coordinates correspond to a valid curve point.
@@ -66,29 +183,67 @@ impl EllipticCurveParams {
         let right = &point.x * &point.x * &point.x + &self.curve.a * &point.x + &self.curve.b;
         let right = self.curve.modulo(&right);
         if left != right {
-            return false;
+            return Err(PointValidationError::NotOnCurve);
         }
 
-        // Checks that the point is not the point at infinity.
-        if point.is_identity_element() {
-            return false;
+        // Checks that the point is in the correct subgroup.
+        if self.cofactor != 1 && !self.is_in_subgroup(point) {
+            return Err(PointValidationError::WrongSubgroup);
         }
 
-        // Checks that the point is in the correct subgroup.
-        if self.cofactor != 1 {
-            let np = self.curve.mul_point(point, &self.base_point_order);
-            if !np.is_identity_element() {
-                return false;
+        Ok(())
+    }
+
+    /// Checks the parameters against the basic attacks on weak curves.
+    ///
+    /// The curve order `h * n` must lie within the Hasse bound, `n` must be prime, the curve
+    /// must be neither anomalous (Smart's attack) nor supersingular, and its embedding
+    /// degree must be greater than `max_embedding_checks` (MOV and Frey–Rück attacks).
+    ///
+    /// Only the first failing condition is reported.
+    pub fn security_check(&self, max_embedding_checks: u32) -> Result<(), CurveSecurityError> {
+        let one = BigInt::one();
+        let p = &self.curve.p;
+        let n = &self.base_point_order;
+        let curve_order = n * &BigInt::from(self.cofactor);
+
+        // Hasse's theorem: |p + 1 - #E| <= 2 * sqrt(p)
+        let trace = p + &one - &curve_order;
+        if &trace * &trace > p * &BigInt::from(4) {
+            return Err(CurveSecurityError::OrderOutsideHasseBound);
+        }
+
+        if !n.is_baillie_psw_prime() {
+            return Err(CurveSecurityError::CompositeBasePointOrder);
+        }
+
+        // #E = p, the trace of Frobenius is 1.
+        if &curve_order == p {
+            return Err(CurveSecurityError::Anomalous);
+        }
+
+        // #E = p + 1, the trace of Frobenius is 0.
+        if trace.is_zero() {
+            return Err(CurveSecurityError::Supersingular);
+        }
+
+        // The embedding degree is the least k with p ^ k = 1 (mod n).
+        let p_mod_n = modulo(p, n);
+        let mut t = p_mod_n.clone();
+        for degree in 1..=max_embedding_checks {
+            if t == one {
+                return Err(CurveSecurityError::SmallEmbeddingDegree { degree });
             }
+            t = &t * &p_mod_n % n;
         }
 
-        true
+        Ok(())
     }
 
     // Concatenates x and y in byte representation.
-    // Both x and y are leading zero padded to the length of base point order in bytes.
+    // Both x and y are leading zero padded to the length of the field modulus in bytes.
     pub(crate) fn point_to_bytes(&self, point: &Point) -> Vec<u8> {
-        let element_byte_length = self.base_point_order.byte_len();
+        let element_byte_length = self.curve.p.byte_len();
         let mut data = Vec::with_capacity(element_byte_length * 2);
 
         let bytes = point.x.to_be_bytes();
@@ -103,11 +258,317 @@ impl EllipticCurveParams {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CurveParamsError {
+    /// The base point isn't a valid point of the curve.
+    InvalidBasePoint,
+    /// `n * G` isn't the point at infinity, `n` isn't the order of the base point `G`.
+    BasePointOrderMismatch,
+}
+
+impl Display for CurveParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveParamsError::InvalidBasePoint => {
+                write!(f, "base point not on the curve")
+            }
+            CurveParamsError::BasePointOrderMismatch => {
+                write!(f, "base point order mismatch")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CurveParamsError {}
+
+/// A coordinate of a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Coordinate {
+    X,
+    Y,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PointValidationError {
+    /// The coordinate `which` is negative, or not lower than the field modulus `p`.
+    CoordinateOutOfRange { which: Coordinate },
+    /// The point is the point at infinity.
+    IdentityElement,
+    /// The coordinates don't satisfy the equation of the curve.
+    NotOnCurve,
+    /// The point isn't in the subgroup generated by the base point.
+    WrongSubgroup,
+}
+
+impl Display for PointValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointValidationError::CoordinateOutOfRange {
+                which: Coordinate::X,
+            } => {
+                write!(f, "x out of the field range")
+            }
+            PointValidationError::CoordinateOutOfRange {
+                which: Coordinate::Y,
+            } => {
+                write!(f, "y out of the field range")
+            }
+            PointValidationError::IdentityElement => write!(f, "point at infinity"),
+            PointValidationError::NotOnCurve => write!(f, "point not on the curve"),
+            PointValidationError::WrongSubgroup => {
+                write!(f, "point not in the subgroup of the base point")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PointValidationError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CurveSecurityError {
+    /// `h * n` isn't a possible number of points of the curve.
+    OrderOutsideHasseBound,
+    /// The base point order `n` isn't prime.
+    CompositeBasePointOrder,
+    /// The curve has exactly `p` points.
+    Anomalous,
+    /// The curve has exactly `p + 1` points.
+    Supersingular,
+    /// The embedding degree is `degree`.
+    SmallEmbeddingDegree { degree: u32 },
+}
+
+impl Display for CurveSecurityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveSecurityError::OrderOutsideHasseBound => {
+                write!(f, "curve order outside the Hasse bound")
+            }
+            CurveSecurityError::CompositeBasePointOrder => {
+                write!(f, "base point order not prime")
+            }
+            CurveSecurityError::Anomalous => {
+                write!(f, "anomalous curve, the curve order equals p")
+            }
+            CurveSecurityError::Supersingular => {
+                write!(f, "supersingular curve, the curve order equals p + 1")
+            }
+            CurveSecurityError::SmallEmbeddingDegree { degree } => {
+                write!(f, "embedding degree {degree} too small")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CurveSecurityError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::{secp256k1, secp256r1, secp384r1, secp521r1};
     use crate::math::elliptic_curve::Curve;
 
+    fn toy_curve_params(
+        a: i32,
+        b: i32,
+        p: i32,
+        base_point: (i32, i32),
+        base_point_order: i32,
+        cofactor: u32,
+    ) -> EllipticCurveParams {
+        EllipticCurveParams {
+            curve: Curve {
+                a: BigInt::from(a),
+                b: BigInt::from(b),
+                p: BigInt::from(p),
+            },
+            base_point: Point {
+                x: BigInt::from(base_point.0),
+                y: BigInt::from(base_point.1),
+            },
+            base_point_order: BigInt::from(base_point_order),
+            cofactor,
+        }
+    }
+
+    fn new_from_toy_curve_params(
+        curve_params: EllipticCurveParams,
+    ) -> Result<EllipticCurveParams, CurveParamsError> {
+        EllipticCurveParams::new(
+            curve_params.curve,
+            curve_params.base_point,
+            curve_params.base_point_order,
+            curve_params.cofactor,
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        for curve_params in [secp256k1(), secp256r1()] {
+            assert!(curve_params.generator_order_product_check());
+            let new_curve_params = EllipticCurveParams::new(
+                Curve {
+                    a: curve_params.curve.a.clone(),
+                    b: curve_params.curve.b.clone(),
+                    p: curve_params.curve.p.clone(),
+                },
+                curve_params.base_point.clone(),
+                curve_params.base_point_order.clone(),
+                curve_params.cofactor,
+            )
+            .unwrap();
+            assert_eq!(&new_curve_params, curve_params);
+        }
+
+        // y^2 = x^3 + 2 * x + 2 mod 17, (5, 1) of order 19
+        assert!(new_from_toy_curve_params(toy_curve_params(2, 2, 17, (5, 1), 19, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_new_err_cases() {
+        let curve_params = secp256k1();
+        let new_with_order = |base_point_order: BigInt| {
+            EllipticCurveParams::new(
+                Curve {
+                    a: curve_params.curve.a.clone(),
+                    b: curve_params.curve.b.clone(),
+                    p: curve_params.curve.p.clone(),
+                },
+                curve_params.base_point.clone(),
+                base_point_order,
+                curve_params.cofactor,
+            )
+        };
+        let n = &curve_params.base_point_order;
+        for base_point_order in [
+            n - BigInt::one(),
+            n + BigInt::one(),
+            BigInt::zero(),
+            BigInt::one(),
+        ] {
+            assert_eq!(
+                new_with_order(base_point_order),
+                Err(CurveParamsError::BasePointOrderMismatch)
+            );
+        }
+
+        // y^2 = x^3 + 2 * x + 2 mod 17
+        // wrong order
+        assert_eq!(
+            new_from_toy_curve_params(toy_curve_params(2, 2, 17, (5, 1), 20, 1)),
+            Err(CurveParamsError::BasePointOrderMismatch)
+        );
+        // not a curve point
+        assert_eq!(
+            new_from_toy_curve_params(toy_curve_params(2, 2, 17, (1, 2), 19, 1)),
+            Err(CurveParamsError::InvalidBasePoint)
+        );
+        // the point at infinity
+        assert_eq!(
+            new_from_toy_curve_params(toy_curve_params(2, 2, 17, (0, 0), 19, 1)),
+            Err(CurveParamsError::InvalidBasePoint)
+        );
+    }
+
+    #[test]
+    fn test_security_check_bundled_curves() {
+        for curve_params in [secp256k1(), secp256r1(), secp384r1(), secp521r1()] {
+            assert_eq!(curve_params.security_check(1000), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_security_check_weak_curves() {
+        // (curve_params, error)
+        let data = [
+            // y^2 = x^3 + 2 * x + 2 mod 17, 19 points, with wrong orders
+            (
+                toy_curve_params(2, 2, 17, (5, 1), 100, 1),
+                CurveSecurityError::OrderOutsideHasseBound,
+            ),
+            (
+                toy_curve_params(2, 2, 17, (5, 1), 20, 1),
+                CurveSecurityError::CompositeBasePointOrder,
+            ),
+            // y^2 = x^3 + x + 1 mod 97, 97 points
+            (
+                toy_curve_params(1, 1, 97, (0, 1), 97, 1),
+                CurveSecurityError::Anomalous,
+            ),
+            // y^2 = x^3 + x mod 43, 44 points, embedding degree 2
+            (
+                toy_curve_params(1, 0, 43, (31, 18), 11, 4),
+                CurveSecurityError::Supersingular,
+            ),
+            // y^2 = x^3 + 4 * x + 2 mod 67, 62 points, embedding degree 3
+            (
+                toy_curve_params(4, 2, 67, (46, 38), 31, 2),
+                CurveSecurityError::SmallEmbeddingDegree { degree: 3 },
+            ),
+        ];
+        for (curve_params, error) in data {
+            assert!(curve_params.validate_point(&curve_params.base_point));
+            assert_eq!(curve_params.security_check(10), Err(error));
+        }
+
+        // The embedding degree beyond the bound passes the check.
+        let curve_params = toy_curve_params(4, 2, 67, (46, 38), 31, 2);
+        assert_eq!(curve_params.security_check(2), Ok(()));
+
+        // y^2 = x^3 + 2 * x + 2 mod 17 itself, embedding degree 9
+        let curve_params = toy_curve_params(2, 2, 17, (5, 1), 19, 1);
+        assert_eq!(curve_params.security_check(8), Ok(()));
+        assert_eq!(
+            curve_params.security_check(9),
+            Err(CurveSecurityError::SmallEmbeddingDegree { degree: 9 })
+        );
+    }
+
+    #[test]
+    fn test_is_in_subgroup_and_clear_cofactor_secp256k1() {
+        let curve_params = secp256k1();
+        let point = curve_params
+            .curve
+            .mul_point(&curve_params.base_point, &BigInt::from(12345));
+
+        for point in [&curve_params.base_point, &point] {
+            assert!(curve_params.validate_point(point));
+            assert!(curve_params.is_in_subgroup(point));
+            assert_eq!(&curve_params.clear_cofactor(point), point);
+        }
+        assert!(curve_params.is_in_subgroup(&Point::identity_element()));
+    }
+
+    #[test]
+    fn test_is_in_subgroup_and_clear_cofactor() {
+        // y^2 = x^3 + 4 * x + 2 mod 67, 62 points, (46, 38) of order 31, cofactor 2
+        let curve_params = toy_curve_params(4, 2, 67, (46, 38), 31, 2);
+        let point = |x: i32, y: i32| Point {
+            x: BigInt::from(x),
+            y: BigInt::from(y),
+        };
+
+        // (point, is_in_subgroup, clear_cofactor(point))
+        let data = [
+            (point(46, 38), true, point(11, 38)),
+            (point(10, 29), true, point(63, 49)),
+            (point(4, 22), false, point(46, 38)),
+            // of order 2
+            (point(23, 0), false, Point::identity_element()),
+        ];
+        for (point, is_in_subgroup, cleared) in data {
+            assert_eq!(curve_params.is_in_subgroup(&point), is_in_subgroup);
+            assert_eq!(curve_params.validate_point(&point), is_in_subgroup);
+
+            let point = curve_params.clear_cofactor(&point);
+            assert_eq!(point, cleared);
+            assert!(curve_params.is_in_subgroup(&point));
+        }
+    }
+
     #[test]
     fn test_validate_point() {
         // y^2 = x^3 + 2 * x + 2 mod 17
@@ -140,26 +601,90 @@ mod tests {
             cofactor: 2,
         };
 
-        // (x, y, is_validate)
+        let x_out_of_range = Err(PointValidationError::CoordinateOutOfRange {
+            which: Coordinate::X,
+        });
+        let y_out_of_range = Err(PointValidationError::CoordinateOutOfRange {
+            which: Coordinate::Y,
+        });
+        // (x, y, result)
         let data = [
-            (-1, 2, false), // negative
-            (1, -2, false),
-            (21, 2, false), // greater than the field modulus
-            (2, 21, false),
-            (17, 2, false),
-            (2, 17, false),
-            (1, 2, false), // not valid curve point
-            (0, 0, false), // is at infinity
-            (10, 11, true),
+            (-1, 2, x_out_of_range), // negative
+            (1, -2, y_out_of_range),
+            (21, 2, x_out_of_range), // greater than the field modulus
+            (2, 21, y_out_of_range),
+            (17, 2, x_out_of_range),
+            (2, 17, y_out_of_range),
+            (1, 2, Err(PointValidationError::NotOnCurve)), // not valid curve point
+            (0, 0, Err(PointValidationError::IdentityElement)), // is at infinity
+            (10, 11, Ok(())),
         ];
 
-        for (x, y, is_validate) in data {
+        for (x, y, result) in data {
             let point = Point {
                 x: BigInt::from(x),
                 y: BigInt::from(y),
             };
-            assert_eq!(curve_params.validate_point(&point), is_validate);
-            assert_eq!(curve_params2.validate_point(&point), is_validate);
+            assert_eq!(curve_params.validate_point_detailed(&point), result);
+            assert_eq!(curve_params2.validate_point_detailed(&point), result);
+            assert_eq!(curve_params.validate_point(&point), result.is_ok());
+        }
+
+        // y^2 = x^3 + 4 * x + 2 mod 67, (4, 22) isn't in the subgroup of order 31
+        let curve_params = toy_curve_params(4, 2, 67, (46, 38), 31, 2);
+        let point = Point {
+            x: BigInt::from(4),
+            y: BigInt::from(22),
+        };
+        assert_eq!(
+            curve_params.validate_point_detailed(&point),
+            Err(PointValidationError::WrongSubgroup)
+        );
+    }
+
+    #[test]
+    fn test_validate_point_detailed_secp256k1() {
+        let curve_params = secp256k1();
+        let p = &curve_params.curve.p;
+        let base_point = &curve_params.base_point;
+        let point = |x: &BigInt, y: &BigInt| Point {
+            x: x.clone(),
+            y: y.clone(),
+        };
+
+        // (point, result)
+        let data = [
+            (base_point.clone(), Ok(())),
+            // x + p and y + p are congruent to a point of the curve
+            (
+                point(&(&base_point.x + p), &base_point.y),
+                Err(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::X,
+                }),
+            ),
+            (
+                point(&base_point.x, &(&base_point.y + p)),
+                Err(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::Y,
+                }),
+            ),
+            (
+                point(&base_point.x, p),
+                Err(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::Y,
+                }),
+            ),
+            (
+                point(&base_point.x, &(&base_point.y + &BigInt::one())),
+                Err(PointValidationError::NotOnCurve),
+            ),
+            (
+                Point::identity_element(),
+                Err(PointValidationError::IdentityElement),
+            ),
+        ];
+        for (point, result) in data {
+            assert_eq!(curve_params.validate_point_detailed(&point), result);
         }
     }
 }
@@ -4,6 +4,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use alloc::vec::Vec;
+
 pub trait UnkeyedHash {
     const INPUT_BLOCK_BYTE_LENGTH: usize;
     const OUTPUT_BYTE_LENGTH: usize;
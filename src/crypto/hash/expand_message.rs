@@ -0,0 +1,227 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements `expand_message_xmd` (RFC 9380, 5.3.1.)
+use crate::crypto::hash::core::UnkeyedHash;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::iter::zip;
+
+/// Maximum byte length of a domain separation tag.
+pub const MAX_DST_BYTE_LENGTH: usize = 255;
+
+/// Expands `message` to `len_in_bytes` uniformly random bytes with the hash function
+/// of `hasher`, using the domain separation tag `dst`.
+///
+/// # Errors
+///
+/// Will return `ExpandMessageError::DstTooLong` if `dst` is longer than 255 bytes
+/// (RFC 9380, 5.3.3. describes how to derive a shorter one), and
+/// `ExpandMessageError::OutputTooLong` if `len_in_bytes` is greater than 255 times the
+/// output byte length of the hash function, or 65535.
+pub fn expand_message_xmd<T: AsRef<[u8]>, S: AsRef<[u8]>, H: UnkeyedHash>(
+    message: T,
+    dst: S,
+    len_in_bytes: usize,
+    hasher: &mut H,
+) -> Result<Vec<u8>, ExpandMessageError> {
+    let message = message.as_ref();
+    let dst = dst.as_ref();
+    if dst.len() > MAX_DST_BYTE_LENGTH {
+        return Err(ExpandMessageError::DstTooLong);
+    }
+
+    let b_in_bytes = H::OUTPUT_BYTE_LENGTH;
+    let ell = len_in_bytes.div_ceil(b_in_bytes);
+    if ell > 255 || len_in_bytes > u16::MAX as usize {
+        return Err(ExpandMessageError::OutputTooLong);
+    }
+
+    // DST_prime = DST || I2OSP(len(DST), 1)
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+
+    // msg_prime = Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime
+    let mut message_prime = vec![0; H::INPUT_BLOCK_BYTE_LENGTH];
+    message_prime.extend(message);
+    message_prime.extend((len_in_bytes as u16).to_be_bytes());
+    message_prime.push(0);
+    message_prime.extend(&dst_prime);
+    let b_0 = hasher.digest(&message_prime);
+
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    let mut b_i = vec![0; b_in_bytes];
+    for i in 1..=ell {
+        // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+        // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime)
+        let mut data: Vec<u8> = zip(&b_0, &b_i).map(|(a, b)| a ^ b).collect();
+        data.push(i as u8);
+        data.extend(&dst_prime);
+        b_i = hasher.digest(&data);
+        uniform_bytes.extend(&b_i);
+    }
+
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExpandMessageError {
+    DstTooLong,
+    OutputTooLong,
+}
+
+impl Display for ExpandMessageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ExpandMessageError::DstTooLong => {
+                write!(f, "domain separation tag longer than 255 bytes")
+            }
+            ExpandMessageError::OutputTooLong => {
+                write!(f, "requested output length too long for the hash function")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ExpandMessageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::hash::{Sha256, Sha512};
+
+    #[test]
+    fn test_expand_message_xmd_sha256() {
+        // RFC 9380, K.1. expand_message_xmd(SHA-256)
+        // (message, len_in_bytes, uniform_bytes_hex)
+        let dst = "QUUX-V01-CS02-with-expander-SHA256-128";
+        let q128 = format!("q128_{}", "q".repeat(128));
+        let a512 = format!("a512_{}", "a".repeat(512));
+        let data = [
+            (
+                "",
+                0x20,
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                "abc",
+                0x20,
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                "abcdef0123456789",
+                0x20,
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                q128.as_str(),
+                0x20,
+                "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+            ),
+            (
+                a512.as_str(),
+                0x20,
+                "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+            ),
+            (
+                "",
+                0x80,
+                concat!(
+                    "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe",
+                    "e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18",
+                    "eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc",
+                    "c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"
+                ),
+            ),
+            (
+                "abc",
+                0x80,
+                concat!(
+                    "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a",
+                    "647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635",
+                    "bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00",
+                    "058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"
+                ),
+            ),
+            (
+                "abcdef0123456789",
+                0x80,
+                concat!(
+                    "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9",
+                    "ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4b",
+                    "c95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be1",
+                    "4cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df"
+                ),
+            ),
+            (
+                q128.as_str(),
+                0x80,
+                concat!(
+                    "80be107d0884f0d881bb460322f0443d38bd222db8bd0b0a5312a6fedb49c1bb",
+                    "d88fd75d8b9a09486c60123dfa1d73c1cc3169761b17476d3c6b7cbbd727acd0",
+                    "e2c942f4dd96ae3da5de368d26b32286e32de7e5a8cb2949f866a0b80c58116b",
+                    "29fa7fabb3ea7d520ee603e0c25bcaf0b9a5e92ec6a1fe4e0391d1cdbce8c68a"
+                ),
+            ),
+            (
+                a512.as_str(),
+                0x80,
+                concat!(
+                    "546aff5444b5b79aa6148bd81728704c32decb73a3ba76e9e75885cad9def1d0",
+                    "6d6792f8a7d12794e90efed817d96920d728896a4510864370c207f99bd4a608",
+                    "ea121700ef01ed879745ee3e4ceef777eda6d9e5e38b90c86ea6fb0b36504ba4",
+                    "a45d22e86f6db5dd43d98a294bebb9125d5b794e9d2a81181066eb954966a487"
+                ),
+            ),
+        ];
+        let mut hasher = Sha256::new();
+        for (message, len_in_bytes, uniform_bytes_hex) in data {
+            let uniform_bytes =
+                expand_message_xmd(message, dst, len_in_bytes, &mut hasher).unwrap();
+            assert_eq!(hex::encode_lower(&uniform_bytes), uniform_bytes_hex);
+        }
+    }
+
+    #[test]
+    fn test_expand_message_xmd_lengths() {
+        let mut hasher = Sha256::new();
+        for len_in_bytes in [0, 1, 31, 32, 33, 96, 255 * 32] {
+            let bytes = expand_message_xmd("abc", "DST", len_in_bytes, &mut hasher).unwrap();
+            assert_eq!(bytes.len(), len_in_bytes);
+        }
+
+        // A shorter output isn't a prefix of a longer one: `len_in_bytes` is hashed.
+        let short = expand_message_xmd("abc", "DST", 32, &mut hasher).unwrap();
+        let long = expand_message_xmd("abc", "DST", 64, &mut hasher).unwrap();
+        assert_ne!(short, long[..32]);
+    }
+
+    #[test]
+    fn test_expand_message_xmd_errors() {
+        let mut hasher = Sha256::new();
+        assert_eq!(
+            expand_message_xmd("abc", "DST", 255 * 32 + 1, &mut hasher),
+            Err(ExpandMessageError::OutputTooLong)
+        );
+        assert_eq!(
+            expand_message_xmd("abc", [0x44; 256], 32, &mut hasher),
+            Err(ExpandMessageError::DstTooLong)
+        );
+        assert!(expand_message_xmd("abc", [0x44; 255], 32, &mut hasher).is_ok());
+
+        // The limit depends on the output length of the hash function.
+        let mut hasher = Sha512::new();
+        assert!(expand_message_xmd("abc", "DST", 255 * 32 + 1, &mut hasher).is_ok());
+        assert_eq!(
+            expand_message_xmd("abc", "DST", 255 * 64 + 1, &mut hasher),
+            Err(ExpandMessageError::OutputTooLong)
+        );
+    }
+}
@@ -6,9 +6,13 @@
 
 //! Implements HMAC (NIST’s FIPS 198-1 standard and RFC 2104)
 use crate::crypto::hash::core::UnkeyedHash;
-use std::borrow::Cow;
-use std::cmp::Ordering;
-use std::iter::zip;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Display;
+use core::iter::zip;
 
 pub fn hmac<T: AsRef<[u8]>, S: AsRef<[u8]>, H: UnkeyedHash>(
     key: T,
@@ -67,10 +71,50 @@ pub fn hmac<T: AsRef<[u8]>, S: AsRef<[u8]>, H: UnkeyedHash>(
     hasher.digest(&t)
 }
 
+/// Returns the leftmost `out_len` bytes of the HMAC, the truncated output of
+/// RFC 2104 section 5.
+///
+/// # Errors
+///
+/// Will return `HmacError::OutputTooLong` if `out_len` is greater than the output length
+/// of `H`.
+pub fn hmac_truncated<T: AsRef<[u8]>, S: AsRef<[u8]>, H: UnkeyedHash>(
+    key: T,
+    message: S,
+    hasher: &mut H,
+    out_len: usize,
+) -> Result<Vec<u8>, HmacError> {
+    if out_len > H::OUTPUT_BYTE_LENGTH {
+        return Err(HmacError::OutputTooLong);
+    }
+
+    let mut mac = hmac(key, message, hasher);
+    mac.truncate(out_len);
+    Ok(mac)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HmacError {
+    OutputTooLong,
+}
+
+impl Display for HmacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HmacError::OutputTooLong => {
+                write!(f, "requested output length too long for the hash function")
+            }
+        }
+    }
+}
+
+impl core::error::Error for HmacError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
     use crate::crypto::hash::{Sha256, Sha384, Sha512};
 
     #[test]
@@ -115,9 +159,9 @@ mod tests {
         ];
         let mut hasher = Sha256::new();
         for (key_hex, message, mac_hex) in data {
-            let key = hex_to_bytes(key_hex).unwrap();
+            let key = hex::decode(key_hex).unwrap();
             let result = hmac(key, message, &mut hasher);
-            assert_eq!(bytes_to_lower_hex(&result), mac_hex);
+            assert_eq!(hex::encode_lower(&result), mac_hex);
         }
 
         // https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/HMAC_SHA384.pdf
@@ -182,9 +226,9 @@ mod tests {
         ];
         let mut hasher = Sha384::new();
         for (key_hex, message, mac_hex) in data {
-            let key = hex_to_bytes(key_hex).unwrap();
+            let key = hex::decode(key_hex).unwrap();
             let result = hmac(key, message, &mut hasher);
-            assert_eq!(bytes_to_lower_hex(&result), mac_hex);
+            assert_eq!(hex::encode_lower(&result), mac_hex);
         }
 
         // https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Standards-and-Guidelines/documents/examples/HMAC_SHA512.pdf
@@ -254,9 +298,56 @@ mod tests {
         ];
         let mut hasher = Sha512::new();
         for (key_hex, message, mac_hex) in data {
-            let key = hex_to_bytes(key_hex).unwrap();
+            let key = hex::decode(key_hex).unwrap();
             let result = hmac(key, message, &mut hasher);
-            assert_eq!(bytes_to_lower_hex(&result), mac_hex);
+            assert_eq!(hex::encode_lower(&result), mac_hex);
         }
     }
+
+    #[test]
+    fn test_hmac_truncated_examples() {
+        // The examples "with truncated tag" of the NIST documents in `test_hmac_examples`,
+        // truncated to the stated tag lengths.
+        let key = hex::decode(concat!(
+            "00",
+            "0102030405060708090A0B0C0D0E0F101112131415161718",
+            "191A1B1C1D1E1F202122232425262728292A2B2C2D2E2F30"
+        ))
+        .unwrap();
+        let message = "Sample message for keylen<blocklen, with truncated tag";
+
+        let mac = hmac_truncated(&key, message, &mut Sha256::new(), 16).unwrap();
+        assert_eq!(hex::encode_lower(&mac), "27a8b157839efeac98df070b331d5936");
+
+        let mac = hmac_truncated(&key, message, &mut Sha384::new(), 32).unwrap();
+        assert_eq!(
+            hex::encode_lower(&mac),
+            "c48130d3df703dd7cdaa56800dfbd2ba2458320e6e1f98fec8ad9f57f43800df"
+        );
+
+        let mac = hmac_truncated(&key, message, &mut Sha512::new(), 32).unwrap();
+        assert_eq!(
+            hex::encode_lower(&mac),
+            "00f3e9a77bb0f06de15f160603e42b5028758808596664c03e1ab8fb2b076778"
+        );
+    }
+
+    #[test]
+    fn test_hmac_truncated_lengths() {
+        let mut hasher = Sha256::new();
+        let mac = hmac(b"key", b"message", &mut hasher);
+
+        assert_eq!(
+            hmac_truncated(b"key", b"message", &mut hasher, 32),
+            Ok(mac.clone())
+        );
+        assert_eq!(
+            hmac_truncated(b"key", b"message", &mut hasher, 0),
+            Ok(vec![])
+        );
+        assert_eq!(
+            hmac_truncated(b"key", b"message", &mut hasher, 33),
+            Err(HmacError::OutputTooLong)
+        );
+    }
 }
@@ -5,17 +5,21 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 mod core;
+mod expand_message;
 mod hmac;
 mod sha2;
 mod sha3;
 
 pub use self::core::UnkeyedHash;
-pub use hmac::hmac;
+pub use expand_message::{expand_message_xmd, ExpandMessageError, MAX_DST_BYTE_LENGTH};
+pub use hmac::{hmac, hmac_truncated, HmacError};
 pub use sha2::sha256::Sha256;
 pub use sha2::sha384_512::Sha384;
 pub use sha2::sha384_512::Sha512;
 pub use sha3::keccak256::Keccak256;
+pub use sha3::kmac::{kmac128, kmac256};
 pub use sha3::sha3_224::Sha3_224;
 pub use sha3::sha3_256::Sha3_256;
 pub use sha3::sha3_384::Sha3_384;
 pub use sha3::sha3_512::Sha3_512;
+pub use sha3::shake::{Shake128, Shake256};
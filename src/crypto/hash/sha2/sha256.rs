@@ -10,7 +10,9 @@
 use super::core::calculate_k;
 use super::core::rnd;
 use crate::crypto::hash::core::UnkeyedHash;
-use std::iter::zip;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::zip;
 
 pub struct Sha256 {
     // State
@@ -75,7 +77,7 @@ fn sha256_digest(message: &[u8], s: &mut [u32; 8], w: &mut [u32; 64]) -> Vec<u8>
     }
 
     // output
-    let mut digest = Vec::with_capacity(8 * std::mem::size_of::<u32>());
+    let mut digest = Vec::with_capacity(8 * core::mem::size_of::<u32>());
     for item in s {
         digest.extend(item.to_be_bytes());
     }
@@ -86,7 +88,7 @@ fn sha256_digest(message: &[u8], s: &mut [u32; 8], w: &mut [u32; 64]) -> Vec<u8>
 fn sha256_block_compression(block: &[u8], s: &mut [u32; 8], w: &mut [u32; 64]) {
     // Loads the 64-byte message block into w[0..15] in big-endian order
     for (u32_bytes, w_iter) in zip(
-        block.chunks_exact(std::mem::size_of::<u32>()),
+        block.chunks_exact(core::mem::size_of::<u32>()),
         w[..16].iter_mut(),
     ) {
         *w_iter = u32::from_be_bytes(u32_bytes.try_into().unwrap());
@@ -234,7 +236,7 @@ const S_SHA256: [u32; 8] = [
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use quickcheck::{Gen, QuickCheck};
     use rust_crypto_sha2::Digest;
 
@@ -259,7 +261,7 @@ mod tests {
         let mut sha256 = Sha256::new();
         for (message, digest_hex) in data {
             let digest = sha256.digest(message);
-            assert_eq!(bytes_to_lower_hex(&digest), digest_hex);
+            assert_eq!(hex::encode_lower(&digest), digest_hex);
         }
     }
 
@@ -276,7 +278,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            assert_eq!(bytes_to_lower_hex(&digest), bytes_to_lower_hex(&digest2))
+            assert_eq!(hex::encode_lower(&digest), hex::encode_lower(&digest2))
         }
     }
 
@@ -292,7 +294,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            bytes_to_lower_hex(&digest) == bytes_to_lower_hex(&digest2)
+            hex::encode_lower(&digest) == hex::encode_lower(&digest2)
         }
 
         QuickCheck::new()
@@ -10,7 +10,9 @@
 use super::core::calculate_k;
 use super::core::rnd;
 use crate::crypto::hash::core::UnkeyedHash;
-use std::iter::zip;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::zip;
 
 pub struct Sha384 {
     // State
@@ -41,7 +43,7 @@ impl UnkeyedHash for Sha384 {
     fn digest<T: AsRef<[u8]>>(&mut self, message: T) -> Vec<u8> {
         sha384_512_digest_core(message.as_ref(), &mut self.s, &mut self.w, &S_SHA384);
 
-        let mut digest = Vec::with_capacity(std::mem::size_of::<u64>() * 6);
+        let mut digest = Vec::with_capacity(core::mem::size_of::<u64>() * 6);
         for item in self.s.iter().take(6) {
             digest.extend(item.to_be_bytes());
         }
@@ -80,7 +82,7 @@ impl UnkeyedHash for Sha512 {
     fn digest<T: AsRef<[u8]>>(&mut self, message: T) -> Vec<u8> {
         sha384_512_digest_core(message.as_ref(), &mut self.s, &mut self.w, &S_SHA512);
 
-        let mut digest = Vec::with_capacity(std::mem::size_of::<u64>() * 8);
+        let mut digest = Vec::with_capacity(core::mem::size_of::<u64>() * 8);
         for item in self.s {
             digest.extend(item.to_be_bytes());
         }
@@ -131,7 +133,7 @@ fn sha384_512_digest_core(
 fn sha512_block_compression(block: &[u8], s: &mut [u64; 8], w: &mut [u64; 80]) {
     // Loads the 128-byte message block into w[0..15] in big-endian order
     for (u64_bytes, w_iter) in zip(
-        block.chunks_exact(std::mem::size_of::<u64>()),
+        block.chunks_exact(core::mem::size_of::<u64>()),
         w[..16].iter_mut(),
     ) {
         *w_iter = u64::from_be_bytes(u64_bytes.try_into().unwrap());
@@ -312,7 +314,7 @@ const S_SHA512: [u64; 8] = [
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::bytes_to_lower_hex;
+    use crate::crypto::codecs::hex;
     use quickcheck::{Gen, QuickCheck};
     use rust_crypto_sha2::Digest;
 
@@ -337,7 +339,7 @@ mod tests {
         let mut sha384 = Sha384::new();
         for (message, digest_hex) in data {
             let digest = sha384.digest(message);
-            assert_eq!(bytes_to_lower_hex(&digest), digest_hex);
+            assert_eq!(hex::encode_lower(&digest), digest_hex);
         }
     }
 
@@ -354,7 +356,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            assert_eq!(bytes_to_lower_hex(&digest), bytes_to_lower_hex(&digest2))
+            assert_eq!(hex::encode_lower(&digest), hex::encode_lower(&digest2))
         }
     }
 
@@ -370,7 +372,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            bytes_to_lower_hex(&digest) == bytes_to_lower_hex(&digest2)
+            hex::encode_lower(&digest) == hex::encode_lower(&digest2)
         }
 
         QuickCheck::new()
@@ -400,7 +402,7 @@ mod tests {
         let mut sha512 = Sha512::new();
         for (message, digest_hex) in data {
             let digest = sha512.digest(message);
-            assert_eq!(bytes_to_lower_hex(&digest), digest_hex);
+            assert_eq!(hex::encode_lower(&digest), digest_hex);
         }
     }
 
@@ -417,7 +419,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            assert_eq!(bytes_to_lower_hex(&digest), bytes_to_lower_hex(&digest2))
+            assert_eq!(hex::encode_lower(&digest), hex::encode_lower(&digest2))
         }
     }
 
@@ -433,7 +435,7 @@ mod tests {
             hasher.update(&bytes);
             let digest2 = hasher.finalize();
 
-            bytes_to_lower_hex(&digest) == bytes_to_lower_hex(&digest2)
+            hex::encode_lower(&digest) == hex::encode_lower(&digest2)
         }
 
         QuickCheck::new()
@@ -11,19 +11,40 @@
 /// exchanging readability and code size for performance.
 ///
 /// [1]: https://github.com/mjosaarinen/tiny_sha3
-use std::iter::zip;
-use std::mem::size_of;
+use alloc::vec::Vec;
+use core::iter::zip;
+use core::mem::size_of;
 
 pub(crate) fn sha3_digest(
     message: &[u8],
     s: &mut KeccakfState,
     output_byte_size: usize,
     delimiter_suffix: u8,
+) -> Vec<u8> {
+    let rate_byte_size = KECCAKF_WIDTH_BYTE_SIZE - 2 * output_byte_size;
+    keccak_sponge(
+        message,
+        s,
+        rate_byte_size,
+        output_byte_size,
+        delimiter_suffix,
+    )
+}
+
+/// Absorbs `message` into the sponge of rate `rate_byte_size`, and squeezes
+/// `output_byte_size` bytes out of it, running the permutation again whenever a block of
+/// `rate_byte_size` bytes has been read.
+pub(crate) fn keccak_sponge(
+    message: &[u8],
+    s: &mut KeccakfState,
+    rate_byte_size: usize,
+    output_byte_size: usize,
+    delimiter_suffix: u8,
 ) -> Vec<u8> {
     s.fill(0);
 
-    let rate_byte_size = KECCAKF_WIDTH_BYTE_SIZE - 2 * output_byte_size;
     debug_assert!(rate_byte_size % size_of::<u64>() == 0);
+    debug_assert!(rate_byte_size < KECCAKF_WIDTH_BYTE_SIZE);
 
     // Handles "complete" chunks(blocks).
     let mut chunks = message.chunks_exact(rate_byte_size);
@@ -54,8 +75,18 @@ pub(crate) fn sha3_digest(
     let s: &mut KeccakfState = unsafe { core::mem::transmute(s_bytes) };
     sha3_keccakf(s);
 
-    let s_bytes: &mut [u8; KECCAKF_WIDTH_BYTE_SIZE] = unsafe { core::mem::transmute(s) };
-    s_bytes[..output_byte_size].to_vec()
+    let mut output = Vec::with_capacity(output_byte_size);
+    loop {
+        let s_bytes: &[u8; KECCAKF_WIDTH_BYTE_SIZE] = unsafe { core::mem::transmute(&*s) };
+        let len = rate_byte_size.min(output_byte_size - output.len());
+        output.extend_from_slice(&s_bytes[..len]);
+        if output.len() == output_byte_size {
+            break;
+        }
+        sha3_keccakf(s);
+    }
+
+    output
 }
 
 fn sha3_keccakf(s: &mut KeccakfState) {
@@ -154,6 +185,8 @@ pub(crate) const KECCAKF_ROUNDS: usize = 24;
 
 pub(crate) const KECCAK_DELIMITER_SUFFIX_KECCAK: u8 = 0x01;
 pub(crate) const KECCAK_DELIMITER_SUFFIX_SHA3: u8 = 0x06;
+pub(crate) const KECCAK_DELIMITER_SUFFIX_SHAKE: u8 = 0x1f;
+pub(crate) const KECCAK_DELIMITER_SUFFIX_CSHAKE: u8 = 0x04;
 
 #[rustfmt::skip]
 const KECCAKF_RNDC: [u64; 24] = [
@@ -241,7 +274,7 @@ use chi_iteration;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+    use crate::crypto::codecs::hex;
 
     #[test]
     fn test_basic() {
@@ -306,20 +339,20 @@ mod tests {
         let mut s = [0_64; 25];
         for (message, output_byte_size, sha3_digest_hex, keccak_digest_hex) in data {
             let digest = sha3_digest(
-                &hex_to_bytes(message).unwrap(),
+                &hex::decode(message).unwrap(),
                 &mut s,
                 output_byte_size,
                 KECCAK_DELIMITER_SUFFIX_SHA3,
             );
-            assert_eq!(bytes_to_lower_hex(&digest), sha3_digest_hex);
+            assert_eq!(hex::encode_lower(&digest), sha3_digest_hex);
 
             let digest = sha3_digest(
-                &hex_to_bytes(message).unwrap(),
+                &hex::decode(message).unwrap(),
                 &mut s,
                 output_byte_size,
                 KECCAK_DELIMITER_SUFFIX_KECCAK,
             );
-            assert_eq!(bytes_to_lower_hex(&digest), keccak_digest_hex);
+            assert_eq!(hex::encode_lower(&digest), keccak_digest_hex);
         }
     }
 }
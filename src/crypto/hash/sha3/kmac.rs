@@ -0,0 +1,164 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements KMAC128 and KMAC256 (NIST SP 800-185)
+
+use super::core::{keccak_sponge, KECCAK_DELIMITER_SUFFIX_CSHAKE};
+use super::shake::{Shake128, Shake256};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns the `output_len`-byte KMAC128 of `message` under `key`, with the optional
+/// customization string `custom`.
+pub fn kmac128(key: &[u8], message: &[u8], custom: &[u8], output_len: usize) -> Vec<u8> {
+    kmac(key, message, custom, output_len, Shake128::RATE_BYTE_LENGTH)
+}
+
+/// Returns the `output_len`-byte KMAC256 of `message` under `key`, with the optional
+/// customization string `custom`.
+pub fn kmac256(key: &[u8], message: &[u8], custom: &[u8], output_len: usize) -> Vec<u8> {
+    kmac(key, message, custom, output_len, Shake256::RATE_BYTE_LENGTH)
+}
+
+fn kmac(
+    key: &[u8],
+    message: &[u8],
+    custom: &[u8],
+    output_len: usize,
+    rate_byte_size: usize,
+) -> Vec<u8> {
+    // cSHAKE(bytepad(encode_string(K), rate) || X || right_encode(L), L, "KMAC", S)
+    //
+    // cSHAKE(X, L, N, S) = KECCAK[c](bytepad(encode_string(N) || encode_string(S), rate)
+    // || X || 00, L), with N and S never both empty here.
+    let mut prefix = encode_string(b"KMAC");
+    prefix.extend(encode_string(custom));
+
+    let mut t = bytepad(&prefix, rate_byte_size);
+    t.extend(bytepad(&encode_string(key), rate_byte_size));
+    t.extend(message);
+    t.extend(right_encode(output_len as u64 * 8));
+
+    keccak_sponge(
+        &t,
+        &mut [0; 25],
+        rate_byte_size,
+        output_len,
+        KECCAK_DELIMITER_SUFFIX_CSHAKE,
+    )
+}
+
+/// Encodes `x` as the byte length of its big-endian representation, followed by the
+/// representation itself.
+fn left_encode(x: u64) -> Vec<u8> {
+    let bytes = minimal_be_bytes(x);
+    let mut encoded = vec![bytes.len() as u8];
+    encoded.extend(bytes);
+    encoded
+}
+
+/// Encodes `x` as its big-endian representation, followed by the byte length of the
+/// representation.
+fn right_encode(x: u64) -> Vec<u8> {
+    let mut encoded = minimal_be_bytes(x);
+    encoded.push(encoded.len() as u8);
+    encoded
+}
+
+/// Returns the big-endian representation of `x`, at least one byte long.
+fn minimal_be_bytes(x: u64) -> Vec<u8> {
+    let bytes = x.to_be_bytes();
+    let leading_zeros = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    bytes[leading_zeros..].to_vec()
+}
+
+/// Prefixes `s` with its bit length.
+fn encode_string(s: &[u8]) -> Vec<u8> {
+    let mut encoded = left_encode(s.len() as u64 * 8);
+    encoded.extend(s);
+    encoded
+}
+
+/// Prefixes `x` with `left_encode(w)`, and pads the result with zeros to a multiple of `w`
+/// bytes.
+fn bytepad(x: &[u8], w: usize) -> Vec<u8> {
+    let mut padded = left_encode(w as u64);
+    padded.extend(x);
+    padded.resize(padded.len().div_ceil(w) * w, 0);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(left_encode(0), [1, 0]);
+        assert_eq!(left_encode(168), [1, 168]);
+        assert_eq!(left_encode(0x0100), [2, 1, 0]);
+        assert_eq!(right_encode(0), [0, 1]);
+        assert_eq!(right_encode(256), [1, 0, 2]);
+        assert_eq!(encode_string(b""), [1, 0]);
+        assert_eq!(encode_string(b"KMAC"), [1, 32, b'K', b'M', b'A', b'C']);
+
+        let padded = bytepad(&[0xff; 5], 8);
+        assert_eq!(padded, [1, 8, 0xff, 0xff, 0xff, 0xff, 0xff, 0]);
+        assert_eq!(bytepad(&[0xff; 6], 8).len(), 8);
+        assert_eq!(bytepad(&[0xff; 7], 8).len(), 16);
+    }
+
+    #[test]
+    fn test_kmac_samples() {
+        // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+        // KMAC_samples.pdf
+        let key: Vec<u8> = (0x40..0x60).collect();
+        let short_message: Vec<u8> = (0x00..0x04).collect();
+        let long_message: Vec<u8> = (0x00..=0xc7).collect();
+        let custom = b"My Tagged Application";
+
+        // Sample #1
+        assert_eq!(
+            hex::encode_lower(&kmac128(&key, &short_message, b"", 32)),
+            "e5780b0d3ea6f7d3a429c5706aa43a00fadbd7d49628839e3187243f456ee14e"
+        );
+        // Sample #2
+        assert_eq!(
+            hex::encode_lower(&kmac128(&key, &short_message, custom, 32)),
+            "3b1fba963cd8b0b59e8c1a6d71888b7143651af8ba0a7070c0979e2811324aa5"
+        );
+        // Sample #3
+        assert_eq!(
+            hex::encode_lower(&kmac128(&key, &long_message, custom, 32)),
+            "1f5b4e6cca02209e0dcb5ca635b89a15e271ecc760071dfd805faa38f9729230"
+        );
+        // Sample #4
+        assert_eq!(
+            hex::encode_lower(&kmac256(&key, &short_message, custom, 64)),
+            concat!(
+                "20c570c31346f703c9ac36c61c03cb64c3970d0cfc787e9b79599d273a68d2f7",
+                "f69d4cc3de9d104a351689f27cf6f5951f0103f33f4f24871024d9c27773a8dd"
+            )
+        );
+        // Sample #5
+        assert_eq!(
+            hex::encode_lower(&kmac256(&key, &long_message, b"", 64)),
+            concat!(
+                "75358cf39e41494e949707927cee0af20a3ff553904c86b08f21cc414bcfd691",
+                "589d27cf5e15369cbbff8b9a4c2eb17800855d0235ff635da82533ec6b759b69"
+            )
+        );
+        // Sample #6
+        assert_eq!(
+            hex::encode_lower(&kmac256(&key, &long_message, custom, 64)),
+            concat!(
+                "b58618f71f92e1d56c1b8c55ddd7cd188b97b4ca4d99831eb2699a837da2e4d9",
+                "70fbacfde50033aea585f1a2708510c32d07880801bd182898fe476876fc8965"
+            )
+        );
+    }
+}
@@ -6,7 +6,9 @@
 
 mod core;
 pub mod keccak256;
+pub mod kmac;
 pub mod sha3_224;
 pub mod sha3_256;
 pub mod sha3_384;
 pub mod sha3_512;
+pub mod shake;
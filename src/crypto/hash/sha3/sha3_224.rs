@@ -8,6 +8,7 @@ use super::core::sha3_digest;
 use super::core::KeccakfState;
 use crate::crypto::hash::sha3::core::KECCAK_DELIMITER_SUFFIX_SHA3;
 use crate::crypto::hash::UnkeyedHash;
+use alloc::vec::Vec;
 
 pub struct Sha3_224 {
     s: KeccakfState,
@@ -0,0 +1,116 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements the SHAKE128 and SHAKE256 extendable-output functions (FIPS PUB 202).
+
+use super::core::{keccak_sponge, KeccakfState, KECCAK_DELIMITER_SUFFIX_SHAKE};
+use alloc::vec::Vec;
+
+macro_rules! impl_shake {
+    ($name:ident, $rate_byte_size:literal) => {
+        pub struct $name {
+            s: KeccakfState,
+        }
+
+        impl $name {
+            // `200 - 2 * security strength / u8::BITS`
+            pub(crate) const RATE_BYTE_LENGTH: usize = $rate_byte_size;
+
+            pub fn new() -> $name {
+                $name { s: [0; 25] }
+            }
+
+            /// Returns `output_len` bytes derived from `message`.
+            ///
+            /// A longer output for the same `message` extends a shorter one.
+            pub fn digest_xof(&mut self, message: &[u8], output_len: usize) -> Vec<u8> {
+                keccak_sponge(
+                    message,
+                    &mut self.s,
+                    Self::RATE_BYTE_LENGTH,
+                    output_len,
+                    KECCAK_DELIMITER_SUFFIX_SHAKE,
+                )
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+impl_shake!(Shake128, 168);
+impl_shake!(Shake256, 136);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn test_shake_examples() {
+        // https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+        let empty: &[u8] = &[];
+        assert_eq!(
+            hex::encode_lower(&Shake128::new().digest_xof(empty, 32)),
+            "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+        );
+        assert_eq!(
+            hex::encode_lower(&Shake256::new().digest_xof(empty, 64)),
+            concat!(
+                "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f",
+                "d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+            )
+        );
+
+        // 1600-bit message of 0xA3, 4096-bit output, squeezed over several blocks.
+        // (first 32 bytes, last 32 bytes)
+        let message = [0xa3; 200];
+        let digest = Shake128::new().digest_xof(&message, 512);
+        assert_eq!(
+            hex::encode_lower(&digest[..32]),
+            "131ab8d2b594946b9c81333f9bb6e0ce75c3b93104fa3469d3917457385da037"
+        );
+        assert_eq!(
+            hex::encode_lower(&digest[480..]),
+            "44c9fb359fd56ac0a9a75a743cff6862f17d7259ab075216c0699511643b6439"
+        );
+        let digest = Shake256::new().digest_xof(&message, 512);
+        assert_eq!(
+            hex::encode_lower(&digest[..32]),
+            "cd8a920ed141aa0407a22d59288652e9d9f1a7ee0c1e7c1ca699424da84a904d"
+        );
+        assert_eq!(
+            hex::encode_lower(&digest[480..]),
+            "6a1a9d7846436e4dca5728b6f760eef0ca92bf0be5615e96959d767197a0beeb"
+        );
+    }
+
+    #[quickcheck]
+    fn longer_output_extends_shorter(message: Vec<u8>, len1: u16, len2: u16) -> bool {
+        let (short_len, long_len) = if len1 < len2 {
+            (len1 as usize, len2 as usize)
+        } else {
+            (len2 as usize, len1 as usize)
+        };
+
+        let mut hasher = Shake128::new();
+        let short = hasher.digest_xof(&message, short_len);
+        let long = hasher.digest_xof(&message, long_len);
+        if long.len() != long_len || long[..short_len] != short {
+            return false;
+        }
+
+        let mut hasher = Shake256::new();
+        let short = hasher.digest_xof(&message, short_len);
+        let long = hasher.digest_xof(&message, long_len);
+        long.len() == long_len && long[..short_len] == short
+    }
+}
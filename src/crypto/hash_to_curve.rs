@@ -0,0 +1,244 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements hashing to curve secp256k1 with the suite "secp256k1_XMD:SHA-256_SSWU_RO_".
+//!
+//! See: RFC 9380 Hashing to Elliptic Curves
+//! https://www.rfc-editor.org/rfc/rfc9380.html
+
+use super::hash::{expand_message_xmd, Sha256};
+use super::secp256k1::secp256k1;
+use crate::bigint::{bigint_const, BigInt, Sign};
+use crate::math::elliptic_curve::Point;
+use crate::math::modular::{batch_invert, invert, modulo, sqrt};
+
+/// Byte length of a field element produced by `hash_to_field`: ceil((ceil(log2(p)) + k) / 8),
+/// where k = 128 is the security level (RFC 9380, 5.)
+const FIELD_ELEMENT_EXPANDED_BYTE_LENGTH: usize = 48;
+
+/// Hashes `message` to a point of secp256k1, with the domain separation tag `dst`.
+///
+/// The result is indistinguishable from a random point (random oracle encoding).
+///
+/// This function will panic if `dst` is longer than 255 bytes.
+pub fn hash_to_curve(message: &[u8], dst: &[u8]) -> Point {
+    let curve = &secp256k1().curve;
+
+    let [u0, u1] = hash_to_field(message, dst);
+    let q0 = isogeny_map(&map_to_curve_simple_swu(&u0));
+    let q1 = isogeny_map(&map_to_curve_simple_swu(&u1));
+
+    // The cofactor of secp256k1 is 1, clearing it is a no-op.
+    curve.add_points(&q0, &q1)
+}
+
+/// Hashes `message` to two elements of the field of secp256k1 (RFC 9380, 5.2.)
+fn hash_to_field(message: &[u8], dst: &[u8]) -> [BigInt; 2] {
+    let p = &secp256k1().curve.p;
+    let uniform_bytes = expand_message_xmd(
+        message,
+        dst,
+        2 * FIELD_ELEMENT_EXPANDED_BYTE_LENGTH,
+        &mut Sha256::new(),
+    )
+    .expect("DST too long");
+
+    let mut elements = uniform_bytes
+        .chunks(FIELD_ELEMENT_EXPANDED_BYTE_LENGTH)
+        .map(|bytes| modulo(&BigInt::from_be_bytes(bytes, Sign::Positive), p));
+    [elements.next().unwrap(), elements.next().unwrap()]
+}
+
+/// The curve E': y^2 = x^3 + A' * x + B', 3-isogenous to secp256k1,
+/// and the coefficients of the isogeny map from E' to secp256k1 (RFC 9380, E.1.)
+struct IsogenousCurveParams {
+    a: BigInt,
+    b: BigInt,
+    z: BigInt,
+    x_num: [BigInt; 4],
+    x_den: [BigInt; 3],
+    y_num: [BigInt; 4],
+    y_den: [BigInt; 4],
+}
+
+static ISOGENOUS_CURVE_PARAMS: IsogenousCurveParams = IsogenousCurveParams {
+    a: bigint_const!("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"),
+    // 1771
+    b: bigint_const!("06eb"),
+    // p - 11
+    z: bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc24"),
+    // k_(1,0), k_(1,1), k_(1,2), k_(1,3)
+    x_num: [
+        bigint_const!("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7"),
+        bigint_const!("07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581"),
+        bigint_const!("534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262"),
+        bigint_const!("8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c"),
+    ],
+    // k_(2,0), k_(2,1), 1
+    x_den: [
+        bigint_const!("d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b"),
+        bigint_const!("edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14"),
+        bigint_const!("01"),
+    ],
+    // k_(3,0), k_(3,1), k_(3,2), k_(3,3)
+    y_num: [
+        bigint_const!("4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c"),
+        bigint_const!("c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3"),
+        bigint_const!("29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931"),
+        bigint_const!("2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84"),
+    ],
+    // k_(4,0), k_(4,1), k_(4,2), 1
+    y_den: [
+        bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b"),
+        bigint_const!("7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573"),
+        bigint_const!("6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f"),
+        bigint_const!("01"),
+    ],
+};
+
+/// Maps the field element `u` to a point of E' with the Simplified
+/// Shallue-van de Woestijne-Ulas method (RFC 9380, 6.6.2.)
+fn map_to_curve_simple_swu(u: &BigInt) -> Point {
+    let params = &ISOGENOUS_CURVE_PARAMS;
+    let p = &secp256k1().curve.p;
+    let g = |x: &BigInt| modulo(&(x * x * x + &params.a * x + &params.b), p);
+
+    // tv1 = inv0(Z^2 * u^4 + Z * u^2)
+    let z_u2 = modulo(&(&params.z * u * u), p);
+    let tv1 = invert(&(&z_u2 * &z_u2 + &z_u2), p).unwrap_or_else(BigInt::zero);
+
+    // x1 = (-B / A) * (1 + tv1), or B / (Z * A) if tv1 is 0
+    let x1 = if tv1.is_zero() {
+        &params.b * invert(&(&params.z * &params.a), p).unwrap()
+    } else {
+        -&params.b * invert(&params.a, p).unwrap() * (BigInt::one() + &tv1)
+    };
+    let x1 = modulo(&x1, p);
+
+    let (x, y) = match square_root(&g(&x1), p) {
+        Some(y1) => (x1, y1),
+        None => {
+            // x2 = Z * u^2 * x1, g(x2) is square if g(x1) isn't.
+            let x2 = modulo(&(&z_u2 * &x1), p);
+            let y2 = square_root(&g(&x2), p).unwrap();
+            (x2, y2)
+        }
+    };
+
+    // The sign of y matches the one of u: sgn0(u) == sgn0(y)
+    let y = if u.is_odd() != y.is_odd() {
+        modulo(&-y, p)
+    } else {
+        y
+    };
+    Point { x, y }
+}
+
+/// Maps the point `point` of E' to secp256k1 (RFC 9380, E.1.)
+fn isogeny_map(point: &Point) -> Point {
+    let params = &ISOGENOUS_CURVE_PARAMS;
+    let p = &secp256k1().curve.p;
+
+    // Evaluates the polynomial of the coefficients `k` (in ascending order) at x'.
+    let evaluate = |k: &[BigInt]| {
+        k.iter().rev().fold(BigInt::zero(), |acc, k_i| {
+            modulo(&(acc * &point.x + k_i), p)
+        })
+    };
+
+    // Both denominators are inverted at once. A zero denominator (x' is the one of a
+    // point in the kernel of the isogeny) maps to the identity element (RFC 9380, 6.6.3.)
+    let mut denominators = [evaluate(&params.x_den), evaluate(&params.y_den)];
+    if batch_invert(&mut denominators, p).is_err() {
+        return Point::identity_element();
+    }
+    let [x_den_inverse, y_den_inverse] = denominators;
+
+    let x = evaluate(&params.x_num) * x_den_inverse;
+    let y = &point.y * evaluate(&params.y_num) * y_den_inverse;
+    Point {
+        x: modulo(&x, p),
+        y: modulo(&y, p),
+    }
+}
+
+/// Returns one of the square roots of `a` modulo `p`, None if `a` isn't a square.
+fn square_root(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+    if a.is_zero() {
+        return Some(BigInt::zero());
+    }
+    sqrt(a, p).map(|(root, _)| root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isogenous_curve_params() {
+        let p = &secp256k1().curve.p;
+        assert_eq!(ISOGENOUS_CURVE_PARAMS.b, BigInt::from(1771));
+        assert_eq!(ISOGENOUS_CURVE_PARAMS.z, p - BigInt::from(11));
+        assert_eq!(ISOGENOUS_CURVE_PARAMS.x_den[2], BigInt::one());
+        assert_eq!(ISOGENOUS_CURVE_PARAMS.y_den[3], BigInt::one());
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        // RFC 9380, J.8.1. secp256k1_XMD:SHA-256_SSWU_RO_
+        const DST: &[u8] = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+        // (msg, u0, u1, P.x, P.y)
+        let data = [
+            (
+                "",
+                "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+            (
+                "abc",
+                "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+                "5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+            (
+                "abcdef0123456789",
+                "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+                "7434d0d1a500d38380d1f9615c021857ac8d546925f5f2355319d823a478da18",
+                "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            ),
+        ];
+        for (message, u0_hex, u1_hex, x_hex, y_hex) in data {
+            let [u0, u1] = hash_to_field(message.as_bytes(), DST);
+            assert_eq!(u0.to_lower_hex(), u0_hex);
+            assert_eq!(u1.to_lower_hex(), u1_hex);
+
+            let point = hash_to_curve(message.as_bytes(), DST);
+            assert_eq!(point.x, BigInt::from_hex(x_hex).unwrap());
+            assert_eq!(point.y, BigInt::from_hex(y_hex).unwrap());
+            assert!(secp256k1().validate_point(&point));
+        }
+    }
+
+    #[test]
+    fn test_isogeny_map_zero_denominator() {
+        // x_den = x'^2 + k_(2,1) * x' + k_(2,0) = (x' + k_(2,1) / 2)^2
+        let params = &ISOGENOUS_CURVE_PARAMS;
+        let p = &secp256k1().curve.p;
+        let x = modulo(
+            &(-&params.x_den[1] * invert(&BigInt::from(2), p).unwrap()),
+            p,
+        );
+        let point = Point {
+            x,
+            y: BigInt::one(),
+        };
+        assert!(isogeny_map(&point).is_identity_element());
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Converts digests to scalars, integers modulo the order of a group.
+//!
+//! - `truncate_to_scalar`: keeps the leading bits of the digest, as ECDSA does (SEC1).
+//! - `reduce_to_scalar`: reduces the digest modulo the order, slightly biased.
+//! - `hash_to_scalar_rfc9380_style`: hashes a message to a scalar with a negligible bias.
+
+use crate::bigint::{BigInt, Sign};
+use crate::crypto::hash::{expand_message_xmd, ExpandMessageError, UnkeyedHash};
+use crate::math::modular::modulo;
+
+/// The security level, in bits, of `hash_to_scalar_rfc9380_style` (k in RFC 9380, 5.)
+const SECURITY_BITS_LEN: usize = 128;
+
+/// Converts the leading bits of `digest` to an integer, as many as the bit length of
+/// `order` (SEC1, 4.1.3. step 5).
+///
+/// The result isn't reduced, it may be greater than or equal to `order`.
+pub fn truncate_to_scalar(digest: &[u8], order: &BigInt) -> BigInt {
+    let max_bits_len = order.bit_len();
+    debug_assert!(max_bits_len > 0);
+
+    if digest.len() * 8 <= max_bits_len {
+        return BigInt::from_be_bytes(digest, Sign::Positive);
+    }
+
+    let bytes_len = max_bits_len / 8;
+    let bits_remaining_len = max_bits_len % 8;
+    if bits_remaining_len == 0 {
+        BigInt::from_be_bytes(&digest[0..bytes_len], Sign::Positive)
+    } else {
+        let n = BigInt::from_be_bytes(&digest[0..=bytes_len], Sign::Positive);
+        n >> (8 - bits_remaining_len)
+    }
+}
+
+/// Converts `digest` to an integer, reduced modulo `order`.
+///
+/// Unless `digest` is much longer than `order`, the result is biased: the integers below
+/// `2^(8 * digest.len()) mod order` are more likely than the others. For the digests as
+/// long as `order`, e.g., SHA-256 and secp256k1, the bias is small but not negligible, see
+/// `hash_to_scalar_rfc9380_style`.
+pub fn reduce_to_scalar(digest: &[u8], order: &BigInt) -> BigInt {
+    modulo(&BigInt::from_be_bytes(digest, Sign::Positive), order)
+}
+
+/// Hashes `message` to an integer modulo `order` with the hash function of `hasher` and
+/// the domain separation tag `dst`.
+///
+/// Follows `hash_to_field` of RFC 9380 (5.2.) with a single element: `message` is expanded
+/// with `expand_message_xmd` to `ceil((ceil(log2(order)) + 128) / 8)` bytes, which are
+/// reduced modulo `order`. The extra 128 bits make the bias of the reduction negligible.
+///
+/// # Errors
+///
+/// Will return the errors of `expand_message_xmd`, e.g., `ExpandMessageError::DstTooLong`
+/// if `dst` is longer than 255 bytes.
+pub fn hash_to_scalar_rfc9380_style<T: AsRef<[u8]>, S: AsRef<[u8]>, H: UnkeyedHash>(
+    message: T,
+    dst: S,
+    order: &BigInt,
+    hasher: &mut H,
+) -> Result<BigInt, ExpandMessageError> {
+    let uniform_bytes = expand_message_xmd(message, dst, expanded_byte_len(order), hasher)?;
+    Ok(reduce_to_scalar(&uniform_bytes, order))
+}
+
+/// Returns L of RFC 9380 (5.): ceil((ceil(log2(order)) + k) / 8).
+fn expanded_byte_len(order: &BigInt) -> usize {
+    (order.bit_len() + SECURITY_BITS_LEN).div_ceil(8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::hash::Sha256;
+    use crate::crypto::{secp256k1, secp256r1, secp384r1, secp521r1};
+
+    #[test]
+    fn test_truncate_to_scalar() {
+        // The orders of the bit lengths 1, 2, 3, ... are 1, 2, 4, ...
+        let order_of_bits_len = |bits_len: usize| BigInt::one() << (bits_len - 1);
+
+        #[rustfmt::skip]
+        let data = [
+            (&[u8::MAX][..], 17, BigInt::from(255)),
+            (&[u8::MAX][..], 16, BigInt::from(255)),
+            (&[u8::MAX][..], 15, BigInt::from(255)),
+            (&[u8::MAX][..], 8, BigInt::from(255)),
+            (&[u8::MAX][..], 7, BigInt::from(255 >> 1)),
+            (&[u8::MAX][..], 6, BigInt::from(255 >> 2)),
+            (&[u8::MAX][..], 5, BigInt::from(255 >> 3)),
+            (&[u8::MAX][..], 4, BigInt::from(255 >> 4)),
+            (&[u8::MAX][..], 3, BigInt::from(255 >> 5)),
+            (&[u8::MAX][..], 2, BigInt::from(255 >> 6)),
+            (&[u8::MAX][..], 1, BigInt::from(255 >> 7)),
+            (&[1_u8][..], 1, BigInt::from(0)),
+            (&[1_u8][..], 5, BigInt::from(0)),
+            (&[1_u8][..], 7, BigInt::from(0)),
+            (&[1_u8][..], 8, BigInt::from(1)),
+            (&[1_u8][..], 9, BigInt::from(1)),
+            (&[128_u8, 1][..], 1, BigInt::from(1)),
+            (&[128_u8, 1][..], 3, BigInt::from(1 << 2)),
+            (&[128_u8, 1][..], 5, BigInt::from(1 << 4)),
+            (&[128_u8, 1][..], 7, BigInt::from(1 << 6)),
+            (&[128_u8, 1][..], 8, BigInt::from(1 << 7)),
+            (&[128_u8, 1][..], 9, BigInt::from(256)),
+            (&[128_u8, 1][..], 10, BigInt::from(256 << 1)),
+            (&[128_u8, 1][..], 12, BigInt::from(256 << 3)),
+            (&[128_u8, 1][..], 14, BigInt::from(256 << 5)),
+            (&[128_u8, 1][..], 15, BigInt::from(256 << 6)),
+            (&[128_u8, 1][..], 16, BigInt::from((256 << 7) + 1)),
+            (&[128_u8, 1][..], 17, BigInt::from((256 << 7) + 1)),
+            (&[
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 1_u8,
+            ][..], 256, BigInt::from(1)),
+            (&[
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 1_u8, 66,
+            ][..], 256, BigInt::from(1)),
+        ];
+
+        for (digest, bits_len, n) in data {
+            let order = order_of_bits_len(bits_len);
+            assert_eq!(order.bit_len(), bits_len);
+            assert_eq!(truncate_to_scalar(digest, &order), n);
+        }
+
+        // Not reduced: the SHA-256 digest of all ones is greater than the order of secp256k1
+        let order = &secp256k1().base_point_order;
+        let digest = [u8::MAX; 32];
+        let n = truncate_to_scalar(&digest, order);
+        assert!(&n > order);
+        assert_eq!(n, BigInt::from_be_bytes(&digest, Sign::Positive));
+    }
+
+    #[test]
+    fn test_reduce_to_scalar() {
+        let order = &secp256k1().base_point_order;
+        let data = [
+            // (digest, scalar)
+            ("00", "00"),
+            ("01", "01"),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                "00",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "014551231950b75fc4402da1732fc9bebe",
+            ),
+            // Longer than the order, all the bytes count: 2^256 + n
+            (
+                "01fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                "014551231950b75fc4402da1732fc9bebf",
+            ),
+        ];
+        for (digest_hex, scalar_hex) in data {
+            let digest = hex::decode(digest_hex).unwrap();
+            assert_eq!(
+                reduce_to_scalar(&digest, order),
+                BigInt::from_hex(scalar_hex).unwrap(),
+                "{digest_hex}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expanded_byte_len() {
+        let data = [
+            // (order, L)
+            (&secp256k1().base_point_order, 48),
+            (&secp256r1().base_point_order, 48),
+            (&secp384r1().base_point_order, 64),
+            (&secp521r1().base_point_order, 82),
+        ];
+        for (order, len) in data {
+            assert_eq!(expanded_byte_len(order), len);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_scalar_rfc9380_style() {
+        // The DST of RFC 9380, K.1. expand_message_xmd(SHA-256), whose vectors are covered
+        // by `expand_message_xmd`.
+        let dst = "QUUX-V01-CS02-with-expander-SHA256-128";
+        let order = &secp256k1().base_point_order;
+        for message in ["", "abc", "abcdef0123456789"] {
+            let scalar =
+                hash_to_scalar_rfc9380_style(message, dst, order, &mut Sha256::new()).unwrap();
+            let uniform_bytes =
+                expand_message_xmd(message, dst, 48, &mut Sha256::new()).unwrap();
+            assert_eq!(scalar, reduce_to_scalar(&uniform_bytes, order));
+            assert!(&scalar < order);
+        }
+
+        // The DST separates the domains
+        let scalar1 = hash_to_scalar_rfc9380_style("abc", "A", order, &mut Sha256::new());
+        let scalar2 = hash_to_scalar_rfc9380_style("abc", "B", order, &mut Sha256::new());
+        assert_ne!(scalar1.unwrap(), scalar2.unwrap());
+
+        assert_eq!(
+            hash_to_scalar_rfc9380_style("abc", [0; 256], order, &mut Sha256::new()),
+            Err(ExpandMessageError::DstTooLong)
+        );
+    }
+
+    #[test]
+    fn test_hash_to_scalar_rfc9380_style_uniformity() {
+        // Chi-squared over the low 4 bits of 10000 scalars, 15 degrees of freedom. The
+        // messages are fixed, the threshold loose (p < 0.0001 is about 42).
+        const SAMPLES: usize = 10000;
+        const BUCKETS: usize = 16;
+
+        let order = &secp256k1().base_point_order;
+        let mut hasher = Sha256::new();
+        let mut counts = [0_usize; BUCKETS];
+        for i in 0..SAMPLES {
+            let scalar = hash_to_scalar_rfc9380_style(
+                (i as u64).to_be_bytes(),
+                "uniformity",
+                order,
+                &mut hasher,
+            )
+            .unwrap();
+            let low_byte = scalar.to_be_bytes().last().copied().unwrap_or(0);
+            counts[(low_byte as usize) % BUCKETS] += 1;
+        }
+
+        let expected = (SAMPLES / BUCKETS) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 42.0, "{chi_squared}");
+    }
+}
@@ -4,14 +4,45 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+#[cfg(feature = "std")]
+mod algorithm;
 pub mod codecs;
+#[cfg(feature = "std")]
 pub mod ecdsa;
+#[cfg(feature = "std")]
 mod elliptic_curve_params;
 pub mod hash;
+#[cfg(feature = "std")]
+mod hash_to_curve;
+pub mod hash_to_scalar;
+#[cfg(feature = "std")]
+mod nist_curves;
+#[cfg(feature = "std")]
 pub(crate) mod p1363;
+#[cfg(feature = "std")]
+mod raw_xy;
+#[cfg(feature = "std")]
 mod rfc6979;
+#[cfg(feature = "std")]
 mod sec1;
+#[cfg(feature = "std")]
 mod secp256k1;
 
-pub use elliptic_curve_params::EllipticCurveParams;
+#[cfg(feature = "std")]
+pub use algorithm::Algorithm;
+#[cfg(feature = "std")]
+pub use elliptic_curve_params::{
+    Coordinate, CurveParamsError, CurveSecurityError, EllipticCurveParams, PointValidationError,
+};
+#[cfg(feature = "std")]
+pub use hash_to_curve::hash_to_curve;
+#[cfg(feature = "std")]
+pub use nist_curves::{secp256r1, secp384r1, secp521r1};
+#[cfg(feature = "std")]
+pub use p1363::{SignatureDecodingError, SignatureEncodingError};
+#[cfg(feature = "std")]
+pub use rfc6979::GenerateNonceError;
+#[cfg(feature = "std")]
+pub use sec1::PointDecodingError;
+#[cfg(feature = "std")]
 pub use secp256k1::secp256k1;
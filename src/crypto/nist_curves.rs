@@ -0,0 +1,150 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Defines the NIST curves P-256 (secp256r1), P-384 (secp384r1) and P-521 (secp521r1).
+//!
+//! See: SEC 2: Recommended Elliptic Curve Domain Parameters, Version 2.0
+//! http://www.secg.org/sec2-v2.pdf
+
+use super::elliptic_curve_params::EllipticCurveParams;
+use crate::bigint::bigint_const;
+use crate::math::elliptic_curve::{Curve, Point};
+
+/// Returns the parameters of curve secp256r1 (P-256).
+pub fn secp256r1() -> &'static EllipticCurveParams {
+    static PARAMS: EllipticCurveParams = EllipticCurveParams {
+        curve: Curve {
+            a: bigint_const!(
+                "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"
+            ),
+            b: bigint_const!(
+                "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"
+            ),
+            p: bigint_const!(
+                "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+            ),
+        },
+        base_point: Point {
+            x: bigint_const!(
+                "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+            ),
+            y: bigint_const!(
+                "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"
+            ),
+        },
+        base_point_order: bigint_const!(
+            "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+        ),
+        cofactor: 1,
+    };
+    &PARAMS
+}
+
+/// Returns the parameters of curve secp384r1 (P-384).
+pub fn secp384r1() -> &'static EllipticCurveParams {
+    static PARAMS: EllipticCurveParams = EllipticCurveParams {
+        curve: Curve {
+            a: bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc"),
+            b: bigint_const!("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef"),
+            p: bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff"),
+        },
+        base_point: Point {
+            x: bigint_const!("aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7"),
+            y: bigint_const!("3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f"),
+        },
+        base_point_order: bigint_const!("ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973"),
+        cofactor: 1,
+    };
+    &PARAMS
+}
+
+/// Returns the parameters of curve secp521r1 (P-521).
+pub fn secp521r1() -> &'static EllipticCurveParams {
+    static PARAMS: EllipticCurveParams = EllipticCurveParams {
+        curve: Curve {
+            a: bigint_const!("01fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc"),
+            b: bigint_const!("51953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00"),
+            p: bigint_const!("01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+        },
+        base_point: Point {
+            x: bigint_const!("c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66"),
+            y: bigint_const!("011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650"),
+        },
+        base_point_order: bigint_const!("01fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409"),
+        cofactor: 1,
+    };
+    &PARAMS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+
+    #[test]
+    fn test_base_points() {
+        for curve_params in [secp256r1(), secp384r1(), secp521r1()] {
+            assert!(curve_params.validate_point(&curve_params.base_point));
+            assert!(curve_params
+                .curve
+                .mul_point(&curve_params.base_point, &curve_params.base_point_order)
+                .is_identity_element());
+        }
+    }
+
+    #[test]
+    fn test_static_params_equal_parsed() {
+        // a, b, p, the coordinates of the base point and the order of the base point
+        let cases = [
+            (
+                secp256r1(),
+                [
+                    "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+                    "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+                    "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+                    "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+                    "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+                    "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+                ],
+            ),
+            (
+                secp384r1(),
+                [
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc",
+                    "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff",
+                    "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+                    "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+                    "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+                ],
+            ),
+            (
+                secp521r1(),
+                [
+                    "01fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc",
+                    "51953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+                    "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                    "c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+                    "011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650",
+                    "01fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+                ],
+            ),
+        ];
+        for (params, hexes) in cases {
+            let values = [
+                &params.curve.a,
+                &params.curve.b,
+                &params.curve.p,
+                &params.base_point.x,
+                &params.base_point.y,
+                &params.base_point_order,
+            ];
+            for (value, hex) in values.into_iter().zip(hexes) {
+                assert_eq!(*value, BigInt::from_hex(hex).unwrap());
+            }
+        }
+    }
+}
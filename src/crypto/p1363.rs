@@ -4,9 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::bigint::BigInt;
+use crate::bigint::{BigInt, Sign};
+use crate::crypto::codecs::hex;
 use crate::crypto::ecdsa::ecdsa_encoding::SignatureEncoding;
-use crate::crypto::ecdsa::Signature;
+use crate::crypto::ecdsa::{Signature, SignatureCanonicality};
 use crate::crypto::EllipticCurveParams;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
@@ -34,6 +35,24 @@ impl Display for SignatureDecodingError {
 
 impl Error for SignatureDecodingError {}
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SignatureEncodingError {
+    /// r or s isn't in `(0, n)`, e.g., of a signature created with
+    /// `Signature::new_unchecked`.
+    OutOfRange,
+}
+
+impl Display for SignatureEncodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureEncodingError::OutOfRange => write!(f, "Signature out of range"),
+        }
+    }
+}
+
+impl Error for SignatureEncodingError {}
+
 impl SignatureEncoding for P1363 {
     fn decode<T: AsRef<[u8]>>(
         data: T,
@@ -48,18 +67,17 @@ impl SignatureEncoding for P1363 {
 
         let (r_hex, s_hex) = data.split_at(element_hex_len);
 
-        let r = match BigInt::from_hex(r_hex) {
-            Ok(r) => r,
-            Err(_) => {
-                return Err(Box::new(SignatureDecodingError::InvalidFormat));
-            }
-        };
-        let s = match BigInt::from_hex(s_hex) {
-            Ok(s) => s,
-            Err(_) => {
-                return Err(Box::new(SignatureDecodingError::InvalidFormat));
-            }
+        let element_len = curve_params.base_point_order.byte_len();
+        let decode_element = |hex: &[u8]| {
+            hex::decode(hex)
+                .ok()
+                .and_then(|bytes| {
+                    BigInt::from_be_bytes_exact(&bytes, element_len, Sign::Positive).ok()
+                })
+                .ok_or(SignatureDecodingError::InvalidFormat)
         };
+        let r = decode_element(r_hex)?;
+        let s = decode_element(s_hex)?;
 
         match Signature::new(r, s, curve_params) {
             Some(signature) => Ok(signature),
@@ -71,12 +89,20 @@ impl SignatureEncoding for P1363 {
     ///
     /// For r or s with byte length less than the base point order length,
     /// the hexadecimal representation is leading zero padded.
-    fn encode(signature: &Signature) -> String {
+    ///
+    /// Returns `SignatureEncodingError::OutOfRange` unless r and s are in `(0, n)`, "high s"
+    /// signatures are encoded as they are.
+    fn encode(signature: &Signature) -> Result<String, Box<dyn Error>> {
+        if signature.is_canonical() == SignatureCanonicality::OutOfRange {
+            return Err(Box::new(SignatureEncodingError::OutOfRange));
+        }
+
         let element_hex_len = signature.curve_params.base_point_order.byte_len() * 2;
-        let r_hex = signature.r.to_lower_hex();
-        let s_hex = signature.s.to_lower_hex();
+        // r and s are in (0, n), checked above.
+        let r_hex = signature.r.to_fixed_hex(element_hex_len).unwrap();
+        let s_hex = signature.s.to_fixed_hex(element_hex_len).unwrap();
 
-        format!("{r_hex:0>element_hex_len$}{s_hex:0>element_hex_len$}")
+        Ok(format!("{r_hex}{s_hex}"))
     }
 }
 
@@ -117,7 +143,7 @@ mod test {
         ];
 
         for (signature, signature_hex) in data {
-            assert_eq!(P1363::encode(signature), signature_hex);
+            assert_eq!(P1363::encode(signature).unwrap(), signature_hex);
 
             let decoded = P1363::decode(signature_hex, secp256k1).unwrap();
             assert_eq!(decoded.r, signature.r);
@@ -131,6 +157,23 @@ mod test {
         let data = [
             // incorrect length
             ("0011", SignatureDecodingError::InvalidFormat),
+            // too long, padded with a leading zero byte
+            (
+                concat!(
+                    "00",
+                    "fbe907aac2bd7cd0ce3711f644235486367bdca4b87f19f76a7935fa00c6d169",
+                    "7f16095dd8cb6a4da57da25e3a3178665513e12c7b4dc52f2c212d250eef6407"
+                ),
+                SignatureDecodingError::InvalidFormat,
+            ),
+            // r of 31 bytes with the prefix "0x"
+            (
+                concat!(
+                    "0x07aac2bd7cd0ce3711f644235486367bdca4b87f19f76a7935fa00c6d169",
+                    "7f16095dd8cb6a4da57da25e3a3178665513e12c7b4dc52f2c212d250eef6407"
+                ),
+                SignatureDecodingError::InvalidFormat,
+            ),
             // invalid hex char
             (
                 concat!(
@@ -0,0 +1,256 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::bigint::{BigInt, Sign};
+use crate::crypto::codecs::hex;
+use crate::crypto::elliptic_curve_params::{EllipticCurveParams, EllipticCurveParamsEncoding};
+use crate::crypto::sec1::PointDecodingError;
+use crate::math::elliptic_curve::Point;
+
+/// Encodes a point as `x ‖ y`, without a prefix byte.
+///
+/// Both x and y are big-endian and leading zero padded to the byte length of
+/// the field modulus, e.g., 64 bytes for secp256k1 and 132 bytes for P-521.
+/// There is no compressed form.
+pub(crate) struct RawXY;
+
+impl RawXY {
+    pub(crate) fn element_byte_len(curve_params: &EllipticCurveParams) -> usize {
+        curve_params.curve.p.byte_len()
+    }
+
+    /// Splits `x ‖ y` into a point, without validating it.
+    ///
+    /// Returns `None` if `bytes` isn't exactly twice the byte length of the field modulus.
+    pub(crate) fn decode_unvalidated_point_from_bytes(
+        bytes: &[u8],
+        curve_params: &EllipticCurveParams,
+    ) -> Option<Point> {
+        let element_byte_len = Self::element_byte_len(curve_params);
+        if bytes.len() != element_byte_len * 2 {
+            return None;
+        }
+
+        let (x_bytes, y_bytes) = bytes.split_at(element_byte_len);
+        Some(Point {
+            x: BigInt::from_be_bytes(x_bytes, Sign::Positive),
+            y: BigInt::from_be_bytes(y_bytes, Sign::Positive),
+        })
+    }
+}
+
+impl EllipticCurveParamsEncoding for RawXY {
+    fn decode_point<T: AsRef<[u8]>>(
+        data: T,
+        curve_params: &EllipticCurveParams,
+    ) -> Result<Point, Box<dyn std::error::Error>> {
+        match hex::decode(data) {
+            Ok(bytes) => Self::decode_point_from_bytes(&bytes, curve_params),
+            Err(_) => Err(Box::new(PointDecodingError::InvalidFormat)),
+        }
+    }
+
+    /// Encodes `point` into the hexadecimal of `x ‖ y`.
+    ///
+    /// This function will panic if `compressed` is true.
+    fn encode_point_with_case(
+        point: &Point,
+        curve_params: &EllipticCurveParams,
+        compressed: bool,
+        upper: bool,
+    ) -> String {
+        let mut hex = hex::encode_lower(&Self::encode_point_to_bytes(
+            point,
+            curve_params,
+            compressed,
+        ));
+        if upper {
+            hex.make_ascii_uppercase();
+        }
+        hex
+    }
+
+    /// Decodes `x ‖ y`, which must be exactly twice the byte length of the field modulus.
+    ///
+    /// The decoded point is validated against `curve_params`.
+    fn decode_point_from_bytes(
+        bytes: &[u8],
+        curve_params: &EllipticCurveParams,
+    ) -> Result<Point, Box<dyn std::error::Error>> {
+        let Some(point) = Self::decode_unvalidated_point_from_bytes(bytes, curve_params) else {
+            return Err(Box::new(PointDecodingError::InvalidFormat));
+        };
+        if let Err(reason) = curve_params.validate_point_detailed(&point) {
+            return Err(Box::new(PointDecodingError::from(reason)));
+        }
+
+        Ok(point)
+    }
+
+    /// Encodes `point` into `x ‖ y`.
+    ///
+    /// This method assumes that the caller has made sure `point` is legitimate,
+    /// it does not validate `point` against `curve_params`.
+    ///
+    /// This function will panic if `compressed` is true, or if an element of `point`
+    /// is negative or doesn't fit in the byte length of the field modulus.
+    fn encode_point_to_bytes(
+        point: &Point,
+        curve_params: &EllipticCurveParams,
+        compressed: bool,
+    ) -> Vec<u8> {
+        assert!(!compressed, "RawXY has no compressed form");
+        assert!(!point.x.is_sign_negative() && !point.y.is_sign_negative());
+
+        let element_byte_len = Self::element_byte_len(curve_params);
+        let mut data = Vec::with_capacity(element_byte_len * 2);
+        for element in [&point.x, &point.y] {
+            let bytes = element.to_be_bytes();
+            assert!(bytes.len() <= element_byte_len);
+            data.resize(data.len() + element_byte_len - bytes.len(), 0);
+            data.extend(bytes);
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ecdsa::PublicKey;
+    use crate::crypto::elliptic_curve_params::PointValidationError;
+    use crate::crypto::nist_curves::secp521r1;
+    use crate::crypto::sec1::Sec1;
+    use crate::crypto::secp256k1::secp256k1;
+
+    // (x ‖ y in hex, curve_params)
+    fn points_with_leading_zeros() -> [(&'static str, &'static EllipticCurveParams); 4] {
+        [
+            // 246 * G, leading zero x
+            (
+                concat!(
+                    "00136933174bc388a74ebd6746e13afe0eef5d66580c8e23d33464c342dc0080",
+                    "27015dc47dbfe781689f232541c0410560ac69c82044e8e5906e54680127ff92"
+                ),
+                secp256k1(),
+            ),
+            // 130 * G, leading zero y
+            (
+                concat!(
+                    "9dda94404337db1474e67f1d7052f398a0e70ed205c5e94d6e731b06c6f51cd8",
+                    "0065a58128f755afae6fae38ed6d2ec4306a0a11fdd22b0d7d5fa49229d31669"
+                ),
+                secp256k1(),
+            ),
+            // 2 * G
+            (
+                concat!(
+                    "00433c219024277e7e682fcb288148c282747403279b1ccc06352c6e5505d769be",
+                    "97b3b204da6ef55507aa104a3a35c5af41cf2fa364d60fd967f43e3933ba6d783d",
+                    "00f4bb8cc7f86db26700a7f3eceeeed3f0b5c6b5107c4da97740ab21a29906c42d",
+                    "bbb3e377de9f251f6b93937fa99a3248f4eafcbe95edc0f4f71be356d661f41b02"
+                ),
+                secp521r1(),
+            ),
+            // 7 * G
+            (
+                concat!(
+                    "0056d5d1d99d5b7f6346eeb65fda0b073a0c5f22e0e8f5483228f018d2c2f7114c",
+                    "5d8c308d0abfc698d8c9a6df30dce3bbc46f953f50fdc2619a01cead882816ecd4",
+                    "003d2d1b7d9baaa2a110d1d8317a39d68478b5c582d02824f0dd71dbd98a26cbde",
+                    "556bd0f293cdec9e2b9523a34591ce1a5f9e76712a5ddefc7b5c6b8bc90525251b"
+                ),
+                secp521r1(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (hex, curve_params) in points_with_leading_zeros() {
+            let bytes = hex::decode(hex).unwrap();
+            assert_eq!(bytes.len(), curve_params.curve.p.byte_len() * 2);
+
+            let point = RawXY::decode_point_from_bytes(&bytes, curve_params).unwrap();
+            assert_eq!(
+                RawXY::encode_point_to_bytes(&point, curve_params, false),
+                bytes
+            );
+            assert_eq!(RawXY::decode_point(hex, curve_params).unwrap(), point);
+            assert_eq!(RawXY::encode_point(&point, curve_params, false), hex);
+
+            let public_key = PublicKey::from_raw_xy_bytes(&bytes, curve_params).unwrap();
+            assert_eq!(public_key.data, point);
+            assert_eq!(public_key.to_raw_xy_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_length() {
+        for (hex, curve_params) in points_with_leading_zeros() {
+            let bytes = hex::decode(hex).unwrap();
+            let mut longer = bytes.clone();
+            longer.push(0);
+            let mut longer_leading_zero = vec![0];
+            longer_leading_zero.extend(&bytes);
+
+            for invalid in [
+                &bytes[1..],
+                &bytes[..bytes.len() - 1],
+                &longer,
+                &longer_leading_zero,
+                &[],
+            ] {
+                assert_eq!(
+                    PublicKey::from_raw_xy_bytes(invalid, curve_params).unwrap_err(),
+                    PointDecodingError::InvalidFormat
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_point() {
+        for (hex, curve_params) in points_with_leading_zeros() {
+            let mut bytes = hex::decode(hex).unwrap();
+            *bytes.last_mut().unwrap() ^= 1;
+            assert_eq!(
+                PublicKey::from_raw_xy_bytes(&bytes, curve_params).unwrap_err(),
+                PointDecodingError::InvalidPoint(PointValidationError::NotOnCurve)
+            );
+
+            // identity element
+            let zeros = vec![0; bytes.len()];
+            assert_eq!(
+                PublicKey::from_raw_xy_bytes(&zeros, curve_params).unwrap_err(),
+                PointDecodingError::InvalidPoint(PointValidationError::IdentityElement)
+            );
+        }
+    }
+
+    #[test]
+    fn test_consistency_with_sec1_uncompressed() {
+        for (hex, curve_params) in points_with_leading_zeros() {
+            let bytes = hex::decode(hex).unwrap();
+            let point = RawXY::decode_point_from_bytes(&bytes, curve_params).unwrap();
+
+            let sec1_bytes = Sec1::encode_point_to_bytes(&point, curve_params, false);
+            assert_eq!(sec1_bytes[0], 0x04);
+            assert_eq!(sec1_bytes[1..], bytes);
+            assert_eq!(
+                Sec1::decode_point_from_bytes(&sec1_bytes, curve_params).unwrap(),
+                point
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_encode_compressed_panic() {
+        let secp256k1 = secp256k1();
+        RawXY::encode_point_to_bytes(&secp256k1.base_point, secp256k1, true);
+    }
+}
@@ -13,7 +13,7 @@ use crate::random::GetOsRandomBytesError;
 use std::fmt;
 use std::fmt::Display;
 
-pub(crate) struct Rfc6979 {
+pub(crate) struct Rfc6979<'a> {
     // Base point order of the elliptic curve domain parameters.
     q: BigInt,
 
@@ -25,10 +25,19 @@ pub(crate) struct Rfc6979 {
 
     // Section 3.6 of RFC6979
     employ_extra_random_data: bool,
+
+    // The additional data of section 3.6, supplied instead of the OS random bytes.
+    additional_entropy: Option<&'a [u8]>,
 }
 
-impl Rfc6979 {
-    pub(crate) fn new(q: BigInt, employ_extra_random_data: bool) -> Rfc6979 {
+impl<'a> Rfc6979<'a> {
+    /// Feeds `additional_entropy` to `K` and `V` if it's `Some`, or 32 random bytes from
+    /// the OS if `employ_extra_random_data` is true.
+    pub(crate) fn new(
+        q: BigInt,
+        employ_extra_random_data: bool,
+        additional_entropy: Option<&'a [u8]>,
+    ) -> Rfc6979<'a> {
         let qlen = q.bit_len();
         let rlen = ((qlen + 7) / 8) * 8;
 
@@ -37,6 +46,7 @@ impl Rfc6979 {
             qlen,
             rlen,
             employ_extra_random_data,
+            additional_entropy,
         }
     }
 
@@ -50,7 +60,9 @@ impl Rfc6979 {
 
         let mut key_and_msg = self.int2octets(&private_key.data);
         key_and_msg.extend(&self.bits2octets(hash));
-        if self.employ_extra_random_data {
+        if let Some(additional_entropy) = self.additional_entropy {
+            key_and_msg.extend(additional_entropy);
+        } else if self.employ_extra_random_data {
             match random::generator::get_os_random_bytes(32) {
                 Ok(bytes) => {
                     key_and_msg.extend(&bytes);
@@ -95,7 +107,7 @@ impl Rfc6979 {
             }
 
             let nonce = self.bits2int(&t);
-            if nonce > BigInt::zero() && nonce < self.q {
+            if nonce > 0 && nonce < self.q {
                 return Ok(nonce);
             }
 
@@ -180,7 +192,7 @@ mod tests {
             &curve_params,
         )
         .unwrap();
-        let rfc6979 = Rfc6979::new(q, false);
+        let rfc6979 = Rfc6979::new(q, false, None);
 
         let mut hasher = Sha256::new();
         let hash = hasher.digest("sample");
@@ -4,9 +4,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use crate::bigint::bigint_core::BigInt;
-use crate::crypto::codecs::bytes_to_lower_hex;
-use crate::crypto::elliptic_curve_params::{EllipticCurveParams, EllipticCurveParamsEncoding};
+use crate::bigint::bigint_core::{BigInt, Sign};
+use crate::crypto::codecs::hex;
+use crate::crypto::elliptic_curve_params::{
+    Coordinate, EllipticCurveParams, EllipticCurveParamsEncoding, PointValidationError,
+};
 use crate::math::elliptic_curve::Point;
 use crate::math::modular::{modulo, sqrt};
 use std::fmt::{Display, Formatter};
@@ -20,7 +22,8 @@ pub enum PointDecodingError {
     InvalidX,
     InvalidY,
     YNotFound,
-    InvalidPoint,
+    /// The decoded point isn't legitimate in the curve, for the reason.
+    InvalidPoint(PointValidationError),
 }
 
 impl Display for PointDecodingError {
@@ -30,13 +33,19 @@ impl Display for PointDecodingError {
             PointDecodingError::InvalidX => write!(f, "Invalid x"),
             PointDecodingError::InvalidY => write!(f, "Invalid y"),
             PointDecodingError::YNotFound => write!(f, "Y not found"),
-            PointDecodingError::InvalidPoint => write!(f, "Invalid point"),
+            PointDecodingError::InvalidPoint(reason) => write!(f, "Invalid point: {reason}"),
         }
     }
 }
 
 impl std::error::Error for PointDecodingError {}
 
+impl From<PointValidationError> for PointDecodingError {
+    fn from(reason: PointValidationError) -> Self {
+        PointDecodingError::InvalidPoint(reason)
+    }
+}
+
 impl EllipticCurveParamsEncoding for Sec1 {
     /// Decodes a Point as described in http://www.secg.org/SEC1-Ver-1.0.pdf,
     /// sections 2.3.3/2.3.4.
@@ -48,7 +57,7 @@ impl EllipticCurveParamsEncoding for Sec1 {
         curve_params: &EllipticCurveParams,
     ) -> Result<Point, Box<dyn std::error::Error>> {
         let hex_bytes = data.as_ref();
-        let point_element_hex_len = curve_params.base_point_order.byte_len() * 2;
+        let point_element_hex_len = curve_params.curve.p.byte_len() * 2;
 
         if hex_bytes.len() < point_element_hex_len + 2 {
             return Err(Box::new(PointDecodingError::InvalidFormat));
@@ -61,23 +70,21 @@ impl EllipticCurveParamsEncoding for Sec1 {
                 return Err(Box::new(PointDecodingError::InvalidFormat));
             }
             let x_hex_bytes = &hex_bytes[2..point_element_hex_len + 2];
-            let x = match BigInt::from_hex(x_hex_bytes) {
-                Ok(x) => x,
-                Err(_) => {
-                    return Err(Box::new(PointDecodingError::InvalidX));
-                }
-            };
+            let x = decode_point_element(
+                x_hex_bytes,
+                curve_params.curve.p.byte_len(),
+                PointDecodingError::InvalidX,
+            )?;
             let y_hex_bytes = &hex_bytes[point_element_hex_len + 2..];
-            let y = match BigInt::from_hex(y_hex_bytes) {
-                Ok(y) => y,
-                Err(_) => {
-                    return Err(Box::new(PointDecodingError::InvalidY));
-                }
-            };
+            let y = decode_point_element(
+                y_hex_bytes,
+                curve_params.curve.p.byte_len(),
+                PointDecodingError::InvalidY,
+            )?;
 
             let point = Point { x, y };
-            if !curve_params.validate_point(&point) {
-                return Err(Box::new(PointDecodingError::InvalidPoint));
+            if let Err(reason) = curve_params.validate_point_detailed(&point) {
+                return Err(Box::new(PointDecodingError::from(reason)));
             }
 
             return Ok(point);
@@ -89,12 +96,19 @@ impl EllipticCurveParamsEncoding for Sec1 {
 
         // compressed
         let x_hex_bytes = &hex_bytes[2..];
-        let x = match BigInt::from_hex(x_hex_bytes) {
-            Ok(x) => x,
-            Err(_) => {
-                return Err(Box::new(PointDecodingError::InvalidX));
-            }
-        };
+        let x = decode_point_element(
+            x_hex_bytes,
+            curve_params.curve.p.byte_len(),
+            PointDecodingError::InvalidX,
+        )?;
+        // Otherwise x would be reduced, and the point of x mod p decoded.
+        if x >= curve_params.curve.p {
+            return Err(Box::new(PointDecodingError::from(
+                PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::X,
+                },
+            )));
+        }
 
         // y^2 = x^3 + a * x + b
         let y_squared = &x * &x * &x + &curve_params.curve.a * &x + &curve_params.curve.b;
@@ -124,8 +138,8 @@ impl EllipticCurveParamsEncoding for Sec1 {
         };
 
         let point = Point { x, y };
-        if !curve_params.validate_point(&point) {
-            return Err(Box::new(PointDecodingError::InvalidPoint));
+        if let Err(reason) = curve_params.validate_point_detailed(&point) {
+            return Err(Box::new(PointDecodingError::from(reason)));
         }
 
         Ok(point)
@@ -140,32 +154,52 @@ impl EllipticCurveParamsEncoding for Sec1 {
     /// This method assumes that the caller has made sure `point` is legitimate,
     /// it does not validate `point` against `curve_params`.
     ///
-    /// Both elements of `point` must be in the range (> 0 and < base_point_order),
+    /// Both elements of `point` must be in the range `[0, p)`, where p is the field modulus,
     /// otherwise this function will panic.
-    fn encode_point(
+    fn encode_point_with_case(
         point: &Point,
         curve_params: &EllipticCurveParams,
         compressed: bool,
+        upper: bool,
     ) -> String {
-        assert!(point.x > BigInt::zero() && point.x < curve_params.base_point_order);
-        assert!(point.y > BigInt::zero() && point.y < curve_params.base_point_order);
+        assert!(point.x >= 0 && point.x < curve_params.curve.p);
+        assert!(point.y >= 0 && point.y < curve_params.curve.p);
 
-        let hex_len = curve_params.base_point_order.byte_len() * 2;
+        let hex_len = curve_params.curve.p.byte_len() * 2;
         if compressed {
+            let mut x_hex = point.x.to_fixed_hex(hex_len).unwrap();
+            if upper {
+                x_hex.make_ascii_uppercase();
+            }
             if point.y.is_even() {
-                let x_hex = point.x.to_lower_hex();
-                format!("02{x_hex:0>hex_len$}")
+                format!("02{x_hex}")
             } else {
-                let x_hex = point.x.to_lower_hex();
-                format!("03{x_hex:0>hex_len$}")
+                format!("03{x_hex}")
             }
         } else {
-            let hex = bytes_to_lower_hex(&curve_params.point_to_bytes(point));
+            let mut hex = hex::encode_lower(&curve_params.point_to_bytes(point));
+            if upper {
+                hex.make_ascii_uppercase();
+            }
             format!("04{hex}")
         }
     }
 }
 
+/// Decodes the hex of a point element, exactly `len` bytes long.
+///
+/// Returns `invalid_hex` if `hex_bytes` isn't hex, and `PointDecodingError::InvalidFormat`
+/// if it is of another length, e.g., padded with leading zeros.
+fn decode_point_element(
+    hex_bytes: &[u8],
+    len: usize,
+    invalid_hex: PointDecodingError,
+) -> Result<BigInt, PointDecodingError> {
+    let bytes = hex::decode(hex_bytes).map_err(|_| invalid_hex)?;
+    BigInt::from_be_bytes_exact(&bytes, len, Sign::Positive)
+        .map_err(|_| PointDecodingError::InvalidFormat)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,6 +331,24 @@ mod tests {
             ),
             // compressed x too short
             (concat!("03", "112233"), PointDecodingError::InvalidFormat),
+            // compressed x too long, padded with a leading zero byte
+            (
+                concat!(
+                    "02",
+                    "00",
+                    "e395153848a05cedf4630c2c512a245db2d8281eb1f566cc8768f98c66c042c8"
+                ),
+                PointDecodingError::InvalidFormat,
+            ),
+            // compressed x too long
+            (
+                concat!(
+                    "02",
+                    "e395153848a05cedf4630c2c512a245db2d8281eb1f566cc8768f98c66c042c8",
+                    "11"
+                ),
+                PointDecodingError::InvalidFormat,
+            ),
             // uncompressed x too short
             (
                 concat!(
@@ -353,7 +405,7 @@ mod tests {
                     "42532038bd7d6162d3f54589cf6f96400dd5f0e17eec1a1841fe6c366e6d244b",
                     "29d81cd27a8a096b11d9b5a414b8f2e811da6aad4a694d4e7a9ae6b8f68e09ac"
                 ),
-                PointDecodingError::InvalidPoint,
+                PointDecodingError::InvalidPoint(PointValidationError::NotOnCurve),
             ),
             // invalid curve point (identity element)
             (
@@ -362,7 +414,39 @@ mod tests {
                     "0000000000000000000000000000000000000000000000000000000000000000",
                     "0000000000000000000000000000000000000000000000000000000000000000"
                 ),
-                PointDecodingError::InvalidPoint,
+                PointDecodingError::InvalidPoint(PointValidationError::IdentityElement),
+            ),
+            // uncompressed x >= p
+            (
+                concat!(
+                    "04",
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+                    "8a9e6985181caaadfed4a887fcf4c95c649d20176644612443a8cbb06b0dce7f"
+                ),
+                PointDecodingError::InvalidPoint(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::X,
+                }),
+            ),
+            // uncompressed y >= p
+            (
+                concat!(
+                    "04",
+                    "e395153848a05cedf4630c2c512a245db2d8281eb1f566cc8768f98c66c042c8",
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+                ),
+                PointDecodingError::InvalidPoint(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::Y,
+                }),
+            ),
+            // compressed x >= p, x - p is the x of a point of the curve
+            (
+                concat!(
+                    "02",
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30"
+                ),
+                PointDecodingError::InvalidPoint(PointValidationError::CoordinateOutOfRange {
+                    which: Coordinate::X,
+                }),
             ),
         ];
         for (hex, err) in data {
@@ -469,6 +553,10 @@ mod tests {
         ];
         for (hex, point, compressed) in data {
             assert_eq!(Sec1::encode_point(&point, secp256k1, compressed), hex);
+            assert_eq!(
+                Sec1::encode_point_with_case(&point, secp256k1, compressed, true),
+                hex.to_uppercase()
+            );
         }
     }
 
@@ -498,6 +586,30 @@ mod tests {
         Sec1::encode_point(&point, secp256k1, true);
     }
 
+    #[test]
+    fn test_encode_point_element_not_lower_than_base_point_order() {
+        // Elements are field elements, in `[0, p)` rather than `[1, n)`.
+        let secp256k1 = secp256k1();
+        let x = &secp256k1.base_point_order + &BigInt::one();
+        let point = Point {
+            x: x.clone(),
+            y: BigInt::from(2),
+        };
+        assert_eq!(
+            Sec1::encode_point(&point, secp256k1, true),
+            format!("02{}", x.to_fixed_hex(64).unwrap())
+        );
+
+        let point = Point {
+            x: BigInt::zero(),
+            y: &secp256k1.curve.p - &BigInt::one(),
+        };
+        assert_eq!(
+            Sec1::encode_point(&point, secp256k1, false),
+            format!("04{:0>64}{}", "", point.y.to_fixed_hex(64).unwrap())
+        );
+    }
+
     #[test]
     fn point_to_hex_double_conversion() {
         const GEN_SIZE: usize = 16;
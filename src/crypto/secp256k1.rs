@@ -5,43 +5,70 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::elliptic_curve_params::EllipticCurveParams;
-use crate::bigint::BigInt;
+use crate::bigint::bigint_const;
 use crate::math::elliptic_curve::{Curve, Point};
-use std::sync::Once;
 
-static mut SECP256K1: Option<EllipticCurveParams> = None;
-static INIT: Once = Once::new();
+static SECP256K1: EllipticCurveParams = EllipticCurveParams {
+    curve: Curve {
+        a: bigint_const!("00"),
+        b: bigint_const!("07"),
+        p: bigint_const!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"),
+    },
+    base_point: Point {
+        x: bigint_const!("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        y: bigint_const!("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+    },
+    base_point_order: bigint_const!(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+    ),
+    cofactor: 1,
+};
 
+/// Returns the parameters of curve secp256k1.
+///
+/// The parameters are built at compile time, reading them doesn't parse or allocate.
 pub fn secp256k1() -> &'static EllipticCurveParams {
-    INIT.call_once(|| unsafe {
-        let curve_params = EllipticCurveParams {
-            curve: Curve {
-                a: BigInt::from(0),
-                b: BigInt::from(7),
-                p: BigInt::from_hex(
-                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
-                )
-                .unwrap(),
-            },
-            base_point: Point {
-                x: BigInt::from_hex(
-                    "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
-                )
-                .unwrap(),
-                y: BigInt::from_hex(
-                    "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
-                )
-                .unwrap(),
-            },
-            base_point_order: BigInt::from_hex(
-                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
-            )
-            .unwrap(),
-            cofactor: 1,
-        };
-        SECP256K1 = Some(curve_params);
-    });
+    &SECP256K1
+}
 
-    let params = unsafe { SECP256K1.as_ref().unwrap() };
-    params
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bigint::BigInt;
+
+    #[test]
+    fn test_static_params_equal_parsed() {
+        let params = secp256k1();
+        assert_eq!(params.curve.a, BigInt::from(0));
+        assert_eq!(params.curve.b, BigInt::from(7));
+        assert_eq!(
+            params.curve.p,
+            BigInt::from_hex(
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.base_point.x,
+            BigInt::from_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.base_point.y,
+            BigInt::from_hex(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            params.base_point_order,
+            BigInt::from_hex(
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+            )
+            .unwrap()
+        );
+        assert!(params.validate_point(&params.base_point));
+    }
 }
@@ -4,11 +4,24 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+//! With the default feature "std" disabled, and "alloc" enabled, the crate is `no_std`
+//! and provides the bignum core, the hash functions, the hex codec and the RLP codec.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(feature = "alloc"))]
+compile_error!("lightcryptotools requires the feature \"alloc\", or \"std\" which enables it");
+
+extern crate alloc;
+
 pub mod bigint;
 pub mod blockchain;
 pub mod crypto;
 pub mod math;
+#[cfg(feature = "std")]
 mod os;
+pub mod prelude;
+#[cfg(feature = "std")]
 pub mod random;
 pub mod tools;
 
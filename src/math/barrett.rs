@@ -0,0 +1,140 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Implements Barrett reduction.
+//!
+//! See: Handbook of Applied Cryptography, 14.3.3, Algorithm 14.42
+//! https://cacr.uwaterloo.ca/hac/about/chap14.pdf
+
+use crate::bigint::digit::Digit;
+use crate::bigint::BigInt;
+
+/// Precomputed values to reduce numbers modulo `modulus` repeatedly,
+/// replacing the divisions with multiplications and shifts.
+///
+/// With b = 2^Digit::BITS and k the digit length of the modulus m,
+/// `mu = floor(b^(2k) / m)` is computed once in [`BarrettContext::new`].
+#[derive(Clone, Debug)]
+pub(crate) struct BarrettContext {
+    modulus: BigInt,
+    mu: BigInt,
+    k: usize,
+}
+
+impl BarrettContext {
+    /// Creates a `BarrettContext` for `modulus`.
+    ///
+    /// Will panic if `modulus <= 0`.
+    pub(crate) fn new(modulus: &BigInt) -> BarrettContext {
+        assert!(*modulus > 0);
+
+        let k = modulus.digits_len;
+        let mu = (BigInt::one() << (2 * k * Digit::BITS as usize)) / modulus;
+        BarrettContext {
+            modulus: modulus.clone(),
+            mu,
+            k,
+        }
+    }
+
+    pub(crate) fn modulus(&self) -> &BigInt {
+        &self.modulus
+    }
+
+    /// Returns `x mod m`.
+    ///
+    /// `x` must be in [0, m^2).
+    pub(crate) fn reduce(&self, x: &BigInt) -> BigInt {
+        debug_assert!(*x >= 0);
+        debug_assert!(x < &(&self.modulus * &self.modulus));
+
+        // q = floor(floor(x / b^(k - 1)) * mu / b^(k + 1)), an estimate of floor(x / m),
+        // less than it by at most 2.
+        let digit_bits = Digit::BITS as usize;
+        let mut q = x >> ((self.k - 1) * digit_bits);
+        q *= &self.mu;
+        let q = q >> ((self.k + 1) * digit_bits);
+
+        let mut r = x - &q * &self.modulus;
+        while r >= self.modulus {
+            r -= &self.modulus;
+        }
+        r
+    }
+
+    /// Returns `(a * b) mod m`.
+    ///
+    /// `a` and `b` must be in [0, m).
+    pub(crate) fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.reduce(&(a * b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck::{Gen, QuickCheck};
+
+    #[test]
+    fn test_reduce() {
+        // (modulus, x)
+        let data = [
+            ("01", "00"),
+            ("07", "30"),
+            ("07", "2a"),
+            ("ff", "fe00"),
+            // secp256k1 p
+            (
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+                concat!(
+                    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffdfffff85e",
+                    "00000000000000000000000000000000000000000000000000000001000007a4"
+                ),
+            ),
+        ];
+        for (modulus_hex, x_hex) in data {
+            let modulus = BigInt::from_hex(modulus_hex).unwrap();
+            let x = BigInt::from_hex(x_hex).unwrap();
+            let context = BarrettContext::new(&modulus);
+            assert_eq!(context.reduce(&x), &x % &modulus);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_modulus() {
+        BarrettContext::new(&BigInt::zero());
+    }
+
+    #[test]
+    fn reduce_matches_rem() {
+        const GEN_SIZE: usize = 128;
+        const TEST_NUMBER: u64 = 1000;
+
+        fn prop(modulus_hex: BigIntHexString, x_hex: BigIntHexString) -> bool {
+            let modulus = BigInt::from_hex(&modulus_hex.0).unwrap().abs();
+            if modulus.is_zero() {
+                return true; // just ignore
+            }
+            // x in [0, m^2)
+            let x = BigInt::from_hex(&x_hex.0).unwrap().abs() % (&modulus * &modulus);
+
+            let context = BarrettContext::new(&modulus);
+            let max = &modulus * &modulus - BigInt::one();
+            context.reduce(&x) == &x % &modulus && context.reduce(&max) == &max % &modulus
+        }
+
+        QuickCheck::new()
+            .gen(Gen::new(GEN_SIZE))
+            .tests(TEST_NUMBER)
+            .quickcheck(prop as fn(BigIntHexString, BigIntHexString) -> bool)
+    }
+}
@@ -6,6 +6,11 @@
 
 use super::modular::{invert, modulo};
 use crate::bigint::BigInt;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Display;
+use core::ops::RangeInclusive;
 
 /// A curve "y^2 = x^3 + a * x + b"
 /// with respect to the integers modulo `p`.
@@ -21,15 +26,22 @@ impl Curve {
         modulo(a, &self.p)
     }
 
-    /// Adds `a` to itself.
-    fn double_point(&self, a: &Point) -> Point {
-        debug_assert!(a.x >= BigInt::zero() && a.x < self.p);
-        debug_assert!(a.y >= BigInt::zero() && a.y < self.p);
+    /// Adds `a` to itself, with the tangent line at `a`.
+    ///
+    /// Equivalent to `add_points(a, a)`, which delegates to this method.
+    pub fn double_point(&self, a: &Point) -> Point {
+        debug_assert!(a.x >= 0 && a.x < self.p);
+        debug_assert!(a.y >= 0 && a.y < self.p);
 
         if a.is_identity_element() {
             return Point::identity_element();
         }
 
+        // The tangent is vertical: a = –a, so a + a = O
+        if a.y.is_zero() {
+            return Point::identity_element();
+        }
+
         let two = BigInt::from(2);
         let three = BigInt::from(3);
 
@@ -50,10 +62,10 @@ impl Curve {
 
     /// Adds point `a` to point `b`.
     pub(crate) fn add_points(&self, a: &Point, b: &Point) -> Point {
-        debug_assert!(a.x >= BigInt::zero() && a.x < self.p);
-        debug_assert!(a.y >= BigInt::zero() && a.y < self.p);
-        debug_assert!(b.x >= BigInt::zero() && b.x < self.p);
-        debug_assert!(b.y >= BigInt::zero() && b.y < self.p);
+        debug_assert!(a.x >= 0 && a.x < self.p);
+        debug_assert!(a.y >= 0 && a.y < self.p);
+        debug_assert!(b.x >= 0 && b.x < self.p);
+        debug_assert!(b.y >= 0 && b.y < self.p);
 
         // O + O = O
         if a.is_identity_element() && b.is_identity_element() {
@@ -70,6 +82,7 @@ impl Curve {
             return a.clone();
         }
 
+        // The chord degenerates to the tangent
         if a == b {
             return self.double_point(a);
         }
@@ -98,29 +111,86 @@ impl Curve {
         Point { x, y }
     }
 
-    /// Multiplies `point` with `n`.
+    /// Multiplies `point` with `n`, with the default [`MulConfig`].
+    #[cfg(feature = "std")]
     pub(crate) fn mul_point(&self, point: &Point, n: &BigInt) -> Point {
-        debug_assert!(point.x >= BigInt::zero());
-        debug_assert!(point.y >= BigInt::zero());
-        debug_assert!(n >= &BigInt::zero());
+        self.mul_point_with_config(point, n, &MulConfig::default())
+    }
 
-        if n.is_zero() {
+    /// Multiplies `point` with `n`, employing the wNAF method with the window width of
+    /// `config`.
+    pub fn mul_point_with_config(
+        &self,
+        point: &Point,
+        n: &BigInt,
+        config: &MulConfig,
+    ) -> Point {
+        debug_assert!(point.x >= 0);
+        debug_assert!(point.y >= 0);
+        debug_assert!(*n >= 0);
+
+        // Short-circuits the trivial cases, skipping the precomputation and the loop.
+        // `n` equal to the order of `point` isn't among them, for `Curve` doesn't know the
+        // order, and subgroup checks, e.g., `EllipticCurveParams::is_in_subgroup`, depend on
+        // computing that product.
+        if n.is_zero() || point.is_identity_element() {
             return Point::identity_element();
         }
+        if n.is_one() {
+            return point.clone();
+        }
 
-        // Employs the double-and-add method.
-        // https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#Double-and-add
+        let odd_multiples = self.odd_multiples(point, config.window_width);
+        self.mul_point_with_odd_multiples(&odd_multiples, n, config.window_width)
+    }
+
+    /// Returns the odd multiples `point, 3 * point, ..., (2^(w-1) - 1) * point`, the
+    /// precomputation of the wNAF method with the window width `w`.
+    pub(crate) fn odd_multiples(&self, point: &Point, window_width: usize) -> Vec<Point> {
+        let doubled = self.double_point(point);
+        let mut odd_multiples = Vec::with_capacity(1 << (window_width - 2));
+        odd_multiples.push(point.clone());
+        for i in 1..odd_multiples.capacity() {
+            odd_multiples.push(self.add_points(&odd_multiples[i - 1], &doubled));
+        }
+        odd_multiples
+    }
+
+    /// Multiplies a point with `n`, employing the wNAF method with the window width `w`
+    /// and `odd_multiples`, the odd multiples of the point from [`Self::odd_multiples`].
+    pub(crate) fn mul_point_with_odd_multiples(
+        &self,
+        odd_multiples: &[Point],
+        n: &BigInt,
+        window_width: usize,
+    ) -> Point {
+        debug_assert_eq!(odd_multiples.len(), 1 << (window_width - 2));
+        debug_assert!(*n >= 0);
+
+        // Employs the double-and-add method over the wNAF digits of `n`,
+        // from the most significant digit to the least significant.
+        // https://en.wikipedia.org/wiki/Elliptic_curve_point_multiplication#w-ary_non-adjacent_form_(wNAF)_method
         let mut result = Point::identity_element();
-        let mut base = point.clone();
-        for bit in n.le_bits() {
-            if bit {
-                result = self.add_points(&base, &result);
+        for digit in wnaf_digits(n, window_width).into_iter().rev() {
+            result = self.double_point(&result);
+            if digit > 0 {
+                result = self.add_points(&result, &odd_multiples[digit as usize / 2]);
+            } else if digit < 0 {
+                let negated = self.negate_point(&odd_multiples[-digit as usize / 2]);
+                result = self.add_points(&result, &negated);
             }
-            base = self.double_point(&base);
         }
         result
     }
 
+    /// Returns `-a`, the reflection of `a` over the x-axis.
+    fn negate_point(&self, a: &Point) -> Point {
+        Point {
+            x: a.x.clone(),
+            y: self.modulo(&(-&a.y)),
+        }
+    }
+
     /// Returns the modulo multiplicative inverse of `a`
     /// with respect to the integers modulo `self.p`.
     pub(crate) fn invert(&self, a: &BigInt) -> Option<BigInt> {
@@ -128,6 +198,77 @@ impl Curve {
     }
 }
 
+/// The widths of the window supported by [`MulConfig`].
+pub const MUL_WINDOW_WIDTH_RANGE: RangeInclusive<usize> = 2..=6;
+
+/// The configuration of scalar multiplication.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MulConfig {
+    window_width: usize,
+}
+
+impl MulConfig {
+    /// Creates a configuration with the window width `w` of the wNAF method, or returns
+    /// `None` if `window_width` is not within [`MUL_WINDOW_WIDTH_RANGE`].
+    ///
+    /// A wider window means fewer point additions, but also more precomputation memory:
+    /// `2^(w-2)` points are precomputed for each multiplication.
+    pub fn new(window_width: usize) -> Option<MulConfig> {
+        if MUL_WINDOW_WIDTH_RANGE.contains(&window_width) {
+            Some(MulConfig { window_width })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the window width, 4 by default.
+    pub fn window_width(&self) -> usize {
+        self.window_width
+    }
+}
+
+impl Default for MulConfig {
+    fn default() -> Self {
+        MulConfig { window_width: 4 }
+    }
+}
+
+/// Returns the width-`w` non-adjacent form of `n`, in little-endian order.
+///
+/// Each nonzero digit is odd and within `(-2^(w-1), 2^(w-1))`, and is followed by at
+/// least `w - 1` zeros.
+fn wnaf_digits(n: &BigInt, w: usize) -> Vec<i8> {
+    let bits = n.le_bits();
+    let bits_len = bits.len();
+    // Reads the `w` bits starting at `index`, with zeros beyond `bits_len`.
+    let window_at = |index: usize| {
+        (index..index + w).rev().fold(0, |word, i| {
+            word << 1 | bits.get(i).copied().unwrap_or(false) as i32
+        })
+    };
+
+    let mut digits = vec![0; bits_len + w + 1];
+    let mut carry = 0;
+    let mut index = 0;
+    while index < bits_len {
+        if bits[index] as i32 == carry {
+            index += 1;
+            continue;
+        }
+
+        // `word` is odd. For `word >= 2^(w-1)`, takes the negative digit `word - 2^w`
+        // and carries 1 to the next window.
+        let mut word = window_at(index) + carry;
+        carry = (word >> (w - 1)) & 1;
+        word -= carry << w;
+        digits[index] = word as i8;
+        index += w;
+    }
+    digits[index] = carry as i8;
+
+    digits
+}
+
 /// A curve point.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Point {
@@ -153,9 +294,25 @@ impl Point {
     }
 }
 
+/// Formats the point as `(x, y)` in hexadecimal, e.g., `(0x05, 0x01)`, or `O` for the
+/// identity element.
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_identity_element() {
+            return write!(f, "O");
+        }
+
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::secp256k1;
+    use crate::testing_tools::quickcheck::HexString;
+    use quickcheck::TestResult;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_mul_point() {
@@ -217,6 +374,194 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_point_display() {
+        let base_point = &secp256k1().base_point;
+        assert_eq!(
+            base_point.to_string(),
+            concat!(
+                "(0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798, ",
+                "0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8)"
+            )
+        );
+
+        let point = Point {
+            x: BigInt::from(5),
+            y: BigInt::from(1),
+        };
+        assert_eq!(point.to_string(), "(0x05, 0x01)");
+        assert_eq!(Point::identity_element().to_string(), "O");
+    }
+
+    #[test]
+    fn test_double_point() {
+        // y^2 = x^3 + 2 * x + 2 mod 17, P = (5, 1)
+        let curve = Curve {
+            a: BigInt::from(2),
+            b: BigInt::from(2),
+            p: BigInt::from(17),
+        };
+        let p = Point {
+            x: BigInt::from(5),
+            y: BigInt::from(1),
+        };
+        let two_p = Point {
+            x: BigInt::from(6),
+            y: BigInt::from(3),
+        };
+        assert_eq!(curve.double_point(&p), two_p);
+        assert_eq!(curve.add_points(&p, &p), two_p);
+        assert!(curve
+            .double_point(&Point::identity_element())
+            .is_identity_element());
+
+        // y^2 = x^3 - 1 mod 17, (1, 0) is of order 2
+        let curve = Curve {
+            a: BigInt::from(0),
+            b: BigInt::from(16),
+            p: BigInt::from(17),
+        };
+        let p = Point {
+            x: BigInt::from(1),
+            y: BigInt::from(0),
+        };
+        assert!(curve.double_point(&p).is_identity_element());
+        assert!(curve.add_points(&p, &p).is_identity_element());
+        assert!(curve.mul_point(&p, &BigInt::from(2)).is_identity_element());
+    }
+
+    #[quickcheck]
+    fn double_point_matches_add_points_and_mul_point(k_hex: HexString) -> TestResult {
+        let curve_params = secp256k1();
+        let k = BigInt::from_hex(k_hex.0).unwrap();
+        if k.is_zero() {
+            return TestResult::discard();
+        }
+
+        let curve = &curve_params.curve;
+        let point = curve.mul_point(&curve_params.base_point, &k);
+        let doubled = curve.double_point(&point);
+        TestResult::from_bool(
+            doubled == curve.add_points(&point, &point)
+                && doubled == curve.mul_point(&point, &BigInt::from(2)),
+        )
+    }
+
+    #[quickcheck]
+    fn wnaf_digits_represent_n(n_hex: HexString) -> bool {
+        let n = BigInt::from_hex(n_hex.0).unwrap();
+        MUL_WINDOW_WIDTH_RANGE.clone().all(|w| {
+            let digits = wnaf_digits(&n, w);
+            let mut sum = BigInt::zero();
+            for (i, &digit) in digits.iter().enumerate().rev() {
+                sum = &sum + &sum + BigInt::from(digit);
+                if digit != 0 {
+                    let bound = 1 << (w - 1);
+                    if digit % 2 == 0 || !(-bound..bound).contains(&(digit as i32)) {
+                        return false;
+                    }
+                    // followed by at least `w - 1` zeros
+                    if digits[i + 1..].iter().take(w - 1).any(|&d| d != 0) {
+                        return false;
+                    }
+                }
+            }
+            sum == n
+        })
+    }
+
+    #[test]
+    fn test_mul_point_with_window_widths() {
+        let curve_params = secp256k1();
+        let curve = &curve_params.curve;
+        let base_point = &curve_params.base_point;
+
+        // Double-and-add
+        let mul_point_reference = |point: &Point, n: &BigInt| {
+            let mut result = Point::identity_element();
+            let mut base = point.clone();
+            for bit in n.le_bits() {
+                if bit {
+                    result = curve.add_points(&base, &result);
+                }
+                base = curve.double_point(&base);
+            }
+            result
+        };
+
+        let n_minus_1 = &curve_params.base_point_order - BigInt::one();
+        let scalars = [
+            BigInt::zero(),
+            BigInt::one(),
+            BigInt::from(7),
+            BigInt::from(0xff),
+            BigInt::from_hex("8000000000000000").unwrap(),
+            BigInt::from_hex(
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            )
+            .unwrap(),
+            BigInt::from_hex(
+                "c0ffee0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+            )
+            .unwrap(),
+            n_minus_1.clone(),
+        ];
+        for n in &scalars {
+            let expected = mul_point_reference(base_point, n);
+            for window_width in MUL_WINDOW_WIDTH_RANGE {
+                let config = MulConfig::new(window_width).unwrap();
+                assert_eq!(
+                    curve.mul_point_with_config(base_point, n, &config),
+                    expected
+                );
+            }
+        }
+
+        // (n - 1) * G = -G
+        let neg_base_point = curve.mul_point(base_point, &n_minus_1);
+        assert_eq!(neg_base_point.x, base_point.x);
+        assert_eq!(neg_base_point.y, &curve.p - &base_point.y);
+        assert!(curve
+            .mul_point(base_point, &curve_params.base_point_order)
+            .is_identity_element());
+    }
+
+    #[test]
+    fn test_mul_point_with_trivial_scalars() {
+        let curve_params = secp256k1();
+        let curve = &curve_params.curve;
+        let base_point = &curve_params.base_point;
+        let identity = Point::identity_element();
+
+        for window_width in MUL_WINDOW_WIDTH_RANGE {
+            let config = MulConfig::new(window_width).unwrap();
+            let mul =
+                |point: &Point, n: &BigInt| curve.mul_point_with_config(point, n, &config);
+
+            assert_eq!(mul(base_point, &BigInt::zero()), identity);
+            assert_eq!(mul(base_point, &BigInt::one()), *base_point);
+            assert_eq!(mul(base_point, &curve_params.base_point_order), identity);
+
+            assert_eq!(mul(&identity, &BigInt::zero()), identity);
+            assert_eq!(mul(&identity, &BigInt::one()), identity);
+            assert_eq!(mul(&identity, &BigInt::from(7)), identity);
+        }
+    }
+
+    #[test]
+    fn test_mul_config_window_width() {
+        assert_eq!(MulConfig::default().window_width(), 4);
+        for window_width in MUL_WINDOW_WIDTH_RANGE {
+            assert_eq!(
+                MulConfig::new(window_width).unwrap().window_width(),
+                window_width
+            );
+        }
+        assert_eq!(MulConfig::new(0), None);
+        assert_eq!(MulConfig::new(1), None);
+        assert_eq!(MulConfig::new(7), None);
+    }
+
     #[test]
     #[should_panic]
     fn test_adding_point_with_x_greater_than_p_should_panic() {
@@ -4,7 +4,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+pub(crate) mod barrett;
 pub(crate) mod elliptic_curve;
 pub(crate) mod modular;
 
-pub use elliptic_curve::{Curve, Point};
+pub use elliptic_curve::{Curve, MulConfig, Point, MUL_WINDOW_WIDTH_RANGE};
@@ -8,20 +8,26 @@
 
 use crate::bigint::bigint_core::BigInt;
 use crate::bigint::gcd::gcd;
+use crate::math::barrett::BarrettContext;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::fmt;
+#[cfg(feature = "std")]
+use core::fmt::Display;
 
 /// Calculates `a` modulo `n`,
 /// returning the least non-negative remainder of `a (mod n)`.
 ///
 /// Will panic if `n <= 0`.
 pub(crate) fn modulo(a: &BigInt, n: &BigInt) -> BigInt {
-    debug_assert!(n > &BigInt::zero());
+    debug_assert!(*n > 0);
 
-    let r = a % n;
-    if r < BigInt::zero() {
-        r + n
-    } else {
-        r
+    let mut r = a % n;
+    if r.is_sign_negative() && !r.is_zero() {
+        r += n;
     }
+    r
 }
 
 /// Returns the modulo multiplicative inverse of `a` under modulo `n`.
@@ -30,14 +36,14 @@ pub(crate) fn modulo(a: &BigInt, n: &BigInt) -> BigInt {
 pub(crate) fn invert(a: &BigInt, n: &BigInt) -> Option<BigInt> {
     // Employs extended Euclidean algorithm to compute the multiplicative inverse.
     // https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm#Computing_multiplicative_inverses_in_modular_structures
-    debug_assert!(n > &BigInt::one());
+    debug_assert!(*n > 1);
     debug_assert!(!a.is_zero());
 
     let a = modulo(a, n); // ensures a > 0
 
     let (_, y, v) = gcd(n, &a);
     // xn + ya = 1
-    if v != BigInt::one() {
+    if !v.is_one() {
         None
     } else {
         // ya = 1 mod n
@@ -45,12 +51,163 @@ pub(crate) fn invert(a: &BigInt, n: &BigInt) -> Option<BigInt> {
     }
 }
 
+/// Replaces every element of `values` with its modulo multiplicative inverse under
+/// modulo `n`, all at once.
+///
+/// Employs Montgomery's trick: the prefix products of `values` are inverted with a single
+/// inversion, from which the inverse of every element is recovered by back-substitution.
+/// The cost is one inversion plus about `3 * values.len()` modular multiplications,
+/// instead of `values.len()` inversions.
+///
+/// If an error is returned, `values` is left untouched.
+#[cfg(feature = "std")]
+pub(crate) fn batch_invert(values: &mut [BigInt], n: &BigInt) -> Result<(), BatchInvertError> {
+    debug_assert!(*n > 1);
+
+    let reduced: Vec<BigInt> = values.iter().map(|value| modulo(value, n)).collect();
+    if let Some(index) = reduced.iter().position(|value| value.is_zero()) {
+        return Err(BatchInvertError::ZeroElement { index });
+    }
+
+    if let [value] = values {
+        *value = invert(&reduced[0], n).ok_or(BatchInvertError::NotInvertible { index: 0 })?;
+        return Ok(());
+    }
+
+    // prefix_products[i] = values[0] * ... * values[i] mod n
+    let mut prefix_products: Vec<BigInt> = Vec::with_capacity(reduced.len());
+    for value in &reduced {
+        let product = match prefix_products.last() {
+            Some(last) => modulo(&(last * value), n),
+            None => value.clone(),
+        };
+        prefix_products.push(product);
+    }
+
+    let mut inverse = match prefix_products.last() {
+        None => return Ok(()),
+        Some(product) => match invert(product, n) {
+            Some(inverse) => inverse,
+            None => {
+                // Only reachable for a composite `n`, locates the culprit.
+                let index = reduced
+                    .iter()
+                    .position(|value| invert(value, n).is_none())
+                    .unwrap();
+                return Err(BatchInvertError::NotInvertible { index });
+            }
+        },
+    };
+
+    // With inverse = 1 / (values[0] * ... * values[i]):
+    // 1 / values[i] = inverse * prefix_products[i - 1]
+    // 1 / (values[0] * ... * values[i - 1]) = inverse * values[i]
+    for i in (1..values.len()).rev() {
+        values[i] = modulo(&(&inverse * &prefix_products[i - 1]), n);
+        inverse = modulo(&(&inverse * &reduced[i]), n);
+    }
+    values[0] = inverse;
+
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub(crate) enum BatchInvertError {
+    /// The element at `index` is zero modulo `n`.
+    ZeroElement { index: usize },
+    /// The element at `index` shares a factor with `n`, which is thus not prime.
+    NotInvertible { index: usize },
+}
+
+#[cfg(feature = "std")]
+impl Display for BatchInvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchInvertError::ZeroElement { index } => {
+                write!(f, "The element at index {index} is zero")
+            }
+            BatchInvertError::NotInvertible { index } => {
+                write!(f, "The element at index {index} is not invertible")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for BatchInvertError {}
+
+#[cfg(test)]
+thread_local! {
+    /// Counts the modular multiplications (squarings excluded) performed by
+    /// `invert_prime_ct` on the current thread.
+    static INVERT_PRIME_CT_MUL_COUNT: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+}
+
+/// Returns the modulo multiplicative inverse of `a` under the prime modulo `p`.
+///
+/// Computes `a ^ (p - 2) mod p` (Fermat's little theorem) with a left-to-right
+/// square-and-multiply of fixed length: for every bit of `p - 2`, a squaring and a
+/// multiplication are performed, and the product is discarded if the bit is 0.
+/// The number of operations therefore only depends on `p`, not on `a`,
+/// unlike the extended Euclidean algorithm in `invert`.
+///
+/// Note that the underlying `BigInt` arithmetic itself isn't constant-time.
+///
+/// It is important to note that `p` must be prime, otherwise the result returned is
+/// incorrect. Will panic if `a` is a multiple of `p`.
+#[cfg(feature = "std")]
+pub(crate) fn invert_prime_ct(a: &BigInt, p: &BigInt) -> BigInt {
+    debug_assert!(*p > 2);
+    debug_assert!(p.is_odd());
+
+    let a = modulo(a, p);
+    assert!(!a.is_zero());
+
+    let exp = p - BigInt::from(2);
+    let mut result = BigInt::one();
+    // The loop runs for the bit length of `p - 2`, which only depends on `p`. It is the
+    // bit length of `p` minus 1 for a Fermat prime, e.g., 3, 5 or 17, and the same
+    // otherwise.
+    for bit in exp.le_bits().into_iter().rev() {
+        result = &result * &result % p;
+        let product = &result * &a % p;
+        #[cfg(test)]
+        INVERT_PRIME_CT_MUL_COUNT.with(|count| count.set(count.get() + 1));
+        if bit {
+            result = product;
+        }
+    }
+
+    result
+}
+
 /// Raises `a` to the power of `exp` under modulo `n`.
-pub(crate) fn pow(a: &BigInt, exp: &BigInt, n: &BigInt) -> BigInt {
-    debug_assert!(a > &BigInt::zero());
+///
+/// If `barrett_context` is provided, which must be created for `n`, the reductions are
+/// done with Barrett reduction instead of divisions. The context can be shared among the
+/// calls with the same `n`.
+pub(crate) fn pow(
+    a: &BigInt,
+    exp: &BigInt,
+    n: &BigInt,
+    barrett_context: Option<&BarrettContext>,
+) -> BigInt {
+    debug_assert!(*a > 0);
     debug_assert!(a < n);
-    debug_assert!(exp >= &BigInt::zero());
-    debug_assert!(n > &BigInt::zero());
+    debug_assert!(*exp >= 0);
+    debug_assert!(*n > 0);
+    debug_assert!(barrett_context.is_none_or(|context| context.modulus() == n));
+
+    let mul_mod = |a: &BigInt, b: &BigInt| match barrett_context {
+        Some(context) => context.mul(a, b),
+        None => {
+            let mut product = a * b;
+            product %= n;
+            product
+        }
+    };
 
     let zero = BigInt::zero();
     let mut result = BigInt::one();
@@ -59,10 +216,10 @@ pub(crate) fn pow(a: &BigInt, exp: &BigInt, n: &BigInt) -> BigInt {
 
     while exp > zero {
         if exp.is_odd() {
-            result = &result * &base % n;
+            result = mul_mod(&result, &base);
         }
         exp = exp >> 1;
-        base = &base * &base % n;
+        base = mul_mod(&base, &base);
     }
 
     result
@@ -73,6 +230,7 @@ pub(crate) fn pow(a: &BigInt, exp: &BigInt, n: &BigInt) -> BigInt {
 ///
 /// It is important to note that `p` must be prime, otherwise either the execution may enter an infinite loop
 /// or the result returned is incorrect.
+#[cfg(feature = "std")]
 pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
     // Employs the Tonelli–Shanks algorithm:
     // https://www.maa.org/sites/default/files/pdf/upload_library/22/Polya/07468342.di020786.02p0470a.pdf
@@ -95,9 +253,11 @@ pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
     assert!(a < p);
 
     let p_minus_1 = p - &one;
+    let context = BarrettContext::new(p);
+    let pow = |a: &BigInt, exp: &BigInt| pow(a, exp, p, Some(&context));
 
     // a ^ ((p - 1) / 2) mod p
-    let t = pow(a, &(&p_minus_1 / &two), p);
+    let t = pow(a, &(&p_minus_1 / &two));
     if t != one {
         // no square root
         return None;
@@ -111,13 +271,13 @@ pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
     let mut n = BigInt::from(2);
     let t1 = &p_minus_1 / &two;
     let t2 = modulo(&BigInt::from(-1), p);
-    while pow(&n, &t1, p) != t2 {
-        n = n + &one;
+    while pow(&n, &t1) != t2 {
+        n += &one;
     }
 
-    let mut x = pow(a, &((&s + &one) / &two), p);
-    let mut b = pow(a, &s, p);
-    let mut g = pow(&n, &s, p);
+    let mut x = pow(a, &((&s + &one) / &two));
+    let mut b = pow(a, &s);
+    let mut g = pow(&n, &s);
     let mut r = e;
 
     loop {
@@ -139,7 +299,7 @@ pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
             // t * t = b ^ (2 ^ m) * b ^ (2 ^ m) =
             // b ^ ((2 ^ m) + (2 ^ m)) = b ^ (2 * (2 ^ m))
             // b ^ (2 ^ (m + 1))
-            t = &t * &t % p;
+            t = context.mul(&t, &t);
         };
 
         if m == 0 {
@@ -147,9 +307,9 @@ pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
             return Some(roots);
         }
 
-        x = x * pow(&g, &(BigInt::one() << (r - m - 1)), p) % p;
-        b = b * pow(&g, &(BigInt::one() << (r - m)), p) % p;
-        g = pow(&g, &(BigInt::one() << (r - m)), p);
+        x = context.mul(&x, &pow(&g, &(BigInt::one() << (r - m - 1))));
+        b = context.mul(&b, &pow(&g, &(BigInt::one() << (r - m))));
+        g = pow(&g, &(BigInt::one() << (r - m)));
         r = m
     }
 }
@@ -157,6 +317,12 @@ pub(crate) fn sqrt(a: &BigInt, p: &BigInt) -> Option<(BigInt, BigInt)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::codecs::hex;
+    use crate::crypto::hash::{Sha256, UnkeyedHash};
+    use crate::crypto::secp256k1;
+    use crate::testing_tools::quickcheck::BigIntHexString;
+    use quickcheck::TestResult;
+    use quickcheck_macros::quickcheck;
 
     #[test]
     fn test_modulo() {
@@ -169,21 +335,164 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_invert_prime_ct() {
+        let curve_params = secp256k1();
+        let primes = [
+            BigInt::from(3),
+            BigInt::from(17),
+            BigInt::from(40961),
+            curve_params.base_point_order.clone(),
+            curve_params.curve.p.clone(),
+        ];
+        for p in primes {
+            let one = BigInt::one();
+            let p_minus_1 = &p - &one;
+            assert_eq!(invert_prime_ct(&one, &p), one);
+            assert_eq!(invert_prime_ct(&p_minus_1, &p), p_minus_1);
+            assert_eq!(invert_prime_ct(&-&one, &p), p_minus_1);
+            assert_eq!(invert_prime_ct(&(&p + &one), &p), one);
+        }
+    }
+
+    #[quickcheck]
+    fn invert_prime_ct_matches_invert(hex: BigIntHexString) -> TestResult {
+        let a = BigInt::from_hex(hex.0).unwrap();
+        let n = &secp256k1().base_point_order;
+        if modulo(&a, n).is_zero() {
+            return TestResult::discard();
+        }
+        TestResult::from_bool(invert_prime_ct(&a, n) == invert(&a, n).unwrap())
+    }
+
+    #[test]
+    fn test_invert_prime_ct_mul_count() {
+        let n = &secp256k1().base_point_order;
+        let data = [
+            BigInt::one(),
+            BigInt::one() << 255,
+            n - BigInt::one(),
+            BigInt::from_hex(
+                "5555555555555555555555555555555555555555555555555555555555555555",
+            )
+            .unwrap(),
+        ];
+        for a in data {
+            INVERT_PRIME_CT_MUL_COUNT.with(|count| count.set(0));
+            invert_prime_ct(&a, n);
+            let mul_count = INVERT_PRIME_CT_MUL_COUNT.with(|count| count.get());
+            assert_eq!(mul_count, n.bit_len());
+        }
+    }
+
+    /// Returns `len` pseudorandom elements in [1, n - 1], n: the order of secp256k1.
+    fn pseudorandom_elements(len: usize) -> Vec<BigInt> {
+        let n = &secp256k1().base_point_order;
+        (0..len)
+            .map(|i| {
+                let hash = Sha256::new().digest(i.to_be_bytes());
+                modulo(&BigInt::from_hex(hex::encode_lower(&hash)).unwrap(), n)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_invert() {
+        let n = &secp256k1().base_point_order;
+        for len in [0, 1, 2, 100] {
+            let mut values = pseudorandom_elements(len);
+            let expected: Vec<BigInt> = values.iter().map(|a| invert(a, n).unwrap()).collect();
+            batch_invert(&mut values, n).unwrap();
+            assert_eq!(values, expected);
+        }
+
+        let n_minus_1 = n - BigInt::one();
+        let mut values = vec![
+            BigInt::one(),
+            n_minus_1.clone(),
+            BigInt::from(-1),
+            n + BigInt::one(),
+        ];
+        batch_invert(&mut values, n).unwrap();
+        assert_eq!(
+            values,
+            [BigInt::one(), n_minus_1.clone(), n_minus_1, BigInt::one()]
+        );
+    }
+
+    #[test]
+    fn test_batch_invert_errors() {
+        let n = &secp256k1().base_point_order;
+        let mut values = pseudorandom_elements(10);
+        values[7] = n.clone();
+        let original = values.clone();
+        assert_eq!(
+            batch_invert(&mut values, n),
+            Err(BatchInvertError::ZeroElement { index: 7 })
+        );
+        assert_eq!(values, original);
+
+        assert_eq!(
+            batch_invert(&mut [BigInt::zero()], n),
+            Err(BatchInvertError::ZeroElement { index: 0 })
+        );
+
+        // 15 = 3 * 5
+        let n = BigInt::from(15);
+        let mut values = [BigInt::from(2), BigInt::from(4), BigInt::from(6)];
+        assert_eq!(
+            batch_invert(&mut values, &n),
+            Err(BatchInvertError::NotInvertible { index: 2 })
+        );
+        assert_eq!(
+            batch_invert(&mut values[2..], &n),
+            Err(BatchInvertError::NotInvertible { index: 0 })
+        );
+        batch_invert(&mut values[..2], &n).unwrap();
+        assert_eq!(values[..2], [BigInt::from(8), BigInt::from(4)]);
+    }
+
     #[test]
     fn test_pow() {
         // Tests the basic cases
         let three = BigInt::from(3);
         let n = BigInt::from(u64::MAX);
         for exp in 0..29 {
-            let result = pow(&three, &BigInt::from(exp), &n);
+            let result = pow(&three, &BigInt::from(exp), &n, None);
             assert_eq!(result, BigInt::from(3_u64.pow(exp)));
         }
 
         // 78 ** 12 % 123 = 57
-        let result = pow(&BigInt::from(78), &BigInt::from(12), &BigInt::from(123));
+        let result = pow(
+            &BigInt::from(78),
+            &BigInt::from(12),
+            &BigInt::from(123),
+            None,
+        );
         assert_eq!(result, BigInt::from(57));
     }
 
+    #[test]
+    fn test_pow_with_barrett_context() {
+        // (a, exp, n)
+        let data = [
+            ("4e", "0c", "7b"),
+            ("03", "00", "ffffffffffffffff"),
+            (
+                "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2d",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            ),
+        ];
+        for (a_hex, exp_hex, n_hex) in data {
+            let a = BigInt::from_hex(a_hex).unwrap();
+            let exp = BigInt::from_hex(exp_hex).unwrap();
+            let n = BigInt::from_hex(n_hex).unwrap();
+            let context = BarrettContext::new(&n);
+            assert_eq!(pow(&a, &exp, &n, Some(&context)), pow(&a, &exp, &n, None));
+        }
+    }
+
     #[test]
     fn test_sqrt() {
         // (a, p, root1, root2)
@@ -26,6 +26,9 @@ pub(crate) mod libc_internal {
     }
 }
 
+/// "Interrupted system call", see `man errno`.
+pub(crate) const EINTR: LibcErrno = 4;
+
 #[cfg(test)]
 pub(crate) use self::mock_libc_internal as libc;
 #[cfg(not(test))]
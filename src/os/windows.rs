@@ -42,7 +42,7 @@ use crate::os::NtStatus;
 #[cfg(not(test))]
 pub(crate) use bcrypt_internal as bcrypt;
 
-/// Fills `dest` with random bytes.
+/// Fills `dest`, at most `u32::MAX` bytes, with random bytes.
 ///
 /// Returns a [`NtStatus`] that indicates the success or failure of the function.
 pub(crate) fn bcrypt_gen_random(dest: &mut [u8]) -> NtStatus {
@@ -0,0 +1,64 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Re-exports the supported types and traits of the crate.
+//!
+//! The functions, constants and modules are used at their paths.
+//!
+//! ```
+//! use lightcryptotools::crypto::codecs::hex;
+//! use lightcryptotools::prelude::*;
+//!
+//! let digest = Keccak256::new().digest(b"");
+//! assert_eq!(
+//!     hex::encode_lower(&digest),
+//!     "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+//! );
+//! ```
+//!
+//! The items requiring the feature "std" are re-exported only with it. The traits of
+//! `tools::codable` are sealed: the crate implements them for its types, and implementing
+//! them elsewhere requires the feature "unstable-codable". `SszType` is the extension point
+//! of the SSZ codec instead.
+
+pub use crate::bigint::{BigInt, BigUint, Sign};
+pub use crate::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+pub use crate::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+pub use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+pub use crate::blockchain::ethereum::rlp::schema::{RlpSchema, RlpValue};
+pub use crate::blockchain::ethereum::rlp::RlpItemType;
+pub use crate::crypto::hash::{
+    Keccak256, Sha256, Sha384, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha512, UnkeyedHash,
+};
+pub use crate::math::{Curve, MulConfig, Point};
+pub use crate::tools::codable::{Decodable, DecodingItem, Encodable, EncodingItem};
+
+#[cfg(feature = "std")]
+pub use crate::blockchain::ethereum::account::{
+    EoaPrivateKey, EoaPrivateKeyData, EoaPublicKey, NonceManager,
+};
+#[cfg(feature = "std")]
+pub use crate::blockchain::ethereum::block::BlockHeader;
+#[cfg(feature = "std")]
+pub use crate::blockchain::ethereum::ssz::{
+    SszDataDecodingError, SszDecodingItem, SszEncodingItem, SszType,
+};
+#[cfg(feature = "std")]
+pub use crate::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip155, TransactionEip1559, TransactionEip1559Ref,
+    TransactionEip2930, TransactionLegacy,
+};
+#[cfg(feature = "std")]
+pub use crate::blockchain::ethereum::types::{
+    AccessList, AccessListItem, Address, ChainId, EoaNonce, StorageKey, Wei,
+};
+#[cfg(feature = "std")]
+pub use crate::crypto::ecdsa::{
+    PrecomputedPublicKey, PrivateKey, PublicKey, RecoveryOptions, Signature,
+    SignatureRecoveryId, SigningOptions, VerifyingOptions,
+};
+#[cfg(feature = "std")]
+pub use crate::crypto::EllipticCurveParams;
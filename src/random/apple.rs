@@ -9,13 +9,13 @@
 use super::error::GetOsRandomBytesError;
 use crate::os::apple::sec_random_copy_bytes;
 
-/// Returns cryptographically secure random bytes with the specified `len`.
-pub(crate) fn get_os_random_bytes_impl(len: u32) -> Result<Vec<u8>, GetOsRandomBytesError> {
-    let mut bytes = vec![0u8; len as usize];
-
-    let ret = sec_random_copy_bytes(&mut bytes);
+/// Fills `dest` with cryptographically secure random bytes.
+///
+/// `SecRandomCopyBytes` fills the buffer in one call, without short reads or interruptions.
+pub(crate) fn fill_os_random_bytes_impl(dest: &mut [u8]) -> Result<(), GetOsRandomBytesError> {
+    let ret = sec_random_copy_bytes(dest);
     if ret == 0 {
-        Ok(bytes)
+        Ok(())
     } else {
         Err(GetOsRandomBytesError::AppleSecRandom(ret))
     }
@@ -23,10 +23,15 @@ pub(crate) fn get_os_random_bytes_impl(len: u32) -> Result<Vec<u8>, GetOsRandomB
 
 #[cfg(test)]
 mod tests {
-    use super::get_os_random_bytes_impl;
+    use super::fill_os_random_bytes_impl;
     use crate::os::apple::security;
     use crate::random::GetOsRandomBytesError;
 
+    fn get_os_random_bytes_impl(len: usize) -> Result<Vec<u8>, GetOsRandomBytesError> {
+        let mut bytes = vec![0u8; len];
+        fill_os_random_bytes_impl(&mut bytes).map(|_| bytes)
+    }
+
     #[test]
     fn mock_test_get_os_random_bytes_impl() {
         // success
@@ -40,10 +45,10 @@ mod tests {
         {
             let ctx = security::SecRandomCopyBytes_context();
             ctx.expect().return_const(-36);
-            assert_eq!(
-                get_os_random_bytes_impl(16).unwrap_err(),
-                GetOsRandomBytesError::AppleSecRandom(-36)
-            );
+            let err = get_os_random_bytes_impl(16).unwrap_err();
+            assert_eq!(err, GetOsRandomBytesError::AppleSecRandom(-36));
+            // The platform code is kept for logging
+            assert_eq!(err.to_string(), "SecRandomCopyBytes failed with status -36");
         }
     }
 }
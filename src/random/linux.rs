@@ -1,8 +1,8 @@
 use super::error::GetOsRandomBytesError;
-use crate::os::linux::getrandom;
+use crate::os::linux::{getrandom, EINTR};
 
-/// Returns cryptographically secure random bytes with the specified `len`.
-pub(crate) fn get_os_random_bytes_impl(len: u32) -> Result<Vec<u8>, GetOsRandomBytesError> {
+/// Fills `dest` with cryptographically secure random bytes.
+pub(crate) fn fill_os_random_bytes_impl(dest: &mut [u8]) -> Result<(), GetOsRandomBytesError> {
     // Limits "chunk length" to 256 bytes.
     //
     // `man getrandom`:
@@ -13,35 +13,57 @@ pub(crate) fn get_os_random_bytes_impl(len: u32) -> Result<Vec<u8>, GetOsRandomB
     // buffer sizes.  For example, if the call is interrupted by a
     // signal handler, it may return a partially filled buffer, or fail
     // with the error EINTR.
+    //
+    // The guarantees don't hold before the initialization, hence the short reads and
+    // EINTR are still handled, by requesting the rest and retrying respectively.
     const CHUNK_BYTES_LEN: usize = 256;
 
-    let mut bytes = vec![0u8; len as usize];
-    for chunk in bytes.chunks_mut(CHUNK_BYTES_LEN) {
-        match getrandom(chunk) {
+    let mut remaining = dest;
+    while !remaining.is_empty() {
+        let chunk_len = remaining.len().min(CHUNK_BYTES_LEN);
+        match getrandom(&mut remaining[..chunk_len]) {
             Ok(len) => {
-                if usize::try_from(len).unwrap() != chunk.len() {
+                let len = usize::try_from(len).unwrap();
+                if len == 0 {
+                    // Never spins on a routine copying nothing.
                     return Err(
                         GetOsRandomBytesError::LinuxGetRandomCopiedNumberLessThanRequested,
                     );
                 }
+                remaining = &mut remaining[len..];
             }
+            Err(EINTR) => continue,
             Err(errno) => return Err(GetOsRandomBytesError::LinuxGetRandom(errno)),
         }
     }
 
-    Ok(bytes)
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::get_os_random_bytes_impl;
-    use crate::os::linux::libc;
+    use super::fill_os_random_bytes_impl;
+    use crate::os::linux::{libc, EINTR};
     use crate::os::LibcErrno;
     use crate::random::GetOsRandomBytesError;
+    use std::sync::{Mutex, MutexGuard};
+
+    // The mocked functions are global, the tests setting their expectations run one by one.
+    static MOCK_LOCK: Mutex<()> = Mutex::new(());
+
+    fn lock_mock() -> MutexGuard<'static, ()> {
+        MOCK_LOCK.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn get_os_random_bytes_impl(len: usize) -> Result<Vec<u8>, GetOsRandomBytesError> {
+        let mut bytes = vec![0u8; len];
+        fill_os_random_bytes_impl(&mut bytes).map(|_| bytes)
+    }
 
     #[test]
-    #[should_panic]
     fn mock_test_get_os_random_bytes_impl() {
+        let _guard = lock_mock();
+
         // success
         {
             let ctx = libc::getrandom_context();
@@ -57,28 +79,63 @@ mod tests {
             let mut errno: LibcErrno = 35;
             ctx2.expect()
                 .returning(move || (&mut errno) as *mut LibcErrno);
-            assert_eq!(
-                get_os_random_bytes_impl(16).unwrap_err(),
-                GetOsRandomBytesError::LinuxGetRandom(35)
-            );
+            let err = get_os_random_bytes_impl(16).unwrap_err();
+            assert_eq!(err, GetOsRandomBytesError::LinuxGetRandom(35));
+            // The platform code is kept for logging
+            assert_eq!(err.to_string(), "getrandom failed with errno 35");
         }
 
-        // getrandom returns a number less than requested
+        // getrandom copies nothing
         {
             let ctx = libc::getrandom_context();
-            ctx.expect().return_const(8_isize);
+            ctx.expect().return_const(0_isize);
             assert_eq!(
                 get_os_random_bytes_impl(16).unwrap_err(),
                 GetOsRandomBytesError::LinuxGetRandomCopiedNumberLessThanRequested
             );
         }
+    }
 
-        // panic: getrandom returns < -1
-        // This situation should never happen
-        {
-            let ctx = libc::getrandom_context();
-            ctx.expect().return_const(-6_isize);
-            assert_eq!(get_os_random_bytes_impl(16).unwrap(), vec![0u8; 16]);
+    #[test]
+    fn mock_test_get_os_random_bytes_impl_short_reads_and_eintr() {
+        let _guard = lock_mock();
+
+        // Every third call fails with EINTR, the others copy at most 100 bytes.
+        let ctx1 = libc::getrandom_context();
+        let mut calls = 0;
+        ctx1.expect().returning(move |buffer, length, _| {
+            assert!(length <= 256);
+            calls += 1;
+            if calls % 3 == 0 {
+                return -1;
+            }
+            let len = length.min(100);
+            unsafe { std::slice::from_raw_parts_mut(buffer, len) }.fill(calls as u8);
+            len as isize
+        });
+        let ctx2 = libc::__errno_location_context();
+        let mut errno: LibcErrno = EINTR;
+        ctx2.expect()
+            .returning(move || (&mut errno) as *mut LibcErrno);
+
+        let bytes = get_os_random_bytes_impl(1000).unwrap();
+        assert_eq!(bytes.len(), 1000);
+        assert!(bytes.iter().all(|&x| x != 0));
+        // The calls 1, 2, 4, 5, ... fill 100 bytes each
+        let expected_values: Vec<u8> = (1..15).filter(|x| x % 3 != 0).collect();
+        for (chunk, value) in bytes.chunks(100).zip(expected_values) {
+            assert!(chunk.iter().all(|&x| x == value));
         }
     }
+
+    // This situation should never happen
+    #[test]
+    #[should_panic]
+    fn mock_test_get_os_random_bytes_impl_invalid_return_value() {
+        let _guard = lock_mock();
+
+        let ctx = libc::getrandom_context();
+        ctx.expect().return_const(-6_isize);
+        let _ = get_os_random_bytes_impl(16);
+    }
 }
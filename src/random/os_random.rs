@@ -14,23 +14,40 @@ pub mod generator_internal {
 
     /// Returns cryptographically secure random bytes with the specified `len`.
     ///
+    /// See [`fill_random_bytes`] for the details.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the underlying system routine fails.
+    pub fn get_os_random_bytes(len: u32) -> Result<Vec<u8>, GetOsRandomBytesError> {
+        let mut bytes = vec![0u8; len as usize];
+        fill_random_bytes(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Fills `dest` with cryptographically secure random bytes.
+    ///
     /// The random bytes is provided by an operating system routine:
     /// - `getrandom(2)` on Linux.
     /// - `SecRandomCopyBytes` on iOS and macOS.
     /// - `BCryptGenRandom` on Windows.
     ///
+    /// The routine is called until `dest` is filled, in chunks within its limit per call.
+    /// On Linux, short reads are continued and calls interrupted by signals (`EINTR`) retried.
+    ///
     /// # Errors
     ///
-    /// Will return an error if the underlying system routine fails.
-    pub fn get_os_random_bytes(len: u32) -> Result<Vec<u8>, GetOsRandomBytesError> {
+    /// Will return an error, with the code of the platform, if the underlying system
+    /// routine fails.
+    pub fn fill_random_bytes(dest: &mut [u8]) -> Result<(), GetOsRandomBytesError> {
         #[cfg(target_os = "macos")]
-        use crate::random::apple::get_os_random_bytes_impl;
+        use crate::random::apple::fill_os_random_bytes_impl;
         #[cfg(target_os = "linux")]
-        use crate::random::linux::get_os_random_bytes_impl;
+        use crate::random::linux::fill_os_random_bytes_impl;
         #[cfg(target_os = "windows")]
-        use crate::random::windows::get_os_random_bytes_impl;
+        use crate::random::windows::fill_os_random_bytes_impl;
 
-        get_os_random_bytes_impl(len)
+        fill_os_random_bytes_impl(dest)
     }
 }
 
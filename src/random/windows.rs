@@ -9,28 +9,36 @@
 use super::error::GetOsRandomBytesError;
 use crate::os::windows::bcrypt_gen_random;
 
-/// Returns cryptographically secure random bytes with the specified `len`.
-pub(crate) fn get_os_random_bytes_impl(len: u32) -> Result<Vec<u8>, GetOsRandomBytesError> {
-    let mut bytes = vec![0u8; len as usize];
-
-    let status = bcrypt_gen_random(&mut bytes);
-
-    // The two highest bits represent Severity.
-    // Severity code 0x3 represents error. See [`NtStatus`]
-    if status >> 30 == 0x3 {
-        Err(GetOsRandomBytesError::WindowsBCryptGenRandom(status))
-    } else {
-        Ok(bytes)
+/// Fills `dest` with cryptographically secure random bytes.
+pub(crate) fn fill_os_random_bytes_impl(dest: &mut [u8]) -> Result<(), GetOsRandomBytesError> {
+    // The length parameter of `BCryptGenRandom` is a ULONG, 32 bits.
+    const CHUNK_BYTES_LEN: usize = u32::MAX as usize;
+
+    for chunk in dest.chunks_mut(CHUNK_BYTES_LEN) {
+        let status = bcrypt_gen_random(chunk);
+
+        // The two highest bits represent Severity.
+        // Severity code 0x3 represents error. See [`NtStatus`]
+        if status >> 30 == 0x3 {
+            return Err(GetOsRandomBytesError::WindowsBCryptGenRandom(status));
+        }
     }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::get_os_random_bytes_impl;
+    use super::fill_os_random_bytes_impl;
     use crate::os::windows::bcrypt;
     use crate::os::NtStatus;
     use crate::random::GetOsRandomBytesError;
 
+    fn get_os_random_bytes_impl(len: usize) -> Result<Vec<u8>, GetOsRandomBytesError> {
+        let mut bytes = vec![0u8; len];
+        fill_os_random_bytes_impl(&mut bytes).map(|_| bytes)
+    }
+
     #[test]
     fn mock_test_get_os_random_bytes_impl() {
         // success
@@ -45,10 +53,16 @@ mod tests {
             const STATUS_INVALID_PARAMETER: NtStatus = 0xC000000D;
             let ctx = bcrypt::BCryptGenRandom_context();
             ctx.expect().return_const(STATUS_INVALID_PARAMETER);
+            let err = get_os_random_bytes_impl(16).unwrap_err();
             assert_eq!(
-                get_os_random_bytes_impl(16).unwrap_err(),
+                err,
                 GetOsRandomBytesError::WindowsBCryptGenRandom(STATUS_INVALID_PARAMETER)
             );
+            // The platform code is kept for logging
+            assert_eq!(
+                err.to_string(),
+                "BCryptGenRandom failed with status 3221225485"
+            );
         }
     }
 }
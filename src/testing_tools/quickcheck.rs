@@ -4,6 +4,11 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use crate::bigint::{BigInt, Sign};
+use crate::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use crate::crypto::secp256k1;
+use crate::math::modular::modulo;
+use crate::tools::codable::{Encodable, EncodingItem};
 use quickcheck::{Arbitrary, Gen};
 
 #[derive(Debug)]
@@ -76,3 +81,143 @@ impl Arbitrary for BigIntHexString {
         Self(String::from(from_utf8(&v_char).unwrap()))
     }
 }
+
+/// Generates the big-endian bytes of a random `bit_len`-bit unsigned integer:
+/// the most significant bit is always set.
+fn arbitrary_be_bytes_with_bit_len(g: &mut Gen, bit_len: usize) -> Vec<u8> {
+    assert!(bit_len > 0);
+
+    let mut bytes: Vec<u8> = (0..bit_len.div_ceil(8)).map(|_| u8::arbitrary(g)).collect();
+    let top_bit_len = (bit_len - 1) % 8 + 1;
+    bytes[0] &= u8::MAX >> (8 - top_bit_len);
+    bytes[0] |= 1 << (top_bit_len - 1);
+    bytes
+}
+
+/// A random positive BigInt of exactly `BITS` bits, regardless of the size of `Gen`.
+#[derive(Clone, Debug)]
+pub(crate) struct BigIntFixedBitLen<const BITS: usize>(pub(crate) BigInt);
+
+impl<const BITS: usize> Arbitrary for BigIntFixedBitLen<BITS> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let bytes = arbitrary_be_bytes_with_bit_len(g, BITS);
+        Self(BigInt::from_be_bytes(&bytes, Sign::Positive))
+    }
+}
+
+pub(crate) type BigInt256 = BigIntFixedBitLen<256>;
+pub(crate) type BigInt512 = BigIntFixedBitLen<512>;
+
+/// A random scalar in [1, n - 1], where n is the order of the base point of secp256k1,
+/// e.g., a private key or a nonce.
+#[derive(Clone, Debug)]
+pub(crate) struct ArbitraryEcScalar(pub(crate) BigInt);
+
+impl Arbitrary for ArbitraryEcScalar {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n = &secp256k1().base_point_order;
+        let n_minus_one = n - BigInt::one();
+
+        // Picks a boundary value once in a while.
+        if g.choose(&[true, false, false, false, false]) == Some(&true) {
+            let boundaries = [
+                BigInt::one(),
+                BigInt::from(2),
+                &n_minus_one - BigInt::one(),
+                n_minus_one,
+            ];
+            return Self(g.choose(&boundaries).unwrap().clone());
+        }
+
+        let bytes = arbitrary_be_bytes_with_bit_len(g, n.bit_len() + 64);
+        let value = modulo(&BigInt::from_be_bytes(&bytes, Sign::Positive), &n_minus_one);
+        Self(value + BigInt::one())
+    }
+}
+
+/// Random data prefixed with the first byte of a long-form RLP header, within
+/// [0xb8, 0xbf] or [0xf8, 0xff].
+///
+/// The declared length of the payload is often absurdly large, and the data is often
+/// truncated within the header or the payload.
+#[derive(Clone, Debug)]
+pub(crate) struct RlpLongHeaderData(pub(crate) Vec<u8>);
+
+impl Arbitrary for RlpLongHeaderData {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let first_bytes: Vec<u8> = (0xb8..=0xbf).chain(0xf8..=0xff).collect();
+        let first = *g.choose(&first_bytes).unwrap();
+        let byte_length_of_payload_byte_length = (first & 0x3f) as usize - 0x37;
+
+        let mut data = vec![first];
+        if bool::arbitrary(g) {
+            data.extend(vec![0xff; byte_length_of_payload_byte_length]);
+        } else {
+            data.extend((0..byte_length_of_payload_byte_length).map(|_| u8::arbitrary(g)));
+        }
+        data.extend(Vec::<u8>::arbitrary(g));
+
+        // Truncates the header or the payload.
+        let len = usize::arbitrary(g) % (data.len() + 1);
+        if bool::arbitrary(g) {
+            data.truncate(len);
+        }
+        Self(data)
+    }
+}
+
+/// An RLP item encoded by `RlpEncodingItem`, of nested lists, integers and byte strings
+/// around the boundaries of the encoding forms.
+#[derive(Clone, Debug)]
+pub(crate) struct RlpEncodedData(pub(crate) Vec<u8>);
+
+impl Arbitrary for RlpEncodedData {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut encoding_item = RlpEncodingItem::new();
+        encode_arbitrary_rlp_item(g, &mut encoding_item, 4);
+        Self(encoding_item.take_data())
+    }
+}
+
+fn encode_arbitrary_rlp_item(g: &mut Gen, encoding_item: &mut RlpEncodingItem, depth: usize) {
+    match u8::arbitrary(g) % if depth == 0 { 4 } else { 5 } {
+        0 => u64::arbitrary(g).encode_to(encoding_item),
+        1 => encoding_item.encode_bytes(&[u8::arbitrary(g)]),
+        2 => encoding_item.encode_bytes(&Vec::<u8>::arbitrary(g)),
+        3 => {
+            let len = 50 + usize::arbitrary(g) % 300;
+            let bytes: Vec<u8> = (0..len).map(|_| u8::arbitrary(g)).collect();
+            encoding_item.encode_bytes(&bytes);
+        }
+        _ => {
+            let mut items_encoding_item = RlpEncodingItem::new();
+            for _ in 0..(usize::arbitrary(g) % 8) {
+                encode_arbitrary_rlp_item(g, &mut items_encoding_item, depth - 1);
+            }
+            encoding_item.encode_list_payload(&mut items_encoding_item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn bigint_fixed_bit_len(a: BigInt256, b: BigInt512, c: BigIntFixedBitLen<9>) -> bool {
+        a.0.bit_len() == 256 && b.0.bit_len() == 512 && c.0.bit_len() == 9
+    }
+
+    #[quickcheck]
+    fn rlp_long_header_data_prefixed(data: RlpLongHeaderData) -> bool {
+        data.0
+            .first()
+            .is_none_or(|first| matches!(first, 0xb8..=0xbf | 0xf8..=0xff))
+    }
+
+    #[quickcheck]
+    fn arbitrary_ec_scalar_in_range(k: ArbitraryEcScalar) -> bool {
+        !k.0.is_zero() && !k.0.is_sign_negative() && k.0 < secp256k1().base_point_order
+    }
+}
@@ -8,6 +8,7 @@
 
 use super::decodable::{Decodable, DecodingItem};
 use super::encodable::{Encodable, EncodingItem};
+use alloc::vec::Vec;
 
 /// Encodes `value` to bytes.
 pub fn encode<T: Encodable<E>, E: EncodingItem>(value: &T) -> Vec<u8> {
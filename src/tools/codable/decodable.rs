@@ -4,14 +4,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use super::sealed::{Sealed, SealedItem};
+
 /// Trait for data structure decoding (deserializing).
-pub trait Decodable<'a, D: DecodingItem<'a>>: Sized {
+pub trait Decodable<'a, D: DecodingItem<'a>>: Sized + Sealed<D> {
     /// Decodes a `Self` from a `DecodingItem`.
     fn decode_from(decoding_item: &D) -> Result<Self, D::Error>;
 }
 
 /// Trait for providing the decoding operations.
-pub trait DecodingItem<'a>: Sized {
+pub trait DecodingItem<'a>: Sized + SealedItem {
     type Error;
 
     /// Creates a `DecodingItem` from `data`.
@@ -4,14 +4,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use super::sealed::{Sealed, SealedItem};
+use alloc::vec::Vec;
+
 /// Trait for data structure encoding (serializing).
-pub trait Encodable<E: EncodingItem> {
+pub trait Encodable<E: EncodingItem>: Sealed<E> {
     /// Encodes `self` to a `EncodingItem`.
     fn encode_to(&self, encoding_item: &mut E);
 }
 
 /// Trait for providing the encoding operations.
-pub trait EncodingItem {
+pub trait EncodingItem: SealedItem {
     fn new() -> Self;
 
     /// Returns encoded data and resets the internal state.
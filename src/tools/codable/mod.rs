@@ -7,13 +7,19 @@
 //! Traits for encoding (serializing) and decoding (deserializing) Rust data structures.
 //!
 //! TODO: These traits are designed explicitly for RLP and are overly simplified and unstable.
+//! They are sealed: implementing them outside the crate requires the feature
+//! "unstable-codable", which is exempt from semver.
 
 mod core;
 mod decodable;
 mod encodable;
+mod sealed;
 
 pub use self::core::decode;
 pub use self::core::encode;
 
 pub use decodable::*;
 pub use encodable::*;
+pub(crate) use sealed::seal_codable;
+#[cfg(not(feature = "unstable-codable"))]
+pub(crate) use sealed::{Sealed, SealedItem};
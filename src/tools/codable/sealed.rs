@@ -0,0 +1,44 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Seals the codable traits: the supertraits can't be named outside the crate, so only
+//! the crate implements `Encodable`, `Decodable` and the items, unless the feature
+//! "unstable-codable" is enabled.
+//!
+//! The SSZ codec stays open through `SszType`, which any type may implement.
+
+/// The supertrait of `Encodable<I>` and `Decodable<'a, I>`, where `I` is the item.
+pub trait Sealed<I> {}
+
+/// The supertrait of `EncodingItem` and `DecodingItem`.
+pub trait SealedItem {}
+
+#[cfg(feature = "unstable-codable")]
+impl<T: ?Sized, I> Sealed<I> for T {}
+
+#[cfg(feature = "unstable-codable")]
+impl<T: ?Sized> SealedItem for T {}
+
+/// Implements `Sealed<$item>` for the types, e.g.,
+/// `seal_codable!(<'a> RlpDecodingItem<'a> => Address, ChainId)`.
+///
+/// Generic types are sealed with their own `impl` blocks, under the same `cfg`.
+macro_rules! seal_codable {
+    (<$lt:lifetime> $item:ty => $($T:ty),+ $(,)?) => {
+        $(
+            #[cfg(not(feature = "unstable-codable"))]
+            impl<$lt> $crate::tools::codable::Sealed<$item> for $T {}
+        )+
+    };
+    ($item:ty => $($T:ty),+ $(,)?) => {
+        $(
+            #[cfg(not(feature = "unstable-codable"))]
+            impl $crate::tools::codable::Sealed<$item> for $T {}
+        )+
+    };
+}
+
+pub(crate) use seal_codable;
@@ -0,0 +1,49 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::{bench, pseudo_random_bytes};
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::secp256k1;
+
+/// Returns a `bit_len`-bit positive integer, the most significant bit of which is set.
+fn operand(bit_len: usize, seed: u64) -> BigInt {
+    let mut bytes = pseudo_random_bytes(bit_len / 8, seed);
+    bytes[0] |= 0x80;
+    BigInt::from_hex(hex::encode_lower(&bytes)).unwrap()
+}
+
+#[test]
+#[ignore]
+fn bench_bigint_mul_4096() {
+    let a = operand(4096, 1);
+    let b = operand(4096, 2);
+    bench("bigint_mul_4096", 2000, || &a * &b);
+}
+
+#[test]
+#[ignore]
+fn bench_bigint_div_rem_4096() {
+    let a = operand(4096, 1);
+    let b = operand(2048, 2);
+    bench("bigint_div_rem_4096", 2000, || a.try_div_rem(&b).unwrap());
+}
+
+#[test]
+#[ignore]
+fn bench_bigint_gcd_4096() {
+    let a = operand(4096, 1);
+    let b = operand(4096, 2);
+    bench("bigint_gcd_4096", 100, || a.try_gcd(&b).unwrap());
+}
+
+#[test]
+#[ignore]
+fn bench_invert_secp256k1() {
+    let p = &secp256k1().curve.p;
+    let a = operand(256, 1) % p;
+    bench("invert_secp256k1", 2000, || a.try_invert(p).unwrap());
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::{bench, pseudo_random_bytes};
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::{
+    sign_with_options, verify, PrecomputedPublicKey, PrivateKey, PublicKey, SigningOptions,
+};
+use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+
+const PRIVATE_KEY_HEX: &str =
+    "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d";
+
+fn options() -> SigningOptions {
+    // Deterministic signatures, the work doesn't vary from run to run.
+    SigningOptions {
+        employ_extra_random_data: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+#[ignore]
+fn bench_ecdsa_sign_secp256k1() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let hash = Keccak256::new().digest(pseudo_random_bytes(100, 1));
+    let options = options();
+
+    bench("ecdsa_sign_secp256k1", 200, || {
+        sign_with_options(&hash, &private_key, &options).unwrap()
+    });
+}
+
+#[test]
+#[ignore]
+fn bench_ecdsa_verify_secp256k1() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let public_key = private_key.public_key();
+    let hash = Keccak256::new().digest(pseudo_random_bytes(100, 1));
+    let (signature, _) = sign_with_options(&hash, &private_key, &options()).unwrap();
+    assert!(verify(&hash, &signature, &public_key).unwrap());
+
+    bench("ecdsa_verify_secp256k1", 100, || {
+        verify(&hash, &signature, &public_key).unwrap()
+    });
+}
+
+#[test]
+#[ignore]
+fn bench_public_key_from_private_secp256k1() {
+    let bytes = pseudo_random_bytes(32, 1);
+    let private_key = PrivateKey::new(
+        BigInt::from_hex(hex::encode_lower(&bytes)).unwrap(),
+        secp256k1(),
+    )
+    .unwrap();
+
+    bench("public_key_from_private_secp256k1", 200, || {
+        private_key.public_key()
+    });
+}
+
+#[test]
+#[ignore]
+fn bench_precomputed_public_key_load_vs_regenerate_secp256k1() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let bytes = PrecomputedPublicKey::new(private_key.public_key(), 5).to_bytes();
+
+    // From a validated public key, without the scalar multiplication deriving it.
+    let point = private_key.public_key().data;
+    bench(
+        "precomputed_public_key_regenerate_w5_secp256k1",
+        200,
+        || {
+            let public_key = PublicKey::new(point.clone(), secp256k1()).unwrap();
+            PrecomputedPublicKey::new(public_key, 5)
+        },
+    );
+    bench("precomputed_public_key_load_w5_secp256k1", 200, || {
+        PrecomputedPublicKey::from_bytes(&bytes, secp256k1()).unwrap()
+    });
+    bench(
+        "precomputed_public_key_load_trusted_w5_secp256k1",
+        200,
+        || PrecomputedPublicKey::from_trusted_bytes(&bytes, secp256k1()).unwrap(),
+    );
+}
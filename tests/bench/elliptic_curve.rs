@@ -0,0 +1,26 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::{bench, pseudo_random_bytes};
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::secp256k1;
+use lightcryptotools::math::MulConfig;
+
+#[test]
+#[ignore]
+fn bench_mul_point_secp256k1() {
+    let curve_params = secp256k1();
+    let n = BigInt::from_hex(hex::encode_lower(&pseudo_random_bytes(32, 1))).unwrap()
+        % &curve_params.base_point_order;
+    let config = MulConfig::default();
+
+    bench("mul_point_secp256k1", 200, || {
+        curve_params
+            .curve
+            .mul_point_with_config(&curve_params.base_point, &n, &config)
+    });
+}
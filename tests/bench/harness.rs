@@ -0,0 +1,239 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! A minimal benchmark harness built on `std::time`.
+//!
+//! Each benchmark runs a fixed number of iterations (the work quantum) `RUNS` times after
+//! a warm-up, and reports the median time per iteration. The median of several runs is
+//! less affected by outliers, e.g., a run interrupted by the scheduler, than the mean.
+//!
+//! A report line is printed for each benchmark:
+//!
+//! ```text
+//! bench: name=ecdsa_sign_secp256k1 iterations=200 ns_per_op=152000 ops_per_s=6579
+//! ```
+//!
+//! Benchmarks of throughput append `mb_per_s=<value>` to the line.
+//!
+//! Regression gating is opt-in: if the environment variable `BENCH_BASELINE` is set to a
+//! list of `name=ns_per_op` pairs separated by commas, e.g.,
+//! `BENCH_BASELINE="ecdsa_sign_secp256k1=150000,keccak256_4096b=9000"`, a benchmark listed
+//! fails if its median is slower than the baseline times the tolerance, 1.25 by default
+//! or the value of `BENCH_TOLERANCE`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+pub(crate) const BASELINE_ENV: &str = "BENCH_BASELINE";
+pub(crate) const TOLERANCE_ENV: &str = "BENCH_TOLERANCE";
+const DEFAULT_TOLERANCE: f64 = 1.25;
+
+/// The number of timed runs, the median of which is reported.
+const RUNS: usize = 5;
+
+pub(crate) struct BenchReport {
+    pub(crate) name: &'static str,
+    pub(crate) iterations: u64,
+    pub(crate) ns_per_op: f64,
+    pub(crate) bytes_per_op: Option<u64>,
+}
+
+impl BenchReport {
+    /// Returns the machine-readable report line.
+    pub(crate) fn line(&self) -> String {
+        let mut line = format!(
+            "bench: name={} iterations={} ns_per_op={:.0} ops_per_s={:.0}",
+            self.name,
+            self.iterations,
+            self.ns_per_op,
+            1e9 / self.ns_per_op
+        );
+        if let Some(bytes_per_op) = self.bytes_per_op {
+            let mb_per_s = bytes_per_op as f64 / self.ns_per_op * 1e9 / 1e6;
+            line.push_str(&format!(" mb_per_s={mb_per_s:.2}"));
+        }
+        line
+    }
+}
+
+/// Runs `f` for `iterations` times in each run, prints the report and checks it against
+/// the baseline, if any.
+pub(crate) fn bench<T>(
+    name: &'static str,
+    iterations: u64,
+    f: impl FnMut() -> T,
+) -> BenchReport {
+    run(name, iterations, None, f)
+}
+
+/// Like `bench`, for `f` processing `bytes_per_op` bytes in each iteration.
+pub(crate) fn bench_throughput<T>(
+    name: &'static str,
+    iterations: u64,
+    bytes_per_op: u64,
+    f: impl FnMut() -> T,
+) -> BenchReport {
+    run(name, iterations, Some(bytes_per_op), f)
+}
+
+fn run<T>(
+    name: &'static str,
+    iterations: u64,
+    bytes_per_op: Option<u64>,
+    mut f: impl FnMut() -> T,
+) -> BenchReport {
+    assert!(iterations > 0);
+
+    // Warms up caches, the allocator and the CPU frequency.
+    for _ in 0..(iterations / 10).max(1) {
+        black_box(f());
+    }
+
+    let mut samples = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            black_box(f());
+        }
+        samples.push(start.elapsed().as_nanos() as f64 / iterations as f64);
+    }
+
+    let report = BenchReport {
+        name,
+        iterations,
+        ns_per_op: median(&mut samples),
+        bytes_per_op,
+    };
+    // On its own line, after the name of the test printed by the test runner.
+    println!("\n{}", report.line());
+    check_baseline(&report);
+    report
+}
+
+/// Returns `byte_len` bytes generated from `seed` with xorshift64, so that the inputs, and
+/// the timings of the input-dependent algorithms, are the same from run to run.
+pub(crate) fn pseudo_random_bytes(byte_len: usize, seed: u64) -> Vec<u8> {
+    assert_ne!(seed, 0);
+
+    let mut state = seed;
+    (0..byte_len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+pub(crate) fn median(samples: &mut [f64]) -> f64 {
+    assert!(!samples.is_empty());
+
+    samples.sort_by(|a, b| a.total_cmp(b));
+    let mid = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2.0
+    } else {
+        samples[mid]
+    }
+}
+
+/// Parses a baseline in the format `name=ns_per_op,name=ns_per_op`.
+///
+/// Will panic if the format is invalid, a typo shouldn't disable the gating silently.
+pub(crate) fn parse_baseline(s: &str) -> Vec<(&str, f64)> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, ns_per_op) = entry
+                .split_once('=')
+                .unwrap_or_else(|| panic!("invalid baseline entry: {entry}"));
+            let ns_per_op = ns_per_op
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid baseline entry: {entry}"));
+            (name.trim(), ns_per_op)
+        })
+        .collect()
+}
+
+fn check_baseline(report: &BenchReport) {
+    let Ok(baseline) = std::env::var(BASELINE_ENV) else {
+        return;
+    };
+    let tolerance = match std::env::var(TOLERANCE_ENV) {
+        Ok(s) => s
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid {TOLERANCE_ENV}: {s}")),
+        Err(_) => DEFAULT_TOLERANCE,
+    };
+
+    if let Some((_, baseline_ns_per_op)) = parse_baseline(&baseline)
+        .into_iter()
+        .find(|(name, _)| *name == report.name)
+    {
+        assert!(
+            report.ns_per_op <= baseline_ns_per_op * tolerance,
+            "{} regressed: {:.0} ns/op, baseline {:.0} ns/op, tolerance {}",
+            report.name,
+            report.ns_per_op,
+            baseline_ns_per_op,
+            tolerance
+        );
+    }
+}
+
+#[test]
+fn test_median() {
+    assert_eq!(median(&mut [3.0]), 3.0);
+    assert_eq!(median(&mut [5.0, 1.0, 3.0]), 3.0);
+    assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+    // an outlier doesn't move the median
+    assert_eq!(median(&mut [10.0, 11.0, 1000.0, 9.0, 10.0]), 10.0);
+}
+
+#[test]
+fn test_pseudo_random_bytes() {
+    assert_eq!(pseudo_random_bytes(64, 1), pseudo_random_bytes(64, 1));
+    assert_ne!(pseudo_random_bytes(64, 1), pseudo_random_bytes(64, 2));
+    assert_eq!(pseudo_random_bytes(64, 1).len(), 64);
+}
+
+#[test]
+fn test_parse_baseline() {
+    assert_eq!(parse_baseline(""), vec![]);
+    assert_eq!(
+        parse_baseline("ecdsa_sign_secp256k1=150000, keccak256_4096b = 9000.5,"),
+        vec![
+            ("ecdsa_sign_secp256k1", 150000.0),
+            ("keccak256_4096b", 9000.5)
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid baseline entry")]
+fn test_parse_baseline_invalid() {
+    parse_baseline("ecdsa_sign_secp256k1:150000");
+}
+
+#[test]
+fn test_report_line() {
+    let report = BenchReport {
+        name: "sha256_4096b",
+        iterations: 1000,
+        ns_per_op: 4096.0,
+        bytes_per_op: Some(4096),
+    };
+    assert_eq!(
+        report.line(),
+        concat!(
+            "bench: name=sha256_4096b iterations=1000 ns_per_op=4096 ops_per_s=244141 ",
+            "mb_per_s=1000.00"
+        )
+    );
+}
@@ -0,0 +1,28 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::{bench_throughput, pseudo_random_bytes};
+use lightcryptotools::crypto::hash::{Keccak256, Sha256, UnkeyedHash};
+
+const MESSAGE_BYTE_LEN: usize = 1 << 16;
+
+#[test]
+#[ignore]
+fn bench_keccak256_throughput() {
+    let message = pseudo_random_bytes(MESSAGE_BYTE_LEN, 1);
+    bench_throughput("keccak256_64kib", 200, MESSAGE_BYTE_LEN as u64, || {
+        Keccak256::new().digest(&message)
+    });
+}
+
+#[test]
+#[ignore]
+fn bench_sha256_throughput() {
+    let message = pseudo_random_bytes(MESSAGE_BYTE_LEN, 1);
+    bench_throughput("sha256_64kib", 200, MESSAGE_BYTE_LEN as u64, || {
+        Sha256::new().digest(&message)
+    });
+}
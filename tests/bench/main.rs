@@ -0,0 +1,25 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Benchmarks implemented as ignored tests, runnable on the stable toolchain and without
+//! extra dependencies:
+//!
+//! ```text
+//! cargo test --release --test bench -- --ignored --test-threads=1 --nocapture bench_
+//! ```
+//!
+//! Running the benchmarks one at a time (`--test-threads=1`) keeps them from competing for
+//! the CPU. See `harness` for the report format and the regression gating.
+
+mod bigint;
+mod ecdsa;
+mod elliptic_curve;
+mod harness;
+mod hash;
+mod rlp;
+mod ssz;
+#[path = "../common/ssz_test_structs.rs"]
+mod ssz_test_structs;
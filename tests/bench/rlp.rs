@@ -0,0 +1,118 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::{bench_throughput, pseudo_random_bytes};
+use lightcryptotools::bigint::{BigInt, BigUint};
+use lightcryptotools::blockchain::ethereum::block::{BlockHeader, EMPTY_OMMERS_HASH};
+use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip1559,
+};
+use lightcryptotools::crypto::ecdsa::{PrivateKey, SigningOptions};
+use lightcryptotools::crypto::secp256k1;
+use lightcryptotools::tools::codable::{
+    decode, Decodable, DecodingItem, Encodable, EncodingItem,
+};
+
+const TRANSACTION_COUNT: usize = 500;
+
+/// Returns a synthetic block, `rlp([header, transactions, ommers])`, of
+/// `TRANSACTION_COUNT` EIP-1559 transactions with 200 bytes of data each.
+fn synthetic_block() -> Vec<u8> {
+    let private_key = PrivateKey::new(
+        BigInt::from_hex("89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d")
+            .unwrap(),
+        secp256k1(),
+    )
+    .unwrap();
+    let transaction = TransactionBuilder::new()
+        .with_chain_id(1.into())
+        .with_nonce(42.try_into().unwrap())
+        .with_max_priority_fee_per_gas("0x3b9aca00".try_into().unwrap())
+        .with_max_fee_per_gas("0x04a817c800".try_into().unwrap())
+        .with_gas_limit(0x030d40.into())
+        .with_destination(
+            "0x123456789a123456789a123456789a123456789a"
+                .try_into()
+                .unwrap(),
+        )
+        .with_amount("0x0de0b6b3a7640000".try_into().unwrap())
+        .with_data(pseudo_random_bytes(200, 1))
+        .take_and_build_payload_eip_1559()
+        .unwrap()
+        .take_and_sign_with_options(
+            &private_key,
+            &SigningOptions {
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let header = BlockHeader {
+        parent_hash: [0x11; 32],
+        ommers_hash: EMPTY_OMMERS_HASH,
+        beneficiary: "0xabcdef0123abcdef0123abcdef0123abcdef0123"
+            .try_into()
+            .unwrap(),
+        state_root: [0x22; 32],
+        transactions_root: [0x33; 32],
+        receipts_root: [0x44; 32],
+        logs_bloom: [0x55; 256],
+        difficulty: BigUint::from(0u64),
+        number: 15537394,
+        gas_limit: 30_000_000,
+        gas_used: 29_999_000,
+        timestamp: 1663224179,
+        extra_data: b"lightcryptotools".to_vec(),
+        mix_hash: [0x66; 32],
+        nonce: [0; 8],
+        base_fee_per_gas: Some("0x0b".try_into().unwrap()),
+    };
+    // Typed transactions are RLP strings within the list of transactions.
+    let transactions = vec![transaction.encode(); TRANSACTION_COUNT];
+
+    let mut block_encoding_item = RlpEncodingItem::new();
+    header.encode_to(&mut block_encoding_item);
+    transactions.encode_to(&mut block_encoding_item);
+    Vec::<Vec<u8>>::new().encode_to(&mut block_encoding_item);
+
+    let mut encoding_item = RlpEncodingItem::new();
+    encoding_item.encode_list_payload(&mut block_encoding_item);
+    encoding_item.take_data()
+}
+
+/// Decodes the header fields and the transactions of `block`, returns the transactions.
+fn decode_block(block: &[u8]) -> Vec<TransactionEip1559> {
+    let decoding_item = RlpDecodingItem::new_from_data(block).unwrap();
+    let items = decoding_item.decode_as_items().unwrap();
+    assert_eq!(items.len(), 3);
+
+    for field in items[0].decode_as_items().unwrap() {
+        field.decode_as_bytes().unwrap();
+    }
+    items[1]
+        .decode_as_items()
+        .unwrap()
+        .iter()
+        .map(|item| {
+            let data = <&[u8]>::decode_from(item).unwrap();
+            decode::<TransactionEip1559, RlpDecodingItem>(&data[1..]).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn bench_rlp_decode_block() {
+    let block = synthetic_block();
+    assert_eq!(decode_block(&block).len(), TRANSACTION_COUNT);
+
+    bench_throughput("rlp_decode_block", 20, block.len() as u64, || {
+        decode_block(&block)
+    });
+}
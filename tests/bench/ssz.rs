@@ -0,0 +1,72 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::harness::bench_throughput;
+use crate::ssz_test_structs::{ComplexTestStruct, FixedTestStruct, VarTestStruct};
+use lightcryptotools::blockchain::ethereum::ssz::{SszDecodingItem, SszEncodingItem};
+use lightcryptotools::tools::codable::{decode, encode};
+
+#[test]
+#[ignore]
+fn bench_ssz_decode_complex_test_struct() {
+    let value = ComplexTestStruct {
+        a: 0xaabb,
+        b: vec![0x1122, 0x3344],
+        c: 0xff,
+        d: "foobar".as_bytes().to_vec(),
+        e: VarTestStruct {
+            a: 0xabcd,
+            b: vec![1, 2, 3],
+            c: 0xff,
+        },
+        f: [
+            FixedTestStruct {
+                a: 0xcc,
+                b: 0x4242424242424242,
+                c: 0x13371337,
+            },
+            FixedTestStruct {
+                a: 0xdd,
+                b: 0x3333333333333333,
+                c: 0xabcdabcd,
+            },
+            FixedTestStruct {
+                a: 0xee,
+                b: 0x4444444444444444,
+                c: 0x00112233,
+            },
+            FixedTestStruct {
+                a: 0xff,
+                b: 0x5555555555555555,
+                c: 0x44556677,
+            },
+        ],
+        g: [
+            VarTestStruct {
+                a: 0xdead,
+                b: vec![1, 2, 3],
+                c: 0x11,
+            },
+            VarTestStruct {
+                a: 0xbeef,
+                b: vec![4, 5, 6],
+                c: 0x22,
+            },
+        ],
+    };
+    let data = encode::<_, SszEncodingItem>(&value);
+    assert_eq!(
+        decode::<ComplexTestStruct, SszDecodingItem>(&data).unwrap(),
+        value
+    );
+
+    bench_throughput(
+        "ssz_decode_complex_test_struct",
+        20000,
+        data.len() as u64,
+        || decode::<ComplexTestStruct, SszDecodingItem>(&data).unwrap(),
+    );
+}
@@ -0,0 +1,81 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Benchmarks signing a batch, with and without an arena:
+//!
+//! ```text
+//! cargo test --release --features bigint-arena --test bench_arena -- --ignored --test-threads=1 --nocapture bench_
+//! ```
+//!
+//! In a target of its own, for `BigIntArenaAllocator` is installed as the global allocator
+//! of the target.
+
+// Shares the harness of the other benchmarks, some functions of which are unused here.
+#[allow(dead_code)]
+#[path = "../bench/harness.rs"]
+mod harness;
+
+use harness::{bench, pseudo_random_bytes};
+use lightcryptotools::bigint::{with_arena, BigInt, BigIntArenaAllocator};
+use lightcryptotools::crypto::ecdsa::{sign_with_options, PrivateKey, SigningOptions};
+use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+
+#[global_allocator]
+static GLOBAL: BigIntArenaAllocator = BigIntArenaAllocator;
+
+const PRIVATE_KEY_HEX: &str =
+    "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d";
+const BATCH_SIZE: usize = 10_000;
+const ARENA_SIZE: usize = 1 << 20;
+
+fn sign_batch(hashes: &[Vec<u8>], private_key: &PrivateKey) -> usize {
+    // Deterministic signatures, the work doesn't vary from run to run.
+    let options = SigningOptions {
+        employ_extra_random_data: false,
+        ..Default::default()
+    };
+    hashes
+        .iter()
+        .filter(|hash| sign_with_options(hash, private_key, &options).is_ok())
+        .count()
+}
+
+fn hashes() -> Vec<Vec<u8>> {
+    let bytes = pseudo_random_bytes(BATCH_SIZE * 8, 1);
+    bytes
+        .chunks(8)
+        .map(|chunk| Keccak256::new().digest(chunk))
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn bench_ecdsa_sign_batch_secp256k1() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let hashes = hashes();
+
+    bench("ecdsa_sign_batch_10000_secp256k1", 1, || {
+        sign_batch(&hashes, &private_key)
+    });
+}
+
+#[test]
+#[ignore]
+fn bench_ecdsa_sign_batch_secp256k1_arena() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let hashes = hashes();
+
+    bench("ecdsa_sign_batch_10000_secp256k1_arena", 1, || {
+        with_arena(ARENA_SIZE, |arena| {
+            let count = sign_batch(&hashes, &private_key);
+            assert_eq!(arena.fallback_count(), 0);
+            count
+        })
+    });
+}
@@ -0,0 +1,151 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests the arenas with `BigIntArenaAllocator` installed.
+//!
+//! In a test target of its own, for the global allocator serves every test in the target.
+
+use lightcryptotools::bigint::{
+    with_arena, with_thread_local_arena, BigInt, BigIntArena, BigIntArenaAllocator,
+};
+use lightcryptotools::crypto::ecdsa::{sign_with_options, verify, PrivateKey, SigningOptions};
+use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+
+#[global_allocator]
+static GLOBAL: BigIntArenaAllocator = BigIntArenaAllocator;
+
+const PRIVATE_KEY_HEX: &str =
+    "89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d";
+
+#[test]
+fn test_bigint_operations() {
+    let a =
+        BigInt::from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+            .unwrap();
+    let b =
+        BigInt::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+            .unwrap();
+    let expected = (&(&a * &b) + &a) % &b;
+
+    let output = with_arena(1 << 16, |arena| {
+        assert!(BigIntArena::is_installed());
+        assert_eq!(arena.capacity(), 1 << 16);
+
+        let mut output = BigInt::from(0);
+        for _ in 0..100 {
+            output = (&(&a * &b) + &a) % &b;
+        }
+        assert!(arena.used() > 0);
+        assert_eq!(arena.fallback_count(), 0);
+        output
+    });
+    // allocated from the arena, and valid after it has ended
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_signing() {
+    let private_key =
+        PrivateKey::new(BigInt::from_hex(PRIVATE_KEY_HEX).unwrap(), secp256k1()).unwrap();
+    let public_key = private_key.public_key();
+    let options = SigningOptions {
+        employ_extra_random_data: false,
+        ..Default::default()
+    };
+    let hashes: Vec<_> = (0..20_u8).map(|i| Keccak256::new().digest([i])).collect();
+    let expected: Vec<_> = hashes
+        .iter()
+        .map(|hash| sign_with_options(hash, &private_key, &options).unwrap().0)
+        .collect();
+
+    let signatures = with_arena(1 << 20, |_| {
+        hashes
+            .iter()
+            .map(|hash| sign_with_options(hash, &private_key, &options).unwrap().0)
+            .collect::<Vec<_>>()
+    });
+    for (signature, expected) in signatures.iter().zip(&expected) {
+        assert_eq!(signature.r, expected.r);
+        assert_eq!(signature.s, expected.s);
+    }
+    for (hash, signature) in hashes.iter().zip(&signatures) {
+        assert!(verify(hash, signature, &public_key).unwrap());
+    }
+}
+
+#[test]
+fn test_fallback_when_exhausted() {
+    let output = with_arena(256, |arena| {
+        let n = BigInt::from(1) << 10_000;
+        assert!(arena.fallback_count() > 0);
+        n
+    });
+    assert_eq!(output.bit_len(), 10_001);
+}
+
+#[test]
+fn test_values_dropped_on_another_thread() {
+    let values = with_arena(1 << 12, |_| {
+        (0..10).map(|i| BigInt::from(i) << 100).collect::<Vec<_>>()
+    });
+    let sum = std::thread::spawn(move || {
+        values
+            .into_iter()
+            .fold(BigInt::from(0), |sum, value| &sum + &value)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(sum, BigInt::from(45) << 100);
+}
+
+#[test]
+fn test_nested_arenas() {
+    with_arena(1 << 12, |outer| {
+        let a = BigInt::from(3) << 200;
+        let used = outer.used();
+        let b = with_arena(1 << 12, |inner| {
+            let b = &a * &a;
+            assert!(inner.used() > 0);
+            b
+        });
+        // the inner allocations don't touch the outer arena
+        assert_eq!(outer.used(), used);
+        assert_eq!(b, BigInt::from(9) << 400);
+    });
+}
+
+#[test]
+fn test_thread_local_arena_reused() {
+    let capacity = with_thread_local_arena(1 << 14, |arena| {
+        let _ = BigInt::from(7) << 1000;
+        arena.capacity()
+    });
+    assert_eq!(capacity, 1 << 14);
+
+    // a smaller size reuses the buffer
+    let capacity = with_thread_local_arena(1 << 10, |arena| arena.capacity());
+    assert_eq!(capacity, 1 << 14);
+
+    // a larger size replaces it
+    let capacity = with_thread_local_arena(1 << 16, |arena| arena.capacity());
+    assert_eq!(capacity, 1 << 16);
+}
+
+#[test]
+fn test_panic_restores_allocations() {
+    let result = std::panic::catch_unwind(|| {
+        with_arena(1 << 12, |_| {
+            let _n = BigInt::from(1) << 100;
+            panic!("in arena");
+        })
+    });
+    assert!(result.is_err());
+
+    // allocates from the system allocator again
+    let n = BigInt::from(1) << 100;
+    assert_eq!(n.bit_len(), 101);
+}
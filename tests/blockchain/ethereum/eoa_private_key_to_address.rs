@@ -6,7 +6,7 @@
 
 use devtools::path::integration_testing_data_path;
 use lightcryptotools::blockchain::ethereum::account::{EoaPrivateKey, EoaPrivateKeyData};
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use serde_json::Value;
 use std::fs::File;
 
@@ -25,7 +25,7 @@ fn test_eoa_private_key_to_address() {
         }
         let checksummed_address = value["checksumAddress"].as_str().unwrap();
 
-        let key_bytes = hex_to_bytes(&private_key_hex_with_prefix.unwrap()[2..]).unwrap();
+        let key_bytes = hex::decode(&private_key_hex_with_prefix.unwrap()[2..]).unwrap();
         let key_data: EoaPrivateKeyData = key_bytes.try_into().unwrap();
         let eoa_private_key = EoaPrivateKey::new(key_data).unwrap();
         let eoa_public_key = eoa_private_key.public_key();
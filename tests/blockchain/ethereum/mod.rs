@@ -5,6 +5,13 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 mod eoa_private_key_to_address;
+// Implements `Encodable` and `Decodable` for a newtype of `serde_json::Value`.
+#[cfg(feature = "unstable-codable")]
 mod rlp_ethers_js;
+mod rlp_integer_fields;
+mod rlp_schema_ethers_js;
 mod ssz_worked_example;
+mod transaction_borrowed_decoding_ethers_js;
+mod transaction_hashing_ethers_js;
 mod transaction_signing_ethers_js;
+mod unsigned_bundle_ethers_js;
@@ -18,7 +18,7 @@ use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
 use lightcryptotools::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
 use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
 use lightcryptotools::blockchain::ethereum::rlp::RlpItemType;
-use lightcryptotools::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::tools::codable::{decode, encode, Decodable, Encodable, EncodingItem};
 use serde_json::Value;
 use std::fs::File;
@@ -39,7 +39,7 @@ fn test_encoding() {
             .unwrap();
 
         let encoded = encode(&JsonValue(decoded_value));
-        assert_eq!(bytes_to_lower_hex(&encoded), encoded_hex);
+        assert_eq!(hex::encode_lower(&encoded), encoded_hex);
         count += 1;
     }
     assert!(count > 60);
@@ -60,7 +60,7 @@ fn test_decoding() {
             .strip_prefix("0x")
             .unwrap();
 
-        let json_value: JsonValue = decode(&hex_to_bytes(encoded_hex).unwrap()).unwrap();
+        let json_value: JsonValue = decode(&hex::decode(encoded_hex).unwrap()).unwrap();
         assert_eq!(json_value.0, decoded_value);
         count += 1;
     }
@@ -94,7 +94,7 @@ fn encode_json_value(value: &Value, encoding_item: &mut RlpEncodingItem) {
             unimplemented!();
         }
         Value::String(s) => {
-            let bytes = hex_to_bytes(s.strip_prefix("0x").unwrap()).unwrap();
+            let bytes = hex::decode(s.strip_prefix("0x").unwrap()).unwrap();
             encoding_item.encode_bytes(&bytes);
         }
         Value::Array(values) => {
@@ -114,7 +114,7 @@ fn decode_json_value(decoding_item: &RlpDecodingItem) -> Result<Value, RlpDataDe
     return match decoding_item.item_type {
         RlpItemType::SingleValue => {
             let s = decoding_item.decode_as_bytes()?;
-            let hex = bytes_to_lower_hex(s);
+            let hex = hex::encode_lower(s);
             Ok(Value::String(format!("0x{hex}")))
         }
         RlpItemType::List => {
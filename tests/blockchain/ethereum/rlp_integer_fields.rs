@@ -0,0 +1,318 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Covers the boundary between the integer fields of transactions, encoded without leading
+//! zeros, and the byte string fields, encoded as they are.
+
+use devtools::path::integration_testing_data_path;
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+use lightcryptotools::blockchain::ethereum::rlp::decoding::{
+    decode_uint_bigint, RlpDataDecodingError,
+};
+use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use lightcryptotools::blockchain::ethereum::rlp::encoding::encode_uint_bigint;
+use lightcryptotools::blockchain::ethereum::rlp::RlpItemType;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip1559, TransactionEip2930, TransactionLegacy,
+};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::{PrivateKey, SigningOptions};
+use lightcryptotools::crypto::secp256k1;
+use lightcryptotools::tools::codable::{decode, DecodingItem, EncodingItem};
+use serde_json::Value;
+use std::fs::File;
+
+// The integers next to the boundaries of the single byte and the short string encodings.
+const EDGE_INTEGER_HEX_VALUES: [&str; 5] = ["", "7f", "80", "ff", "100"];
+
+#[test]
+fn test_encoding_uint_bigint() {
+    let data = [
+        // (integer, encoded)
+        ("00", "80"),
+        ("7f", "7f"),
+        ("80", "8180"),
+        ("ff", "81ff"),
+        ("0100", "820100"),
+        ("ffffffffffffffff", "88ffffffffffffffff"),
+        ("010000000000000000", "89010000000000000000"),
+    ];
+    for (n_hex, encoded_hex) in data {
+        let n = BigInt::from_hex(n_hex).unwrap();
+        assert_eq!(hex::encode_lower(&encode_uint_bigint(&n)), encoded_hex);
+        assert_eq!(
+            decode_uint_bigint(&hex::decode(encoded_hex).unwrap()).unwrap(),
+            n
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_encoding_uint_bigint_negative() {
+    encode_uint_bigint(&BigInt::from_hex("-1").unwrap());
+}
+
+#[test]
+fn test_decoding_uint_bigint_error_cases() {
+    let data = [
+        // (encoded, error)
+        // Zero is the empty string
+        ("00", RlpDataDecodingError::NonCanonicalInteger),
+        ("8100", RlpDataDecodingError::NonCanonicalInteger),
+        ("82007f", RlpDataDecodingError::NonCanonicalInteger),
+        ("820001", RlpDataDecodingError::NonCanonicalInteger),
+        (
+            "c0",
+            RlpDataDecodingError::UnexpectedItemType {
+                expected: RlpItemType::SingleValue,
+                found: RlpItemType::List,
+            },
+        ),
+        ("", RlpDataDecodingError::InvalidFormat),
+    ];
+    for (encoded_hex, error) in data {
+        let result = decode_uint_bigint(&hex::decode(encoded_hex).unwrap());
+        assert_eq!(
+            format!("{:?}", result.unwrap_err()),
+            format!("{error:?}"),
+            "{encoded_hex}"
+        );
+    }
+}
+
+#[test]
+fn test_byte_string_fields_keep_leading_zeros() {
+    let private_key = PrivateKey::new(BigInt::from(1_u64), secp256k1()).unwrap();
+    let data = [
+        // (data, encoded data field)
+        (vec![0x00], "00"),
+        (vec![], "80"),
+        (vec![0x00, 0x00], "820000"),
+        (vec![0x00, 0x01], "820001"),
+    ];
+    for (bytes, encoded_hex) in data {
+        let transaction = TransactionBuilder::new()
+            .with_nonce(0.try_into().unwrap())
+            .with_gas_price("0x".try_into().unwrap())
+            .with_gas_limit(0.into())
+            .with_destination(
+                "0x0000000000000000000000000000000000000000"
+                    .try_into()
+                    .unwrap(),
+            )
+            .with_amount("0x".try_into().unwrap())
+            .with_data(bytes.clone())
+            .take_and_build_payload_legacy()
+            .unwrap()
+            .take_and_sign_with_options(&private_key, &SigningOptions::default())
+            .unwrap();
+        let encoded = transaction.encode();
+
+        let items = RlpDecodingItem::new_from_data(&encoded)
+            .unwrap()
+            .decode_as_items()
+            .unwrap();
+        let fields = raw_fields(&encoded);
+        // The integer fields of zero are the empty string, the destination the 20 zero bytes
+        for field in [&fields[0], &fields[1], &fields[2], &fields[4]] {
+            assert_eq!(field, &[0x80]);
+        }
+        assert_eq!(fields[3], [&[0x94][..], &[0; 20]].concat());
+        assert_eq!(hex::encode_lower(&fields[5]), encoded_hex);
+        assert_eq!(items[5].decode_as_bytes().unwrap(), bytes);
+
+        let decoded = decode::<TransactionLegacy, _>(&encoded).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+    }
+}
+
+#[test]
+fn test_decoding_rejects_leading_zeros_of_integer_fields() {
+    let path = integration_testing_data_path("blockchain/ethereum/ethers.js/transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+    let signed = value_vec
+        .iter()
+        .find(|value| value["to"].is_string() && value["data"].as_str() == Some("0x"))
+        .unwrap()["signedTransaction"]
+        .as_str()
+        .unwrap();
+    let encoded = hex::decode(&signed[2..]).unwrap();
+    assert!(decode::<TransactionLegacy, _>(&encoded).is_ok());
+
+    // nonce, gas price, gas limit, value, v, r and s
+    for index in [0, 1, 2, 4, 6, 7, 8] {
+        let tampered = prepend_zero_to_field(&encoded, index);
+        assert!(
+            matches!(
+                decode::<TransactionLegacy, _>(&tampered),
+                Err(RlpDataDecodingError::NonCanonicalInteger)
+            ),
+            "{index}"
+        );
+    }
+
+    // The data is a byte string, a leading zero is part of it.
+    let tampered = prepend_zero_to_field(&encoded, 5);
+    let decoded = decode::<TransactionLegacy, _>(&tampered).unwrap();
+    assert_eq!(
+        decoded.payload().to_string().lines().last(),
+        Some("data: 0x00")
+    );
+}
+
+#[test]
+#[ignore]
+fn test_signing_transaction_legacy_edge_integers() {
+    let path = integration_testing_data_path("blockchain/ethereum/ethers.js/transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        let d_hex = value["privateKey"].as_str().unwrap();
+        let fields = ["nonce", "gasPrice", "gasLimit", "to", "value", "data"]
+            .map(|key| value[key].as_str());
+        let [Some(nonce_hex), Some(gas_price_hex), Some(gas_limit_hex), Some(to_hex), Some(value_hex), Some(data_hex)] =
+            fields
+        else {
+            continue;
+        };
+        if ![nonce_hex, gas_price_hex, gas_limit_hex, value_hex]
+            .into_iter()
+            .any(is_edge_integer)
+            && !matches!(data_hex, "0x" | "0x00")
+        {
+            continue;
+        }
+        // Ignores u64 overflow, for nonce and gas_limit are not big integers
+        let (Some(nonce), Some(gas_limit)) = (parse_u64(nonce_hex), parse_u64(gas_limit_hex))
+        else {
+            continue;
+        };
+
+        let d = BigInt::from_hex(&d_hex[2..]).unwrap();
+        let private_key = PrivateKey::new(d, secp256k1()).unwrap();
+        let transaction = TransactionBuilder::new()
+            .with_nonce(nonce.try_into().unwrap())
+            .with_gas_price(gas_price_hex.try_into().unwrap())
+            .with_gas_limit(gas_limit.into())
+            .with_destination(to_hex.try_into().unwrap())
+            .with_amount(value_hex.try_into().unwrap())
+            .with_data(hex::decode(&data_hex[2..]).unwrap())
+            .take_and_build_payload_legacy()
+            .unwrap()
+            .take_and_sign_with_options(
+                &private_key,
+                &SigningOptions {
+                    employ_extra_random_data: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let signed = value["signedTransaction"].as_str().unwrap();
+        assert_eq!(hex::encode_lower(&transaction.encode()), signed[2..]);
+
+        count += 1;
+    }
+
+    assert!(count > 400);
+}
+
+#[test]
+fn test_typed_transactions_edge_integers() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        let tx_value = &value["tx"];
+        if tx_value["to"].is_null() {
+            continue;
+        }
+        let has_edge_integer = [
+            "gasPrice",
+            "maxPriorityFeePerGas",
+            "maxFeePerGas",
+            "gasLimit",
+            "value",
+        ]
+        .into_iter()
+        .filter_map(|key| tx_value[key].as_str())
+        .any(is_edge_integer);
+        if !has_edge_integer && !matches!(tx_value["data"].as_str(), Some("0x" | "0x00")) {
+            continue;
+        }
+
+        let signed = hex::decode(&value["signed"].as_str().unwrap()[2..]).unwrap();
+        let encoded = match tx_value["type"].as_u64().unwrap() {
+            1 => decode::<TransactionEip2930, _>(&signed[1..])
+                .unwrap()
+                .encode(),
+            2 => decode::<TransactionEip1559, _>(&signed[1..])
+                .unwrap()
+                .encode(),
+            _ => continue,
+        };
+        assert_eq!(encoded, signed);
+
+        count += 1;
+    }
+
+    assert!(count >= 10);
+}
+
+fn is_edge_integer(hex: &str) -> bool {
+    EDGE_INTEGER_HEX_VALUES.contains(&hex[2..].trim_start_matches('0'))
+}
+
+fn parse_u64(hex: &str) -> Option<u64> {
+    if hex == "0x" {
+        return Some(0);
+    }
+    u64::from_str_radix(&hex[2..], 16).ok()
+}
+
+// Returns the encoded fields of the RLP list `encoded`.
+fn raw_fields(encoded: &[u8]) -> Vec<Vec<u8>> {
+    let items = RlpDecodingItem::new_from_data(encoded)
+        .unwrap()
+        .decode_as_items()
+        .unwrap();
+    items
+        .iter()
+        .map(|item| {
+            let mut encoding_item = RlpEncodingItem::new();
+            encoding_item.encode_bytes(item.decode_as_bytes().unwrap());
+            encoding_item.take_data()
+        })
+        .collect()
+}
+
+// Re-encodes the RLP list `encoded` with a zero byte prepended to the field at `index`.
+fn prepend_zero_to_field(encoded: &[u8], index: usize) -> Vec<u8> {
+    let items = RlpDecodingItem::new_from_data(encoded)
+        .unwrap()
+        .decode_as_items()
+        .unwrap();
+    let mut fields_encoding_item = RlpEncodingItem::new();
+    for (i, item) in items.iter().enumerate() {
+        let bytes = item.decode_as_bytes().unwrap();
+        if i == index {
+            fields_encoding_item.encode_bytes(&[&[0], bytes].concat());
+        } else {
+            fields_encoding_item.encode_bytes(bytes);
+        }
+    }
+
+    let mut encoding_item = RlpEncodingItem::new();
+    encoding_item.encode_list_payload(&mut fields_encoding_item);
+    encoding_item.take_data()
+}
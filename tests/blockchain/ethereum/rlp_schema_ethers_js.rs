@@ -0,0 +1,50 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use devtools::hex::byte_aligned_hex;
+use devtools::path::integration_testing_data_path;
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::blockchain::ethereum::rlp::schema::{decode_schema, RlpValue};
+use lightcryptotools::blockchain::ethereum::transaction::TransactionLegacy;
+use lightcryptotools::crypto::codecs::hex;
+use serde_json::Value;
+use std::fs::File;
+
+#[test]
+fn test_decoding_transaction_legacy_with_schema() {
+    let path = integration_testing_data_path("blockchain/ethereum/ethers.js/transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let schema = TransactionLegacy::rlp_schema();
+    let mut count = 0;
+    for value in value_vec {
+        // A missing field is encoded as zero or empty bytes.
+        let field_hex = |key: &str| {
+            let hex = value[key].as_str().unwrap_or("0x");
+            byte_aligned_hex(hex.strip_prefix("0x").unwrap()).into_owned()
+        };
+        let uint = |key: &str| RlpValue::Uint(BigInt::from_hex(field_hex(key)).unwrap());
+        let bytes = |key: &str| hex::decode(field_hex(key)).unwrap();
+
+        let data = hex::decode(&value["signedTransaction"].as_str().unwrap()[2..]).unwrap();
+        let values = decode_schema(&data, &schema).unwrap();
+        assert_eq!(values.len(), 9);
+        assert_eq!(values[0], uint("nonce"));
+        assert_eq!(values[1], uint("gasPrice"));
+        assert_eq!(values[2], uint("gasLimit"));
+        assert_eq!(values[3], RlpValue::Bytes(&bytes("to")));
+        assert_eq!(values[4], uint("value"));
+        assert_eq!(values[5], RlpValue::Bytes(&bytes("data")));
+        assert!(
+            values[6] == RlpValue::Uint(BigInt::from(27))
+                || values[6] == RlpValue::Uint(BigInt::from(28))
+        );
+
+        count += 1;
+    }
+    assert!(count > 1000);
+}
@@ -1,10 +1,10 @@
 //! Worked example: https://eth2book.info/altair/part2/building_blocks/ssz/#worked-example
 
 use lightcryptotools::blockchain::ethereum::ssz::{
-    SszDataDecodingError, SszDecodingItem, SszEncodingItem, SszType,
+    SszContainerWriter, SszDataDecodingError, SszDecodingItem, SszType,
 };
-use lightcryptotools::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
-use lightcryptotools::tools::codable::{decode, encode, Decodable, DecodingItem, EncodingItem};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::tools::codable::{decode, encode, Decodable, DecodingItem};
 
 #[test]
 fn test_indexed_attestation_encoding_and_decoding() {
@@ -13,7 +13,7 @@ fn test_indexed_attestation_encoding_and_decoding() {
         data: AttestationData {
             slot: 3080829,
             index: 9,
-            beacon_block_root: hex_to_bytes(
+            beacon_block_root: hex::decode(
                 "4f4250c05956f5c2b87129cf7372f14dd576fc152543bf7042e963196b843fe6",
             )
             .unwrap()
@@ -21,7 +21,7 @@ fn test_indexed_attestation_encoding_and_decoding() {
             .unwrap(),
             source: Checkpoint {
                 epoch: 96274,
-                root: hex_to_bytes(
+                root: hex::decode(
                     "d24639f2e661bc1adcbe7157280776cf76670fff0fee0691f146ab827f4f1ade",
                 )
                 .unwrap()
@@ -30,7 +30,7 @@ fn test_indexed_attestation_encoding_and_decoding() {
             },
             target: Checkpoint {
                 epoch: 96275,
-                root: hex_to_bytes(
+                root: hex::decode(
                     "9bcd31881817ddeab686f878c8619d664e8bfa4f8948707cba5bc25c8d74915d",
                 )
                 .unwrap()
@@ -38,7 +38,7 @@ fn test_indexed_attestation_encoding_and_decoding() {
                 .unwrap(),
             },
         },
-        signature: hex_to_bytes(concat!(
+        signature: hex::decode(concat!(
             "aaf504503ff15ae86723c906b4b6bac91ad728e4431aea3be2e8e3acc888d8af",
             "5dffbbcf53b234ea8e3fde67fbb09120027335ec63cf23f0213cc439e8d1b856",
             "c2ddfc1a78ed3326fb9b4fe333af4ad3702159dbf9caeb1a4633b752991ac437"
@@ -58,11 +58,10 @@ fn test_indexed_attestation_encoding_and_decoding() {
     );
 
     // Tests encoding
-    assert_eq!(bytes_to_lower_hex(&encode(&value)), encoded_hex);
+    assert_eq!(hex::encode_lower(&encode(&value)), encoded_hex);
 
     // Tests decoding
-    let decoded_value: IndexedAttestation =
-        decode(&hex_to_bytes(encoded_hex).unwrap()).unwrap();
+    let decoded_value: IndexedAttestation = decode(&hex::decode(encoded_hex).unwrap()).unwrap();
     assert_eq!(decoded_value, value);
 }
 
@@ -79,12 +78,17 @@ impl SszType for IndexedAttestation {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut encoding_item = SszEncodingItem::new();
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
 
-        encoding_item.encode_as_container_element(&self.attesting_indices);
-        encoding_item.encode_as_container_element(&self.data);
-        encoding_item.encode_as_container_element(&self.signature);
-        encoding_item.take_data()
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.attesting_indices);
+            writer.write_element(&self.data);
+            writer.write_element(&self.signature);
+        });
     }
 
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -129,14 +133,19 @@ impl SszType for AttestationData {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut encoding_item = SszEncodingItem::new();
-
-        encoding_item.encode_as_container_element(&self.slot);
-        encoding_item.encode_as_container_element(&self.index);
-        encoding_item.encode_as_container_element(&self.beacon_block_root);
-        encoding_item.encode_as_container_element(&self.source);
-        encoding_item.encode_as_container_element(&self.target);
-        encoding_item.take_data()
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.slot);
+            writer.write_element(&self.index);
+            writer.write_element(&self.beacon_block_root);
+            writer.write_element(&self.source);
+            writer.write_element(&self.target);
+        });
     }
 
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -179,11 +188,16 @@ impl SszType for Checkpoint {
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        let mut encoding_item = SszEncodingItem::new();
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
 
-        encoding_item.encode_as_container_element(&self.epoch);
-        encoding_item.encode_as_container_element(&self.root);
-        encoding_item.take_data()
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.epoch);
+            writer.write_element(&self.root);
+        });
     }
 
     fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
@@ -0,0 +1,60 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use devtools::path::integration_testing_data_path;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionEip1559, TransactionEip1559Ref,
+};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::tools::codable::decode;
+use serde_json::Value;
+use std::fs::File;
+
+#[test]
+fn test_decoding_transaction_eip_1559_borrowed() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        if value["tx"]["type"].as_u64().unwrap() != 2 {
+            continue;
+        }
+
+        let data = hex::decode(&value["signed"].as_str().unwrap()[2..]).unwrap();
+        let owned = decode::<TransactionEip1559, _>(&data[1..]);
+        let borrowed = TransactionEip1559Ref::decode_borrowed(&data);
+        // Both fail the same cases, e.g., those missing the destination.
+        assert_eq!(owned.is_ok(), borrowed.is_ok());
+        let (Ok(owned), Ok(borrowed)) = (owned, borrowed) else {
+            continue;
+        };
+
+        // The byte fields lie within `data`.
+        let range = data.as_ptr_range();
+        assert!(range.contains(&borrowed.destination().as_ptr()));
+        if !borrowed.data().is_empty() {
+            assert!(range.contains(&borrowed.data().as_ptr()));
+            assert!(range.contains(&(borrowed.data().last().unwrap() as *const u8)));
+        }
+        for item in borrowed.access_list() {
+            assert!(range.contains(&item.address.as_ptr()));
+            for storage_key in &item.storage_keys {
+                assert!(range.contains(&storage_key.as_ptr()));
+            }
+        }
+
+        // `Display` outputs all the fields.
+        let converted = borrowed.to_transaction();
+        assert_eq!(converted.to_string(), owned.to_string());
+        assert_eq!(converted.encode(), data);
+
+        count += 1;
+    }
+    assert!(count > 500);
+}
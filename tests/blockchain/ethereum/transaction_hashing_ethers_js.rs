@@ -0,0 +1,139 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Tests the signing hashes against the unsigned transactions serialized by ethers.js, and
+//! the transaction IDs against the signed ones.
+
+use devtools::path::integration_testing_data_path;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionEip155, TransactionEip1559, TransactionEip2930, TransactionLegacy,
+};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+use lightcryptotools::tools::codable::decode;
+use serde_json::Value;
+use std::fs::File;
+
+fn keccak256_of_hex(s: &str) -> Vec<u8> {
+    Keccak256::new().digest(hex::decode(&s[2..]).unwrap())
+}
+
+#[test]
+fn test_hashing_transaction_legacy_and_eip_155() {
+    let path = integration_testing_data_path("blockchain/ethereum/ethers.js/transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        let signed = hex::decode(&value["signedTransaction"].as_str().unwrap()[2..]).unwrap();
+        let Ok(transaction) = decode::<TransactionLegacy, _>(&signed) else {
+            continue;
+        };
+        // Re-encoding doesn't normalize any field, which would change the ID.
+        assert_eq!(transaction.encode(), signed);
+        assert_eq!(
+            transaction.transaction_id().as_slice(),
+            Keccak256::new().digest(&signed)
+        );
+        assert_eq!(
+            transaction.payload().signing_hash().as_slice(),
+            keccak256_of_hex(value["unsignedTransaction"].as_str().unwrap())
+        );
+
+        let signed =
+            hex::decode(&value["signedTransactionChainId5"].as_str().unwrap()[2..]).unwrap();
+        let transaction = decode::<TransactionEip155, _>(&signed).unwrap();
+        assert_eq!(transaction.encode(), signed);
+        assert_eq!(
+            transaction.transaction_id().as_slice(),
+            Keccak256::new().digest(&signed)
+        );
+        assert_eq!(
+            transaction.payload().signing_hash().as_slice(),
+            keccak256_of_hex(value["unsignedTransactionChainId5"].as_str().unwrap())
+        );
+
+        count += 1;
+    }
+
+    assert!(count > 900);
+}
+
+#[test]
+fn test_hashing_typed_transactions() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        let signed = hex::decode(&value["signed"].as_str().unwrap()[2..]).unwrap();
+        let signing_hash = keccak256_of_hex(value["unsigned"].as_str().unwrap());
+        let transaction_id = Keccak256::new().digest(&signed);
+
+        match value["tx"]["type"].as_u64().unwrap() {
+            1 => {
+                let Ok(transaction) = decode::<TransactionEip2930, _>(&signed[1..]) else {
+                    continue;
+                };
+                assert_eq!(transaction.encode(), signed);
+                assert_eq!(transaction.transaction_id().as_slice(), transaction_id);
+                assert_eq!(
+                    transaction.payload().signing_hash().as_slice(),
+                    signing_hash
+                );
+            }
+            2 => {
+                let Ok(transaction) = decode::<TransactionEip1559, _>(&signed[1..]) else {
+                    continue;
+                };
+                assert_eq!(transaction.encode(), signed);
+                assert_eq!(transaction.transaction_id().as_slice(), transaction_id);
+                assert_eq!(
+                    transaction.payload().signing_hash().as_slice(),
+                    signing_hash
+                );
+            }
+            _ => continue,
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 1000);
+}
+
+#[test]
+#[ignore]
+fn test_recovering_sender_of_transaction_eip_1559() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        if value["tx"]["type"].as_u64().unwrap() != 2 {
+            continue;
+        }
+        let signed = hex::decode(&value["signed"].as_str().unwrap()[2..]).unwrap();
+        let Ok(transaction) = decode::<TransactionEip1559, _>(&signed[1..]) else {
+            continue;
+        };
+
+        // The sender is recovered over the signing hash, not the ID.
+        assert_eq!(
+            transaction.recover_sender().unwrap(),
+            value["address"].as_str().unwrap().try_into().unwrap()
+        );
+
+        count += 1;
+    }
+
+    assert!(count > 500);
+}
@@ -7,9 +7,10 @@
 use devtools::hex::byte_aligned_hex;
 use devtools::path::integration_testing_data_path;
 use lightcryptotools::bigint::BigInt;
+use lightcryptotools::blockchain::ethereum::account::EoaPrivateKey;
 use lightcryptotools::blockchain::ethereum::transaction::TransactionBuilder;
 use lightcryptotools::blockchain::ethereum::types::{AccessList, AccessListItem, StorageKey};
-use lightcryptotools::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::ecdsa::{PrivateKey, SigningOptions};
 use lightcryptotools::crypto::secp256k1;
 use serde_json::Value;
@@ -60,7 +61,7 @@ fn test_signing_transaction_legacy() {
         let d = BigInt::from_hex(&d_hex[2..]).unwrap();
         let private_key = PrivateKey::new(d, curve).unwrap();
 
-        let transaction = TransactionBuilder::new()
+        let builder = TransactionBuilder::new()
             .with_nonce(
                 u64::from_str_radix(&nonce_hex.unwrap()[2..], 16)
                     .unwrap_or_default()
@@ -68,10 +69,12 @@ fn test_signing_transaction_legacy() {
                     .unwrap(),
             )
             .with_gas_price(gas_price_hex.unwrap().try_into().unwrap())
-            .with_gas_limit(gas_limit)
+            .with_gas_limit(gas_limit.into())
             .with_destination(to_hex.unwrap().try_into().unwrap())
             .with_amount(value_hex.unwrap().try_into().unwrap())
-            .with_data(hex_to_bytes(&data_hex.unwrap()[2..]).unwrap())
+            .with_data(hex::decode(&data_hex.unwrap()[2..]).unwrap());
+        let transaction = builder
+            .clone()
             .take_and_build_payload_legacy()
             .unwrap()
             .take_and_sign_with_options(
@@ -84,10 +87,25 @@ fn test_signing_transaction_legacy() {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             value["signedTransaction"].as_str().unwrap()[2..]
         );
 
+        // The convenience methods sign with the same options
+        let eoa_private_key = EoaPrivateKey::from_any_hex(d_hex).unwrap();
+        let payload = builder.clone().take_and_build_payload_legacy().unwrap();
+        assert_eq!(
+            eoa_private_key.sign_legacy(payload).unwrap().encode(),
+            transaction.encode()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .take_and_sign_typed(0x0, &eoa_private_key)
+                .unwrap(),
+            transaction.encode()
+        );
+
         count += 1;
     }
 
@@ -140,7 +158,7 @@ fn test_signing_transaction_eip_155_with_chain_id_5() {
         let d = BigInt::from_hex(&d_hex[2..]).unwrap();
         let private_key = PrivateKey::new(d, curve).unwrap();
 
-        let transaction = TransactionBuilder::new()
+        let builder = TransactionBuilder::new()
             .with_chain_id(CHAIN_ID.into())
             .with_nonce(
                 u64::from_str_radix(&nonce_hex.unwrap()[2..], 16)
@@ -149,10 +167,12 @@ fn test_signing_transaction_eip_155_with_chain_id_5() {
                     .unwrap(),
             )
             .with_gas_price(gas_price_hex.unwrap().try_into().unwrap())
-            .with_gas_limit(gas_limit)
+            .with_gas_limit(gas_limit.into())
             .with_destination(to_hex.unwrap().try_into().unwrap())
             .with_amount(value_hex.unwrap().try_into().unwrap())
-            .with_data(hex_to_bytes(&data_hex.unwrap()[2..]).unwrap())
+            .with_data(hex::decode(&data_hex.unwrap()[2..]).unwrap());
+        let transaction = builder
+            .clone()
             .take_and_build_payload_eip_155()
             .unwrap()
             .take_and_sign_with_options(
@@ -165,10 +185,25 @@ fn test_signing_transaction_eip_155_with_chain_id_5() {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             value["signedTransactionChainId5"].as_str().unwrap()[2..]
         );
 
+        // The convenience methods sign with the same options
+        let eoa_private_key = EoaPrivateKey::from_any_hex(d_hex).unwrap();
+        let payload = builder.clone().take_and_build_payload_eip_155().unwrap();
+        assert_eq!(
+            eoa_private_key.sign_eip155(payload).unwrap().encode(),
+            transaction.encode()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .take_and_sign_typed(0x0, &eoa_private_key)
+                .unwrap(),
+            transaction.encode()
+        );
+
         count += 1;
     }
 
@@ -233,7 +268,7 @@ fn test_signing_transaction_eip_2930() {
         let d = BigInt::from_hex(&d_hex[2..]).unwrap();
         let private_key = PrivateKey::new(d, curve).unwrap();
 
-        let transaction = TransactionBuilder::new()
+        let builder = TransactionBuilder::new()
             .with_chain_id(chain_id.unwrap().into())
             .with_nonce(nonce.unwrap().try_into().unwrap())
             .with_gas_price(
@@ -242,11 +277,13 @@ fn test_signing_transaction_eip_2930() {
                     .try_into()
                     .unwrap(),
             )
-            .with_gas_limit(gas_limit)
+            .with_gas_limit(gas_limit.into())
             .with_destination(to_hex.unwrap().try_into().unwrap())
             .with_amount(value_hex.unwrap().try_into().unwrap())
-            .with_data(hex_to_bytes(&data_hex.unwrap()[2..]).unwrap())
-            .with_access_list(access_list)
+            .with_data(hex::decode(&data_hex.unwrap()[2..]).unwrap())
+            .with_access_list(access_list);
+        let transaction = builder
+            .clone()
             .take_and_build_payload_eip_2930()
             .unwrap()
             .take_and_sign_with_options(
@@ -259,16 +296,141 @@ fn test_signing_transaction_eip_2930() {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             value["signed"].as_str().unwrap()[2..]
         );
 
+        // The convenience methods sign with the same options
+        let eoa_private_key = EoaPrivateKey::from_any_hex(d_hex).unwrap();
+        let payload = builder.clone().take_and_build_payload_eip_2930().unwrap();
+        assert_eq!(
+            eoa_private_key.sign_eip2930(payload).unwrap().encode(),
+            transaction.encode()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .take_and_sign_typed(0x1, &eoa_private_key)
+                .unwrap(),
+            transaction.encode()
+        );
+
         count += 1;
     }
 
     assert!(count > 500);
 }
 
+#[cfg(feature = "serde")]
+#[test]
+fn test_access_list_json() {
+    use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+    use lightcryptotools::tools::codable::encode;
+
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let mut count = 0;
+    for value in value_vec {
+        let tx_value = &value["tx"];
+        if tx_value["type"].as_u64().unwrap() != 1 || tx_value["accessList"].is_null() {
+            continue;
+        }
+
+        // The access list constructed by `test_signing_transaction_eip_2930`
+        let access_list = json_object_to_access_list(&tx_value["accessList"]);
+        let deserialized: AccessList =
+            serde_json::from_value(tx_value["accessList"].clone()).unwrap();
+        assert_eq!(
+            encode::<_, RlpEncodingItem>(&deserialized),
+            encode::<_, RlpEncodingItem>(&access_list)
+        );
+        assert_eq!(
+            serde_json::to_value(&deserialized).unwrap(),
+            serde_json::to_value(&access_list).unwrap()
+        );
+
+        count += 1;
+    }
+
+    assert!(count > 500);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_access_list_json_rpc_response() {
+    use lightcryptotools::blockchain::ethereum::account::EoaPublicKey;
+    use lightcryptotools::crypto::ecdsa::{
+        recover_public_keys_from_signature, Signature, SignatureRecoveryId,
+    };
+
+    // The `eth_getTransactionByHash` response of a Ropsten transaction, from the tests of
+    // ethers-rs.
+    let path =
+        integration_testing_data_path("blockchain/ethereum/rpc_transaction_access_list.json");
+    let file = File::open(path).unwrap();
+    let tx_value: Value = serde_json::from_reader(file).unwrap();
+
+    let access_list: AccessList =
+        serde_json::from_value(tx_value["accessList"].clone()).unwrap();
+    assert_eq!(access_list.0.len(), 1);
+    assert_eq!(access_list.0[0].storage_keys.len(), 2);
+    assert_eq!(
+        serde_json::to_value(&access_list).unwrap(),
+        tx_value["accessList"]
+    );
+
+    // The signature only verifies if the access list is encoded as it was signed.
+    let field = |name: &str| tx_value[name].as_str().unwrap();
+    let payload = TransactionBuilder::new()
+        .with_chain_id(
+            u64::from_str_radix(&field("chainId")[2..], 16)
+                .unwrap()
+                .into(),
+        )
+        .with_nonce(
+            u64::from_str_radix(&field("nonce")[2..], 16)
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        )
+        .with_max_priority_fee_per_gas(field("maxPriorityFeePerGas").try_into().unwrap())
+        .with_max_fee_per_gas(field("maxFeePerGas").try_into().unwrap())
+        .with_gas_limit(u64::from_str_radix(&field("gas")[2..], 16).unwrap().into())
+        .with_destination(field("to").try_into().unwrap())
+        .with_amount(
+            byte_aligned_hex(field("value"))
+                .as_ref()
+                .try_into()
+                .unwrap(),
+        )
+        .with_data(hex::decode(&field("input")[2..]).unwrap())
+        .with_access_list(access_list)
+        .take_and_build_payload_eip_1559()
+        .unwrap();
+
+    assert_eq!(field("v"), "0x1");
+    let signature = Signature::new(
+        BigInt::from_hex(&field("r")[2..]).unwrap(),
+        BigInt::from_hex(&field("s")[2..]).unwrap(),
+        secp256k1(),
+    )
+    .unwrap();
+    let public_key = recover_public_keys_from_signature(
+        &signature,
+        &payload.signing_hash(),
+        Some(SignatureRecoveryId::LowXOddY),
+    )
+    .unwrap()
+    .remove(0);
+    assert_eq!(
+        EoaPublicKey(public_key).address(),
+        field("from").try_into().unwrap()
+    );
+}
+
 #[test]
 #[ignore]
 fn test_signing_transaction_eip_1559() {
@@ -329,7 +491,7 @@ fn test_signing_transaction_eip_1559() {
         let d = BigInt::from_hex(&d_hex[2..]).unwrap();
         let private_key = PrivateKey::new(d, curve).unwrap();
 
-        let transaction = TransactionBuilder::new()
+        let builder = TransactionBuilder::new()
             .with_chain_id(chain_id.unwrap().into())
             .with_nonce(nonce.unwrap().try_into().unwrap())
             .with_max_priority_fee_per_gas(
@@ -344,11 +506,13 @@ fn test_signing_transaction_eip_1559() {
                     .try_into()
                     .unwrap(),
             )
-            .with_gas_limit(gas_limit)
+            .with_gas_limit(gas_limit.into())
             .with_destination(to_hex.unwrap().try_into().unwrap())
             .with_amount(value_hex.unwrap().try_into().unwrap())
-            .with_data(hex_to_bytes(&data_hex.unwrap()[2..]).unwrap())
-            .with_access_list(access_list)
+            .with_data(hex::decode(&data_hex.unwrap()[2..]).unwrap())
+            .with_access_list(access_list);
+        let transaction = builder
+            .clone()
             .take_and_build_payload_eip_1559()
             .unwrap()
             .take_and_sign_with_options(
@@ -361,10 +525,25 @@ fn test_signing_transaction_eip_1559() {
             .unwrap();
 
         assert_eq!(
-            bytes_to_lower_hex(&transaction.encode()),
+            hex::encode_lower(&transaction.encode()),
             value["signed"].as_str().unwrap()[2..]
         );
 
+        // The convenience methods sign with the same options
+        let eoa_private_key = EoaPrivateKey::from_any_hex(d_hex).unwrap();
+        let payload = builder.clone().take_and_build_payload_eip_1559().unwrap();
+        assert_eq!(
+            eoa_private_key.sign_eip1559(payload).unwrap().encode(),
+            transaction.encode()
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .take_and_sign_typed(0x2, &eoa_private_key)
+                .unwrap(),
+            transaction.encode()
+        );
+
         count += 1;
     }
 
@@ -0,0 +1,144 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Passes the unsigned transactions of ethers.js from an online constructor, holding only
+//! the addresses, to an offline signer as `UnsignedTransactionBundle`, and compares the
+//! signed transactions with those of ethers.js.
+
+use devtools::path::integration_testing_data_path;
+use lightcryptotools::blockchain::ethereum::account::EoaPrivateKey;
+use lightcryptotools::blockchain::ethereum::unsigned_bundle::{
+    BundleSigningError, BundleValidationError, UnsignedPayload, UnsignedTransactionBundle,
+};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::hash::{Keccak256, UnkeyedHash};
+use serde_json::Value;
+use std::fs::File;
+
+// The cases of each transaction type, signing all of them is slow.
+const CASE_COUNT: usize = 8;
+
+#[test]
+fn test_signing_bundles_of_transactions() {
+    let path = integration_testing_data_path("blockchain/ethereum/ethers.js/transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    let cases = value_vec
+        .iter()
+        .filter(|value| value["to"].is_string())
+        .take(CASE_COUNT);
+    let mut count = 0;
+    for value in cases {
+        for (unsigned_key, signed_key) in [
+            ("unsignedTransaction", "signedTransaction"),
+            ("unsignedTransactionChainId5", "signedTransactionChainId5"),
+        ] {
+            sign_and_compare(
+                value["name"].as_str().unwrap(),
+                value["accountAddress"].as_str().unwrap(),
+                value["privateKey"].as_str().unwrap(),
+                value[unsigned_key].as_str().unwrap(),
+                value[signed_key].as_str().unwrap(),
+            );
+            count += 1;
+        }
+    }
+
+    assert_eq!(count, CASE_COUNT * 2);
+}
+
+#[test]
+fn test_signing_bundles_of_typed_transactions() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+
+    for tx_type in [1, 2] {
+        let cases = value_vec
+            .iter()
+            .filter(|value| {
+                value["tx"]["type"].as_u64() == Some(tx_type) && value["tx"]["to"].is_string()
+            })
+            .take(CASE_COUNT);
+        let mut count = 0;
+        for value in cases {
+            sign_and_compare(
+                value["name"].as_str().unwrap(),
+                value["address"].as_str().unwrap(),
+                value["key"].as_str().unwrap(),
+                value["unsigned"].as_str().unwrap(),
+                value["signed"].as_str().unwrap(),
+            );
+            count += 1;
+        }
+
+        assert_eq!(count, CASE_COUNT);
+    }
+}
+
+#[test]
+fn test_refusing_bundles_of_other_senders() {
+    let path =
+        integration_testing_data_path("blockchain/ethereum/ethers.js/typed-transactions.json");
+    let file = File::open(path).unwrap();
+    let value_vec: Vec<Value> = serde_json::from_reader(file).unwrap();
+    let mut cases = value_vec
+        .iter()
+        .filter(|value| value["tx"]["to"].is_string());
+    let (value, other_value) = (cases.next().unwrap(), cases.next().unwrap());
+
+    let payload = UnsignedPayload::decode(
+        &hex::decode(&value["unsigned"].as_str().unwrap()[2..]).unwrap(),
+    )
+    .unwrap();
+    let bundle = UnsignedTransactionBundle::new(
+        payload,
+        value["address"].as_str().unwrap().try_into().unwrap(),
+        value["name"].as_str().unwrap().to_owned(),
+    );
+    let other_key = EoaPrivateKey::from_any_hex(other_value["key"].as_str().unwrap()).unwrap();
+    assert!(matches!(
+        bundle.sign(&other_key),
+        Err(BundleSigningError::InvalidBundle(
+            BundleValidationError::SenderMismatch { .. }
+        ))
+    ));
+}
+
+fn sign_and_compare(name: &str, address: &str, key: &str, unsigned: &str, signed: &str) {
+    // Online: constructs the bundle, knowing only the address of the sender.
+    let unsigned = hex::decode(&unsigned[2..]).unwrap();
+    let payload = UnsignedPayload::decode(&unsigned).unwrap();
+    assert_eq!(payload.encode(), unsigned, "{name}");
+    let encoded =
+        UnsignedTransactionBundle::new(payload, address.try_into().unwrap(), name.to_owned())
+            .encode();
+
+    // Offline: validates and signs the bundle.
+    let bundle = UnsignedTransactionBundle::decode(&encoded).unwrap();
+    bundle.validate().unwrap();
+    assert_eq!(bundle.description(), name);
+    assert_eq!(
+        bundle.payload().unwrap().signing_hash().as_slice(),
+        Keccak256::new().digest(&unsigned),
+        "{name}"
+    );
+    let private_key = EoaPrivateKey::from_any_hex(key).unwrap();
+    let signed_bundle = bundle.sign(&private_key).unwrap();
+
+    // Online: broadcasts the signed transaction.
+    assert_eq!(
+        hex::encode_lower(&signed_bundle.raw_transaction),
+        signed[2..],
+        "{name}"
+    );
+    assert_eq!(
+        signed_bundle.transaction_id.as_slice(),
+        Keccak256::new().digest(&signed_bundle.raw_transaction)
+    );
+}
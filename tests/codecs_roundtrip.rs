@@ -0,0 +1,561 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Property tests of the RLP and SSZ codecs over randomly generated values:
+//! - encode -> decode -> encode produces identical bytes.
+//! - Decoding an encoding with a flipped bit never panics.
+//! - The payloads of the transactions encode the same as assembled field by field.
+//!
+//! The runs are controlled with the environment variables:
+//! - `CODECS_ROUNDTRIP_SEED`: the seed of the first case, each following case increments
+//!   it by 1. A random seed is picked if unset. The seed of a failing case is reported in
+//!   the panic message, rerun with it and `CODECS_ROUNDTRIP_CASES=1` to reproduce.
+//! - `CODECS_ROUNDTRIP_CASES`: the number of cases per codec, 2000 by default.
+//! - `CODECS_ROUNDTRIP_CORPUS`: if set, writes the encodings of failing cases, as hex,
+//!   to "target/codecs_roundtrip/".
+
+use lightcryptotools::bigint::{BigInt, BigUint};
+use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip155, TransactionEip1559, TransactionEip2930,
+    TransactionLegacy,
+};
+use lightcryptotools::blockchain::ethereum::types::{
+    AccessList, AccessListItem, Address, ChainId, EoaNonce, StorageKey, Wei,
+};
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::{PrivateKey, SigningOptions};
+use lightcryptotools::crypto::secp256k1;
+use lightcryptotools::tools::codable::{decode, encode, Encodable, EncodingItem};
+use quickcheck::{Arbitrary, Gen};
+use ssz_test_structs::{ComplexTestStruct, FixedTestStruct, VarTestStruct};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+#[path = "common/ssz_test_structs.rs"]
+mod ssz_test_structs;
+
+const GEN_SIZE: usize = 64;
+const DEFAULT_CASES: u64 = 2000;
+
+#[test]
+fn test_access_list_roundtrip() {
+    check_codec(
+        "access_list",
+        |g| encode(&arbitrary_access_list(g)),
+        |data| decode::<AccessList, _>(data).ok().map(|list| encode(&list)),
+    );
+}
+
+#[test]
+fn test_transaction_legacy_roundtrip() {
+    check_codec(
+        "transaction_legacy",
+        |g| TransactionFields::arbitrary(g).encode_signed(TransactionKind::Legacy, g),
+        |data| {
+            decode::<TransactionLegacy, _>(data)
+                .ok()
+                .map(|tx| tx.encode())
+        },
+    );
+}
+
+#[test]
+fn test_transaction_eip_155_roundtrip() {
+    check_codec(
+        "transaction_eip_155",
+        |g| TransactionFields::arbitrary(g).encode_signed(TransactionKind::Eip155, g),
+        |data| {
+            decode::<TransactionEip155, _>(data)
+                .ok()
+                .map(|tx| tx.encode())
+        },
+    );
+}
+
+#[test]
+fn test_transaction_eip_2930_roundtrip() {
+    check_codec(
+        "transaction_eip_2930",
+        |g| TransactionFields::arbitrary(g).encode_signed(TransactionKind::Eip2930, g),
+        |data| {
+            let (&transaction_type, rlp_data) = data.split_first()?;
+            if transaction_type != TransactionEip2930::transaction_type() {
+                return None;
+            }
+            decode::<TransactionEip2930, _>(rlp_data)
+                .ok()
+                .map(|tx| tx.encode())
+        },
+    );
+}
+
+#[test]
+fn test_transaction_eip_1559_roundtrip() {
+    check_codec(
+        "transaction_eip_1559",
+        |g| TransactionFields::arbitrary(g).encode_signed(TransactionKind::Eip1559, g),
+        |data| {
+            let (&transaction_type, rlp_data) = data.split_first()?;
+            if transaction_type != TransactionEip1559::transaction_type() {
+                return None;
+            }
+            decode::<TransactionEip1559, _>(rlp_data)
+                .ok()
+                .map(|tx| tx.encode())
+        },
+    );
+}
+
+#[test]
+fn test_transaction_payload_encoding_differential() {
+    // Compares the encodings of the (unsigned) payloads to the ones assembled field by
+    // field in `TransactionFields::encode`.
+    for_each_case("transaction_payload", |g| {
+        let kind = *g
+            .choose(&[
+                TransactionKind::Legacy,
+                TransactionKind::Eip155,
+                TransactionKind::Eip2930,
+                TransactionKind::Eip1559,
+            ])
+            .unwrap();
+        let fields = TransactionFields::arbitrary(g);
+        let expected = fields.encode(kind, None);
+
+        let mut builder = fields.builder();
+        let data = match kind {
+            TransactionKind::Legacy => {
+                encode(&builder.take_and_build_payload_legacy().unwrap())
+            }
+            TransactionKind::Eip155 => {
+                encode(&builder.take_and_build_payload_eip_155().unwrap())
+            }
+            TransactionKind::Eip2930 => {
+                encode(&builder.take_and_build_payload_eip_2930().unwrap())
+            }
+            TransactionKind::Eip1559 => {
+                encode(&builder.take_and_build_payload_eip_1559().unwrap())
+            }
+        };
+        if data != expected {
+            return Err(("differential", data));
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn test_signed_transaction_roundtrip() {
+    // Signing is slow without optimizations, only a few cases are run.
+    let private_key = PrivateKey::new(
+        BigInt::from_hex("89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d")
+            .unwrap(),
+        secp256k1(),
+    )
+    .unwrap();
+    let signing_options = SigningOptions {
+        employ_extra_random_data: false,
+        ..Default::default()
+    };
+    for seed in 0..8 {
+        let mut g = Gen::from_size_and_seed(GEN_SIZE, seed);
+        let transaction = TransactionFields::arbitrary(&mut g)
+            .builder()
+            .take_and_build_payload_eip_1559()
+            .unwrap()
+            .take_and_sign_with_options(&private_key, &signing_options)
+            .unwrap();
+        let data = transaction.encode();
+
+        let decoded: TransactionEip1559 = decode(&data[1..]).unwrap();
+        assert_eq!(decoded.encode(), data);
+        assert_eq!(decoded.recover_sender(), transaction.recover_sender());
+        assert!(decoded.recover_sender().is_some());
+    }
+}
+
+#[test]
+fn test_ssz_fixed_test_struct_roundtrip() {
+    check_codec(
+        "ssz_fixed_test_struct",
+        |g| encode(&FixedTestStruct::arbitrary(g)),
+        |data| decode::<FixedTestStruct, _>(data).ok().map(|v| encode(&v)),
+    );
+}
+
+#[test]
+fn test_ssz_var_test_struct_roundtrip() {
+    check_codec(
+        "ssz_var_test_struct",
+        |g| encode(&VarTestStruct::arbitrary(g)),
+        |data| decode::<VarTestStruct, _>(data).ok().map(|v| encode(&v)),
+    );
+}
+
+#[test]
+fn test_ssz_complex_test_struct_roundtrip() {
+    check_codec(
+        "ssz_complex_test_struct",
+        |g| encode(&ComplexTestStruct::arbitrary(g)),
+        |data| {
+            decode::<ComplexTestStruct, _>(data)
+                .ok()
+                .map(|v| encode(&v))
+        },
+    );
+}
+
+/// Checks the properties of a codec over the encodings created with `generate`.
+///
+/// `reencode` decodes the data and encodes the result again, returns `None` if the
+/// decoding fails.
+fn check_codec(
+    name: &str,
+    generate: impl Fn(&mut Gen) -> Vec<u8>,
+    reencode: impl Fn(&[u8]) -> Option<Vec<u8>>,
+) {
+    for_each_case(name, |g| {
+        let data = generate(g);
+        if reencode(&data).as_ref() != Some(&data) {
+            return Err(("roundtrip", data));
+        }
+
+        if data.is_empty() {
+            return Ok(());
+        }
+        let mut mutated_data = data;
+        let bit = usize::arbitrary(g) % (mutated_data.len() * 8);
+        mutated_data[bit / 8] ^= 1 << (bit % 8);
+        if catch_unwind(AssertUnwindSafe(|| reencode(&mutated_data))).is_err() {
+            return Err(("mutation", mutated_data));
+        }
+        Ok(())
+    });
+}
+
+/// Runs `case` with a `Gen` of a different seed for each case.
+///
+/// On failure, `case` returns the name of the failing property and the offending data.
+fn for_each_case(
+    name: &str,
+    mut case: impl FnMut(&mut Gen) -> Result<(), (&'static str, Vec<u8>)>,
+) {
+    let first_seed = env::var("CODECS_ROUNDTRIP_SEED")
+        .map(|s| s.parse().expect("invalid CODECS_ROUNDTRIP_SEED"))
+        .unwrap_or_else(|_| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64
+        });
+    let cases = env::var("CODECS_ROUNDTRIP_CASES")
+        .map(|s| s.parse().expect("invalid CODECS_ROUNDTRIP_CASES"))
+        .unwrap_or(DEFAULT_CASES);
+
+    for i in 0..cases {
+        let seed = first_seed.wrapping_add(i);
+        let mut g = Gen::from_size_and_seed(GEN_SIZE, seed);
+        if let Err((property, data)) = case(&mut g) {
+            let hex = hex::encode_lower(&data);
+            if env::var_os("CODECS_ROUNDTRIP_CORPUS").is_some() {
+                let dir =
+                    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/codecs_roundtrip");
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(dir.join(format!("{name}-{property}-{seed}.hex")), &hex).unwrap();
+            }
+            panic!("{name}: {property} failed with CODECS_ROUNDTRIP_SEED={seed}, data: {hex}");
+        }
+    }
+}
+
+fn arbitrary_bytes<const N: usize>(g: &mut Gen) -> [u8; N] {
+    let mut bytes = [0; N];
+    for byte in &mut bytes {
+        *byte = u8::arbitrary(g);
+    }
+    bytes
+}
+
+/// Returns a `BigUint` of up to 32 bytes.
+fn arbitrary_biguint(g: &mut Gen) -> BigUint {
+    let len = usize::arbitrary(g) % 33;
+    let bytes = &arbitrary_bytes::<32>(g)[..len];
+    BigUint::from_hex(format!("00{}", hex::encode_lower(bytes))).unwrap()
+}
+
+fn arbitrary_wei(g: &mut Gen) -> Wei {
+    Wei::from(arbitrary_biguint(g))
+}
+
+fn arbitrary_access_list(g: &mut Gen) -> AccessList {
+    let items = (0..usize::arbitrary(g) % 4)
+        .map(|_| AccessListItem {
+            address: Address(arbitrary_bytes(g)),
+            storage_keys: (0..usize::arbitrary(g) % 4)
+                .map(|_| {
+                    let hex = hex::encode_lower(&arbitrary_bytes::<32>(g));
+                    StorageKey::try_from(format!("0x{hex}").as_str()).unwrap()
+                })
+                .collect(),
+        })
+        .collect();
+    AccessList(items)
+}
+
+#[derive(Clone, Copy)]
+enum TransactionKind {
+    Legacy,
+    Eip155,
+    Eip2930,
+    Eip1559,
+}
+
+/// The fields of the transaction types, each type picks the ones it has.
+struct TransactionFields {
+    chain_id: u64,
+    nonce: EoaNonce,
+    gas_price: Wei,
+    max_priority_fee_per_gas: Wei,
+    max_fee_per_gas: Wei,
+    gas_limit: u64,
+    destination: Address,
+    amount: Wei,
+    data: Vec<u8>,
+    access_list: AccessList,
+}
+
+impl TransactionFields {
+    fn arbitrary(g: &mut Gen) -> TransactionFields {
+        let nonce = u64::arbitrary(g) % u64::MAX;
+        TransactionFields {
+            chain_id: u64::arbitrary(g),
+            nonce: EoaNonce::from_u64(nonce).unwrap(),
+            gas_price: arbitrary_wei(g),
+            max_priority_fee_per_gas: arbitrary_wei(g),
+            max_fee_per_gas: arbitrary_wei(g),
+            gas_limit: u64::arbitrary(g),
+            destination: Address(arbitrary_bytes(g)),
+            amount: arbitrary_wei(g),
+            data: Vec::<u8>::arbitrary(g),
+            access_list: arbitrary_access_list(g),
+        }
+    }
+
+    fn builder(self) -> TransactionBuilder {
+        TransactionBuilder::new()
+            .with_chain_id(self.chain_id.into())
+            .with_nonce(self.nonce)
+            .with_gas_price(self.gas_price)
+            .with_max_priority_fee_per_gas(self.max_priority_fee_per_gas)
+            .with_max_fee_per_gas(self.max_fee_per_gas)
+            .with_gas_limit(self.gas_limit.into())
+            .with_destination(self.destination)
+            .with_amount(self.amount)
+            .with_data(self.data)
+            .with_access_list(self.access_list)
+    }
+
+    /// Encodes the transaction of `kind` field by field, with the signature fields
+    /// `signature`, or as the payload to sign if `None`.
+    ///
+    /// The transaction type prefix isn't included.
+    fn encode(&self, kind: TransactionKind, signature: Option<[BigUint; 3]>) -> Vec<u8> {
+        let mut list_encoding_item = RlpEncodingItem::new();
+        let chain_id = ChainId::from(self.chain_id);
+        match kind {
+            TransactionKind::Legacy | TransactionKind::Eip155 => {
+                self.nonce.encode_to(&mut list_encoding_item);
+                self.gas_price.encode_to(&mut list_encoding_item);
+            }
+            TransactionKind::Eip2930 => {
+                chain_id.encode_to(&mut list_encoding_item);
+                self.nonce.encode_to(&mut list_encoding_item);
+                self.gas_price.encode_to(&mut list_encoding_item);
+            }
+            TransactionKind::Eip1559 => {
+                chain_id.encode_to(&mut list_encoding_item);
+                self.nonce.encode_to(&mut list_encoding_item);
+                self.max_priority_fee_per_gas
+                    .encode_to(&mut list_encoding_item);
+                self.max_fee_per_gas.encode_to(&mut list_encoding_item);
+            }
+        }
+        self.gas_limit.encode_to(&mut list_encoding_item);
+        self.destination.encode_to(&mut list_encoding_item);
+        self.amount.encode_to(&mut list_encoding_item);
+        self.data.encode_to(&mut list_encoding_item);
+        if let TransactionKind::Eip2930 | TransactionKind::Eip1559 = kind {
+            self.access_list.encode_to(&mut list_encoding_item);
+        }
+
+        match (signature, kind) {
+            (Some(signature), _) => {
+                for n in &signature {
+                    n.encode_to(&mut list_encoding_item);
+                }
+            }
+            (None, TransactionKind::Eip155) => {
+                chain_id.encode_to(&mut list_encoding_item);
+                0_u64.encode_to(&mut list_encoding_item);
+                0_u64.encode_to(&mut list_encoding_item);
+            }
+            (None, _) => {}
+        }
+
+        let mut encoding_item = RlpEncodingItem::new();
+        encoding_item.encode_list_payload(&mut list_encoding_item);
+        encoding_item.take_data()
+    }
+
+    /// Encodes the signed transaction of `kind` with an arbitrary signature, including the
+    /// transaction type prefix.
+    ///
+    /// The decoders don't validate the signatures.
+    fn encode_signed(&self, kind: TransactionKind, g: &mut Gen) -> Vec<u8> {
+        let y_parity = u8::from(bool::arbitrary(g));
+        let (prefix, v) = match kind {
+            TransactionKind::Legacy => (None, BigUint::from(27 + y_parity)),
+            TransactionKind::Eip155 => (
+                None,
+                BigUint::from(y_parity)
+                    + BigUint::from(self.chain_id) * BigUint::from(2_u8)
+                    + BigUint::from(35_u8),
+            ),
+            TransactionKind::Eip2930 => (
+                Some(TransactionEip2930::transaction_type()),
+                BigUint::from(y_parity),
+            ),
+            TransactionKind::Eip1559 => (
+                Some(TransactionEip1559::transaction_type()),
+                BigUint::from(y_parity),
+            ),
+        };
+        let signature = [v, arbitrary_biguint(g), arbitrary_biguint(g)];
+
+        let mut data: Vec<u8> = prefix.into_iter().collect();
+        data.extend(self.encode(kind, Some(signature)));
+        data
+    }
+}
+
+// Implementing `Encodable` and `Decodable` outside the crate requires the feature
+// "unstable-codable".
+#[cfg(feature = "unstable-codable")]
+mod rlp_tree {
+    use super::*;
+    use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+    use lightcryptotools::blockchain::ethereum::rlp::decoding::RlpDataDecodingError;
+    use lightcryptotools::blockchain::ethereum::rlp::RlpItemType;
+    use lightcryptotools::tools::codable::Decodable;
+
+    #[test]
+    fn test_rlp_tree_roundtrip() {
+        check_codec(
+            "rlp_tree",
+            |g| encode(&RlpTree::arbitrary_with_depth(g, 3)),
+            |data| decode::<RlpTree, _>(data).ok().map(|tree| encode(&tree)),
+        );
+    }
+
+    /// An RLP item of any shape, with the single values decoded as bytes.
+    enum RlpTree {
+        Bytes(Vec<u8>),
+        List(Vec<RlpTree>),
+    }
+
+    impl RlpTree {
+        fn arbitrary_with_depth(g: &mut Gen, depth: usize) -> RlpTree {
+            if depth == 0 || bool::arbitrary(g) {
+                // Mixes the short single values with the ones longer than 55 bytes.
+                let bytes = if bool::arbitrary(g) {
+                    Vec::<u8>::arbitrary(g)
+                } else {
+                    (0..usize::arbitrary(g) % 80)
+                        .map(|_| u8::arbitrary(g))
+                        .collect()
+                };
+                RlpTree::Bytes(bytes)
+            } else {
+                let len = usize::arbitrary(g) % 6;
+                RlpTree::List(
+                    (0..len)
+                        .map(|_| RlpTree::arbitrary_with_depth(g, depth - 1))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    impl Encodable<RlpEncodingItem> for RlpTree {
+        fn encode_to(&self, encoding_item: &mut RlpEncodingItem) {
+            match self {
+                RlpTree::Bytes(bytes) => encoding_item.encode_bytes(bytes),
+                RlpTree::List(trees) => {
+                    let mut list_encoding_item = RlpEncodingItem::new();
+                    for tree in trees {
+                        tree.encode_to(&mut list_encoding_item);
+                    }
+                    encoding_item.encode_list_payload(&mut list_encoding_item);
+                }
+            }
+        }
+    }
+
+    impl<'a> Decodable<'a, RlpDecodingItem<'a>> for RlpTree {
+        fn decode_from(decoding_item: &RlpDecodingItem) -> Result<Self, RlpDataDecodingError> {
+            match decoding_item.item_type {
+                RlpItemType::SingleValue => {
+                    Ok(RlpTree::Bytes(decoding_item.decode_as_bytes()?.to_vec()))
+                }
+                RlpItemType::List => {
+                    let items = decoding_item.decode_as_items()?;
+                    let trees = items
+                        .iter()
+                        .map(RlpTree::decode_from)
+                        .collect::<Result<_, _>>()?;
+                    Ok(RlpTree::List(trees))
+                }
+            }
+        }
+    }
+}
+
+impl FixedTestStruct {
+    fn arbitrary(g: &mut Gen) -> FixedTestStruct {
+        FixedTestStruct {
+            a: u8::arbitrary(g),
+            b: u64::arbitrary(g),
+            c: u32::arbitrary(g),
+        }
+    }
+}
+
+impl VarTestStruct {
+    fn arbitrary(g: &mut Gen) -> VarTestStruct {
+        VarTestStruct {
+            a: u16::arbitrary(g),
+            b: Vec::<u16>::arbitrary(g),
+            c: u8::arbitrary(g),
+        }
+    }
+}
+
+impl ComplexTestStruct {
+    fn arbitrary(g: &mut Gen) -> ComplexTestStruct {
+        ComplexTestStruct {
+            a: u16::arbitrary(g),
+            b: Vec::<u16>::arbitrary(g),
+            c: u8::arbitrary(g),
+            d: Vec::<u8>::arbitrary(g),
+            e: VarTestStruct::arbitrary(g),
+            f: [(); 4].map(|_| FixedTestStruct::arbitrary(g)),
+            g: [(); 2].map(|_| VarTestStruct::arbitrary(g)),
+        }
+    }
+}
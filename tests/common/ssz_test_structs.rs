@@ -0,0 +1,174 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! The containers of the SSZ generic test suite, shared by the test targets:
+//! https://github.com/ethereum/consensus-specs/tree/dev/tests/generators/ssz_generic
+
+use lightcryptotools::blockchain::ethereum::ssz::{
+    SszContainerWriter, SszDataDecodingError, SszDecodingItem, SszType,
+};
+use lightcryptotools::tools::codable::{Decodable, DecodingItem};
+
+// class FixedTestStruct(Container):
+//     A: uint8
+//     B: uint64
+//     C: uint32
+#[derive(Debug, PartialEq, Eq)]
+pub struct FixedTestStruct {
+    pub a: u8,
+    pub b: u64,
+    pub c: u32,
+}
+
+impl SszType for FixedTestStruct {
+    fn size() -> Option<u32> {
+        Some(u8::size().unwrap() + u64::size().unwrap() + u32::size().unwrap())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.a);
+            writer.write_element(&self.b);
+            writer.write_element(&self.c);
+        });
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
+        let decoding_item = SszDecodingItem::new_from_data(bytes).unwrap();
+        let sizes = [u8::size(), u64::size(), u32::size()];
+        let items = decoding_item.decode_as_items(&sizes)?;
+        let mut iter = items.iter();
+
+        let a = u8::decode_from(iter.next().unwrap())?;
+        let b = u64::decode_from(iter.next().unwrap())?;
+        let c = u32::decode_from(iter.next().unwrap())?;
+        Ok(FixedTestStruct { a, b, c })
+    }
+}
+
+// class VarTestStruct(Container):
+//     A: uint16
+//     B: List[uint16, 1024]
+//     C: uint8
+#[derive(Debug, PartialEq, Eq)]
+pub struct VarTestStruct {
+    pub a: u16,
+    pub b: Vec<u16>,
+    pub c: u8,
+}
+
+impl SszType for VarTestStruct {
+    fn size() -> Option<u32> {
+        None
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.a);
+            writer.write_element(&self.b);
+            writer.write_element(&self.c);
+        });
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
+        let decoding_item = SszDecodingItem::new_from_data(bytes).unwrap();
+        let sizes = [u16::size(), Vec::<u16>::size(), u8::size()];
+        let items = decoding_item.decode_as_items(&sizes)?;
+        let mut iter = items.iter();
+
+        let a = u16::decode_from(iter.next().unwrap())?;
+        let b = Vec::<u16>::decode_from(iter.next().unwrap())?;
+        let c = u8::decode_from(iter.next().unwrap())?;
+        Ok(VarTestStruct { a, b, c })
+    }
+}
+
+// class ComplexTestStruct(Container):
+//     A: uint16
+//     B: List[uint16, 128]
+//     C: uint8
+//     D: List[byte, 256]
+//     E: VarTestStruct
+//     F: Vector[FixedTestStruct, 4]
+//     G: Vector[VarTestStruct, 2]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ComplexTestStruct {
+    pub a: u16,
+    pub b: Vec<u16>,
+    pub c: u8,
+    pub d: Vec<u8>,
+    pub e: VarTestStruct,
+    pub f: [FixedTestStruct; 4],
+    pub g: [VarTestStruct; 2],
+}
+
+impl SszType for ComplexTestStruct {
+    fn size() -> Option<u32> {
+        None
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.a);
+            writer.write_element(&self.b);
+            writer.write_element(&self.c);
+            writer.write_element(&self.d);
+            writer.write_element(&self.e);
+            writer.write_element(&self.f);
+            writer.write_element(&self.g);
+        });
+    }
+
+    fn try_from_bytes(bytes: &[u8]) -> Result<Self, SszDataDecodingError> {
+        let decoding_item = SszDecodingItem::new_from_data(bytes).unwrap();
+        let sizes = [
+            u16::size(),
+            Vec::<u16>::size(),
+            u8::size(),
+            Vec::<u8>::size(),
+            VarTestStruct::size(),
+            <[FixedTestStruct; 4]>::size(),
+            <[VarTestStruct; 2]>::size(),
+        ];
+        let items = decoding_item.decode_as_items(&sizes)?;
+        let mut iter = items.iter();
+
+        let a = u16::decode_from(iter.next().unwrap())?;
+        let b = Vec::<u16>::decode_from(iter.next().unwrap())?;
+        let c = u8::decode_from(iter.next().unwrap())?;
+        let d = Vec::<u8>::decode_from(iter.next().unwrap())?;
+        let e = VarTestStruct::decode_from(iter.next().unwrap())?;
+        let f = <[FixedTestStruct; 4]>::decode_from(iter.next().unwrap())?;
+        let g = <[VarTestStruct; 2]>::decode_from(iter.next().unwrap())?;
+        Ok(ComplexTestStruct {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            g,
+        })
+    }
+}
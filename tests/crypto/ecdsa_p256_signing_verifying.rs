@@ -38,7 +38,7 @@ fn test_ecdsa_p256_sha256_sign() {
         },
     )
     .unwrap();
-    assert_eq!(signature.to_p1363_hex(), signature_expected);
+    assert_eq!(signature.to_p1363_hex().unwrap(), signature_expected);
 
     let public_key = private_key.public_key();
     assert!(verify(&hash, &signature, &public_key).unwrap());
@@ -70,7 +70,7 @@ fn test_ecdsa_p256_sha384_sign() {
         &mut hasher,
     )
     .unwrap();
-    assert_eq!(signature.to_p1363_hex(), signature_expected);
+    assert_eq!(signature.to_p1363_hex().unwrap(), signature_expected);
 
     let public_key = private_key.public_key();
     assert!(verify_with_options(
@@ -111,7 +111,7 @@ fn test_ecdsa_p256_sha512_sign() {
         &mut hasher,
     )
     .unwrap();
-    assert_eq!(signature.to_p1363_hex(), signature_expected);
+    assert_eq!(signature.to_p1363_hex().unwrap(), signature_expected);
 
     let public_key = private_key.public_key();
     assert!(verify_with_options(
@@ -6,7 +6,7 @@
 
 use crate::curves::*;
 use devtools::path::integration_testing_data_path;
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::ecdsa::{
     verify_with_options, PublicKey, Signature, VerifyingOptions,
 };
@@ -113,7 +113,7 @@ fn test_ecdsa_wycheproof_p1363<H: UnkeyedHash>(
                 }
             };
 
-            let hash = hasher.digest(&hex_to_bytes(m_hex).unwrap());
+            let hash = hasher.digest(&hex::decode(m_hex).unwrap());
             let enforce_low_s = false;
             let strict_hash_byte_length = false;
             let result = verify_with_options(
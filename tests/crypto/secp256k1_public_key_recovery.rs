@@ -7,7 +7,7 @@
 use crate::curves::*;
 use devtools::path::integration_testing_data_path;
 use lightcryptotools::bigint::BigInt;
-use lightcryptotools::crypto::codecs::{bytes_to_lower_hex, hex_to_bytes};
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::ecdsa::{
     recover_public_keys_from_signature, recover_public_keys_from_signature_with_options,
     sign_with_options, PrivateKey, RecoveryOptions, SignatureRecoveryId, SigningOptions,
@@ -61,7 +61,7 @@ fn test_curve_w25519(enforce_low_s: bool) {
     let curve = w25519();
 
     let hash_hex = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
-    let hash = hex_to_bytes(hash_hex).unwrap();
+    let hash = hex::decode(hash_hex).unwrap();
     let d = BigInt::from(1);
 
     let private_key = PrivateKey::new(d, &curve).unwrap();
@@ -117,7 +117,7 @@ fn test_recovery(enforce_low_s: bool) {
     for value in value_vec {
         let d_hex = value["d"].as_str().unwrap();
         let m_hex = value["m"].as_str().unwrap();
-        let m = hex_to_bytes(m_hex).unwrap();
+        let m = hex::decode(m_hex).unwrap();
 
         // Ignore zero hash, for recovery doesn't allow zero hash input
         if BigInt::from_hex(m_hex).unwrap().is_zero() {
@@ -172,7 +172,7 @@ fn test_recovery_with_curves(enforce_low_s: bool) {
 
 fn test_recovery_with_curve(curve: &EllipticCurveParams, enforce_low_s: bool) {
     let key_bytes = get_os_random_bytes(12).unwrap();
-    let d = BigInt::from_hex(bytes_to_lower_hex(&key_bytes)).unwrap();
+    let d = BigInt::from_hex(hex::encode_lower(&key_bytes)).unwrap();
     let private_key = PrivateKey::new(d, curve).unwrap();
     let public_key = private_key.public_key();
 
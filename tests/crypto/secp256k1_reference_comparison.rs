@@ -0,0 +1,136 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Cross-checks ECDSA signing on secp256k1 against the `k256` crate,
+//! over random private keys and messages.
+
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{Signature as ReferenceSignature, SigningKey, VerifyingKey};
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::crypto::codecs::hex;
+use lightcryptotools::crypto::ecdsa::{
+    sign_with_options, verify, PrivateKey, Signature, SigningOptions,
+};
+use lightcryptotools::crypto::hash::{Sha256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+use lightcryptotools::random::generator::get_os_random_bytes;
+
+const ROUND_COUNT: usize = 256;
+
+#[test]
+#[ignore]
+fn test_signing_matches_reference_low_s() {
+    for _ in 0..ROUND_COUNT {
+        let (d_bytes, hash) = random_private_key_and_hash();
+        let private_key = private_key(&d_bytes);
+        let (signature, recovery_id) = sign_with_options(
+            &hash,
+            &private_key,
+            &SigningOptions {
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // `k256` always produces low-s signatures: with RFC 6979 nonces,
+        // both `r`, `s` and the recovery id are expected to be identical.
+        let signing_key = SigningKey::from_slice(&d_bytes).unwrap();
+        let (reference_signature, reference_recovery_id) =
+            signing_key.sign_prehash_recoverable(&hash).unwrap();
+        assert_eq!(
+            signature.to_p1363_hex().unwrap(),
+            hex::encode_lower(&reference_signature.to_bytes())
+        );
+        assert_eq!(recovery_id as u8, reference_recovery_id.to_byte());
+
+        verify_with_reference(&signing_key, &hash, &signature);
+    }
+}
+
+#[test]
+#[ignore]
+fn test_signing_matches_reference_non_low_s() {
+    let secp256k1 = secp256k1();
+
+    let mut high_s_count = 0;
+    for _ in 0..ROUND_COUNT {
+        let (d_bytes, hash) = random_private_key_and_hash();
+        let private_key = private_key(&d_bytes);
+        let (signature, _) = sign_with_options(
+            &hash,
+            &private_key,
+            &SigningOptions {
+                enforce_low_s: false,
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let signing_key = SigningKey::from_slice(&d_bytes).unwrap();
+        let (reference_signature, _) = signing_key.sign_prehash_recoverable(&hash).unwrap();
+        let reference_r =
+            BigInt::from_hex(hex::encode_lower(&reference_signature.r().to_bytes())).unwrap();
+        let reference_s =
+            BigInt::from_hex(hex::encode_lower(&reference_signature.s().to_bytes())).unwrap();
+
+        // The same nonce is used, so `r` is identical,
+        // and `s` is either identical or the negation of the low-s one.
+        assert_eq!(signature.r, reference_r);
+        if signature.s != reference_s {
+            assert_eq!(signature.s, &secp256k1.base_point_order - &reference_s);
+            high_s_count += 1;
+        }
+
+        verify_with_reference(&signing_key, &hash, &signature);
+
+        // The reference signature verifies with this crate as well.
+        let reference_signature = Signature::new(reference_r, reference_s, secp256k1).unwrap();
+        assert!(verify(&hash, &reference_signature, &private_key.public_key()).unwrap());
+    }
+
+    // About half of the signatures are expected to be high-s.
+    assert!(high_s_count > 0);
+}
+
+fn random_private_key_and_hash() -> ([u8; 32], Vec<u8>) {
+    let secp256k1 = secp256k1();
+    let d_bytes: [u8; 32] = loop {
+        let bytes = get_os_random_bytes(32).unwrap();
+        let d = BigInt::from_hex(hex::encode_lower(&bytes)).unwrap();
+        if !d.is_zero() && d < secp256k1.base_point_order {
+            break bytes.try_into().unwrap();
+        }
+    };
+
+    let message_len = get_os_random_bytes(1).unwrap()[0] as u32;
+    let message = get_os_random_bytes(message_len).unwrap();
+    (d_bytes, Sha256::new().digest(message))
+}
+
+fn private_key(d_bytes: &[u8]) -> PrivateKey<'static> {
+    PrivateKey::new(
+        BigInt::from_hex(hex::encode_lower(d_bytes)).unwrap(),
+        secp256k1(),
+    )
+    .unwrap()
+}
+
+/// Verifies `signature` with `k256`, which only accepts low-s signatures.
+fn verify_with_reference(signing_key: &SigningKey, hash: &[u8], signature: &Signature) {
+    let reference_signature = ReferenceSignature::from_slice(
+        &hex::decode(signature.to_p1363_hex().unwrap()).unwrap(),
+    )
+    .unwrap();
+    let reference_signature = reference_signature
+        .normalize_s()
+        .unwrap_or(reference_signature);
+    let verifying_key = VerifyingKey::from(signing_key);
+    assert!(verifying_key
+        .verify_prehash(hash, &reference_signature)
+        .is_ok());
+}
@@ -6,7 +6,7 @@
 
 use devtools::path::integration_testing_data_path;
 use lightcryptotools::bigint::BigInt;
-use lightcryptotools::crypto::codecs::hex_to_bytes;
+use lightcryptotools::crypto::codecs::hex;
 use lightcryptotools::crypto::ecdsa::{
     sign_with_options, verify, verify_with_options, PrivateKey, PublicKey, Signature,
     SigningOptions, VerifyingOptions,
@@ -32,7 +32,7 @@ fn test_ecdsa_secp256k1_signing_cases() {
     for (hash_hex, d_hex, signature_hex) in data {
         let private_key = PrivateKey::new(BigInt::from_hex(d_hex).unwrap(), secp256k1).unwrap();
         let (signature, _) = sign_with_options(
-            &hex_to_bytes(hash_hex).unwrap(),
+            &hex::decode(hash_hex).unwrap(),
             &private_key,
             &SigningOptions {
                 enforce_low_s: false,
@@ -42,7 +42,7 @@ fn test_ecdsa_secp256k1_signing_cases() {
         )
         .unwrap();
 
-        let hex = signature.to_p1363_hex();
+        let hex = signature.to_p1363_hex().unwrap();
         assert_eq!(hex, signature_hex);
     }
 }
@@ -73,7 +73,7 @@ fn test_verify_non_strict_msg_bb5a() {
     let public_key = PublicKey::from_sec1_hex(point_hex, secp256k1).unwrap();
     let signature = Signature::new(r, s, secp256k1).unwrap();
 
-    assert!(verify(&hex_to_bytes(hash_hex).unwrap(), &signature, &public_key).unwrap());
+    assert!(verify(&hex::decode(hash_hex).unwrap(), &signature, &public_key).unwrap());
 }
 
 #[test]
@@ -141,7 +141,7 @@ fn test_sign_hash_greater_than_base_point_order() {
     let private_key = PrivateKey::new(BigInt::one(), secp256k1).unwrap();
     for (hash_hex, signature_hex) in data {
         let (signature, _) = sign_with_options(
-            &hex_to_bytes(&hash_hex).unwrap(),
+            &hex::decode(&hash_hex).unwrap(),
             &private_key,
             &SigningOptions {
                 enforce_low_s: false,
@@ -150,7 +150,7 @@ fn test_sign_hash_greater_than_base_point_order() {
             },
         )
         .unwrap();
-        assert_eq!(signature.to_p1363_hex(), signature_hex);
+        assert_eq!(signature.to_p1363_hex().unwrap(), signature_hex);
     }
 }
 
@@ -168,7 +168,7 @@ fn test_match_secp256k1_fix_1063() {
     let private_key =
         PrivateKey::new(BigInt::from_str_radix(d_decimal, 10).unwrap(), secp256k1).unwrap();
     let (signature, _) = sign_with_options(
-        &hex_to_bytes(&hash_hex).unwrap(),
+        &hex::decode(&hash_hex).unwrap(),
         &private_key,
         &SigningOptions {
             enforce_low_s: true,
@@ -178,7 +178,7 @@ fn test_match_secp256k1_fix_1063() {
     )
     .unwrap();
     assert_eq!(
-        signature.to_p1363_hex(),
+        signature.to_p1363_hex().unwrap(),
         concat!(
             "e3d70248ea2fc771fc8d5e62d76b9cfd5402c96990333549eaadce1ae9f737eb",
             "5cfbdc7d1e0ec18cc9b57bbb18f0a57dc929ec3c4dfac9073c581705015f6a8a"
@@ -226,7 +226,7 @@ fn test_sign_hash_bytes_padding() {
         )
         .unwrap();
 
-        let hex = signature.to_p1363_hex();
+        let hex = signature.to_p1363_hex().unwrap();
         assert_eq!(hex, signature_hex);
     }
 }
@@ -247,7 +247,7 @@ fn test_valid_signing() {
 
         let private_key = PrivateKey::new(BigInt::from_hex(d_hex).unwrap(), secp256k1).unwrap();
         let (signature, _) = sign_with_options(
-            &hex_to_bytes(m_hex).unwrap(),
+            &hex::decode(m_hex).unwrap(),
             &private_key,
             &SigningOptions {
                 employ_extra_random_data: false,
@@ -256,7 +256,7 @@ fn test_valid_signing() {
             },
         )
         .unwrap();
-        assert_eq!(signature.to_p1363_hex(), signature_hex);
+        assert_eq!(signature.to_p1363_hex().unwrap(), signature_hex);
     }
 }
 
@@ -276,7 +276,7 @@ fn test_invalid_signing() {
             None => continue,
         };
         let result = sign_with_options(
-            &hex_to_bytes(m_hex).unwrap(),
+            &hex::decode(m_hex).unwrap(),
             &private_key,
             &SigningOptions {
                 employ_extra_random_data: false,
@@ -312,7 +312,7 @@ fn test_invalid_verifying() {
             Err(_) => continue,
         };
         let result = verify_with_options(
-            &hex_to_bytes(m_hex).unwrap(),
+            &hex::decode(m_hex).unwrap(),
             &signature,
             &public_key,
             &VerifyingOptions {
@@ -0,0 +1,38 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::crypto::codecs::{bech32, hex};
+use lightcryptotools::crypto::ecdsa::PrivateKey;
+use lightcryptotools::crypto::hash::{Sha256, UnkeyedHash};
+use lightcryptotools::crypto::secp256k1;
+use ripemd::{Digest, Ripemd160};
+
+#[test]
+fn test_p2wpkh_address_from_private_key() {
+    let private_key = PrivateKey::new(BigInt::one(), secp256k1()).unwrap();
+    let public_key = private_key.public_key();
+    let sec1_hex = public_key.to_sec1_hex(true);
+    assert_eq!(
+        sec1_hex,
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+
+    // HASH160: RIPEMD-160 of SHA-256 of the compressed public key.
+    let sha256 = Sha256::new().digest(hex::decode(sec1_hex).unwrap());
+    let hash160 = Ripemd160::digest(sha256);
+    assert_eq!(
+        hex::encode_lower(&hash160),
+        "751e76e8199196d454941c45d1b3a323f1433bd6"
+    );
+
+    let address = bech32::segwit_address("bc", 0, &hash160).unwrap();
+    assert_eq!(address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+    assert_eq!(
+        bech32::decode_segwit_address("bc", &address).unwrap(),
+        (0, hash160.to_vec())
+    );
+}
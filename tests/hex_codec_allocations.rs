@@ -0,0 +1,63 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Counts the allocations of the hex codec variants writing into the given buffers.
+//!
+//! In a test target of its own, for the global allocator counts the allocations of every
+//! test in the target.
+
+use lightcryptotools::crypto::codecs::hex::{self, DecodingOptions};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_codec_into_reserved_buffers_without_allocations() {
+    let bytes: Vec<u8> = (0..=u8::MAX).cycle().take(4096).collect();
+    let mut hex = String::with_capacity(bytes.len() * 4 + 2);
+    let mut decoded = Vec::with_capacity(bytes.len() * 2 + 1);
+    let options = DecodingOptions {
+        allows_0x_prefix: true,
+    };
+
+    ALLOCATION_COUNT.store(0, Ordering::Relaxed);
+    hex.push_str("0x");
+    hex::encode_into(&bytes, &mut hex);
+    hex::encode_upper_into(&bytes, &mut hex);
+    hex::decode_into_with_options(&hex, &mut decoded, &options).unwrap();
+    // The error path doesn't allocate either.
+    assert!(hex::decode_into("0g", &mut decoded).is_err());
+    let mut buffer = hex.into_bytes();
+    let len = hex::decode_in_place_with_options(&mut buffer, &options).unwrap();
+    let allocation_count = ALLOCATION_COUNT.load(Ordering::Relaxed);
+
+    assert_eq!(allocation_count, 0);
+    assert_eq!(decoded[..bytes.len()], bytes);
+    assert_eq!(decoded[bytes.len()..], bytes);
+    assert_eq!(buffer[..len], decoded);
+}
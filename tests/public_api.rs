@@ -0,0 +1,273 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Imports the supported public items at their paths, so that removing or moving one by
+//! accident fails to build the tests.
+//!
+//! The imports are the test, checked at compile time. A new public item is supported once
+//! it's imported here; the methods and fields are covered by the tests of their types.
+
+#![allow(unused_imports)]
+
+mod bigint {
+    #[cfg(feature = "bigint-arena")]
+    use lightcryptotools::bigint::{
+        with_arena, with_thread_local_arena, BigIntArena, BigIntArenaAllocator,
+    };
+    use lightcryptotools::bigint::{
+        BigInt, BigIntBudget, BigIntError, BigUint, BudgetExceeded, Digit, ParseIntError, Sign,
+    };
+}
+
+mod ethereum_abi {
+    use lightcryptotools::blockchain::ethereum::abi::{
+        decode, decode_log_event, encode, encode_with_types, event_topic_value,
+        keccak256_abi_encode, parse_abi_type, AbiDecodingError, AbiEncodingError, AbiType,
+        AbiTypeParseError, AbiValue, EventAbi, EventFilter, EventInput,
+    };
+}
+
+mod ethereum_account {
+    use lightcryptotools::blockchain::ethereum::account::{
+        EoaKeyParsingError, EoaPrivateKey, EoaPrivateKeyData, EoaPublicKey, NonceManager,
+        EOA_PRIVATE_KEY_DATA_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_block {
+    use lightcryptotools::blockchain::ethereum::block::{
+        compute_ommers_hash, ommers_hash_is_empty, BlockHash, BlockHeader,
+        BLOCK_HASH_BYTE_LENGTH, EMPTY_OMMERS_HASH, LOGS_BLOOM_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_create2 {
+    #[cfg(feature = "rayon")]
+    use lightcryptotools::blockchain::ethereum::create2::find_create2_salt_for_prefix_parallel;
+    use lightcryptotools::blockchain::ethereum::create2::{
+        create2_address, find_create2_salt_for_prefix, Create2Salt, CREATE2_SALT_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_eip1271 {
+    use lightcryptotools::blockchain::ethereum::eip1271::{
+        decode_is_valid_signature_response, eip1271_magic_value,
+        encode_is_valid_signature_call, EIP1271_MAGIC_VALUE,
+    };
+}
+
+mod ethereum_eip191 {
+    use lightcryptotools::blockchain::ethereum::eip191::{
+        eip191_hash, eip191_recover, eip191_sign, eip191_sign_with_options,
+        personal_message_hash, personal_sign_recover, Eip191DataError, Eip191SigningError,
+        SignatureValidationError, EIP191_VERSION_INTENDED_VALIDATOR,
+        EIP191_VERSION_PERSONAL_SIGN, EIP191_VERSION_STRUCTURED_DATA,
+        PERSONAL_SIGNATURE_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_eip2098 {
+    use lightcryptotools::blockchain::ethereum::eip2098::{
+        eip2098_to_rsv_bytes, rsv_to_eip2098_bytes, Eip2098DecodingError, Eip2098EncodingError,
+        EIP2098_SIGNATURE_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_eip3085 {
+    use lightcryptotools::blockchain::ethereum::eip3085::{
+        AddEthereumChainParams, NativeCurrency,
+    };
+}
+
+mod ethereum_eip4337 {
+    use lightcryptotools::blockchain::ethereum::eip4337::UserOperation;
+}
+
+mod ethereum_eip6492 {
+    use lightcryptotools::blockchain::ethereum::eip6492::{
+        validate_eip6492_signature, Eip6492Signature, EIP6492_MAGIC_SUFFIX,
+    };
+}
+
+mod ethereum_eip712 {
+    use lightcryptotools::blockchain::ethereum::eip712::{
+        eip712_encode_data, eip712_encode_type, eip712_hash_struct, eip712_hash_typed_data,
+        eip712_type_hash, Eip712Error, Eip712Member, Eip712Type, Eip712Types,
+        EIP712_DOMAIN_TYPE_NAME,
+    };
+}
+
+mod ethereum_fees {
+    use lightcryptotools::blockchain::ethereum::fees::{
+        estimate_eip1559_fees, next_base_fee, FeeEstimate, FeeSuggestion, FeeSuggestionError,
+        DEFAULT_PRIORITY_FEE_FLOOR_IN_WEI,
+    };
+}
+
+mod ethereum_rlp {
+    use lightcryptotools::blockchain::ethereum::rlp::RlpItemType;
+}
+
+mod ethereum_rlp_decoder {
+    use lightcryptotools::blockchain::ethereum::rlp::decoder::RlpDecodingItem;
+}
+
+mod ethereum_rlp_decoding {
+    use lightcryptotools::blockchain::ethereum::rlp::decoding::{
+        decode_list, decode_uint_bigint, decode_value, validate_canonical_rlp,
+        RlpDataDecodingError,
+    };
+}
+
+mod ethereum_rlp_encoder {
+    use lightcryptotools::blockchain::ethereum::rlp::encoder::RlpEncodingItem;
+}
+
+mod ethereum_rlp_encoding {
+    use lightcryptotools::blockchain::ethereum::rlp::encoding::encode_uint_bigint;
+}
+
+mod ethereum_rlp_schema {
+    use lightcryptotools::blockchain::ethereum::rlp::schema::{
+        decode_schema, RlpSchema, RlpValue,
+    };
+}
+
+mod ethereum_siwe {
+    use lightcryptotools::blockchain::ethereum::siwe::{
+        SiweMessage, SiweParseError, SIWE_VERSION,
+    };
+}
+
+mod ethereum_ssz {
+    use lightcryptotools::blockchain::ethereum::ssz::{
+        SszContainerWriter, SszDataDecodingError, SszDecodingItem, SszEncodingItem, SszType,
+    };
+}
+
+mod ethereum_transaction {
+    use lightcryptotools::blockchain::ethereum::transaction::{
+        estimate_calldata_cost, validate_eip4488_calldata_size, PayloadEip155, PayloadEip1559,
+        PayloadEip2930, PayloadLegacy, TransactionBuilder, TransactionBuildingError,
+        TransactionEip155, TransactionEip1559, TransactionEip1559Ref, TransactionEip2930,
+        TransactionLegacy, TransactionValidationError, CALLDATA_NON_ZERO_BYTE_GAS,
+        CALLDATA_NON_ZERO_BYTE_GAS_PRE_EIP2028, CALLDATA_ZERO_BYTE_GAS,
+    };
+}
+
+mod ethereum_types {
+    use lightcryptotools::blockchain::ethereum::types::{
+        AccessList, AccessListItem, AccessListItemRef, Address, AddressData, Chain, ChainId,
+        EoaNonce, EoaNonceError, GasLimit, GasUsed, StorageKey, StorageKeyData,
+        TransactionType, Wei, ADDRESS_DATA_BYTE_LENGTH,
+    };
+}
+
+mod ethereum_unsigned_bundle {
+    use lightcryptotools::blockchain::ethereum::unsigned_bundle::{
+        BundleSigningError, BundleValidationError, SignedBundle, TransactionSigner,
+        UnsignedPayload, UnsignedTransactionBundle, UNSIGNED_BUNDLE_VERSION,
+    };
+}
+
+mod crypto {
+    use lightcryptotools::crypto::{
+        hash_to_curve, secp256k1, secp256r1, secp384r1, secp521r1, Algorithm, Coordinate,
+        CurveParamsError, CurveSecurityError, EllipticCurveParams, GenerateNonceError,
+        PointDecodingError, PointValidationError, SignatureDecodingError,
+        SignatureEncodingError,
+    };
+}
+
+mod crypto_codecs {
+    // Deprecated, kept until they're removed.
+    #[allow(deprecated)]
+    use lightcryptotools::crypto::codecs::{
+        bytes_to_lower_hex, hex_to_array, hex_to_bytes, CodecsError,
+    };
+}
+
+mod crypto_codecs_bech32 {
+    use lightcryptotools::crypto::codecs::bech32::{
+        decode, decode_segwit_address, encode, regroup_from_5bit, regroup_to_5bit,
+        segwit_address, Bech32Error, Bech32Variant,
+    };
+}
+
+mod crypto_codecs_hex {
+    use lightcryptotools::crypto::codecs::hex::{
+        decode, decode_in_place, decode_in_place_with_options, decode_into,
+        decode_into_with_options, decode_to_array, decode_with_options, encode_into,
+        encode_lower, encode_upper, encode_upper_into, DecodingOptions, HexDecodingError,
+    };
+}
+
+mod crypto_ecdsa {
+    use lightcryptotools::crypto::ecdsa::{
+        recover_public_keys_from_signature, recover_public_keys_from_signature_with_options,
+        sign, sign_with_options, sign_with_options_and_rfc6979_hmac_hasher, verify,
+        verify_with_options, PrecomputedPublicKey, PrecomputedTableError, PrivateKey,
+        PrivateKeyDecodingError, PublicKey, RecoveryError, RecoveryOptions, Signature,
+        SignatureCanonicality, SignatureRecoveryId, SigningError, SigningOptions,
+        VerifyingError, VerifyingOptions,
+    };
+}
+
+#[cfg(feature = "forensics")]
+mod crypto_ecdsa_forensics {
+    use lightcryptotools::crypto::ecdsa::forensics::{
+        detect_shared_nonce, recover_private_key_from_nonce_reuse, NonceReuseError,
+    };
+}
+
+mod crypto_hash {
+    use lightcryptotools::crypto::hash::{
+        expand_message_xmd, hmac, hmac_truncated, kmac128, kmac256, ExpandMessageError,
+        HmacError, Keccak256, Sha256, Sha384, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha512,
+        Shake128, Shake256, UnkeyedHash, MAX_DST_BYTE_LENGTH,
+    };
+}
+
+mod crypto_hash_to_scalar {
+    use lightcryptotools::crypto::hash_to_scalar::{
+        hash_to_scalar_rfc9380_style, reduce_to_scalar, truncate_to_scalar,
+    };
+}
+
+mod math {
+    use lightcryptotools::math::{Curve, MulConfig, Point, MUL_WINDOW_WIDTH_RANGE};
+}
+
+mod random {
+    use lightcryptotools::random::GetOsRandomBytesError;
+}
+
+mod random_generator {
+    use lightcryptotools::random::generator::{fill_random_bytes, get_os_random_bytes};
+}
+
+mod tools_codable {
+    use lightcryptotools::tools::codable::{
+        decode, encode, Decodable, DecodingItem, Encodable, EncodingItem,
+    };
+}
+
+#[test]
+fn test_prelude() {
+    // The prelude re-exports the types and traits only.
+    use lightcryptotools::prelude::{
+        AccessList, AccessListItem, Address, BigInt, BigUint, BlockHeader, ChainId, Curve,
+        Decodable, DecodingItem, EllipticCurveParams, Encodable, EncodingItem, EoaNonce,
+        EoaPrivateKey, EoaPrivateKeyData, EoaPublicKey, Keccak256, MulConfig, NonceManager,
+        Point, PrecomputedPublicKey, PrivateKey, PublicKey, RecoveryOptions,
+        RlpDataDecodingError, RlpDecodingItem, RlpEncodingItem, RlpItemType, RlpSchema,
+        RlpValue, Sha256, Sha384, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Sha512, Sign,
+        Signature, SignatureRecoveryId, SigningOptions, SszDataDecodingError, SszDecodingItem,
+        SszEncodingItem, SszType, StorageKey, TransactionBuilder, TransactionEip155,
+        TransactionEip1559, TransactionEip1559Ref, TransactionEip2930, TransactionLegacy,
+        UnkeyedHash, VerifyingOptions, Wei,
+    };
+}
@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use lightcryptotools::random::generator::get_os_random_bytes;
+use lightcryptotools::random::generator::{fill_random_bytes, get_os_random_bytes};
 
 #[test]
 fn get_os_random_bytes_0() {
@@ -32,6 +32,24 @@ fn get_os_random_bytes_1000() {
     test_get_os_random_bytes_with_len(1000);
 }
 
+#[test]
+fn get_os_random_bytes_1mb() {
+    test_get_os_random_bytes_with_len(1024 * 1024);
+}
+
+#[test]
+fn fill_random_bytes_existing_buffer() {
+    let mut bytes = [0u8; 1000];
+    fill_random_bytes(&mut bytes[..0]).unwrap();
+    assert_eq!(bytes, [0u8; 1000]);
+
+    // Leaves the bytes outside `dest` intact
+    fill_random_bytes(&mut bytes[10..990]).unwrap();
+    assert_eq!(bytes[..10], [0u8; 10]);
+    assert_eq!(bytes[990..], [0u8; 10]);
+    assert_ne!(bytes[10..990], [0u8; 980]);
+}
+
 fn test_get_os_random_bytes_with_len(len: u32) {
     let bytes = get_os_random_bytes(len).unwrap();
     assert_eq!(bytes.len(), len as usize);
@@ -0,0 +1,155 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Counts the allocations of writing nested SSZ containers into a reserved buffer.
+//!
+//! In a test target of its own, for the global allocator counts the allocations of every
+//! test in the target.
+
+use lightcryptotools::blockchain::ethereum::ssz::{
+    SszContainerWriter, SszDataDecodingError, SszType,
+};
+use lightcryptotools::crypto::codecs::hex;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+// class Inner(Container):
+//     A: uint16
+//     B: List[uint16, 1024]
+//     C: uint8
+#[derive(Debug)]
+struct Inner {
+    a: u16,
+    b: Vec<u16>,
+    c: u8,
+}
+
+impl SszType for Inner {
+    fn size() -> Option<u32> {
+        None
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.a);
+            writer.write_element(&self.b);
+            writer.write_element(&self.c);
+        });
+    }
+
+    fn try_from_bytes(_: &[u8]) -> Result<Self, SszDataDecodingError> {
+        unimplemented!()
+    }
+}
+
+// class Outer(Container):
+//     A: Inner
+//     B: uint32
+//     C: Vector[Inner, 2]
+struct Outer {
+    a: Inner,
+    b: u32,
+    c: [Inner; 2],
+}
+
+impl SszType for Outer {
+    fn size() -> Option<u32> {
+        None
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        SszContainerWriter::write_container(bytes, |writer| {
+            writer.write_element(&self.a);
+            writer.write_element(&self.b);
+            writer.write_element(&self.c);
+        });
+    }
+
+    fn try_from_bytes(_: &[u8]) -> Result<Self, SszDataDecodingError> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_nested_container_into_reserved_buffer_without_allocations() {
+    let value = Outer {
+        a: Inner {
+            a: 0xabcd,
+            b: vec![1, 2, 3],
+            c: 0xff,
+        },
+        b: 0x12345678,
+        c: [
+            Inner {
+                a: 0xdead,
+                b: vec![],
+                c: 0x11,
+            },
+            Inner {
+                a: 0xbeef,
+                b: vec![4],
+                c: 0x22,
+            },
+        ],
+    };
+    let mut bytes = Vec::with_capacity(256);
+    bytes.push(0xab);
+
+    ALLOCATION_COUNT.store(0, Ordering::Relaxed);
+    value.write_bytes(&mut bytes);
+    let allocation_count = ALLOCATION_COUNT.load(Ordering::Relaxed);
+
+    assert_eq!(allocation_count, 0);
+    assert_eq!(
+        hex::encode_lower(&bytes),
+        concat!(
+            "ab",
+            "0c000000",                   // offset of A
+            "78563412",                   // B
+            "19000000",                   // offset of C
+            "cdab07000000ff010002000300", // contents of A
+            "08000000",                   // [start C]: local offsets of [2]Inner
+            "0f000000",
+            "adde0700000011",
+            "efbe07000000220400",
+        )
+    );
+}
@@ -0,0 +1,58 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Counts the allocations of reading the parameters of the named curves, which are built
+//! at compile time.
+//!
+//! In a test target of its own, for the global allocator counts the allocations of every
+//! test in the target.
+
+use lightcryptotools::crypto::{secp256k1, secp256r1, secp384r1, secp521r1};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_reading_curve_params_without_allocations() {
+    ALLOCATION_COUNT.store(0, Ordering::Relaxed);
+    let mut bit_len = 0;
+    for params in [secp256k1(), secp256r1(), secp384r1(), secp521r1()] {
+        bit_len += params.curve.p.bit_len();
+        assert!(params.base_point_order <= params.curve.p);
+        assert!(params.base_point.x < params.curve.p);
+        assert!(params.base_point.y < params.curve.p);
+
+        // Clones borrow the same static digits.
+        let a = params.curve.a.clone();
+        assert_eq!(a, params.curve.a);
+    }
+    let allocation_count = ALLOCATION_COUNT.load(Ordering::Relaxed);
+
+    assert_eq!(bit_len, 256 + 256 + 384 + 521);
+    assert_eq!(allocation_count, 0);
+}
@@ -0,0 +1,97 @@
+// Copyright 2022 Developers of the lightcryptotools project.
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+//! Counts the allocations of decoding a transaction with large data in the borrowed mode.
+//!
+//! In a test target of its own, for the global allocator counts the allocations of every
+//! test in the target.
+
+use lightcryptotools::bigint::BigInt;
+use lightcryptotools::blockchain::ethereum::transaction::{
+    TransactionBuilder, TransactionEip1559Ref,
+};
+use lightcryptotools::crypto::ecdsa::{PrivateKey, SigningOptions};
+use lightcryptotools::crypto::secp256k1;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static MAX_ALLOCATION_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        MAX_ALLOCATION_SIZE.fetch_max(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        MAX_ALLOCATION_SIZE.fetch_max(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+#[ignore]
+fn test_decoding_large_data_borrowed_without_large_allocations() {
+    const DATA_LEN: usize = 1024 * 1024;
+
+    let private_key = PrivateKey::new(
+        BigInt::from_hex("89f8496f444e0bbb708eaad5e7ed1d71fd9c4d7977a39f7c6a6f1cf0aefd0a6d")
+            .unwrap(),
+        secp256k1(),
+    )
+    .unwrap();
+    let data = TransactionBuilder::new()
+        .with_chain_id(1.into())
+        .with_nonce(0.try_into().unwrap())
+        .with_max_priority_fee_per_gas("0x3b9aca00".try_into().unwrap())
+        .with_max_fee_per_gas("0x0ba43b7400".try_into().unwrap())
+        .with_gas_limit(30_000_000.into())
+        .with_destination(
+            "0x123456789a123456789a123456789a123456789a"
+                .try_into()
+                .unwrap(),
+        )
+        .with_amount("0x00".try_into().unwrap())
+        .with_data(vec![0x5a; DATA_LEN])
+        .take_and_build_payload_eip_1559()
+        .unwrap()
+        .take_and_sign_with_options(
+            &private_key,
+            &SigningOptions {
+                employ_extra_random_data: false,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .encode();
+
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+    MAX_ALLOCATION_SIZE.store(0, Ordering::Relaxed);
+    let transaction = TransactionEip1559Ref::decode_borrowed(&data).unwrap();
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let max_allocation_size = MAX_ALLOCATION_SIZE.load(Ordering::Relaxed);
+
+    assert_eq!(transaction.data().len(), DATA_LEN);
+    // Only the small fields, e.g., the item lists and the big integers, are allocated.
+    assert!(max_allocation_size < 4096, "{max_allocation_size}");
+    assert!(allocated_bytes < 16 * 1024, "{allocated_bytes}");
+
+    // For comparison, converting copies the data.
+    let _ = transaction.to_transaction();
+    assert!(ALLOCATED_BYTES.load(Ordering::Relaxed) > DATA_LEN);
+}